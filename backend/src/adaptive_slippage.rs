@@ -0,0 +1,215 @@
+//! Adaptive Per-Symbol Slippage Tolerance
+//! Learns how much slippage each symbol needs from repeated gate failures,
+//! bounded by a global ceiling, and blocklists symbols that can't trade within it
+
+use serde::Serialize;
+use std::collections::HashMap;
+use chrono::Utc;
+
+/// Slippage tolerance configuration
+#[derive(Debug, Clone)]
+pub struct SlippageConfig {
+    /// Starting tolerance for every symbol (basis points)
+    pub base_bps: u16,
+    /// Hard ceiling - tolerance is never raised above this
+    pub ceiling_bps: u16,
+    /// How much to raise tolerance after repeated failures
+    pub step_bps: u16,
+    /// Consecutive failures before the tolerance is raised (or the symbol blocklisted)
+    pub failures_before_raise: u32,
+}
+
+impl Default for SlippageConfig {
+    fn default() -> Self {
+        Self {
+            base_bps: 50,     // 0.5%
+            ceiling_bps: 300, // 3%
+            step_bps: 25,
+            failures_before_raise: 3,
+        }
+    }
+}
+
+impl SlippageConfig {
+    /// Load from SLIPPAGE_BASE_BPS / SLIPPAGE_CEILING_BPS / SLIPPAGE_STEP_BPS / SLIPPAGE_FAILURES_BEFORE_RAISE
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let read_u16 = |key: &str, default: u16| {
+            std::env::var(key).ok().and_then(|v| v.parse::<u16>().ok()).unwrap_or(default)
+        };
+
+        let base_bps = read_u16("SLIPPAGE_BASE_BPS", defaults.base_bps);
+        Self {
+            base_bps,
+            ceiling_bps: read_u16("SLIPPAGE_CEILING_BPS", defaults.ceiling_bps).max(base_bps),
+            step_bps: read_u16("SLIPPAGE_STEP_BPS", defaults.step_bps).max(1),
+            failures_before_raise: std::env::var("SLIPPAGE_FAILURES_BEFORE_RAISE")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(defaults.failures_before_raise)
+                .max(1),
+        }
+    }
+}
+
+/// Learned slippage state for a single symbol
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolSlippage {
+    pub symbol: String,
+    pub tolerance_bps: u16,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    pub total_successes: u64,
+    pub blocklisted: bool,
+    pub blocklisted_at: Option<i64>,
+    pub last_failure_at: Option<i64>,
+}
+
+/// Outcome of recording a slippage failure
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlippageAdjustment {
+    Unchanged,
+    Raised { from_bps: u16, to_bps: u16 },
+    Blocklisted,
+}
+
+/// Adaptive per-symbol slippage tracker
+#[derive(Debug)]
+pub struct AdaptiveSlippage {
+    config: SlippageConfig,
+    symbols: HashMap<String, SymbolSlippage>,
+}
+
+impl AdaptiveSlippage {
+    pub fn new(config: SlippageConfig) -> Self {
+        Self {
+            config,
+            symbols: HashMap::new(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(SlippageConfig::from_env())
+    }
+
+    pub fn config(&self) -> &SlippageConfig {
+        &self.config
+    }
+
+    fn entry(&mut self, symbol: &str) -> &mut SymbolSlippage {
+        let base_bps = self.config.base_bps;
+        self.symbols.entry(symbol.to_string()).or_insert_with(|| SymbolSlippage {
+            symbol: symbol.to_string(),
+            tolerance_bps: base_bps,
+            consecutive_failures: 0,
+            total_failures: 0,
+            total_successes: 0,
+            blocklisted: false,
+            blocklisted_at: None,
+            last_failure_at: None,
+        })
+    }
+
+    /// Current effective tolerance for a symbol (basis points)
+    pub fn tolerance_bps(&self, symbol: &str) -> u16 {
+        self.symbols.get(symbol)
+            .map(|s| s.tolerance_bps)
+            .unwrap_or(self.config.base_bps)
+    }
+
+    pub fn is_blocklisted(&self, symbol: &str) -> bool {
+        self.symbols.get(symbol).map(|s| s.blocklisted).unwrap_or(false)
+    }
+
+    /// Record a "no route within slippage" failure
+    /// After `failures_before_raise` consecutive failures the tolerance is raised by one step;
+    /// once it is already at the ceiling the symbol is blocklisted instead
+    pub fn record_failure(&mut self, symbol: &str) -> SlippageAdjustment {
+        let config = self.config.clone();
+        let state = self.entry(symbol);
+        state.consecutive_failures += 1;
+        state.total_failures += 1;
+        state.last_failure_at = Some(Utc::now().timestamp());
+
+        if state.blocklisted || state.consecutive_failures < config.failures_before_raise {
+            return SlippageAdjustment::Unchanged;
+        }
+
+        state.consecutive_failures = 0;
+        if state.tolerance_bps >= config.ceiling_bps {
+            state.blocklisted = true;
+            state.blocklisted_at = Some(Utc::now().timestamp());
+            log::warn!("🚫 {} blocklisted - cannot trade within {} bps slippage ceiling", symbol, config.ceiling_bps);
+            SlippageAdjustment::Blocklisted
+        } else {
+            let from_bps = state.tolerance_bps;
+            state.tolerance_bps = from_bps.saturating_add(config.step_bps).min(config.ceiling_bps);
+            log::info!("📈 Raised {} slippage tolerance {} -> {} bps after repeated failures",
+                      symbol, from_bps, state.tolerance_bps);
+            SlippageAdjustment::Raised { from_bps, to_bps: state.tolerance_bps }
+        }
+    }
+
+    /// Record a trade that passed the slippage gate
+    pub fn record_success(&mut self, symbol: &str) {
+        let state = self.entry(symbol);
+        state.consecutive_failures = 0;
+        state.total_successes += 1;
+    }
+
+    /// All learned per-symbol tolerances
+    pub fn snapshot(&self) -> Vec<SymbolSlippage> {
+        let mut symbols: Vec<SymbolSlippage> = self.symbols.values().cloned().collect();
+        symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> SlippageConfig {
+        SlippageConfig {
+            base_bps: 50,
+            ceiling_bps: 100,
+            step_bps: 25,
+            failures_before_raise: 2,
+        }
+    }
+
+    #[test]
+    fn test_tolerance_raises_after_repeated_failures() {
+        let mut tracker = AdaptiveSlippage::new(test_config());
+        assert_eq!(tracker.tolerance_bps("BONK"), 50);
+
+        assert_eq!(tracker.record_failure("BONK"), SlippageAdjustment::Unchanged);
+        assert_eq!(
+            tracker.record_failure("BONK"),
+            SlippageAdjustment::Raised { from_bps: 50, to_bps: 75 }
+        );
+        assert_eq!(tracker.tolerance_bps("BONK"), 75);
+
+        // A success resets the consecutive failure count
+        tracker.record_failure("BONK");
+        tracker.record_success("BONK");
+        assert_eq!(tracker.record_failure("BONK"), SlippageAdjustment::Unchanged);
+    }
+
+    #[test]
+    fn test_blocklist_at_ceiling() {
+        let mut tracker = AdaptiveSlippage::new(test_config());
+        for _ in 0..4 {
+            tracker.record_failure("RUG");
+        }
+        assert_eq!(tracker.tolerance_bps("RUG"), 100);
+        assert!(!tracker.is_blocklisted("RUG"));
+
+        tracker.record_failure("RUG");
+        assert_eq!(tracker.record_failure("RUG"), SlippageAdjustment::Blocklisted);
+        assert!(tracker.is_blocklisted("RUG"));
+        // Never exceeds the ceiling
+        assert_eq!(tracker.tolerance_bps("RUG"), 100);
+        assert!(!tracker.is_blocklisted("BONK"));
+    }
+}
@@ -490,6 +490,7 @@ impl AIOrchestrator {
                     address: params.get("address").unwrap_or(&"placeholder_address".to_string()).clone(),
                     encrypted_key: params.get("key").unwrap_or(&"placeholder_key".to_string()).clone(),
                     key_type: crate::key_manager::KeyType::Base58,
+                    key_env: None,
                 };
                 wallet_manager.add_wallet(name.clone(), config);
                 
@@ -36,13 +36,14 @@ mod algorithm_improvement_tests {
     /// Test adaptive exploration decay based on performance
     #[tokio::test]
     async fn test_adaptive_exploration_decay() {
-        let mut agent = RLAgent::new(
+        let agent = RLAgent::new(
             "test_agent".to_string(),
             "test_provider".to_string(),
             None,
         );
+        agent.set_exploration(ExplorationConfig::default()).await.unwrap();
         
-        let initial_epsilon = agent.epsilon;
+        let initial_epsilon = agent.exploration().await.epsilon;
         
         // Simulate good performance (high win rate)
         {
@@ -53,13 +54,13 @@ mod algorithm_improvement_tests {
         }
         
         agent.decay_exploration().await;
-        let high_performance_epsilon = agent.epsilon;
+        let high_performance_epsilon = agent.exploration().await.epsilon;
         
         // With high win rate, epsilon should decay more aggressively
         assert!(high_performance_epsilon < initial_epsilon);
         
         // Reset and simulate poor performance
-        agent.epsilon = 0.2;
+        agent.set_exploration(ExplorationConfig { epsilon: 0.2, ..ExplorationConfig::default() }).await.unwrap();
         {
             let mut perf = agent.performance.lock().await;
             perf.successful_trades = 3;
@@ -68,7 +69,7 @@ mod algorithm_improvement_tests {
         }
         
         agent.decay_exploration().await;
-        let low_performance_epsilon = agent.epsilon;
+        let low_performance_epsilon = agent.exploration().await.epsilon;
         
         // With low win rate, epsilon should maintain higher exploration
         assert!(low_performance_epsilon >= 0.10); // Minimum 10% for struggling agents
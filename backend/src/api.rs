@@ -36,6 +36,23 @@ fn safe_serialize<T: serde::Serialize>(value: &T, default: serde_json::Value, fi
     })
 }
 
+// WALLETS: Resolve a request's target wallet - None / "default" is the main wallet
+async fn resolve_wallet(
+    engine: &Arc<Mutex<super::trading_engine::TradingEngine>>,
+    default_client: &Arc<Mutex<super::solana_integration::SolanaClient>>,
+    wallet: Option<&str>,
+) -> Result<(String, Arc<Mutex<super::solana_integration::SolanaClient>>), String> {
+    match wallet.map(|name| name.trim().to_lowercase()) {
+        None => Ok((crate::wallet::DEFAULT_WALLET_NAME.to_string(), default_client.clone())),
+        Some(name) if name == crate::wallet::DEFAULT_WALLET_NAME => Ok((name, default_client.clone())),
+        Some(name) => {
+            let client = engine.lock().await.strategy_wallets.get(&name).cloned()
+                .ok_or_else(|| format!("Unknown wallet: {}", name))?;
+            Ok((name, client))
+        }
+    }
+}
+
 pub async fn start_server(
     engine: Arc<Mutex<super::trading_engine::TradingEngine>>,
     risk_manager: Arc<Mutex<super::risk_management::RiskManager>>,
@@ -45,11 +62,25 @@ pub async fn start_server(
     circuit_breaker: Option<Arc<Mutex<super::error_handling::CircuitBreaker>>>, // ADD: Circuit breaker for API protection
     live_data_feed: Option<Arc<super::live_data_feed::LiveDataFeed>>, // ADD: Live data feed for management
     enhanced_marketplace: Option<Arc<super::enhanced_marketplace::EnhancedMarketplace>>, // ADD: Enhanced marketplace for advanced features
+    shared_marketplace: Option<Arc<SignalMarketplace>>, // ADD: Marketplace shared with the auto-executor
+    notifier: Option<Arc<dyn super::notifications::Notifier>>, // ADD: Webhook notifier for alert delivery
+    watchdog: Option<Arc<super::watchdog::Watchdog>>, // ADD: Watchdog for background task heartbeats
+    database: Option<Arc<Mutex<super::database::Database>>>, // ADD: Database for portfolio history
+    loop_intervals: Option<super::loop_intervals::LoopIntervals>, // ADD: Live-adjustable loop cadence
+    provider_registry: Option<super::provider_registry::ProviderRegistry>, // ADD: Runtime provider enable/disable
+    shutdown: Option<super::shutdown::Shutdown>, // ADD: Graceful shutdown on Ctrl-C
+    token_filter: Option<super::security::TokenFilter>, // ADD: Manual mint blacklist / whitelist
+    pumpfun_stream: Option<Arc<PumpFunClient>>, // ADD: Client running the pump.fun WebSocket listener
+    memecoin_filter: Option<super::specialized_providers::MemecoinFilter>, // ADD: Live-editable memecoin gates
+    twitter_sentiment: Option<Arc<super::twitter_sentiment::TwitterSentimentClient>>, // ADD: Sentiment service probed by /health
+    market_regime: Option<super::market_regime::MarketRegimeState>, // ADD: Shared regime read by the providers
+    signal_callbacks: Option<super::signal_callbacks::SignalCallbacks>, // ADD: Durable lifecycle callback queue
 ) {
     log::info!("🌐 Starting Warp server on :8080");
     
-    // Create WebSocket broadcaster for real-time updates
-    let ws_broadcaster = create_ws_broadcaster();
+    // Share the engine's broadcaster so /ws clients receive trade executions and live feed updates
+    let ws_broadcaster = engine.lock().await.ws_broadcaster.clone()
+        .unwrap_or_else(create_ws_broadcaster);
     
     // Create Jupiter client for DEX integration
     let jupiter_client = Arc::new(JupiterClient::new());
@@ -81,32 +112,59 @@ pub async fn start_server(
         Arc::new(PumpFunClient::new())
     };
     
-    // Create Signal Marketplace
-    let signal_marketplace = Arc::new(SignalMarketplace::new(rpc_url.clone()));
+    // Use the marketplace shared with the auto-executor so signal state (and skip log) is consistent
+    let signal_marketplace = shared_marketplace
+        .unwrap_or_else(|| Arc::new(SignalMarketplace::new(rpc_url.clone())));
     
     // Create Quant Analyzer
     let quant_analyzer = Arc::new(crate::quant_analysis::QuantAnalyzer::new());
+    let arbitrage_scanner = Arc::new(crate::arbitrage::ArbitrageScanner::new(
+        crate::arbitrage::ArbitrageConfig::from_env(),
+        dex_screener_client.clone(),
+        jupiter_client.clone(),
+    ));
     
     // Create Jito BAM client for atomic bundle execution
     let use_mainnet = rpc_url.contains("mainnet");
-    let jito_client = Arc::new(crate::jito_bam::JitoBamClient::new(use_mainnet));
+    let jito_client = Arc::new(crate::jito_bam::JitoBamClient::with_config(use_mainnet, crate::jito_bam::BundleConfig::from_env()));
     
     let cors = crate::security::cors_config();
     
-    // Enhanced health check with system status
+    // Oracle aggregator shared by /oracle/* and the /health readiness probe
+    let oracle_aggregator = Arc::new(crate::switchboard_oracle::OracleAggregator::new(switchboard_client.clone()));
+    
+    // Readiness check: system status plus a probe of every downstream dependency
+    // Answers 503 while a critical dependency (RPC, oracle, Jupiter, database) is down; dependencies this
+    // deployment doesn't configure count as healthy, and probe results are reused for HEALTH_CACHE_SECS
     let health = {
         let engine = engine.clone();
         let trading_enabled = trading_enabled.clone();
+        let pumpfun_stream = pumpfun_stream.clone();
+        let oracle_aggregator = oracle_aggregator.clone();
+        let jupiter_client = jupiter_client.clone();
+        let database = database.clone();
+        let twitter_sentiment = twitter_sentiment.clone();
+        let rpc = Arc::new(crate::rpc_client::SolanaRpcClient::new(rpc_url.clone()));
+        let probe_timeout = crate::health::ReadinessReport::timeout_from_env();
+        let readiness_cache = crate::health::ReadinessCache::from_env();
         
         warp::path("health")
             .and(warp::get())
             .and_then(move || {
                 let engine = engine.clone();
                 let trading_enabled = trading_enabled.clone();
+                let pumpfun_stream = pumpfun_stream.clone();
+                let oracle_aggregator = oracle_aggregator.clone();
+                let jupiter_client = jupiter_client.clone();
+                let database = database.clone();
+                let twitter_sentiment = twitter_sentiment.clone();
+                let rpc = rpc.clone();
+                let readiness_cache = readiness_cache.clone();
                 
                 async move {
+                    use crate::health::{DependencyStatus, ReadinessReport, NOT_CONFIGURED};
+                    
                     let mut response = HashMap::new();
-                    response.insert("status".to_string(), "healthy".to_string());
                     response.insert("timestamp".to_string(), chrono::Utc::now().to_rfc3339());
                     
                     // Check trading engine status
@@ -123,10 +181,7 @@ pub async fn start_server(
                     drop(enabled);
                     
                     // Check dry-run mode
-                    let dry_run_mode = std::env::var("DRY_RUN_MODE")
-                        .unwrap_or_else(|_| "true".to_string())
-                        .parse::<bool>()
-                        .unwrap_or(true);
+                    let dry_run_mode = crate::solana_integration::SolanaClient::dry_run_mode();
                     response.insert("dry_run_mode".to_string(), dry_run_mode.to_string());
                     
                     // Check network
@@ -135,7 +190,164 @@ pub async fn start_server(
                     let is_mainnet = rpc_url.contains("mainnet");
                     response.insert("network".to_string(), if is_mainnet { "mainnet".to_string() } else { "devnet".to_string() });
                     
-                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "System is healthy")))
+                    // Probe every dependency concurrently - each under the same timeout
+                    let report = readiness_cache.get_or_probe(|| async move {
+                        let rpc_check = DependencyStatus::probe("solana_rpc", true, probe_timeout, async move {
+                            // The RPC client is blocking - keep it off the async worker
+                            match tokio::task::spawn_blocking(move || rpc.client().get_slot()).await {
+                                Ok(Ok(slot)) => Ok(Some(format!("slot {}", slot))),
+                                Ok(Err(e)) => Err(format!("RPC unreachable: {}", e)),
+                                Err(e) => Err(format!("RPC probe task failed: {}", e)),
+                            }
+                        });
+                        let oracle_check = DependencyStatus::probe("oracle", true, probe_timeout, async {
+                            let health = oracle_aggregator.get_health().await;
+                            let detail = format!("{} ({}/{} sources)", health.overall_status, health.available_sources, health.total_sources);
+                            if health.available_sources > 0 { Ok(Some(detail)) } else { Err(detail) }
+                        });
+                        let jupiter_check = DependencyStatus::probe("jupiter", true, probe_timeout, async {
+                            jupiter_client.get_quote(crate::dex_aggregator::SOL_MINT, crate::dex_aggregator::USDC_MINT, 1_000_000, 50).await
+                                .map(|_| None)
+                                .map_err(|e| format!("Quote failed: {}", e))
+                        });
+                        let database_check = DependencyStatus::probe("database", true, probe_timeout, async {
+                            match database {
+                                Some(ref db) => db.lock().await.check_writable().map(|_| None),
+                                None => Ok(Some(NOT_CONFIGURED.to_string())),
+                            }
+                        });
+                        // pump.fun falls back to page scraping while the socket is down
+                        let pumpfun_check = DependencyStatus::probe("pumpfun_stream", false, probe_timeout, async {
+                            let stream = match pumpfun_stream {
+                                Some(ref pumpfun) => pumpfun.websocket_status().await,
+                                None => return Ok(Some(NOT_CONFIGURED.to_string())),
+                            };
+                            if stream.connected {
+                                Ok(Some("websocket".to_string()))
+                            } else {
+                                Err(format!("Disconnected after {} reconnect attempts - scraping instead{}", stream.reconnect_attempts,
+                                    stream.last_error.map(|e| format!(": {}", e)).unwrap_or_default()))
+                            }
+                        });
+                        // Sentiment is optional - analysis falls back to the base score without it
+                        let twitter_check = DependencyStatus::probe("twitter_sentiment", false, probe_timeout, async {
+                            match twitter_sentiment {
+                                Some(ref client) => match client.health_check().await {
+                                    Ok(true) => Ok(None),
+                                    Ok(false) => Err(format!("Service at {} not responding", client.service_url())),
+                                    Err(e) => Err(e.to_string()),
+                                },
+                                None => Ok(Some(NOT_CONFIGURED.to_string())),
+                            }
+                        });
+                        let (rpc, oracle, jupiter, database, pumpfun, twitter) = tokio::join!(
+                            rpc_check, oracle_check, jupiter_check, database_check, pumpfun_check, twitter_check
+                        );
+                        ReadinessReport::new(vec![rpc, oracle, jupiter, database, pumpfun, twitter])
+                    }).await;
+                    
+                    for dependency in report.dependencies.iter().filter(|d| !d.healthy) {
+                        log::debug!("🩺 /health: {} down{}: {}", dependency.name,
+                                   if dependency.critical { " (critical)" } else { "" },
+                                   dependency.detail.as_deref().unwrap_or("unknown"));
+                    }
+                    
+                    let status = if report.is_ready() {
+                        warp::http::StatusCode::OK
+                    } else {
+                        warp::http::StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    let message = match report.status.as_str() {
+                        "healthy" => "System is healthy",
+                        "degraded" => "System is degraded - optional dependencies down",
+                        _ => "System is unhealthy - critical dependency down",
+                    };
+                    let body = serde_json::json!({
+                        "status": report.status,
+                        "dependencies": report.dependencies,
+                        "checked_at": report.checked_at,
+                        "system": response,
+                    });
+                    let mut reply = ApiResponse::new(body, message);
+                    reply.success = report.is_ready();
+                    Ok::<_, warp::Rejection>(warp::reply::with_status(warp::reply::json(&reply), status))
+                }
+            })
+    };
+    
+    // Detailed health - per-task heartbeats so stalls are visible before the watchdog acts
+    // GET /health/detailed
+    let health_detailed_route = {
+        let watchdog = watchdog.clone();
+        
+        warp::path!("health" / "detailed")
+            .and(warp::get())
+            .and_then(move || {
+                let watchdog = watchdog.clone();
+                
+                async move {
+                    let watchdog = match watchdog {
+                        Some(w) => w,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Watchdog not available"}),
+                                "Detailed health unavailable"
+                            )));
+                        }
+                    };
+                    
+                    let tasks = watchdog.snapshot().await;
+                    let stalled: Vec<String> = tasks.iter()
+                        .filter(|t| t.stalled)
+                        .map(|t| t.name.clone())
+                        .collect();
+                    let status = if stalled.is_empty() { "healthy" } else { "degraded" };
+                    let config = watchdog.config();
+                    
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "status": status,
+                            "timestamp": chrono::Utc::now().to_rfc3339(),
+                            "watchdog": {
+                                "enabled": config.enabled,
+                                "check_interval_secs": config.check_interval_secs,
+                                "timeout_secs": config.timeout_secs,
+                            },
+                            "stalled_tasks": stalled,
+                            "tasks": tasks,
+                        }),
+                        "Detailed health retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // Last check of tracked positions against the wallets' on-chain token balances
+    // GET /portfolio/reconciliation
+    let portfolio_reconciliation_route = {
+        let engine = engine.clone();
+        
+        warp::path!("portfolio" / "reconciliation")
+            .and(warp::get())
+            .and_then(move || {
+                let engine = engine.clone();
+                async move {
+                    let reconciliation = engine.lock().await.portfolio_reconciliation.clone();
+                    let config = crate::production_safeguards::PortfolioReconciliationConfig::from_env();
+                    let message = match &reconciliation {
+                        Some(last) if last.discrepancies.is_empty() => "Portfolio matches on-chain balances",
+                        Some(_) => "Portfolio corrected to on-chain balances",
+                        None => "No portfolio reconciliation has run yet",
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "enabled": config.enabled,
+                            "interval_secs": config.interval_secs,
+                            "tolerance_pct": config.tolerance_pct,
+                            "last": reconciliation,
+                        }),
+                        message
+                    )))
                 }
             })
     };
@@ -186,6 +398,13 @@ pub async fn start_server(
                     response.insert("is_paper_trading".to_string(), safe_serialize(&dry_run_mode, serde_json::Value::Bool(false), "is_paper_trading"));
                     response.insert("initial_balance".to_string(), safe_serialize(&engine_lock.initial_balance, serde_json::json!(0.0), "initial_balance"));
                     
+                    // COST BASIS: Average entry, mark and unrealized P&L per open position
+                    let breakdown = engine_lock.position_breakdown();
+                    let unrealized_pnl: f64 = breakdown.iter().filter_map(|p| p.unrealized_pnl).sum();
+                    response.insert("position_breakdown".to_string(), safe_serialize(&breakdown, serde_json::json!([]), "position_breakdown"));
+                    response.insert("realized_pnl".to_string(), safe_serialize(&engine_lock.realized_pnl, serde_json::json!(0.0), "realized_pnl"));
+                    response.insert("unrealized_pnl".to_string(), safe_serialize(&unrealized_pnl, serde_json::json!(0.0), "unrealized_pnl"));
+                    
                     Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "Portfolio data retrieved")))
                 }
             })
@@ -217,46 +436,91 @@ pub async fn start_server(
                     response.insert("total_pnl".to_string(), safe_serialize(metrics.get("total_pnl").unwrap_or(&0.0), serde_json::json!(0.0), "total_pnl"));
                     response.insert("trade_count".to_string(), safe_serialize(metrics.get("trade_count").unwrap_or(&0.0), serde_json::json!(0.0), "trade_count"));
                     
+                    // STREAKS: consecutive winning/losing closes and the size multiplier they currently imply
+                    response.insert("win_streak".to_string(), serde_json::json!(risk_lock.win_streak));
+                    response.insert("loss_streak".to_string(), serde_json::json!(risk_lock.loss_streak));
+                    response.insert("streak_multiplier".to_string(), safe_serialize(&risk_lock.streak_multiplier(), serde_json::json!(1.0), "streak_multiplier"));
+                    
                     // Add ROI from engine
                     let roi = engine_lock.get_roi();
                     response.insert("roi_percent".to_string(), safe_serialize(&roi, serde_json::json!(0.0), "roi_percent"));
                     
+                    // FEE BUDGET: fee drag = share of gross PnL (before fees) consumed by fees
+                    let total_fees_paid = engine_lock.total_fees_paid();
+                    let gross_pnl = metrics.get("total_pnl").copied().unwrap_or(0.0) + total_fees_paid;
+                    let fee_drag_pct = if gross_pnl.abs() > 0.0 { total_fees_paid / gross_pnl.abs() * 100.0 } else { 0.0 };
+                    response.insert("total_fees_paid".to_string(), safe_serialize(&total_fees_paid, serde_json::json!(0.0), "total_fees_paid"));
+                    response.insert("fee_drag_pct".to_string(), safe_serialize(&fee_drag_pct, serde_json::json!(0.0), "fee_drag_pct"));
+                    
                     Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "Performance metrics retrieved")))
                 }
             })
     };
     
+    // GET /market-data?window_secs=86400 - change is computed against the buffered price nearest to
+    // `window_secs` ago (default 24h); the oracle's 24h change is preferred when the feed provides it
     let market_data_route = {
         let engine = engine.clone();
+        let live_data_feed = live_data_feed.clone();
         
         warp::path("market-data")
             .and(warp::get())
-            .and_then(move || {
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
                 let engine = engine.clone();
+                let live_data_feed = live_data_feed.clone();
                 
                 async move {
+                    const DAY_SECS: i64 = 86_400;
+                    let window_secs = params.get("window_secs")
+                        .and_then(|w| w.parse::<i64>().ok())
+                        .filter(|w| *w > 0)
+                        .unwrap_or(DAY_SECS);
+                    
+                    // Oracle-reported 24h change per symbol (only meaningful for the default 24h window)
+                    let mut oracle_changes: HashMap<String, f64> = HashMap::new();
+                    if window_secs == DAY_SECS {
+                        if let Some(ref feed) = live_data_feed {
+                            if let Ok(stats) = feed.get_feed_statistics(None).await {
+                                for stat in stats {
+                                    if let Some(change) = stat.price_change_24h {
+                                        oracle_changes.insert(stat.symbol, change);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    
                     let engine_lock = engine.lock().await;
                     
                     let mut market_data = Vec::new();
                     
                     for (symbol, data) in &engine_lock.market_state {
                         if let Some(latest) = data.back() {
-                            // Calculate 24h change if we have historical data
-                            let change_24h = if data.len() > 1 {
-                                let first_price = data.front().map(|d| d.price).unwrap_or(latest.price);
-                                if first_price > 0.0 {
-                                    ((latest.price - first_price) / first_price) * 100.0
-                                } else {
-                                    0.0
-                                }
+                            let (change, change_window_secs, source) = match oracle_changes.get(symbol) {
+                                Some(change) => (*change, DAY_SECS, "oracle"),
+                                None => match engine_lock.price_change_over(symbol, window_secs) {
+                                    Some((change, actual_window)) => (change, actual_window, "buffer"),
+                                    None => (0.0, 0, "none"),
+                                },
+                            };
+                            
+                            // Label the window honestly - only call it the requested window if the buffer covers it
+                            let window_label = if source == "none" {
+                                "none".to_string()
+                            } else if change_window_secs as f64 >= window_secs as f64 * 0.95 {
+                                if window_secs == DAY_SECS { "24h".to_string() } else { format!("{}s", window_secs) }
                             } else {
-                                0.0
+                                format!("partial_{}s", change_window_secs)
                             };
                             
                             let mut item = HashMap::new();
                             item.insert("symbol".to_string(), symbol.clone());
                             item.insert("price".to_string(), format!("{:.2}", latest.price));
-                            item.insert("change".to_string(), format!("{:.2}", change_24h));
+                            item.insert("change".to_string(), format!("{:.2}", change));
+                            item.insert("change_window".to_string(), window_label);
+                            item.insert("change_window_secs".to_string(), change_window_secs.to_string());
+                            item.insert("change_source".to_string(), source.to_string());
                             item.insert("volume".to_string(), format!("{:.0}", latest.volume));
                             market_data.push(item);
                         }
@@ -294,6 +558,12 @@ pub async fn start_server(
                         item.insert("confidence".to_string(), format!("{:.2}", signal.confidence));
                         item.insert("price".to_string(), format!("{:.2}", signal.price));
                         item.insert("size".to_string(), format!("{:.2}", signal.size));
+                        if let Some(ref aggregator) = signal.aggregator {
+                            item.insert("aggregator".to_string(), aggregator.clone());
+                        }
+                        if let Some(fee) = engine_lock.realized_fee(&signal.id) {
+                            item.insert("fee_paid".to_string(), format!("{:.9}", fee));
+                        }
                         item
                     }).collect();
                     
@@ -452,20 +722,64 @@ pub async fn start_server(
     };
     
     // Jito BAM endpoints for atomic bundle execution
+    // Tips come from the engine's client when bundles are enabled - it holds the live landing rate
     let jito_status_route = {
         let jito = jito_client.clone();
+        let engine = engine.clone();
         warp::path("jito")
             .and(warp::path("status"))
             .and(warp::get())
-            .map(move || {
-                let mut status = HashMap::new();
-                status.insert("enabled".to_string(), "true".to_string());
-                status.insert("network".to_string(), if use_mainnet { "mainnet".to_string() } else { "devnet".to_string() });
-                status.insert("block_engine".to_string(), jito.block_engine_url.clone());
-                status.insert("features".to_string(), "Atomic bundle execution, MEV protection, priority tips".to_string());
-                status.insert("tip_accounts".to_string(), jito.tip_accounts.len().to_string());
-                
-                warp::reply::json(&ApiResponse::new(status, "Jito BAM status"))
+            .and_then(move || {
+                let jito = jito.clone();
+                let engine = engine.clone();
+                async move {
+                    use crate::fee_optimization::FeePriority;
+                    let jito = engine.lock().await.jito_client.clone().unwrap_or(jito);
+                    let config = jito.config();
+                    let status = serde_json::json!({
+                        "enabled": "true",
+                        "network": if use_mainnet { "mainnet" } else { "devnet" },
+                        "block_engine": jito.block_engine_url.clone(),
+                        "features": "Atomic bundle execution, MEV protection, priority tips",
+                        "tip_accounts": jito.tip_accounts.len().to_string(),
+                        "recent_landing_rate": jito.recent_landing_rate(),
+                        // DRY_RUN_MODE never sends a bundle - tips below are what would be paid
+                        "tips_simulated": crate::solana_integration::SolanaClient::dry_run_mode(),
+                        "effective_tip_lamports": {
+                            "low": jito.effective_tip(FeePriority::Low),
+                            "normal": jito.effective_tip(FeePriority::Normal),
+                            "high": jito.effective_tip(FeePriority::High),
+                        },
+                        "base_tip_lamports": config.tip_amount_lamports,
+                        "min_tip_lamports": config.min_tip_lamports,
+                        "max_tip_lamports": config.max_tip_lamports,
+                        "tip_bump_pct": config.tip_bump_pct,
+                    });
+                    
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(status, "Jito BAM status")))
+                }
+            })
+    };
+    
+    // GET /fees/status - base fee, the network's recent priority fees (compute-unit price per priority)
+    // and the lamport estimate each priority gets, plus fee history and budget
+    let fee_status_route = {
+        let engine = engine.clone();
+        warp::path!("fees" / "status")
+            .and(warp::get())
+            .and_then(move || {
+                let engine = engine.clone();
+                async move {
+                    let fee_optimizer = engine.lock().await.fee_optimizer.clone();
+                    let Some(fee_optimizer) = fee_optimizer else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Fee optimizer not configured"}),
+                            "Fee status unavailable"
+                        )));
+                    };
+                    let status = fee_optimizer.lock().await.status(chrono::Utc::now().timestamp());
+                    Ok(warp::reply::json(&ApiResponse::new(status, "Fee status retrieved")))
+                }
             })
     };
     
@@ -536,49 +850,77 @@ pub async fn start_server(
                 async move {
                     match switchboard.fetch_price(&symbol).await {
                         Ok(feed) => {
-                            Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(feed, "Oracle price retrieved")))
+                            let message = if feed.stale {
+                                format!("Oracle price is stale ({}s old, max {}s)", feed.age_secs(), switchboard.max_staleness_secs())
+                            } else {
+                                "Oracle price retrieved".to_string()
+                            };
+                            let mut data = serde_json::to_value(&feed).unwrap_or_default();
+                            if let Some(obj) = data.as_object_mut() {
+                                obj.insert("age_secs".to_string(), serde_json::json!(feed.age_secs()));
+                                obj.insert("max_staleness_secs".to_string(), serde_json::json!(switchboard.max_staleness_secs()));
+                            }
+                            Ok::<_, warp::Rejection>(warp::reply::with_status(
+                                warp::reply::json(&ApiResponse::new(data, &message)),
+                                warp::http::StatusCode::OK,
+                            ))
                         }
+                        // Never a zero-price feed - callers could mistake it for a real quote
                         Err(e) => {
                             log::error!("Oracle price error: {}", e);
-                            let empty_feed = crate::switchboard_oracle::OracleFeed {
-                                feed_address: "".to_string(),
-                                symbol: symbol.clone(),
-                                price: 0.0,
-                                confidence: 0.0,
-                                timestamp: 0,
-                                slot: 0,
-                                min_price: 0.0,
-                                max_price: 0.0,
-                                price_change_24h: None,
+                            let (status, body) = match &e {
+                                crate::error_handling::AppError::NotFound(_) => (warp::http::StatusCode::NOT_FOUND, serde_json::json!({
+                                    "error": e.to_string(),
+                                    "unknown_symbols": [symbol],
+                                    "known_symbols": switchboard.get_available_symbols(),
+                                })),
+                                _ => (warp::http::StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({"error": e.to_string()})),
                             };
-                            Ok(warp::reply::json(&ApiResponse::new(
-                                empty_feed,
-                                &format!("Failed to get price: {}", e)
-                            )))
+                            let mut reply = ApiResponse::new(body, &format!("Failed to get price: {}", e));
+                            reply.success = false;
+                            Ok(warp::reply::with_status(warp::reply::json(&reply), status))
                         }
                     }
                 }
             })
     };
     
+    // GET /oracle/feeds?symbols=SOL/USD,WIF/USD - every registered feed when symbols is omitted
     let oracle_feeds_route = {
         let switchboard = switchboard_client.clone();
         
         warp::path!("oracle" / "feeds")
             .and(warp::get())
-            .and_then(move || {
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
                 let switchboard = switchboard.clone();
                 
                 async move {
-                    let symbols = vec!["SOL/USD".to_string(), "BTC/USD".to_string(), "ETH/USD".to_string()];
+                    let symbols: Vec<String> = match params.get("symbols") {
+                        Some(list) => list.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect(),
+                        None => switchboard.get_available_symbols(),
+                    };
+                    let unknown = switchboard.unknown_symbols(&symbols);
+                    if !unknown.is_empty() {
+                        let mut reply = ApiResponse::new(
+                            serde_json::json!({
+                                "error": format!("No oracle feed for: {}", unknown.join(", ")),
+                                "unknown_symbols": unknown,
+                                "known_symbols": switchboard.get_available_symbols(),
+                            }),
+                            "Unknown oracle symbols - register them via POST /oracle/feeds/register"
+                        );
+                        reply.success = false;
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&reply));
+                    }
                     match switchboard.fetch_multiple_feeds(&symbols).await {
                         Ok(feeds) => {
-                            Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(feeds, "Oracle feeds retrieved")))
+                            Ok(warp::reply::json(&ApiResponse::new(serde_json::to_value(feeds).unwrap_or_default(), "Oracle feeds retrieved")))
                         }
                         Err(e) => {
                             log::error!("Oracle feeds error: {}", e);
                             Ok(warp::reply::json(&ApiResponse::new(
-                                Vec::<crate::switchboard_oracle::OracleFeed>::new(),
+                                serde_json::json!([]),
                                 &format!("Failed to get feeds: {}", e)
                             )))
                         }
@@ -587,6 +929,39 @@ pub async fn start_server(
             })
     };
     
+    // POST /oracle/feeds/register {"symbol": "WIF/USD", "feed_address": "<switchboard feed account>"}
+    // Adds a feed (or overrides a built-in one) for every oracle client without a restart
+    let oracle_register_feed_route = {
+        let switchboard = switchboard_client.clone();
+        
+        warp::path!("oracle" / "feeds" / "register")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let switchboard = switchboard.clone();
+                
+                async move {
+                    let symbol = body.get("symbol").and_then(|v| v.as_str()).unwrap_or_default();
+                    let feed_address = body.get("feed_address").and_then(|v| v.as_str()).unwrap_or_default();
+                    match crate::switchboard_oracle::FeedRegistry::global().register(symbol, feed_address) {
+                        Ok(symbol) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({
+                                "symbol": symbol,
+                                "feed_address": feed_address,
+                                "known_symbols": switchboard.get_available_symbols(),
+                            }),
+                            &format!("Oracle feed registered for {}", symbol)
+                        ))),
+                        Err(e) => {
+                            let mut reply = ApiResponse::new(serde_json::json!({"error": e}), "Failed to register oracle feed");
+                            reply.success = false;
+                            Ok(warp::reply::json(&reply))
+                        }
+                    }
+                }
+            })
+    };
+    
     // Mobula API endpoints (GMGN-compatible, replacing DEX Screener)
     let dex_search_route = {
         let dex_screener = dex_screener_client.clone();
@@ -638,6 +1013,25 @@ pub async fn start_server(
             })
     };
     
+    // Shared DEX Screener / Mobula quota - tune DEXSCREENER_REQUESTS_PER_MIN for free vs paid tier
+    // GET /dex/ratelimit
+    let dex_ratelimit_route = {
+        let dex_screener = dex_screener_client.clone();
+        
+        warp::path!("dex" / "ratelimit")
+            .and(warp::get())
+            .and_then(move || {
+                let dex_screener = dex_screener.clone();
+                
+                async move {
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        dex_screener.rate_limit_status(),
+                        "DEX rate limit status retrieved"
+                    )))
+                }
+            })
+    };
+    
     // PumpFun endpoints
     let pumpfun_launches_route = {
         let pumpfun = pumpfun_client.clone();
@@ -720,6 +1114,119 @@ pub async fn start_server(
             })
     };
     
+    // GET /signals/marketplace/sandbox?provider= - signals from sandboxed providers, newest first,
+    // with the current mark and how far it has moved from entry (paper-traded only)
+    let signal_sandbox_route = {
+        let marketplace = signal_marketplace.clone();
+        let engine = engine.clone();
+        
+        warp::path!("signals" / "marketplace" / "sandbox")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let marketplace = marketplace.clone();
+                let engine = engine.clone();
+                
+                async move {
+                    let signals = marketplace.get_sandbox_signals(params.get("provider").map(|p| p.as_str())).await;
+                    let engine_lock = engine.lock().await;
+                    let signals: Vec<serde_json::Value> = signals.into_iter().map(|signal| {
+                        let last_price = engine_lock.last_price(&signal.symbol);
+                        let move_pct = last_price.filter(|_| signal.entry_price > 0.0)
+                            .map(|price| (price - signal.entry_price) / signal.entry_price * 100.0);
+                        serde_json::json!({
+                            "signal": signal,
+                            "last_price": last_price,
+                            "move_pct": move_pct,
+                        })
+                    }).collect();
+                    drop(engine_lock);
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(signals, "Sandbox signals retrieved")))
+                }
+            })
+    };
+    
+    // GET /signals/marketplace/locks - signals held in Executing, how long, and any swap still live on-chain
+    // POST /signals/marketplace/locks/release/{id}?force=true - clear a stuck lock now instead of waiting
+    // for the sweep; refused while the signal's swap is still live unless forced
+    let execution_lock_routes = {
+        let list_marketplace = signal_marketplace.clone();
+        let list_database = database.clone();
+        let list_route = warp::path!("signals" / "marketplace" / "locks")
+            .and(warp::get())
+            .and_then(move || {
+                let marketplace = list_marketplace.clone();
+                let database = list_database.clone();
+                async move {
+                    let locks = marketplace.execution_locks().await;
+                    // The audit trail lives in the shared database - no need to wait on the engine
+                    let db = match database {
+                        Some(ref database) => Some(database.lock().await),
+                        None => None,
+                    };
+                    let mut entries = Vec::with_capacity(locks.len());
+                    for lock in locks {
+                        let live_swap = db.as_ref().and_then(|db| db.get_live_execution_for_signal(&lock.signal_id).cloned());
+                        entries.push(serde_json::json!({
+                            "lock": lock,
+                            "live_swap": live_swap.map(|execution| serde_json::json!({
+                                "signature": execution.signature,
+                                "status": execution.status,
+                                "submitted_at": execution.submitted_at,
+                            })),
+                        }));
+                    }
+                    drop(db);
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({ "count": entries.len(), "locks": entries }),
+                        "Execution locks retrieved"
+                    )))
+                }
+            });
+        
+        let release_marketplace = signal_marketplace.clone();
+        let release_database = database.clone();
+        let release_route = warp::path!("signals" / "marketplace" / "locks" / "release" / String)
+            .and(warp::post())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |signal_id: String, params: HashMap<String, String>| {
+                let marketplace = release_marketplace.clone();
+                let database = release_database.clone();
+                async move {
+                    let force = params.get("force").is_some_and(|f| f == "true");
+                    let live_execution = match (force, database) {
+                        (false, Some(database)) => database.lock().await.get_live_execution_for_signal(&signal_id).cloned(),
+                        _ => None,
+                    };
+                    if let Some(execution) = live_execution {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({
+                                "error": format!("Swap {} for {} is still {:?} on-chain - pass force=true to release anyway",
+                                                 execution.signature, signal_id, execution.status),
+                            }),
+                            "Execution lock not released"
+                        )));
+                    }
+                    match marketplace.release_execution_lock(&signal_id).await {
+                        Ok(status) => {
+                            log::warn!("🔓 Execution lock on {} released by operator{} -> {:?}",
+                                      signal_id, if force { " (forced)" } else { "" }, status);
+                            Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({ "signal_id": signal_id, "status": status }),
+                                "Execution lock released"
+                            )))
+                        }
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Execution lock not released"
+                        ))),
+                    }
+                }
+            });
+        
+        list_route.or(release_route)
+    };
+    
     let signal_by_symbol_route = {
         let marketplace = signal_marketplace.clone();
         
@@ -735,6 +1242,65 @@ pub async fn start_server(
             })
     };
     
+    // GET /signals/marketplace/history?from=&to=&status=filled,expired&provider=&symbol=&limit=
+    // Current and persisted signals, newest first; from/to are unix seconds
+    let signal_history_route = {
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("signals" / "marketplace" / "history")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let marketplace = marketplace.clone();
+                
+                async move {
+                    let mut statuses = Vec::new();
+                    for name in params.get("status").map(|v| v.split(',').filter(|n| !n.trim().is_empty()).collect::<Vec<_>>()).unwrap_or_default() {
+                        match super::signal_platform::SignalStatus::parse(name) {
+                            Some(status) => statuses.push(status),
+                            None => {
+                                let mut response = ApiResponse::new(
+                                    serde_json::json!({"error": format!("Unknown status '{}'", name.trim())}),
+                                    "Invalid signal history query"
+                                );
+                                response.success = false;
+                                return Ok::<_, warp::Rejection>(warp::reply::with_status(
+                                    warp::reply::json(&response),
+                                    warp::http::StatusCode::BAD_REQUEST,
+                                ));
+                            }
+                        }
+                    }
+                    let filter = super::signal_platform::SignalFilter {
+                        from: params.get("from").and_then(|v| v.parse::<i64>().ok()),
+                        to: params.get("to").and_then(|v| v.parse::<i64>().ok()),
+                        statuses,
+                        provider: params.get("provider").cloned(),
+                        symbol: params.get("symbol").cloned(),
+                    };
+                    let limit = params.get("limit")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(100)
+                        .min(1000);
+                    
+                    let mut signals = marketplace.get_signals(&filter).await;
+                    let total = signals.len();
+                    signals.truncate(limit);
+                    Ok(warp::reply::with_status(
+                        warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({
+                                "signals": signals,
+                                "count": signals.len(),
+                                "total": total,
+                            }),
+                            "Signal history retrieved"
+                        )),
+                        warp::http::StatusCode::OK,
+                    ))
+                }
+            })
+    };
+    
     let signal_generate_route = {
         let marketplace = signal_marketplace.clone();
         
@@ -884,16 +1450,45 @@ pub async fn start_server(
             })
     };
     
+    // GET /signals/marketplace/revenue?from=&to=&provider= - purchase payments per provider, highest first;
+    // from/to are unix seconds
+    let signal_revenue_route = {
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("signals" / "marketplace" / "revenue")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let marketplace = marketplace.clone();
+                
+                async move {
+                    let from = params.get("from").and_then(|v| v.parse::<i64>().ok());
+                    let to = params.get("to").and_then(|v| v.parse::<i64>().ok());
+                    let mut report = marketplace.get_revenue_report(from, to).await;
+                    if let Some(provider) = params.get("provider") {
+                        report.providers.retain(|p| &p.provider_id == provider);
+                        report.total_purchases = report.providers.iter().map(|p| p.purchases).sum();
+                        report.total_revenue = report.providers.iter().map(|p| p.revenue).sum();
+                    }
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(report, "Signal revenue retrieved")))
+                }
+            })
+    };
+    
     // Wallet status route
     let wallet_status_route = {
         let solana_client = solana_client.clone();
+        let engine = engine.clone();
         
         warp::path!("wallet" / "status")
             .and(warp::get())
             .and_then(move || {
                 let solana_client = solana_client.clone();
+                let engine = engine.clone();
                 
                 async move {
+                    // WALLETS: Per-wallet balances first - the engine locks each client in turn
+                    let wallets = engine.lock().await.wallet_balances().await;
                     let client_lock = solana_client.lock().await;
                     
                     let mut status = HashMap::new();
@@ -913,6 +1508,7 @@ pub async fn start_server(
                     if let Some(rpc) = &client_lock.rpc_url {
                         status.insert("rpc_url", serde_json::to_value(rpc).unwrap());
                     }
+                    status.insert("wallets", serde_json::to_value(wallets).unwrap());
                     
                     Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
                         status,
@@ -1019,28 +1615,64 @@ pub async fn start_server(
     #[derive(Deserialize)]
     struct PDADepositRequest {
         amount_sol: f64,
+        /// Strategy wallet to fund (default: the main wallet)
+        #[serde(default)]
+        wallet: Option<String>,
     }
 
     let pda_deposit_route = {
         let solana_client = solana_client.clone();
+        let engine = engine.clone();
         
         warp::path!("pda" / "deposit")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |req: PDADepositRequest| {
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |req: PDADepositRequest, params: HashMap<String, String>| {
                 let solana_client = solana_client.clone();
+                let engine = engine.clone();
                 
                 async move {
-                    let mut client_lock = solana_client.lock().await;
+                    let (wallet_name, target_client) = match resolve_wallet(&engine, &solana_client, req.wallet.as_deref()).await {
+                        Ok(target) => target,
+                        Err(e) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e}),
+                                &format!("Failed to deposit to PDA: {}", e)
+                            )));
+                        }
+                    };
+                    let mut client_lock = target_client.lock().await;
+                    
+                    // ?dry_run=true previews the fee and resulting balances without sending anything
+                    if params.get("dry_run").is_some_and(|v| v == "true") {
+                        return Ok(match client_lock.preview_deposit(req.amount_sol).await {
+                            Ok(preview) => warp::reply::json(&ApiResponse::new(preview, "Deposit preview - nothing was sent")),
+                            Err(e) => warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e}),
+                                &format!("Failed to preview deposit: {}", e)
+                            )),
+                        });
+                    }
                     
                     match client_lock.deposit_to_pda(req.amount_sol).await {
                         Ok((signature, new_balance)) => {
                             let mut response: HashMap<String, serde_json::Value> = HashMap::new();
+                            response.insert("wallet".to_string(), serde_json::to_value(&wallet_name).unwrap());
                             response.insert("transaction_signature".to_string(), serde_json::to_value(signature).unwrap());
                             response.insert("pda_balance".to_string(), serde_json::to_value(new_balance).unwrap());
                             response.insert("deposited_sol".to_string(), serde_json::to_value(req.amount_sol).unwrap());
                             response.insert("treasury_address".to_string(), 
                                 serde_json::to_value(client_lock.get_treasury_address().unwrap_or_default()).unwrap());
+                            drop(client_lock);
+                            // BALANCE ANOMALY: A deposit the detector knows about isn't drift
+                            // (a SOL transfer only moves the tracked balance with a SOL base)
+                            if wallet_name == crate::wallet::DEFAULT_WALLET_NAME {
+                                let mut engine_lock = engine.lock().await;
+                                if engine_lock.base_currency == crate::dex_aggregator::BaseCurrency::Sol {
+                                    engine_lock.record_treasury_flow(req.amount_sol);
+                                }
+                            }
                             
                             Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
                                 response,
@@ -1182,28 +1814,66 @@ pub async fn start_server(
     #[derive(Deserialize)]
     struct PDAWithdrawRequest {
         amount_sol: f64,
+        /// Strategy wallet to withdraw from (default: the main wallet)
+        #[serde(default)]
+        wallet: Option<String>,
     }
 
     let pda_withdraw_route = {
         let solana_client = solana_client.clone();
+        let engine = engine.clone();
         
         warp::path!("pda" / "withdraw")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |req: PDAWithdrawRequest| {
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |req: PDAWithdrawRequest, params: HashMap<String, String>| {
                 let solana_client = solana_client.clone();
+                let engine = engine.clone();
                 
                 async move {
-                    let mut client_lock = solana_client.lock().await;
+                    let (wallet_name, target_client) = match resolve_wallet(&engine, &solana_client, req.wallet.as_deref()).await {
+                        Ok(target) => target,
+                        Err(e) => {
+                            let mut error_response: HashMap<String, serde_json::Value> = HashMap::new();
+                            error_response.insert("error".to_string(), serde_json::to_value(&e).unwrap());
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                error_response,
+                                &format!("Withdrawal failed: {}", e)
+                            )));
+                        }
+                    };
+                    let mut client_lock = target_client.lock().await;
+                    
+                    // ?dry_run=true previews the fee, resulting balances and rent check without sending anything
+                    if params.get("dry_run").is_some_and(|v| v == "true") {
+                        return Ok(match client_lock.preview_withdraw(req.amount_sol).await {
+                            Ok(preview) => warp::reply::json(&ApiResponse::new(preview, "Withdrawal preview - nothing was sent")),
+                            Err(e) => warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e}),
+                                &format!("Failed to preview withdrawal: {}", e)
+                            )),
+                        });
+                    }
                     
                     match client_lock.withdraw_from_pda(req.amount_sol).await {
                         Ok((signature, new_balance)) => {
                             let mut response: HashMap<String, serde_json::Value> = HashMap::new();
+                            response.insert("wallet".to_string(), serde_json::to_value(&wallet_name).unwrap());
                             response.insert("transaction_signature".to_string(), serde_json::to_value(signature).unwrap());
                             response.insert("pda_balance".to_string(), serde_json::to_value(new_balance).unwrap());
                             response.insert("withdrawn_sol".to_string(), serde_json::to_value(req.amount_sol).unwrap());
+                            response.insert("wallet_balance".to_string(), serde_json::to_value(client_lock.wallet_balance).unwrap());
                             response.insert("treasury_address".to_string(), 
                                 serde_json::to_value(client_lock.get_treasury_address().unwrap_or_default()).unwrap());
+                            drop(client_lock);
+                            // BALANCE ANOMALY: Only withdrawals made here are explained - anything else trips the detector
+                            if wallet_name == crate::wallet::DEFAULT_WALLET_NAME {
+                                let mut engine_lock = engine.lock().await;
+                                if engine_lock.base_currency == crate::dex_aggregator::BaseCurrency::Sol {
+                                    engine_lock.record_treasury_flow(-req.amount_sol);
+                                }
+                            }
                             
                             Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
                                 response,
@@ -1214,14 +1884,16 @@ pub async fn start_server(
                             // Return error with helpful information
                             let mut error_response: HashMap<String, serde_json::Value> = HashMap::new();
                             error_response.insert("error".to_string(), serde_json::to_value(e.clone()).unwrap());
-                            error_response.insert("note".to_string(), 
-                                serde_json::to_value("PDA withdrawals require a Solana program using invoke_signed. See error details for more information.").unwrap());
+                            if crate::pda::withdraw_program_id().is_none() {
+                                error_response.insert("note".to_string(), 
+                                    serde_json::to_value("PDA withdrawals require a Solana program using invoke_signed. Set WITHDRAW_PROGRAM_ID - see error details.").unwrap());
+                            }
                             error_response.insert("pda_address".to_string(),
                                 serde_json::to_value(client_lock.get_treasury_address().unwrap_or_default()).unwrap());
                             
                             Ok(warp::reply::json(&ApiResponse::new(
                                 error_response,
-                                &format!("Withdrawal not yet implemented: {}", e)
+                                &format!("Withdrawal failed: {}", e)
                             )))
                         }
                     }
@@ -1297,6 +1969,15 @@ pub async fn start_server(
                             response.insert("current_price", serde_json::to_value(current_price).unwrap());
                             response.insert("indicators", serde_json::to_value(&indicators).unwrap());
                             response.insert("signal_quality", serde_json::to_value(&signal_quality).unwrap());
+                            // Volatility-scaled stop providers would place for a long entered now
+                            let atr_pct = if current_price > 0.0 { indicators.atr_14 / current_price * 100.0 } else { 0.0 };
+                            response.insert("atr", serde_json::json!({
+                                "atr_14": indicators.atr_14,
+                                "atr_pct": atr_pct,
+                                "stop_multiplier": crate::quant_analysis::DEFAULT_ATR_STOP_MULTIPLIER,
+                                "suggested_stop": crate::quant_analysis::QuantAnalyzer::suggested_stop(
+                                    current_price, indicators.atr_14, crate::quant_analysis::DEFAULT_ATR_STOP_MULTIPLIER),
+                            }));
                             
                             Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
                                 response,
@@ -1362,6 +2043,39 @@ pub async fn start_server(
             })
     };
     
+    // Arbitrage endpoint - DEX pool prices against Jupiter's executable round trip, net of the pool leg's costs
+    // Usage: GET /quant/arbitrage?symbols=USDT,SOL&all=true (all=false lists only profitable spreads)
+    let quant_arbitrage_route = {
+        let arbitrage_scanner = arbitrage_scanner.clone();
+        
+        warp::path!("quant" / "arbitrage")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let arbitrage_scanner = arbitrage_scanner.clone();
+                
+                async move {
+                    let symbols: Vec<String> = match params.get("symbols") {
+                        Some(symbols) => symbols.split(',').map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty()).collect(),
+                        None => arbitrage_scanner.config().symbols.clone(),
+                    };
+                    let include_all = params.get("all").is_some_and(|a| a == "true");
+                    let mut opportunities = arbitrage_scanner.scan(&symbols).await;
+                    if !include_all {
+                        opportunities.retain(|o| o.profitable);
+                    }
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "symbols": symbols,
+                            "opportunities": opportunities,
+                            "config": arbitrage_scanner.config(),
+                        }),
+                        "Arbitrage scan completed"
+                    )))
+                }
+            })
+    };
+    
     // Break the route chain into smaller groups to avoid type complexity
     // Agent stats endpoint (uses autonomous_agent.get_stats method)
     let agent_stats_route = {
@@ -1387,7 +2101,9 @@ pub async fn start_server(
             })
     };
     
-    let core_routes = health
+    let core_routes = health_detailed_route
+        .or(health)
+        .or(portfolio_reconciliation_route) // Before /portfolio, which matches any path under it
         .or(portfolio_route)
         .or(performance_route)
         .or(market_data_route)
@@ -1404,8 +2120,6 @@ pub async fn start_server(
         .boxed();
     
     // Oracle Aggregator endpoints
-    let oracle_aggregator = Arc::new(crate::switchboard_oracle::OracleAggregator::new(switchboard_client.clone()));
-    
     let oracle_aggregated_route = {
         let aggregator = oracle_aggregator.clone();
         
@@ -1488,6 +2202,31 @@ pub async fn start_server(
             })
     };
     
+    let oracle_consensus_route = {
+        let aggregator = oracle_aggregator.clone();
+        
+        warp::path!("oracle" / "consensus" / String)
+            .and(warp::get())
+            .and_then(move |symbol: String| {
+                let aggregator = aggregator.clone();
+                
+                async move {
+                    match aggregator.get_consensus_price(&symbol).await {
+                        Ok(consensus) => {
+                            Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(consensus, "Consensus price retrieved")))
+                        }
+                        Err(e) => {
+                            log::error!("Oracle consensus price error: {}", e);
+                            Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({}),
+                                &format!("Failed to get consensus price: {}", e)
+                            )))
+                        }
+                    }
+                }
+            })
+    };
+    
     let oracle_health_route = {
         let aggregator = oracle_aggregator.clone();
         
@@ -1871,16 +2610,19 @@ pub async fn start_server(
     };
     
     let oracle_routes = oracle_price_route
+        .or(oracle_register_feed_route)
         .or(oracle_feeds_route)
         .or(oracle_aggregated_route)
         .or(oracle_confidence_route)
         .or(oracle_compare_route)
+        .or(oracle_consensus_route)
         .or(oracle_health_route)
         .or(oracle_batch_route)
         .boxed();
     
     let dex_routes = dex_search_route
         .or(dex_opportunities_route)
+        .or(dex_ratelimit_route)
         .boxed();
     
     let pumpfun_routes = pumpfun_launches_route
@@ -2067,6 +2809,39 @@ pub async fn start_server(
                 })
         };
         
+        // GET /marketplace/providers/{id}/replay?limit=50 - Replay past signals against the recorded session
+        let replay_provider_route = {
+            let enhanced = enhanced_clone.clone();
+            warp::path!("marketplace" / "providers" / String / "replay")
+                .and(warp::get())
+                .and(warp::query::<HashMap<String, String>>())
+                .and_then(move |provider_id: String, query: HashMap<String, String>| {
+                    let enhanced = enhanced.clone();
+                    async move {
+                        let limit = query.get("limit")
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(super::enhanced_marketplace::DEFAULT_REPLAY_SIGNAL_LIMIT);
+                        let path = std::path::PathBuf::from(
+                            std::env::var("SESSION_FILE").unwrap_or_else(|_| super::historical_data::DEFAULT_SESSION_FILE.to_string())
+                        );
+                        match super::historical_data::load_session(&path) {
+                            Ok(events) => {
+                                let history = super::historical_data::PriceHistory::from_session(&events);
+                                let replay = enhanced.replay_provider_signals(&provider_id, &history, limit).await;
+                                Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                    serde_json::json!(replay),
+                                    "Provider signals replayed"
+                                )))
+                            }
+                            Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("{} - record one with SESSION_MODE=record", e)}),
+                                "Failed to replay provider signals"
+                            )))
+                        }
+                    }
+                })
+        };
+        
         recommend_route
             .or(advanced_search_route)
             .or(compare_providers_route)
@@ -2074,6 +2849,7 @@ pub async fn start_server(
             .or(trends_route)
             .or(leaderboard_route)
             .or(update_leaderboard_route)
+            .or(replay_provider_route)
             .boxed()
     } else {
         // Return empty routes if enhanced marketplace not provided
@@ -2158,80 +2934,1846 @@ pub async fn start_server(
                 )))
             });
         
-        empty_recommend
+        let empty_replay_provider = warp::path!("marketplace" / "providers" / String / "replay")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |_: String, _: HashMap<String, String>| async move {
+                Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                    serde_json::json!({"error": "Enhanced marketplace not available"}),
+                    "Enhanced marketplace service not initialized"
+                )))
+            });
+        
+        empty_recommend
             .or(empty_search)
             .or(empty_compare)
             .or(empty_portfolio)
             .or(empty_trends)
             .or(empty_leaderboard)
             .or(empty_update_leaderboard)
+            .or(empty_replay_provider)
             .boxed()
     };
     
-    let marketplace_routes = signal_marketplace_stats_route
-        .or(signal_active_route)
-        .or(signal_by_symbol_route)
-        .or(signal_generate_route)
-        .or(signal_provider_register_route)
-        .or(signal_provider_stats_route)
-        .or(signal_providers_list_route)
-        .or(signal_purchase_route)
-        .or(enhanced_marketplace_routes)
-        .boxed();
-    
-    let wallet_routes = wallet_status_route
-        .or(treasury_status_route)
-        .boxed();
-    
-    let budget_routes = budget_status_route
-        .or(set_budget_route)
-        .or(pda_deposit_route)
-        .or(pda_balance_route)
-        .or(pda_info_route)
-        .or(pda_withdraw_route)
-        .or(deposit_route)
-        .or(withdraw_route)
-        .boxed();
+    let marketplace_routes = signal_marketplace_stats_route
+        .or(signal_active_route)
+        .or(signal_sandbox_route)
+        .or(execution_lock_routes)
+        .or(signal_by_symbol_route)
+        .or(signal_history_route)
+        .or(signal_generate_route)
+        .or(signal_provider_register_route)
+        .or(signal_provider_stats_route)
+        .or(signal_providers_list_route)
+        .or(signal_purchase_route)
+        .or(signal_revenue_route)
+        .or(enhanced_marketplace_routes)
+        .boxed();
+    
+    let wallet_routes = wallet_status_route
+        .or(treasury_status_route)
+        .boxed();
+    
+    let budget_routes = budget_status_route
+        .or(set_budget_route)
+        .or(pda_deposit_route)
+        .or(pda_balance_route)
+        .or(pda_info_route)
+        .or(pda_withdraw_route)
+        .or(deposit_route)
+        .or(withdraw_route)
+        .boxed();
+    
+    // Trading state routes
+    let trading_state_route = {
+        let trading_enabled = trading_enabled.clone();
+        warp::path("trading-state")
+            .and(warp::get())
+            .and_then(move || {
+                let trading_enabled = trading_enabled.clone();
+                async move {
+                    let enabled = trading_enabled.lock().await;
+                    let mut response = HashMap::new();
+                    response.insert("enabled".to_string(), safe_serialize(&*enabled, serde_json::Value::Bool(false), "enabled"));
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "Trading state retrieved")))
+                }
+            })
+    };
+    
+    let trading_toggle_route = {
+        let trading_enabled = trading_enabled.clone();
+        let engine = engine.clone();
+        warp::path("trading-toggle")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: HashMap<String, serde_json::Value>| {
+                let trading_enabled = trading_enabled.clone();
+                let engine = engine.clone();
+                async move {
+                    let new_state = body.get("enabled")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    
+                    // Re-enabling trading is the explicit reset for an engaged kill switch
+                    if new_state {
+                        let mut engine_lock = engine.lock().await;
+                        
+                        // FUNDING GUARD: real trading stays off until the treasury is funded
+                        if let Some(lockout) = &engine_lock.funding_lockout {
+                            let reason = lockout.message();
+                            log::warn!("🚫 Trading re-enable refused: {}", reason);
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "error": reason,
+                                    "enabled": false,
+                                    "balance": lockout.balance,
+                                    "min_balance": lockout.min_balance,
+                                    "currency": lockout.currency,
+                                }),
+                                "Trading re-enable refused"
+                            )));
+                        }
+                        
+                        // DRAWDOWN GUARD: no re-enable during the cooldown unless forced
+                        let force = body.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+                        let now = chrono::Utc::now().timestamp();
+                        if let Some(lockout) = engine_lock.drawdown_lockout.as_mut() {
+                            if let Some(reason) = lockout.reenable_block(now) {
+                                if !force {
+                                    log::warn!("🚫 Trading re-enable refused: {}", reason);
+                                    return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                        serde_json::json!({
+                                            "error": reason,
+                                            "enabled": false,
+                                            "cooldown_remaining_secs": lockout.cooldown_remaining_secs(now),
+                                        }),
+                                        "Trading re-enable refused"
+                                    )));
+                                }
+                                log::warn!("⚠️ Drawdown cooldown overridden by operator (force): {}", reason);
+                            }
+                            if !lockout.released {
+                                lockout.released = true;
+                                log::warn!("✅ Drawdown lockout released by operator at {:.2}% drawdown", lockout.drawdown_pct);
+                            }
+                        }
+                        
+                        if engine_lock.halted {
+                            engine_lock.halted = false;
+                            log::warn!("✅ Kill switch released - trading can resume");
+                        }
+                    }
+                    
+                    let mut enabled = trading_enabled.lock().await;
+                    *enabled = new_state;
+                    
+                    let status = if new_state { "enabled" } else { "disabled" };
+                    log::info!("🔄 Trading {} by user request", status);
+                    
+                    let mut response = HashMap::new();
+                    response.insert("enabled".to_string(), safe_serialize(&*enabled, serde_json::Value::Bool(false), "enabled"));
+                    response.insert("message".to_string(), safe_serialize(&format!("Trading {}", status), serde_json::Value::String(String::new()), "message"));
+                    
+                    Ok(warp::reply::json(&ApiResponse::new(serde_json::json!(response), &format!("Trading {}", status))))
+                }
+            })
+    };
+    
+    // Per-symbol auto-execution confidence thresholds
+    // GET /config/confidence-threshold - current overrides and global default
+    // POST /config/confidence-threshold {"symbol": "PUMP", "threshold": 0.85}
+    //   omit "symbol" to change the global default; "threshold": null removes the symbol's override
+    let confidence_threshold_routes = {
+        let get_marketplace = signal_marketplace.clone();
+        let get_route = warp::path!("config" / "confidence-threshold")
+            .and(warp::get())
+            .and_then(move || {
+                let marketplace = get_marketplace.clone();
+                async move {
+                    let (overrides, default_threshold) = marketplace.get_confidence_thresholds().await;
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "default_threshold": default_threshold,
+                            "overrides": overrides,
+                        }),
+                        "Confidence thresholds retrieved"
+                    )))
+                }
+            });
+        
+        let set_marketplace = signal_marketplace.clone();
+        let set_route = warp::path!("config" / "confidence-threshold")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let marketplace = set_marketplace.clone();
+                async move {
+                    let symbol = body.get("symbol").and_then(|s| s.as_str()).map(|s| s.to_string());
+                    let threshold = body.get("threshold").and_then(|t| t.as_f64());
+                    
+                    let result = match (&symbol, threshold) {
+                        (Some(symbol), None) => {
+                            if marketplace.remove_confidence_threshold(symbol).await {
+                                Ok(format!("Override for {} removed", symbol))
+                            } else {
+                                Err(format!("No override set for {}", symbol))
+                            }
+                        }
+                        (None, None) => Err("Missing 'threshold'".to_string()),
+                        (symbol, Some(threshold)) => marketplace
+                            .set_confidence_threshold(symbol.as_deref(), threshold)
+                            .await
+                            .map(|_| "Confidence threshold updated".to_string()),
+                    };
+                    
+                    match result {
+                        Ok(message) => {
+                            let (overrides, default_threshold) = marketplace.get_confidence_thresholds().await;
+                            Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "default_threshold": default_threshold,
+                                    "overrides": overrides,
+                                }),
+                                &message
+                            )))
+                        }
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update confidence threshold"
+                        ))),
+                    }
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // Per-provider paper trading
+    // GET /config/dry-run-providers - providers whose signals always paper-trade
+    // POST /config/dry-run-providers {"provider_id": "master_analyzer", "dry_run": true}
+    let dry_run_provider_routes = {
+        let get_marketplace = signal_marketplace.clone();
+        let get_route = warp::path!("config" / "dry-run-providers")
+            .and(warp::get())
+            .and_then(move || {
+                let marketplace = get_marketplace.clone();
+                async move {
+                    let providers = marketplace.get_dry_run_providers().await;
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({"providers": providers}),
+                        "Dry-run providers retrieved"
+                    )))
+                }
+            });
+        
+        let set_marketplace = signal_marketplace.clone();
+        let set_route = warp::path!("config" / "dry-run-providers")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let marketplace = set_marketplace.clone();
+                async move {
+                    let provider_id = match body.get("provider_id").and_then(|p| p.as_str()) {
+                        Some(p) if !p.trim().is_empty() => p.trim().to_string(),
+                        _ => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Missing 'provider_id'"}),
+                                "Failed to update dry-run providers"
+                            )));
+                        }
+                    };
+                    let dry_run = body.get("dry_run").and_then(|d| d.as_bool()).unwrap_or(true);
+                    
+                    marketplace.set_provider_dry_run(&provider_id, dry_run).await;
+                    let providers = marketplace.get_dry_run_providers().await;
+                    let message = if dry_run {
+                        format!("Provider {} now paper-trades", provider_id)
+                    } else {
+                        format!("Provider {} follows the global mode", provider_id)
+                    };
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({"providers": providers}),
+                        &message
+                    )))
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // Provider switches
+    // GET /providers - every specialized provider and whether it is generating signals
+    // POST /providers/{id}/toggle?reason=... - pause or resume one provider without a restart
+    // POST /providers/{id}/interval {"check_interval_secs": 10} - change how often one provider checks for signals
+    // POST /providers/{id}/sandbox - paper-trade a provider's future signals and keep them off the marketplace
+    // POST /providers/{id}/promote - move a sandboxed provider's future signals into the live marketplace
+    // POST /providers/{id}/pricing {"strategy": "performance", "min_multiplier": 0.5, "max_multiplier": 2.0}
+    //   - how the provider's future signals are priced ("fixed", "flat" with "price", or "performance")
+    let provider_toggle_routes = {
+        let list_registry = provider_registry.clone();
+        let list_route = warp::path!("providers")
+            .and(warp::get())
+            .and_then(move || {
+                let registry = list_registry.clone();
+                async move {
+                    match registry {
+                        Some(registry) => {
+                            let providers = registry.list().await;
+                            let enabled = providers.iter().filter(|p| p.enabled).count();
+                            Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "providers": providers,
+                                    "enabled": enabled,
+                                    "total": providers.len(),
+                                }),
+                                "Providers retrieved"
+                            )))
+                        }
+                        None => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Provider registry not configured"}),
+                            "Providers unavailable"
+                        ))),
+                    }
+                }
+            });
+        
+        // GET /providers/capital - each provider's treasury slice and how much of it is deployed
+        let capital_registry = provider_registry.clone();
+        let capital_engine = engine.clone();
+        let capital_route = warp::path!("providers" / "capital")
+            .and(warp::get())
+            .and_then(move || {
+                let registry = capital_registry.clone();
+                let engine = capital_engine.clone();
+                async move {
+                    let provider_ids: Vec<String> = match registry {
+                        Some(registry) => registry.list().await.into_iter().map(|p| p.provider_id).collect(),
+                        None => Vec::new(),
+                    };
+                    let allocations = engine.lock().await.provider_capital(&provider_ids).await;
+                    let deployed: f64 = allocations.iter().map(|a| a.deployed_capital).sum();
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "providers": allocations,
+                            "total_deployed": deployed,
+                        }),
+                        "Provider capital allocations retrieved"
+                    )))
+                }
+            });
+        
+        let toggle_registry = provider_registry.clone();
+        let toggle_route = warp::path!("providers" / String / "toggle")
+            .and(warp::post())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |provider_id: String, params: HashMap<String, String>| {
+                let registry = toggle_registry.clone();
+                async move {
+                    let Some(registry) = registry else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Provider registry not configured"}),
+                            "Failed to toggle provider"
+                        )));
+                    };
+                    
+                    let reason = params.get("reason").cloned().or_else(|| Some("Toggled via API".to_string()));
+                    match registry.toggle(&provider_id, reason).await {
+                        Ok(state) => {
+                            let message = if state.enabled {
+                                format!("Provider {} enabled", provider_id)
+                            } else {
+                                format!("Provider {} disabled", provider_id)
+                            };
+                            Ok(warp::reply::json(&ApiResponse::new(state, &message)))
+                        }
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to toggle provider"
+                        ))),
+                    }
+                }
+            });
+        
+        let interval_registry = provider_registry.clone();
+        let interval_route = warp::path!("providers" / String / "interval")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |provider_id: String, body: serde_json::Value| {
+                let registry = interval_registry.clone();
+                async move {
+                    let Some(registry) = registry else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Provider registry not configured"}),
+                            "Failed to update provider interval"
+                        )));
+                    };
+                    let Some(secs) = body.get("check_interval_secs").and_then(|v| v.as_u64()) else {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Missing 'check_interval_secs'"}),
+                            "Failed to update provider interval"
+                        )));
+                    };
+                    
+                    match registry.set_check_interval(&provider_id, secs).await {
+                        Ok(state) => Ok(warp::reply::json(&ApiResponse::new(
+                            state,
+                            &format!("Provider {} now checks every {}s - applied after its current cycle", provider_id, secs)
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update provider interval"
+                        ))),
+                    }
+                }
+            });
+        
+        let promote_marketplace = signal_marketplace.clone();
+        let promote_route = warp::path!("providers" / String / "promote")
+            .and(warp::post())
+            .and_then(move |provider_id: String| {
+                let marketplace = promote_marketplace.clone();
+                async move {
+                    match marketplace.promote_provider(&provider_id).await {
+                        Ok(provider) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            provider,
+                            &format!("Provider {} promoted - its signals now reach the auto-executor", provider_id)
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to promote provider"
+                        ))),
+                    }
+                }
+            });
+        
+        let pricing_marketplace = signal_marketplace.clone();
+        let pricing_route = warp::path!("providers" / String / "pricing")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |provider_id: String, strategy: super::signal_platform::SignalPricingStrategy| {
+                let marketplace = pricing_marketplace.clone();
+                async move {
+                    match marketplace.set_signal_pricing_strategy(&provider_id, strategy).await {
+                        Ok(provider) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            provider,
+                            &format!("Provider {} pricing updated - applies to signals it publishes from now on", provider_id)
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update provider pricing"
+                        ))),
+                    }
+                }
+            });
+        
+        let sandbox_marketplace = signal_marketplace.clone();
+        let sandbox_route = warp::path!("providers" / String / "sandbox")
+            .and(warp::post())
+            .and_then(move |provider_id: String| {
+                let marketplace = sandbox_marketplace.clone();
+                async move {
+                    match marketplace.set_provider_sandboxed(&provider_id, true).await {
+                        Ok(()) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"provider_id": provider_id, "sandboxed": true}),
+                            &format!("Provider {} sandboxed - its signals now paper-trade until promoted", provider_id)
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to sandbox provider"
+                        ))),
+                    }
+                }
+            });
+        
+        list_route.or(capital_route).or(toggle_route).or(interval_route).or(sandbox_route).or(promote_route).or(pricing_route)
+    };
+    
+    // Loop cadence
+    // GET /config/intervals - current auto-execution and performance tracker intervals
+    // POST /config/intervals {"auto_exec_secs": 5, "perf_tracker_secs": 3} - either field may be omitted
+    let interval_routes = {
+        let get_intervals = loop_intervals.clone();
+        let get_route = warp::path!("config" / "intervals")
+            .and(warp::get())
+            .and_then(move || {
+                let intervals = get_intervals.clone();
+                async move {
+                    match intervals {
+                        Some(intervals) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            intervals.settings(),
+                            "Loop intervals retrieved"
+                        ))),
+                        None => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Loop intervals not configured"}),
+                            "Loop intervals unavailable"
+                        ))),
+                    }
+                }
+            });
+        
+        let set_intervals = loop_intervals.clone();
+        let set_route = warp::path!("config" / "intervals")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let intervals = set_intervals.clone();
+                async move {
+                    let intervals = match intervals {
+                        Some(intervals) => intervals,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Loop intervals not configured"}),
+                                "Failed to update loop intervals"
+                            )));
+                        }
+                    };
+                    
+                    let auto_exec_secs = body.get("auto_exec_secs").and_then(|v| v.as_u64());
+                    let perf_tracker_secs = body.get("perf_tracker_secs").and_then(|v| v.as_u64());
+                    
+                    // Validate both before applying either so a bad request changes nothing
+                    let result = match (auto_exec_secs, perf_tracker_secs) {
+                        (None, None) => Err("Missing 'auto_exec_secs' or 'perf_tracker_secs'".to_string()),
+                        _ => auto_exec_secs.into_iter()
+                            .chain(perf_tracker_secs)
+                            .try_for_each(crate::loop_intervals::LoopIntervals::validate),
+                    };
+                    if let Err(e) = result {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update loop intervals"
+                        )));
+                    }
+                    
+                    if let Some(secs) = auto_exec_secs {
+                        intervals.set_auto_exec_secs(secs).ok();
+                    }
+                    if let Some(secs) = perf_tracker_secs {
+                        intervals.set_perf_tracker_secs(secs).ok();
+                    }
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::to_value(intervals.settings()).unwrap_or_default(),
+                        "Loop intervals updated - applied from the next tick"
+                    )))
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // Token filter - hard manual override on which mints may be bought
+    // GET /config/token-filter - mode, both lists and how many signals were refused
+    // POST /config/token-filter {"mode": "whitelist_only", "blacklist_add": ["<mint>"], "whitelist_remove": ["RAY"]}
+    // Every field is optional; entries are mints or known symbols
+    let token_filter_routes = {
+        let get_filter = token_filter.clone();
+        let get_route = warp::path!("config" / "token-filter")
+            .and(warp::get())
+            .and_then(move || {
+                let token_filter = get_filter.clone();
+                async move {
+                    match token_filter {
+                        Some(token_filter) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            token_filter.settings().await,
+                            "Token filter retrieved"
+                        ))),
+                        None => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Token filter not configured"}),
+                            "Token filter unavailable"
+                        ))),
+                    }
+                }
+            });
+        
+        let set_filter = token_filter.clone();
+        let set_route = warp::path!("config" / "token-filter")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let token_filter = set_filter.clone();
+                async move {
+                    let Some(token_filter) = token_filter else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Token filter not configured"}),
+                            "Failed to update token filter"
+                        )));
+                    };
+                    
+                    // Validate everything before applying anything so a bad request changes nothing
+                    let mode = match body.get("mode").and_then(|m| m.as_str()) {
+                        Some(mode) => match crate::security::TokenFilterMode::parse(mode) {
+                            Some(mode) => Some(mode),
+                            None => {
+                                return Ok(warp::reply::json(&ApiResponse::new(
+                                    serde_json::json!({"error": format!("Unknown mode '{}' (blacklist or whitelist_only)", mode)}),
+                                    "Failed to update token filter"
+                                )));
+                            }
+                        },
+                        None => None,
+                    };
+                    let list = |key: &str| -> Vec<String> {
+                        body.get(key)
+                            .and_then(|v| v.as_array())
+                            .map(|tokens| tokens.iter().filter_map(|t| t.as_str()).map(|t| t.trim().to_string()).collect())
+                            .unwrap_or_default()
+                    };
+                    let changes = [
+                        (list("blacklist_add"), false, true),
+                        (list("blacklist_remove"), false, false),
+                        (list("whitelist_add"), true, true),
+                        (list("whitelist_remove"), true, false),
+                    ];
+                    if let Some(e) = changes.iter()
+                        .flat_map(|(tokens, _, _)| tokens)
+                        .find_map(|token| crate::security::TokenFilter::normalize(token).err())
+                    {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update token filter"
+                        )));
+                    }
+                    
+                    for (tokens, whitelist, add) in &changes {
+                        for token in tokens {
+                            if *add {
+                                token_filter.add(token, *whitelist).await.ok();
+                            } else {
+                                token_filter.remove(token, *whitelist).await.ok();
+                            }
+                        }
+                    }
+                    if let Some(mode) = mode {
+                        token_filter.set_mode(mode).await;
+                    }
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        token_filter.settings().await,
+                        "Token filter updated"
+                    )))
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // GET /config/memecoin-filter - liquidity, token age and holder gates for memecoin signals
+    // POST /config/memecoin-filter {"min_liquidity_usd": 8000, "min_token_age_hours": 2, "reject_unknown": true}
+    // Fields are optional and merged into the current settings; invalid values change nothing
+    let memecoin_filter_routes = {
+        let get_filter = memecoin_filter.clone();
+        let get_route = warp::path!("config" / "memecoin-filter")
+            .and(warp::get())
+            .and_then(move || {
+                let memecoin_filter = get_filter.clone();
+                async move {
+                    match memecoin_filter {
+                        Some(memecoin_filter) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            memecoin_filter.get().await,
+                            "Memecoin filter retrieved"
+                        ))),
+                        None => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Memecoin filter not configured"}),
+                            "Memecoin filter unavailable"
+                        ))),
+                    }
+                }
+            });
+        
+        let set_filter = memecoin_filter.clone();
+        let set_route = warp::path!("config" / "memecoin-filter")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let memecoin_filter = set_filter.clone();
+                async move {
+                    let Some(memecoin_filter) = memecoin_filter else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Memecoin filter not configured"}),
+                            "Failed to update memecoin filter"
+                        )));
+                    };
+                    let Some(changes) = body.as_object() else {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Body must be a JSON object"}),
+                            "Failed to update memecoin filter"
+                        )));
+                    };
+                    
+                    let mut merged = serde_json::to_value(memecoin_filter.get().await).unwrap_or_default();
+                    if let Some(current) = merged.as_object_mut() {
+                        current.extend(changes.clone());
+                    }
+                    let result = serde_json::from_value::<crate::specialized_providers::MemecoinFilterConfig>(merged)
+                        .map_err(|e| format!("Invalid memecoin filter: {}", e));
+                    let result = match result {
+                        Ok(config) => memecoin_filter.set(config).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(()) => {
+                            let config = memecoin_filter.get().await;
+                            log::info!("🚧 Memecoin filter updated: {:?}", config);
+                            Ok(warp::reply::json(&ApiResponse::new(config, "Memecoin filter updated")))
+                        }
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update memecoin filter"
+                        ))),
+                    }
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // GET /market/regime - the Master Analyzer's latest classification and which provider types it stands down
+    // POST /market/regime/gating {"provider_type": "memecoin_monitor", "stand_down_in": ["BEARISH", "NEUTRAL"]}
+    // An empty stand_down_in lets the provider type publish buys in every regime
+    let market_regime_routes = {
+        let get_regime = market_regime.clone();
+        let get_route = warp::path!("market" / "regime")
+            .and(warp::get())
+            .and_then(move || {
+                let market_regime = get_regime.clone();
+                async move {
+                    let Some(market_regime) = market_regime else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Market regime not configured"}),
+                            "Market regime unavailable"
+                        )));
+                    };
+                    let now = chrono::Utc::now().timestamp();
+                    let current = market_regime.current(now).await;
+                    let gating = market_regime.gating().await;
+                    let mut standing_down = Vec::new();
+                    for provider_type in crate::specialized_providers::ProviderType::ALL {
+                        if market_regime.stand_down(provider_type.key(), now).await.is_some() {
+                            standing_down.push(provider_type.key());
+                        }
+                    }
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "regime": current,
+                            "reading": market_regime.reading().await,
+                            "stale": current.is_none(),
+                            "max_age_secs": market_regime.max_age_secs(),
+                            "benchmarks": market_regime.benchmarks(),
+                            "threshold_pct": market_regime.threshold_pct(),
+                            "gating": gating,
+                            "standing_down": standing_down,
+                        }),
+                        "Market regime retrieved"
+                    )))
+                }
+            });
+        
+        let set_regime = market_regime.clone();
+        let set_route = warp::path!("market" / "regime" / "gating")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let market_regime = set_regime.clone();
+                async move {
+                    let Some(market_regime) = market_regime else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Market regime not configured"}),
+                            "Failed to update regime gating"
+                        )));
+                    };
+                    let provider_type = body.get("provider_type").and_then(|v| v.as_str()).unwrap_or_default();
+                    if !crate::specialized_providers::ProviderType::ALL.iter().any(|t| t.key() == provider_type) {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Unknown provider_type {:?}", provider_type)}),
+                            "Failed to update regime gating"
+                        )));
+                    }
+                    let regimes = body.get("stand_down_in").cloned()
+                        .ok_or_else(|| "Missing stand_down_in".to_string())
+                        .and_then(|v| serde_json::from_value::<Vec<String>>(v)
+                            .map_err(|_| "stand_down_in must be a list of regimes".to_string()))
+                        .and_then(|regimes| regimes.iter()
+                            .map(|regime| crate::market_regime::MarketRegime::parse(regime)
+                                .ok_or_else(|| format!("Unknown regime {:?}", regime)))
+                            .collect::<Result<Vec<_>, _>>());
+                    match regimes {
+                        Ok(regimes) => {
+                            log::info!("🌡️ Regime gating for {} set to {:?}", provider_type, regimes);
+                            market_regime.set_gating(provider_type, regimes).await;
+                            Ok(warp::reply::json(&ApiResponse::new(market_regime.gating().await, "Regime gating updated")))
+                        }
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update regime gating"
+                        ))),
+                    }
+                }
+            });
+        
+        get_route.or(set_route)
+    };
+    
+    // GET /callbacks/status - lifecycle callback queue depth (pending / dead) and the last delivery error
+    // POST /callbacks/retry - give every dead callback a fresh set of delivery attempts
+    let callback_routes = {
+        let status_callbacks = signal_callbacks.clone();
+        let status_route = warp::path!("callbacks" / "status")
+            .and(warp::get())
+            .and_then(move || {
+                let signal_callbacks = status_callbacks.clone();
+                async move {
+                    let Some(signal_callbacks) = signal_callbacks else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "SIGNAL_CALLBACK_URL not configured"}),
+                            "Signal callbacks disabled"
+                        )));
+                    };
+                    Ok(warp::reply::json(&ApiResponse::new(signal_callbacks.status().await, "Callback queue retrieved")))
+                }
+            });
+        
+        let retry_callbacks = signal_callbacks.clone();
+        let retry_route = warp::path!("callbacks" / "retry")
+            .and(warp::post())
+            .and_then(move || {
+                let signal_callbacks = retry_callbacks.clone();
+                async move {
+                    let Some(signal_callbacks) = signal_callbacks else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "SIGNAL_CALLBACK_URL not configured"}),
+                            "Signal callbacks disabled"
+                        )));
+                    };
+                    match signal_callbacks.retry_dead().await {
+                        Ok(revived) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"requeued": revived}),
+                            "Dead callbacks requeued"
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to requeue callbacks"
+                        ))),
+                    }
+                }
+            });
+        
+        status_route.or(retry_route)
+    };
+    
+    // Skipped signals endpoint - why the auto-executor passed on recent signals
+    // Usage: GET /skipped?limit=50&reason=confidence_below_threshold
+    let skipped_signals_route = {
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("skipped")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let marketplace = marketplace.clone();
+                async move {
+                    let limit = params.get("limit")
+                        .and_then(|l| l.parse::<usize>().ok())
+                        .unwrap_or(50)
+                        .min(500);
+                    
+                    let reason = match params.get("reason") {
+                        Some(r) => match r.parse::<crate::signal_platform::SkipReason>() {
+                            Ok(reason) => Some(reason),
+                            Err(e) => {
+                                return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                    serde_json::json!({"error": e}),
+                                    "Invalid skip reason filter"
+                                )));
+                            }
+                        },
+                        None => None,
+                    };
+                    
+                    let skipped = marketplace.get_skipped_signals(limit, reason).await;
+                    let count = skipped.len();
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "skipped": skipped,
+                            "count": count,
+                            "limit": limit,
+                        }),
+                        "Skipped signals retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // Adaptive slippage endpoint - learned per-symbol tolerances and auto-blocklist
+    // GET /analytics/slippage
+    let slippage_analytics_route = {
+        let engine = engine.clone();
+        
+        warp::path!("analytics" / "slippage")
+            .and(warp::get())
+            .and_then(move || {
+                let engine = engine.clone();
+                async move {
+                    let engine = engine.lock().await;
+                    let config = engine.slippage.config().clone();
+                    let symbols = engine.slippage.snapshot();
+                    let blocklisted: Vec<String> = symbols.iter()
+                        .filter(|s| s.blocklisted)
+                        .map(|s| s.symbol.clone())
+                        .collect();
+                    
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "base_bps": config.base_bps,
+                            "ceiling_bps": config.ceiling_bps,
+                            "step_bps": config.step_bps,
+                            "failures_before_raise": config.failures_before_raise,
+                            "symbols": symbols,
+                            "blocklisted": blocklisted,
+                        }),
+                        "Slippage tolerances retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // Notification delivery test endpoint
+    // POST /notifications/test {"event": "stop_loss_hit"} - send a synthetic notification through the configured notifier
+    let notification_test_route = {
+        let notifier = notifier.clone();
+        
+        warp::path!("notifications" / "test")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let notifier = notifier.clone();
+                async move {
+                    let event = match body.get("event").and_then(|e| e.as_str()) {
+                        Some(e) => match e.parse::<crate::notifications::NotificationEvent>() {
+                            Ok(event) => event,
+                            Err(e) => {
+                                return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                    serde_json::json!({"error": e}),
+                                    "Invalid notification event"
+                                )));
+                            }
+                        },
+                        None => crate::notifications::NotificationEvent::TradeExecuted,
+                    };
+                    
+                    let notifier = match notifier {
+                        Some(n) => n,
+                        None => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "No notifier configured (set NOTIFY_WEBHOOK_URL)"}),
+                                "Notifications not configured"
+                            )));
+                        }
+                    };
+                    
+                    let notification = crate::notifications::Notification::synthetic(event);
+                    let result = notifier.send(&notification).await;
+                    if result.delivered {
+                        log::info!("🔔 Test notification delivered via {}", notifier.name());
+                    } else {
+                        log::warn!("⚠️ Test notification via {} failed: {:?}", notifier.name(), result.error);
+                    }
+                    
+                    let message = if result.delivered { "Test notification delivered" } else { "Test notification failed" };
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "notifier": notifier.name(),
+                            "event": event,
+                            "delivered": result.delivered,
+                            "status_code": result.status_code,
+                            "error": result.error,
+                        }),
+                        message
+                    )))
+                }
+            })
+    };
+    
+    // Limit order endpoints
+    // GET /orders/pending - resting limit orders
+    // POST /orders/limit - place a limit order at a marketplace signal's entry price
+    // POST /orders/cancel - cancel a resting limit order
+    let order_routes = {
+        let pending_engine = engine.clone();
+        let pending_route = warp::path!("orders" / "pending")
+            .and(warp::get())
+            .and_then(move || {
+                let engine = pending_engine.clone();
+                async move {
+                    let orders = engine.lock().await.get_pending_orders();
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "count": orders.len(),
+                            "orders": orders,
+                        }),
+                        "Pending orders retrieved"
+                    )))
+                }
+            });
+        
+        let limit_engine = engine.clone();
+        let limit_marketplace = signal_marketplace.clone();
+        let limit_route = warp::path!("orders" / "limit")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let engine = limit_engine.clone();
+                let marketplace = limit_marketplace.clone();
+                async move {
+                    let signal_id = match body.get("signal_id").and_then(|s| s.as_str()) {
+                        Some(id) => id.to_string(),
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Missing signal_id"}),
+                                "Limit order not placed"
+                            )));
+                        }
+                    };
+                    
+                    let mut signal = match marketplace.get_signal(&signal_id).await {
+                        Some(s) => s,
+                        None => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("Signal {} not found", signal_id)}),
+                                "Limit order not placed"
+                            )));
+                        }
+                    };
+                    // Optional override of the signal's entry price
+                    if let Some(limit_price) = body.get("limit_price").and_then(|p| p.as_f64()) {
+                        signal.entry_price = limit_price;
+                    }
+                    
+                    match engine.lock().await.place_limit_order(&signal) {
+                        Ok(order) => Ok(warp::reply::json(&ApiResponse::new(order, "Limit order placed"))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Limit order not placed"
+                        ))),
+                    }
+                }
+            });
+        
+        let cancel_engine = engine.clone();
+        let cancel_route = warp::path!("orders" / "cancel")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let engine = cancel_engine.clone();
+                async move {
+                    let order_id = body.get("order_id").and_then(|s| s.as_str()).unwrap_or_default().to_string();
+                    match engine.lock().await.cancel_order(&order_id) {
+                        Ok(order) => Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(order, "Order cancelled"))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Order not cancelled"
+                        ))),
+                    }
+                }
+            });
+        
+        pending_route.or(limit_route).or(cancel_route)
+    };
+    
+    // Manual signal execution endpoint
+    // POST /signals/marketplace/execute/{signal_id} - execute one marketplace signal now, whatever its
+    // confidence, honoring DRY_RUN_MODE and the same Executing lock the auto-executor takes
+    let signal_execute_route = {
+        let engine = engine.clone();
+        let trading_enabled = trading_enabled.clone();
+        let enhanced_marketplace = enhanced_marketplace.clone();
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("signals" / "marketplace" / "execute" / String)
+            .and(warp::post())
+            .and_then(move |signal_id: String| {
+                let engine = engine.clone();
+                let trading_enabled = trading_enabled.clone();
+                let enhanced_marketplace = enhanced_marketplace.clone();
+                let marketplace = marketplace.clone();
+                async move {
+                    let signal = match marketplace.get_signal(&signal_id).await {
+                        Some(s) => s,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("Signal {} not found", signal_id)}),
+                                "Signal not executed"
+                            )));
+                        }
+                    };
+                    
+                    let not_executable = match signal.status {
+                        super::signal_platform::SignalStatus::Filled => Some("Signal already filled".to_string()),
+                        super::signal_platform::SignalStatus::Expired => Some("Signal expired".to_string()),
+                        _ if signal.expiry <= chrono::Utc::now().timestamp() => Some("Signal expired".to_string()),
+                        super::signal_platform::SignalStatus::Active => None,
+                        ref status => Some(format!("Signal is {:?}", status)),
+                    };
+                    if let Some(error) = not_executable {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": error, "signal_id": signal_id}),
+                            "Signal not executed"
+                        )));
+                    }
+                    
+                    // Same atomic lock as the auto-executor, so the two can never both run this signal
+                    match marketplace.try_mark_executing(&signal_id).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Signal already being executed", "signal_id": signal_id}),
+                                "Signal not executed"
+                            )));
+                        }
+                        Err(e) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e, "signal_id": signal_id}),
+                                "Signal not executed"
+                            )));
+                        }
+                    }
+                    
+                    if let Some(ref enhanced) = enhanced_marketplace {
+                        if let Err(e) = enhanced.initialize_signal_performance(&signal).await {
+                            let _ = marketplace.update_signal_status(&signal_id, super::signal_platform::SignalStatus::Active).await;
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e, "signal_id": signal_id}),
+                                "Performance tracking unavailable"
+                            )));
+                        }
+                    }
+                    
+                    let dry_run_mode = crate::solana_integration::SolanaClient::dry_run_mode();
+                    let dry_run = Arc::new(Mutex::new(dry_run_mode));
+                    
+                    log::info!("🖐️ Manual execution requested for signal {}", signal_id);
+                    let execution = crate::trading_engine::TradingEngine::execute_marketplace_signal_shared(
+                        &engine, &signal, Some(&trading_enabled), Some(&dry_run)).await;
+                    let engine_lock = engine.lock().await;
+                    match execution {
+                        Ok(result) => {
+                            let fill_price = engine_lock.fill_price_usd(&signal_id, &signal.symbol).unwrap_or(signal.entry_price);
+                            let fill_size = engine_lock.fill_size(&signal_id);
+                            drop(engine_lock);
+                            let is_paper = dry_run_mode || signal.force_dry_run;
+                            if let Some(ref enhanced) = enhanced_marketplace {
+                                if let Err(e) = enhanced.mark_signal_filled(&signal_id).await {
+                                    log::warn!("⚠️ Failed to mark signal as filled: {}", e);
+                                }
+                                enhanced.set_fill_price(&signal_id, fill_price).await.ok();
+                                enhanced.set_position_mode(&signal_id, is_paper).await.ok();
+                            }
+                            if let Err(e) = marketplace.mark_signal_executed(&signal_id, fill_price, fill_size).await {
+                                log::warn!("⚠️ Failed to update signal status: {}", e);
+                            }
+                            Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "signal_id": signal_id,
+                                    "symbol": signal.symbol,
+                                    "action": signal.action,
+                                    "fill_price": fill_price,
+                                    "dry_run": is_paper,
+                                    "result": result,
+                                }),
+                                "Signal executed"
+                            )))
+                        }
+                        Err(e) => {
+                            // AUDIT TRAIL: A swap already on-chain may still fill - leave it Executing
+                            let live_execution = engine_lock.live_execution(&signal_id).await;
+                            drop(engine_lock);
+                            if live_execution.is_none() {
+                                let _ = marketplace.update_signal_status(&signal_id, super::signal_platform::SignalStatus::Active).await;
+                            }
+                            Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "error": e,
+                                    "signal_id": signal_id,
+                                    "pending_signature": live_execution.map(|execution| execution.signature),
+                                }),
+                                "Signal execution failed"
+                            )))
+                        }
+                    }
+                }
+            })
+    };
+    
+    // DCA execution endpoint
+    // POST /signals/{signal_id}/dca - execute a marketplace signal in chunks
+    // Body (optional fields): {"chunks": 4, "interval_secs": 30}; defaults from DCA_CHUNKS / DCA_INTERVAL_SECS
+    let dca_route = {
+        let engine = engine.clone();
+        let trading_enabled = trading_enabled.clone();
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("signals" / String / "dca")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |signal_id: String, body: serde_json::Value| {
+                let engine = engine.clone();
+                let trading_enabled = trading_enabled.clone();
+                let marketplace = marketplace.clone();
+                async move {
+                    let signal = match marketplace.get_signal(&signal_id).await {
+                        Some(s) => s,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("Signal {} not found", signal_id)}),
+                                "DCA not started"
+                            )));
+                        }
+                    };
+                    
+                    let not_executable = match signal.status {
+                        super::signal_platform::SignalStatus::Filled => Some("Signal already filled".to_string()),
+                        super::signal_platform::SignalStatus::Expired => Some("Signal expired".to_string()),
+                        _ if signal.expiry <= chrono::Utc::now().timestamp() => Some("Signal expired".to_string()),
+                        super::signal_platform::SignalStatus::Active => None,
+                        ref status => Some(format!("Signal is {:?}", status)),
+                    };
+                    if let Some(error) = not_executable {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": error, "signal_id": signal_id}),
+                            "DCA not started"
+                        )));
+                    }
+                    
+                    // Same atomic lock as single-shot execution - the chunks can't race the auto-executor
+                    match marketplace.try_mark_executing(&signal_id).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Signal already being executed", "signal_id": signal_id}),
+                                "DCA not started"
+                            )));
+                        }
+                        Err(e) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e, "signal_id": signal_id}),
+                                "DCA not started"
+                            )));
+                        }
+                    }
+                    
+                    let defaults = crate::trading_engine::DcaConfig::from_env();
+                    let config = crate::trading_engine::DcaConfig {
+                        chunks: body.get("chunks").and_then(|c| c.as_u64()).map(|c| c as u32).unwrap_or(defaults.chunks).max(1),
+                        interval_secs: body.get("interval_secs").and_then(|i| i.as_u64()).unwrap_or(defaults.interval_secs),
+                    };
+                    let dry_run_mode = crate::solana_integration::SolanaClient::dry_run_mode();
+                    
+                    // Chunks are spread over time - run in the background and report via logs
+                    // Any filled chunk fills the signal; one that filled nothing is cancelled
+                    let task_config = config.clone();
+                    tokio::spawn(async move {
+                        let dry_run = Arc::new(Mutex::new(dry_run_mode));
+                        let status = match crate::trading_engine::TradingEngine::dca_execute(
+                            &engine, &signal, &task_config, Some(&trading_enabled), Some(&dry_run)
+                        ).await {
+                            Ok(result) => {
+                                log::info!("✅ DCA {} finished: {}/{} chunks, avg fill ${:.8}",
+                                          result.signal_id, result.chunks_filled, result.chunks_planned, result.average_fill_price);
+                                marketplace.mark_signal_executed(&signal.id, result.average_fill_price, Some(result.filled_size)).await
+                            }
+                            Err(e) => {
+                                log::warn!("⚠️ DCA {} failed: {}", signal.id, e);
+                                marketplace.update_signal_status(&signal.id, super::signal_platform::SignalStatus::Cancelled).await
+                            }
+                        };
+                        if let Err(e) = status {
+                            log::warn!("⚠️ Failed to update signal status: {}", e);
+                        }
+                    });
+                    
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "signal_id": signal_id,
+                            "chunks": config.chunks,
+                            "interval_secs": config.interval_secs,
+                            "dry_run": dry_run_mode,
+                        }),
+                        "DCA execution started"
+                    )))
+                }
+            })
+    };
+    
+    // What-if sizing
+    // POST /simulate/execute - quote, slippage, fee, risk-checked size and projected P&L for a hypothetical signal
+    // Body: {"symbol", "action": "buy"|"sell", "entry_price", "target_price", "stop_loss", "confidence", "provider"?}
+    // Read-only: no funds move and nothing is published to the marketplace
+    let simulate_execute_route = {
+        let engine = engine.clone();
+        
+        warp::path!("simulate" / "execute")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let engine = engine.clone();
+                async move {
+                    let text = |key: &str| body.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+                    let number = |key: &str| body.get(key).and_then(|v| v.as_f64());
+                    let action = match text("action").map(|a| a.to_lowercase()).as_deref() {
+                        Some("buy") => Some(super::signal_platform::SignalAction::Buy),
+                        Some("sell") => Some(super::signal_platform::SignalAction::Sell),
+                        _ => None,
+                    };
+                    let (symbol, action, entry_price) = match (text("symbol"), action, number("entry_price")) {
+                        (Some(symbol), Some(action), Some(entry_price)) => (symbol, action, entry_price),
+                        _ => {
+                            let mut response = ApiResponse::new(
+                                serde_json::json!({"error": "symbol, action (buy/sell) and entry_price are required"}),
+                                "Invalid simulation request"
+                            );
+                            response.success = false;
+                            return Ok::<_, warp::Rejection>(warp::reply::with_status(
+                                warp::reply::json(&response),
+                                warp::http::StatusCode::BAD_REQUEST,
+                            ));
+                        }
+                    };
+                    let now = chrono::Utc::now().timestamp();
+                    let signal = super::signal_platform::TradingSignalData {
+                        id: text("id").unwrap_or_else(|| format!("simulation_{}", uuid::Uuid::new_v4())),
+                        provider: text("provider").unwrap_or_else(|| "simulation".to_string()),
+                        symbol,
+                        action,
+                        entry_price,
+                        target_price: number("target_price").unwrap_or(entry_price),
+                        stop_loss: number("stop_loss").unwrap_or(entry_price),
+                        confidence: number("confidence").unwrap_or(0.75),
+                        timeframe: text("timeframe").unwrap_or_else(|| "1h".to_string()),
+                        data_sources: vec![],
+                        analysis: "What-if simulation".to_string(),
+                        timestamp: now,
+                        expiry: now + 3600,
+                        price: 0.0,
+                        status: super::signal_platform::SignalStatus::Active,
+                        force_dry_run: true,
+                        ..Default::default()
+                    };
+                    
+                    let simulation = crate::trading_engine::TradingEngine::simulate_marketplace_signal(&engine, &signal).await;
+                    Ok(match simulation {
+                        Ok(simulation) => {
+                            let message = if simulation.would_execute {
+                                "Signal would execute"
+                            } else {
+                                "Signal would be rejected"
+                            };
+                            warp::reply::with_status(
+                                warp::reply::json(&ApiResponse::new(simulation, message)),
+                                warp::http::StatusCode::OK,
+                            )
+                        }
+                        Err(e) => {
+                            let mut response = ApiResponse::new(serde_json::json!({"error": e}), "Simulation failed");
+                            response.success = false;
+                            warp::reply::with_status(
+                                warp::reply::json(&response),
+                                warp::http::StatusCode::BAD_REQUEST,
+                            )
+                        }
+                    })
+                }
+            })
+    };
     
-    // Trading state routes
-    let trading_state_route = {
+    // Emergency kill switch
+    // POST /emergency/flatten - disable trading, cancel pending orders and sell every open position
+    // Body (optional): {"reason": "..."}; re-enable via POST /trading-toggle
+    let emergency_flatten_route = {
+        let engine = engine.clone();
         let trading_enabled = trading_enabled.clone();
-        warp::path("trading-state")
+        let enhanced_marketplace = enhanced_marketplace.clone();
+        
+        warp::path!("emergency" / "flatten")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |body: serde_json::Value| {
+                let engine = engine.clone();
+                let trading_enabled = trading_enabled.clone();
+                let enhanced_marketplace = enhanced_marketplace.clone();
+                async move {
+                    let reason = body.get("reason")
+                        .and_then(|r| r.as_str())
+                        .unwrap_or("Manual kill switch")
+                        .to_string();
+                    let dry_run_mode = crate::solana_integration::SolanaClient::dry_run_mode();
+                    
+                    let report = crate::production_safeguards::flatten_all(&engine, &trading_enabled, dry_run_mode, &reason).await;
+                    if let Some(ref enhanced) = enhanced_marketplace {
+                        for close in report.closes.iter().filter(|c| c.success) {
+                            if let Some(price) = close.price {
+                                enhanced.close_symbol_positions(&close.symbol, price, crate::enhanced_marketplace::ExitReason::Flatten).await;
+                            }
+                        }
+                    }
+                    let message = if report.positions_remaining == 0 {
+                        "All positions flattened - trading disabled".to_string()
+                    } else {
+                        format!("Flatten incomplete - {} positions still open", report.positions_remaining)
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(report, &message)))
+                }
+            })
+    };
+    
+    // DEX aggregator health
+    // GET /aggregators/status - fallback chain in priority order with per-aggregator circuit breaker state
+    let aggregator_status_route = {
+        let engine = engine.clone();
+        
+        warp::path!("aggregators" / "status")
             .and(warp::get())
             .and_then(move || {
-                let trading_enabled = trading_enabled.clone();
+                let engine = engine.clone();
                 async move {
-                    let enabled = trading_enabled.lock().await;
-                    let mut response = HashMap::new();
-                    response.insert("enabled".to_string(), safe_serialize(&*enabled, serde_json::Value::Bool(false), "enabled"));
-                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "Trading state retrieved")))
+                    let router = engine.lock().await.aggregator_router.clone();
+                    let statuses = match router {
+                        Some(router) => router.status().await,
+                        None => Vec::new(),
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(statuses, "Aggregator status retrieved")))
                 }
             })
     };
     
-    let trading_toggle_route = {
+    // Portfolio equity curve
+    // GET /portfolio/history?from=<unix>&to=<unix>&dry_run=true|false - omit dry_run for both curves
+    let portfolio_history_route = {
+        let database = database.clone();
+        
+        warp::path!("portfolio" / "history")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let database = database.clone();
+                async move {
+                    let database = match database {
+                        Some(db) => db,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "No database configured"}),
+                                "Portfolio history unavailable"
+                            )));
+                        }
+                    };
+                    
+                    let from = params.get("from").and_then(|v| v.parse::<i64>().ok());
+                    let to = params.get("to").and_then(|v| v.parse::<i64>().ok());
+                    let dry_run = params.get("dry_run").and_then(|v| v.parse::<bool>().ok());
+                    
+                    let history = database.lock().await.get_portfolio_history(from, to, dry_run);
+                    let count = history.len();
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "snapshots": history,
+                            "count": count,
+                            "from": from,
+                            "to": to,
+                            "dry_run": dry_run,
+                        }),
+                        "Portfolio history retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // Execution audit trail endpoints
+    // GET /executions/recent?limit= - latest submitted swaps and their on-chain status
+    let executions_recent_route = {
+        let database = database.clone();
+        
+        warp::path!("executions" / "recent")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let database = database.clone();
+                async move {
+                    let database = match database {
+                        Some(db) => db,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "No database configured"}),
+                                "Execution audit trail unavailable"
+                            )));
+                        }
+                    };
+                    
+                    let limit = params.get("limit").and_then(|v| v.parse::<usize>().ok()).unwrap_or(50);
+                    let db = database.lock().await;
+                    let executions: Vec<_> = db.get_recent_executions(limit).into_iter().cloned().collect();
+                    let pending = db.get_pending_executions().len();
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "executions": executions,
+                            "count": executions.len(),
+                            "pending": pending,
+                        }),
+                        "Recent executions retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // GET /executions/{signature} - lifecycle of one submitted swap
+    let execution_by_signature_route = {
+        let database = database.clone();
+        
+        warp::path!("executions" / String)
+            .and(warp::get())
+            .and_then(move |signature: String| {
+                let database = database.clone();
+                async move {
+                    let database = match database {
+                        Some(db) => db,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "No database configured"}),
+                                "Execution audit trail unavailable"
+                            )));
+                        }
+                    };
+                    
+                    let execution = database.lock().await.get_execution(&signature).cloned();
+                    match execution {
+                        Some(execution) => Ok(warp::reply::json(&ApiResponse::new(
+                            execution,
+                            "Execution retrieved"
+                        ))),
+                        None => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("No execution recorded for {}", signature)}),
+                            "Execution not found"
+                        ))),
+                    }
+                }
+            })
+    };
+    
+    // Provider backtest endpoint
+    // POST /backtest/provider - replay OHLCV candles through a provider's live analysis before enabling it
+    // Body: {"provider_type": "MemecoinMonitor", "series": {"symbol": ..., "data": [...], "max_size": ...},
+    //        "initial_balance": 10000.0, "liquidity_usd": 50000.0}
+    let backtest_memecoin_filter = memecoin_filter.clone();
+    let backtest_route = warp::path!("backtest" / "provider")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and_then(move |body: serde_json::Value| {
+            let memecoin_filter = backtest_memecoin_filter.clone();
+            async move {
+                let provider_type = match serde_json::from_value::<crate::specialized_providers::ProviderType>(
+                    body.get("provider_type").cloned().unwrap_or_default()
+                ) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Invalid provider_type: {}", e)}),
+                            "Backtest failed"
+                        )));
+                    }
+                };
+                let series = match serde_json::from_value::<crate::historical_data::HistoricalDataset>(
+                    body.get("series").cloned().unwrap_or_default()
+                ) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Invalid series: {}", e)}),
+                            "Backtest failed"
+                        )));
+                    }
+                };
+            
+                let mut config = crate::backtesting::BacktestConfig::default();
+                if let Some(balance) = body.get("initial_balance").and_then(|b| b.as_f64()) {
+                    config.initial_balance = balance;
+                }
+                // Same sim_seed + series = same report (the seed used is always returned)
+                config.sim_seed = body.get("sim_seed").and_then(|seed| seed.as_u64());
+                if let Some(bps) = body.get("random_slippage_bps").and_then(|b| b.as_f64()) {
+                    config.random_slippage_bps = bps.max(0.0);
+                }
+                let mut backtester = crate::backtesting::Backtester::new(config).with_liquidity(
+                    body.get("liquidity_usd").and_then(|l| l.as_f64())
+                        .unwrap_or(crate::backtesting::DEFAULT_BACKTEST_LIQUIDITY_USD)
+                );
+                if let Some(memecoin_filter) = &memecoin_filter {
+                    backtester = backtester.with_memecoin_filter(memecoin_filter.get().await);
+                }
+            
+                match backtester.run(&provider_type, &series) {
+                    Ok(report) => Ok(warp::reply::json(&ApiResponse::new(report, "Backtest complete"))),
+                    Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({"error": e}),
+                        "Backtest failed"
+                    ))),
+                }
+            }
+        });
+    
+    // Strategy comparison endpoint
+    // POST /backtest/compare - replay the same candles through several strategy variants side by side
+    // Body: {"series": {...}, "strategies": [{"name": "tp5", "provider_type": "MemecoinMonitor", "target_pct": 5.0}, ...],
+    //        "initial_balance": 10000.0, "sim_seed": 42, "random_slippage_bps": 20.0}
+    let compare_memecoin_filter = memecoin_filter.clone();
+    let backtest_compare_route = warp::path!("backtest" / "compare")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and_then(move |body: serde_json::Value| {
+            let memecoin_filter = compare_memecoin_filter.clone();
+            async move {
+                let series = match serde_json::from_value::<crate::historical_data::HistoricalDataset>(
+                    body.get("series").cloned().unwrap_or_default()
+                ) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Invalid series: {}", e)}),
+                            "Comparison failed"
+                        )));
+                    }
+                };
+                let strategies = match serde_json::from_value::<Vec<crate::backtesting::BacktestStrategy>>(
+                    body.get("strategies").cloned().unwrap_or_default()
+                ) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Invalid strategies: {}", e)}),
+                            "Comparison failed"
+                        )));
+                    }
+                };
+            
+                let mut config = crate::backtesting::BacktestConfig::default();
+                if let Some(balance) = body.get("initial_balance").and_then(|b| b.as_f64()) {
+                    config.initial_balance = balance;
+                }
+                config.sim_seed = body.get("sim_seed").and_then(|seed| seed.as_u64());
+                if let Some(bps) = body.get("random_slippage_bps").and_then(|b| b.as_f64()) {
+                    config.random_slippage_bps = bps.max(0.0);
+                }
+                let mut backtester = crate::backtesting::Backtester::new(config);
+                if let Some(memecoin_filter) = &memecoin_filter {
+                    backtester = backtester.with_memecoin_filter(memecoin_filter.get().await);
+                }
+            
+                match backtester.compare(&strategies, &series) {
+                    Ok(comparison) => {
+                        let table = comparison.to_table();
+                        Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"comparison": comparison, "table": table}),
+                            "Comparison complete"
+                        )))
+                    }
+                    Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({"error": e}),
+                        "Comparison failed"
+                    ))),
+                }
+            }
+        });
+    
+    // Manual exit
+    // POST /positions/{signal_id}/close - sell this position's fill at the last price and journal it as a manual close
+    // Other positions in the same symbol stay open
+    let close_position_route = {
+        let engine = engine.clone();
+        let enhanced_marketplace = enhanced_marketplace.clone();
+        
+        warp::path!("positions" / String / "close")
+            .and(warp::post())
+            .and_then(move |signal_id: String| {
+                let engine = engine.clone();
+                let enhanced_marketplace = enhanced_marketplace.clone();
+                async move {
+                    let enhanced = match enhanced_marketplace {
+                        Some(ref e) => e.clone(),
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Enhanced marketplace not available"}),
+                                "Position tracking service not initialized"
+                            )));
+                        }
+                    };
+                    
+                    let perf = match enhanced.get_signal_performance(&signal_id).await {
+                        Some(perf) if perf.filled_at.is_some() && perf.closed_at.is_none() => perf,
+                        _ => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("No open position for signal {}", signal_id)}),
+                                "Position not closed"
+                            )));
+                        }
+                    };
+                    
+                    let dry_run = perf.is_paper || crate::solana_integration::SolanaClient::dry_run_mode();
+                    let (price, size) = {
+                        let engine_lock = engine.lock().await;
+                        (engine_lock.last_price(&perf.symbol).unwrap_or(perf.current_price), engine_lock.fill_size(&signal_id))
+                    };
+                    
+                    match enhanced.close_signal_position(&signal_id, price, crate::enhanced_marketplace::ExitReason::Manual).await {
+                        Ok(closed) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({
+                                "signal_id": signal_id,
+                                "symbol": perf.symbol,
+                                "size": size,
+                                "price": price,
+                                "dry_run": dry_run,
+                                "position": closed,
+                            }),
+                            "Position closed"
+                        ))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Position not closed"
+                        ))),
+                    }
+                }
+            })
+    };
+    
+    // Trade journal
+    // GET /positions/closed?limit= - closed positions newest first with entry, exit, P&L, duration and exit reason
+    let closed_positions_route = {
+        let database = database.clone();
+        
+        warp::path!("positions" / "closed")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(move |params: HashMap<String, String>| {
+                let database = database.clone();
+                async move {
+                    let database = match database {
+                        Some(db) => db,
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "No database configured"}),
+                                "Closed positions unavailable"
+                            )));
+                        }
+                    };
+                    
+                    let limit = params.get("limit")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(50)
+                        .min(1000);
+                    let db = database.lock().await;
+                    let positions = db.get_closed_positions(limit);
+                    Ok(warp::reply::json(&ApiResponse::new(
+                        serde_json::json!({
+                            "positions": positions,
+                            "count": positions.len(),
+                        }),
+                        "Closed positions retrieved"
+                    )))
+                }
+            })
+    };
+    
+    // Paper-to-live promotion endpoint
+    // POST /positions/{signal_id}/promote - open a live position at the current price for a paper position
+    let promote_position_route = {
+        let engine = engine.clone();
         let trading_enabled = trading_enabled.clone();
-        warp::path("trading-toggle")
+        let enhanced_marketplace = enhanced_marketplace.clone();
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("positions" / String / "promote")
             .and(warp::post())
-            .and(warp::body::json())
-            .and_then(move |body: HashMap<String, serde_json::Value>| {
+            .and_then(move |signal_id: String| {
+                let engine = engine.clone();
                 let trading_enabled = trading_enabled.clone();
+                let enhanced_marketplace = enhanced_marketplace.clone();
+                let marketplace = marketplace.clone();
                 async move {
-                    let new_state = body.get("enabled")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
+                    let enhanced = match enhanced_marketplace {
+                        Some(ref e) => e.clone(),
+                        None => {
+                            return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": "Enhanced marketplace not available"}),
+                                "Position tracking service not initialized"
+                            )));
+                        }
+                    };
                     
-                    let mut enabled = trading_enabled.lock().await;
-                    *enabled = new_state;
+                    let paper = match enhanced.validate_promotion(&signal_id).await {
+                        Ok(p) => p,
+                        Err(e) => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e}),
+                                "Position cannot be promoted"
+                            )));
+                        }
+                    };
                     
-                    let status = if new_state { "enabled" } else { "disabled" };
-                    log::info!("🔄 Trading {} by user request", status);
+                    let original = match marketplace.get_signal(&signal_id).await {
+                        Some(s) => s,
+                        None => {
+                            return Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": format!("Signal {} not found", signal_id)}),
+                                "Position cannot be promoted"
+                            )));
+                        }
+                    };
                     
-                    let mut response = HashMap::new();
-                    response.insert("enabled".to_string(), safe_serialize(&*enabled, serde_json::Value::Bool(false), "enabled"));
-                    response.insert("message".to_string(), safe_serialize(&format!("Trading {}", status), serde_json::Value::String(String::new()), "message"));
+                    // The remaining opportunity must still be between the stop and the target
+                    let current_price = paper.current_price;
+                    if current_price <= paper.stop_price || current_price >= original.target_price {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({
+                                "error": "Current price is outside the stop/target range",
+                                "current_price": current_price,
+                                "stop_price": paper.stop_price,
+                                "target_price": original.target_price,
+                            }),
+                            "Position cannot be promoted"
+                        )));
+                    }
+                    
+                    let live_signal = super::signal_platform::TradingSignalData {
+                        id: format!("{}_live", signal_id),
+                        entry_price: current_price,
+                        stop_loss: paper.stop_price,
+                        timestamp: chrono::Utc::now().timestamp(),
+                        analysis: format!("Promoted from paper position {}", signal_id),
+                        status: super::signal_platform::SignalStatus::Executing,
+                        force_dry_run: false, // Promotion is an explicit request to go live
+                        ..original
+                    };
                     
-                    Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, &format!("Trading {}", status))))
+                    // dry_run = None forces the live path, including trading-enabled and risk manager checks
+                    let execution = crate::trading_engine::TradingEngine::execute_marketplace_signal_shared(
+                        &engine, &live_signal, Some(&trading_enabled), None).await;
+                    
+                    match execution {
+                        Ok(result) => match enhanced.link_promoted_position(&signal_id, &live_signal).await {
+                            Ok(live) => Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({
+                                    "paper_signal_id": signal_id,
+                                    "live_signal_id": live.signal_id,
+                                    "entry_price": live.entry_price,
+                                    "stop_price": live.stop_price,
+                                    "result": result,
+                                }),
+                                "Paper position promoted to live"
+                            ))),
+                            Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                                serde_json::json!({"error": e, "result": result}),
+                                "Live trade executed but position linking failed"
+                            ))),
+                        },
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Live execution rejected"
+                        ))),
+                    }
                 }
             })
     };
@@ -2257,10 +4799,7 @@ pub async fn start_server(
                     let engine_lock = engine.lock().await;
                     
                     // Check dry-run mode from environment
-                    let dry_run_mode = std::env::var("DRY_RUN_MODE")
-                        .unwrap_or_else(|_| "true".to_string())
-                        .parse::<bool>()
-                        .unwrap_or(true);
+                    let dry_run_mode = crate::solana_integration::SolanaClient::dry_run_mode();
                     
                     let mut response = HashMap::new();
                     response.insert("trading_enabled".to_string(), safe_serialize(&*enabled, serde_json::Value::Bool(false), "trading_enabled"));
@@ -2275,11 +4814,39 @@ pub async fn start_server(
                     response.insert("risk_manager_capital".to_string(), safe_serialize(&risk_lock.current_capital, serde_json::json!(0.0), "risk_manager_capital"));
                     response.insert("risk_manager_peak_capital".to_string(), safe_serialize(&risk_lock.peak_capital, serde_json::json!(0.0), "risk_manager_peak_capital"));
                     response.insert("drawdown_blocking_trades".to_string(), safe_serialize(&(current_drawdown >= max_drawdown), serde_json::Value::Bool(false), "drawdown_blocking_trades"));
+                    let now = chrono::Utc::now().timestamp();
+                    response.insert("drawdown_window".to_string(), serde_json::json!(risk_lock.drawdown_window.label()));
+                    response.insert("all_time_drawdown_pct".to_string(), safe_serialize(&(risk_lock.all_time_drawdown() * 100.0), serde_json::json!(0.0), "all_time_drawdown_pct"));
+                    response.insert("windowed_drawdown_pct".to_string(), safe_serialize(&risk_lock.windowed_drawdown(now).map(|drawdown| drawdown * 100.0), serde_json::Value::Null, "windowed_drawdown_pct"));
+                    response.insert("window_peak_capital".to_string(), safe_serialize(&risk_lock.window_peak_capital(now), serde_json::Value::Null, "window_peak_capital"));
+                    let open_positions = engine_lock.portfolio.values().filter(|size| **size > 0.0).count();
+                    let max_open_positions = risk_lock.max_open_positions;
+                    response.insert("open_positions".to_string(), safe_serialize(&open_positions, serde_json::json!(0), "open_positions"));
+                    response.insert("max_open_positions".to_string(), safe_serialize(&max_open_positions, serde_json::json!(0), "max_open_positions"));
+                    response.insert("max_positions_per_symbol".to_string(), safe_serialize(&risk_lock.max_positions_per_symbol, serde_json::json!(0), "max_positions_per_symbol"));
+                    response.insert("max_position_pct".to_string(), safe_serialize(&(risk_lock.max_position_fraction * 100.0), serde_json::json!(0.0), "max_position_pct"));
+                    response.insert("max_position_value".to_string(), safe_serialize(&risk_lock.max_position_value(risk_lock.current_capital), serde_json::json!(0.0), "max_position_value"));
+                    let concentration = risk_lock.concentration(&engine_lock.position_exposures(), &engine_lock.correlation_matrix());
+                    response.insert("concentration".to_string(), safe_serialize(&concentration, serde_json::Value::Null, "concentration"));
                     drop(risk_lock);
                     response.insert("pda_balance".to_string(), safe_serialize(&client_lock.get_trading_budget(), serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap_or(serde_json::Number::from(0))), "pda_balance"));
                     response.insert("wallet_balance".to_string(), safe_serialize(&client_lock.wallet_balance, serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap_or(serde_json::Number::from(0))), "wallet_balance"));
                     response.insert("current_balance".to_string(), safe_serialize(&engine_lock.current_balance, serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap_or(serde_json::Number::from(0))), "current_balance"));
                     response.insert("total_trades".to_string(), safe_serialize(&engine_lock.trade_history.len(), serde_json::Value::Number(serde_json::Number::from(0)), "total_trades"));
+                    response.insert("kill_switch_engaged".to_string(), safe_serialize(&engine_lock.halted, serde_json::Value::Bool(false), "kill_switch_engaged"));
+                    let drawdown_lockout = engine_lock.drawdown_lockout.clone();
+                    response.insert("drawdown_lockout".to_string(), safe_serialize(&drawdown_lockout, serde_json::Value::Null, "drawdown_lockout"));
+                    let funding_lockout = engine_lock.funding_lockout.clone();
+                    response.insert("funding_lockout".to_string(), safe_serialize(&funding_lockout, serde_json::Value::Null, "funding_lockout"));
+                    let balance_reconciliation = engine_lock.balance_reconciliation.clone();
+                    response.insert("balance_reconciliation".to_string(), safe_serialize(&balance_reconciliation, serde_json::Value::Null, "balance_reconciliation"));
+                    let fee_budget = match &engine_lock.fee_optimizer {
+                        Some(fee_optimizer) => Some(fee_optimizer.lock().await.budget_status(chrono::Utc::now().timestamp())),
+                        None => None,
+                    };
+                    let fee_budget_exhausted = fee_budget.as_ref().is_some_and(|status| status.exhausted);
+                    response.insert("fee_budget".to_string(), safe_serialize(&fee_budget, serde_json::Value::Null, "fee_budget"));
+                    response.insert("fee_budget_exhausted".to_string(), safe_serialize(&fee_budget_exhausted, serde_json::Value::Bool(false), "fee_budget_exhausted"));
                     
                     // Check if on mainnet
                     let is_mainnet = client_lock.rpc_url.as_ref()
@@ -2313,6 +4880,26 @@ pub async fn start_server(
                         warnings.push(format!("⚠️ Drawdown {:.2}% exceeds max {:.2}% - Trading blocked", 
                                             current_drawdown * 100.0, max_drawdown * 100.0));
                     }
+                    if engine_lock.halted {
+                        warnings.push("🛑 Kill switch engaged - all trading halted until re-enabled via POST /trading-toggle".to_string());
+                    }
+                    if let Some(reason) = drawdown_lockout.as_ref().and_then(|lockout| lockout.reenable_block(now)) {
+                        warnings.push(format!("📉 {}", reason));
+                    }
+                    if let Some(lockout) = &funding_lockout {
+                        warnings.push(format!("💸 {}", lockout.message()));
+                    }
+                    if concentration.over_limit {
+                        warnings.push(format!("⚠️ Correlated exposure {:.2}% in [{}] exceeds {:.0}% limit - correlated buys blocked",
+                                            concentration.exposure_pct, concentration.symbols.join(", "), concentration.limit_pct));
+                    }
+                    if fee_budget_exhausted {
+                        warnings.push("💸 Fee budget exhausted - trading paused until the window rolls over".to_string());
+                    }
+                    if open_positions >= max_open_positions {
+                        warnings.push(format!("⚠️ Open position limit reached ({}/{}) - New symbols blocked",
+                                            open_positions, max_open_positions));
+                    }
                     response.insert("warnings".to_string(), safe_serialize(&warnings, serde_json::Value::Array(vec![]), "warnings"));
                     
                     Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(response, "Safety status retrieved")))
@@ -2336,6 +4923,10 @@ pub async fn start_server(
                         
                         let mut agents = Vec::new();
                         for (agent_id, perf) in perf_map.iter() {
+                            let exploration = match coordinator_lock.get_agent(agent_id).await {
+                                Some(agent) => Some(agent.exploration().await),
+                                None => None,
+                            };
                             let mut agent_data = HashMap::new();
                             agent_data.insert("agent_id".to_string(), serde_json::to_value(agent_id).unwrap());
                             agent_data.insert("total_trades".to_string(), serde_json::to_value(perf.total_trades).unwrap());
@@ -2348,6 +4939,8 @@ pub async fn start_server(
                             agent_data.insert("learning_rate".to_string(), serde_json::to_value(perf.learning_rate).unwrap());
                             agent_data.insert("total_profit".to_string(), serde_json::to_value(perf.total_profit).unwrap());
                             agent_data.insert("total_loss".to_string(), serde_json::to_value(perf.total_loss).unwrap());
+                            agent_data.insert("epsilon".to_string(), serde_json::to_value(exploration.map(|e| e.epsilon)).unwrap());
+                            agent_data.insert("exploration".to_string(), serde_json::to_value(exploration).unwrap());
                             agents.push(agent_data);
                         }
                         
@@ -2359,11 +4952,62 @@ pub async fn start_server(
             })
     };
     
+    // POST /rl/agents/{id}/config {"epsilon": 0.05, "min_epsilon": 0.02, "epsilon_decay": 0.99}
+    // Also winning_/struggling_ min_epsilon and epsilon_decay for agents winning over 60% / under 40%
+    // Fields are optional and merged into the agent's current exploration; invalid values change nothing
+    let rl_agent_config_route = {
+        let rl_coordinator = rl_coordinator.clone();
+        
+        warp::path!("rl" / "agents" / String / "config")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(move |agent_id: String, body: serde_json::Value| {
+                let rl_coordinator = rl_coordinator.clone();
+                
+                async move {
+                    let agent = match rl_coordinator {
+                        Some(coordinator) => coordinator.lock().await.get_agent(&agent_id).await,
+                        None => None,
+                    };
+                    let Some(agent) = agent else {
+                        return Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": format!("Unknown RL agent: {}", agent_id)}),
+                            "Failed to update RL agent config"
+                        )));
+                    };
+                    let Some(changes) = body.as_object() else {
+                        return Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": "Body must be a JSON object"}),
+                            "Failed to update RL agent config"
+                        )));
+                    };
+                    
+                    let mut merged = serde_json::to_value(agent.exploration().await).unwrap_or_default();
+                    if let Some(current) = merged.as_object_mut() {
+                        current.extend(changes.clone());
+                    }
+                    let result = serde_json::from_value::<crate::reinforcement_learning::ExplorationConfig>(merged)
+                        .map_err(|e| format!("Invalid exploration config: {}", e));
+                    let result = match result {
+                        Ok(config) => agent.set_exploration(config).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(()) => Ok(warp::reply::json(&ApiResponse::new(agent.exploration().await, "RL agent exploration updated"))),
+                        Err(e) => Ok(warp::reply::json(&ApiResponse::new(
+                            serde_json::json!({"error": e}),
+                            "Failed to update RL agent config"
+                        ))),
+                    }
+                }
+            })
+    };
+    
     let trading_routes = trading_state_route
         .or(trading_toggle_route)
         .boxed();
     
-    let rl_routes = rl_agents_route.boxed();
+    let rl_routes = rl_agents_route.or(rl_agent_config_route).boxed();
     
     // Circuit Breaker status endpoint
     let circuit_breaker_status_route = {
@@ -2378,24 +5022,21 @@ pub async fn start_server(
                         let cb_lock = cb.lock().await;
                         let state = cb_lock.get_state().await;
                         
-                        // Get stats from circuit breaker (fields are private, use available methods)
-                        // For now, just return basic state info
-                        let failures = 0u32; // CircuitBreaker doesn't expose these directly
-                        let successes = 0u32;
-                        let total_calls = failures + successes;
-                        let success_rate = if total_calls > 0 {
-                            (successes as f64 / total_calls as f64) * 100.0
+                        let stats = cb_lock.get_stats().await;
+                        let success_rate = if stats.total_calls > 0 {
+                            (stats.successful_calls as f64 / stats.total_calls as f64) * 100.0
                         } else {
                             0.0
                         };
                         
                         let mut response = HashMap::new();
                         response.insert("state".to_string(), serde_json::to_value(format!("{:?}", state)).unwrap());
-                        response.insert("total_calls".to_string(), serde_json::to_value(total_calls).unwrap());
-                        response.insert("successful_calls".to_string(), serde_json::to_value(successes).unwrap());
-                        response.insert("failed_calls".to_string(), serde_json::to_value(failures).unwrap());
+                        response.insert("total_calls".to_string(), serde_json::to_value(stats.total_calls).unwrap());
+                        response.insert("successful_calls".to_string(), serde_json::to_value(stats.successful_calls).unwrap());
+                        response.insert("failed_calls".to_string(), serde_json::to_value(stats.failed_calls).unwrap());
                         response.insert("success_rate".to_string(), serde_json::to_value(success_rate).unwrap());
-                        response.insert("consecutive_failures".to_string(), serde_json::to_value(failures).unwrap());
+                        response.insert("consecutive_failures".to_string(), serde_json::to_value(stats.consecutive_failures).unwrap());
+                        response.insert("last_state_change".to_string(), serde_json::to_value(stats.last_state_change).unwrap());
                         
                         Ok::<_, warp::Rejection>(warp::reply::json(&ApiResponse::new(
                             response,
@@ -2413,11 +5054,101 @@ pub async fn start_server(
     
     let circuit_breaker_routes = circuit_breaker_status_route.boxed();
     
+    // Prometheus scrape endpoint
+    // GET /metrics - text exposition format; latency histograms come from crate::metrics
+    let metrics_route = {
+        let engine = engine.clone();
+        let risk_manager = risk_manager.clone();
+        let circuit_breaker = circuit_breaker.clone();
+        let rl_coordinator = rl_coordinator.clone();
+        let marketplace = signal_marketplace.clone();
+        
+        warp::path!("metrics")
+            .and(warp::get())
+            .and_then(move || {
+                let engine = engine.clone();
+                let risk_manager = risk_manager.clone();
+                let circuit_breaker = circuit_breaker.clone();
+                let rl_coordinator = rl_coordinator.clone();
+                let marketplace = marketplace.clone();
+                async move {
+                    use super::metrics::{Exposition, Metrics};
+                    let mut out = Exposition::new();
+                    
+                    let (trades_executed, open_positions, balance) = {
+                        let engine_lock = engine.lock().await;
+                        (
+                            engine_lock.trade_history.len(),
+                            engine_lock.portfolio.values().filter(|size| **size > 0.0).count(),
+                            engine_lock.current_balance,
+                        )
+                    };
+                    let win_rate_pct = risk_manager.lock().await.get_performance_metrics()
+                        .get("win_rate").copied().unwrap_or(0.0);
+                    out.counter("agentburn_trades_executed_total", "Trades executed by the engine", trades_executed as f64);
+                    out.gauge("agentburn_open_positions", "Open positions", open_positions as f64);
+                    out.gauge("agentburn_balance_sol", "Current trading balance (SOL)", balance);
+                    out.gauge("agentburn_win_ratio", "Share of closed trades with positive PnL", win_rate_pct / 100.0);
+                    
+                    if let Some(ref cb) = circuit_breaker {
+                        let state = cb.lock().await.get_state().await;
+                        let samples: Vec<(Vec<(&str, &str)>, f64)> = [
+                            ("closed", super::error_handling::CircuitState::Closed),
+                            ("half_open", super::error_handling::CircuitState::HalfOpen),
+                            ("open", super::error_handling::CircuitState::Open),
+                        ].into_iter()
+                            .map(|(label, candidate)| (vec![("state", label)], if candidate == state { 1.0 } else { 0.0 }))
+                            .collect();
+                        out.family("agentburn_circuit_breaker_state", "Circuit breaker state (1 = current)", "gauge", &samples);
+                    }
+                    
+                    // Per-provider counts of the signals currently held by the marketplace
+                    let mut signal_counts: std::collections::BTreeMap<(String, String), u64> = std::collections::BTreeMap::new();
+                    for signal in marketplace.signals.lock().await.values() {
+                        *signal_counts.entry((signal.provider.clone(), format!("{:?}", signal.status).to_lowercase())).or_default() += 1;
+                    }
+                    let samples: Vec<(Vec<(&str, &str)>, f64)> = signal_counts.iter()
+                        .map(|((provider, status), count)| (vec![("provider", provider.as_str()), ("status", status.as_str())], *count as f64))
+                        .collect();
+                    out.family("agentburn_provider_signals", "Marketplace signals per provider and status", "gauge", &samples);
+                    
+                    let mut providers = marketplace.get_all_providers().await;
+                    providers.sort_by(|a, b| a.id.cmp(&b.id));
+                    let samples: Vec<(Vec<(&str, &str)>, f64)> = providers.iter()
+                        .flat_map(|p| [
+                            (vec![("provider", p.id.as_str()), ("outcome", "success")], p.successful_signals as f64),
+                            (vec![("provider", p.id.as_str()), ("outcome", "failure")], p.total_signals.saturating_sub(p.successful_signals) as f64),
+                        ])
+                        .collect();
+                    out.family("agentburn_provider_signal_outcomes_total", "Evaluated signal outcomes per provider", "counter", &samples);
+                    
+                    if let Some(ref coordinator) = rl_coordinator {
+                        let performance = coordinator.lock().await.get_all_performance().await;
+                        let mut agents: Vec<(&String, f64)> = performance.iter().map(|(id, p)| (id, p.win_rate)).collect();
+                        agents.sort_by(|a, b| a.0.cmp(b.0));
+                        let samples: Vec<(Vec<(&str, &str)>, f64)> = agents.into_iter()
+                            .map(|(id, win_rate)| (vec![("provider", id.as_str())], win_rate))
+                            .collect();
+                        out.family("agentburn_rl_agent_win_ratio", "RL agent win rate per provider", "gauge", &samples);
+                    }
+                    
+                    Metrics::global().write_latencies(&mut out);
+                    
+                    Ok::<_, warp::Rejection>(warp::reply::with_header(
+                        out.finish(),
+                        "content-type",
+                        "text/plain; version=0.0.4; charset=utf-8",
+                    ))
+                }
+            })
+    };
+    
     let quant_routes = quant_analysis_route
         .or(quant_overview_route)
+        .or(quant_arbitrage_route)
         .boxed();
     
-    let routes = core_routes
+    let api_routes = core_routes
         .or(jito_routes)
         .or(oracle_routes)
         .or(feed_management_routes)
@@ -2431,10 +5162,52 @@ pub async fn start_server(
         .or(rl_routes)
         .or(circuit_breaker_routes)
         .or(safety_status_route)
+        .or(skipped_signals_route)
+        .or(promote_position_route)
+        .or(close_position_route)
+        .or(closed_positions_route)
+        .or(slippage_analytics_route)
+        .or(notification_test_route)
+        .or(confidence_threshold_routes)
+        .or(dry_run_provider_routes)
+        .or(provider_toggle_routes)
+        .or(interval_routes)
+        .or(token_filter_routes)
+        .or(memecoin_filter_routes)
+        .or(market_regime_routes)
+        .or(callback_routes)
+        .or(fee_status_route)
+        .or(order_routes)
+        .or(signal_execute_route)
+        .or(dca_route)
+        .or(simulate_execute_route)
+        .or(backtest_route)
+        .or(backtest_compare_route)
+        .or(emergency_flatten_route)
+        .or(aggregator_status_route)
+        .or(portfolio_history_route)
+        .or(executions_recent_route) // Before the {signature} route so "recent" isn't taken as a signature
+        .or(execution_by_signature_route)
+        .or(metrics_route);
+    
+    // AUTH: Rate limit and check API keys in front of every route (CORS still answers preflights)
+    let api_auth = crate::security::ApiAuth::from_env();
+    if !api_auth.has_admin_key() {
+        log::warn!("🔐 No API_ADMIN_KEYS configured - all mutating endpoints on :8080 will return 401");
+    }
+    let routes = crate::security::api_guard(api_auth, crate::security::api_rate_limiter())
+        .and(api_routes)
+        .recover(crate::security::handle_api_rejection)
         .with(cors)
         .with(warp::log("api"));
     
-    warp::serve(routes)
-        .run(([0, 0, 0, 0], 8080))
-        .await;
+    match shutdown {
+        Some(shutdown) => {
+            let (_, server) = warp::serve(routes)
+                .bind_with_graceful_shutdown(([0, 0, 0, 0], 8080), async move { shutdown.wait().await });
+            server.await;
+            log::info!("🌐 Warp server on :8080 stopped");
+        }
+        None => warp::serve(routes).run(([0, 0, 0, 0], 8080)).await,
+    }
 }
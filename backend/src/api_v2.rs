@@ -154,22 +154,32 @@ pub fn create_routes(
             .boxed()
     };
 
+    // AUTH: Same API keys and rate limit as the main API - /orchestrate and /execute run functions
+    let api_auth = crate::security::ApiAuth::from_env();
+    if !api_auth.has_admin_key() {
+        log::warn!("🔐 No API_ADMIN_KEYS configured - all mutating endpoints on :8081 will return 401");
+    }
+    
     // Combine all routes using boxed filters
-    health
-        .or(orchestrate)
-        .or(execute_function)
-        .or(list_functions)
+    crate::security::api_guard(api_auth, crate::security::api_rate_limiter())
+        .and(health
+            .or(orchestrate)
+            .or(execute_function)
+            .or(list_functions))
+        .recover(crate::security::handle_api_rejection)
         .with(cors)
         .with(warp::log("api_v2"))
 }
 
 /// Start the API v2 server
-pub async fn start_server(orchestrator: Arc<AIOrchestrator>) {
+/// Stops accepting connections once `shutdown` is triggered and returns after in-flight requests finish
+pub async fn start_server(orchestrator: Arc<AIOrchestrator>, shutdown: crate::shutdown::Shutdown) {
     log::info!("🌐 Starting API v2 Server with AI Orchestration on :8081");
     
     let routes = create_routes(orchestrator);
     
-    warp::serve(routes)
-        .run(([0, 0, 0, 0], 8081))
-        .await;
+    let (_, server) = warp::serve(routes)
+        .bind_with_graceful_shutdown(([0, 0, 0, 0], 8081), async move { shutdown.wait().await });
+    server.await;
+    log::info!("🌐 API v2 Server on :8081 stopped");
 }
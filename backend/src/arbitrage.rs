@@ -0,0 +1,254 @@
+//! DEX vs Jupiter Arbitrage Report
+//! Compares each DEX pool's price for a token against what Jupiter actually fills a round trip at, and
+//! reports spreads that pay for the pool's fee and impact. Both legs are priced from real quotes - a buy
+//! through Jupiter for the pool-rich direction, a sell through Jupiter for the pool-cheap one. Report only:
+//! the pool leg can't be routed by the executor, so nothing here publishes signals (GET /quant/arbitrage)
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::dex_aggregator::DexAggregator;
+use crate::dex_screener::{DexScreenerClient, TokenPair};
+use crate::jupiter_integration::JupiterClient;
+
+/// A round trip must clear its costs by this much to count as an arbitrage (percent)
+pub const DEFAULT_ARBITRAGE_MIN_PROFIT_PCT: f64 = 0.3;
+/// Swap fee on the DEX-pool leg (percent) - the Jupiter legs' fees are already netted out of their quotes
+pub const DEFAULT_ARBITRAGE_POOL_FEE_PCT: f64 = 0.25;
+/// USDC size each round trip is priced at
+pub const DEFAULT_ARBITRAGE_TRADE_USD: f64 = 100.0;
+/// Pools shallower than this are skipped - their quoted prices can't be filled
+pub const DEFAULT_ARBITRAGE_MIN_LIQUIDITY_USD: f64 = 50_000.0;
+/// Scanned when ARBITRAGE_SYMBOLS is unset; legs are priced in USDC, so USDC itself can't be scanned
+pub const DEFAULT_ARBITRAGE_SYMBOLS: &[&str] = &["USDT", "SOL"];
+
+/// Which tokens the arbitrage report compares and what a spread has to clear
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageConfig {
+    pub symbols: Vec<String>,
+    pub min_profit_pct: f64,
+    pub pool_fee_pct: f64,
+    pub trade_usd: f64,
+    pub min_liquidity_usd: f64,
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            symbols: DEFAULT_ARBITRAGE_SYMBOLS.iter().map(|s| s.to_string()).collect(),
+            min_profit_pct: DEFAULT_ARBITRAGE_MIN_PROFIT_PCT,
+            pool_fee_pct: DEFAULT_ARBITRAGE_POOL_FEE_PCT,
+            trade_usd: DEFAULT_ARBITRAGE_TRADE_USD,
+            min_liquidity_usd: DEFAULT_ARBITRAGE_MIN_LIQUIDITY_USD,
+        }
+    }
+}
+
+impl ArbitrageConfig {
+    /// ARBITRAGE_SYMBOLS (comma-separated), ARBITRAGE_MIN_PROFIT_PCT, ARBITRAGE_POOL_FEE_PCT,
+    /// ARBITRAGE_TRADE_USD, ARBITRAGE_MIN_LIQUIDITY_USD
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let number = |key: &str, default: f64| {
+            std::env::var(key).ok().and_then(|v| v.parse::<f64>().ok()).filter(|v| *v >= 0.0).unwrap_or(default)
+        };
+        let symbols = std::env::var("ARBITRAGE_SYMBOLS").ok()
+            .map(|value| value.split(',')
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty() && s != "USDC")
+                .collect::<Vec<_>>())
+            .filter(|symbols| !symbols.is_empty())
+            .unwrap_or(defaults.symbols);
+        Self {
+            symbols,
+            min_profit_pct: number("ARBITRAGE_MIN_PROFIT_PCT", defaults.min_profit_pct),
+            pool_fee_pct: number("ARBITRAGE_POOL_FEE_PCT", defaults.pool_fee_pct),
+            trade_usd: match number("ARBITRAGE_TRADE_USD", defaults.trade_usd) {
+                usd if usd > 0.0 => usd,
+                _ => defaults.trade_usd,
+            },
+            min_liquidity_usd: number("ARBITRAGE_MIN_LIQUIDITY_USD", defaults.min_liquidity_usd),
+        }
+    }
+}
+
+/// Jupiter's executable prices for one token, both directions, in USDC per token after fees and impact
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct JupiterRoundTrip {
+    /// Paid per token on a `trade_usd` USDC -> token swap
+    pub buy_usd: f64,
+    /// Received per token selling the tokens that buy returns
+    pub sell_usd: f64,
+}
+
+/// Which venue each leg of the round trip trades on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArbitrageRoute {
+    /// The pool pays more than Jupiter charges - buy through Jupiter, sell into the pool
+    JupiterToPool,
+    /// The pool sells for less than Jupiter pays - buy from the pool, sell through Jupiter
+    PoolToJupiter,
+}
+
+/// One DEX pool's price against the Jupiter round trip for the same token (all prices USD per token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageOpportunity {
+    pub symbol: String,
+    pub mint: String,
+    pub dex_id: String,
+    pub pair_address: String,
+    pub dex_price_usd: f64,
+    pub jupiter_buy_usd: f64,
+    pub jupiter_sell_usd: f64,
+    pub route: ArbitrageRoute,
+    /// Sell-leg price over buy-leg price in percent, for the better route
+    pub spread_pct: f64,
+    /// The pool leg's fee and estimated impact (percent) - the Jupiter leg's are in its quote
+    pub cost_pct: f64,
+    pub net_profit_pct: f64,
+    pub profitable: bool,
+    pub timestamp: i64,
+}
+
+/// Prices DEX pools against Jupiter round trips - shares the API's DEX Screener and Jupiter clients
+pub struct ArbitrageScanner {
+    config: ArbitrageConfig,
+    dex_screener: Arc<DexScreenerClient>,
+    jupiter: Arc<JupiterClient>,
+}
+
+impl ArbitrageScanner {
+    pub fn new(config: ArbitrageConfig, dex_screener: Arc<DexScreenerClient>, jupiter: Arc<JupiterClient>) -> Self {
+        Self { config, dex_screener, jupiter }
+    }
+
+    pub fn config(&self) -> &ArbitrageConfig {
+        &self.config
+    }
+
+    /// Compare one pool against the Jupiter round trip for `mint` (None if the pool is too shallow or unpriced)
+    pub fn evaluate(&self, symbol: &str, mint: &str, pair: &TokenPair, jupiter: JupiterRoundTrip, now: i64) -> Option<ArbitrageOpportunity> {
+        let liquidity_usd = pair.liquidity.usd.unwrap_or(0.0);
+        let dex_price = pair.price_usd.as_ref().and_then(|p| p.parse::<f64>().ok()).unwrap_or(0.0);
+        if pair.base_token.address != mint || liquidity_usd <= 0.0 || liquidity_usd < self.config.min_liquidity_usd
+            || dex_price <= 0.0 || jupiter.buy_usd <= 0.0 || jupiter.sell_usd <= 0.0 {
+            return None;
+        }
+        // Constant-product impact of trade_usd against the pool's half of its liquidity
+        let pool_impact_pct = self.config.trade_usd / (liquidity_usd / 2.0) * 100.0;
+        let cost_pct = self.config.pool_fee_pct + pool_impact_pct;
+        let into_pool_pct = (dex_price - jupiter.buy_usd) / jupiter.buy_usd * 100.0;
+        let out_of_pool_pct = (jupiter.sell_usd - dex_price) / dex_price * 100.0;
+        let (route, spread_pct) = if into_pool_pct >= out_of_pool_pct {
+            (ArbitrageRoute::JupiterToPool, into_pool_pct)
+        } else {
+            (ArbitrageRoute::PoolToJupiter, out_of_pool_pct)
+        };
+        let net_profit_pct = spread_pct - cost_pct;
+        Some(ArbitrageOpportunity {
+            symbol: symbol.to_string(),
+            mint: mint.to_string(),
+            dex_id: pair.dex_id.clone(),
+            pair_address: pair.pair_address.clone(),
+            dex_price_usd: dex_price,
+            jupiter_buy_usd: jupiter.buy_usd,
+            jupiter_sell_usd: jupiter.sell_usd,
+            route,
+            spread_pct,
+            cost_pct,
+            net_profit_pct,
+            profitable: net_profit_pct >= self.config.min_profit_pct,
+            timestamp: now,
+        })
+    }
+
+    /// Jupiter's round trip for `mint`: a `trade_usd` USDC buy, then a sell of the tokens it returns
+    async fn jupiter_round_trip(&self, mint: &str, decimals: u8) -> Result<JupiterRoundTrip, String> {
+        let usdc = crate::dex_aggregator::USDC_MINT;
+        let slippage_bps = crate::dex_aggregator::DEFAULT_ROUTE_SLIPPAGE_BPS;
+        let buy = self.jupiter.quote(usdc, mint, (self.config.trade_usd * 1_000_000.0) as u64, slippage_bps).await?;
+        let raw_tokens = buy.net_out_amount();
+        if raw_tokens == 0 {
+            return Err(format!("Jupiter quoted no output for {}", mint));
+        }
+        let sell = self.jupiter.quote(mint, usdc, raw_tokens, slippage_bps).await?;
+        let tokens = raw_tokens as f64 / 10f64.powi(decimals as i32);
+        Ok(JupiterRoundTrip {
+            buy_usd: self.config.trade_usd / tokens,
+            sell_usd: sell.net_out_amount() as f64 / 1_000_000.0 / tokens,
+        })
+    }
+
+    /// Most profitable round trip for `symbol` across its Solana pools, profitable or not
+    pub async fn scan_symbol(&self, symbol: &str) -> Result<Option<ArbitrageOpportunity>, String> {
+        let (mint, decimals) = crate::dex_aggregator::resolve_token(symbol)
+            .ok_or_else(|| format!("No mint known for {}", symbol))?;
+        let jupiter = self.jupiter_round_trip(&mint, decimals).await?;
+        let pairs = self.dex_screener.get_token_pairs(&mint).await.map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        Ok(pairs.iter()
+            .filter(|pair| pair.chain_id == "solana")
+            .filter_map(|pair| self.evaluate(symbol, &mint, pair, jupiter, now))
+            .max_by(|a, b| a.net_profit_pct.total_cmp(&b.net_profit_pct)))
+    }
+
+    /// Best opportunity per symbol, most profitable first; symbols that fail to price are logged and skipped
+    pub async fn scan(&self, symbols: &[String]) -> Vec<ArbitrageOpportunity> {
+        let mut opportunities = Vec::new();
+        for symbol in symbols {
+            match self.scan_symbol(symbol).await {
+                Ok(Some(opportunity)) => opportunities.push(opportunity),
+                Ok(None) => log::debug!("No pool deep enough to compare {} against Jupiter", symbol),
+                Err(e) => log::debug!("Arbitrage scan skipped {}: {}", symbol, e),
+            }
+        }
+        opportunities.sort_by(|a, b| b.net_profit_pct.total_cmp(&a.net_profit_pct));
+        opportunities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrage_spread_net_of_costs() {
+        let scanner = ArbitrageScanner::new(
+            ArbitrageConfig::default(),
+            Arc::new(DexScreenerClient::new()),
+            Arc::new(JupiterClient::new()),
+        );
+        let usdt = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+        let pool = |price: &str, liquidity_usd: f64| -> TokenPair {
+            serde_json::from_value(serde_json::json!({
+                "chainId": "solana", "dexId": "orca", "url": "", "pairAddress": "pool1",
+                "baseToken": { "address": usdt, "name": "Tether", "symbol": "USDT" },
+                "quoteToken": { "address": "USDC", "name": "USD Coin", "symbol": "USDC" },
+                "priceNative": price, "priceUsd": price,
+                "volume": { "h24": 0.0, "h6": 0.0, "h1": 0.0, "m5": 0.0 },
+                "liquidity": { "usd": liquidity_usd, "base": 0.0, "quote": 0.0 },
+                "fdv": null, "priceChange": {},
+            })).unwrap()
+        };
+        let jupiter = JupiterRoundTrip { buy_usd: 1.0, sell_usd: 0.999 };
+
+        // 1% rich against the Jupiter buy, less the 0.25% fee + 0.02% pool impact ($100 into $1M)
+        let rich = scanner.evaluate("USDT", usdt, &pool("1.01", 1_000_000.0), jupiter, 0).unwrap();
+        assert_eq!(rich.route, ArbitrageRoute::JupiterToPool);
+        assert!((rich.spread_pct - 1.0).abs() < 1e-9);
+        assert!((rich.net_profit_pct - 0.73).abs() < 1e-9);
+        assert!(rich.profitable);
+
+        // A cheap pool is priced against what Jupiter pays on the sell, not what it charges on the buy
+        let cheap = scanner.evaluate("USDT", usdt, &pool("0.98", 1_000_000.0), jupiter, 0).unwrap();
+        assert_eq!(cheap.route, ArbitrageRoute::PoolToJupiter);
+        assert!((cheap.spread_pct - (0.999 - 0.98) / 0.98 * 100.0).abs() < 1e-9);
+        assert!(cheap.profitable);
+
+        // A pool inside Jupiter's own round-trip spread doesn't pay, and shallow pools aren't compared at all
+        assert!(!scanner.evaluate("USDT", usdt, &pool("0.9995", 1_000_000.0), jupiter, 0).unwrap().profitable);
+        assert!(scanner.evaluate("USDT", usdt, &pool("1.05", 10_000.0), jupiter, 0).is_none());
+        assert!(scanner.evaluate("USDT", "other_mint", &pool("1.05", 1_000_000.0), jupiter, 0).is_none());
+    }
+}
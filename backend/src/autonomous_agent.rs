@@ -283,6 +283,7 @@ impl AutonomousAgent {
             stop_loss: price * 0.95,
             take_profit: price * 1.10,
             timestamp: Utc::now().timestamp(),
+            ..Default::default()
         };
         
         // Use trading_engine.execute_trade to handle the actual trade execution
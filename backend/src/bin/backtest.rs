@@ -36,6 +36,11 @@ async fn main() {
         max_drawdown: 0.20,
         commission_rate: 0.001, // 0.1%
         slippage: 0.0005, // 0.05%
+        random_slippage_bps: 0.0,
+        // Same seed = same generated data and fills
+        sim_seed: Some(args.get(3)
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or_else(rand::random)),
         min_confidence: 0.6,
         max_position_size_pct: 0.1,
     };
@@ -46,6 +51,7 @@ async fn main() {
     println!("   Max Drawdown: {:.1}%", config.max_drawdown * 100.0);
     println!("   Commission: {:.3}%", config.commission_rate * 100.0);
     println!("   Slippage: {:.3}%", config.slippage * 100.0);
+    println!("   Sim Seed: {}", config.sim_seed.unwrap_or_default());
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
     // Generate sample historical data
@@ -55,6 +61,7 @@ async fn main() {
         config.start_date,
         config.end_date,
         100.0, // Initial price
+        config.sim_seed.unwrap_or_default(),
     );
     
     println!("   Generated {} data points", historical_data.len());
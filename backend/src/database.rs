@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::collections::HashMap;
 
+use crate::signal_callbacks::CallbackEvent;
+use crate::signal_platform::{PurchaseRecord, SignalFilter, TradingSignalData};
+
 /// Trade record for database storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeRecord {
@@ -24,11 +27,18 @@ pub struct PortfolioSnapshot {
     pub timestamp: i64,
     pub total_value: f64,
     pub cash_balance: f64,
-    pub positions: HashMap<String, f64>,
+    pub positions: HashMap<String, f64>, // symbol -> size
+    #[serde(default)]
+    pub marks: HashMap<String, f64>, // symbol -> mark price used to value the position
     pub daily_pnl: f64,
     pub total_pnl: f64,
+    #[serde(default)]
+    pub dry_run: bool, // Paper and real equity curves are kept apart
 }
 
+/// Default spacing between portfolio snapshots
+pub const DEFAULT_SNAPSHOT_INTERVAL_SECS: i64 = 300;
+
 /// Performance metrics for analytics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceRecord {
@@ -42,36 +52,137 @@ pub struct PerformanceRecord {
     pub losing_trades: i32,
 }
 
+/// On-chain lifecycle of a submitted swap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    Submitted, // Sent, not yet seen at confirmed commitment
+    Confirmed, // Voted on by a supermajority - very unlikely to roll back
+    Finalized, // Rooted - final
+    Failed,    // Landed with an error, or its blockhash expired before it landed
+}
+
+impl ExecutionStatus {
+    /// Still waiting on the chain
+    pub fn is_pending(&self) -> bool {
+        matches!(self, ExecutionStatus::Submitted | ExecutionStatus::Confirmed)
+    }
+}
+
+/// Audit-trail entry for one submitted swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub signature: String,
+    pub signal_id: String,
+    pub symbol: String,
+    pub action: String, // "BUY" or "SELL"
+    pub size: f64,
+    pub price: f64,
+    pub submitted_at: i64,
+    pub confirmation_slot: Option<u64>,
+    pub status: ExecutionStatus,
+    pub error: Option<String>,
+    pub updated_at: i64,
+    /// Strategy wallet that sent the swap (None = the main wallet)
+    #[serde(default)]
+    pub wallet: Option<String>,
+    /// Tokens the confirmed transaction actually moved - set once the fill is reconciled
+    #[serde(default)]
+    pub filled_size: Option<f64>,
+    /// Average price of the confirmed fill, in the quote token
+    #[serde(default)]
+    pub fill_price: Option<f64>,
+    /// Route the swap went out on, e.g. "raydium" or "jupiter_jito_bundle" (None for older records)
+    #[serde(default)]
+    pub venue: Option<String>,
+}
+
+/// Trade journal entry for one closed position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedPositionRecord {
+    pub signal_id: String,
+    pub provider_id: String,
+    pub symbol: String,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    /// Size-weighted across partial closes
+    pub pnl_pct: f64,
+    pub opened_at: Option<i64>,
+    pub closed_at: i64,
+    pub duration_secs: Option<i64>,
+    pub exit_reason: crate::enhanced_marketplace::ExitReason,
+    pub is_paper: bool,
+    /// SOL/USDC the exit swap realized (None when nothing was swapped back)
+    #[serde(default)]
+    pub exit_proceeds: Option<crate::trading_engine::ExitProceeds>,
+}
+
 /// In-memory database (for simplicity - can be replaced with SQL)
+/// Persisted to a JSON file unless created with `new_in_memory`
 /// CRASH PROTECTION: Added resource limits to prevent memory exhaustion
+#[derive(Debug)]
 pub struct Database {
     trades: Vec<TradeRecord>,
     snapshots: Vec<PortfolioSnapshot>,
     performance: Vec<PerformanceRecord>,
-    data_file: String,
+    trailing_peaks: HashMap<String, f64>, // signal_id -> peak price for open trailing-stop positions
+    executions: Vec<ExecutionRecord>,
+    signals: Vec<TradingSignalData>, // Marketplace signals, kept after the marketplace drops them
+    closed_positions: Vec<ClosedPositionRecord>,
+    purchases: Vec<PurchaseRecord>, // Paid signal purchases behind the marketplace revenue report
+    callback_events: Vec<CallbackEvent>, // Lifecycle callbacks not yet acknowledged by the receiver
+    data_file: Option<String>, // None = in-memory only, never touches disk
     max_trades: usize,      // Limit to prevent memory exhaustion
     max_snapshots: usize,   // Limit to prevent memory exhaustion
     max_performance: usize, // Limit to prevent memory exhaustion
+    max_executions: usize,  // Limit to prevent memory exhaustion
+    max_signals: usize,     // Limit to prevent memory exhaustion
+    max_closed_positions: usize, // Limit to prevent memory exhaustion
+    max_purchases: usize,   // Limit to prevent memory exhaustion
+    max_callback_events: usize, // Limit to prevent memory exhaustion
 }
 
 impl Database {
     pub fn new(data_file: &str) -> Self {
-        let mut db = Self {
+        let mut db = Self::with_data_file(Some(data_file.to_string()));
+
+        // Load existing data if available
+        if let Err(e) = db.load_from_file() {
+            log::warn!("Could not load database: {}. Starting fresh.", e);
+        }
+
+        db
+    }
+
+    /// Same interface, nothing read from or written to disk - each instance starts empty (tests)
+    pub fn new_in_memory() -> Self {
+        Self::with_data_file(None)
+    }
+
+    fn with_data_file(data_file: Option<String>) -> Self {
+        Self {
             trades: Vec::new(),
             snapshots: Vec::new(),
             performance: Vec::new(),
-            data_file: data_file.to_string(),
+            trailing_peaks: HashMap::new(),
+            executions: Vec::new(),
+            signals: Vec::new(),
+            closed_positions: Vec::new(),
+            purchases: Vec::new(),
+            callback_events: Vec::new(),
+            data_file,
             max_trades: 10000,      // Limit to 10k trades in memory
             max_snapshots: 1000,    // Limit to 1k snapshots in memory
             max_performance: 1000,  // Limit to 1k performance records in memory
-        };
-
-        // Load existing data if available
-        if let Err(e) = db.load_from_file() {
-            log::warn!("Could not load database: {}. Starting fresh.", e);
+            max_executions: 1000,   // Limit to 1k execution records in memory
+            max_signals: 10000,     // Limit to 10k signals in memory
+            max_closed_positions: 10000, // Limit to 10k closed positions in memory
+            max_purchases: 10000,   // Limit to 10k purchases in memory
+            max_callback_events: 10000, // Limit to 10k undelivered callbacks in memory
         }
+    }
 
-        db
+    pub fn is_in_memory(&self) -> bool {
+        self.data_file.is_none()
     }
 
     /// Insert a new trade record with resource limit protection
@@ -135,6 +246,28 @@ impl Database {
         Ok(())
     }
 
+    /// Record a point on the equity curve
+    /// daily_pnl is measured against the oldest same-mode snapshot from the last 24 hours
+    pub fn record_portfolio_snapshot(&mut self, mut snapshot: PortfolioSnapshot) -> Result<(), String> {
+        let day_ago = snapshot.timestamp - 86_400;
+        snapshot.daily_pnl = self.snapshots.iter()
+            .find(|s| s.dry_run == snapshot.dry_run && s.timestamp >= day_ago)
+            .map(|s| snapshot.total_value - s.total_value)
+            .unwrap_or(0.0);
+        self.insert_snapshot(snapshot)
+    }
+
+    /// Snapshots between `from` and `to` (inclusive), optionally only paper or only real
+    pub fn get_portfolio_history(&self, from: Option<i64>, to: Option<i64>, dry_run: Option<bool>) -> Vec<PortfolioSnapshot> {
+        self.snapshots
+            .iter()
+            .filter(|s| from.is_none_or(|from| s.timestamp >= from))
+            .filter(|s| to.is_none_or(|to| s.timestamp <= to))
+            .filter(|s| dry_run.is_none_or(|dry_run| s.dry_run == dry_run))
+            .cloned()
+            .collect()
+    }
+
     /// Get all snapshots
     pub fn get_all_snapshots(&self) -> &[PortfolioSnapshot] {
         &self.snapshots
@@ -168,6 +301,225 @@ impl Database {
         &self.performance
     }
 
+    /// Persist the running peak price of a trailing-stop position
+    pub fn upsert_trailing_peak(&mut self, signal_id: &str, peak_price: f64) -> Result<(), String> {
+        self.trailing_peaks.insert(signal_id.to_string(), peak_price);
+        self.save_to_file()
+    }
+
+    /// Peak price recorded for a trailing-stop position (survives restarts)
+    pub fn get_trailing_peak(&self, signal_id: &str) -> Option<f64> {
+        self.trailing_peaks.get(signal_id).copied()
+    }
+
+    /// Forget the peak once the position is closed
+    pub fn remove_trailing_peak(&mut self, signal_id: &str) -> Result<(), String> {
+        if self.trailing_peaks.remove(signal_id).is_some() {
+            self.save_to_file()?;
+        }
+        Ok(())
+    }
+
+    /// Start the audit trail for a submitted swap
+    pub fn insert_execution(&mut self, execution: ExecutionRecord) -> Result<(), String> {
+        // RESOURCE LIMIT: Remove oldest execution records if we exceed limit
+        if self.executions.len() >= self.max_executions {
+            let remove_count = self.executions.len() - self.max_executions + 1;
+            self.executions.drain(0..remove_count);
+            log::debug!("🧹 Removed {} old execution records (limit: {})", remove_count, self.max_executions);
+        }
+        
+        self.executions.push(execution);
+        self.save_to_file()?;
+        Ok(())
+    }
+
+    /// Record what the chain says about a submitted swap
+    pub fn update_execution(&mut self, signature: &str, status: ExecutionStatus, confirmation_slot: Option<u64>,
+                            error: Option<String>, now: i64) -> Result<(), String> {
+        let execution = self.executions.iter_mut()
+            .rev()
+            .find(|e| e.signature == signature)
+            .ok_or_else(|| format!("No execution recorded for {}", signature))?;
+        execution.status = status;
+        execution.confirmation_slot = confirmation_slot.or(execution.confirmation_slot);
+        execution.error = error;
+        execution.updated_at = now;
+        self.save_to_file()
+    }
+
+    /// Record the size and average price a confirmed swap actually filled at
+    pub fn record_fill(&mut self, signature: &str, filled_size: f64, fill_price: f64, now: i64) -> Result<(), String> {
+        let execution = self.executions.iter_mut()
+            .rev()
+            .find(|e| e.signature == signature)
+            .ok_or_else(|| format!("No execution recorded for {}", signature))?;
+        execution.filled_size = Some(filled_size);
+        execution.fill_price = Some(fill_price);
+        execution.updated_at = now;
+        self.save_to_file()
+    }
+
+    pub fn get_execution(&self, signature: &str) -> Option<&ExecutionRecord> {
+        self.executions.iter().rev().find(|e| e.signature == signature)
+    }
+
+    /// Get recent executions (last N)
+    pub fn get_recent_executions(&self, count: usize) -> Vec<&ExecutionRecord> {
+        self.executions
+            .iter()
+            .rev()
+            .take(count)
+            .collect()
+    }
+
+    /// Executions the chain hasn't settled yet
+    pub fn get_pending_executions(&self) -> Vec<ExecutionRecord> {
+        self.executions
+            .iter()
+            .filter(|e| e.status.is_pending())
+            .cloned()
+            .collect()
+    }
+
+    /// Latest swap for a signal that may have filled (anything but Failed)
+    pub fn get_live_execution_for_signal(&self, signal_id: &str) -> Option<&ExecutionRecord> {
+        self.executions
+            .iter()
+            .rev()
+            .find(|e| e.signal_id == signal_id && e.status != ExecutionStatus::Failed)
+    }
+
+    /// Insert or refresh marketplace signals (matched by id) so their final status outlives the marketplace
+    pub fn upsert_signals(&mut self, signals: &[TradingSignalData]) -> Result<(), String> {
+        for signal in signals {
+            match self.signals.iter_mut().rev().find(|s| s.id == signal.id) {
+                Some(stored) => *stored = signal.clone(),
+                None => {
+                    // RESOURCE LIMIT: Remove oldest signals if we exceed limit
+                    if self.signals.len() >= self.max_signals {
+                        let remove_count = self.signals.len() - self.max_signals + 1;
+                        self.signals.drain(0..remove_count);
+                        log::debug!("🧹 Removed {} old signals (limit: {})", remove_count, self.max_signals);
+                    }
+                    self.signals.push(signal.clone());
+                }
+            }
+        }
+        self.save_to_file()
+    }
+
+    /// Persisted signals matching `filter`, in insertion order
+    pub fn query_signals(&self, filter: &SignalFilter) -> Vec<TradingSignalData> {
+        self.signals
+            .iter()
+            .filter(|s| filter.matches(s))
+            .cloned()
+            .collect()
+    }
+
+    /// Journal a closed position
+    pub fn insert_closed_position(&mut self, record: ClosedPositionRecord) -> Result<(), String> {
+        // RESOURCE LIMIT: Remove oldest closed positions if we exceed limit
+        if self.closed_positions.len() >= self.max_closed_positions {
+            let remove_count = self.closed_positions.len() - self.max_closed_positions + 1;
+            self.closed_positions.drain(0..remove_count);
+            log::debug!("🧹 Removed {} old closed positions (limit: {})", remove_count, self.max_closed_positions);
+        }
+        
+        self.closed_positions.push(record);
+        self.save_to_file()
+    }
+
+    /// Most recently closed positions (last N), newest first
+    pub fn get_closed_positions(&self, count: usize) -> Vec<&ClosedPositionRecord> {
+        self.closed_positions
+            .iter()
+            .rev()
+            .take(count)
+            .collect()
+    }
+
+    /// Record paid signal purchases in one save
+    pub fn insert_purchases(&mut self, purchases: &[PurchaseRecord]) -> Result<(), String> {
+        self.purchases.extend_from_slice(purchases);
+        // RESOURCE LIMIT: Remove oldest purchases if we exceed limit
+        if self.purchases.len() > self.max_purchases {
+            let remove_count = self.purchases.len() - self.max_purchases;
+            self.purchases.drain(0..remove_count);
+            log::debug!("🧹 Removed {} old purchases (limit: {})", remove_count, self.max_purchases);
+        }
+        
+        self.save_to_file()
+    }
+
+    /// Every recorded signal purchase, oldest first
+    pub fn get_purchases(&self) -> &[PurchaseRecord] {
+        &self.purchases
+    }
+
+    /// Queue a lifecycle callback - it stays here until delivered, so it survives restarts
+    pub fn enqueue_callback(&mut self, callback: CallbackEvent) -> Result<(), String> {
+        // RESOURCE LIMIT: Only dead callbacks make room - undelivered ones stay queued (and on disk)
+        // past the limit until the receiver takes them
+        if self.callback_events.len() >= self.max_callback_events {
+            match self.callback_events.iter().position(|c| c.dead) {
+                Some(position) => {
+                    let dropped = self.callback_events.remove(position);
+                    log::warn!("🧹 Callback queue full ({}) - dropped dead {:?} event {} for {}",
+                              self.max_callback_events, dropped.event, dropped.id, dropped.signal_id);
+                }
+                None => log::warn!("⚠️ Callback queue over its limit ({}) with nothing dead to drop - is the receiver down?",
+                                   self.max_callback_events),
+            }
+        }
+
+        self.callback_events.push(callback);
+        self.save_to_file()
+    }
+
+    /// Callbacks ready to (re)send, oldest first
+    pub fn due_callbacks(&self, now: i64, limit: usize) -> Vec<CallbackEvent> {
+        self.callback_events
+            .iter()
+            .filter(|c| c.is_due(now))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// One delivery pass's outcomes in a single save: forget the delivered callbacks and reschedule
+    /// (or park) the one that failed
+    pub fn record_callback_deliveries(&mut self, delivered: &[String], failed: Option<(String, String)>,
+                                      now: i64, max_attempts: u32) -> Result<(), String> {
+        self.callback_events.retain(|c| !delivered.contains(&c.id));
+        if let Some((id, error)) = failed {
+            if let Some(callback) = self.callback_events.iter_mut().find(|c| c.id == id) {
+                callback.record_failure(error, now, max_attempts);
+            }
+        }
+        self.save_to_file()
+    }
+
+    /// Re-arm every dead callback with a fresh set of attempts
+    pub fn retry_dead_callbacks(&mut self, now: i64) -> Result<usize, String> {
+        let mut revived = 0;
+        for callback in self.callback_events.iter_mut().filter(|c| c.dead) {
+            callback.dead = false;
+            callback.attempts = 0;
+            callback.next_attempt_at = now;
+            revived += 1;
+        }
+        if revived > 0 {
+            self.save_to_file()?;
+        }
+        Ok(revived)
+    }
+
+    pub fn get_callback_events(&self) -> &[CallbackEvent] {
+        &self.callback_events
+    }
+
     /// Calculate aggregate statistics
     pub fn get_statistics(&self) -> TradingStatistics {
         let total_trades = self.trades.len();
@@ -218,27 +570,56 @@ impl Database {
         }
     }
 
+    /// Write everything to disk now (e.g. on shutdown)
+    pub fn flush(&self) -> Result<(), String> {
+        self.save_to_file()
+    }
+
+    /// Prove the data file's directory still accepts writes without rewriting the whole database
+    pub fn check_writable(&self) -> Result<(), String> {
+        let Some(ref data_file) = self.data_file else {
+            return Ok(());
+        };
+        let probe = format!("{}.probe", data_file);
+        std::fs::write(&probe, b"ok")
+            .map_err(|e| format!("Database directory not writable: {}", e))?;
+        std::fs::remove_file(&probe)
+            .map_err(|e| format!("Could not remove database probe file: {}", e))
+    }
+
     /// Save database to file
     fn save_to_file(&self) -> Result<(), String> {
+        let Some(ref data_file) = self.data_file else {
+            return Ok(());
+        };
         let data = DatabaseData {
             trades: self.trades.clone(),
             snapshots: self.snapshots.clone(),
             performance: self.performance.clone(),
+            trailing_peaks: self.trailing_peaks.clone(),
+            executions: self.executions.clone(),
+            signals: self.signals.clone(),
+            closed_positions: self.closed_positions.clone(),
+            purchases: self.purchases.clone(),
+            callback_events: self.callback_events.clone(),
         };
 
         let json = serde_json::to_string_pretty(&data)
             .map_err(|e| format!("Failed to serialize database: {}", e))?;
 
-        std::fs::write(&self.data_file, json)
+        std::fs::write(data_file, json)
             .map_err(|e| format!("Failed to write database file: {}", e))?;
 
-        log::debug!("💾 Database saved to {}", self.data_file);
+        log::debug!("💾 Database saved to {}", data_file);
         Ok(())
     }
 
     /// Load database from file
     fn load_from_file(&mut self) -> Result<(), String> {
-        let path = Path::new(&self.data_file);
+        let Some(ref data_file) = self.data_file else {
+            return Ok(());
+        };
+        let path = Path::new(data_file);
         
         if !path.exists() {
             return Ok(()); // No file yet, start fresh
@@ -253,6 +634,12 @@ impl Database {
         self.trades = data.trades;
         self.snapshots = data.snapshots;
         self.performance = data.performance;
+        self.trailing_peaks = data.trailing_peaks;
+        self.executions = data.executions;
+        self.signals = data.signals;
+        self.closed_positions = data.closed_positions;
+        self.purchases = data.purchases;
+        self.callback_events = data.callback_events;
 
         log::info!("✅ Loaded database: {} trades, {} snapshots", 
                   self.trades.len(), self.snapshots.len());
@@ -264,6 +651,12 @@ impl Database {
         self.trades.clear();
         self.snapshots.clear();
         self.performance.clear();
+        self.trailing_peaks.clear();
+        self.executions.clear();
+        self.signals.clear();
+        self.closed_positions.clear();
+        self.purchases.clear();
+        self.callback_events.clear();
         self.save_to_file()?;
         log::warn!("🗑️ Database cleared!");
         Ok(())
@@ -297,11 +690,50 @@ impl Database {
     }
 }
 
+/// Write a value as JSON atomically: serialize to a temp file next to `path`, then rename over it
+/// A crash mid-write leaves the previous file intact instead of a truncated one
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write temp file {}: {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Read a JSON file written by `write_json_atomic`; Ok(None) if it doesn't exist yet
+pub fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DatabaseData {
     trades: Vec<TradeRecord>,
     snapshots: Vec<PortfolioSnapshot>,
     performance: Vec<PerformanceRecord>,
+    #[serde(default)]
+    trailing_peaks: HashMap<String, f64>,
+    #[serde(default)]
+    executions: Vec<ExecutionRecord>,
+    #[serde(default)]
+    signals: Vec<TradingSignalData>,
+    #[serde(default)]
+    closed_positions: Vec<ClosedPositionRecord>,
+    #[serde(default)]
+    purchases: Vec<PurchaseRecord>,
+    #[serde(default)]
+    callback_events: Vec<CallbackEvent>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -325,13 +757,14 @@ mod tests {
 
     #[test]
     fn test_database_creation() {
-        let db = Database::new("/tmp/test_db.json");
+        let db = Database::new_in_memory();
+        assert!(db.is_in_memory());
         assert_eq!(db.get_all_trades().len(), 0);
     }
 
     #[test]
     fn test_insert_and_retrieve_trade() {
-        let mut db = Database::new("/tmp/test_trade_db.json");
+        let mut db = Database::new_in_memory();
         
         let trade = TradeRecord {
             id: "test_1".to_string(),
@@ -351,13 +784,15 @@ mod tests {
         assert_eq!(db.get_all_trades().len(), 1);
         assert_eq!(db.get_all_trades()[0].symbol, "SOL/USDC");
 
-        // Cleanup
-        let _ = fs::remove_file("/tmp/test_trade_db.json");
+        // Nothing shared between in-memory instances, nothing on disk
+        assert!(db.flush().is_ok());
+        assert!(db.check_writable().is_ok());
+        assert!(Database::new_in_memory().get_all_trades().is_empty());
     }
 
     #[test]
     fn test_statistics_calculation() {
-        let mut db = Database::new("/tmp/test_stats_db.json");
+        let mut db = Database::new_in_memory();
         
         // Add winning trade
         db.insert_trade(TradeRecord {
@@ -394,8 +829,152 @@ mod tests {
         assert_eq!(stats.winning_trades, 1);
         assert_eq!(stats.losing_trades, 1);
         assert_eq!(stats.win_rate, 50.0);
+    }
+
+    #[test]
+    fn test_trailing_peak_persists_across_reload() {
+        let path = "/tmp/test_trailing_peak_db.json";
+        let _ = fs::remove_file(path);
+
+        let mut db = Database::new(path);
+        db.upsert_trailing_peak("sig_1", 123.45).unwrap();
+
+        let reloaded = Database::new(path);
+        assert_eq!(reloaded.get_trailing_peak("sig_1"), Some(123.45));
+
+        db.remove_trailing_peak("sig_1").unwrap();
+        assert_eq!(Database::new(path).get_trailing_peak("sig_1"), None);
+
+        // Cleanup
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_portfolio_history_filters_by_range_and_mode() {
+        let path = "/tmp/test_portfolio_history_db.json";
+        let _ = fs::remove_file(path);
+
+        let mut db = Database::new(path);
+        for (timestamp, total_value, dry_run) in [(1_000, 100.0, true), (2_000, 110.0, true), (2_500, 50.0, false), (3_000, 95.0, true)] {
+            db.record_portfolio_snapshot(PortfolioSnapshot {
+                timestamp,
+                total_value,
+                cash_balance: total_value,
+                positions: HashMap::new(),
+                marks: HashMap::new(),
+                daily_pnl: 0.0,
+                total_pnl: 0.0,
+                dry_run,
+            }).unwrap();
+        }
+
+        let paper = db.get_portfolio_history(None, None, Some(true));
+        assert_eq!(paper.len(), 3);
+        assert_eq!(paper[2].daily_pnl, -5.0); // vs the first paper snapshot, not the real one
+
+        let window = db.get_portfolio_history(Some(2_000), Some(2_500), None);
+        assert_eq!(window.len(), 2);
+        assert!(!db.get_portfolio_history(None, None, Some(false))[0].dry_run);
+
+        // Survives a reload
+        assert_eq!(Database::new(path).get_portfolio_history(None, None, None).len(), 4);
+
+        // Cleanup
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_execution_audit_trail() {
+        let path = "/tmp/test_execution_audit_db.json";
+        let _ = fs::remove_file(path);
+
+        let mut db = Database::new(path);
+        db.insert_execution(ExecutionRecord {
+            signature: "5sig".to_string(),
+            signal_id: "sig_1".to_string(),
+            symbol: "SOL/USDC".to_string(),
+            action: "BUY".to_string(),
+            size: 1.0,
+            price: 100.0,
+            submitted_at: 1_000,
+            confirmation_slot: None,
+            status: ExecutionStatus::Submitted,
+            error: None,
+            updated_at: 1_000,
+            wallet: None,
+            filled_size: None,
+            fill_price: None,
+            venue: None,
+        }).unwrap();
+        assert_eq!(db.get_pending_executions().len(), 1);
+
+        db.update_execution("5sig", ExecutionStatus::Confirmed, Some(42), None, 1_005).unwrap();
+        db.update_execution("5sig", ExecutionStatus::Finalized, None, None, 1_020).unwrap();
+        let execution = db.get_execution("5sig").unwrap();
+        assert_eq!(execution.status, ExecutionStatus::Finalized);
+        assert_eq!(execution.confirmation_slot, Some(42)); // Kept when a later poll has no slot
+        assert!(db.get_pending_executions().is_empty());
+        assert!(db.get_live_execution_for_signal("sig_1").is_some());
+        assert!(db.update_execution("unknown", ExecutionStatus::Failed, None, None, 1_030).is_err());
+
+        db.record_fill("5sig", 0.8, 101.0, 1_040).unwrap();
+        let execution = db.get_execution("5sig").unwrap();
+        assert_eq!((execution.filled_size, execution.fill_price), (Some(0.8), Some(101.0)));
+
+        // Survives a reload
+        let reloaded = Database::new(path);
+        assert_eq!(reloaded.get_recent_executions(10)[0].updated_at, 1_020);
+
+        // Cleanup
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_atomic_json_roundtrip() {
+        let path = Path::new("/tmp/test_atomic_json.json");
+        let _ = fs::remove_file(path);
+        assert_eq!(read_json::<Vec<u32>>(path).unwrap(), None);
+
+        write_json_atomic(path, &vec![1u32, 2, 3]).unwrap();
+        assert_eq!(read_json::<Vec<u32>>(path).unwrap(), Some(vec![1, 2, 3]));
+        assert!(!path.with_extension("tmp").exists());
+
+        // Cleanup
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_callback_queue_survives_reload() {
+        use crate::signal_callbacks::SignalLifecycleEvent;
+        let path = "/tmp/test_callback_queue_db.json";
+        let _ = fs::remove_file(path);
+
+        let mut db = Database::new(path);
+        let published = CallbackEvent::new(SignalLifecycleEvent::Published, "sig_1", serde_json::json!({}), 1_000);
+        let executed = CallbackEvent::new(SignalLifecycleEvent::Executed, "sig_1", serde_json::json!({}), 1_001);
+        db.enqueue_callback(published.clone()).unwrap();
+        db.enqueue_callback(executed.clone()).unwrap();
+        db.record_callback_deliveries(&[], Some((published.id.clone(), "HTTP 500".to_string())), 1_010, 1).unwrap();
+
+        // Undelivered events come back after a restart; the dead one waits for a manual retry
+        let mut reloaded = Database::new(path);
+        assert_eq!(reloaded.get_callback_events().len(), 2);
+        let due = reloaded.due_callbacks(1_010, 10);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, executed.id);
+
+        reloaded.record_callback_deliveries(std::slice::from_ref(&executed.id), None, 1_010, 1).unwrap();
+        assert_eq!(reloaded.retry_dead_callbacks(1_020).unwrap(), 1);
+        assert_eq!(Database::new(path).due_callbacks(1_020, 10)[0].id, published.id);
+
+        // A full queue of live events keeps growing rather than dropping one
+        let mut full = Database::new_in_memory();
+        full.max_callback_events = 1;
+        full.enqueue_callback(published.clone()).unwrap();
+        full.enqueue_callback(executed.clone()).unwrap();
+        assert_eq!(full.get_callback_events().len(), 2);
 
         // Cleanup
-        let _ = fs::remove_file("/tmp/test_stats_db.json");
+        let _ = fs::remove_file(path);
     }
 }
@@ -53,6 +53,31 @@ pub struct TradingDecision {
     pub take_profit: Option<f64>,
 }
 
+/// Verdict of a pre-execution review of a provider's signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalReview {
+    pub approve: bool,
+    pub adjusted_confidence: f64, // 0.0 - 1.0, replaces the provider's confidence
+    pub reasoning: String,
+}
+
+impl SignalReview {
+    /// Parse and validate the model's reply (tolerates a ```json fence around the object)
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let json = content.trim()
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim();
+        let review: SignalReview = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse signal review: {}. Response: {}", e, content))?;
+        if !(0.0..=1.0).contains(&review.adjusted_confidence) {
+            return Err(format!("Invalid adjusted confidence: {}", review.adjusted_confidence));
+        }
+        Ok(review)
+    }
+}
+
 pub struct DeepSeekClient {
     api_key: String,
     api_url: String,
@@ -70,6 +95,12 @@ impl DeepSeekClient {
         }
     }
 
+    /// Point at a different OpenAI-compatible endpoint
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+
     /// Analyze market data and generate trading decision
     pub async fn analyze_trade(
         &self,
@@ -292,6 +323,92 @@ Provide a brief risk assessment (LOW, MEDIUM, or HIGH) with reasoning in 1-2 sen
         
         Ok(deepseek_response.choices[0].message.content.clone())
     }
+
+    /// Second opinion on a provider's signal before funds are committed
+    /// `market_context` is a few lines of current data (consensus price, dispersion) for the model to weigh
+    pub async fn review_signal(
+        &self,
+        signal: &crate::signal_platform::TradingSignalData,
+        market_context: &str,
+    ) -> Result<SignalReview, Box<dyn Error>> {
+        let prompt = format!(
+            r#"Review this trading signal before it is executed with real funds.
+
+Signal:
+- Symbol: {}
+- Action: {:?}
+- Entry Price: ${:.8}
+- Target Price: ${:.8}
+- Stop Loss: ${:.8}
+- Provider Confidence: {:.2}
+- Timeframe: {}
+- Provider: {}
+- Data Sources: {}
+- Provider Analysis: {}
+
+Current Market Context:
+{}
+
+Task: Decide whether the trade is sound. Reject signals whose entry is stale against the current price,
+whose stop or target is unrealistic, or that look like chasing a pump. Respond with this JSON format:
+{{
+  "approve": true | false,
+  "adjusted_confidence": 0.0-1.0,
+  "reasoning": "Brief explanation"
+}}
+
+Respond ONLY with valid JSON, no additional text."#,
+            signal.symbol,
+            signal.action,
+            signal.entry_price,
+            signal.target_price,
+            signal.stop_loss,
+            signal.confidence,
+            signal.timeframe,
+            signal.provider,
+            signal.data_sources.join(", "),
+            signal.analysis,
+            market_context
+        );
+
+        let request = DeepSeekRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: "You are a skeptical cryptocurrency risk reviewer. Approve only trades you would fund. Respond in JSON only.".to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: prompt,
+                },
+            ],
+            temperature: 0.2,
+            max_tokens: 300,
+        };
+
+        let response = self
+            .client
+            .post(&self.api_url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("DeepSeek API error: {}", error_text).into());
+        }
+
+        let deepseek_response: DeepSeekResponse = response.json().await?;
+        let content = &deepseek_response.choices.first()
+            .ok_or("No choices returned from DeepSeek API")?
+            .message.content;
+        log::debug!("AI review of {}: {}", signal.id, content);
+
+        Ok(SignalReview::parse(content)?)
+    }
 }
 
 impl Default for DeepSeekClient {
@@ -327,4 +444,13 @@ mod tests {
         assert!(decision.confidence >= 0.0 && decision.confidence <= 1.0);
         assert!(decision.suggested_size >= 0.0 && decision.suggested_size <= 100.0);
     }
+
+    #[test]
+    fn test_signal_review_parsing() {
+        let review = SignalReview::parse("```json\n{\"approve\": false, \"adjusted_confidence\": 0.4, \"reasoning\": \"Chasing a 60% candle\"}\n```").unwrap();
+        assert!(!review.approve);
+        assert_eq!(review.adjusted_confidence, 0.4);
+        assert!(SignalReview::parse("{\"approve\": true, \"adjusted_confidence\": 1.5, \"reasoning\": \"\"}").is_err());
+        assert!(SignalReview::parse("Looks fine to me").is_err());
+    }
 }
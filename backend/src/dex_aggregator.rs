@@ -0,0 +1,721 @@
+//! DEX Aggregator Routing
+//! Quotes swaps across several aggregators (Jupiter, Raydium direct) and picks the best net output,
+//! then builds the swap on that same aggregator
+//! Each aggregator sits behind its own circuit breaker so a flaky one is skipped instead of failing trades
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error_handling::{CircuitBreaker, CircuitBreakerStats, AppError};
+use crate::http_client::SharedHttpClient;
+use crate::jupiter_integration::JupiterClient;
+
+pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Currency the treasury is held in - every buy spends it and every sell settles into it
+/// Provider prices are USD, so USDC keeps the cash leg free of SOL price swings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseCurrency {
+    #[default]
+    Sol,
+    Usdc,
+}
+
+impl BaseCurrency {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "sol" => Some(Self::Sol),
+            "usdc" => Some(Self::Usdc),
+            _ => None,
+        }
+    }
+
+    /// Load from BASE_CURRENCY (sol | usdc, default sol)
+    pub fn from_env() -> Self {
+        match std::env::var("BASE_CURRENCY") {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|| {
+                log::warn!("⚠️ Invalid BASE_CURRENCY '{}' - using SOL", value);
+                Self::Sol
+            }),
+            Err(_) => Self::Sol,
+        }
+    }
+
+    pub fn mint(&self) -> &'static str {
+        match self {
+            Self::Sol => SOL_MINT,
+            Self::Usdc => USDC_MINT,
+        }
+    }
+
+    pub fn decimals(&self) -> i32 {
+        match self {
+            Self::Sol => 9,
+            Self::Usdc => 6,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Sol => "SOL",
+            Self::Usdc => "USDC",
+        }
+    }
+}
+
+/// Default slippage for routed trades (0.5%)
+pub const DEFAULT_ROUTE_SLIPPAGE_BPS: u16 = 50;
+
+/// Consecutive failures before an aggregator is skipped, and how long it stays skipped
+const BREAKER_FAILURE_THRESHOLD: u32 = 3;
+const BREAKER_SUCCESS_THRESHOLD: u32 = 1;
+const BREAKER_TIMEOUT_SECS: u64 = 60;
+
+/// Known tokens: (symbol, mint, decimals)
+const KNOWN_TOKENS: &[(&str, &str, u8)] = &[
+    ("SOL", SOL_MINT, 9),
+    ("USDC", USDC_MINT, 6),
+    ("USDT", "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", 6),
+    ("BTC", "9n4nbM75f5Ui33ZbPYXn59EwSgE8CGsHtAeTH5YFeJ9E", 6),
+    ("ETH", "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs", 8),
+    ("RAY", "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R", 6),
+];
+
+/// Resolve a trading symbol ("RAY", "SOL/USD", "RAY (Jupiter)" or a raw mint) to (mint, decimals)
+/// Unknown raw mints are assumed to use 6 decimals (pump.fun standard)
+pub fn resolve_token(symbol: &str) -> Option<(String, u8)> {
+    let symbol = crate::symbol_registry::strip_venue(symbol);
+    let base = symbol.split('/').next().unwrap_or(symbol).trim();
+    if let Some((_, mint, decimals)) = KNOWN_TOKENS.iter().find(|(s, _, _)| s.eq_ignore_ascii_case(base)) {
+        return Some((mint.to_string(), *decimals));
+    }
+    solana_sdk::pubkey::Pubkey::from_str(base).ok().map(|_| (base.to_string(), 6))
+}
+
+/// Symbol of a known mint - raw mints have none
+pub fn symbol_for_mint(mint: &str) -> Option<&'static str> {
+    KNOWN_TOKENS.iter().find(|(_, known, _)| *known == mint).map(|(symbol, _, _)| *symbol)
+}
+
+/// (mint, decimals) of the token `token_mint` is priced and paid for in - the base currency, except
+/// that SOL always trades against USDC and, with a USDC base, USDC itself trades against SOL
+pub fn quote_token(token_mint: &str, base: BaseCurrency) -> (&'static str, i32) {
+    if token_mint == SOL_MINT {
+        (USDC_MINT, 6)
+    } else if token_mint == base.mint() {
+        (SOL_MINT, 9)
+    } else {
+        (base.mint(), base.decimals())
+    }
+}
+
+/// (input mint, output mint, raw input amount) for trading `size` of `symbol` at `price`
+/// Buys spend the quote token (see `quote_token`), sells spend the token
+pub fn trade_amounts(symbol: &str, size: f64, price: f64, is_buy: bool, base: BaseCurrency) -> Result<(String, String, u64), String> {
+    let (token_mint, decimals) = resolve_token(symbol)
+        .ok_or_else(|| format!("No mint known for {}", symbol))?;
+    let (quote_mint, quote_decimals) = quote_token(&token_mint, base);
+
+    let (input_mint, output_mint, amount) = if is_buy {
+        (quote_mint.to_string(), token_mint, (size * price * 10f64.powi(quote_decimals)) as u64)
+    } else {
+        (token_mint, quote_mint.to_string(), (size * 10f64.powi(decimals as i32)) as u64)
+    };
+    if amount == 0 {
+        return Err(format!("Trade of {} {} rounds to zero", size, symbol));
+    }
+    Ok((input_mint, output_mint, amount))
+}
+
+/// Quote a trade from a single aggregator, bypassing the router's circuit breakers
+/// Used for paper fills - a failing simulation must not trip breakers that guard real swaps
+pub async fn quote_trade(aggregator: &dyn DexAggregator, symbol: &str, size: f64, price: f64, is_buy: bool, base: BaseCurrency) -> Result<AggregatorQuote, String> {
+    let (input_mint, output_mint, amount) = trade_amounts(symbol, size, price, is_buy, base)?;
+    aggregator.quote(&input_mint, &output_mint, amount, DEFAULT_ROUTE_SLIPPAGE_BPS).await
+}
+
+/// Price per token a quote implies for `symbol`, in the quote token `trade_amounts` trades against
+pub fn quoted_price(symbol: &str, quote: &AggregatorQuote, is_buy: bool, base: BaseCurrency) -> Option<f64> {
+    let (token_mint, decimals) = resolve_token(symbol)?;
+    let (_, quote_decimals) = quote_token(&token_mint, base);
+    let (quote_amount, token_amount) = if is_buy {
+        (quote.in_amount, quote.out_amount)
+    } else {
+        (quote.out_amount, quote.in_amount)
+    };
+    if token_amount == 0 {
+        return None;
+    }
+    let quote_amount = quote_amount as f64 / 10f64.powi(quote_decimals);
+    let token_amount = token_amount as f64 / 10f64.powi(decimals as i32);
+    Some(quote_amount / token_amount)
+}
+
+/// Aggregator-neutral swap quote
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregatorQuote {
+    pub aggregator: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    /// Fees charged in the output token
+    pub fee_amount: u64,
+    pub price_impact_pct: f64,
+}
+
+impl AggregatorQuote {
+    /// Output after fees - what the trade actually receives
+    pub fn net_out_amount(&self) -> u64 {
+        self.out_amount.saturating_sub(self.fee_amount)
+    }
+}
+
+/// Anything that can quote a swap and build it
+#[async_trait]
+pub trait DexAggregator: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u16) -> Result<AggregatorQuote, String>;
+
+    /// Unsigned legacy swap transaction for `user_pubkey`, reverting on-chain below the quote minus `slippage_bps`
+    #[allow(clippy::too_many_arguments)]
+    async fn swap_transaction(
+        &self,
+        user_pubkey: &str,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<Transaction, String>;
+}
+
+#[async_trait]
+impl DexAggregator for JupiterClient {
+    fn name(&self) -> &str {
+        "jupiter"
+    }
+
+    async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u16) -> Result<AggregatorQuote, String> {
+        if let Some(replay_prices) = self.replay_prices() {
+            return Ok(AggregatorQuote {
+                aggregator: self.name().to_string(),
+                input_mint: input_mint.to_string(),
+                output_mint: output_mint.to_string(),
+                in_amount: amount,
+                out_amount: replay_prices.convert(input_mint, output_mint, amount)?,
+                fee_amount: 0,
+                price_impact_pct: 0.0,
+            });
+        }
+        
+        let quote = self.get_quote(input_mint, output_mint, amount, slippage_bps).await
+            .map_err(|e| e.to_string())?;
+
+        let out_amount = quote.out_amount.parse::<u64>()
+            .map_err(|e| format!("Invalid Jupiter out_amount '{}': {}", quote.out_amount, e))?;
+        let fee_amount = quote.route_plan.iter()
+            .filter(|step| step.swap_info.fee_mint == output_mint)
+            .filter_map(|step| step.swap_info.fee_amount.parse::<u64>().ok())
+            .sum();
+
+        Ok(AggregatorQuote {
+            aggregator: self.name().to_string(),
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            in_amount: quote.in_amount.parse().unwrap_or(amount),
+            out_amount,
+            fee_amount,
+            price_impact_pct: quote.price_impact_pct,
+        })
+    }
+
+    async fn swap_transaction(
+        &self,
+        user_pubkey: &str,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<Transaction, String> {
+        self.get_legacy_swap_transaction(user_pubkey, input_mint, output_mint, amount, slippage_bps, compute_unit_price, compute_unit_limit).await
+    }
+}
+
+/// Direct Raydium client (Raydium trade API - routes only through Raydium pools)
+pub struct RaydiumClient {
+    api_url: String,
+    client: Arc<reqwest::Client>,
+}
+
+impl RaydiumClient {
+    pub fn new() -> Self {
+        Self {
+            api_url: std::env::var("RAYDIUM_API_URL")
+                .unwrap_or_else(|_| "https://transaction-v1.raydium.io".to_string()),
+            client: SharedHttpClient::shared(),
+        }
+    }
+
+    /// Parse a `compute/swap-base-in` response body
+    fn parse_quote(body: &serde_json::Value, input_mint: &str, output_mint: &str, amount: u64) -> Result<AggregatorQuote, String> {
+        if !body.get("success").and_then(|s| s.as_bool()).unwrap_or(false) {
+            let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or("no route");
+            return Err(format!("Raydium quote failed: {}", msg));
+        }
+        let data = body.get("data").ok_or("Raydium response missing data")?;
+        let amount_field = |key: &str| -> Option<u64> {
+            data.get(key).and_then(|v| v.as_str().and_then(|s| s.parse().ok()).or_else(|| v.as_u64()))
+        };
+
+        let out_amount = amount_field("outputAmount").ok_or("Raydium response missing outputAmount")?;
+        let fee_amount = data.get("routePlan")
+            .and_then(|r| r.as_array())
+            .map(|steps| steps.iter()
+                .filter(|step| step.get("feeMint").and_then(|m| m.as_str()) == Some(output_mint))
+                .filter_map(|step| step.get("feeAmount")
+                    .and_then(|v| v.as_str().and_then(|s| s.parse::<u64>().ok()).or_else(|| v.as_u64())))
+                .sum())
+            .unwrap_or(0);
+
+        Ok(AggregatorQuote {
+            aggregator: "raydium".to_string(),
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            in_amount: amount_field("inputAmount").unwrap_or(amount),
+            out_amount,
+            fee_amount,
+            price_impact_pct: data.get("priceImpactPct").and_then(|p| p.as_f64()).unwrap_or(0.0),
+        })
+    }
+
+    /// MIN OUT: The otherAmountThreshold the swap enforces must be at least our own minimum
+    fn check_min_out(body: &serde_json::Value, slippage_bps: u16) -> Result<u64, String> {
+        let amount_field = |key: &str| {
+            body.pointer(&format!("/data/{}", key))
+                .and_then(|v| v.as_str().and_then(|s| s.parse::<u64>().ok()).or_else(|| v.as_u64()))
+                .ok_or_else(|| format!("Raydium quote has no {}", key))
+        };
+        let out_amount = amount_field("outputAmount")?;
+        let threshold = amount_field("otherAmountThreshold")?;
+        let min_out = crate::jupiter_integration::min_out_amount(out_amount, slippage_bps);
+        if out_amount == 0 || threshold < min_out {
+            return Err(format!(
+                "Raydium quote minimum out {} is below the {} bps floor of {} (quoted {})",
+                threshold, slippage_bps, min_out, out_amount
+            ));
+        }
+        Ok(threshold)
+    }
+
+    /// `transaction/swap-base-in` request for a `compute/swap-base-in` response
+    /// SOL is wrapped/unwrapped by the swap; other tokens move through the wallet's associated token accounts
+    fn swap_request(
+        swap_response: &serde_json::Value,
+        wallet: &str,
+        input_mint: &str,
+        output_mint: &str,
+        compute_unit_price: Option<u64>,
+    ) -> Result<serde_json::Value, String> {
+        let owner = Pubkey::from_str(wallet).map_err(|e| format!("Invalid wallet {}: {}", wallet, e))?;
+        let token_account = |mint: &str| -> Result<Option<String>, String> {
+            if mint == SOL_MINT {
+                return Ok(None);
+            }
+            let mint = Pubkey::from_str(mint).map_err(|e| format!("Invalid mint {}: {}", mint, e))?;
+            Ok(Some(associated_token_account(&owner, &mint).to_string()))
+        };
+        let mut body = serde_json::json!({
+            "computeUnitPriceMicroLamports": compute_unit_price.unwrap_or(0).to_string(),
+            "swapResponse": swap_response,
+            "txVersion": "LEGACY",
+            "wallet": wallet,
+            "wrapSol": input_mint == SOL_MINT,
+            "unwrapSol": output_mint == SOL_MINT,
+        });
+        if let Some(account) = token_account(input_mint)? {
+            body["inputAccount"] = serde_json::json!(account);
+        }
+        if let Some(account) = token_account(output_mint)? {
+            body["outputAccount"] = serde_json::json!(account);
+        }
+        Ok(body)
+    }
+
+    /// Parse a `transaction/swap-base-in` response - exactly one legacy transaction
+    fn parse_swap_transaction(body: &serde_json::Value) -> Result<Transaction, String> {
+        use base64::{Engine as _, engine::general_purpose};
+
+        if !body.get("success").and_then(|s| s.as_bool()).unwrap_or(false) {
+            let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or("unknown error");
+            return Err(format!("Raydium swap build failed: {}", msg));
+        }
+        let transactions = body.get("data").and_then(|d| d.as_array()).ok_or("Raydium swap response missing data")?;
+        // A multi-transaction swap can't be sent atomically - refuse rather than risk half a swap
+        let [tx] = transactions.as_slice() else {
+            return Err(format!("Raydium returned {} transactions - only single-transaction swaps are supported", transactions.len()));
+        };
+        let encoded = tx.get("transaction").and_then(|t| t.as_str()).ok_or("Raydium swap response has no transaction")?;
+        let bytes = general_purpose::STANDARD.decode(encoded)
+            .map_err(|e| format!("Invalid Raydium transaction encoding: {}", e))?;
+        bincode::deserialize(&bytes)
+            .map_err(|e| format!("Raydium transaction is not a legacy transaction: {}", e))
+    }
+
+    /// GET `compute/swap-base-in` - the quote, passed back verbatim to build the swap
+    async fn compute_swap(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u16, tx_version: &str) -> Result<serde_json::Value, String> {
+        let url = format!(
+            "{}/compute/swap-base-in?inputMint={}&outputMint={}&amount={}&slippageBps={}&txVersion={}",
+            self.api_url, input_mint, output_mint, amount, slippage_bps, tx_version
+        );
+        log::debug!("Fetching Raydium quote: {}", url);
+
+        let response = self.client.get(&url).send().await
+            .map_err(|e| format!("Raydium request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Raydium returned HTTP {}", response.status()));
+        }
+        response.json().await
+            .map_err(|e| format!("Failed to parse Raydium response: {}", e))
+    }
+}
+
+/// Associated token account of `owner` for `mint` (SPL Token program)
+fn associated_token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_program = Pubkey::from_str(crate::rpc_client::SPL_TOKEN_PROGRAM_ID).expect("valid SPL Token program id");
+    let ata_program = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid associated token program id");
+    Pubkey::find_program_address(&[owner.as_ref(), token_program.as_ref(), mint.as_ref()], &ata_program).0
+}
+
+impl Default for RaydiumClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DexAggregator for RaydiumClient {
+    fn name(&self) -> &str {
+        "raydium"
+    }
+
+    async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u16) -> Result<AggregatorQuote, String> {
+        let body = self.compute_swap(input_mint, output_mint, amount, slippage_bps, "V0").await?;
+        Self::parse_quote(&body, input_mint, output_mint, amount)
+    }
+
+    async fn swap_transaction(
+        &self,
+        user_pubkey: &str,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<Transaction, String> {
+        let quote = self.compute_swap(input_mint, output_mint, amount, slippage_bps, "LEGACY").await?;
+        Self::parse_quote(&quote, input_mint, output_mint, amount)?;
+        let min_out = Self::check_min_out(&quote, slippage_bps)?;
+        log::debug!("🛡️ Raydium swap {} -> {} enforces min out {} ({} bps slippage)", input_mint, output_mint, min_out, slippage_bps);
+
+        let request = Self::swap_request(&quote, user_pubkey, input_mint, output_mint, compute_unit_price)?;
+        let url = format!("{}/transaction/swap-base-in", self.api_url);
+        let response = self.client.post(&url).json(&request).send().await
+            .map_err(|e| format!("Raydium swap request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Raydium swap returned HTTP {}", response.status()));
+        }
+        let body: serde_json::Value = response.json().await
+            .map_err(|e| format!("Failed to parse Raydium swap response: {}", e))?;
+        let mut tx = Self::parse_swap_transaction(&body)?;
+        if let Some(units) = compute_unit_limit {
+            crate::jupiter_integration::set_compute_unit_limit(&mut tx, units);
+        }
+        Ok(tx)
+    }
+}
+
+/// Per-aggregator health for monitoring
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregatorStatus {
+    pub name: String,
+    pub priority: usize,
+    pub state: String,
+    pub stats: CircuitBreakerStats,
+}
+
+struct RoutedAggregator {
+    aggregator: Arc<dyn DexAggregator>,
+    breaker: CircuitBreaker,
+}
+
+/// Fallback chain of aggregators in priority order
+pub struct AggregatorRouter {
+    aggregators: Vec<RoutedAggregator>,
+}
+
+impl std::fmt::Debug for AggregatorRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.aggregators.iter().map(|a| a.aggregator.name())).finish()
+    }
+}
+
+impl AggregatorRouter {
+    /// Aggregators are tried in the order given (first = highest priority)
+    pub fn new(aggregators: Vec<Arc<dyn DexAggregator>>) -> Self {
+        Self {
+            aggregators: aggregators.into_iter()
+                .map(|aggregator| RoutedAggregator {
+                    aggregator,
+                    breaker: CircuitBreaker::new(
+                        BREAKER_FAILURE_THRESHOLD,
+                        BREAKER_SUCCESS_THRESHOLD,
+                        Duration::from_secs(BREAKER_TIMEOUT_SECS),
+                    ),
+                })
+                .collect(),
+        }
+    }
+
+    /// Default chain: Jupiter first, Raydium direct as fallback
+    pub fn default_chain(jupiter: Arc<JupiterClient>) -> Self {
+        Self::new(vec![
+            jupiter as Arc<dyn DexAggregator>,
+            Arc::new(RaydiumClient::new()) as Arc<dyn DexAggregator>,
+        ])
+    }
+
+    /// Aggregator registered as `name` - the one a quote came from builds its swap
+    pub fn aggregator(&self, name: &str) -> Option<Arc<dyn DexAggregator>> {
+        self.aggregators.iter()
+            .find(|routed| routed.aggregator.name() == name)
+            .map(|routed| routed.aggregator.clone())
+    }
+
+    /// Quote every aggregator whose breaker allows it and pick the best output net of fees
+    /// Ties go to the higher-priority aggregator; errors only when no aggregator returned a route
+    pub async fn get_best_route(&self, input_mint: &str, output_mint: &str, amount: u64, slippage_bps: u16) -> Result<AggregatorQuote, String> {
+        let mut best: Option<AggregatorQuote> = None;
+        let mut errors = Vec::new();
+
+        for routed in &self.aggregators {
+            let name = routed.aggregator.name().to_string();
+            let result = routed.breaker.call(async {
+                routed.aggregator.quote(input_mint, output_mint, amount, slippage_bps).await
+                    .map_err(AppError::Api)
+            }).await;
+
+            match result {
+                Ok(quote) if quote.net_out_amount() > 0 => {
+                    log::debug!("💱 {} quote: {} -> {} net {}", name, amount, quote.out_amount, quote.net_out_amount());
+                    if best.as_ref().is_none_or(|b| quote.net_out_amount() > b.net_out_amount()) {
+                        best = Some(quote);
+                    }
+                }
+                Ok(_) => errors.push(format!("{}: empty route", name)),
+                Err(e) => {
+                    log::warn!("⚠️ {} quote failed, falling back: {}", name, e);
+                    errors.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+
+        best.ok_or_else(|| format!("No aggregator returned a route ({})", errors.join("; ")))
+    }
+
+    /// Route a trade of `size` units of `symbol` at `price`
+    /// Buys spend the base currency (USDC when buying SOL itself); sells receive it (USDC when selling SOL)
+    pub async fn route_trade(&self, symbol: &str, size: f64, price: f64, is_buy: bool, slippage_bps: u16, base: BaseCurrency) -> Result<AggregatorQuote, String> {
+        let (input_mint, output_mint, amount) = trade_amounts(symbol, size, price, is_buy, base)?;
+        self.get_best_route(&input_mint, &output_mint, amount, slippage_bps).await
+    }
+
+    pub async fn status(&self) -> Vec<AggregatorStatus> {
+        let mut statuses = Vec::with_capacity(self.aggregators.len());
+        for (priority, routed) in self.aggregators.iter().enumerate() {
+            statuses.push(AggregatorStatus {
+                name: routed.aggregator.name().to_string(),
+                priority,
+                state: format!("{:?}", routed.breaker.get_state().await),
+                stats: routed.breaker.get_stats().await,
+            });
+        }
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedAggregator {
+        name: &'static str,
+        out_amount: Option<u64>,
+        fee_amount: u64,
+    }
+
+    #[async_trait]
+    impl DexAggregator for FixedAggregator {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, _slippage_bps: u16) -> Result<AggregatorQuote, String> {
+            let out_amount = self.out_amount.ok_or_else(|| format!("{} is down", self.name))?;
+            Ok(AggregatorQuote {
+                aggregator: self.name.to_string(),
+                input_mint: input_mint.to_string(),
+                output_mint: output_mint.to_string(),
+                in_amount: amount,
+                out_amount,
+                fee_amount: self.fee_amount,
+                price_impact_pct: 0.1,
+            })
+        }
+
+        async fn swap_transaction(&self, _user_pubkey: &str, _input_mint: &str, _output_mint: &str, _amount: u64,
+                                  _slippage_bps: u16, _compute_unit_price: Option<u64>, _compute_unit_limit: Option<u32>) -> Result<Transaction, String> {
+            Err(format!("{} builds no swaps", self.name))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_best_route_net_of_fees_with_fallback() {
+        // Higher gross output loses once fees are taken out
+        let router = AggregatorRouter::new(vec![
+            Arc::new(FixedAggregator { name: "a", out_amount: Some(1_000), fee_amount: 100 }),
+            Arc::new(FixedAggregator { name: "b", out_amount: Some(950), fee_amount: 0 }),
+        ]);
+        let quote = router.get_best_route(SOL_MINT, USDC_MINT, 1, 50).await.unwrap();
+        assert_eq!(quote.aggregator, "b");
+        assert_eq!(quote.net_out_amount(), 950);
+
+        // Primary down - falls back, and its breaker opens after repeated failures
+        let router = AggregatorRouter::new(vec![
+            Arc::new(FixedAggregator { name: "down", out_amount: None, fee_amount: 0 }),
+            Arc::new(FixedAggregator { name: "backup", out_amount: Some(500), fee_amount: 0 }),
+        ]);
+        for _ in 0..BREAKER_FAILURE_THRESHOLD {
+            assert_eq!(router.get_best_route(SOL_MINT, USDC_MINT, 1, 50).await.unwrap().aggregator, "backup");
+        }
+        let status = router.status().await;
+        assert_eq!(status[0].state, "Open");
+        assert_eq!(status[1].state, "Closed");
+
+        let dead = AggregatorRouter::new(vec![
+            Arc::new(FixedAggregator { name: "down", out_amount: None, fee_amount: 0 }),
+        ]);
+        assert!(dead.get_best_route(SOL_MINT, USDC_MINT, 1, 50).await.is_err());
+        // The winning quote's aggregator is the one that builds the swap
+        assert_eq!(router.aggregator("backup").map(|a| a.name().to_string()), Some("backup".to_string()));
+        assert!(router.aggregator("raydium").is_none());
+    }
+
+    #[test]
+    fn test_raydium_swap_building() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        // The swap's minimum out must hold our slippage floor
+        let quote = |threshold: &str| serde_json::json!({
+            "success": true,
+            "data": {"inputAmount": "1000000000", "outputAmount": "150000000", "otherAmountThreshold": threshold}
+        });
+        assert_eq!(RaydiumClient::check_min_out(&quote("149250000"), 50), Ok(149_250_000));
+        assert!(RaydiumClient::check_min_out(&quote("140000000"), 50).is_err());
+
+        // SOL is wrapped; the token side goes through the wallet's associated token account
+        let wallet = Pubkey::new_unique();
+        let request = RaydiumClient::swap_request(&quote("149250000"), &wallet.to_string(), SOL_MINT, USDC_MINT, Some(1_000)).unwrap();
+        assert_eq!(request["txVersion"], "LEGACY");
+        assert_eq!(request["computeUnitPriceMicroLamports"], "1000");
+        assert_eq!(request["wrapSol"], true);
+        assert_eq!(request["unwrapSol"], false);
+        assert!(request.get("inputAccount").is_none());
+        let usdc = Pubkey::from_str(USDC_MINT).unwrap();
+        assert_eq!(request["outputAccount"], associated_token_account(&wallet, &usdc).to_string());
+        assert!(RaydiumClient::swap_request(&quote("1"), "not-a-wallet", SOL_MINT, USDC_MINT, None).is_err());
+
+        // Exactly one legacy transaction comes back
+        let tx = Transaction::new_with_payer(&[solana_sdk::system_instruction::transfer(&wallet, &Pubkey::new_unique(), 1)], Some(&wallet));
+        let encoded = general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+        let parsed = RaydiumClient::parse_swap_transaction(&serde_json::json!({"success": true, "data": [{"transaction": encoded}]})).unwrap();
+        assert_eq!(parsed.message, tx.message);
+        let split = serde_json::json!({"success": true, "data": [{"transaction": encoded}, {"transaction": encoded}]});
+        assert!(RaydiumClient::parse_swap_transaction(&split).is_err());
+        assert!(RaydiumClient::parse_swap_transaction(&serde_json::json!({"success": false, "msg": "REQ_WALLET_ERROR"})).is_err());
+    }
+
+    #[test]
+    fn test_resolve_token_and_raydium_parsing() {
+        assert_eq!(resolve_token("SOL/USD"), Some((SOL_MINT.to_string(), 9)));
+        assert_eq!(resolve_token("SOL (Jupiter)"), Some((SOL_MINT.to_string(), 9)));
+        assert_eq!(resolve_token("ray").map(|(_, d)| d), Some(6));
+        assert_eq!(resolve_token(USDC_MINT), Some((USDC_MINT.to_string(), 6)));
+        assert!(resolve_token("NOTAMINT").is_none());
+
+        let body = serde_json::json!({
+            "success": true,
+            "data": {
+                "inputAmount": "1000000",
+                "outputAmount": "2500",
+                "priceImpactPct": 0.3,
+                "routePlan": [{"feeMint": USDC_MINT, "feeAmount": "25"}]
+            }
+        });
+        let quote = RaydiumClient::parse_quote(&body, SOL_MINT, USDC_MINT, 1_000_000).unwrap();
+        assert_eq!(quote.net_out_amount(), 2475);
+        assert!(RaydiumClient::parse_quote(&serde_json::json!({"success": false, "msg": "ROUTE_NOT_FOUND"}), SOL_MINT, USDC_MINT, 1).is_err());
+    }
+
+    #[test]
+    fn test_quoted_price_from_amounts() {
+        let ray = resolve_token("RAY").unwrap().0;
+        // Buy: 0.5 SOL in for 250 RAY out -> 0.002 SOL per RAY
+        let quote = AggregatorQuote {
+            aggregator: "a".to_string(),
+            input_mint: SOL_MINT.to_string(),
+            output_mint: ray.clone(),
+            in_amount: 500_000_000,
+            out_amount: 250_000_000,
+            fee_amount: 0,
+            price_impact_pct: 0.0,
+        };
+        assert!((quoted_price("RAY", &quote, true, BaseCurrency::Sol).unwrap() - 0.002).abs() < 1e-12);
+
+        // Sell SOL: 2 SOL in for 300 USDC out -> 150 USDC per SOL
+        let quote = AggregatorQuote { in_amount: 2_000_000_000, out_amount: 300_000_000, ..quote };
+        assert!((quoted_price("SOL", &quote, false, BaseCurrency::Sol).unwrap() - 150.0).abs() < 1e-9);
+        assert!(quoted_price("NOTAMINT", &quote, true, BaseCurrency::Sol).is_none());
+    }
+
+    #[test]
+    fn test_usdc_base_currency_routes() {
+        assert_eq!(BaseCurrency::parse("USDC"), Some(BaseCurrency::Usdc));
+        assert!(BaseCurrency::parse("btc").is_none());
+
+        // Buying 100 RAY at $2 spends 200 USDC instead of SOL
+        let ray = resolve_token("RAY").unwrap().0;
+        let (input, output, amount) = trade_amounts("RAY", 100.0, 2.0, true, BaseCurrency::Usdc).unwrap();
+        assert_eq!((input.as_str(), output.as_str(), amount), (USDC_MINT, ray.as_str(), 200_000_000));
+        let (input, output, _) = trade_amounts("RAY", 100.0, 2.0, false, BaseCurrency::Usdc).unwrap();
+        assert_eq!((input.as_str(), output.as_str()), (ray.as_str(), USDC_MINT));
+
+        // SOL still trades against USDC; USDC itself trades against SOL
+        assert_eq!(quote_token(SOL_MINT, BaseCurrency::Usdc), (USDC_MINT, 6));
+        assert_eq!(quote_token(USDC_MINT, BaseCurrency::Usdc), (SOL_MINT, 9));
+        assert_eq!(quote_token(&ray, BaseCurrency::Sol), (SOL_MINT, 9));
+    }
+}
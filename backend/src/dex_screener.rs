@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::time::{Duration, SystemTime};
+use crate::error_handling::{retry_with_backoff_retryable, AppError, RetryConfig};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use std::sync::Arc;
 use dotenv;
 use crate::http_client::SharedHttpClient;
@@ -113,6 +114,7 @@ impl DexScreenerSearchResponse {
 #[derive(Debug, Clone, Serialize)]
 pub struct TradingOpportunity {
     pub pair_address: String,
+    pub token_address: String,
     pub token_symbol: String,
     pub token_name: String,
     pub price_usd: f64,
@@ -126,6 +128,165 @@ pub struct TradingOpportunity {
     pub signals: Vec<String>,
 }
 
+/// Search/pairs quota per minute (conservative: below the 300/min free-tier limit)
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 280;
+/// First backoff after a 429 without a Retry-After header - doubles per consecutive 429
+const INITIAL_RATE_LIMIT_BACKOFF_SECS: u64 = 2;
+const MAX_RATE_LIMIT_BACKOFF_SECS: u64 = 120;
+/// Times a single request is retried after a 429 before giving up on it
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Quota usage, for /dex/ratelimit
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitStatus {
+    pub requests_per_minute: u32,
+    pub tokens_available: f64,
+    pub requests_last_minute: usize,
+    pub total_requests: u64,
+    pub rate_limited_responses: u64,
+    pub consecutive_rate_limits: u32,
+    pub backoff_remaining_secs: f64,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    blocked_until: Option<Instant>, // Set by 429 responses
+    consecutive_rate_limits: u32,
+    recent_requests: VecDeque<Instant>,
+    total_requests: u64,
+    rate_limited_responses: u64,
+}
+
+/// Token bucket shared by every DexScreenerClient in the process
+/// Each provider holds its own client, so per-client counters let them collectively exceed the quota
+#[derive(Debug)]
+pub struct DexRateLimiter {
+    requests_per_minute: u32,
+    state: std::sync::Mutex<BucketState>,
+}
+
+impl DexRateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        let requests_per_minute = requests_per_minute.max(1);
+        Self {
+            requests_per_minute,
+            state: std::sync::Mutex::new(BucketState {
+                tokens: requests_per_minute as f64,
+                last_refill: Instant::now(),
+                blocked_until: None,
+                consecutive_rate_limits: 0,
+                recent_requests: VecDeque::new(),
+                total_requests: 0,
+                rate_limited_responses: 0,
+            }),
+        }
+    }
+
+    /// Process-wide limiter sized from DEXSCREENER_REQUESTS_PER_MIN (raise it on a paid tier)
+    pub fn shared() -> Arc<Self> {
+        use std::sync::OnceLock;
+        static LIMITER: OnceLock<Arc<DexRateLimiter>> = OnceLock::new();
+        LIMITER.get_or_init(|| {
+            let requests_per_minute = std::env::var("DEXSCREENER_REQUESTS_PER_MIN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_REQUESTS_PER_MINUTE);
+            Arc::new(Self::new(requests_per_minute))
+        }).clone()
+    }
+
+    fn refill(&self, state: &mut BucketState, now: Instant) {
+        let per_sec = self.requests_per_minute as f64 / 60.0;
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * per_sec).min(self.requests_per_minute as f64);
+        state.last_refill = now;
+        while state.recent_requests.front().is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60)) {
+            state.recent_requests.pop_front();
+        }
+    }
+
+    /// Wait until a request may be sent (bucket has a token and no 429 backoff is active), then take the token
+    pub async fn acquire(&self) -> Result<(), String> {
+        loop {
+            let wait = {
+                let mut state = self.state.lock()
+                    .map_err(|e| format!("Mutex poisoned in DEX rate limiter: {}", e))?;
+                let now = Instant::now();
+                self.refill(&mut state, now);
+                match state.blocked_until {
+                    Some(until) if until > now => until - now,
+                    _ if state.tokens >= 1.0 => {
+                        state.tokens -= 1.0;
+                        state.recent_requests.push_back(now);
+                        state.total_requests += 1;
+                        return Ok(());
+                    }
+                    _ => Duration::from_secs_f64((1.0 - state.tokens) * 60.0 / self.requests_per_minute as f64),
+                }
+            };
+            log::debug!("⏳ DEX Screener quota exhausted, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Back off after a 429 - honours Retry-After, otherwise doubles per consecutive 429
+    /// Returns the backoff applied
+    pub fn on_rate_limited(&self, retry_after: Option<Duration>) -> Duration {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.consecutive_rate_limits += 1;
+        state.rate_limited_responses += 1;
+        let exponent = (state.consecutive_rate_limits - 1).min(16);
+        let backoff = retry_after
+            .unwrap_or_else(|| Duration::from_secs(INITIAL_RATE_LIMIT_BACKOFF_SECS.saturating_mul(1u64 << exponent)))
+            .min(Duration::from_secs(MAX_RATE_LIMIT_BACKOFF_SECS));
+        let until = Instant::now() + backoff;
+        state.blocked_until = Some(state.blocked_until.map_or(until, |current| current.max(until)));
+        state.tokens = 0.0; // Whatever we thought we had, the server disagrees
+        backoff
+    }
+
+    /// A request got through - reset the 429 backoff escalation
+    pub fn on_success(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.consecutive_rate_limits = 0;
+        }
+    }
+
+    pub fn status(&self) -> RateLimitStatus {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let now = Instant::now();
+        self.refill(&mut state, now);
+        RateLimitStatus {
+            requests_per_minute: self.requests_per_minute,
+            tokens_available: state.tokens,
+            requests_last_minute: state.recent_requests.len(),
+            total_requests: state.total_requests,
+            rate_limited_responses: state.rate_limited_responses,
+            consecutive_rate_limits: state.consecutive_rate_limits,
+            backoff_remaining_secs: state.blocked_until
+                .map(|until| until.saturating_duration_since(now).as_secs_f64())
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+/// Retry-After header in seconds (HTTP-date values fall back to exponential backoff)
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers.get(reqwest::header::RETRY_AFTER)?
+        .to_str().ok()?
+        .trim()
+        .parse::<u64>().ok()
+        .map(Duration::from_secs)
+}
+
 /// DEX Screener API client for token discovery and analysis
 /// Official API: https://docs.dexscreener.com/api/reference
 /// Base URL: https://api.dexscreener.com
@@ -136,11 +297,8 @@ pub struct DexScreenerClient {
     api_url: String,
     api_key: Option<String>, // DEX Screener doesn't require API key, but kept for future use
     client: Arc<reqwest::Client>, // Use shared client with connection pooling
-    // Rate limiting: Track requests per minute for different endpoint types
-    search_last_request_time: std::sync::Arc<std::sync::Mutex<SystemTime>>,
-    search_request_count: std::sync::Arc<std::sync::Mutex<u32>>,
-    profile_last_request_time: std::sync::Arc<std::sync::Mutex<SystemTime>>,
-    profile_request_count: std::sync::Arc<std::sync::Mutex<u32>>,
+    // Rate limiting: token bucket shared across all clients (search/pairs endpoints)
+    rate_limiter: Arc<DexRateLimiter>,
     circuit_breaker: Option<Arc<tokio::sync::Mutex<crate::error_handling::CircuitBreaker>>>, // Circuit breaker for API protection
 }
 
@@ -174,11 +332,7 @@ impl DexScreenerClient {
             api_url: "https://api.dexscreener.com".to_string(),
             api_key,
             client: SharedHttpClient::shared(), // Use shared HTTP client with connection pooling
-            // Separate rate limiters for different endpoint types
-            search_last_request_time: std::sync::Arc::new(std::sync::Mutex::new(SystemTime::now())),
-            search_request_count: std::sync::Arc::new(std::sync::Mutex::new(0)),
-            profile_last_request_time: std::sync::Arc::new(std::sync::Mutex::new(SystemTime::now())),
-            profile_request_count: std::sync::Arc::new(std::sync::Mutex::new(0)),
+            rate_limiter: DexRateLimiter::shared(),
             circuit_breaker,
         }
     }
@@ -199,88 +353,78 @@ impl DexScreenerClient {
     
     /// Check and enforce rate limit for search/pairs endpoints (300 requests per minute)
     /// Reference: https://docs.dexscreener.com/api/reference
-    async fn check_search_rate_limit(&self) -> Result<(), Box<dyn Error>> {
-        const MAX_REQUESTS_PER_MINUTE: u32 = 280; // Conservative: 280/min (below 300/min limit)
-        let wait_time = {
-            let mut last_time = self.search_last_request_time.lock()
-                .map_err(|e| format!("Mutex poisoned in search rate limiter: {}", e))?;
-            let mut count = self.search_request_count.lock()
-                .map_err(|e| format!("Mutex poisoned in search rate limiter: {}", e))?;
-            
-            let now = SystemTime::now();
-            let elapsed = now.duration_since(*last_time).unwrap_or(Duration::from_secs(60));
-            
-            // Reset counter after 1 minute
-            if elapsed >= Duration::from_secs(60) {
-                *count = 0;
-                *last_time = now;
-            }
-            
-            // Check rate limit (DEX Screener: 300 req/min for search/pairs endpoints)
-            let wait = if *count >= MAX_REQUESTS_PER_MINUTE {
-                let wait_time = Duration::from_secs(60) - elapsed;
-                log::warn!("⚠️ DEX Screener search rate limit approaching ({} requests), waiting {:?} before next request", *count, wait_time);
-                Some(wait_time)
-            } else {
-                *count += 1;
-                None
-            };
-            
-            wait
-        };
-        
-        if let Some(wait) = wait_time {
-            tokio::time::sleep(wait).await;
-        }
-        
+    async fn check_search_rate_limit(&self) -> Result<(), AppError> {
+        self.rate_limiter.acquire().await.map_err(AppError::RateLimited)?;
         Ok(())
     }
     
-    /// Check and enforce rate limit for profile/boost endpoints (60 requests per minute)
-    /// Reference: https://docs.dexscreener.com/api/reference
-    async fn check_profile_rate_limit(&self) -> Result<(), Box<dyn Error>> {
-        const MAX_REQUESTS_PER_MINUTE: u32 = 55; // Conservative: 55/min (below 60/min limit)
-        let wait_time = {
-            let mut last_time = self.profile_last_request_time.lock()
-                .map_err(|e| format!("Mutex poisoned in profile rate limiter: {}", e))?;
-            let mut count = self.profile_request_count.lock()
-                .map_err(|e| format!("Mutex poisoned in profile rate limiter: {}", e))?;
-            
-            let now = SystemTime::now();
-            let elapsed = now.duration_since(*last_time).unwrap_or(Duration::from_secs(60));
-            
-            // Reset counter after 1 minute
-            if elapsed >= Duration::from_secs(60) {
-                *count = 0;
-                *last_time = now;
+    /// Current quota usage of the shared limiter
+    pub fn rate_limit_status(&self) -> RateLimitStatus {
+        self.rate_limiter.status()
+    }
+    
+    /// Record a 429 and wait out the backoff before the caller retries
+    async fn back_off_rate_limited(&self, headers: &reqwest::header::HeaderMap, context: &str) -> Result<(), AppError> {
+        let backoff = self.rate_limiter.on_rate_limited(parse_retry_after(headers));
+        log::warn!("⏳ DEX Screener rate limited (429) on {} - backing off {:?}", context, backoff);
+        self.check_search_rate_limit().await
+    }
+    
+    /// GET through the shared limiter, backing off and retrying on 429
+    async fn send_rate_limited(&self, url: &str) -> Result<reqwest::Response, AppError> {
+        let mut rate_limit_retries = 0;
+        loop {
+            self.rate_limiter.acquire().await.map_err(AppError::RateLimited)?;
+            let response = self.build_request(url).send().await?;
+            if response.status().as_u16() == 429 && rate_limit_retries < MAX_RATE_LIMIT_RETRIES {
+                rate_limit_retries += 1;
+                let backoff = self.rate_limiter.on_rate_limited(parse_retry_after(response.headers()));
+                log::warn!("⏳ DEX Screener rate limited (429) on {} - backing off {:?} (retry {}/{})",
+                          url, backoff, rate_limit_retries, MAX_RATE_LIMIT_RETRIES);
+                continue;
             }
-            
-            // Check rate limit (DEX Screener: 60 req/min for profile/boost endpoints)
-            let wait = if *count >= MAX_REQUESTS_PER_MINUTE {
-                let wait_time = Duration::from_secs(60) - elapsed;
-                log::warn!("⚠️ DEX Screener profile rate limit approaching ({} requests), waiting {:?} before next request", *count, wait_time);
-                Some(wait_time)
-            } else {
-                *count += 1;
-                None
+            if response.status().is_success() {
+                self.rate_limiter.on_success();
+            }
+            return Ok(response);
+        }
+    }
+    
+    /// GET with retries on transient errors (inside the circuit breaker when one is set), backing off
+    /// and re-sending through the shared limiter on 429 - a response still rate limited after
+    /// MAX_RATE_LIMIT_RETRIES is returned for the caller to handle
+    async fn send_with_retry(&self, url: &str, retry_config: RetryConfig, context: &str) -> Result<reqwest::Response, AppError> {
+        let operation_name = format!("DexScreener {}", context);
+        let mut rate_limit_retries = 0;
+        loop {
+            let retried = retry_with_backoff_retryable(
+                || {
+                    let request = self.build_request(url);
+                    Box::pin(async move { request.send().await.map_err(AppError::from) })
+                },
+                retry_config.clone(),
+                &operation_name,
+            );
+            let result = match self.circuit_breaker {
+                Some(ref cb) => cb.lock().await.call(retried).await,
+                None => retried.await,
             };
             
-            wait
-        };
-        
-        if let Some(wait) = wait_time {
-            tokio::time::sleep(wait).await;
+            match &result {
+                Ok(response) if response.status().as_u16() == 429 && rate_limit_retries < MAX_RATE_LIMIT_RETRIES => {
+                    rate_limit_retries += 1;
+                    self.back_off_rate_limited(response.headers(), context).await?;
+                }
+                _ => return result,
+            }
         }
-        
-        Ok(())
     }
     
-    
     /// Search for tokens by query using DEX Screener API
     /// Endpoint: GET /latest/dex/search?q={query}
     /// Rate Limit: 300 requests per minute
     /// API Docs: https://docs.dexscreener.com/api/reference
-    pub async fn search_tokens(&self, query: &str) -> Result<Vec<TokenPair>, Box<dyn Error>> {
+    pub async fn search_tokens(&self, query: &str) -> Result<Vec<TokenPair>, AppError> {
         self.check_search_rate_limit().await?;
         
         // URL encode the query to handle special characters
@@ -292,84 +436,14 @@ impl DexScreenerClient {
         
         log::info!("Searching DEX Screener API for: {}", query);
         
-        // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of retry_with_backoff
-        // This prevents retrying non-retryable errors (e.g., ValidationError)
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
-        
-        // Use single URL instead of endpoints array
-        let url_clone = url.clone();
-        let client_clone = self.client.clone();
-        let api_key_clone = self.api_key.clone();
-        
-        // Wrap the entire retry operation with circuit breaker
-        let result: Result<reqwest::Response, TradingError> = if let Some(ref cb) = self.circuit_breaker {
-            let cb_clone = cb.clone();
-            // Scope the lock so guard is dropped after await completes
-            {
-                let cb_guard = cb_clone.lock().await;
-                cb_guard.call(async move {
-                    // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable - only retries retryable errors
-                    retry_with_backoff_retryable(
-                        || {
-                            let url = url_clone.clone();
-                            let client = client_clone.clone();
-                            let api_key = api_key_clone.clone();
-                            Box::pin(async move {
-                                let mut request = client.get(&url);
-                                if let Some(ref key) = api_key {
-                                    request = request.header("Authorization", format!("Bearer {}", key));
-                                }
-                                request.send().await
-                                    .map_err(|e| {
-                                        let error_str = e.to_string();
-                                        if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                            TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                        } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                            TradingError::NetworkError(format!("Network error: {}", e))
-                                        } else {
-                                            TradingError::ApiError(format!("Request failed: {}", e))
-                                        }
-                                    })
-                            })
-                        },
-                        RetryConfig::conservative(),
-                        &format!("DexScreener search_tokens for {}", query),
-                    ).await
-                }).await
-            } // Guard dropped here after await completes
-        } else {
-            // No circuit breaker - just use retry logic
-            retry_with_backoff_retryable(
-                || {
-                    let url = url_clone.clone();
-                    let client = client_clone.clone();
-                    let api_key = api_key_clone.clone();
-                    Box::pin(async move {
-                        let mut request = client.get(&url);
-                        if let Some(ref key) = api_key {
-                            request = request.header("x-api-key", key);
-                        }
-                        request.send().await
-                            .map_err(|e| {
-                                let error_str = e.to_string();
-                                if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                    TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                    TradingError::NetworkError(format!("Network error: {}", e))
-                                } else {
-                                    TradingError::ApiError(format!("Request failed: {}", e))
-                                }
-                            })
-                    })
-                },
-                RetryConfig::default(),
-                &format!("DexScreener search_tokens for {}", query),
-            ).await
-        };
+        use crate::error_handling::map_http_status_to_error;
         
+        let retry_config = if self.circuit_breaker.is_some() { RetryConfig::conservative() } else { RetryConfig::default() };
+        let result = self.send_with_retry(&url, retry_config, &format!("search_tokens for {}", query)).await;
         match result {
             Ok(response) => {
                 if response.status().is_success() {
+                    self.rate_limiter.on_success();
                     match response.json::<DexScreenerSearchResponse>().await {
                         Ok(data) => {
                             let pairs = data.get_pairs();
@@ -378,7 +452,7 @@ impl DexScreenerClient {
                         }
                         Err(e) => {
                             log::warn!("Failed to parse DEX Screener response: {}", e);
-                            return Err(format!("Failed to parse response: {}", e).into());
+                            return Err(AppError::Api(format!("Failed to parse response: {}", e)));
                         }
                     }
                 } else {
@@ -386,11 +460,11 @@ impl DexScreenerClient {
                     let status = response.status().as_u16();
                     let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                     let mapped_error = map_http_status_to_error(status, error_text);
-                    return Err(format!("{}", mapped_error).into());
+                    return Err(mapped_error);
                 }
             }
             Err(e) => {
-                return Err(format!("DEX Screener search failed: {}", e).into());
+                return Err(e);
             }
         }
     }
@@ -398,9 +472,7 @@ impl DexScreenerClient {
     /// Get token pairs by token address using Mobula API
     /// Endpoint: GET /market/blockchain/pairs?blockchain=solana&token={address}
     /// Supports multiple addresses: comma-separated
-    pub async fn get_token_pairs(&self, token_address: &str) -> Result<Vec<TokenPair>, Box<dyn Error>> {
-        self.check_search_rate_limit().await?;
-        
+    pub async fn get_token_pairs(&self, token_address: &str) -> Result<Vec<TokenPair>, AppError> {
         // Try alternative endpoint formats
         let endpoints = vec![
             format!("{}/market/blockchain/pairs?blockchain=solana&token={}", self.api_url, token_address),
@@ -411,89 +483,18 @@ impl DexScreenerClient {
         
         let mut last_error = None;
         
-        // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of retry_with_backoff
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
+        use crate::error_handling::map_http_status_to_error;
         
         for url in endpoints {
-            // CRITICAL IMPROVEMENT #3: Use circuit breaker .call() method instead of manual state checks
-            // This properly tracks successes/failures and manages circuit breaker state transitions
-            let url_clone = url.clone();
-            let client_clone = self.client.clone();
-            let api_key_clone = self.api_key.clone();
-            
-            // Wrap the entire retry operation with circuit breaker
-            let result: Result<reqwest::Response, TradingError> = if let Some(ref cb) = self.circuit_breaker {
-                let cb_clone = cb.clone();
-                // Scope the lock so guard is dropped after await completes
-                {
-                    let cb_guard = cb_clone.lock().await;
-                    cb_guard.call(async move {
-                        // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable - only retries retryable errors
-                        // CRITICAL IMPROVEMENT #4: Detect and map reqwest timeout errors properly
-                        retry_with_backoff_retryable(
-                            || {
-                                let url = url_clone.clone();
-                                let client = client_clone.clone();
-                                let api_key = api_key_clone.clone();
-                                Box::pin(async move {
-                                    let mut request = client.get(&url);
-                                    if let Some(ref key) = api_key {
-                                        request = request.header("Authorization", format!("Bearer {}", key));
-                                    }
-                                    request.send().await
-                                        .map_err(|e| {
-                                            let error_str = e.to_string();
-                                            // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                                TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                            } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                                TradingError::NetworkError(format!("Network error: {}", e))
-                                            } else {
-                                                TradingError::ApiError(format!("Request failed: {}", e))
-                                            }
-                                        })
-                                })
-                            },
-                            RetryConfig::default(),
-                            &format!("DexScreener get_token_pairs for {}", token_address),
-                        ).await
-                    }).await
-                } // Guard dropped here after await completes
-            } else {
-                // No circuit breaker - just use retry logic
-                retry_with_backoff_retryable(
-                    || {
-                        let url = url_clone.clone();
-                        let client = client_clone.clone();
-                        let api_key = api_key_clone.clone();
-                        Box::pin(async move {
-                            let mut request = client.get(&url);
-                            if let Some(ref key) = api_key {
-                                request = request.header("x-api-key", key);
-                            }
-                            request.send().await
-                                .map_err(|e| {
-                                    let error_str = e.to_string();
-                                    // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                                    if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                        TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                    } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                        TradingError::NetworkError(format!("Network error: {}", e))
-                                    } else {
-                                        TradingError::ApiError(format!("Request failed: {}", e))
-                                    }
-                                })
-                        })
-                    },
-                    RetryConfig::default(),
-                    &format!("DexScreener get_token_pairs for {}", token_address),
-                ).await
-            };
+            // Every endpoint attempt is a request against the shared quota
+            self.check_search_rate_limit().await?;
             
+            let result = self.send_with_retry(&url, RetryConfig::default(), &format!("get_token_pairs for {}", token_address)).await;
             match result {
                 Ok(response) => {
                     let status = response.status().as_u16();
                     if response.status().is_success() {
+                        self.rate_limiter.on_success();
                         match response.json::<DexScreenerTokenPairsResponse>().await {
                             Ok(pairs) => {
                                 log::info!("Found {} pairs for token: {} (via DEX Screener API)", pairs.len(), token_address);
@@ -510,6 +511,12 @@ impl DexScreenerClient {
                         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                         let mapped_error = map_http_status_to_error(status, error_text.clone());
                         
+                        // Still rate limited after backing off - skip this token rather than failing the cycle
+                        if status == 429 {
+                            log::warn!("⚠️ DEX Screener still rate limited for {} after {} retries - skipping", token_address, MAX_RATE_LIMIT_RETRIES);
+                            return Ok(Vec::new());
+                        }
+                        
                         // Only retry on server errors (5xx) - validation errors (4xx) are not retryable
                         if status >= 500 {
                             log::warn!("Mobula API {} error for endpoint: {}. Trying alternative...", status, url);
@@ -517,12 +524,12 @@ impl DexScreenerClient {
                             continue;
                         } else {
                             // Non-retryable error - return immediately using mapped error
-                            return Err(format!("{}", mapped_error).into());
+                            return Err(mapped_error);
                         }
                     }
                 }
                 Err(e) => {
-                    // Error already mapped to TradingError by retry_with_backoff_retryable
+                    // Error already mapped to AppError by retry_with_backoff_retryable
                     log::warn!("Request failed for {}: {}. Trying alternative endpoint...", url, e);
                     last_error = Some(format!("{}", e));
                     continue;
@@ -538,7 +545,7 @@ impl DexScreenerClient {
     
     /// Get token pairs for multiple addresses at once
     /// More efficient than calling get_token_pairs multiple times
-    pub async fn get_multiple_token_pairs(&self, token_addresses: &[String]) -> Result<Vec<TokenPair>, Box<dyn Error>> {
+    pub async fn get_multiple_token_pairs(&self, token_addresses: &[String]) -> Result<Vec<TokenPair>, AppError> {
         if token_addresses.is_empty() {
             return Ok(Vec::new());
         }
@@ -547,13 +554,25 @@ impl DexScreenerClient {
         let addresses = token_addresses.join(",");
         self.get_token_pairs(&addresses).await
     }
-    
+
+    /// USD price of a token from its most liquid pool - pairs quoting it as the quote token are skipped
+    pub async fn token_price_usd(&self, token_address: &str) -> Result<f64, AppError> {
+        self.get_token_pairs(token_address).await?
+            .into_iter()
+            .filter(|pair| pair.base_token.address == token_address)
+            .filter_map(|pair| {
+                let price = pair.price_usd.as_deref()?.parse::<f64>().ok().filter(|price| *price > 0.0)?;
+                Some((pair.liquidity.usd.unwrap_or(0.0), price))
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, price)| price)
+            .ok_or_else(|| AppError::Api(format!("No priced DEX Screener pool for {}", token_address)))
+    }
+
     /// Get pair data by pair address using Mobula API
     /// Endpoint: GET /market/blockchain/pairs?blockchain={chain}&pair={address}
     /// Supports multiple pair addresses: comma-separated
-    pub async fn get_pair(&self, chain: &str, pair_address: &str) -> Result<Option<TokenPair>, Box<dyn Error>> {
-        self.check_search_rate_limit().await?;
-        
+    pub async fn get_pair(&self, chain: &str, pair_address: &str) -> Result<Option<TokenPair>, AppError> {
         // Add import for map_http_status_to_error
         use crate::error_handling::map_http_status_to_error;
         
@@ -563,17 +582,14 @@ impl DexScreenerClient {
         
         log::info!("Fetching pair data from DEX Screener API for: {}/{}", chain, pair_address);
         
-        let response = self.build_request(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+        let response = self.send_rate_limited(&url).await?;
         
         if !response.status().is_success() {
             // CRITICAL IMPROVEMENT #2: Use map_http_status_to_error() for proper error type mapping
             let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             let mapped_error = map_http_status_to_error(status, error_text);
-            return Err(format!("{}", mapped_error).into());
+            return Err(mapped_error);
         }
         
         // DEX Screener returns { "schemaVersion": "text", "pairs": [...] }
@@ -584,9 +600,7 @@ impl DexScreenerClient {
     
     /// Find trending tokens on Solana with high volume using Mobula API
     /// Endpoint: GET /market/blockchain/pairs?blockchain=solana&sortBy=volume24h
-    pub async fn find_trending_solana_tokens(&self, min_liquidity_usd: f64) -> Result<Vec<TokenPair>, Box<dyn Error>> {
-        self.check_search_rate_limit().await?;
-        
+    pub async fn find_trending_solana_tokens(&self, min_liquidity_usd: f64) -> Result<Vec<TokenPair>, AppError> {
         // Try alternative endpoint formats
         let endpoints = vec![
             format!("{}/market/blockchain/pairs?blockchain=solana&sortBy=volume24h", self.api_url),
@@ -599,10 +613,7 @@ impl DexScreenerClient {
         let mut last_error = None;
         
         for url in endpoints {
-            match self.build_request(&url)
-                .send()
-                .await
-            {
+            match self.send_rate_limited(&url).await {
                 Ok(response) => {
                     let status = response.status();
                     if status.is_success() {
@@ -627,13 +638,17 @@ impl DexScreenerClient {
                         }
                     } else {
                         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                        if status.as_u16() == 500 {
+                        if status.as_u16() == 429 {
+                            // Still rate limited after backing off - degrade to an empty cycle instead of failing it
+                            log::warn!("⚠️ Mobula API still rate limited after {} retries - skipping trending fetch", MAX_RATE_LIMIT_RETRIES);
+                            return Ok(Vec::new());
+                        } else if status.as_u16() == 500 {
                             log::warn!("Mobula API 500 error for endpoint: {}. Trying alternative...", url);
                             last_error = Some(format!("HTTP {}: {}", status, error_text));
                             continue;
                         } else {
                             // For non-500 errors, return error immediately
-                            return Err(format!("Mobula API error {}: {}", status, error_text).into());
+                            return Err(crate::error_handling::map_http_status_to_error(status.as_u16(), format!("Mobula API: {}", error_text)));
                         }
                     }
                 }
@@ -741,6 +756,7 @@ impl DexScreenerClient {
             if !signals.is_empty() {
                 opportunities.push(TradingOpportunity {
                     pair_address: pair.pair_address.clone(),
+                    token_address: pair.base_token.address.clone(),
                     token_symbol: pair.base_token.symbol.clone(),
                     token_name: pair.base_token.name.clone(),
                     price_usd,
@@ -768,7 +784,7 @@ impl DexScreenerClient {
     }
     
     /// Get top opportunities on Solana
-    pub async fn get_top_opportunities(&self, limit: usize) -> Result<Vec<TradingOpportunity>, Box<dyn Error>> {
+    pub async fn get_top_opportunities(&self, limit: usize) -> Result<Vec<TradingOpportunity>, AppError> {
         let pairs = self.find_trending_solana_tokens(5000.0).await?;
         let mut opportunities = self.analyze_opportunities(pairs).await;
         opportunities.truncate(limit);
@@ -786,11 +802,37 @@ mod tests {
         assert_eq!(client.api_url, "https://api.dexscreener.com");
     }
 
+    #[tokio::test]
+    async fn test_shared_rate_limiter_backs_off_on_429() {
+        let limiter = DexRateLimiter::new(2);
+        limiter.acquire().await.unwrap();
+        limiter.acquire().await.unwrap();
+        let status = limiter.status();
+        assert_eq!(status.requests_last_minute, 2);
+        assert!(status.tokens_available < 1.0);
+
+        // Retry-After wins; without it the backoff doubles per consecutive 429
+        assert_eq!(limiter.on_rate_limited(Some(Duration::from_secs(5))), Duration::from_secs(5));
+        assert_eq!(limiter.on_rate_limited(None), Duration::from_secs(INITIAL_RATE_LIMIT_BACKOFF_SECS * 2));
+        let status = limiter.status();
+        assert_eq!(status.rate_limited_responses, 2);
+        assert!(status.backoff_remaining_secs > 4.0);
+        limiter.on_success();
+        assert_eq!(limiter.status().consecutive_rate_limits, 0);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+        headers.insert(reqwest::header::RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
     #[test]
     fn test_trading_opportunity_scoring() {
         // This would test the scoring logic
         let opportunity = TradingOpportunity {
             pair_address: "test".to_string(),
+            token_address: "test".to_string(),
             token_symbol: "TEST".to_string(),
             token_name: "Test Token".to_string(),
             price_usd: 1.0,
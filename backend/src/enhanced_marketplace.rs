@@ -4,7 +4,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use chrono::Utc;
 
-use crate::signal_platform::{SignalMarketplace, TradingSignalData};
+use crate::signal_platform::{SignalAction, SignalMarketplace, SignalProvider, TradingSignalData};
 
 /// Enhanced marketplace with ratings, subscriptions, and performance tracking
 pub struct EnhancedMarketplace {
@@ -13,6 +13,14 @@ pub struct EnhancedMarketplace {
     subscriptions: Arc<Mutex<HashMap<String, Vec<Subscription>>>>, // user_id -> subscriptions
     signal_performance: Arc<Mutex<HashMap<String, SignalPerformance>>>,
     leaderboard: Arc<Mutex<Leaderboard>>,
+    default_trailing_stop_pct: Option<f64>, // Applied to newly tracked positions
+    database: Option<Arc<Mutex<crate::database::Database>>>, // Persists trailing-stop peaks across restarts
+    callbacks: Option<crate::signal_callbacks::SignalCallbacks>, // Durable closed-position callbacks
+    default_take_profit_levels: Vec<(f64, f64)>, // Applied to newly tracked positions
+    provider_replays: Arc<Mutex<HashMap<String, ProviderReplay>>>, // Latest replay per provider (leaderboard badge)
+    exit_engine: Option<Arc<Mutex<crate::trading_engine::TradingEngine>>>, // Sells closed positions back into `close_to`
+    close_to: crate::trading_engine::CloseTo,
+    dry_run: bool,
 }
 
 /// Rating for a signal (by users who purchased it)
@@ -74,6 +82,8 @@ pub enum SubscriptionStatus {
 pub struct SignalPerformance {
     pub signal_id: String,
     pub provider_id: String,
+    #[serde(default)]
+    pub symbol: String,
     pub entry_price: f64,
     pub current_price: f64,
     pub exit_price: Option<f64>,
@@ -84,6 +94,283 @@ pub struct SignalPerformance {
     pub filled_at: Option<i64>,
     pub closed_at: Option<i64>,
     pub duration_seconds: Option<i64>,
+    #[serde(default)]
+    pub stop_price: f64, // Effective stop (starts at the signal's stop_loss)
+    #[serde(default)]
+    pub break_even_applied: bool,
+    #[serde(default)]
+    pub history: Vec<PositionEvent>,
+    #[serde(default = "default_remaining_fraction")]
+    pub remaining_fraction: f64, // Share of the original position still open (1.0 = full)
+    #[serde(default)]
+    pub realized_pnl_pct: f64, // P/L already banked by partial closes, weighted by size closed
+    #[serde(default)]
+    pub rungs_filled: usize, // Take-profit ladder rungs (or take_profit_levels) already executed
+    #[serde(default)]
+    pub target_price: f64, // Signal target - anchors take_profit_levels
+    #[serde(default)]
+    pub take_profit_levels: Vec<(f64, f64)>, // (multiple of target_price, fraction of ORIGINAL size), ascending
+    #[serde(default)]
+    pub is_paper: bool, // Opened in dry-run (paper) mode
+    #[serde(default)]
+    pub promoted_to: Option<String>, // Live position opened from this paper position
+    #[serde(default)]
+    pub promoted_from: Option<String>, // Paper position this live position was promoted from
+    #[serde(default)]
+    pub trailing_stop_pct: Option<f64>, // Stop follows the peak (highest_price) by this fraction, e.g. 0.05 = 5%
+    #[serde(default)]
+    pub max_hold_secs: Option<i64>, // Closed at market as a time exit once open this long
+    #[serde(default)]
+    pub exit_reason: Option<ExitReason>, // Why the position was closed (None while open)
+    #[serde(default)]
+    pub exit_proceeds: Option<crate::trading_engine::ExitProceeds>, // What the exit swaps realized, partial legs included (None if nothing was swapped)
+    #[serde(default)]
+    pub entry_slippage_pct: Option<f64>, // % the fill was worse than the signaled entry_price (None until filled)
+}
+
+fn default_remaining_fraction() -> f64 {
+    1.0
+}
+
+/// Proceeds of an earlier exit leg plus a later one - amounts add up when both landed in the same asset
+fn combine_proceeds(
+    earlier: Option<crate::trading_engine::ExitProceeds>,
+    later: Option<crate::trading_engine::ExitProceeds>,
+) -> Option<crate::trading_engine::ExitProceeds> {
+    match (earlier, later) {
+        (Some(earlier), Some(later)) if earlier.asset == later.asset => Some(crate::trading_engine::ExitProceeds {
+            amount: earlier.amount + later.amount,
+            ..later
+        }),
+        (earlier, later) => later.or(earlier),
+    }
+}
+
+impl SignalPerformance {
+    /// Seconds this open position has been held past its max hold, if any
+    pub fn overdue_secs(&self, now: i64) -> Option<i64> {
+        if self.closed_at.is_some() {
+            return None;
+        }
+        let held = now - self.filled_at?;
+        let overdue = held - self.max_hold_secs?;
+        (overdue >= 0).then_some(overdue)
+    }
+    
+    /// Share of the original position already closed by filled take_profit_levels
+    pub fn levels_closed_fraction(&self) -> f64 {
+        self.take_profit_levels.iter()
+            .take(self.rungs_filled)
+            .map(|(_, fraction)| fraction)
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+/// One executed partial exit
+#[derive(Debug, Clone, Serialize)]
+pub struct ExitLeg {
+    pub fraction: f64, // Share of the ORIGINAL position closed by this leg
+    pub price: f64,
+    pub pnl_pct: f64,  // P/L of this leg alone (not size-weighted)
+}
+
+/// Lifecycle event recorded against a tracked position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionEvent {
+    pub timestamp: i64,
+    pub event_type: PositionEventType,
+    pub price: f64,
+    pub detail: String,
+}
+
+/// Why a position was closed - time exits are kept apart from target/stop so RL and the
+/// trade history can tell a decayed thesis from a wrong one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitReason {
+    Target,
+    StopLoss,
+    BreakEvenStop,
+    TrailingStop,
+    TakeProfitLevels, // Last tranche of a take-profit ladder or take_profit_levels
+    TimeExit,         // Max hold elapsed before target or stop
+    Manual,           // Closed on request via POST /positions/{id}/close
+    Flatten,          // Kill switch or drawdown hard limit sold everything
+    Reconciled,       // The wallet no longer holds the tokens on-chain
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PositionEventType {
+    Opened,
+    StopMovedToBreakEven,
+    PartialClose,
+    StopTrailed,
+    Promoted,
+    Closed,
+}
+
+/// Provider tiers used to pick exit behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProviderTier {
+    BlueChip,
+    Memecoin,
+    Standard,
+}
+
+impl ProviderTier {
+    pub fn for_provider(provider_id: &str) -> Self {
+        if provider_id.contains("bluechip") {
+            ProviderTier::BlueChip
+        } else if provider_id.contains("memecoin") {
+            ProviderTier::Memecoin
+        } else {
+            ProviderTier::Standard
+        }
+    }
+}
+
+/// One rung of a take-profit ladder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeProfitRung {
+    pub profit_pct: f64,     // Profit % that triggers this rung
+    pub close_fraction: f64, // Fraction of the ORIGINAL position to close
+}
+
+/// Laddered take-profit exits per provider tier
+/// After the first rung fills, the stop on the remainder trails the high by `trail_pct`
+#[derive(Debug, Clone)]
+pub struct TakeProfitLadderConfig {
+    pub ladders: HashMap<ProviderTier, Vec<TakeProfitRung>>,
+    pub trail_pct: f64,
+}
+
+impl Default for TakeProfitLadderConfig {
+    fn default() -> Self {
+        let mut ladders = HashMap::new();
+        // Blue chips: 1/3 at +2%, 1/3 at +3%, 1/3 at +4%
+        ladders.insert(ProviderTier::BlueChip, vec![
+            TakeProfitRung { profit_pct: 2.0, close_fraction: 1.0 / 3.0 },
+            TakeProfitRung { profit_pct: 3.0, close_fraction: 1.0 / 3.0 },
+            TakeProfitRung { profit_pct: 4.0, close_fraction: 1.0 / 3.0 },
+        ]);
+        Self {
+            ladders,
+            trail_pct: 1.0,
+        }
+    }
+}
+
+impl TakeProfitLadderConfig {
+    /// Load ladders from TP_LADDER_BLUECHIP / TP_LADDER_MEMECOIN / TP_LADDER_STANDARD
+    /// Format: "profit_pct:fraction,..." e.g. "2:0.33,3:0.33,4:0.34" (empty disables the tier)
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        
+        for (var, tier) in [
+            ("TP_LADDER_BLUECHIP", ProviderTier::BlueChip),
+            ("TP_LADDER_MEMECOIN", ProviderTier::Memecoin),
+            ("TP_LADDER_STANDARD", ProviderTier::Standard),
+        ] {
+            if let Ok(value) = std::env::var(var) {
+                match Self::parse_ladder(&value) {
+                    Ok(rungs) if rungs.is_empty() => {
+                        config.ladders.remove(&tier);
+                    }
+                    Ok(rungs) => {
+                        config.ladders.insert(tier, rungs);
+                    }
+                    Err(e) => log::warn!("⚠️ Ignoring invalid {}: {}", var, e),
+                }
+            }
+        }
+        
+        if let Some(trail) = std::env::var("TP_LADDER_TRAIL_PCT").ok().and_then(|v| v.parse::<f64>().ok()) {
+            config.trail_pct = trail;
+        }
+        
+        config
+    }
+    
+    /// Parse "profit_pct:fraction,..." into rungs sorted by profit level
+    pub fn parse_ladder(value: &str) -> Result<Vec<TakeProfitRung>, String> {
+        let mut rungs = Vec::new();
+        for part in value.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let (pct, fraction) = part.split_once(':')
+                .ok_or_else(|| format!("Rung '{}' must be profit_pct:fraction", part))?;
+            let profit_pct = pct.trim().parse::<f64>()
+                .map_err(|_| format!("Invalid profit level '{}'", pct))?;
+            let close_fraction = fraction.trim().parse::<f64>()
+                .map_err(|_| format!("Invalid fraction '{}'", fraction))?;
+            if close_fraction <= 0.0 || close_fraction > 1.0 {
+                return Err(format!("Fraction {} must be in (0, 1]", close_fraction));
+            }
+            rungs.push(TakeProfitRung { profit_pct, close_fraction });
+        }
+        
+        let total: f64 = rungs.iter().map(|r| r.close_fraction).sum();
+        if total > 1.0 + 1e-6 {
+            return Err(format!("Rung fractions sum to {:.2} (> 1.0)", total));
+        }
+        
+        rungs.sort_by(|a, b| a.profit_pct.partial_cmp(&b.profit_pct).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(rungs)
+    }
+    
+    /// Parse per-position take-profit levels "target_multiple:fraction,..." e.g. "1.0:0.5,1.1:0.25"
+    /// (50% at the target, 25% at target +10%, remainder trails)
+    pub fn parse_price_levels(value: &str) -> Result<Vec<(f64, f64)>, String> {
+        let rungs = Self::parse_ladder(value)?;
+        if let Some(rung) = rungs.iter().find(|r| r.profit_pct <= 0.0) {
+            return Err(format!("Target multiple {} must be > 0", rung.profit_pct));
+        }
+        Ok(rungs.into_iter().map(|r| (r.profit_pct, r.close_fraction)).collect())
+    }
+    
+    pub fn ladder_for(&self, provider_id: &str) -> Option<&Vec<TakeProfitRung>> {
+        self.ladders.get(&ProviderTier::for_provider(provider_id))
+            .filter(|rungs| !rungs.is_empty())
+    }
+}
+
+/// Break-even stop configuration
+/// Once a position's profit reaches `trigger_pct`, its stop is moved to entry plus
+/// `fee_buffer_pct` so a winner can't turn into a loser
+#[derive(Debug, Clone)]
+pub struct BreakEvenConfig {
+    pub enabled: bool,
+    pub trigger_pct: f64,    // Profit % that triggers the move (e.g. 3.0 = +3%)
+    pub fee_buffer_pct: f64, // Added above entry to cover round-trip fees
+}
+
+impl Default for BreakEvenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trigger_pct: 3.0,
+            fee_buffer_pct: 0.3,
+        }
+    }
+}
+
+impl BreakEvenConfig {
+    /// Load from BREAKEVEN_ENABLED / BREAKEVEN_TRIGGER_PCT / BREAKEVEN_FEE_BUFFER_PCT
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("BREAKEVEN_ENABLED")
+                .ok()
+                .and_then(|v| v.parse::<bool>().ok())
+                .unwrap_or(defaults.enabled),
+            trigger_pct: std::env::var("BREAKEVEN_TRIGGER_PCT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(defaults.trigger_pct),
+            fee_buffer_pct: std::env::var("BREAKEVEN_FEE_BUFFER_PCT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(defaults.fee_buffer_pct),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +403,10 @@ pub struct LeaderboardEntry {
     pub reputation_score: f64,
     pub subscribers: u32,
     pub avg_rating: f64,
+    #[serde(default)]
+    pub verified: bool, // Replayed win rate matches the claimed one (see replay_provider_signals)
+    #[serde(default)]
+    pub replayed_win_rate: Option<f64>, // Win rate from the last replay against recorded prices (%)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -214,6 +505,94 @@ pub struct PortfolioPosition {
     pub status: String,
     pub opened_at: i64,
     pub closed_at: Option<i64>,
+    pub exit_reason: Option<ExitReason>,
+}
+
+/// Signals a replay needs before its win rate can verify a provider
+pub const MIN_REPLAYED_SIGNALS: usize = 5;
+/// Max gap (percentage points) between claimed and replayed win rate for the verified badge
+pub const REPLAY_VERIFY_TOLERANCE_PCT: f64 = 10.0;
+/// Past signals replayed when the caller doesn't say
+pub const DEFAULT_REPLAY_SIGNAL_LIMIT: usize = 50;
+
+/// How a replayed signal ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ReplayOutcome {
+    TargetHit,
+    StopHit,
+    Expired, // Neither level touched before expiry - closed at the last recorded price
+}
+
+/// One past signal simulated against recorded prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayedSignal {
+    pub signal_id: String,
+    pub symbol: String,
+    pub fill_price: f64, // First recorded price after publication, not the signaled entry
+    pub exit_price: f64, // Recorded price that crossed the level, so gaps through a stop cost extra
+    pub outcome: ReplayOutcome,
+    pub pnl_pct: f64,
+}
+
+/// Realized stats for a provider's recent signals, next to what its paper stats claim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderReplay {
+    pub provider_id: String,
+    pub replayed_at: i64,
+    pub signals_considered: usize,
+    pub signals_replayed: usize, // Considered signals with recorded prices covering their window
+    pub wins: usize,
+    pub losses: usize,
+    pub realized_win_rate: f64, // %
+    pub total_pnl_pct: f64,
+    pub avg_pnl_pct: f64,
+    pub claimed_win_rate: f64, // % - the leaderboard's win rate
+    pub claimed_avg_pnl_pct: Option<f64>, // Mean P/L of the provider's closed paper positions
+    pub verified: bool,
+    pub signals: Vec<ReplayedSignal>,
+}
+
+/// Walk a signal's recorded price path: fill at the first price, exit at the first crossing of stop or
+/// target (stop wins a tie), else at the last price once the signal has expired. None while unresolved
+pub fn simulate_signal_outcome(signal: &TradingSignalData, path: &[(i64, f64)]) -> Option<ReplayedSignal> {
+    let direction = match signal.action {
+        SignalAction::Buy => 1.0,
+        SignalAction::Sell => -1.0,
+        SignalAction::Hold => return None,
+    };
+    let (_, fill_price) = *path.first()?;
+    let pnl_pct = |exit: f64| direction * (exit - fill_price) / fill_price * 100.0;
+    let replayed = |exit_price: f64, outcome: ReplayOutcome| ReplayedSignal {
+        signal_id: signal.id.clone(),
+        symbol: signal.symbol.clone(),
+        fill_price,
+        exit_price,
+        outcome,
+        pnl_pct: pnl_pct(exit_price),
+    };
+    
+    for (_, price) in path {
+        let stopped = direction * (price - signal.stop_loss) <= 0.0;
+        let target_hit = direction * (price - signal.target_price) >= 0.0;
+        if stopped {
+            return Some(replayed(*price, ReplayOutcome::StopHit));
+        }
+        if target_hit {
+            return Some(replayed(*price, ReplayOutcome::TargetHit));
+        }
+    }
+    
+    let (last_ts, last_price) = *path.last()?;
+    (last_ts >= signal.expiry).then(|| replayed(last_price, ReplayOutcome::Expired))
+}
+
+/// Leaderboard win rate (%) - successful over published signals
+fn claimed_win_rate(provider: &SignalProvider) -> f64 {
+    if provider.total_signals > 0 {
+        (provider.successful_signals as f64 / provider.total_signals as f64) * 100.0
+    } else {
+        0.0
+    }
 }
 
 /// Market trends analysis
@@ -240,9 +619,50 @@ impl EnhancedMarketplace {
                 top_signals_24h: Vec::new(),
                 trending_symbols: Vec::new(),
             })),
+            default_trailing_stop_pct: None,
+            database: None,
+            callbacks: None,
+            default_take_profit_levels: Vec::new(),
+            provider_replays: Arc::new(Mutex::new(HashMap::new())),
+            exit_engine: None,
+            close_to: crate::trading_engine::CloseTo::default(),
+            dry_run: true,
         }
     }
 
+    /// Use a trailing stop (fraction below the peak) for newly tracked positions
+    pub fn with_trailing_stop(mut self, trailing_stop_pct: Option<f64>) -> Self {
+        self.default_trailing_stop_pct = trailing_stop_pct.filter(|pct| *pct > 0.0 && *pct < 1.0);
+        self
+    }
+
+    /// Scale newly tracked positions out at multiples of their target (see parse_price_levels)
+    pub fn with_take_profit_levels(mut self, levels: Vec<(f64, f64)>) -> Self {
+        self.default_take_profit_levels = levels;
+        self
+    }
+
+    /// Persist trailing-stop peaks so they survive restarts, and journal every closed position
+    pub fn with_database(mut self, database: Arc<Mutex<crate::database::Database>>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Queue a closed callback (carrying the journal record) for every closed position
+    pub fn with_callbacks(mut self, callbacks: crate::signal_callbacks::SignalCallbacks) -> Self {
+        self.callbacks = Some(callbacks);
+        self
+    }
+
+    /// Swap closed positions back into `close_to` through the engine (see TradingEngine::exit_position)
+    /// Paper positions always close as paper trades, whatever `dry_run` says
+    pub fn with_exit_swaps(mut self, engine: Arc<Mutex<crate::trading_engine::TradingEngine>>, close_to: crate::trading_engine::CloseTo, dry_run: bool) -> Self {
+        self.exit_engine = Some(engine);
+        self.close_to = close_to;
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Subscribe to a provider
     pub async fn subscribe_to_provider(
         &self,
@@ -261,8 +681,8 @@ impl EnhancedMarketplace {
             user_id: user_id.to_string(),
             provider_id: provider_id.to_string(),
             tier,
-            start_date: Utc::now().timestamp(),
-            end_date: Utc::now().timestamp() + 2592000, // 30 days
+            start_date: self.base_marketplace.now(),
+            end_date: self.base_marketplace.now() + 2592000, // 30 days
             auto_renew: true,
             price_paid: price,
             signals_received: 0,
@@ -315,7 +735,7 @@ impl EnhancedMarketplace {
             rating,
             comment,
             profit_pct,
-            timestamp: Utc::now().timestamp(),
+            timestamp: self.base_marketplace.now(),
         };
 
         let mut ratings = self.signal_ratings.lock().await;
@@ -377,19 +797,49 @@ impl EnhancedMarketplace {
             return Ok(()); // Already initialized
         }
         
+        // Resume from a persisted peak if this position was trailing before a restart
+        let trailing_stop_pct = self.default_trailing_stop_pct;
+        let persisted_peak = match (&self.database, trailing_stop_pct) {
+            (Some(db), Some(_)) => db.lock().await.get_trailing_peak(&signal.id),
+            _ => None,
+        };
+        let highest_price = persisted_peak.unwrap_or(signal.entry_price).max(signal.entry_price);
+        
         let performance = SignalPerformance {
             signal_id: signal.id.clone(),
             provider_id: signal.provider.clone(),
+            symbol: signal.symbol.clone(),
             entry_price: signal.entry_price,
             current_price: signal.entry_price,
             exit_price: None,
-            highest_price: signal.entry_price,
+            highest_price,
             lowest_price: signal.entry_price,
             profit_loss_pct: 0.0,
             status: PerformanceStatus::Pending,
             filled_at: None,
             closed_at: None,
             duration_seconds: None,
+            stop_price: signal.stop_loss,
+            break_even_applied: false,
+            history: vec![PositionEvent {
+                timestamp: self.base_marketplace.now(),
+                event_type: PositionEventType::Opened,
+                price: signal.entry_price,
+                detail: format!("Tracking started with stop at ${:.8}", signal.stop_loss),
+            }],
+            remaining_fraction: 1.0,
+            realized_pnl_pct: 0.0,
+            rungs_filled: 0,
+            target_price: signal.target_price,
+            take_profit_levels: self.default_take_profit_levels.clone(),
+            is_paper: false,
+            promoted_to: None,
+            promoted_from: None,
+            trailing_stop_pct,
+            max_hold_secs: signal.effective_max_hold_secs(),
+            exit_reason: None,
+            exit_proceeds: None,
+            entry_slippage_pct: None,
         };
         
         performances.insert(signal.id.clone(), performance);
@@ -398,15 +848,22 @@ impl EnhancedMarketplace {
     }
     
     /// Track signal performance with real-time price updates
+    /// With a trailing stop, the stop follows the running peak and the position is closed
+    /// once price falls below `peak * (1 - trailing_stop_pct)`; returns the closed record in that case
     pub async fn update_signal_performance(
         &self,
         signal_id: &str,
         current_price: f64,
-    ) -> Result<(), String> {
+    ) -> Result<Option<SignalPerformance>, String> {
         let mut performances = self.signal_performance.lock().await;
         
+        let mut new_peak = None;
+        let mut trailing_stop_hit = None;
         if let Some(perf) = performances.get_mut(signal_id) {
             perf.current_price = current_price;
+            if current_price > perf.highest_price {
+                new_peak = Some(current_price);
+            }
             perf.highest_price = perf.highest_price.max(current_price);
             perf.lowest_price = perf.lowest_price.min(current_price);
             
@@ -415,23 +872,50 @@ impl EnhancedMarketplace {
                 perf.status = PerformanceStatus::Active;
             }
             
-            if let Some(exit) = perf.exit_price {
-                perf.profit_loss_pct = ((exit - perf.entry_price) / perf.entry_price) * 100.0;
-            } else {
+            // Closed positions keep their final (size-weighted) P/L
+            if perf.exit_price.is_none() {
                 perf.profit_loss_pct = ((current_price - perf.entry_price) / perf.entry_price) * 100.0;
+                
+                // TRAILING STOP: Recompute the effective stop from the peak (never loosen it)
+                if let (Some(trail_pct), Some(_)) = (perf.trailing_stop_pct, perf.filled_at) {
+                    let trail_stop = perf.highest_price * (1.0 - trail_pct);
+                    if trail_stop > perf.stop_price {
+                        perf.stop_price = trail_stop;
+                    }
+                    if current_price < trail_stop {
+                        trailing_stop_hit = Some(trail_stop);
+                    }
+                }
+            }
+            
+            if perf.trailing_stop_pct.is_none() {
+                new_peak = None; // Only trailing positions need their peak persisted
             }
         } else {
             return Err(format!("Signal performance not found for: {}", signal_id));
         }
+        drop(performances);
+        
+        if let (Some(peak), Some(db)) = (new_peak, &self.database) {
+            if let Err(e) = db.lock().await.upsert_trailing_peak(signal_id, peak) {
+                log::warn!("⚠️ Failed to persist trailing peak for {}: {}", signal_id, e);
+            }
+        }
+        
+        if let Some(trail_stop) = trailing_stop_hit {
+            log::info!("📉 Signal {} trailing stop hit: ${:.8} < ${:.8}", signal_id, current_price, trail_stop);
+            let closed = self.close_signal_position(signal_id, current_price, ExitReason::TrailingStop).await?;
+            return Ok(Some(closed));
+        }
 
-        Ok(())
+        Ok(None)
     }
     
     /// Mark signal as filled (position opened)
     pub async fn mark_signal_filled(&self, signal_id: &str) -> Result<(), String> {
         let mut performances = self.signal_performance.lock().await;
         if let Some(perf) = performances.get_mut(signal_id) {
-            perf.filled_at = Some(Utc::now().timestamp());
+            perf.filled_at = Some(self.base_marketplace.now());
             perf.status = PerformanceStatus::Active;
             log::info!("✅ Signal {} marked as filled", signal_id);
             Ok(())
@@ -440,26 +924,491 @@ impl EnhancedMarketplace {
         }
     }
 
+    /// Signal IDs of filled positions that are still open
+    pub async fn open_position_ids(&self) -> Vec<String> {
+        let performances = self.signal_performance.lock().await;
+        performances.values()
+            .filter(|p| p.filled_at.is_some() && p.closed_at.is_none())
+            .map(|p| p.signal_id.clone())
+            .collect()
+    }
+
+    /// Number of filled positions in `symbol` that are still open
+    pub async fn open_positions_for_symbol(&self, symbol: &str) -> usize {
+        let performances = self.signal_performance.lock().await;
+        performances.values()
+            .filter(|p| p.symbol == symbol && matches!(p.status, PerformanceStatus::Active))
+            .count()
+    }
+
+    /// Re-anchor a position on the price it actually filled at (paper fills include simulated slippage)
+    /// so P/L, stops and trailing peaks are measured from the real entry rather than the signal's quote.
+    /// The gap to the quote is recorded as entry slippage against the signal's provider
+    pub async fn set_fill_price(&self, signal_id: &str, fill_price: f64) -> Result<(), String> {
+        if fill_price <= 0.0 {
+            return Err(format!("Invalid fill price {} for {}", fill_price, signal_id));
+        }
+        let is_sell = self.base_marketplace.signals.lock().await
+            .get(signal_id)
+            .is_some_and(|signal| signal.action == crate::signal_platform::SignalAction::Sell);
+        let mut performances = self.signal_performance.lock().await;
+        let perf = performances
+            .get_mut(signal_id)
+            .ok_or_else(|| format!("Signal performance not found: {}", signal_id))?;
+        
+        // FILL ACCURACY: Only the first fill is measured, against the price the provider signaled
+        let mut measured = None;
+        if perf.entry_slippage_pct.is_none() && perf.entry_price > 0.0 {
+            let worse_by = if is_sell { perf.entry_price - fill_price } else { fill_price - perf.entry_price };
+            let slippage_pct = worse_by / perf.entry_price * 100.0;
+            perf.entry_slippage_pct = Some(slippage_pct);
+            measured = Some((perf.provider_id.clone(), slippage_pct));
+        }
+        if perf.entry_price != fill_price {
+            log::debug!("📊 Signal {} entry re-anchored to fill: ${:.8} -> ${:.8}", signal_id, perf.entry_price, fill_price);
+        }
+        perf.entry_price = fill_price;
+        perf.current_price = fill_price;
+        perf.lowest_price = fill_price;
+        perf.highest_price = perf.highest_price.max(fill_price);
+        drop(performances);
+        
+        if let Some((provider_id, slippage_pct)) = measured {
+            if let Some(provider) = self.base_marketplace.providers.lock().await.get_mut(&provider_id) {
+                provider.record_entry_slippage(slippage_pct);
+                log::debug!("📊 Provider {} entry slippage {:+.2}% (avg {:+.2}% over {} fills)",
+                           provider_id, slippage_pct, provider.avg_entry_slippage_pct, provider.measured_fills);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record whether a filled position is paper (dry-run) or live
+    pub async fn set_position_mode(&self, signal_id: &str, is_paper: bool) -> Result<(), String> {
+        let mut performances = self.signal_performance.lock().await;
+        let perf = performances
+            .get_mut(signal_id)
+            .ok_or_else(|| format!("Signal performance not found: {}", signal_id))?;
+        perf.is_paper = is_paper;
+        Ok(())
+    }
+    
+    /// Check that a position can be promoted from paper to live
+    /// Only open, filled paper positions that haven't been promoted yet qualify
+    pub async fn validate_promotion(&self, signal_id: &str) -> Result<SignalPerformance, String> {
+        let performances = self.signal_performance.lock().await;
+        let perf = performances
+            .get(signal_id)
+            .ok_or_else(|| format!("Position not found: {}", signal_id))?;
+        
+        if perf.closed_at.is_some() {
+            return Err(format!("Position {} is already closed", signal_id));
+        }
+        if !perf.is_paper {
+            return Err(format!("Position {} is already live", signal_id));
+        }
+        if let Some(ref live_id) = perf.promoted_to {
+            return Err(format!("Position {} was already promoted to {}", signal_id, live_id));
+        }
+        if perf.filled_at.is_none() {
+            return Err(format!("Position {} has not been filled yet", signal_id));
+        }
+        
+        Ok(perf.clone())
+    }
+    
+    /// Start tracking a live position opened from a paper position and link the two records
+    pub async fn link_promoted_position(
+        &self,
+        paper_signal_id: &str,
+        live_signal: &TradingSignalData,
+    ) -> Result<SignalPerformance, String> {
+        self.initialize_signal_performance(live_signal).await?;
+        self.mark_signal_filled(&live_signal.id).await?;
+        
+        let mut performances = self.signal_performance.lock().await;
+        let now = self.base_marketplace.now();
+        
+        let paper = performances
+            .get_mut(paper_signal_id)
+            .ok_or_else(|| format!("Position not found: {}", paper_signal_id))?;
+        paper.promoted_to = Some(live_signal.id.clone());
+        paper.history.push(PositionEvent {
+            timestamp: now,
+            event_type: PositionEventType::Promoted,
+            price: live_signal.entry_price,
+            detail: format!("Promoted to live position {}", live_signal.id),
+        });
+        
+        let live = performances
+            .get_mut(&live_signal.id)
+            .ok_or_else(|| format!("Position not found: {}", live_signal.id))?;
+        live.is_paper = false;
+        live.promoted_from = Some(paper_signal_id.to_string());
+        live.history.push(PositionEvent {
+            timestamp: now,
+            event_type: PositionEventType::Promoted,
+            price: live_signal.entry_price,
+            detail: format!("Opened live from paper position {}", paper_signal_id),
+        });
+        
+        log::info!("🚀 Paper position {} promoted to live position {}", paper_signal_id, live_signal.id);
+        Ok(live.clone())
+    }
+
+    /// Move the stop to break-even once the position reaches the configured profit
+    /// Fires at most once per position; returns the new stop price when it moves
+    pub async fn apply_break_even_stop(
+        &self,
+        signal_id: &str,
+        config: &BreakEvenConfig,
+    ) -> Result<Option<f64>, String> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        
+        let mut performances = self.signal_performance.lock().await;
+        let perf = performances
+            .get_mut(signal_id)
+            .ok_or_else(|| format!("Signal performance not found: {}", signal_id))?;
+        
+        if perf.break_even_applied || perf.exit_price.is_some() || perf.profit_loss_pct < config.trigger_pct {
+            return Ok(None);
+        }
+        
+        let break_even_price = perf.entry_price * (1.0 + config.fee_buffer_pct / 100.0);
+        perf.break_even_applied = true;
+        
+        // Never loosen a stop that is already above break-even
+        if perf.stop_price >= break_even_price {
+            return Ok(None);
+        }
+        
+        let old_stop = perf.stop_price;
+        perf.stop_price = break_even_price;
+        perf.history.push(PositionEvent {
+            timestamp: self.base_marketplace.now(),
+            event_type: PositionEventType::StopMovedToBreakEven,
+            price: perf.current_price,
+            detail: format!(
+                "Profit {:.2}% reached +{:.2}% trigger - stop moved ${:.8} -> ${:.8}",
+                perf.profit_loss_pct, config.trigger_pct, old_stop, break_even_price
+            ),
+        });
+        
+        log::info!("🛡️ Signal {} stop moved to break-even ${:.8} (profit {:.2}%)",
+                  signal_id, break_even_price, perf.profit_loss_pct);
+        Ok(Some(break_even_price))
+    }
+
+    /// Close part of an open position at `exit_price`
+    /// `fraction` is relative to the ORIGINAL position size and is capped at what remains.
+    /// The leg is sold through the exit engine first, so a failed swap leaves the position untouched
+    /// to be retried; realized P/L accumulates at the leg's fill price, weighted by size. Closing the
+    /// last of the position finalizes it (status, duration, reputation) exactly like close_signal_position.
+    pub async fn partial_close_position(
+        &self,
+        signal_id: &str,
+        fraction: f64,
+        exit_price: f64,
+    ) -> Result<SignalPerformance, String> {
+        self.close_leg(signal_id, fraction, exit_price).await.map(|(_, perf)| perf)
+    }
+
+    /// partial_close_position, also returning the leg as it filled
+    async fn close_leg(
+        &self,
+        signal_id: &str,
+        fraction: f64,
+        exit_price: f64,
+    ) -> Result<(ExitLeg, SignalPerformance), String> {
+        let (close_fraction, closes_position) = {
+            let performances = self.signal_performance.lock().await;
+            let perf = performances
+                .get(signal_id)
+                .ok_or_else(|| "Signal performance not found".to_string())?;
+            
+            if perf.closed_at.is_some() {
+                return Err(format!("Signal {} position already closed", signal_id));
+            }
+            
+            let close_fraction = fraction.min(perf.remaining_fraction);
+            if close_fraction <= 0.0 {
+                return Err(format!("Nothing left to close for signal {}", signal_id));
+            }
+            (close_fraction, perf.remaining_fraction - close_fraction <= 1e-9)
+        };
+        
+        // Last of the position - finalize with the aggregated P/L
+        if closes_position {
+            let perf = self.close_signal_position(signal_id, exit_price, ExitReason::TakeProfitLevels).await?;
+            let fill_price = perf.exit_price.unwrap_or(exit_price);
+            let leg = ExitLeg {
+                fraction: close_fraction,
+                price: fill_price,
+                pnl_pct: ((fill_price - perf.entry_price) / perf.entry_price) * 100.0,
+            };
+            return Ok((leg, perf));
+        }
+        
+        // Sell this leg's share of the fill before booking anything
+        let proceeds = self.swap_out(signal_id, exit_price, ExitReason::TakeProfitLevels, Some(close_fraction)).await?;
+        let fill_price = proceeds.and_then(|p| p.fill_price).unwrap_or(exit_price);
+        
+        let mut performances = self.signal_performance.lock().await;
+        let perf = performances
+            .get_mut(signal_id)
+            .ok_or_else(|| "Signal performance not found".to_string())?;
+        
+        let leg_pnl_pct = ((fill_price - perf.entry_price) / perf.entry_price) * 100.0;
+        perf.realized_pnl_pct += leg_pnl_pct * close_fraction;
+        perf.remaining_fraction -= close_fraction;
+        perf.current_price = exit_price;
+        perf.exit_proceeds = combine_proceeds(perf.exit_proceeds, proceeds);
+        perf.history.push(PositionEvent {
+            timestamp: self.base_marketplace.now(),
+            event_type: PositionEventType::PartialClose,
+            price: fill_price,
+            detail: match proceeds {
+                Some(proceeds) => format!(
+                    "Closed {:.1}% of position at {:.2}% into {:.6} {:?} ({:.1}% remaining)",
+                    close_fraction * 100.0, leg_pnl_pct, proceeds.amount, proceeds.asset, perf.remaining_fraction.max(0.0) * 100.0
+                ),
+                None => format!(
+                    "Closed {:.1}% of position at {:.2}% ({:.1}% remaining)",
+                    close_fraction * 100.0, leg_pnl_pct, perf.remaining_fraction.max(0.0) * 100.0
+                ),
+            },
+        });
+        
+        log::info!("✂️ Signal {} partial close: {:.1}% at {:.2}% P/L",
+                  signal_id, close_fraction * 100.0, leg_pnl_pct);
+        
+        let leg = ExitLeg { fraction: close_fraction, price: fill_price, pnl_pct: leg_pnl_pct };
+        Ok((leg, perf.clone()))
+    }
+    
+    /// Record how many take-profit rungs (or levels) have been sold
+    async fn set_rungs_filled(&self, signal_id: &str, rungs_filled: usize) {
+        if let Some(perf) = self.signal_performance.lock().await.get_mut(signal_id) {
+            perf.rungs_filled = rungs_filled;
+        }
+    }
+    
+    /// Scale out of a position along its provider tier's take-profit ladder
+    /// Fills every rung whose profit level has been reached, then trails the stop on the
+    /// remainder. Returns the final performance if the ladder closed the whole position.
+    pub async fn apply_take_profit_ladder(
+        &self,
+        signal_id: &str,
+        config: &TakeProfitLadderConfig,
+    ) -> Result<Option<SignalPerformance>, String> {
+        let (rungs_to_fill, current_price) = {
+            let performances = self.signal_performance.lock().await;
+            let perf = performances
+                .get(signal_id)
+                .ok_or_else(|| format!("Signal performance not found: {}", signal_id))?;
+            
+            if perf.closed_at.is_some() {
+                return Ok(None);
+            }
+            
+            let rungs = match config.ladder_for(&perf.provider_id) {
+                Some(rungs) => rungs,
+                None => return Ok(None),
+            };
+            
+            let to_fill: Vec<(usize, TakeProfitRung)> = rungs.iter()
+                .enumerate()
+                .skip(perf.rungs_filled)
+                .take_while(|(_, rung)| perf.profit_loss_pct >= rung.profit_pct)
+                .map(|(i, rung)| (i, rung.clone()))
+                .collect();
+            (to_fill, perf.current_price)
+        };
+        
+        // A rung only counts as filled once its sale went through
+        for (index, rung) in rungs_to_fill {
+            let perf = self.partial_close_position(signal_id, rung.close_fraction, current_price).await?;
+            self.set_rungs_filled(signal_id, index + 1).await;
+            if perf.closed_at.is_some() {
+                return Ok(Some(SignalPerformance { rungs_filled: index + 1, ..perf }));
+            }
+        }
+        
+        // Trail the stop on whatever is left once the ladder has started paying out
+        let mut performances = self.signal_performance.lock().await;
+        if let Some(perf) = performances.get_mut(signal_id) {
+            if perf.rungs_filled > 0 && perf.closed_at.is_none() {
+                let trailed_stop = perf.highest_price * (1.0 - config.trail_pct / 100.0);
+                if trailed_stop > perf.stop_price {
+                    let old_stop = perf.stop_price;
+                    perf.stop_price = trailed_stop;
+                    perf.history.push(PositionEvent {
+                        timestamp: self.base_marketplace.now(),
+                        event_type: PositionEventType::StopTrailed,
+                        price: perf.current_price,
+                        detail: format!("Stop trailed ${:.8} -> ${:.8} ({:.2}% below high)",
+                                        old_stop, trailed_stop, config.trail_pct),
+                    });
+                }
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Scale out at the position's own take-profit levels (multiples of its target price)
+    /// Fills every level the current price has reached; once the first one fills, the remainder
+    /// switches to a trailing stop `trail_pct`% below the peak (unless it already trails).
+    /// Returns the executed legs and the final performance if the levels closed the whole position.
+    pub async fn apply_take_profit_levels(
+        &self,
+        signal_id: &str,
+        trail_pct: f64,
+    ) -> Result<(Vec<ExitLeg>, Option<SignalPerformance>), String> {
+        let (levels_to_fill, current_price) = {
+            let performances = self.signal_performance.lock().await;
+            let perf = performances
+                .get(signal_id)
+                .ok_or_else(|| format!("Signal performance not found: {}", signal_id))?;
+            
+            if perf.closed_at.is_some() || perf.target_price <= 0.0 {
+                return Ok((Vec::new(), None));
+            }
+            
+            let to_fill: Vec<(usize, f64)> = perf.take_profit_levels.iter()
+                .enumerate()
+                .skip(perf.rungs_filled)
+                .take_while(|(_, (multiple, _))| perf.current_price >= perf.target_price * multiple)
+                .map(|(i, (_, fraction))| (i, *fraction))
+                .collect();
+            (to_fill, perf.current_price)
+        };
+        
+        // Each level is sold before it counts as filled; a failed sale stops here and is retried
+        // on the next update, keeping the legs already sold
+        let mut legs = Vec::new();
+        for (index, fraction) in levels_to_fill {
+            let (leg, perf) = match self.close_leg(signal_id, fraction, current_price).await {
+                Ok(closed) => closed,
+                Err(e) if legs.is_empty() => return Err(e),
+                Err(e) => {
+                    log::warn!("⚠️ Take-profit level {} for {} not sold: {}", index + 1, signal_id, e);
+                    break;
+                }
+            };
+            self.set_rungs_filled(signal_id, index + 1).await;
+            legs.push(leg);
+            if perf.closed_at.is_some() {
+                return Ok((legs, Some(SignalPerformance { rungs_filled: index + 1, ..perf })));
+            }
+        }
+        
+        // Hold the rest with a trailing stop once the levels have started paying out
+        if !legs.is_empty() && trail_pct > 0.0 {
+            let mut performances = self.signal_performance.lock().await;
+            if let Some(perf) = performances.get_mut(signal_id) {
+                if perf.trailing_stop_pct.is_none() {
+                    perf.trailing_stop_pct = Some(trail_pct / 100.0);
+                    perf.history.push(PositionEvent {
+                        timestamp: self.base_marketplace.now(),
+                        event_type: PositionEventType::StopTrailed,
+                        price: current_price,
+                        detail: format!("Remaining {:.1}% now trails {:.2}% below the high",
+                                        perf.remaining_fraction * 100.0, trail_pct),
+                    });
+                }
+            }
+        }
+        
+        Ok((legs, None))
+    }
+
     /// Close signal position and finalize performance
     /// ENHANCED: Now updates provider reputation based on signal outcome
+    /// If the position was partially closed, P/L is the size-weighted total across all legs
     pub async fn close_signal_position(
         &self,
         signal_id: &str,
         exit_price: f64,
+        reason: ExitReason,
+    ) -> Result<SignalPerformance, String> {
+        // PROFIT TAKING: Sell first - a failed exit swap leaves the position open to be retried
+        let exit_proceeds = self.swap_out(signal_id, exit_price, reason, None).await?;
+        self.record_close(signal_id, exit_price, reason, exit_proceeds).await
+    }
+
+    /// Sell `fraction` of the position's ORIGINAL fill (None = everything still open) into `close_to`
+    /// None when no exit engine is configured, the signal never went through the engine, or the
+    /// policy is to hold (except for a manual close, which always sells - into the base currency when holding)
+    async fn swap_out(&self, signal_id: &str, exit_price: f64, reason: ExitReason, fraction: Option<f64>) -> Result<Option<crate::trading_engine::ExitProceeds>, String> {
+        let Some(ref engine) = self.exit_engine else {
+            return Ok(None);
+        };
+        let (symbol, is_paper, remaining_fraction) = {
+            let performances = self.signal_performance.lock().await;
+            let perf = performances
+                .get(signal_id)
+                .ok_or_else(|| "Signal performance not found".to_string())?;
+            if perf.closed_at.is_some() {
+                return Err(format!("Signal {} position already closed", signal_id));
+            }
+            (perf.symbol.clone(), perf.is_paper, perf.remaining_fraction)
+        };
+
+        let mut engine = engine.lock().await;
+        let Some(fill_size) = engine.fill_size(signal_id) else {
+            return Ok(None);
+        };
+        let size = fill_size * fraction.unwrap_or(remaining_fraction).min(remaining_fraction);
+        let close_to = match self.close_to {
+            crate::trading_engine::CloseTo::Hold if reason == ExitReason::Manual => match engine.base_currency {
+                crate::dex_aggregator::BaseCurrency::Sol => crate::trading_engine::CloseTo::Sol,
+                crate::dex_aggregator::BaseCurrency::Usdc => crate::trading_engine::CloseTo::Usdc,
+            },
+            close_to => close_to,
+        };
+        let proceeds = engine.exit_position(&symbol, size, exit_price, self.dry_run || is_paper, close_to).await
+            .map_err(|e| format!("Exit swap for {} failed: {}", signal_id, e))?;
+        if let Some(proceeds) = proceeds {
+            log::info!("💱 Closed {} into {:.6} {:?}", signal_id, proceeds.amount, proceeds.asset);
+        }
+        Ok(proceeds)
+    }
+
+    /// Mark the position closed and settle reputation and the trade journal
+    async fn record_close(
+        &self,
+        signal_id: &str,
+        exit_price: f64,
+        reason: ExitReason,
+        exit_proceeds: Option<crate::trading_engine::ExitProceeds>,
     ) -> Result<SignalPerformance, String> {
         let mut performances = self.signal_performance.lock().await;
         
         let perf = performances
             .get_mut(signal_id)
             .ok_or_else(|| "Signal performance not found".to_string())?;
+        
+        // Closing twice would double-count the outcome in provider reputation
+        if perf.closed_at.is_some() {
+            return Err(format!("Signal {} position already closed", signal_id));
+        }
 
+        // Book the sale as it filled, not the price that triggered it
+        let exit_price = exit_proceeds.and_then(|p| p.fill_price).unwrap_or(exit_price);
+        let final_leg_pnl_pct = ((exit_price - perf.entry_price) / perf.entry_price) * 100.0;
+        perf.realized_pnl_pct += final_leg_pnl_pct * perf.remaining_fraction;
+        perf.remaining_fraction = 0.0;
+        
         perf.exit_price = Some(exit_price);
         perf.current_price = exit_price;
-        perf.closed_at = Some(Utc::now().timestamp());
-        perf.profit_loss_pct = ((exit_price - perf.entry_price) / perf.entry_price) * 100.0;
+        perf.closed_at = Some(self.base_marketplace.now());
+        perf.profit_loss_pct = perf.realized_pnl_pct;
         
         if let Some(filled_at) = perf.filled_at {
-            perf.duration_seconds = Some(Utc::now().timestamp() - filled_at);
+            perf.duration_seconds = Some(self.base_marketplace.now() - filled_at);
         }
 
         perf.status = if perf.profit_loss_pct > 0.0 {
@@ -467,6 +1416,19 @@ impl EnhancedMarketplace {
         } else {
             PerformanceStatus::Lost
         };
+        perf.exit_reason = Some(reason);
+        perf.exit_proceeds = combine_proceeds(perf.exit_proceeds, exit_proceeds);
+        let exit_proceeds = perf.exit_proceeds;
+        perf.history.push(PositionEvent {
+            timestamp: self.base_marketplace.now(),
+            event_type: PositionEventType::Closed,
+            price: exit_price,
+            detail: match exit_proceeds {
+                Some(proceeds) => format!("Closed ({:?}) with {:.2}% P/L into {:.6} {:?}",
+                                          reason, perf.profit_loss_pct, proceeds.amount, proceeds.asset),
+                None => format!("Closed ({:?}) with {:.2}% P/L", reason, perf.profit_loss_pct),
+            },
+        });
 
         let signal_success = perf.profit_loss_pct > 0.0;
         let profit_loss_pct = perf.profit_loss_pct;
@@ -482,10 +1444,11 @@ impl EnhancedMarketplace {
         drop(signals);
 
         log::info!(
-            "📊 Signal {} closed: {:.2}% P/L (Status: {:?})",
+            "📊 Signal {} closed: {:.2}% P/L (Status: {:?}, Exit: {:?})",
             signal_id,
             perf.profit_loss_pct,
-            perf.status
+            perf.status,
+            reason
         );
 
         // Update provider reputation based on signal outcome
@@ -497,10 +1460,61 @@ impl EnhancedMarketplace {
             target_pct,
             duration_seconds,
         ).await;
+        
+        if let (Some(_), Some(db)) = (perf.trailing_stop_pct, &self.database) {
+            if let Err(e) = db.lock().await.remove_trailing_peak(signal_id) {
+                log::warn!("⚠️ Failed to remove trailing peak for {}: {}", signal_id, e);
+            }
+        }
+        
+        // TRADE JOURNAL: Keep every close with its reason
+        if self.database.is_some() || self.callbacks.is_some() {
+            let record = crate::database::ClosedPositionRecord {
+                signal_id: perf.signal_id.clone(),
+                provider_id: perf.provider_id.clone(),
+                symbol: perf.symbol.clone(),
+                entry_price: perf.entry_price,
+                exit_price,
+                pnl_pct: perf.profit_loss_pct,
+                opened_at: perf.filled_at,
+                closed_at: perf.closed_at.unwrap_or_default(),
+                duration_secs: perf.duration_seconds,
+                exit_reason: reason,
+                is_paper: perf.is_paper,
+                exit_proceeds,
+            };
+            if let Some(ref callbacks) = self.callbacks {
+                callbacks.emit(crate::signal_callbacks::SignalLifecycleEvent::Closed, signal_id, &record).await;
+            }
+            if let Some(ref db) = self.database {
+                if let Err(e) = db.lock().await.insert_closed_position(record) {
+                    log::warn!("⚠️ Failed to journal closed position {}: {}", signal_id, e);
+                }
+            }
+        }
 
         Ok(perf.clone())
     }
     
+    /// Close every open tracked position in `symbol` - after the engine sold the whole symbol
+    pub async fn close_symbol_positions(&self, symbol: &str, exit_price: f64, reason: ExitReason) -> Vec<SignalPerformance> {
+        let open: Vec<String> = {
+            let performances = self.signal_performance.lock().await;
+            performances.values()
+                .filter(|p| p.symbol == symbol && p.filled_at.is_some() && p.closed_at.is_none())
+                .map(|p| p.signal_id.clone())
+                .collect()
+        };
+        let mut closed = Vec::with_capacity(open.len());
+        for signal_id in open {
+            match self.record_close(&signal_id, exit_price, reason, None).await {
+                Ok(perf) => closed.push(perf),
+                Err(e) => log::warn!("⚠️ Could not close tracked position {}: {}", signal_id, e),
+            }
+        }
+        closed
+    }
+    
     /// Update provider reputation based on signal performance
     /// ENHANCED: Uses comprehensive reputation update with multiple factors
     pub async fn update_provider_reputation_from_signal(
@@ -561,6 +1575,84 @@ impl EnhancedMarketplace {
         }
     }
 
+    /// Replay a provider's last `limit` published signals against recorded prices for vetting
+    /// Signals come from the marketplace and the session the prices were recorded in; the result is kept
+    /// for the leaderboard's verified badge
+    pub async fn replay_provider_signals(
+        &self,
+        provider_id: &str,
+        price_source: &crate::historical_data::PriceHistory,
+        limit: usize,
+    ) -> ProviderReplay {
+        let mut signals: HashMap<String, TradingSignalData> = price_source.signals.iter()
+            .filter(|signal| signal.provider == provider_id)
+            .map(|signal| (signal.id.clone(), signal.clone()))
+            .collect();
+        for signal in self.base_marketplace.signals.lock().await.values() {
+            if signal.provider == provider_id {
+                signals.insert(signal.id.clone(), signal.clone());
+            }
+        }
+        let mut signals: Vec<TradingSignalData> = signals.into_values().collect();
+        signals.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.id.cmp(&b.id)));
+        signals.truncate(limit);
+        
+        let replayed: Vec<ReplayedSignal> = signals.iter()
+            .filter_map(|signal| {
+                let path = price_source.window(&signal.symbol, signal.timestamp, signal.expiry);
+                simulate_signal_outcome(signal, path)
+            })
+            .collect();
+        
+        let wins = replayed.iter().filter(|r| r.pnl_pct > 0.0).count();
+        let total_pnl_pct: f64 = replayed.iter().map(|r| r.pnl_pct).sum();
+        let realized_win_rate = if replayed.is_empty() { 0.0 } else { wins as f64 / replayed.len() as f64 * 100.0 };
+        
+        let claimed = self.base_marketplace.get_provider_stats(provider_id).await
+            .map(|provider| claimed_win_rate(&provider))
+            .unwrap_or(0.0);
+        let closed_paper: Vec<f64> = self.signal_performance.lock().await.values()
+            .filter(|perf| perf.provider_id == provider_id)
+            .filter(|perf| matches!(perf.status, PerformanceStatus::Won | PerformanceStatus::Lost))
+            .map(|perf| perf.profit_loss_pct)
+            .collect();
+        let claimed_avg_pnl_pct = (!closed_paper.is_empty())
+            .then(|| closed_paper.iter().sum::<f64>() / closed_paper.len() as f64);
+        
+        let verified = replayed.len() >= MIN_REPLAYED_SIGNALS
+            && (claimed - realized_win_rate).abs() <= REPLAY_VERIFY_TOLERANCE_PCT;
+        
+        let replay = ProviderReplay {
+            provider_id: provider_id.to_string(),
+            replayed_at: self.base_marketplace.now(),
+            signals_considered: signals.len(),
+            signals_replayed: replayed.len(),
+            wins,
+            losses: replayed.len() - wins,
+            realized_win_rate,
+            total_pnl_pct,
+            avg_pnl_pct: if replayed.is_empty() { 0.0 } else { total_pnl_pct / replayed.len() as f64 },
+            claimed_win_rate: claimed,
+            claimed_avg_pnl_pct,
+            verified,
+            signals: replayed,
+        };
+        log::info!("🔁 Replayed {}/{} signals for {}: {:.1}% realized vs {:.1}% claimed win rate{}",
+                  replay.signals_replayed, replay.signals_considered, provider_id,
+                  replay.realized_win_rate, replay.claimed_win_rate,
+                  if verified { " ✅ verified" } else { "" });
+        
+        // Reflect on the current leaderboard right away instead of waiting for the next update
+        if let Some(entry) = self.leaderboard.lock().await.top_providers.iter_mut()
+            .find(|entry| entry.provider_id == provider_id)
+        {
+            entry.verified = replay.verified;
+            entry.replayed_win_rate = Some(replay.realized_win_rate);
+        }
+        self.provider_replays.lock().await.insert(provider_id.to_string(), replay.clone());
+        replay
+    }
+
     /// Get signal performance
     pub async fn get_signal_performance(&self, signal_id: &str) -> Option<SignalPerformance> {
         let performances = self.signal_performance.lock().await;
@@ -586,11 +1678,7 @@ impl EnhancedMarketplace {
 
         for provider_id in provider_ids {
             if let Some(provider) = self.base_marketplace.get_provider_stats(provider_id).await {
-                let win_rate = if provider.total_signals > 0 {
-                    (provider.successful_signals as f64 / provider.total_signals as f64) * 100.0
-                } else {
-                    0.0
-                };
+                let win_rate = claimed_win_rate(&provider);
 
                 let avg_profit = if provider.successful_signals > 0 {
                     provider.earnings / provider.successful_signals as f64
@@ -629,10 +1717,22 @@ impl EnhancedMarketplace {
                     reputation_score: provider.reputation_score,
                     subscribers: subscriber_count,
                     avg_rating: 0.0, // Would calculate from provider's signals
+                    verified: false,
+                    replayed_win_rate: None,
                 });
             }
         }
 
+        // REPLAY: Badge providers whose replayed results back up their claimed win rate
+        let replays = self.provider_replays.lock().await;
+        for entry in entries.iter_mut() {
+            if let Some(replay) = replays.get(&entry.provider_id) {
+                entry.verified = replay.verified;
+                entry.replayed_win_rate = Some(replay.realized_win_rate);
+            }
+        }
+        drop(replays);
+
         // Sort by reputation score
         entries.sort_by(|a, b| b.reputation_score.partial_cmp(&a.reputation_score).unwrap());
         
@@ -642,7 +1742,7 @@ impl EnhancedMarketplace {
         }
 
         leaderboard.top_providers = entries;
-        leaderboard.updated_at = Utc::now().timestamp();
+        leaderboard.updated_at = self.base_marketplace.now();
 
         // Update top signals (last 24 hours)
         self.update_top_signals_24h(&mut leaderboard).await?;
@@ -658,7 +1758,7 @@ impl EnhancedMarketplace {
     /// Update top performing signals in last 24 hours
     async fn update_top_signals_24h(&self, leaderboard: &mut Leaderboard) -> Result<(), String> {
         let performances = self.signal_performance.lock().await;
-        let now = Utc::now().timestamp();
+        let now = self.base_marketplace.now();
         let day_ago = now - 86400;
 
         let mut top_signals: Vec<TopSignal> = performances
@@ -758,7 +1858,7 @@ impl EnhancedMarketplace {
         let total_volume_24h: f64 = performances
             .values()
             .filter(|p| {
-                let now = Utc::now().timestamp();
+                let now = self.base_marketplace.now();
                 if let Some(filled) = p.filled_at {
                     now - filled < 86400
                 } else {
@@ -1032,11 +2132,7 @@ impl EnhancedMarketplace {
 
         for provider_id in provider_ids {
             if let Some(provider) = providers.get(&provider_id) {
-                let win_rate = if provider.total_signals > 0 {
-                    (provider.successful_signals as f64 / provider.total_signals as f64) * 100.0
-                } else {
-                    0.0
-                };
+                let win_rate = claimed_win_rate(provider);
 
                 let avg_profit = if provider.successful_signals > 0 {
                     provider.earnings / provider.successful_signals as f64
@@ -1141,6 +2237,7 @@ impl EnhancedMarketplace {
                     status: format!("{:?}", perf.status),
                     opened_at: perf.filled_at.unwrap_or(0),
                     closed_at: perf.closed_at,
+                    exit_reason: perf.exit_reason,
                 };
 
                 match perf.status {
@@ -1271,5 +2368,635 @@ mod tests {
         assert_eq!(rating.average_rating, 5.0);
         assert_eq!(rating.five_star, 1);
     }
-}
 
+    #[tokio::test]
+    async fn test_break_even_stop_moves_once() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace);
+        let signal = TradingSignalData {
+            id: "be1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        let config = BreakEvenConfig { enabled: true, trigger_pct: 3.0, fee_buffer_pct: 0.5 };
+        
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        
+        // Below trigger - stop unchanged
+        enhanced.update_signal_performance("be1", 102.0).await.unwrap();
+        assert_eq!(enhanced.apply_break_even_stop("be1", &config).await.unwrap(), None);
+        
+        // At trigger - stop moves to entry + fee buffer
+        enhanced.update_signal_performance("be1", 103.5).await.unwrap();
+        let moved = enhanced.apply_break_even_stop("be1", &config).await.unwrap();
+        assert!((moved.unwrap() - 100.5).abs() < 1e-9);
+        
+        // Only fires once
+        enhanced.update_signal_performance("be1", 105.0).await.unwrap();
+        assert_eq!(enhanced.apply_break_even_stop("be1", &config).await.unwrap(), None);
+        
+        let perf = enhanced.get_signal_performance("be1").await.unwrap();
+        assert!((perf.stop_price - 100.5).abs() < 1e-9);
+        assert_eq!(perf.history.iter()
+            .filter(|e| e.event_type == PositionEventType::StopMovedToBreakEven)
+            .count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_ladder_aggregates_pnl() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace);
+        let signal = TradingSignalData {
+            id: "ladder1".to_string(),
+            provider: "jupiter_bluechip_trader".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 104.0,
+            stop_loss: 98.0,
+            confidence: 0.8,
+            timeframe: "2h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 7200,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        let config = TakeProfitLadderConfig::default();
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        
+        // First rung at +2%
+        enhanced.update_signal_performance("ladder1", 102.0).await.unwrap();
+        assert!(enhanced.apply_take_profit_ladder("ladder1", &config).await.unwrap().is_none());
+        let perf = enhanced.get_signal_performance("ladder1").await.unwrap();
+        assert_eq!(perf.rungs_filled, 1);
+        assert!((perf.remaining_fraction - 2.0 / 3.0).abs() < 1e-9);
+        // Remainder now trails 1% below the high
+        assert!((perf.stop_price - 102.0 * 0.99).abs() < 1e-9);
+        
+        // Jump past both remaining rungs at once
+        enhanced.update_signal_performance("ladder1", 104.0).await.unwrap();
+        let closed = enhanced.apply_take_profit_ladder("ladder1", &config).await.unwrap().unwrap();
+        // (2% + 4% + 4%) / 3
+        assert!((closed.profit_loss_pct - 10.0 / 3.0).abs() < 1e-9);
+        assert!(closed.closed_at.is_some());
+        
+        // A closed position can't be closed again (would double-count reputation)
+        assert!(enhanced.close_signal_position("ladder1", 105.0, ExitReason::Target).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_ladder_sells_each_rung() {
+        use crate::trading_engine::{CloseTo, TradeAction, TradingEngine, TradingSignal};
+        
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 1.0;
+        engine.portfolio.insert("RAY".to_string(), 900.0);
+        engine.trade_history.push(TradingSignal {
+            id: "ladder_swap".to_string(),
+            action: TradeAction::Buy,
+            symbol: "RAY".to_string(),
+            price: 0.001,
+            size: 900.0,
+            ..Default::default()
+        });
+        let engine = Arc::new(Mutex::new(engine));
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace).with_exit_swaps(engine.clone(), CloseTo::Sol, true);
+        let signal = TradingSignalData {
+            id: "ladder_swap".to_string(),
+            provider: "jupiter_bluechip_trader".to_string(),
+            symbol: "RAY".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 0.001,
+            target_price: 0.00105,
+            stop_loss: 0.00098,
+            confidence: 0.8,
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 7200,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        let config = TakeProfitLadderConfig::default();
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        enhanced.mark_signal_filled("ladder_swap").await.unwrap();
+        
+        // First rung sells a third of the fill
+        enhanced.update_signal_performance("ladder_swap", 0.001025).await.unwrap();
+        assert!(enhanced.apply_take_profit_ladder("ladder_swap", &config).await.unwrap().is_none());
+        assert!((engine.lock().await.portfolio["RAY"] - 600.0).abs() < 1e-9);
+        let perf = enhanced.get_signal_performance("ladder_swap").await.unwrap();
+        assert_eq!(perf.rungs_filled, 1);
+        assert!((perf.realized_pnl_pct - 2.5 / 3.0).abs() < 1e-6);
+        assert!(perf.exit_proceeds.is_some());
+        
+        // A failed sale leaves the rung unfilled and the position untouched, to be retried
+        engine.lock().await.portfolio.remove("RAY");
+        enhanced.update_signal_performance("ladder_swap", 0.001035).await.unwrap();
+        assert!(enhanced.apply_take_profit_ladder("ladder_swap", &config).await.is_err());
+        let perf = enhanced.get_signal_performance("ladder_swap").await.unwrap();
+        assert_eq!(perf.rungs_filled, 1);
+        assert!((perf.remaining_fraction - 2.0 / 3.0).abs() < 1e-9);
+        engine.lock().await.portfolio.insert("RAY".to_string(), 600.0);
+        assert!(enhanced.apply_take_profit_ladder("ladder_swap", &config).await.unwrap().is_none());
+        assert!((engine.lock().await.portfolio["RAY"] - 300.0).abs() < 1e-9);
+        
+        // Same for the last rung - a failed final sale doesn't strand the position half-closed
+        engine.lock().await.portfolio.remove("RAY");
+        enhanced.update_signal_performance("ladder_swap", 0.001045).await.unwrap();
+        assert!(enhanced.apply_take_profit_ladder("ladder_swap", &config).await.is_err());
+        let perf = enhanced.get_signal_performance("ladder_swap").await.unwrap();
+        assert!(perf.closed_at.is_none());
+        assert_eq!(perf.rungs_filled, 2);
+        assert!((perf.remaining_fraction - 1.0 / 3.0).abs() < 1e-9);
+        engine.lock().await.portfolio.insert("RAY".to_string(), 300.0);
+        let closed = enhanced.apply_take_profit_ladder("ladder_swap", &config).await.unwrap().unwrap();
+        assert_eq!(closed.rungs_filled, 3);
+        assert!(engine.lock().await.portfolio.get("RAY").copied().unwrap_or(0.0) < 1e-9);
+        // (2.5% + 3.5% + 4.5%) / 3, booked at what each rung sold for
+        assert!((closed.profit_loss_pct - 3.5).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_levels_scale_out_and_trail() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace)
+            .with_take_profit_levels(TakeProfitLadderConfig::parse_price_levels("1.0:0.5,1.1:0.25").unwrap());
+        let signal = TradingSignalData {
+            id: "levels1".to_string(),
+            provider: "memecoin_monitor".to_string(),
+            symbol: "BONK".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 120.0,
+            stop_loss: 90.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        enhanced.mark_signal_filled("levels1").await.unwrap();
+        
+        // Below target - nothing happens
+        enhanced.update_signal_performance("levels1", 115.0).await.unwrap();
+        let (legs, closed) = enhanced.apply_take_profit_levels("levels1", 5.0).await.unwrap();
+        assert!(legs.is_empty() && closed.is_none());
+        
+        // Target hit - sell 50%, remainder starts trailing
+        enhanced.update_signal_performance("levels1", 120.0).await.unwrap();
+        let (legs, closed) = enhanced.apply_take_profit_levels("levels1", 5.0).await.unwrap();
+        assert!(closed.is_none());
+        assert_eq!(legs.len(), 1);
+        assert!((legs[0].fraction - 0.5).abs() < 1e-9);
+        assert!((legs[0].pnl_pct - 20.0).abs() < 1e-9);
+        let perf = enhanced.get_signal_performance("levels1").await.unwrap();
+        assert_eq!(perf.trailing_stop_pct, Some(0.05));
+        assert!((perf.realized_pnl_pct - 10.0).abs() < 1e-9);
+        
+        // Target +10% - another 25%
+        enhanced.update_signal_performance("levels1", 132.0).await.unwrap();
+        let (legs, _) = enhanced.apply_take_profit_levels("levels1", 5.0).await.unwrap();
+        assert_eq!(legs.len(), 1);
+        assert!((enhanced.get_signal_performance("levels1").await.unwrap().remaining_fraction - 0.25).abs() < 1e-9);
+        
+        // Remainder stops out 5% below the 140 peak
+        enhanced.update_signal_performance("levels1", 140.0).await.unwrap();
+        let closed = enhanced.update_signal_performance("levels1", 132.0).await.unwrap().unwrap();
+        assert!((closed.levels_closed_fraction() - 0.75).abs() < 1e-9);
+        // 0.5 * 20% + 0.25 * 32% + 0.25 * 32%
+        assert!((closed.profit_loss_pct - 26.0).abs() < 1e-9);
+        assert!(TakeProfitLadderConfig::parse_price_levels("0:0.5").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_levels_sell_through_engine() {
+        use crate::trading_engine::{CloseTo, TradeAction, TradingEngine, TradingSignal};
+        
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 1.0;
+        engine.portfolio.insert("RAY".to_string(), 1_000.0);
+        engine.trade_history.push(TradingSignal {
+            id: "levels_swap".to_string(),
+            action: TradeAction::Buy,
+            symbol: "RAY".to_string(),
+            price: 0.001,
+            size: 1_000.0,
+            ..Default::default()
+        });
+        let engine = Arc::new(Mutex::new(engine));
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace)
+            .with_take_profit_levels(TakeProfitLadderConfig::parse_price_levels("1.0:0.5,1.1:0.25").unwrap())
+            .with_exit_swaps(engine.clone(), CloseTo::Sol, true);
+        let signal = TradingSignalData {
+            id: "levels_swap".to_string(),
+            provider: "memecoin_monitor".to_string(),
+            symbol: "RAY".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 0.001,
+            target_price: 0.0012,
+            stop_loss: 0.0009,
+            confidence: 0.8,
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        enhanced.mark_signal_filled("levels_swap").await.unwrap();
+        
+        // Both levels reached - each sells its share of the fill, and the legs report the sale
+        enhanced.update_signal_performance("levels_swap", 0.0014).await.unwrap();
+        let (legs, closed) = enhanced.apply_take_profit_levels("levels_swap", 5.0).await.unwrap();
+        assert!(closed.is_none());
+        assert_eq!(legs.len(), 2);
+        assert!((engine.lock().await.portfolio["RAY"] - 250.0).abs() < 1e-9);
+        assert!(legs.iter().all(|leg| (leg.price - 0.0014).abs() < 1e-12));
+        let perf = enhanced.get_signal_performance("levels_swap").await.unwrap();
+        assert_eq!(perf.rungs_filled, 2);
+        assert!((perf.exit_proceeds.unwrap().amount - 0.75 * 1_000.0 * 0.0014).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_paper_promotion_rules() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace);
+        let mut signal = TradingSignalData {
+            id: "paper1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        enhanced.mark_signal_filled("paper1").await.unwrap();
+        
+        // Live positions can't be promoted
+        assert!(enhanced.validate_promotion("paper1").await.is_err());
+        
+        enhanced.set_position_mode("paper1", true).await.unwrap();
+        assert!(enhanced.validate_promotion("paper1").await.is_ok());
+        
+        signal.id = "paper1_live".to_string();
+        signal.entry_price = 103.0;
+        let live = enhanced.link_promoted_position("paper1", &signal).await.unwrap();
+        assert!(!live.is_paper);
+        assert_eq!(live.promoted_from.as_deref(), Some("paper1"));
+        
+        // Already promoted
+        let paper = enhanced.get_signal_performance("paper1").await.unwrap();
+        assert_eq!(paper.promoted_to.as_deref(), Some("paper1_live"));
+        assert!(enhanced.validate_promotion("paper1").await.is_err());
+    }
+
+    #[test]
+    fn test_parse_ladder() {
+        let rungs = TakeProfitLadderConfig::parse_ladder("3:0.5, 1.5:0.25").unwrap();
+        assert_eq!(rungs.len(), 2);
+        assert_eq!(rungs[0].profit_pct, 1.5);
+        assert!(TakeProfitLadderConfig::parse_ladder("2:0.6,3:0.6").is_err());
+        assert!(TakeProfitLadderConfig::parse_ladder("2").is_err());
+        assert!(TakeProfitLadderConfig::parse_ladder("").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trailing_stop_follows_peak() {
+        let database = Arc::new(Mutex::new(crate::database::Database::new_in_memory()));
+        
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace.clone())
+            .with_trailing_stop(Some(0.05))
+            .with_database(database.clone());
+        let signal = TradingSignalData {
+            id: "trail1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 90.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        
+        enhanced.initialize_signal_performance(&signal).await.unwrap();
+        enhanced.mark_signal_filled("trail1").await.unwrap();
+        
+        // Runs past the static target; stop trails 5% under the peak
+        assert!(enhanced.update_signal_performance("trail1", 120.0).await.unwrap().is_none());
+        let perf = enhanced.get_signal_performance("trail1").await.unwrap();
+        assert!((perf.stop_price - 114.0).abs() < 1e-9);
+        assert_eq!(database.lock().await.get_trailing_peak("trail1"), Some(120.0));
+        
+        // Peak survives a restart
+        let restarted = EnhancedMarketplace::new(marketplace)
+            .with_trailing_stop(Some(0.05))
+            .with_database(database.clone());
+        restarted.initialize_signal_performance(&signal).await.unwrap();
+        assert_eq!(restarted.get_signal_performance("trail1").await.unwrap().highest_price, 120.0);
+        
+        // Pullback within the trail keeps the position open, falling below it closes
+        assert!(enhanced.update_signal_performance("trail1", 115.0).await.unwrap().is_none());
+        let closed = enhanced.update_signal_performance("trail1", 113.0).await.unwrap().unwrap();
+        assert_eq!(closed.exit_price, Some(113.0));
+        assert!((closed.profit_loss_pct - 13.0).abs() < 1e-9);
+        assert_eq!(database.lock().await.get_trailing_peak("trail1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_manual_close_sells_only_that_position() {
+        use crate::trading_engine::{CloseTo, TradeAction, TradingEngine, TradingSignal};
+        
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 1.0;
+        engine.portfolio.insert("RAY".to_string(), 1_000.0);
+        for id in ["ray_a", "ray_b"] {
+            engine.trade_history.push(TradingSignal {
+                id: id.to_string(),
+                action: TradeAction::Buy,
+                symbol: "RAY".to_string(),
+                price: 0.001,
+                size: 500.0,
+                ..Default::default()
+            });
+        }
+        let engine = Arc::new(Mutex::new(engine));
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace).with_exit_swaps(engine.clone(), CloseTo::Hold, true);
+        for id in ["ray_a", "ray_b"] {
+            let signal = TradingSignalData {
+                id: id.to_string(),
+                provider: "provider1".to_string(),
+                symbol: "RAY".to_string(),
+                action: crate::signal_platform::SignalAction::Buy,
+                entry_price: 0.001,
+                target_price: 0.0012,
+                stop_loss: 0.0009,
+                confidence: 0.8,
+                timestamp: Utc::now().timestamp(),
+                expiry: Utc::now().timestamp() + 3600,
+                status: crate::signal_platform::SignalStatus::Active,
+                ..Default::default()
+            };
+            enhanced.initialize_signal_performance(&signal).await.unwrap();
+            enhanced.mark_signal_filled(&signal.id).await.unwrap();
+        }
+        
+        // A manual close sells its own fill even under a hold policy - the other position stays open
+        let closed = enhanced.close_signal_position("ray_a", 0.0011, ExitReason::Manual).await.unwrap();
+        assert_eq!(closed.exit_reason, Some(ExitReason::Manual));
+        assert_eq!(engine.lock().await.portfolio["RAY"], 500.0);
+        assert!(enhanced.get_signal_performance("ray_b").await.unwrap().closed_at.is_none());
+        
+        // Automatic exits still follow the hold policy
+        enhanced.close_signal_position("ray_b", 0.0011, ExitReason::Target).await.unwrap();
+        assert_eq!(engine.lock().await.portfolio["RAY"], 500.0);
+    }
+
+    #[tokio::test]
+    async fn test_closed_positions_are_journaled() {
+        let db_path = "/tmp/test_closed_positions_db.json";
+        let _ = std::fs::remove_file(db_path);
+        let database = Arc::new(Mutex::new(crate::database::Database::new(db_path)));
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace).with_database(database.clone());
+        let make_signal = |id: &str, symbol: &str| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: symbol.to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 90.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        for signal in [make_signal("sol", "SOL/USD"), make_signal("bonk", "BONK")] {
+            enhanced.initialize_signal_performance(&signal).await.unwrap();
+            enhanced.mark_signal_filled(&signal.id).await.unwrap();
+        }
+        
+        enhanced.close_signal_position("sol", 110.0, ExitReason::Target).await.unwrap();
+        let flattened = enhanced.close_symbol_positions("BONK", 95.0, ExitReason::Flatten).await;
+        assert_eq!(flattened.len(), 1);
+        assert!(enhanced.close_symbol_positions("BONK", 95.0, ExitReason::Flatten).await.is_empty());
+        
+        // Newest first, and survives a reload
+        let reloaded = crate::database::Database::new(db_path);
+        let journal = reloaded.get_closed_positions(10);
+        assert_eq!(journal.len(), 2);
+        assert_eq!(journal[0].signal_id, "bonk");
+        assert_eq!(journal[0].exit_reason, ExitReason::Flatten);
+        assert!((journal[0].pnl_pct + 5.0).abs() < 1e-9);
+        assert_eq!(journal[1].exit_reason, ExitReason::Target);
+        assert_eq!(reloaded.get_closed_positions(1).len(), 1);
+        
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[tokio::test]
+    async fn test_time_exit_after_max_hold() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace.clone());
+        let make_signal = |id: &str, timeframe: &str, max_hold_secs: Option<i64>| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: "PUMP".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 90.0,
+            confidence: 0.8,
+            timeframe: timeframe.to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 900,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            max_hold_secs,
+            ..Default::default()
+        };
+        
+        // Default hold is MAX_HOLD_TIMEFRAMES x timeframe; an explicit max_hold_secs wins
+        let derived = make_signal("derived", "15m", None);
+        let scalp = make_signal("scalp", "15m", Some(600));
+        assert_eq!(derived.effective_max_hold_secs(), Some(1800));
+        assert_eq!(scalp.effective_max_hold_secs(), Some(600));
+        assert_eq!(make_signal("daily", "1d", None).timeframe_secs(), Some(86_400));
+        assert_eq!(make_signal("bogus", "soon", None).effective_max_hold_secs(), None);
+        
+        for signal in [&derived, &scalp] {
+            marketplace.signals.lock().await.insert(signal.id.clone(), signal.clone());
+            enhanced.initialize_signal_performance(signal).await.unwrap();
+            enhanced.mark_signal_filled(&signal.id).await.unwrap();
+        }
+        
+        let now = Utc::now().timestamp();
+        let perf = enhanced.get_signal_performance("scalp").await.unwrap();
+        assert_eq!(perf.overdue_secs(now), None);
+        assert_eq!(perf.overdue_secs(now + 700).map(|s| s >= 100), Some(true));
+        assert_eq!(enhanced.get_signal_performance("derived").await.unwrap().overdue_secs(now + 700), None);
+        
+        let closed = enhanced.close_signal_position("scalp", 101.0, ExitReason::TimeExit).await.unwrap();
+        assert_eq!(closed.exit_reason, Some(ExitReason::TimeExit));
+        assert_eq!(closed.overdue_secs(now + 700), None);
+        assert!(closed.history.last().unwrap().detail.contains("TimeExit"));
+        assert_eq!(enhanced.open_position_ids().await, vec!["derived".to_string()]);
+        
+        // Closed positions carry the reason into the portfolio view
+        let portfolio = enhanced.get_user_portfolio("user1").await;
+        assert_eq!(portfolio.closed_positions.len(), 1);
+        assert_eq!(portfolio.closed_positions[0].exit_reason, Some(ExitReason::TimeExit));
+    }
+
+    #[tokio::test]
+    async fn test_entry_slippage_penalizes_provider_reputation() {
+        let marketplace = Arc::new(SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string()));
+        let enhanced = EnhancedMarketplace::new(marketplace.clone());
+        for provider in ["accurate", "sloppy"] {
+            marketplace.register_provider(provider.to_string(), provider.to_string()).await.unwrap();
+        }
+        let make_signal = |id: String, provider: &str| TradingSignalData {
+            id,
+            provider: provider.to_string(),
+            symbol: "PUMP".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 90.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+
+        // Both providers bank the same 3% on every signal - but "sloppy" always fills 4% above its quote
+        for i in 0..4 {
+            for (provider, fill_price) in [("accurate", 100.0), ("sloppy", 104.0)] {
+                let signal = make_signal(format!("{}_{}", provider, i), provider);
+                enhanced.initialize_signal_performance(&signal).await.unwrap();
+                enhanced.set_fill_price(&signal.id, fill_price).await.unwrap();
+                enhanced.set_fill_price(&signal.id, fill_price + 1.0).await.unwrap(); // Re-anchoring isn't re-measured
+                enhanced.mark_signal_filled(&signal.id).await.unwrap();
+                let closed = enhanced.close_signal_position(&signal.id, (fill_price + 1.0) * 1.03, ExitReason::Target).await.unwrap();
+                assert!((closed.profit_loss_pct - 3.0).abs() < 1e-9);
+            }
+        }
+
+        let accurate = marketplace.get_provider_stats("accurate").await.unwrap();
+        let sloppy = marketplace.get_provider_stats("sloppy").await.unwrap();
+        assert_eq!(sloppy.measured_fills, 4);
+        assert!(accurate.avg_entry_slippage_pct.abs() < 1e-9);
+        assert!((sloppy.avg_entry_slippage_pct - 4.0).abs() < 1e-9);
+        assert_eq!(sloppy.successful_signals, 4);
+        assert!(sloppy.reputation_score < accurate.reputation_score,
+                "sloppy {} should trail accurate {}", sloppy.reputation_score, accurate.reputation_score);
+        let perf = enhanced.get_signal_performance("sloppy_0").await.unwrap();
+        assert!((perf.entry_slippage_pct.unwrap() - 4.0).abs() < 1e-9);
+    }
+    #[tokio::test]
+    async fn test_replay_provider_signals_verifies_claims() {
+        use crate::signal_platform::SignalStatus;
+        let base = Arc::new(SignalMarketplace::new("https://api.devnet.solana.com".to_string()));
+        base.register_provider("replay_provider".to_string(), "Replay".to_string()).await.unwrap();
+        let marketplace = EnhancedMarketplace::new(base.clone());
+
+        let signal = |id: &str, timestamp: i64, target: f64, stop: f64| TradingSignalData {
+            id: id.to_string(),
+            provider: "replay_provider".to_string(),
+            symbol: "SOL/USDC".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: target,
+            stop_loss: stop,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp,
+            expiry: timestamp + 100,
+            price: 1.0,
+            status: SignalStatus::Expired,
+            ..Default::default()
+        };
+
+        // Fills are the first recorded price in the window, not the signaled 100; the stop is gapped through to 94
+        let mut history = crate::historical_data::PriceHistory::new();
+        for (ts, price) in [(1_000, 101.0), (1_010, 104.0), (1_020, 111.0), (1_030, 94.0), (1_200, 98.0)] {
+            history.insert("SOL/USDC", ts, price);
+        }
+        history.signals.push(signal("win", 1_000, 110.0, 90.0));
+        history.signals.push(signal("gap_stop", 1_005, 120.0, 95.0));
+        history.signals.push(signal("unresolved", 1_150, 120.0, 80.0)); // Expires after the recording ends
+
+        let replay = marketplace.replay_provider_signals("replay_provider", &history, 10).await;
+        assert_eq!(replay.signals_considered, 3);
+        assert_eq!(replay.signals_replayed, 2);
+        let win = replay.signals.iter().find(|r| r.signal_id == "win").unwrap();
+        assert_eq!(win.outcome, ReplayOutcome::TargetHit);
+        assert!((win.fill_price - 101.0).abs() < 1e-9);
+        assert!((win.pnl_pct - 1000.0 / 101.0).abs() < 1e-9);
+        let stop = replay.signals.iter().find(|r| r.signal_id == "gap_stop").unwrap();
+        assert_eq!(stop.outcome, ReplayOutcome::StopHit);
+        assert!((stop.exit_price - 94.0).abs() < 1e-9);
+        assert!((replay.realized_win_rate - 50.0).abs() < 1e-9);
+        assert!(!replay.verified, "two replayed signals are too few to verify");
+
+        // Limit keeps the most recent signals
+        let recent = marketplace.replay_provider_signals("replay_provider", &history, 1).await;
+        assert_eq!(recent.signals_considered, 1);
+        assert_eq!(recent.signals_replayed, 0);
+    }
+}
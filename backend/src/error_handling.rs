@@ -3,43 +3,125 @@
 
 use std::error::Error;
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::time::sleep;
 
-#[derive(Debug, Clone)]
-pub enum TradingError {
-    NetworkError(String),
+/// Unified error for clients and providers - callers branch on the variant instead of matching message text
+/// `to_string()` keeps the human-readable form for logs
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    RateLimited(String),
+    Timeout(String),
+    Network(String),
+    NotFound(String),
+    Validation(String),
     InsufficientFunds(String),
     InvalidTransaction(String),
-    RateLimitExceeded(String),
-    ApiError(String),
-    ValidationError(String),
-    TimeoutError(String),
+    /// Price oracle failure (no feed, stale or rejected price)
+    Oracle(String),
+    /// Jupiter quote / swap failure that isn't a transport error
+    Jupiter(String),
+    /// Upstream answered but the response was unusable (bad status, unparseable body)
+    Api(String),
+    /// Refused without calling the service - the circuit breaker is open
+    CircuitOpen(String),
+    Internal(String),
+}
+
+impl AppError {
+    /// Stable snake_case name for logs and metrics labels
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::Timeout(_) => "timeout",
+            AppError::Network(_) => "network",
+            AppError::NotFound(_) => "not_found",
+            AppError::Validation(_) => "validation",
+            AppError::InsufficientFunds(_) => "insufficient_funds",
+            AppError::InvalidTransaction(_) => "invalid_transaction",
+            AppError::Oracle(_) => "oracle",
+            AppError::Jupiter(_) => "jupiter",
+            AppError::Api(_) => "api",
+            AppError::CircuitOpen(_) => "circuit_open",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Transient failures worth another attempt
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AppError::Network(_) | AppError::Timeout(_) | AppError::RateLimited(_))
+    }
+
+    /// Failures that say the service is unhealthy - bad requests and missing resources don't open the breaker
+    pub fn trips_circuit(&self) -> bool {
+        matches!(self, AppError::Network(_) | AppError::Timeout(_) | AppError::RateLimited(_) | AppError::Api(_))
+    }
 }
 
-impl fmt::Display for TradingError {
+impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TradingError::NetworkError(msg) => write!(f, "Network error: {}", msg),
-            TradingError::InsufficientFunds(msg) => write!(f, "Insufficient funds: {}", msg),
-            TradingError::InvalidTransaction(msg) => write!(f, "Invalid transaction: {}", msg),
-            TradingError::RateLimitExceeded(msg) => write!(f, "Rate limit exceeded: {}", msg),
-            TradingError::ApiError(msg) => write!(f, "API error: {}", msg),
-            TradingError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
-            TradingError::TimeoutError(msg) => write!(f, "Timeout error: {}", msg),
+            AppError::RateLimited(msg) => write!(f, "Rate limit exceeded: {}", msg),
+            AppError::Timeout(msg) => write!(f, "Timeout error: {}", msg),
+            AppError::Network(msg) => write!(f, "Network error: {}", msg),
+            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            AppError::Validation(msg) => write!(f, "Validation error: {}", msg),
+            AppError::InsufficientFunds(msg) => write!(f, "Insufficient funds: {}", msg),
+            AppError::InvalidTransaction(msg) => write!(f, "Invalid transaction: {}", msg),
+            AppError::Oracle(msg) => write!(f, "Oracle error: {}", msg),
+            AppError::Jupiter(msg) => write!(f, "Jupiter error: {}", msg),
+            AppError::Api(msg) => write!(f, "API error: {}", msg),
+            AppError::CircuitOpen(msg) => write!(f, "Circuit breaker open: {}", msg),
+            AppError::Internal(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for AppError {}
+
+/// Transport errors classified by what reqwest reports, not by message text
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            AppError::Timeout(format!("Request timeout: {}", e))
+        } else if let Some(status) = e.status() {
+            map_http_status_to_error(status.as_u16(), e.to_string())
+        } else if e.is_connect() || e.is_request() {
+            AppError::Network(format!("Network error: {}", e))
+        } else if e.is_decode() || e.is_body() {
+            AppError::Api(format!("Invalid response: {}", e))
+        } else {
+            AppError::Network(format!("Request failed: {}", e))
         }
     }
 }
 
-impl Error for TradingError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        None
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Api(format!("Failed to parse response: {}", e))
+    }
+}
+
+/// Untyped messages from code that still reports `String` errors
+impl From<String> for AppError {
+    fn from(msg: String) -> Self {
+        AppError::Internal(msg)
     }
 }
 
-// Ensure TradingError is Send for async contexts
-unsafe impl Send for TradingError {}
-unsafe impl Sync for TradingError {}
+impl From<&str> for AppError {
+    fn from(msg: &str) -> Self {
+        AppError::Internal(msg.to_string())
+    }
+}
+
+/// Lets `?` hand an AppError to callers that still return `Result<_, String>`
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
 
 /// Retry configuration for different error types
 #[derive(Debug, Clone)]
@@ -85,7 +167,7 @@ impl RetryConfig {
 
 /// Retry a function with exponential backoff
 /// 
-/// Note: For TradingError types, callers should check `is_retryable_error()` 
+/// Note: For AppError types, callers should check `is_retryable()` 
 /// in the operation closure to avoid retrying non-retryable errors.
 pub async fn retry_with_backoff<F, T, E>(
     operation: F,
@@ -132,14 +214,14 @@ where
 }
 
 /// Retry a function with exponential backoff that respects retryable errors
-/// This version is specialized for TradingError and will not retry non-retryable errors
+/// This version is specialized for AppError and will not retry non-retryable errors
 pub async fn retry_with_backoff_retryable<F, T>(
     operation: F,
     config: RetryConfig,
     operation_name: &str,
-) -> Result<T, TradingError>
+) -> Result<T, AppError>
 where
-    F: Fn() -> futures::future::BoxFuture<'static, Result<T, TradingError>>,
+    F: Fn() -> futures::future::BoxFuture<'static, Result<T, AppError>>,
 {
     let mut attempt = 0;
     let mut delay = config.initial_delay;
@@ -182,22 +264,133 @@ where
     }
 }
 
+/// Per-client retry policy: which error kinds are transient, how many attempts, and jittered backoff
+/// 4xx responses (ValidationError) and other API errors are never retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Each delay is randomized by up to this fraction either way (0.2 = ±20%)
+    pub jitter: f64,
+    pub retry_timeouts: bool,
+    pub retry_server_errors: bool,
+    pub retry_rate_limits: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: 0.2,
+            retry_timeouts: true,
+            retry_server_errors: true,
+            retry_rate_limits: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Same error classification, no waiting between attempts (tests)
+    #[allow(dead_code)]
+    pub fn no_delay(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// Load from {PREFIX}_RETRY_ATTEMPTS / {PREFIX}_RETRY_BASE_DELAY_MS (defaults otherwise)
+    pub fn from_env(prefix: &str) -> Self {
+        let mut policy = Self::default();
+        if let Some(attempts) = std::env::var(format!("{}_RETRY_ATTEMPTS", prefix)).ok().and_then(|v| v.parse::<u32>().ok()) {
+            policy.max_attempts = attempts.max(1);
+        }
+        if let Some(ms) = std::env::var(format!("{}_RETRY_BASE_DELAY_MS", prefix)).ok().and_then(|v| v.parse::<u64>().ok()) {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        policy
+    }
+
+    pub fn is_retryable(&self, error: &AppError) -> bool {
+        match error {
+            AppError::Timeout(_) => self.retry_timeouts,
+            AppError::Network(_) => self.retry_server_errors,
+            AppError::RateLimited(_) => self.retry_rate_limits,
+            _ => false,
+        }
+    }
+
+    /// Backoff before retry number `retry` (1-based): base * 2^(retry-1), capped, then jittered
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let exponential = self.base_delay.as_millis() as f64 * 2f64.powi(retry.saturating_sub(1) as i32);
+        let capped = exponential.min(self.max_delay.as_millis() as f64);
+        let jitter = if self.jitter > 0.0 {
+            use rand::Rng;
+            rand::thread_rng().gen_range(-self.jitter..=self.jitter)
+        } else {
+            0.0
+        };
+        Duration::from_millis((capped * (1.0 + jitter)).max(0.0) as u64)
+    }
+
+    /// Run `operation` until it succeeds, fails with a non-retryable error, or runs out of attempts
+    pub async fn run<F, Fut, T>(&self, operation_name: &str, mut operation: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, AppError>>,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match operation().await {
+                Ok(result) => {
+                    if attempt > 1 {
+                        log::info!("✅ {} succeeded after {} retries", operation_name, attempt - 1);
+                    }
+                    return Ok(result);
+                }
+                Err(e) if !self.is_retryable(&e) => {
+                    if attempt > 1 {
+                        log::warn!("⚠️ {} failed with non-retryable error after {} retries: {}", operation_name, attempt - 1, e);
+                    }
+                    return Err(e);
+                }
+                Err(e) if attempt >= max_attempts => {
+                    log::error!("❌ {} failed after {} attempts: {}", operation_name, attempt, e);
+                    return Err(e);
+                }
+                Err(e) => {
+                    let delay = self.delay_for(attempt);
+                    log::warn!("⚠️ {} attempt {}/{} failed: {}. Retrying in {:?}...",
+                              operation_name, attempt, max_attempts, e, delay);
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+}
+
 /// Determine if an error is retryable
-pub fn is_retryable_error(error: &TradingError) -> bool {
-    matches!(
-        error,
-        TradingError::NetworkError(_) | TradingError::TimeoutError(_) | TradingError::RateLimitExceeded(_)
-    )
+pub fn is_retryable_error(error: &AppError) -> bool {
+    error.is_retryable()
 }
 
-/// Helper function to map HTTP status codes to TradingError
-pub fn map_http_status_to_error(status: u16, error_text: String) -> TradingError {
+/// Helper function to map HTTP status codes to AppError
+pub fn map_http_status_to_error(status: u16, error_text: String) -> AppError {
     match status {
-        429 => TradingError::RateLimitExceeded(format!("Rate limit exceeded: {}", error_text)),
-        408 | 504 | 503 => TradingError::TimeoutError(format!("HTTP {}: {}", status, error_text)),
-        500..=599 => TradingError::NetworkError(format!("Server error {}: {}", status, error_text)),
-        400..=499 => TradingError::ValidationError(format!("Client error {}: {}", status, error_text)),
-        _ => TradingError::ApiError(format!("HTTP {}: {}", status, error_text)),
+        429 => AppError::RateLimited(format!("Rate limit exceeded: {}", error_text)),
+        408 | 504 | 503 => AppError::Timeout(format!("HTTP {}: {}", status, error_text)),
+        500..=599 => AppError::Network(format!("Server error {}: {}", status, error_text)),
+        404 => AppError::NotFound(format!("HTTP 404: {}", error_text)),
+        400..=499 => AppError::Validation(format!("Client error {}: {}", status, error_text)),
+        _ => AppError::Api(format!("HTTP {}: {}", status, error_text)),
     }
 }
 
@@ -210,6 +403,21 @@ pub struct CircuitBreaker {
     successes: tokio::sync::Mutex<u32>,
     state: tokio::sync::Mutex<CircuitState>,
     last_failure_time: tokio::sync::Mutex<Option<std::time::Instant>>,
+    total_calls: AtomicU64,
+    successful_calls: AtomicU64,
+    failed_calls: AtomicU64,
+    last_state_change: tokio::sync::Mutex<Option<i64>>, // Unix timestamp of the last state transition
+    notifier: Option<std::sync::Arc<dyn crate::notifications::Notifier>>, // Alerted when the breaker opens
+}
+
+/// Lifetime counters exposed for monitoring
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CircuitBreakerStats {
+    pub total_calls: u64,      // Calls that were executed (rejected calls while OPEN are not counted)
+    pub successful_calls: u64,
+    pub failed_calls: u64,
+    pub consecutive_failures: u32,
+    pub last_state_change: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -229,13 +437,31 @@ impl CircuitBreaker {
             successes: tokio::sync::Mutex::new(0),
             state: tokio::sync::Mutex::new(CircuitState::Closed),
             last_failure_time: tokio::sync::Mutex::new(None),
+            total_calls: AtomicU64::new(0),
+            successful_calls: AtomicU64::new(0),
+            failed_calls: AtomicU64::new(0),
+            last_state_change: tokio::sync::Mutex::new(None),
+            notifier: None,
         }
     }
 
-    pub async fn call<F, T, E>(&self, operation: F) -> Result<T, TradingError>
+    /// Send a circuit-breaker alert whenever the breaker opens
+    pub fn with_notifier(mut self, notifier: Option<std::sync::Arc<dyn crate::notifications::Notifier>>) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    async fn set_state(&self, new_state: CircuitState) {
+        *self.state.lock().await = new_state;
+        *self.last_state_change.lock().await = Some(chrono::Utc::now().timestamp());
+    }
+
+    /// Run `operation` unless the breaker is open - the error keeps its kind, and only
+    /// service-health failures (`AppError::trips_circuit`) count toward opening the breaker
+    pub async fn call<F, T, E>(&self, operation: F) -> Result<T, AppError>
     where
         F: std::future::Future<Output = Result<T, E>>,
-        E: Error + 'static,
+        E: Into<AppError>,
     {
         let state = self.state.lock().await.clone();
 
@@ -246,11 +472,11 @@ impl CircuitBreaker {
                 if let Some(time) = *last_failure {
                     if time.elapsed() >= self.timeout {
                         // Move to half-open state
-                        *self.state.lock().await = CircuitState::HalfOpen;
+                        self.set_state(CircuitState::HalfOpen).await;
                         log::info!("🔄 Circuit breaker moving to HALF-OPEN state");
                         drop(last_failure);
                     } else {
-                        return Err(TradingError::ApiError("Circuit breaker is OPEN".to_string()));
+                        return Err(AppError::CircuitOpen("Circuit breaker is OPEN".to_string()));
                     }
                 }
             }
@@ -263,19 +489,22 @@ impl CircuitBreaker {
         }
 
         // Execute operation
+        self.total_calls.fetch_add(1, Ordering::Relaxed);
         match operation.await {
             Ok(result) => {
                 self.on_success().await;
                 Ok(result)
             }
             Err(e) => {
-                self.on_failure().await;
-                Err(TradingError::ApiError(e.to_string()))
+                let e = e.into();
+                self.on_failure(e.trips_circuit()).await;
+                Err(e)
             }
         }
     }
 
     async fn on_success(&self) {
+        self.successful_calls.fetch_add(1, Ordering::Relaxed);
         let mut successes = self.successes.lock().await;
         *successes += 1;
 
@@ -288,27 +517,51 @@ impl CircuitBreaker {
         }
         
         if state == CircuitState::HalfOpen && *successes >= self.success_threshold {
-            *self.state.lock().await = CircuitState::Closed;
+            self.set_state(CircuitState::Closed).await;
             *self.failures.lock().await = 0;
             *successes = 0;
             log::info!("✅ Circuit breaker CLOSED - service recovered");
         }
     }
 
-    async fn on_failure(&self) {
+    async fn on_failure(&self, trips_circuit: bool) {
+        self.failed_calls.fetch_add(1, Ordering::Relaxed);
+        if !trips_circuit {
+            return;
+        }
         let mut failures = self.failures.lock().await;
         *failures += 1;
 
         if *failures >= self.failure_threshold {
-            *self.state.lock().await = CircuitState::Open;
+            let was_open = *self.state.lock().await == CircuitState::Open;
+            self.set_state(CircuitState::Open).await;
             *self.last_failure_time.lock().await = Some(std::time::Instant::now());
             log::error!("🚨 Circuit breaker OPEN - too many failures");
+            if !was_open {
+                crate::notifications::dispatch(&self.notifier, crate::notifications::Notification::new(
+                    crate::notifications::NotificationEvent::CircuitBreakerTripped,
+                    "⚡ Circuit breaker open",
+                    &format!("{} consecutive failures - external calls blocked for {}s",
+                             *failures, self.timeout.as_secs()),
+                ));
+            }
         }
     }
 
     pub async fn get_state(&self) -> CircuitState {
         self.state.lock().await.clone()
     }
+
+    /// Snapshot of call counters for metrics scraping
+    pub async fn get_stats(&self) -> CircuitBreakerStats {
+        CircuitBreakerStats {
+            total_calls: self.total_calls.load(Ordering::Relaxed),
+            successful_calls: self.successful_calls.load(Ordering::Relaxed),
+            failed_calls: self.failed_calls.load(Ordering::Relaxed),
+            consecutive_failures: *self.failures.lock().await,
+            last_state_change: *self.last_state_change.lock().await,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -321,6 +574,44 @@ mod tests {
         assert_eq!(config.max_attempts, 3);
     }
 
+    #[tokio::test]
+    async fn test_retry_policy_retries_transient_errors_only() {
+        let policy = RetryPolicy::no_delay(3);
+
+        // 503 twice, then success
+        let mut calls = 0;
+        let result = policy.run("flaky", || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 { Err(map_http_status_to_error(503, "unavailable".to_string())) } else { Ok(attempt) }
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 3);
+
+        // 400 fails immediately
+        let mut calls = 0;
+        let result: Result<(), _> = policy.run("bad request", || {
+            calls += 1;
+            async { Err(map_http_status_to_error(400, "bad mint".to_string())) }
+        }).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert_eq!(calls, 1);
+
+        // Timeouts stop at max_attempts
+        let mut calls = 0;
+        let result: Result<(), _> = policy.run("timeout", || {
+            calls += 1;
+            async { Err(AppError::Timeout("slow".to_string())) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        let no_timeouts = RetryPolicy { retry_timeouts: false, ..RetryPolicy::no_delay(3) };
+        assert!(!no_timeouts.is_retryable(&AppError::Timeout("slow".to_string())));
+        assert_eq!(RetryPolicy { jitter: 0.0, ..RetryPolicy::default() }.delay_for(3), Duration::from_millis(800));
+    }
+
     #[test]
     fn test_retry_config_aggressive() {
         let config = RetryConfig::aggressive();
@@ -329,24 +620,24 @@ mod tests {
 
     #[test]
     fn test_is_retryable_error() {
-        assert!(is_retryable_error(&TradingError::NetworkError("test".to_string())));
-        assert!(is_retryable_error(&TradingError::TimeoutError("test".to_string())));
-        assert!(!is_retryable_error(&TradingError::ValidationError("test".to_string())));
+        assert!(is_retryable_error(&AppError::Network("test".to_string())));
+        assert!(is_retryable_error(&AppError::Timeout("test".to_string())));
+        assert!(!is_retryable_error(&AppError::Validation("test".to_string())));
     }
 
     #[test]
     fn test_map_http_status_to_error() {
         assert!(matches!(
             map_http_status_to_error(429, "test".to_string()),
-            TradingError::RateLimitExceeded(_)
+            AppError::RateLimited(_)
         ));
         assert!(matches!(
             map_http_status_to_error(500, "test".to_string()),
-            TradingError::NetworkError(_)
+            AppError::Network(_)
         ));
         assert!(matches!(
             map_http_status_to_error(400, "test".to_string()),
-            TradingError::ValidationError(_)
+            AppError::Validation(_)
         ));
     }
 
@@ -355,4 +646,64 @@ mod tests {
         let cb = CircuitBreaker::new(3, 2, Duration::from_secs(1));
         assert_eq!(cb.get_state().await, CircuitState::Closed);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_circuit_breaker_alerts_once_when_it_opens() {
+        struct CountingNotifier(std::sync::atomic::AtomicU32);
+        #[async_trait::async_trait]
+        impl crate::notifications::Notifier for CountingNotifier {
+            fn name(&self) -> &str {
+                "counting"
+            }
+            async fn send(&self, notification: &crate::notifications::Notification) -> crate::notifications::DeliveryResult {
+                assert_eq!(notification.event, crate::notifications::NotificationEvent::CircuitBreakerTripped);
+                self.0.fetch_add(1, Ordering::SeqCst);
+                crate::notifications::DeliveryResult { delivered: true, status_code: None, error: None }
+            }
+        }
+        let notifier = std::sync::Arc::new(CountingNotifier(std::sync::atomic::AtomicU32::new(0)));
+        let cb = CircuitBreaker::new(2, 1, Duration::from_secs(60)).with_notifier(Some(notifier.clone()));
+        for _ in 0..4 {
+            let _ = cb.call(async { Err::<i32, _>(AppError::Network("down".to_string())) }).await;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(cb.get_state().await, CircuitState::Open);
+        assert_eq!(notifier.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_stats() {
+        let cb = CircuitBreaker::new(2, 1, Duration::from_secs(60));
+        let _ = cb.call(async { Ok::<_, AppError>(1) }).await;
+        let _ = cb.call(async { Err::<i32, _>(AppError::Network("down".to_string())) }).await;
+        let _ = cb.call(async { Err::<i32, _>(AppError::Network("down".to_string())) }).await;
+        // Rejected while OPEN - not executed, not counted
+        assert!(cb.call(async { Ok::<_, AppError>(1) }).await.is_err());
+
+        let stats = cb.get_stats().await;
+        assert_eq!(cb.get_state().await, CircuitState::Open);
+        assert_eq!(stats.total_calls, 3);
+        assert_eq!(stats.successful_calls, 1);
+        assert_eq!(stats.failed_calls, 2);
+        assert_eq!(stats.consecutive_failures, 2);
+        assert!(stats.last_state_change.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_keeps_error_kind() {
+        let cb = CircuitBreaker::new(2, 1, Duration::from_secs(60));
+        // Bad input is the caller's fault - it fails the call but never opens the breaker
+        for _ in 0..3 {
+            let result = cb.call(async { Err::<i32, _>(AppError::NotFound("no pair".to_string())) }).await;
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+        }
+        assert_eq!(cb.get_state().await, CircuitState::Closed);
+
+        let _ = cb.call(async { Err::<i32, _>(AppError::RateLimited("429".to_string())) }).await;
+        let _ = cb.call(async { Err::<i32, _>(AppError::Timeout("slow".to_string())) }).await;
+        let result = cb.call(async { Ok::<_, AppError>(1) }).await;
+        assert!(matches!(result, Err(AppError::CircuitOpen(_))));
+        assert!(!AppError::CircuitOpen(String::new()).is_retryable());
+        assert_eq!(AppError::Oracle("stale".to_string()).to_string(), "Oracle error: stale");
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 
 /// Fee estimation based on network congestion
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +23,159 @@ pub enum CongestionLevel {
     Extreme,
 }
 
+/// Solana's per-signature base fee
+pub const DEFAULT_BASE_FEE_LAMPORTS: u64 = 5000;
+/// Seconds between getRecentPrioritizationFees refreshes
+pub const DEFAULT_PRIORITY_FEE_REFRESH_SECS: u64 = 30;
+/// Ceiling on the compute-unit price (micro-lamports) a congestion spike can push a swap to
+pub const DEFAULT_MAX_COMPUTE_UNIT_PRICE: u64 = 2_000_000;
+/// A network estimate older than this many refresh intervals is ignored
+const PRIORITY_FEE_STALE_INTERVALS: i64 = 3;
+
+/// Base fee and network priority-fee settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityFeeConfig {
+    pub base_fee_lamports: u64,
+    pub refresh_secs: u64, // 0 = never query the network
+    pub max_compute_unit_price: u64,
+    pub compute_unit_limit: u32, // CUs a swap requests - the priority fee is paid on all of them
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            base_fee_lamports: DEFAULT_BASE_FEE_LAMPORTS,
+            refresh_secs: DEFAULT_PRIORITY_FEE_REFRESH_SECS,
+            max_compute_unit_price: DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+            compute_unit_limit: crate::jupiter_integration::DEFAULT_COMPUTE_UNIT_LIMIT,
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    /// Load from FEE_BASE_LAMPORTS / PRIORITY_FEE_REFRESH_SECS / PRIORITY_FEE_MAX_MICRO_LAMPORTS,
+    /// and the swap limit from SWAP_COMPUTE_UNIT_LIMIT (the same ComputeUnitConfig the swaps are sent with)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            base_fee_lamports: std::env::var("FEE_BASE_LAMPORTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.base_fee_lamports),
+            refresh_secs: std::env::var("PRIORITY_FEE_REFRESH_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.refresh_secs),
+            max_compute_unit_price: std::env::var("PRIORITY_FEE_MAX_MICRO_LAMPORTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_compute_unit_price),
+            compute_unit_limit: crate::jupiter_integration::ComputeUnitConfig::from_env().default_limit,
+        }
+    }
+}
+
+/// Compute-unit prices (micro-lamports) recent slots paid to land, by priority
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkPriorityFee {
+    pub low: u64,    // 25th percentile
+    pub normal: u64, // Median
+    pub high: u64,   // 75th percentile
+    pub samples: usize,
+    pub updated_at: i64,
+}
+
+impl NetworkPriorityFee {
+    /// Percentiles of per-slot prioritization fees, each capped at `max_price` (None without samples)
+    pub fn from_samples(samples: &[u64], max_price: u64, now: i64) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let percentile = |pct: usize| sorted[(sorted.len() - 1) * pct / 100].min(max_price);
+        Some(Self {
+            low: percentile(25),
+            normal: percentile(50),
+            high: percentile(75),
+            samples: sorted.len(),
+            updated_at: now,
+        })
+    }
+
+    pub fn compute_unit_price(&self, priority: FeePriority) -> u64 {
+        match priority {
+            FeePriority::Low => self.low,
+            FeePriority::Normal => self.normal,
+            FeePriority::High => self.high,
+        }
+    }
+}
+
+/// Lamports a swap requesting `compute_units` pays on top of the base fee at `compute_unit_price` micro-lamports per CU
+pub fn priority_fee_lamports(compute_unit_price: u64, compute_units: u32) -> u64 {
+    compute_unit_price.saturating_mul(compute_units as u64) / 1_000_000
+}
+
+/// Current fee picture, for GET /fees/status
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeStatus {
+    pub base_fee_lamports: u64,
+    pub network_priority_fee: Option<NetworkPriorityFee>,
+    pub network_estimate_fresh: bool,
+    pub refresh_secs: u64,
+    pub max_compute_unit_price: u64,
+    pub compute_unit_price: std::collections::HashMap<String, Option<u64>>,
+    pub estimates: std::collections::HashMap<String, FeeEstimate>,
+    pub stats: FeeStats,
+    pub budget: FeeBudgetStatus,
+}
+
+/// Default rolling window for the fee budget
+pub const DEFAULT_FEE_BUDGET_WINDOW_SECS: i64 = 86_400;
+
+/// Cap on transaction fees spent over a rolling window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeBudget {
+    pub limit_lamports: Option<u64>, // None = unlimited
+    pub window_secs: i64,
+}
+
+impl Default for FeeBudget {
+    fn default() -> Self {
+        Self {
+            limit_lamports: None,
+            window_secs: DEFAULT_FEE_BUDGET_WINDOW_SECS,
+        }
+    }
+}
+
+impl FeeBudget {
+    /// Load from FEE_BUDGET_LAMPORTS / FEE_BUDGET_WINDOW_SECS (no budget unless FEE_BUDGET_LAMPORTS is set)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            limit_lamports: std::env::var("FEE_BUDGET_LAMPORTS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            window_secs: std::env::var("FEE_BUDGET_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.window_secs),
+        }
+    }
+}
+
+/// Fee budget usage, for the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeBudgetStatus {
+    pub limit_lamports: Option<u64>,
+    pub window_secs: i64,
+    pub spent_lamports: u64,
+    pub remaining_lamports: Option<u64>,
+    pub exhausted: bool,
+}
+
 /// Fee optimizer that tracks network conditions
 #[derive(Debug)]
 pub struct FeeOptimizer {
@@ -28,6 +183,10 @@ pub struct FeeOptimizer {
     recent_confirmations: VecDeque<(u64, Duration)>, // (fee, confirmation_time)
     max_history: usize,
     base_fee: u64, // Base fee in lamports (5000 = 0.000005 SOL)
+    budget: FeeBudget,
+    fees_spent: VecDeque<(i64, u64)>, // (timestamp, lamports) inside the budget window
+    priority_fees: PriorityFeeConfig,
+    network_priority_fee: Option<NetworkPriorityFee>, // Latest getRecentPrioritizationFees reading
 }
 
 impl FeeOptimizer {
@@ -37,6 +196,106 @@ impl FeeOptimizer {
             recent_confirmations: VecDeque::with_capacity(100),
             max_history: 100,
             base_fee,
+            budget: FeeBudget::default(),
+            fees_spent: VecDeque::new(),
+            priority_fees: PriorityFeeConfig { base_fee_lamports: base_fee, ..PriorityFeeConfig::default() },
+            network_priority_fee: None,
+        }
+    }
+
+    /// Price swaps from the network's recent prioritization fees (see refresh_priority_fee)
+    pub fn with_priority_fees(mut self, config: PriorityFeeConfig) -> Self {
+        self.base_fee = config.base_fee_lamports;
+        self.priority_fees = config;
+        self
+    }
+
+    pub fn priority_fee_config(&self) -> &PriorityFeeConfig {
+        &self.priority_fees
+    }
+
+    /// Replace the network estimate with percentiles of fresh per-slot samples
+    pub fn update_network_priority_fee(&mut self, samples: &[u64], now: i64) -> Option<NetworkPriorityFee> {
+        let estimate = NetworkPriorityFee::from_samples(samples, self.priority_fees.max_compute_unit_price, now)?;
+        self.network_priority_fee = Some(estimate.clone());
+        Some(estimate)
+    }
+
+    /// Network estimate, unless refreshes have stopped landing
+    pub fn network_priority_fee(&self, now: i64) -> Option<&NetworkPriorityFee> {
+        let max_age = (self.priority_fees.refresh_secs.max(1) as i64) * PRIORITY_FEE_STALE_INTERVALS;
+        self.network_priority_fee.as_ref()
+            .filter(|estimate| now - estimate.updated_at <= max_age)
+    }
+
+    /// Compute-unit price (micro-lamports) a swap should attach - None until the network has been sampled
+    pub fn compute_unit_price(&self, priority: FeePriority, now: i64) -> Option<u64> {
+        self.network_priority_fee(now).map(|estimate| estimate.compute_unit_price(priority))
+    }
+
+    /// Enforce a fee budget on top of estimation
+    pub fn with_budget(mut self, budget: FeeBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Record a fee actually paid so it counts against the budget
+    pub fn record_fee_spent(&mut self, fee: u64, now: i64) {
+        self.fees_spent.push_back((now, fee));
+        self.purge_spent(now);
+    }
+
+    /// Replace an estimated fee already counted against the budget with what the transaction paid
+    pub fn correct_fee_spent(&mut self, estimated: u64, realized: u64, now: i64) {
+        if realized > estimated {
+            self.record_fee_spent(realized - estimated, now);
+            return;
+        }
+        // Overestimated - refund the difference from the newest entries
+        let mut refund = estimated - realized;
+        for (_, fee) in self.fees_spent.iter_mut().rev() {
+            if refund == 0 {
+                break;
+            }
+            let taken = refund.min(*fee);
+            *fee -= taken;
+            refund -= taken;
+        }
+    }
+
+    fn purge_spent(&mut self, now: i64) {
+        let window = self.budget.window_secs;
+        while self.fees_spent.front().is_some_and(|(at, _)| now - at >= window) {
+            self.fees_spent.pop_front();
+        }
+    }
+
+    fn spent_in_window(&self, now: i64) -> u64 {
+        self.fees_spent.iter()
+            .filter(|(at, _)| now - at < self.budget.window_secs)
+            .map(|(_, fee)| fee)
+            .sum()
+    }
+
+    /// Whether paying `estimated` lamports now stays within the fee budget
+    pub fn can_afford_fee(&self, estimated: u64) -> bool {
+        self.can_afford_fee_at(estimated, chrono::Utc::now().timestamp())
+    }
+
+    pub fn can_afford_fee_at(&self, estimated: u64, now: i64) -> bool {
+        self.budget.limit_lamports
+            .is_none_or(|limit| self.spent_in_window(now) + estimated <= limit)
+    }
+
+    pub fn budget_status(&self, now: i64) -> FeeBudgetStatus {
+        let spent_lamports = self.spent_in_window(now);
+        FeeBudgetStatus {
+            limit_lamports: self.budget.limit_lamports,
+            window_secs: self.budget.window_secs,
+            spent_lamports,
+            remaining_lamports: self.budget.limit_lamports.map(|limit| limit.saturating_sub(spent_lamports)),
+            // Exhausted once even a base-fee transaction no longer fits
+            exhausted: !self.can_afford_fee_at(self.base_fee, now),
         }
     }
 
@@ -57,8 +316,28 @@ impl FeeOptimizer {
                    fee, confirmation_time);
     }
 
-    /// Get current fee estimate based on recent transactions
+    /// Base network fee in lamports
+    pub fn base_fee(&self) -> u64 {
+        self.base_fee
+    }
+
+    /// Get current fee estimate - from the network's priority fees when fresh, else recent transactions
     pub fn estimate_fee(&self, priority: FeePriority) -> FeeEstimate {
+        self.estimate_fee_at(priority, chrono::Utc::now().timestamp())
+    }
+
+    pub fn estimate_fee_at(&self, priority: FeePriority, now: i64) -> FeeEstimate {
+        if let Some(network) = self.network_priority_fee(now) {
+            let units = self.priority_fees.compute_unit_limit;
+            return FeeEstimate {
+                min_fee: self.base_fee,
+                recommended_fee: self.base_fee + priority_fee_lamports(network.compute_unit_price(priority), units),
+                priority_fee: self.base_fee + priority_fee_lamports(network.high, units),
+                max_fee: self.base_fee + priority_fee_lamports(self.priority_fees.max_compute_unit_price, units),
+                confidence: 0.9,
+            };
+        }
+
         if self.recent_fees.is_empty() {
             return self.default_estimate();
         }
@@ -179,6 +458,25 @@ impl FeeOptimizer {
         }
     }
 
+    pub fn status(&self, now: i64) -> FeeStatus {
+        let priorities = [("low", FeePriority::Low), ("normal", FeePriority::Normal), ("high", FeePriority::High)];
+        FeeStatus {
+            base_fee_lamports: self.base_fee,
+            network_priority_fee: self.network_priority_fee.clone(),
+            network_estimate_fresh: self.network_priority_fee(now).is_some(),
+            refresh_secs: self.priority_fees.refresh_secs,
+            max_compute_unit_price: self.priority_fees.max_compute_unit_price,
+            compute_unit_price: priorities.iter()
+                .map(|(name, priority)| (name.to_string(), self.compute_unit_price(*priority, now)))
+                .collect(),
+            estimates: priorities.iter()
+                .map(|(name, priority)| (name.to_string(), self.estimate_fee_at(*priority, now)))
+                .collect(),
+            stats: self.get_stats(),
+            budget: self.budget_status(now),
+        }
+    }
+
     fn calculate_avg_confirmation_time(&self) -> Duration {
         if self.recent_confirmations.is_empty() {
             return Duration::from_secs(0);
@@ -210,6 +508,36 @@ pub struct FeeStats {
     pub avg_confirmation_time: String,
 }
 
+/// Pull the network's recent prioritization fees into the optimizer's estimate
+pub async fn refresh_priority_fee(
+    optimizer: &Arc<Mutex<FeeOptimizer>>,
+    rpc: &crate::rpc_client::SolanaRpcClient,
+) -> Result<NetworkPriorityFee, String> {
+    let samples = rpc.get_recent_prioritization_fees(&[]).await?;
+    optimizer.lock().await
+        .update_network_priority_fee(&samples, chrono::Utc::now().timestamp())
+        .ok_or_else(|| "RPC returned no recent prioritization fees".to_string())
+}
+
+/// Refresh the priority-fee estimate every `refresh_secs` until shutdown
+pub fn spawn_priority_fee_refresh(optimizer: Arc<Mutex<FeeOptimizer>>, rpc_url: String, refresh_secs: u64, shutdown: crate::shutdown::Shutdown) {
+    tokio::spawn(async move {
+        let rpc = crate::rpc_client::SolanaRpcClient::new(rpc_url);
+        let interval = Duration::from_secs(refresh_secs.max(1));
+        loop {
+            match refresh_priority_fee(&optimizer, &rpc).await {
+                Ok(estimate) => log::debug!("⛽ Priority fee: {} / {} / {} micro-lamports per CU (low / normal / high, {} slots)",
+                                           estimate.low, estimate.normal, estimate.high, estimate.samples),
+                Err(e) => log::warn!("⚠️ Priority fee refresh failed: {}", e),
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = shutdown.wait() => return,
+            }
+        }
+    });
+}
+
 impl From<CongestionLevel> for String {
     fn from(level: CongestionLevel) -> Self {
         match level {
@@ -253,6 +581,37 @@ mod tests {
         assert_eq!(optimizer.detect_congestion(), CongestionLevel::Low);
     }
 
+    #[test]
+    fn test_fee_budget_rolls_over_window() {
+        let mut optimizer = FeeOptimizer::new(5000).with_budget(FeeBudget {
+            limit_lamports: Some(20_000),
+            window_secs: 100,
+        });
+        assert!(optimizer.can_afford_fee_at(20_000, 0));
+
+        optimizer.record_fee_spent(10_000, 0);
+        optimizer.record_fee_spent(6_000, 50);
+        assert!(optimizer.can_afford_fee_at(4_000, 60));
+        assert!(!optimizer.can_afford_fee_at(5_000, 60));
+        let status = optimizer.budget_status(60);
+        assert_eq!(status.spent_lamports, 16_000);
+        assert_eq!(status.remaining_lamports, Some(4_000));
+        assert!(status.exhausted); // A base-fee transaction no longer fits
+
+        // The realized fee replaces the estimate in either direction
+        optimizer.correct_fee_spent(6_000, 1_000, 60);
+        assert_eq!(optimizer.budget_status(60).spent_lamports, 11_000);
+        optimizer.correct_fee_spent(1_000, 6_000, 60);
+        assert_eq!(optimizer.budget_status(60).spent_lamports, 16_000);
+
+        // The first fee leaves the window
+        assert!(optimizer.can_afford_fee_at(14_000, 100));
+        assert!(!optimizer.budget_status(100).exhausted);
+
+        // No budget configured - never blocks
+        assert!(FeeOptimizer::new(5000).can_afford_fee_at(u64::MAX / 2, 0));
+    }
+
     #[test]
     fn test_priority_fee_calculation() {
         let mut optimizer = FeeOptimizer::new(5000);
@@ -265,4 +624,31 @@ mod tests {
         assert!(low.recommended_fee <= normal.recommended_fee);
         assert!(normal.recommended_fee <= high.recommended_fee);
     }
+
+    #[test]
+    fn test_network_priority_fee_estimate() {
+        let mut optimizer = FeeOptimizer::new(5000).with_priority_fees(PriorityFeeConfig {
+            base_fee_lamports: 6000,
+            refresh_secs: 30,
+            max_compute_unit_price: 50_000,
+            compute_unit_limit: 200_000,
+        });
+        assert_eq!(optimizer.compute_unit_price(FeePriority::Normal, 0), None);
+
+        let samples = [0, 1_000, 2_000, 10_000, 20_000, 30_000, 500_000, 900_000, 1_000_000];
+        let estimate = optimizer.update_network_priority_fee(&samples, 1_000).unwrap();
+        assert_eq!((estimate.low, estimate.normal, estimate.high), (2_000, 20_000, 50_000)); // High capped
+        assert_eq!(optimizer.compute_unit_price(FeePriority::Normal, 1_010), Some(20_000));
+
+        // 20k micro-lamports x 200k CUs = 4000 lamports on top of the configured base fee
+        let normal = optimizer.estimate_fee_at(FeePriority::Normal, 1_010);
+        assert_eq!(normal.min_fee, 6000);
+        assert_eq!(normal.recommended_fee, 10_000);
+        assert_eq!(normal.priority_fee, 16_000);
+
+        // Stale after three missed refreshes - back to the history-based estimate
+        assert!(optimizer.compute_unit_price(FeePriority::Normal, 1_091).is_none());
+        assert_eq!(optimizer.estimate_fee_at(FeePriority::Normal, 1_091).recommended_fee, 12_000);
+        assert!(optimizer.update_network_priority_fee(&[], 1_100).is_none());
+    }
 }
@@ -0,0 +1,185 @@
+//! Readiness Checks
+//! /health probes every downstream dependency instead of only proving the process is up;
+//! any critical dependency being down turns the response into a 503 for load balancers.
+//! Reports are cached briefly so frequent load-balancer polls don't each hit RPC and Jupiter
+
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long one dependency probe may take before it counts as down
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT_SECS: u64 = 3;
+
+/// How long a readiness report is reused before the dependencies are probed again
+pub const DEFAULT_HEALTH_CACHE_SECS: u64 = 10;
+
+/// Detail of a dependency this deployment doesn't use - reported healthy, since nothing is down
+pub const NOT_CONFIGURED: &str = "not configured";
+
+/// Outcome of probing a single dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub healthy: bool,
+    /// A critical dependency being down makes the whole service unready
+    pub critical: bool,
+    pub latency_ms: u64,
+    pub detail: Option<String>,
+}
+
+impl DependencyStatus {
+    /// Run `check` under `timeout` - a hung dependency is reported as down, not waited on
+    pub async fn probe<F>(name: &str, critical: bool, timeout: Duration, check: F) -> Self
+    where
+        F: Future<Output = Result<Option<String>, String>>,
+    {
+        let start = Instant::now();
+        let (healthy, detail) = match tokio::time::timeout(timeout, check).await {
+            Ok(Ok(detail)) => (true, detail),
+            Ok(Err(e)) => (false, Some(e)),
+            Err(_) => (false, Some(format!("No response within {}s", timeout.as_secs()))),
+        };
+        Self {
+            name: name.to_string(),
+            healthy,
+            critical,
+            latency_ms: start.elapsed().as_millis() as u64,
+            detail,
+        }
+    }
+}
+
+/// Status of every dependency plus the verdict a load balancer acts on
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// "healthy" (all up), "degraded" (only optional ones down) or "unhealthy" (a critical one down)
+    pub status: String,
+    pub dependencies: Vec<DependencyStatus>,
+    /// When the dependencies were probed - a cached report can be up to HEALTH_CACHE_SECS old
+    pub checked_at: i64,
+}
+
+impl ReadinessReport {
+    pub fn new(dependencies: Vec<DependencyStatus>) -> Self {
+        let status = if dependencies.iter().any(|d| d.critical && !d.healthy) {
+            "unhealthy"
+        } else if dependencies.iter().any(|d| !d.healthy) {
+            "degraded"
+        } else {
+            "healthy"
+        };
+        Self {
+            status: status.to_string(),
+            dependencies,
+            checked_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// False when a critical dependency is down - /health answers 503
+    pub fn is_ready(&self) -> bool {
+        self.status != "unhealthy"
+    }
+
+    /// HEALTH_CHECK_TIMEOUT_SECS (default 3)
+    pub fn timeout_from_env() -> Duration {
+        let secs = std::env::var("HEALTH_CHECK_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT_SECS);
+        Duration::from_secs(secs)
+    }
+}
+
+/// Latest readiness report, shared by every /health request while it is fresh
+#[derive(Debug, Clone)]
+pub struct ReadinessCache {
+    ttl: Duration,
+    last: Arc<Mutex<Option<(Instant, ReadinessReport)>>>,
+}
+
+impl ReadinessCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// HEALTH_CACHE_SECS (default 10, 0 probes on every request)
+    pub fn from_env() -> Self {
+        let secs = std::env::var("HEALTH_CACHE_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_HEALTH_CACHE_SECS);
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// The cached report while it is younger than the TTL, otherwise a fresh one from `probe`
+    /// Requests arriving mid-probe wait for it and share its result rather than probing again
+    pub async fn get_or_probe<F, Fut>(&self, probe: F) -> ReadinessReport
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ReadinessReport>,
+    {
+        let mut last = self.last.lock().await;
+        if let Some((probed_at, ref report)) = *last {
+            if probed_at.elapsed() < self.ttl {
+                return report.clone();
+            }
+        }
+        let report = probe().await;
+        *last = Some((Instant::now(), report.clone()));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_readiness_verdict() {
+        let timeout = Duration::from_millis(50);
+        let up = DependencyStatus::probe("rpc", true, timeout, async { Ok(Some("slot 1".to_string())) }).await;
+        let optional_down = DependencyStatus::probe("twitter", false, timeout, async { Err("refused".to_string()) }).await;
+        let hung = DependencyStatus::probe("jupiter", true, timeout, async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(None)
+        }).await;
+        assert!(up.healthy);
+        assert!(!hung.healthy);
+
+        assert_eq!(ReadinessReport::new(vec![up.clone()]).status, "healthy");
+        let degraded = ReadinessReport::new(vec![up.clone(), optional_down.clone()]);
+        assert_eq!(degraded.status, "degraded");
+        assert!(degraded.is_ready());
+        let unhealthy = ReadinessReport::new(vec![up, optional_down, hung]);
+        assert_eq!(unhealthy.status, "unhealthy");
+        assert!(!unhealthy.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_readiness_cache_reuses_fresh_report() {
+        let probes = std::sync::atomic::AtomicUsize::new(0);
+        let probe = || async {
+            probes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            ReadinessReport::new(vec![
+                DependencyStatus::probe("database", true, Duration::from_millis(50), async { Ok(Some(NOT_CONFIGURED.to_string())) }).await,
+            ])
+        };
+
+        let cache = ReadinessCache::new(Duration::from_secs(60));
+        assert!(cache.get_or_probe(probe).await.is_ready());
+        cache.get_or_probe(probe).await;
+        assert_eq!(probes.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A zero TTL probes every time
+        let uncached = ReadinessCache::new(Duration::ZERO);
+        uncached.get_or_probe(probe).await;
+        uncached.get_or_probe(probe).await;
+        assert_eq!(probes.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+}
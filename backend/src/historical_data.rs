@@ -316,6 +316,276 @@ impl HistoricalDataManager {
     }
 }
 
+/// Speed-up applied to the gaps between replayed events by default (a day replays in ~9s)
+pub const DEFAULT_REPLAY_SPEED: f64 = 10_000.0;
+/// Session file used when SESSION_FILE is unset
+pub const DEFAULT_SESSION_FILE: &str = "session.jsonl";
+
+/// One recorded engine input - a session file holds one JSON event per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SessionEvent {
+    Feed {
+        recorded_at_ms: i64,
+        feed: crate::switchboard_oracle::OracleFeed,
+    },
+    Signal {
+        recorded_at_ms: i64,
+        signal: crate::signal_platform::TradingSignalData,
+    },
+}
+
+impl SessionEvent {
+    pub fn recorded_at_ms(&self) -> i64 {
+        match self {
+            SessionEvent::Feed { recorded_at_ms, .. } | SessionEvent::Signal { recorded_at_ms, .. } => *recorded_at_ms,
+        }
+    }
+}
+
+/// Where engine inputs come from (SESSION_MODE=record|replay, SESSION_FILE, REPLAY_SPEED)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionMode {
+    Live,
+    /// Live, and every feed update and published signal is appended to the file
+    Record { path: std::path::PathBuf },
+    /// No network feeds or providers - the file drives the live feed and the executor
+    Replay { path: std::path::PathBuf, speed: f64 },
+}
+
+impl SessionMode {
+    pub fn from_env() -> Self {
+        let path = std::path::PathBuf::from(
+            std::env::var("SESSION_FILE").unwrap_or_else(|_| DEFAULT_SESSION_FILE.to_string())
+        );
+        match std::env::var("SESSION_MODE").map(|v| v.trim().to_lowercase()).as_deref() {
+            Ok("record") => SessionMode::Record { path },
+            Ok("replay") => {
+                let speed = std::env::var("REPLAY_SPEED")
+                    .ok()
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|speed| *speed > 0.0)
+                    .unwrap_or(DEFAULT_REPLAY_SPEED);
+                SessionMode::Replay { path, speed }
+            }
+            _ => SessionMode::Live,
+        }
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self, SessionMode::Replay { .. })
+    }
+}
+
+/// Cheap, cloneable handle shared between the live feed and the marketplace
+#[derive(Debug, Clone)]
+pub struct SessionRecorder {
+    file: std::sync::Arc<std::sync::Mutex<std::io::BufWriter<std::fs::File>>>,
+}
+
+impl SessionRecorder {
+    /// Append to `path` (created if missing)
+    pub fn create(path: &std::path::Path) -> Result<Self, String> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open session file {}: {}", path.display(), e))?;
+        Ok(Self {
+            file: std::sync::Arc::new(std::sync::Mutex::new(std::io::BufWriter::new(file))),
+        })
+    }
+
+    pub fn record_feed(&self, feed: &crate::switchboard_oracle::OracleFeed) {
+        self.record(&SessionEvent::Feed { recorded_at_ms: chrono::Utc::now().timestamp_millis(), feed: feed.clone() });
+    }
+
+    pub fn record_signal(&self, signal: &crate::signal_platform::TradingSignalData) {
+        self.record(&SessionEvent::Signal { recorded_at_ms: chrono::Utc::now().timestamp_millis(), signal: signal.clone() });
+    }
+
+    /// Flushed per event so a crash still leaves a replayable file
+    fn record(&self, event: &SessionEvent) {
+        use std::io::Write;
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("⚠️ Failed to serialize session event: {}", e);
+                return;
+            }
+        };
+        if let Ok(mut file) = self.file.lock() {
+            if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+                log::warn!("⚠️ Failed to record session event: {}", e);
+            }
+        }
+    }
+}
+
+/// Read a recorded session, ordered by record time
+pub fn load_session(path: &std::path::Path) -> Result<Vec<SessionEvent>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read session file {}: {}", path.display(), e))?;
+    let mut events = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: SessionEvent = serde_json::from_str(line)
+            .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e))?;
+        events.push(event);
+    }
+    events.sort_by_key(|event| event.recorded_at_ms()); // Stable - same-millisecond events keep file order
+    Ok(events)
+}
+
+/// Latest replayed price per symbol - oracle clients read these instead of the network
+#[derive(Debug, Clone, Default)]
+pub struct ReplayPrices {
+    prices: std::sync::Arc<std::sync::RwLock<std::collections::HashMap<String, crate::switchboard_oracle::OracleFeed>>>,
+}
+
+impl ReplayPrices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, feed: crate::switchboard_oracle::OracleFeed) {
+        if let Ok(mut prices) = self.prices.write() {
+            prices.insert(crate::symbol_registry::canonical(&feed.symbol), feed);
+        }
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<crate::switchboard_oracle::OracleFeed> {
+        self.prices.read().ok().and_then(|prices| prices.get(&crate::symbol_registry::canonical(symbol)).cloned())
+    }
+    
+    /// USD price of a known mint - stablecoins at $1, everything else from its replayed "SYMBOL/USD" feed
+    pub fn usd_price(&self, mint: &str) -> Option<f64> {
+        match crate::dex_aggregator::symbol_for_mint(mint)? {
+            "USDC" | "USDT" => Some(1.0),
+            symbol => self.get(symbol).map(|feed| feed.price).filter(|price| *price > 0.0),
+        }
+    }
+    
+    /// Raw output for swapping `amount` of `input_mint` at the replayed prices - no impact and no fees,
+    /// since a recording carries prices but not pool depth
+    pub fn convert(&self, input_mint: &str, output_mint: &str, amount: u64) -> Result<u64, String> {
+        let price = |mint: &str| self.usd_price(mint)
+            .ok_or_else(|| format!("No replayed price for mint {}", mint));
+        let decimals = |mint: &str| crate::dex_aggregator::symbol_for_mint(mint)
+            .and_then(crate::dex_aggregator::resolve_token)
+            .map(|(_, decimals)| decimals as i32)
+            .ok_or_else(|| format!("No decimals known for mint {}", mint));
+        
+        let usd = amount as f64 / 10f64.powi(decimals(input_mint)?) * price(input_mint)?;
+        Ok((usd / price(output_mint)? * 10f64.powi(decimals(output_mint)?)) as u64)
+    }
+}
+
+/// Time a replay is paced and stamped by - the wall clock when running live, a simulated one shared by
+/// the replayed feed, the executor and the tracker while replaying, a manual one in tests so a replay can
+/// be driven without sleeping
+#[async_trait::async_trait]
+pub trait ReplayClock: Send + Sync + std::fmt::Debug {
+    fn now_ms(&self) -> i64;
+    async fn sleep(&self, duration: std::time::Duration);
+    
+    fn now_secs(&self) -> i64 {
+        self.now_ms() / 1000
+    }
+}
+
+/// Real time: `Utc::now` and tokio sleeps
+#[derive(Debug)]
+pub struct WallClock;
+
+#[async_trait::async_trait]
+impl ReplayClock for WallClock {
+    fn now_ms(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+    
+    async fn sleep(&self, duration: std::time::Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Replay time: starts at the wall clock and runs `speed` times faster, so the feed's gaps and the loops'
+/// ticks compress together - an hour replayed at 60x still sees 120 executor ticks of 30s
+#[derive(Debug)]
+pub struct SimulatedClock {
+    origin_ms: i64,
+    started: std::time::Instant,
+    speed: f64,
+}
+
+impl SimulatedClock {
+    pub fn new(speed: f64) -> Self {
+        Self {
+            origin_ms: chrono::Utc::now().timestamp_millis(),
+            started: std::time::Instant::now(),
+            speed,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReplayClock for SimulatedClock {
+    fn now_ms(&self) -> i64 {
+        self.origin_ms + (self.started.elapsed().as_millis() as f64 * self.speed) as i64
+    }
+    
+    async fn sleep(&self, duration: std::time::Duration) {
+        tokio::time::sleep(duration.div_f64(self.speed)).await;
+    }
+}
+
+/// Recorded price paths per symbol plus the signals published alongside them - the price source for
+/// replaying how past signals would actually have played out
+#[derive(Debug, Clone, Default)]
+pub struct PriceHistory {
+    prices: std::collections::HashMap<String, Vec<(i64, f64)>>, // symbol -> (timestamp secs, price), ascending
+    pub signals: Vec<crate::signal_platform::TradingSignalData>,
+}
+
+impl PriceHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds are keyed by when the bot saw them, the same clock signals are stamped with
+    pub fn from_session(events: &[SessionEvent]) -> Self {
+        let mut history = Self::new();
+        for event in events {
+            match event {
+                SessionEvent::Feed { recorded_at_ms, feed } => history.insert(&feed.symbol, recorded_at_ms / 1000, feed.price),
+                SessionEvent::Signal { signal, .. } => history.signals.push(signal.clone()),
+            }
+        }
+        history
+    }
+
+    pub fn insert(&mut self, symbol: &str, timestamp: i64, price: f64) {
+        if !(price.is_finite() && price > 0.0) {
+            return;
+        }
+        let series = self.prices.entry(crate::symbol_registry::canonical(symbol)).or_default();
+        let at = series.partition_point(|(ts, _)| *ts <= timestamp);
+        series.insert(at, (timestamp, price));
+    }
+
+    /// Recorded prices with `from <= timestamp <= to`, oldest first - signal labels match their oracle feed
+    pub fn window(&self, symbol: &str, from: i64, to: i64) -> &[(i64, f64)] {
+        let Some(series) = self.prices.get(&crate::symbol_registry::canonical(symbol)) else {
+            return &[];
+        };
+        let start = series.partition_point(|(ts, _)| *ts < from);
+        let end = series.partition_point(|(ts, _)| *ts <= to);
+        &series[start..end.max(start)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,4 +703,57 @@ mod tests {
         assert!(features.volatility >= 0.0);
         assert!(features.data_points > 0);
     }
+    
+    #[test]
+    fn test_session_round_trip() {
+        let path = std::env::temp_dir().join(format!("session_{}.jsonl", uuid::Uuid::new_v4()));
+        let recorder = SessionRecorder::create(&path).unwrap();
+        let feed = crate::switchboard_oracle::OracleFeed {
+            feed_address: "test".to_string(),
+            symbol: "SOL/USD".to_string(),
+            price: 150.0,
+            confidence: 0.1,
+            timestamp: 1_700_000_000,
+            slot: 1,
+            min_price: 149.9,
+            max_price: 150.1,
+            price_change_24h: None,
+            stale: false,
+        };
+        recorder.record_feed(&feed);
+        recorder.record_feed(&crate::switchboard_oracle::OracleFeed { price: 151.0, ..feed });
+        
+        let events = load_session(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            SessionEvent::Feed { feed, .. } => assert_eq!(feed.price, 151.0),
+            other => panic!("expected a feed event, got {:?}", other),
+        }
+        assert!(events[0].recorded_at_ms() <= events[1].recorded_at_ms());
+    }
+    
+    #[test]
+    fn test_replay_prices_convert() {
+        use crate::dex_aggregator::{SOL_MINT, USDC_MINT};
+        
+        let prices = ReplayPrices::new();
+        assert!(prices.convert(USDC_MINT, SOL_MINT, 150_000_000).is_err());
+        
+        prices.set(crate::switchboard_oracle::OracleFeed {
+            feed_address: "test".to_string(),
+            symbol: "SOL/USD".to_string(),
+            price: 150.0,
+            confidence: 0.1,
+            timestamp: 1_700_000_000,
+            slot: 1,
+            min_price: 149.9,
+            max_price: 150.1,
+            price_change_24h: None,
+            stale: false,
+        });
+        // $150 of USDC buys 1 SOL, and 2 SOL sell for $300
+        assert_eq!(prices.convert(USDC_MINT, SOL_MINT, 150_000_000).unwrap(), 1_000_000_000);
+        assert_eq!(prices.convert(SOL_MINT, USDC_MINT, 2_000_000_000).unwrap(), 300_000_000);
+    }
 }
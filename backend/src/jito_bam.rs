@@ -13,11 +13,19 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use reqwest::Client;
 use solana_sdk::{
+    hash::Hash,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
     transaction::Transaction,
     pubkey::Pubkey,
 };
+use std::collections::VecDeque;
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::fee_optimization::FeePriority;
+
 /// Jito Block Engine endpoints
 pub const JITO_MAINNET_URL: &str = "https://mainnet.block-engine.jito.wtf";
 pub const JITO_DEVNET_URL: &str = "https://dallas.testnet.block-engine.jito.wtf";
@@ -34,6 +42,9 @@ pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
     "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
 ];
 
+/// Bundle outcomes remembered for the recent landing rate
+const LANDING_HISTORY_LEN: usize = 20;
+
 /// Bundle submission request
 #[derive(Debug, Serialize)]
 pub struct BundleRequest {
@@ -91,7 +102,12 @@ pub struct BundleStatusValue {
 /// Bundle configuration
 #[derive(Debug, Clone)]
 pub struct BundleConfig {
+    /// Base tip at normal priority with every recent bundle landing
     pub tip_amount_lamports: u64,
+    pub min_tip_lamports: u64,
+    pub max_tip_lamports: u64,
+    /// Tip increase (percent) each time a bundle fails to land and is rebuilt
+    pub tip_bump_pct: f64,
     pub max_retries: u32,
     pub timeout_ms: u64,
 }
@@ -100,18 +116,44 @@ impl Default for BundleConfig {
     fn default() -> Self {
         Self {
             tip_amount_lamports: 10_000, // 0.00001 SOL tip
+            min_tip_lamports: 1_000,
+            max_tip_lamports: 1_000_000, // 0.001 SOL
+            tip_bump_pct: 50.0,
             max_retries: 3,
             timeout_ms: 30_000,
         }
     }
 }
 
+impl BundleConfig {
+    /// Load from JITO_TIP_LAMPORTS / JITO_MIN_TIP_LAMPORTS / JITO_MAX_TIP_LAMPORTS / JITO_TIP_BUMP_PCT
+    pub fn from_env() -> Self {
+        let lamports = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        let defaults = Self::default();
+        let min_tip_lamports = lamports("JITO_MIN_TIP_LAMPORTS").unwrap_or(defaults.min_tip_lamports);
+        Self {
+            tip_amount_lamports: lamports("JITO_TIP_LAMPORTS").unwrap_or(defaults.tip_amount_lamports),
+            min_tip_lamports,
+            max_tip_lamports: lamports("JITO_MAX_TIP_LAMPORTS").unwrap_or(defaults.max_tip_lamports).max(min_tip_lamports),
+            tip_bump_pct: std::env::var("JITO_TIP_BUMP_PCT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|pct| *pct >= 0.0)
+                .unwrap_or(defaults.tip_bump_pct),
+            ..defaults
+        }
+    }
+}
+
 /// Jito BAM client for atomic bundle submission
+#[derive(Debug)]
 pub struct JitoBamClient {
     client: Client,
     pub block_engine_url: String,
     pub tip_accounts: Vec<Pubkey>,
     config: BundleConfig,
+    /// Recent bundle outcomes (true = landed), newest last
+    landing_history: Mutex<VecDeque<bool>>,
 }
 
 impl JitoBamClient {
@@ -133,6 +175,7 @@ impl JitoBamClient {
             block_engine_url,
             tip_accounts,
             config: BundleConfig::default(),
+            landing_history: Mutex::new(VecDeque::with_capacity(LANDING_HISTORY_LEN)),
         }
     }
 
@@ -148,7 +191,7 @@ impl JitoBamClient {
     pub async fn submit_bundle(
         &self,
         transactions: Vec<Transaction>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
         if transactions.is_empty() {
             return Err("Bundle must contain at least one transaction".into());
         }
@@ -191,7 +234,16 @@ impl JitoBamClient {
     pub async fn get_bundle_status(
         &self,
         bundle_id: &str,
-    ) -> Result<BundleStatus, Box<dyn Error>> {
+    ) -> Result<BundleStatus, Box<dyn Error + Send + Sync>> {
+        self.fetch_bundle_status(bundle_id).await?
+            .ok_or_else(|| "Bundle status not found".into())
+    }
+
+    /// None until the block engine has seen the bundle (right after submission)
+    async fn fetch_bundle_status(
+        &self,
+        bundle_id: &str,
+    ) -> Result<Option<BundleStatus>, Box<dyn Error + Send + Sync>> {
         let request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": Self::generate_request_id(),
@@ -209,30 +261,26 @@ impl JitoBamClient {
 
         let status_response: BundleStatusResponse = response.json().await?;
         
-        if let Some(bundle_status) = status_response.result.value.first() {
-            Ok(bundle_status.status.clone())
-        } else {
-            Err("Bundle status not found".into())
-        }
+        Ok(status_response.result.value.first().map(|bundle_status| bundle_status.status.clone()))
     }
 
     /// Wait for bundle to land (or fail)
     pub async fn wait_for_bundle(
         &self,
         bundle_id: &str,
-    ) -> Result<BundleStatus, Box<dyn Error>> {
+    ) -> Result<BundleStatus, Box<dyn Error + Send + Sync>> {
         let start = SystemTime::now();
         let timeout = std::time::Duration::from_millis(self.config.timeout_ms);
 
         loop {
-            let status = self.get_bundle_status(bundle_id).await?;
+            let status = self.fetch_bundle_status(bundle_id).await?;
             
             match status {
-                BundleStatus::Landed | BundleStatus::Failed | BundleStatus::Dropped => {
+                Some(status @ (BundleStatus::Landed | BundleStatus::Failed | BundleStatus::Dropped)) => {
                     return Ok(status);
                 }
-                BundleStatus::Pending | BundleStatus::Processing => {
-                    // Continue waiting
+                Some(BundleStatus::Pending | BundleStatus::Processing) | None => {
+                    // Continue waiting (None = not yet seen by the block engine)
                 }
             }
 
@@ -244,6 +292,64 @@ impl JitoBamClient {
         }
     }
 
+    /// Configured base tip (normal priority, everything landing)
+    pub fn tip_amount_lamports(&self) -> u64 {
+        self.config.tip_amount_lamports
+    }
+
+    pub fn config(&self) -> &BundleConfig {
+        &self.config
+    }
+
+    /// Tip for a bundle that should land at `priority` given how many recent bundles landed
+    /// Low priority tips half the base, high double; every point of landing rate lost adds 2% on top
+    /// (a 50% landing rate doubles the tip). Always within the configured min/max
+    pub fn compute_tip(&self, priority: FeePriority, recent_landing_rate: Option<f64>) -> u64 {
+        let priority_multiplier = match priority {
+            FeePriority::Low => 0.5,
+            FeePriority::Normal => 1.0,
+            FeePriority::High => 2.0,
+        };
+        let congestion_multiplier = recent_landing_rate.map_or(1.0, |rate| 1.0 + 2.0 * (1.0 - rate.clamp(0.0, 1.0)));
+        let tip = (self.config.tip_amount_lamports as f64 * priority_multiplier * congestion_multiplier).round() as u64;
+        tip.clamp(self.config.min_tip_lamports, self.config.max_tip_lamports)
+    }
+
+    /// Tip to send now at `priority`, from the live landing rate
+    pub fn effective_tip(&self, priority: FeePriority) -> u64 {
+        self.compute_tip(priority, self.recent_landing_rate())
+    }
+
+    /// Tip for the next attempt after a bundle failed to land - bumped by `tip_bump_pct`, up to the cap
+    pub fn bumped_tip(&self, tip_lamports: u64) -> u64 {
+        let bumped = (tip_lamports as f64 * (1.0 + self.config.tip_bump_pct / 100.0)).ceil() as u64;
+        bumped.clamp(self.config.min_tip_lamports, self.config.max_tip_lamports)
+    }
+
+    /// Highest tip a bundle starting at `tip_lamports` may go out with after every retry bumps it
+    /// Only the landing attempt pays, so this is the most one bundled swap can cost in tips
+    pub fn worst_case_tip(&self, tip_lamports: u64) -> u64 {
+        (1..self.config.max_retries).fold(tip_lamports, |tip, _| self.bumped_tip(tip))
+    }
+
+    /// Remember whether a bundle landed, for the landing rate
+    pub fn record_bundle_outcome(&self, landed: bool) {
+        let mut history = self.landing_history.lock().unwrap_or_else(|e| e.into_inner());
+        if history.len() >= LANDING_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(landed);
+    }
+
+    /// Share of the last LANDING_HISTORY_LEN bundles that landed (None before any settled)
+    pub fn recent_landing_rate(&self) -> Option<f64> {
+        let history = self.landing_history.lock().unwrap_or_else(|e| e.into_inner());
+        if history.is_empty() {
+            return None;
+        }
+        Some(history.iter().filter(|landed| **landed).count() as f64 / history.len() as f64)
+    }
+
     /// Get a random tip account for priority fees
     pub fn get_random_tip_account(&self) -> Option<&Pubkey> {
         use rand::seq::SliceRandom;
@@ -254,7 +360,7 @@ impl JitoBamClient {
     pub async fn submit_bundle_with_retry(
         &self,
         transactions: Vec<Transaction>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
         let mut last_error = None;
 
         for attempt in 1..=self.config.max_retries {
@@ -308,6 +414,13 @@ impl TradingBundleBuilder {
         self
     }
 
+    /// Append the tip transfer - it goes last so the tip is only paid if every trade in the bundle lands
+    pub fn add_tip(self, payer: &Keypair, tip_account: &Pubkey, tip_lamports: u64, recent_blockhash: Hash) -> Self {
+        let instruction = system_instruction::transfer(&payer.pubkey(), tip_account, tip_lamports);
+        let tip_tx = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer], recent_blockhash);
+        self.add_transaction(tip_tx)
+    }
+
     /// Build the final bundle
     pub fn build(self) -> Vec<Transaction> {
         log::info!(
@@ -337,6 +450,50 @@ mod tests {
         assert_eq!(bundle.len(), 0);
     }
 
+    #[test]
+    fn test_bundle_tip_goes_last() {
+        let client = JitoBamClient::new(true);
+        let payer = Keypair::new();
+        let tip_account = *client.get_random_tip_account().unwrap();
+        let swap_ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000);
+        let swap_tx = Transaction::new_signed_with_payer(&[swap_ix], Some(&payer.pubkey()), &[&payer], Hash::default());
+
+        let bundle = TradingBundleBuilder::new("Swap with tip")
+            .add_transaction(swap_tx)
+            .add_tip(&payer, &tip_account, client.tip_amount_lamports(), Hash::default())
+            .build();
+
+        assert_eq!(bundle.len(), 2);
+        let tip_tx = &bundle[1];
+        assert!(tip_tx.is_signed());
+        assert!(tip_tx.message.account_keys.contains(&tip_account));
+        assert!(!bundle[0].message.account_keys.contains(&tip_account));
+    }
+
+    #[test]
+    fn test_dynamic_tip() {
+        let client = JitoBamClient::new(true);
+        assert_eq!(client.recent_landing_rate(), None);
+        assert_eq!(client.effective_tip(FeePriority::Normal), 10_000);
+        assert_eq!(client.compute_tip(FeePriority::Low, None), 5_000);
+        assert_eq!(client.compute_tip(FeePriority::High, Some(0.5)), 40_000);
+        // Nothing landing triples the tip; retries bump it up to the cap
+        assert_eq!(client.compute_tip(FeePriority::High, Some(0.0)), 60_000);
+        assert_eq!(client.bumped_tip(900_000), 1_000_000);
+        // Three attempts at a 50% bump: 10k, 15k, 22.5k
+        assert_eq!(client.worst_case_tip(10_000), 22_500);
+        assert_eq!(client.worst_case_tip(900_000), 1_000_000);
+
+        client.record_bundle_outcome(true);
+        client.record_bundle_outcome(false);
+        assert_eq!(client.recent_landing_rate(), Some(0.5));
+        assert_eq!(client.effective_tip(FeePriority::Normal), 20_000);
+        for _ in 0..LANDING_HISTORY_LEN {
+            client.record_bundle_outcome(true);
+        }
+        assert_eq!(client.recent_landing_rate(), Some(1.0));
+    }
+
     #[test]
     fn test_config_default() {
         let config = BundleConfig::default();
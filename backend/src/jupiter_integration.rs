@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::sync::Arc;
 use crate::http_client::SharedHttpClient;
+use crate::error_handling::{map_http_status_to_error, RetryPolicy, AppError};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JupiterQuote {
@@ -38,12 +39,107 @@ pub struct QuoteRequest {
     pub slippage_bps: u16,
 }
 
+/// Compute units a swap transaction needs before any AMM work (SOL wrap/unwrap, token account setup)
+pub const SWAP_BASE_COMPUTE_UNITS: u32 = 80_000;
+/// Compute units budgeted for each hop of a route
+pub const DEFAULT_COMPUTE_UNITS_PER_HOP: u32 = 120_000;
+/// Compute-unit limit for swaps whose route can't be inspected
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 400_000;
+/// Most compute units a single Solana transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Compute-unit limit attached to swap transactions
+/// Too low and a multi-hop route fails with "exceeded CUs"; too high and every CU of priority fee is paid on the excess
+#[derive(Debug, Clone, Serialize)]
+pub struct ComputeUnitConfig {
+    /// Limit used when the route can't be estimated, or always when `estimate_from_route` is off
+    pub default_limit: u32,
+    /// Size the limit from the route: SWAP_BASE_COMPUTE_UNITS + `per_hop` for each hop
+    pub estimate_from_route: bool,
+    pub per_hop: u32,
+}
+
+impl Default for ComputeUnitConfig {
+    fn default() -> Self {
+        Self {
+            default_limit: DEFAULT_COMPUTE_UNIT_LIMIT,
+            estimate_from_route: true,
+            per_hop: DEFAULT_COMPUTE_UNITS_PER_HOP,
+        }
+    }
+}
+
+impl ComputeUnitConfig {
+    /// SWAP_COMPUTE_UNIT_LIMIT, SWAP_COMPUTE_UNIT_ESTIMATE (true/false), SWAP_COMPUTE_UNITS_PER_HOP
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let number = |key: &str, default: u32| {
+            std::env::var(key).ok().and_then(|v| v.parse::<u32>().ok()).filter(|v| *v > 0).unwrap_or(default)
+        };
+        Self {
+            default_limit: number("SWAP_COMPUTE_UNIT_LIMIT", defaults.default_limit).min(MAX_COMPUTE_UNIT_LIMIT),
+            estimate_from_route: std::env::var("SWAP_COMPUTE_UNIT_ESTIMATE")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(defaults.estimate_from_route),
+            per_hop: number("SWAP_COMPUTE_UNITS_PER_HOP", defaults.per_hop),
+        }
+    }
+
+    /// Compute units for a raw /quote response, from the hops in its routePlan - None if it has none
+    pub fn estimate(&self, quote: &serde_json::Value) -> Option<u32> {
+        let hops = quote.get("routePlan")?.as_array()?.len() as u32;
+        if hops == 0 {
+            return None;
+        }
+        Some(SWAP_BASE_COMPUTE_UNITS.saturating_add(self.per_hop.saturating_mul(hops)).min(MAX_COMPUTE_UNIT_LIMIT))
+    }
+
+    /// Limit for one swap: the trade's own override, else the route estimate, else the default
+    pub fn limit_for(&self, quote: &serde_json::Value, override_limit: Option<u32>) -> u32 {
+        override_limit
+            .filter(|limit| *limit > 0)
+            .or_else(|| self.estimate_from_route.then(|| self.estimate(quote)).flatten())
+            .unwrap_or(self.default_limit)
+            .min(MAX_COMPUTE_UNIT_LIMIT)
+    }
+}
+
+/// Set the transaction's SetComputeUnitLimit to `units`, replacing Jupiter's or adding one ahead of the swap
+/// Only for unsigned transactions - changing the message invalidates any signature on it
+pub fn set_compute_unit_limit(tx: &mut solana_sdk::transaction::Transaction, units: u32) {
+    use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+    use solana_sdk::instruction::CompiledInstruction;
+    
+    let data = ComputeBudgetInstruction::set_compute_unit_limit(units).data;
+    let message = &mut tx.message;
+    let program_index = match message.account_keys.iter().position(|key| *key == compute_budget::id()) {
+        Some(index) => index,
+        None => {
+            // Programs are read-only and unsigned, the last section of a legacy account list
+            message.account_keys.push(compute_budget::id());
+            message.header.num_readonly_unsigned_accounts += 1;
+            message.account_keys.len() - 1
+        }
+    };
+    let existing = message.instructions.iter_mut().find(|ix| {
+        ix.program_id_index as usize == program_index && ix.data.first() == data.first()
+    });
+    match existing {
+        Some(ix) => ix.data = data,
+        None => message.instructions.insert(0, CompiledInstruction::new_from_raw_parts(program_index as u8, data, vec![])),
+    }
+}
+
 #[derive(Debug)]
 pub struct JupiterClient {
     quote_api_url: String,
     ultra_api_url: String,
     api_key: Option<String>,
     client: Arc<reqwest::Client>, // Use shared client with connection pooling
+    retry_policy: RetryPolicy, // Quote and swap calls (JUPITER_RETRY_ATTEMPTS / JUPITER_RETRY_BASE_DELAY_MS)
+    compute_units: ComputeUnitConfig, // Compute-unit limit for legacy swap transactions
+    // Set while replaying a recorded session - quotes come from the recording, never the network
+    replay_prices: Option<crate::historical_data::ReplayPrices>,
 }
 
 impl JupiterClient {
@@ -64,9 +160,33 @@ impl JupiterClient {
             ultra_api_url: "https://api.jup.ag/ultra/v1".to_string(),
             api_key,
             client: SharedHttpClient::shared(), // Use shared HTTP client with connection pooling
+            retry_policy: RetryPolicy::from_env("JUPITER"),
+            compute_units: ComputeUnitConfig::from_env(),
+            replay_prices: None,
         }
     }
     
+    pub fn compute_unit_config(&self) -> &ComputeUnitConfig {
+        &self.compute_units
+    }
+    
+    /// Override the retry policy (e.g. RetryPolicy::no_delay in tests)
+    #[allow(dead_code)]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    
+    /// REPLAY: Quote from the recorded session's prices and refuse anything that would hit the network
+    pub fn with_replay_prices(mut self, replay_prices: crate::historical_data::ReplayPrices) -> Self {
+        self.replay_prices = Some(replay_prices);
+        self
+    }
+    
+    pub fn replay_prices(&self) -> Option<&crate::historical_data::ReplayPrices> {
+        self.replay_prices.as_ref()
+    }
+    
     /// Build request with API key if available (for Ultra Swap API)
     fn build_ultra_request(&self, url: &str) -> reqwest::RequestBuilder {
         let mut request = self.client.get(url);
@@ -92,13 +212,18 @@ impl JupiterClient {
     }
 
     /// Get a quote for swapping tokens
+    /// Transient failures (5xx, timeouts, 429) are retried under the client's RetryPolicy
     pub async fn get_quote(
         &self,
         input_mint: &str,
         output_mint: &str,
         amount: u64,
         slippage_bps: u16,
-    ) -> Result<JupiterQuote, Box<dyn Error>> {
+    ) -> Result<JupiterQuote, AppError> {
+        if self.replay_prices.is_some() {
+            return Err(AppError::Api("Jupiter quotes are served from the recording while replaying".to_string()));
+        }
+        
         let url = format!(
             "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
             self.quote_api_url, input_mint, output_mint, amount, slippage_bps
@@ -106,55 +231,30 @@ impl JupiterClient {
 
         log::debug!("Fetching Jupiter quote: {}", url);
 
-        // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of retry_with_backoff
-        // This prevents retrying non-retryable errors (e.g., ValidationError)
-        // CRITICAL IMPROVEMENT #5: Use aggressive retry config for critical trading operations
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
-        
-        let url_clone = url.clone();
-        let client_clone = self.client.clone();
-        
-        // RETRY + ERROR HANDLING: Use retry_with_backoff_retryable for robust error handling
-        // Circuit breaker integration can be added as a field to JupiterClient if needed
-        // CRITICAL IMPROVEMENT #5: Use aggressive config for critical trading operations
-        let result: Result<JupiterQuote, TradingError> = retry_with_backoff_retryable(
-            || {
-                let url = url_clone.clone();
-                let client = client_clone.clone();
-                Box::pin(async move {
-                    let response = client.get(&url).send().await
-                        .map_err(|e| {
-                            let error_str = e.to_string();
-                            // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                TradingError::TimeoutError(format!("Request timeout: {}", e))
-                            } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                TradingError::NetworkError(format!("Network error: {}", e))
-                            } else {
-                                TradingError::NetworkError(format!("Network error: {}", e))
-                            }
-                        })?;
-                    
+        let response = self.send_with_retry("Jupiter get_quote", || self.client.get(&url)).await?;
+        let quote: JupiterQuote = response.json().await
+            .map_err(|e| AppError::Api(format!("Failed to parse response: {}", e)))?;
+        Ok(quote)
+    }
+
+    /// Send a request under the retry policy. Connection failures map to AppError::Network/Timeout and
+    /// HTTP errors go through map_http_status_to_error, so 5xx and 429 are retried but 4xx is not
+    async fn send_with_retry<F>(&self, operation_name: &str, build_request: F) -> Result<reqwest::Response, AppError>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        self.retry_policy.run(operation_name, || {
+            let request = build_request();
+            async move {
+                let response = request.send().await.map_err(AppError::from)?;
+                if !response.status().is_success() {
                     let status = response.status().as_u16();
-                    if !response.status().is_success() {
-                        // CRITICAL IMPROVEMENT #2: Use map_http_status_to_error() for proper error type mapping
-                        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                        let error = map_http_status_to_error(status, error_text);
-                        // Error is already properly mapped, retry_with_backoff_retryable will check if it's retryable
-                        return Err(error);
-                    }
-                    
-                    let quote: JupiterQuote = response.json().await
-                        .map_err(|e| TradingError::ApiError(format!("Failed to parse response: {}", e)))?;
-                    Ok(quote)
-                })
-            },
-            RetryConfig::aggressive(), // CRITICAL IMPROVEMENT #5: Aggressive retry for critical trading operations
-            "Jupiter get_quote",
-        ).await;
-        
-        // Convert TradingError to Box<dyn Error> for return type
-        result.map_err(|e| Box::new(e) as Box<dyn Error>)
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    return Err(map_http_status_to_error(status, error_text));
+                }
+                Ok(response)
+            }
+        }).await
     }
 
     /// Get the best route for a swap
@@ -163,9 +263,8 @@ impl JupiterClient {
         input_mint: &str,
         output_mint: &str,
         amount: u64,
-    ) -> Result<JupiterQuote, Box<dyn Error>> {
-        // Use default 50 bps (0.5%) slippage
-        self.get_quote(input_mint, output_mint, amount, 50).await
+    ) -> Result<JupiterQuote, AppError> {
+        self.get_quote(input_mint, output_mint, amount, crate::dex_aggregator::DEFAULT_ROUTE_SLIPPAGE_BPS).await
     }
 
     /// Check if a token pair is supported
@@ -173,7 +272,7 @@ impl JupiterClient {
         &self,
         input_mint: &str,
         output_mint: &str,
-    ) -> Result<bool, Box<dyn Error>> {
+    ) -> Result<bool, AppError> {
         // Try to get a quote with minimal amount (1 token unit)
         match self.get_quote(input_mint, output_mint, 1, 50).await {
             Ok(_) => Ok(true),
@@ -181,6 +280,71 @@ impl JupiterClient {
         }
     }
     
+    /// Unsigned legacy (non-versioned) swap transaction for `user_public_key`
+    /// Endpoints: GET /quote + POST /swap with asLegacyTransaction - Jito bundles carry legacy transactions
+    /// Refuses quotes whose on-chain minimum out is looser than `slippage_bps` below the quoted output
+    /// `compute_unit_price` (micro-lamports per CU) sets the priority fee; None leaves it to Jupiter
+    /// `compute_unit_limit` overrides the limit the route would otherwise be given (see ComputeUnitConfig::limit_for)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_legacy_swap_transaction(
+        &self,
+        user_public_key: &str,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<solana_sdk::transaction::Transaction, String> {
+        use base64::{Engine as _, engine::general_purpose};
+        
+        if self.replay_prices.is_some() {
+            return Err("Swaps are disabled while replaying".to_string());
+        }
+        
+        let quote_url = format!(
+            "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}&asLegacyTransaction=true",
+            self.quote_api_url, input_mint, output_mint, amount, slippage_bps
+        );
+        let quote_response = self.send_with_retry("Jupiter legacy quote", || self.client.get(&quote_url)).await
+            .map_err(|e| format!("Jupiter quote failed: {}", e))?;
+        // Passed back verbatim to /swap
+        let quote: serde_json::Value = quote_response.json().await
+            .map_err(|e| format!("Failed to parse quote: {}", e))?;
+        let min_out = check_min_out(&quote, slippage_bps)?;
+        log::debug!("🛡️ Swap {} -> {} enforces min out {} ({} bps slippage)", input_mint, output_mint, min_out, slippage_bps);
+        // COMPUTE UNITS: Sized to the route so multi-hop memecoin swaps don't run out mid-route
+        let units = self.compute_units.limit_for(&quote, compute_unit_limit);
+        
+        // MIN OUT: The swap instruction carries the quote's otherAmountThreshold and reverts on-chain
+        // if the pool moved past it - dynamic slippage is off so Jupiter can't widen it for us
+        let swap_url = format!("{}/swap", self.quote_api_url);
+        let mut swap_body = serde_json::json!({
+            "quoteResponse": quote,
+            "userPublicKey": user_public_key,
+            "wrapAndUnwrapSol": true,
+            "asLegacyTransaction": true,
+            "dynamicSlippage": false,
+        });
+        if let Some(price) = compute_unit_price {
+            swap_body["computeUnitPriceMicroLamports"] = serde_json::json!(price);
+        }
+        let swap_response = self.send_with_retry("Jupiter swap", || self.client.post(&swap_url).json(&swap_body)).await
+            .map_err(|e| format!("Jupiter swap failed: {}", e))?;
+        let swap: serde_json::Value = swap_response.json().await
+            .map_err(|e| format!("Failed to parse swap response: {}", e))?;
+        let encoded = swap.get("swapTransaction").and_then(|tx| tx.as_str())
+            .ok_or("Jupiter swap response has no swapTransaction")?;
+        
+        let bytes = general_purpose::STANDARD.decode(encoded)
+            .map_err(|e| format!("Invalid swap transaction encoding: {}", e))?;
+        let mut tx: solana_sdk::transaction::Transaction = bincode::deserialize(&bytes)
+            .map_err(|e| format!("Swap transaction is not a legacy transaction: {}", e))?;
+        set_compute_unit_limit(&mut tx, units);
+        log::debug!("⚙️ Swap {} -> {} limited to {} CUs", input_mint, output_mint, units);
+        Ok(tx)
+    }
+    
     // ========== Jupiter Ultra Swap API Methods ==========
     // Ultra Swap API documentation: https://dev.jup.ag/api-reference/ultra
     
@@ -218,7 +382,7 @@ impl JupiterClient {
             input_mint: input_mint.to_string(),
             output_mint: output_mint.to_string(),
             amount: amount.to_string(),
-            slippage_bps: slippage_bps.or(Some(50)),
+            slippage_bps: slippage_bps.or(Some(crate::dex_aggregator::DEFAULT_ROUTE_SLIPPAGE_BPS)),
         };
         
         log::debug!("Requesting Ultra Swap order: {}", url);
@@ -266,16 +430,9 @@ impl JupiterClient {
         
         log::debug!("Executing Ultra Swap transaction: {}", url);
         
-        let response = self.build_ultra_post_request(&url)
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
-        
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Jupiter Ultra Swap API error: {}", error_text).into());
-        }
+        // Resending the same signed transaction is idempotent - it can only land once
+        let response = self.send_with_retry("Jupiter ultra_execute", || self.build_ultra_post_request(&url).json(&request_body)).await
+            .map_err(|e| format!("Jupiter Ultra Swap API error: {}", e))?;
         
         let result: UltraExecuteResponse = response.json().await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
@@ -511,6 +668,44 @@ pub struct RouterInfo {
     pub enabled: Option<bool>,
 }
 
+/// Least output a swap may fill for: `out_amount` less `slippage_bps`, rounded down
+pub fn min_out_amount(out_amount: u64, slippage_bps: u16) -> u64 {
+    let kept_bps = 10_000u128.saturating_sub(slippage_bps as u128);
+    (out_amount as u128 * kept_bps / 10_000) as u64
+}
+
+/// MIN OUT: Check a raw /quote response before it is turned into a swap
+/// The quote must be for the slippage we asked for, and the otherAmountThreshold the swap instruction
+/// enforces must be at least our own minimum - anything looser could fill at a terrible price
+fn check_min_out(quote: &serde_json::Value, slippage_bps: u16) -> Result<u64, String> {
+    let amount_field = |key: &str| {
+        quote.get(key)
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| format!("Jupiter quote has no {}", key))
+    };
+    let out_amount = amount_field("outAmount")?;
+    let threshold = amount_field("otherAmountThreshold")?;
+    
+    if quote.get("swapMode").and_then(|mode| mode.as_str()).is_some_and(|mode| mode != "ExactIn") {
+        return Err("Jupiter quote is not ExactIn - refusing to build a swap without a minimum out".to_string());
+    }
+    if let Some(quoted_bps) = quote.get("slippageBps").and_then(|bps| bps.as_u64()) {
+        if quoted_bps > slippage_bps as u64 {
+            return Err(format!("Jupiter quote slippage {} bps exceeds the requested {} bps", quoted_bps, slippage_bps));
+        }
+    }
+    
+    let min_out = min_out_amount(out_amount, slippage_bps);
+    if out_amount == 0 || threshold < min_out {
+        return Err(format!(
+            "Jupiter quote minimum out {} is below the {} bps floor of {} (quoted {})",
+            threshold, slippage_bps, min_out, out_amount
+        ));
+    }
+    Ok(threshold)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -522,4 +717,83 @@ mod tests {
         // For now, just verify client is created successfully
         assert!(std::mem::size_of_val(&client) > 0);
     }
+
+    #[tokio::test]
+    async fn test_quote_retries_5xx_but_not_4xx() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Answers 503 to the first request and 400 to every one after
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let server_requests = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let status = if server_requests.fetch_add(1, Ordering::SeqCst) == 0 { "503 Service Unavailable" } else { "400 Bad Request" };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let mut client = JupiterClient::new().with_retry_policy(RetryPolicy::no_delay(5));
+        client.quote_api_url = format!("http://127.0.0.1:{}", port);
+        let err = client.get_quote("SOL", "BONK", 1_000, 50).await.unwrap_err();
+
+        assert!(err.to_string().contains("400"), "unexpected error: {}", err);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_swap_requires_min_out_within_slippage() {
+        assert_eq!(min_out_amount(1_000_000, 50), 995_000);
+        assert_eq!(min_out_amount(999, 50), 994);
+        assert_eq!(min_out_amount(1_000, 20_000), 0);
+
+        let quote = |threshold: &str, bps: u64| serde_json::json!({
+            "outAmount": "1000000",
+            "otherAmountThreshold": threshold,
+            "slippageBps": bps,
+            "swapMode": "ExactIn",
+        });
+        assert_eq!(check_min_out(&quote("995000", 50), 50), Ok(995_000));
+        assert_eq!(check_min_out(&quote("999000", 10), 50), Ok(999_000));
+        // Threshold looser than our floor, or the quote was built with more slippage than requested
+        assert!(check_min_out(&quote("990000", 50), 50).is_err());
+        assert!(check_min_out(&quote("995000", 100), 50).is_err());
+        assert!(check_min_out(&serde_json::json!({ "outAmount": "1000000" }), 50).is_err());
+    }
+
+    #[test]
+    fn test_compute_unit_limit_from_route() {
+        use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+        use solana_sdk::signature::{Keypair, Signer};
+
+        let config = ComputeUnitConfig::default();
+        let quote = |hops: usize| serde_json::json!({ "routePlan": vec![serde_json::json!({}); hops] });
+        assert_eq!(config.estimate(&quote(1)), Some(200_000));
+        assert_eq!(config.estimate(&quote(3)), Some(440_000));
+        assert_eq!(config.estimate(&quote(20)), Some(MAX_COMPUTE_UNIT_LIMIT));
+        // No route to go on, then a per-trade override wins over everything
+        assert_eq!(config.limit_for(&serde_json::json!({}), None), DEFAULT_COMPUTE_UNIT_LIMIT);
+        assert_eq!(config.limit_for(&quote(3), Some(250_000)), 250_000);
+        assert_eq!(config.limit_for(&quote(3), Some(5_000_000)), MAX_COMPUTE_UNIT_LIMIT);
+
+        // A transaction without a compute budget gets one ahead of the swap...
+        let payer = Keypair::new();
+        let transfer = solana_sdk::system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1);
+        let mut tx = solana_sdk::transaction::Transaction::new_with_payer(&[transfer], Some(&payer.pubkey()));
+        let readonly = tx.message.header.num_readonly_unsigned_accounts;
+        set_compute_unit_limit(&mut tx, 200_000);
+        assert_eq!(tx.message.instructions.len(), 2);
+        assert_eq!(tx.message.header.num_readonly_unsigned_accounts, readonly + 1);
+        assert_eq!(tx.message.program_id(0), Some(&compute_budget::id()));
+        // ...and one that has it is rewritten in place
+        set_compute_unit_limit(&mut tx, 440_000);
+        assert_eq!(tx.message.instructions.len(), 2);
+        assert_eq!(tx.message.instructions[0].data, ComputeBudgetInstruction::set_compute_unit_limit(440_000).data);
+    }
 }
@@ -17,6 +17,8 @@ pub struct WalletConfig {
     pub address: String,
     pub encrypted_key: String,
     pub key_type: KeyType,
+    #[serde(default)]
+    pub key_env: Option<String>, // Key is read from this env var at signing time (encrypted_key left empty)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -191,17 +193,80 @@ impl KeyManager {
 }
 
 /// Wallet manager for multiple wallets
+/// Strategy wallets isolate providers from each other: each has its own key and PDA treasury,
+/// so a provider can only ever spend what was deposited to the wallet it is assigned to
+#[derive(Debug, Clone)]
 pub struct WalletManager {
     wallets: std::collections::HashMap<String, WalletConfig>,
+    assignments: std::collections::HashMap<String, String>, // provider_id -> wallet name
 }
 
 impl WalletManager {
     pub fn new() -> Self {
         Self {
             wallets: std::collections::HashMap::new(),
+            assignments: std::collections::HashMap::new(),
         }
     }
 
+    /// STRATEGY_WALLETS="memecoin:memecoin_monitor|jupiter_memecoin_trader,bluechip:jupiter_bluechip_trader"
+    /// Each wallet's key is read from WALLET_PRIVATE_KEY_<NAME>. A wallet whose key can't be loaded keeps
+    /// its assignments, so its providers are refused rather than falling back to the default wallet
+    pub fn from_env() -> Self {
+        let mut manager = Self::new();
+        let Ok(value) = std::env::var("STRATEGY_WALLETS") else { return manager };
+        let strategy_wallets = match parse_strategy_wallets(&value) {
+            Ok(strategy_wallets) => strategy_wallets,
+            Err(e) => {
+                log::error!("❌ Invalid STRATEGY_WALLETS ({}) - every provider uses the default wallet", e);
+                return manager;
+            }
+        };
+        
+        for (name, providers) in strategy_wallets {
+            let key_env = crate::wallet::key_env_for(&name);
+            match crate::wallet::Wallet::from_env(&key_env) {
+                Ok(wallet) => manager.add_wallet(name.clone(), WalletConfig {
+                    address: wallet.pubkey().to_string(),
+                    encrypted_key: String::new(),
+                    key_type: KeyType::Base58,
+                    key_env: Some(key_env),
+                }),
+                Err(e) => log::error!("❌ Strategy wallet {} unavailable: {} - its providers will not trade", name, e),
+            }
+            for provider_id in providers {
+                manager.assignments.insert(provider_id, name.clone());
+            }
+        }
+        manager
+    }
+
+    /// Route a provider's trades to `wallet_name` (which must already be added)
+    #[allow(dead_code)]
+    pub fn assign_provider(&mut self, provider_id: &str, wallet_name: &str) -> Result<(), String> {
+        if wallet_name != crate::wallet::DEFAULT_WALLET_NAME && !self.wallets.contains_key(wallet_name) {
+            return Err(format!("Unknown wallet: {}", wallet_name));
+        }
+        log::info!("👛 Provider {} trades from wallet {}", provider_id, wallet_name);
+        self.assignments.insert(provider_id.to_string(), wallet_name.to_string());
+        Ok(())
+    }
+
+    /// Wallet a provider trades from - unassigned providers use the default wallet
+    pub fn wallet_for_provider(&self, provider_id: &str) -> &str {
+        self.assignments.get(provider_id).map(|name| name.as_str()).unwrap_or(crate::wallet::DEFAULT_WALLET_NAME)
+    }
+
+    /// Providers assigned to `wallet_name`, sorted
+    pub fn providers_for_wallet(&self, wallet_name: &str) -> Vec<String> {
+        let mut providers: Vec<String> = self.assignments.iter()
+            .filter(|(_, name)| name.as_str() == wallet_name)
+            .map(|(provider_id, _)| provider_id.clone())
+            .collect();
+        providers.sort();
+        providers
+    }
+
     /// Add a wallet to the manager
     pub fn add_wallet(&mut self, name: String, config: WalletConfig) {
         log::info!("Added wallet: {}", name);
@@ -230,6 +295,35 @@ impl WalletManager {
     }
 }
 
+/// Parse "name:provider|provider,name:provider" into (wallet name, provider ids)
+/// Names are lowercase alphanumeric/underscore (they become part of an env var) and can't be "default"
+pub fn parse_strategy_wallets(value: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let mut wallets: Vec<(String, Vec<String>)> = Vec::new();
+    for entry in value.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let (name, providers) = entry.split_once(':')
+            .ok_or_else(|| format!("'{}' is not name:provider|provider", entry))?;
+        let name = name.trim().to_lowercase();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid wallet name '{}'", name));
+        }
+        if name == crate::wallet::DEFAULT_WALLET_NAME {
+            return Err("'default' is the main wallet - assign providers to a named wallet".to_string());
+        }
+        if wallets.iter().any(|(existing, _)| *existing == name) {
+            return Err(format!("Wallet '{}' listed twice", name));
+        }
+        let providers: Vec<String> = providers.split('|')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if let Some((other, _)) = wallets.iter().find(|(_, assigned)| assigned.iter().any(|p| providers.contains(p))) {
+            return Err(format!("A provider of '{}' is already assigned to '{}'", name, other));
+        }
+        wallets.push((name, providers));
+    }
+    Ok(wallets)
+}
+
 impl Default for WalletManager {
     fn default() -> Self {
         Self::new()
@@ -285,6 +379,7 @@ mod tests {
             address: "test_address".to_string(),
             encrypted_key: "test_key".to_string(),
             key_type: KeyType::Base58,
+            key_env: None,
         };
 
         manager.add_wallet("test".to_string(), config.clone());
@@ -296,6 +391,33 @@ mod tests {
         assert_eq!(manager.list_wallets().len(), 0);
     }
 
+    #[test]
+    fn test_strategy_wallet_assignments() {
+        let wallets = parse_strategy_wallets("memecoin:memecoin_monitor|jupiter_memecoin_trader, Bluechip:jupiter_bluechip_trader").unwrap();
+        assert_eq!(wallets, vec![
+            ("memecoin".to_string(), vec!["memecoin_monitor".to_string(), "jupiter_memecoin_trader".to_string()]),
+            ("bluechip".to_string(), vec!["jupiter_bluechip_trader".to_string()]),
+        ]);
+        assert!(parse_strategy_wallets("default:memecoin_monitor").is_err());
+        assert!(parse_strategy_wallets("meme-coin:memecoin_monitor").is_err());
+        assert!(parse_strategy_wallets("a:memecoin_monitor,b:memecoin_monitor").is_err());
+        assert!(parse_strategy_wallets("memecoin").is_err());
+        assert_eq!(crate::wallet::key_env_for("memecoin"), "WALLET_PRIVATE_KEY_MEMECOIN");
+
+        let mut manager = WalletManager::new();
+        assert!(manager.assign_provider("memecoin_monitor", "memecoin").is_err());
+        manager.add_wallet("memecoin".to_string(), WalletConfig {
+            address: "memecoin_address".to_string(),
+            encrypted_key: String::new(),
+            key_type: KeyType::Base58,
+            key_env: Some("WALLET_PRIVATE_KEY_MEMECOIN".to_string()),
+        });
+        manager.assign_provider("memecoin_monitor", "memecoin").unwrap();
+        assert_eq!(manager.wallet_for_provider("memecoin_monitor"), "memecoin");
+        assert_eq!(manager.wallet_for_provider("oracle_monitor"), crate::wallet::DEFAULT_WALLET_NAME);
+        assert_eq!(manager.providers_for_wallet("memecoin"), vec!["memecoin_monitor".to_string()]);
+    }
+
     #[test]
     fn test_encryption_key_generation() {
         let key1 = KeyManager::generate_encryption_key();
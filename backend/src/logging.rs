@@ -0,0 +1,145 @@
+//! Logging
+//! LOG_FORMAT=pretty (default) keeps the colored emoji terminal output, LOG_FORMAT=json writes one JSON
+//! object per line for Loki/ELK. Key-value fields on a log call (`log::info!(signal_id = id; "...")`) become
+//! top-level JSON fields; the pretty format only prints the message. Filtering still follows RUST_LOG
+
+use log::kv::{Key, Value, VisitSource, VisitValue};
+use std::io::Write;
+
+/// Output format selected at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    /// LOG_FORMAT=pretty|json (default pretty)
+    pub fn from_env() -> Self {
+        match std::env::var("LOG_FORMAT").map(|v| v.trim().to_lowercase()) {
+            Ok(value) if value == "json" => LogFormat::Json,
+            Ok(value) if value.is_empty() || value == "pretty" => LogFormat::Pretty,
+            Ok(value) => {
+                eprintln!("Unknown LOG_FORMAT '{}' - using pretty", value);
+                LogFormat::Pretty
+            }
+            Err(_) => LogFormat::Pretty,
+        }
+    }
+}
+
+/// Install the global logger - call once, before anything logs
+pub fn init() -> LogFormat {
+    let format = LogFormat::from_env();
+    match format {
+        LogFormat::Pretty => pretty_env_logger::init(),
+        LogFormat::Json => env_logger::Builder::from_default_env()
+            .format(|buf, record| writeln!(buf, "{}", json_line(record, chrono::Utc::now())))
+            .init(),
+    }
+    format
+}
+
+/// One record as a JSON object: timestamp, level, target, message, then any key-value fields
+pub fn json_line(record: &log::Record, timestamp: chrono::DateTime<chrono::Utc>) -> String {
+    let mut line = serde_json::Map::new();
+    line.insert("timestamp".to_string(), serde_json::Value::String(timestamp.to_rfc3339()));
+    line.insert("level".to_string(), serde_json::Value::String(record.level().to_string()));
+    line.insert("target".to_string(), serde_json::Value::String(record.target().to_string()));
+    line.insert("message".to_string(), serde_json::Value::String(record.args().to_string().trim().to_string()));
+
+    let mut fields = FieldCollector(&mut line);
+    let _ = record.key_values().visit(&mut fields);
+
+    serde_json::Value::Object(line).to_string()
+}
+
+/// Copies key-value pairs into the JSON object (fields never overwrite the fixed keys above)
+struct FieldCollector<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        let mut json = JsonValue(serde_json::Value::Null);
+        value.visit(&mut json)?;
+        self.0.entry(key.as_str().to_string()).or_insert(json.0);
+        Ok(())
+    }
+}
+
+/// Keeps numbers and booleans typed so dashboards can aggregate on them
+struct JsonValue(serde_json::Value);
+
+impl<'v> VisitValue<'v> for JsonValue {
+    fn visit_any(&mut self, value: Value) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::String(value.to_string());
+        Ok(())
+    }
+
+    fn visit_null(&mut self) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::Null;
+        Ok(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::from(value);
+        Ok(())
+    }
+
+    fn visit_i64(&mut self, value: i64) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::from(value);
+        Ok(())
+    }
+
+    fn visit_f64(&mut self, value: f64) -> Result<(), log::kv::Error> {
+        // NaN / infinity have no JSON form - keep them readable as strings
+        self.0 = serde_json::Number::from_f64(value)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(value.to_string()));
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, value: bool) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::Bool(value);
+        Ok(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> Result<(), log::kv::Error> {
+        self.0 = serde_json::Value::String(value.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_line_flattens_fields() {
+        let fields: &[(&str, Value)] = &[
+            ("signal_id", Value::from("sig_1")),
+            ("symbol", Value::from("SOL/USDC")),
+            ("confidence", Value::from(0.82)),
+            ("message", Value::from("ignored")),
+        ];
+        let timestamp = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let line = json_line(
+            &log::Record::builder()
+                .args(format_args!("✅ Trade executed for {} ", "SOL/USDC"))
+                .level(log::Level::Info)
+                .target("agentburn_backend")
+                .key_values(&fields)
+                .build(),
+            timestamp,
+        );
+
+        assert!(!line.contains('\n'));
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["level"], "INFO");
+        assert_eq!(parsed["target"], "agentburn_backend");
+        assert_eq!(parsed["message"], "✅ Trade executed for SOL/USDC");
+        assert_eq!(parsed["timestamp"], "2023-11-14T22:13:20+00:00");
+        assert_eq!(parsed["signal_id"], "sig_1");
+        assert_eq!(parsed["symbol"], "SOL/USDC");
+        assert_eq!(parsed["confidence"], 0.82);
+    }
+}
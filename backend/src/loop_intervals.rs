@@ -0,0 +1,174 @@
+//! Runtime Loop Cadence
+//! Shared, live-adjustable tick intervals for the auto-executor and performance tracker
+//! Loops read the value each iteration, so a change applies from the next tick without a restart
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub const DEFAULT_AUTO_EXEC_INTERVAL_SECS: u64 = 30;
+pub const DEFAULT_PERF_TRACKER_INTERVAL_SECS: u64 = 10;
+
+/// Faster than this hammers RPC and price-feed rate limits
+pub const MIN_INTERVAL_SECS: u64 = 1;
+
+/// Slower than this risks the watchdog (600s default timeout) restarting a healthy loop as stalled
+pub const MAX_INTERVAL_SECS: u64 = 300;
+
+/// Current cadence, for the API
+#[derive(Debug, Clone, Serialize)]
+pub struct IntervalSettings {
+    pub auto_exec_secs: u64,
+    pub perf_tracker_secs: u64,
+    pub min_secs: u64,
+    pub max_secs: u64,
+}
+
+/// Cheap, cloneable handle shared between the loops and the API
+#[derive(Debug, Clone)]
+pub struct LoopIntervals {
+    auto_exec_secs: Arc<AtomicU64>,
+    perf_tracker_secs: Arc<AtomicU64>,
+}
+
+impl LoopIntervals {
+    pub fn new(auto_exec_secs: u64, perf_tracker_secs: u64) -> Self {
+        Self {
+            auto_exec_secs: Arc::new(AtomicU64::new(auto_exec_secs.clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS))),
+            perf_tracker_secs: Arc::new(AtomicU64::new(perf_tracker_secs.clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS))),
+        }
+    }
+
+    /// Load from AUTO_EXEC_INTERVAL_SECS / PERF_TRACKER_INTERVAL_SECS (clamped to the allowed range)
+    pub fn from_env() -> Self {
+        let auto_exec_secs = std::env::var("AUTO_EXEC_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_AUTO_EXEC_INTERVAL_SECS);
+        let perf_tracker_secs = std::env::var("PERF_TRACKER_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_PERF_TRACKER_INTERVAL_SECS);
+        Self::new(auto_exec_secs, perf_tracker_secs)
+    }
+
+    pub fn validate(secs: u64) -> Result<(), String> {
+        if !(MIN_INTERVAL_SECS..=MAX_INTERVAL_SECS).contains(&secs) {
+            return Err(format!("Interval must be between {}s and {}s, got {}s",
+                              MIN_INTERVAL_SECS, MAX_INTERVAL_SECS, secs));
+        }
+        Ok(())
+    }
+
+    pub fn auto_exec_secs(&self) -> u64 {
+        self.auto_exec_secs.load(Ordering::Relaxed)
+    }
+
+    pub fn perf_tracker_secs(&self) -> u64 {
+        self.perf_tracker_secs.load(Ordering::Relaxed)
+    }
+
+    pub fn set_auto_exec_secs(&self, secs: u64) -> Result<(), String> {
+        Self::validate(secs)?;
+        self.auto_exec_secs.store(secs, Ordering::Relaxed);
+        log::info!("⏱️ Auto-execution interval set to {}s", secs);
+        Ok(())
+    }
+
+    pub fn set_perf_tracker_secs(&self, secs: u64) -> Result<(), String> {
+        Self::validate(secs)?;
+        self.perf_tracker_secs.store(secs, Ordering::Relaxed);
+        log::info!("⏱️ Performance tracker interval set to {}s", secs);
+        Ok(())
+    }
+
+    pub fn settings(&self) -> IntervalSettings {
+        IntervalSettings {
+            auto_exec_secs: self.auto_exec_secs(),
+            perf_tracker_secs: self.perf_tracker_secs(),
+            min_secs: MIN_INTERVAL_SECS,
+            max_secs: MAX_INTERVAL_SECS,
+        }
+    }
+}
+
+impl Default for LoopIntervals {
+    fn default() -> Self {
+        Self::new(DEFAULT_AUTO_EXEC_INTERVAL_SECS, DEFAULT_PERF_TRACKER_INTERVAL_SECS)
+    }
+}
+
+/// Fixed-period ticks on a clock - the wall clock live, the shared replay clock while replaying, so a loop
+/// ticks as often over a replayed session as it would have live. The first tick is immediate, and a late
+/// tick pushes the next one back rather than bursting to catch up
+pub struct Ticker {
+    clock: Arc<dyn crate::historical_data::ReplayClock>,
+    period_secs: u64,
+    next_ms: i64,
+}
+
+impl Ticker {
+    pub fn new(clock: Arc<dyn crate::historical_data::ReplayClock>, secs: u64) -> Self {
+        let next_ms = clock.now_ms();
+        Self { clock, period_secs: secs, next_ms }
+    }
+
+    /// Switch to `secs` when the configured period changed - the next tick comes one new period from now
+    pub fn retune(&mut self, secs: u64) -> bool {
+        if self.period_secs == secs {
+            return false;
+        }
+        self.period_secs = secs;
+        self.next_ms = self.clock.now_ms() + (secs * 1000) as i64;
+        true
+    }
+
+    pub async fn tick(&mut self) {
+        let wait_ms = self.next_ms - self.clock.now_ms();
+        if wait_ms > 0 {
+            self.clock.sleep(std::time::Duration::from_millis(wait_ms as u64)).await;
+        }
+        self.next_ms = self.next_ms.max(self.clock.now_ms()) + (self.period_secs * 1000) as i64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::historical_data::ReplayClock;
+
+    #[tokio::test]
+    async fn test_interval_updates_are_validated_and_shared() {
+        let intervals = LoopIntervals::default();
+        let loop_handle = intervals.clone();
+
+        assert!(intervals.set_auto_exec_secs(0).is_err());
+        assert!(intervals.set_perf_tracker_secs(MAX_INTERVAL_SECS + 1).is_err());
+        assert_eq!(loop_handle.auto_exec_secs(), DEFAULT_AUTO_EXEC_INTERVAL_SECS);
+
+        intervals.set_auto_exec_secs(2).unwrap();
+        assert_eq!(loop_handle.auto_exec_secs(), 2);
+
+        let clock = Arc::new(crate::historical_data::SimulatedClock::new(1_000.0));
+        let mut ticker = Ticker::new(clock, DEFAULT_AUTO_EXEC_INTERVAL_SECS);
+        assert!(ticker.retune(loop_handle.auto_exec_secs()));
+        assert!(!ticker.retune(2));
+
+        // Out-of-range startup values are clamped rather than rejected
+        assert_eq!(LoopIntervals::new(0, 10_000).settings().perf_tracker_secs, MAX_INTERVAL_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_ticker_runs_on_replay_clock() {
+        // At 1000x, ten 30s ticks are five simulated minutes but a fraction of a second of real time
+        let clock = Arc::new(crate::historical_data::SimulatedClock::new(1_000.0));
+        let mut ticker = Ticker::new(clock.clone(), DEFAULT_AUTO_EXEC_INTERVAL_SECS);
+        let started_ms = clock.now_ms();
+        let started = std::time::Instant::now();
+        for _ in 0..11 {
+            ticker.tick().await;
+        }
+        assert!(clock.now_ms() - started_ms >= 300_000);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+}
@@ -23,6 +23,7 @@ mod enhanced_marketplace;
 mod historical_data;
 mod wallet;
 mod pda;
+mod pda_withdraw_helper;
 mod rpc_client;
 mod quant_analysis;
 mod jito_bam;
@@ -33,6 +34,23 @@ mod http_client;
 mod twitter_sentiment;
 mod backtesting;
 mod production_safeguards;
+mod adaptive_slippage;
+mod notifications;
+mod watchdog;
+mod dex_aggregator;
+mod swap_dedupe;
+mod loop_intervals;
+mod provider_registry;
+mod shutdown;
+mod metrics;
+mod logging;
+mod symbol_registry;
+mod health;
+mod simulation;
+mod signal_review;
+mod market_regime;
+mod signal_callbacks;
+mod arbitrage;
 
 #[cfg(test)]
 mod algorithm_tests;
@@ -41,28 +59,302 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use futures::FutureExt; // For catch_unwind
 
+/// Symbols auto-executed at once unless AUTO_EXEC_MAX_CONCURRENCY says otherwise
+const DEFAULT_AUTO_EXEC_CONCURRENCY: usize = 4;
+
+/// Everything one auto-execution needs - shared by the per-symbol tasks
+struct SignalExecutor {
+    trading_engine: Arc<Mutex<trading_engine::TradingEngine>>,
+    marketplace: Arc<signal_platform::SignalMarketplace>,
+    enhanced_marketplace: Arc<enhanced_marketplace::EnhancedMarketplace>,
+    oracle_aggregator: Arc<switchboard_oracle::OracleAggregator>,
+    max_oracle_dispersion_pct: f64,
+    symbol_cooldown_secs: Option<i64>,
+    signal_reviewer: Option<Arc<signal_review::SignalReviewer>>,
+    trading_enabled: Arc<Mutex<bool>>,
+    dry_run: Arc<Mutex<bool>>,
+    rl_coordinator: Arc<Mutex<reinforcement_learning::LearningCoordinator>>,
+    notifier: Option<Arc<dyn notifications::Notifier>>,
+    shutdown: shutdown::Shutdown,
+    execution_count: std::sync::atomic::AtomicU64,
+}
+
+impl SignalExecutor {
+    /// Claim, check and execute one signal
+    /// The claim and oracle cross-check run concurrently with other symbols; position limits, balance and
+    /// wallet routing are checked under the engine lock, so concurrent signals can't overshoot a cap or
+    /// overspend a shared wallet. Live swaps are sent with the engine unlocked
+    async fn execute_signal(&self, signal: signal_platform::TradingSignalData) {
+        let threshold = self.marketplace.get_confidence_threshold(&signal.symbol).await;
+        log::info!(event = "signal_processing", signal_id = signal.id.as_str(), symbol = signal.symbol.as_str(),
+                   provider = signal.provider.as_str(), confidence = signal.confidence;
+                  "📈 Processing signal: {} | Symbol: {} | Confidence: {:.1}% (threshold {:.1}%) | Provider: {}", 
+                  signal.id, signal.symbol, signal.confidence * 100.0, threshold * 100.0, signal.provider);
+        
+        // FIX #1: Atomic status update - mark as Executing BEFORE execution to prevent duplicate execution
+        match self.marketplace.try_mark_executing(&signal.id).await {
+            Ok(true) => {
+                log::debug!("🔒 Acquired execution lock for signal {}", signal.id);
+            }
+            Ok(false) => {
+                log::warn!("⚠️ Signal {} already being processed by another task, skipping", signal.id);
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::AlreadyExecuting,
+                    "Signal already being processed by another task".to_string()).await;
+                return; // Already being processed
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to mark signal {} as Executing: {}", signal.id, e);
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::AlreadyExecuting, e).await;
+                return;
+            }
+        }
+        
+        // ORACLE CONSENSUS: Sources that disagree often mean a manipulated pool (buys only - exits still go out)
+        if matches!(signal.action, signal_platform::SignalAction::Buy) {
+            // Fails closed: a price fewer than two sources quote can't be cross-checked
+            if let Err(reason) = self.oracle_aggregator.verify_price(&signal.symbol, self.max_oracle_dispersion_pct).await {
+                log::warn!("⚖️ Skipping signal {}: {}", signal.id, reason);
+                let _ = self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::OracleDisagreement, reason).await;
+                return;
+            }
+        }
+        
+        // AI REVIEW: DeepSeek second opinion on buys before funds are committed
+        if let (Some(reviewer), signal_platform::SignalAction::Buy) = (&self.signal_reviewer, &signal.action) {
+            let market_context = match self.oracle_aggregator.get_consensus_price(&signal.symbol).await {
+                Ok(consensus) => format!("- Consensus Price: ${:.8} ({:.2}% dispersion across sources)\n- Entry vs Consensus: {:+.2}%",
+                                         consensus.price, consensus.dispersion_pct,
+                                         (signal.entry_price - consensus.price) / consensus.price * 100.0),
+                Err(_) => "- No independent price source quotes this token".to_string(),
+            };
+            match reviewer.gate(&signal, &market_context, threshold).await {
+                Ok(Some(review)) => log::info!("🧠 AI review approved {} at {:.1}% confidence: {}",
+                                               signal.id, review.adjusted_confidence * 100.0, review.reasoning),
+                Ok(None) => {}
+                Err(reason) => {
+                    log::warn!("🧠 Skipping signal {}: {}", signal.id, reason);
+                    let _ = self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
+                    self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::AiRejected, reason).await;
+                    return;
+                }
+            }
+        }
+        
+        // Everything below runs under the engine lock, so concurrent symbols still see each other's fills
+        // (a live swap's send is the exception - its position is booked optimistically before the lock is released)
+        let mut engine = self.trading_engine.lock().await;
+        
+        // COOLDOWN: No re-entry on a symbol that just traded - stops flip-flopping on a choppy token (buys only)
+        if matches!(signal.action, signal_platform::SignalAction::Buy) {
+            let cooldown_secs = signal.cooldown_secs(self.symbol_cooldown_secs);
+            if let Some(remaining) = engine.symbol_cooldown_remaining(&signal.symbol, cooldown_secs, self.marketplace.now()) {
+                let reason = format!("{} traded within the last {}s - cooldown ends in {}s", signal.symbol, cooldown_secs, remaining);
+                log::info!("⏳ Skipping signal {}: {}", signal.id, reason);
+                let _ = self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::Cooldown, reason).await;
+                return;
+            }
+        }
+        
+        // POSITION LIMITS: Cap concurrent positions and stacking on a single token (buys only)
+        if matches!(signal.action, signal_platform::SignalAction::Buy) {
+            let open_positions = engine.portfolio.values().filter(|size| **size > 0.0).count();
+            let symbol_positions = self.enhanced_marketplace.open_positions_for_symbol(&signal.symbol).await;
+            let limit_reason = engine.risk_manager.lock().await
+                .position_limit_reason(&signal.symbol, open_positions, symbol_positions);
+            if let Some(reason) = limit_reason {
+                log::info!("🚧 Skipping signal {}: {}", signal.id, reason);
+                let _ = self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::PositionCap, reason).await;
+                return;
+            }
+        }
+        
+        // Initialize performance tracking
+        if let Err(e) = self.enhanced_marketplace.initialize_signal_performance(&signal).await {
+            log::warn!("⚠️ Failed to initialize performance tracking for {}: {}", signal.id, e);
+            // FIX #1: Revert status on failure
+            let _ = self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
+            self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::TrackingUnavailable, e).await;
+            return;
+        }
+        
+        // Execute signal via trading engine (REAL Solana transactions)
+        // The checks above and the trade's book entries share one lock hold; the swap itself goes out unlocked
+        let begun = engine.begin_marketplace_signal(&signal, Some(&self.trading_enabled), Some(&self.dry_run)).await;
+        let result = match begun {
+            Ok(trade) => {
+                drop(engine);
+                let trade = trade.settle(&self.trading_engine).await;
+                engine = self.trading_engine.lock().await;
+                engine.finish_marketplace_signal(&signal, trade).await
+            }
+            Err(e) => Err(e),
+        };
+        // Marketplace, tracker, RL and notification bookkeeping below don't touch the engine - let other symbols trade
+        let fill_price = engine.fill_price_usd(&signal.id, &signal.symbol).unwrap_or(signal.entry_price);
+        let filled_size = engine.fill_size(&signal.id);
+        let live_execution = match result {
+            Ok(_) => None,
+            Err(_) => engine.live_execution(&signal.id).await,
+        };
+        drop(engine);
+        match result {
+            Ok(result) => {
+                let execution_count = self.execution_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                log::info!("✅ AUTONOMOUS TRADE EXECUTED #{}", execution_count);
+                log::info!("   Signal ID: {}", signal.id);
+                log::info!("   Symbol: {}", signal.symbol);
+                log::info!("   Action: {:?}", signal.action);
+                log::info!("   Entry Price: ${:.8}", signal.entry_price);
+                log::info!("   Fill Price: ${:.8}", fill_price);
+                log::info!("   Target Price: ${:.8}", signal.target_price);
+                log::info!("   Stop Loss: ${:.8}", signal.stop_loss);
+                let mode = if *self.dry_run.lock().await || signal.force_dry_run { "PAPER" } else { "LIVE" };
+                log::info!("   Mode: {}", mode);
+                // One machine-readable record per trade (the banner above is for terminals)
+                log::info!(event = "trade_executed", signal_id = signal.id.as_str(), symbol = signal.symbol.as_str(),
+                           provider = signal.provider.as_str(), confidence = signal.confidence,
+                           action:? = signal.action, entry_price = signal.entry_price, fill_price = fill_price,
+                           target_price = signal.target_price, stop_loss = signal.stop_loss, mode = mode;
+                           "Trade executed: {:?} {} via {}", signal.action, signal.symbol, signal.provider);
+                log::info!("   Result: {}", result);
+                log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                notifications::dispatch(&self.notifier, notifications::Notification::new(
+                    notifications::NotificationEvent::TradeExecuted,
+                    &format!("✅ {} trade executed", mode),
+                    &format!("{:?} {:.6} {} at ${:.8} | target ${:.8} | stop ${:.8} | {} ({:.0}% confidence)",
+                             signal.action, filled_size.unwrap_or_default(), signal.symbol, fill_price, signal.target_price,
+                             signal.stop_loss, signal.provider, signal.confidence * 100.0),
+                ));
+                
+                // Mark signal as filled
+                if let Err(e) = self.enhanced_marketplace.mark_signal_filled(&signal.id).await {
+                    log::warn!("⚠️ Failed to mark signal as filled: {}", e);
+                }
+                // Track P/L from the actual fill so paper results include slippage and fees
+                self.enhanced_marketplace.set_fill_price(&signal.id, fill_price).await.ok();
+                // Remember paper vs live so paper positions can later be promoted
+                let is_paper = *self.dry_run.lock().await || signal.force_dry_run;
+                self.enhanced_marketplace.set_position_mode(&signal.id, is_paper).await.ok();
+                
+                // FIX #1: Update marketplace signal status to Filled after successful execution
+                if let Err(e) = self.marketplace.mark_signal_executed(&signal.id, fill_price, filled_size).await {
+                    log::warn!("⚠️ Failed to update signal status: {}", e);
+                }
+                
+                // RL LEARNING: Update RL agents with trade outcome
+                // Record experience via LearningCoordinator
+                let coordinator = self.rl_coordinator.lock().await;
+                // Size as recorded at execution; the confirmation poller amends it to the on-chain fill
+                let filled_size = filled_size.unwrap_or(signal.entry_price * 0.05);
+                
+                // Create experience for RL learning
+                let experience = reinforcement_learning::Experience::entry(&signal, fill_price, filled_size);
+                
+                // Record experience via coordinator (it will route to the appropriate agent)
+                coordinator.record_experience_for_provider(&signal.provider, experience).await;
+                log::debug!("🧠 Recorded trade experience for RL agent: {}", signal.provider);
+                drop(coordinator);
+                
+                // Note: Provider reputation is updated when signal performance is tracked
+                // in the track_signal_performance function based on actual outcomes
+            }
+            Err(e) => {
+                log::warn!(event = "trade_failed", signal_id = signal.id.as_str(), symbol = signal.symbol.as_str(),
+                           provider = signal.provider.as_str(), confidence = signal.confidence, error = e.as_str();
+                           "⚠️ Failed to auto-execute signal {}: {}", signal.id, e);
+                log::warn!("   Signal remains in marketplace for manual execution");
+                self.marketplace.record_skipped_signal(&signal, signal_platform::SkipReason::from_execution_error(&e), e.clone()).await;
+                // AUDIT TRAIL: A swap already on its way to the chain may still fill - reverting
+                // to Active would hide that position and invite a second buy
+                if let Some(execution) = live_execution {
+                    log::warn!("⚠️ Signal {} has swap {} ({:?}) on-chain - leaving it Executing instead of reverting",
+                              signal.id, execution.signature, execution.status);
+                    log::warn!("   Reconcile via GET /executions/{}", execution.signature);
+                    return;
+                }
+                // Revert status to Active on failure (validated transition)
+                match self.marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await {
+                    Ok(_) => {
+                        log::info!("🔄 Signal {} reverted to Active for retry", signal.id);
+                    }
+                    Err(revert_err) => {
+                        log::warn!("⚠️ Failed to revert signal {} status to Active: {}", signal.id, revert_err);
+                        log::warn!("   Signal may be stuck in Executing state - manual intervention may be needed");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Report auto-execution tasks that panicked - the rest of the batch keeps going
+fn log_execution_task(joined: Option<Result<(), tokio::task::JoinError>>) {
+    if let Some(Err(e)) = joined {
+        log::error!("💥 Auto-execution task failed: {}", e);
+    }
+}
+
 /// Auto-execute high-confidence signals from marketplace
+#[allow(clippy::too_many_arguments)]
 async fn auto_execute_marketplace_signals(
     trading_engine: Arc<Mutex<trading_engine::TradingEngine>>,
     marketplace: Arc<signal_platform::SignalMarketplace>,
     enhanced_marketplace: Arc<enhanced_marketplace::EnhancedMarketplace>,
-    _oracle_client: Arc<switchboard_oracle::SwitchboardClient>,
+    oracle_aggregator: Arc<switchboard_oracle::OracleAggregator>,
     trading_enabled: Arc<Mutex<bool>>,
     dry_run: Arc<Mutex<bool>>,
     rl_coordinator: Arc<Mutex<reinforcement_learning::LearningCoordinator>>,
+    signal_reviewer: Option<Arc<signal_review::SignalReviewer>>,
+    drawdown_guard: production_safeguards::DrawdownGuardConfig,
+    intervals: loop_intervals::LoopIntervals,
+    notifier: Option<Arc<dyn notifications::Notifier>>,
+    heartbeat: watchdog::Heartbeat,
+    shutdown: shutdown::Shutdown,
+    clock: Arc<dyn historical_data::ReplayClock>,
 ) {
     log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     log::info!("🚀 AUTONOMOUS TRADING SERVICE STARTED");
     log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    log::info!("📊 Monitoring marketplace for high-confidence signals (≥75%)");
-    log::info!("⏱️  Check interval: 30 seconds");
+    log::info!("📊 Monitoring marketplace for high-confidence signals (≥75% default, per-symbol overrides via /config/confidence-threshold)");
+    log::info!("⏱️  Check interval: {} seconds (adjust live via /config/intervals)", intervals.auto_exec_secs());
     log::info!("💡 Signals are published to marketplace AND executed autonomously");
     log::info!("🔗 Using REAL Solana transactions via Jupiter API");
     log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
-    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30)); // Check every 30 seconds
-    let min_confidence = 0.75; // Auto-execute signals with 75%+ confidence
-    let mut execution_count = 0u64;
+    let max_concurrency = std::env::var("AUTO_EXEC_MAX_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_AUTO_EXEC_CONCURRENCY);
+    log::info!("🧵 Executing up to {} symbols concurrently (AUTO_EXEC_MAX_CONCURRENCY)", max_concurrency);
+    // SYMBOL_COOLDOWN_SECS overrides the per-signal default of one timeframe (0 disables)
+    let symbol_cooldown_secs = std::env::var("SYMBOL_COOLDOWN_SECS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok());
+    
+    let executor = Arc::new(SignalExecutor {
+        trading_engine: trading_engine.clone(),
+        marketplace: marketplace.clone(),
+        enhanced_marketplace,
+        // ORACLE CONSENSUS: Buys wait while Switchboard / Jupiter / Mobula / DEX Screener disagree on the price
+        max_oracle_dispersion_pct: switchboard_oracle::ConsensusPrice::max_dispersion_from_env(),
+        oracle_aggregator,
+        symbol_cooldown_secs,
+        signal_reviewer,
+        trading_enabled: trading_enabled.clone(),
+        dry_run: dry_run.clone(),
+        rl_coordinator,
+        notifier: notifier.clone(),
+        shutdown: shutdown.clone(),
+        execution_count: std::sync::atomic::AtomicU64::new(0),
+    });
+    
+    // REPLAY: Ticks come from the shared replay clock, so a compressed session still gets every check
+    let mut ticker = loop_intervals::Ticker::new(clock, intervals.auto_exec_secs());
     let mut last_check_time = std::time::Instant::now();
     
     // CRASH PROTECTION: Wrap main loop in panic handler
@@ -70,9 +362,62 @@ async fn auto_execute_marketplace_signals(
     let max_consecutive_errors = 10u32;
     
     loop {
+        // CADENCE: Pick up interval changes from /config/intervals
+        if ticker.retune(intervals.auto_exec_secs()) {
+            log::info!("⏱️ Auto-execution now checking every {}s", intervals.auto_exec_secs());
+        }
+        
         // CRASH PROTECTION: Catch panics in auto-execution loop
         let result = std::panic::AssertUnwindSafe(async {
-            interval.tick().await;
+            ticker.tick().await;
+            heartbeat.beat();
+            
+            // SHUTDOWN: Stop picking up new signals
+            if shutdown.is_triggered() {
+                return;
+            }
+            
+            // DRAWDOWN GUARD: disable trading past max drawdown (cooldown), flatten at the hard limit (operator re-enable)
+            let is_dry_run = *dry_run.lock().await;
+            match production_safeguards::enforce_drawdown_limits(
+                &trading_engine, &trading_enabled, is_dry_run, drawdown_guard
+            ).await {
+                Some(production_safeguards::DrawdownAction::Disabled(lockout)) => {
+                    notifications::dispatch(&notifier, notifications::Notification::new(
+                        notifications::NotificationEvent::CircuitBreakerTripped,
+                        "📉 Drawdown limit - trading disabled",
+                        &format!("Drawdown {:.2}% crossed max {:.2}% - trading off, re-enable allowed after {}s cooldown",
+                                 lockout.drawdown_pct, lockout.limit_pct, drawdown_guard.cooldown_secs),
+                    ));
+                    return;
+                }
+                Some(production_safeguards::DrawdownAction::Flattened(lockout, report)) => {
+                    log::error!("🚨 {} - flattened {} positions, trading disabled until an operator re-enables it",
+                               report.reason, report.closes.len());
+                    for close in report.closes.iter().filter(|c| c.success) {
+                        if let Some(price) = close.price {
+                            enhanced_marketplace.close_symbol_positions(&close.symbol, price, enhanced_marketplace::ExitReason::Flatten).await;
+                        }
+                    }
+                    notifications::dispatch(&notifier, notifications::Notification::new(
+                        notifications::NotificationEvent::CircuitBreakerTripped,
+                        "🚨 Kill switch engaged",
+                        &format!("{} - flattened {} positions ({} still open), trading disabled until re-enabled via POST /trading-toggle (cooldown {}s)",
+                                 report.reason, report.closes.len(), report.positions_remaining,
+                                 lockout.cooldown_remaining_secs(chrono::Utc::now().timestamp())),
+                    ));
+                    return;
+                }
+                Some(production_safeguards::DrawdownAction::Resumed(lockout)) => {
+                    notifications::dispatch(&notifier, notifications::Notification::new(
+                        notifications::NotificationEvent::CircuitBreakerTripped,
+                        "✅ Drawdown recovered - trading re-enabled",
+                        &format!("Cooldown after the {:.2}% drawdown is over and drawdown is back under {:.2}%",
+                                 lockout.drawdown_pct, lockout.limit_pct),
+                    ));
+                }
+                None => {}
+            }
             
             // Check if trading is enabled
             let is_enabled = {
@@ -88,8 +433,11 @@ async fn auto_execute_marketplace_signals(
                 return; // Skip execution if trading is disabled
             }
             
-            // Get executable signals from marketplace
-            let signals = marketplace.get_executable_signals(min_confidence).await;
+            // Record why low-confidence / expired signals are being passed over
+            marketplace.record_unexecutable_signals().await;
+            
+            // Get executable signals from marketplace (per-symbol confidence thresholds), most urgent first
+            let signals = marketplace.get_executable_signals().await;
             
             if signals.is_empty() {
                 return;
@@ -97,113 +445,36 @@ async fn auto_execute_marketplace_signals(
             
             log::info!("🔍 Found {} high-confidence signals ready for auto-execution", signals.len());
             
+            // CONCURRENCY: One task per symbol, up to AUTO_EXEC_MAX_CONCURRENCY at once - a symbol's signals
+            // still run in order, and one token's slow price checks no longer hold up the others
+            // Symbols are dispatched in the order of their most urgent signal, so scalps start first
+            // (the engine call itself still takes the engine lock)
+            let mut by_symbol: Vec<(String, Vec<signal_platform::TradingSignalData>)> = Vec::new();
             for signal in signals {
-                log::info!("📈 Processing signal: {} | Symbol: {} | Confidence: {:.1}% | Provider: {}", 
-                          signal.id, signal.symbol, signal.confidence * 100.0, signal.provider);
-                
-                // FIX #1: Atomic status update - mark as Executing BEFORE execution to prevent duplicate execution
-                match marketplace.try_mark_executing(&signal.id).await {
-                    Ok(true) => {
-                        log::debug!("🔒 Acquired execution lock for signal {}", signal.id);
-                    }
-                    Ok(false) => {
-                        log::warn!("⚠️ Signal {} already being processed by another task, skipping", signal.id);
-                        continue; // Already being processed
-                    }
-                    Err(e) => {
-                        log::warn!("⚠️ Failed to mark signal {} as Executing: {}", signal.id, e);
-                        continue;
-                    }
+                let symbol = symbol_registry::canonical(&signal.symbol);
+                match by_symbol.iter_mut().find(|(key, _)| *key == symbol) {
+                    Some((_, queue)) => queue.push(signal),
+                    None => by_symbol.push((symbol, vec![signal])),
                 }
-                
-                // Initialize performance tracking
-                if let Err(e) = enhanced_marketplace.initialize_signal_performance(&signal).await {
-                    log::warn!("⚠️ Failed to initialize performance tracking for {}: {}", signal.id, e);
-                    // FIX #1: Revert status on failure
-                    let _ = marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await;
-                    continue;
+            }
+            
+            let mut tasks = tokio::task::JoinSet::new();
+            for (_, queue) in by_symbol {
+                if tasks.len() >= max_concurrency {
+                    log_execution_task(tasks.join_next().await);
                 }
-                
-                // Execute signal via trading engine (REAL Solana transactions)
-                let mut engine = trading_engine.lock().await;
-                match engine.execute_marketplace_signal(&signal, Some(&trading_enabled), Some(&dry_run)).await {
-                    Ok(result) => {
-                        execution_count += 1;
-                        log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-                        log::info!("✅ AUTONOMOUS TRADE EXECUTED #{}", execution_count);
-                        log::info!("   Signal ID: {}", signal.id);
-                        log::info!("   Symbol: {}", signal.symbol);
-                        log::info!("   Action: {:?}", signal.action);
-                        log::info!("   Entry Price: ${:.8}", signal.entry_price);
-                        log::info!("   Target Price: ${:.8}", signal.target_price);
-                        log::info!("   Stop Loss: ${:.8}", signal.stop_loss);
-                        log::info!("   Result: {}", result);
-                        log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-                        
-                        // Mark signal as filled
-                        if let Err(e) = enhanced_marketplace.mark_signal_filled(&signal.id).await {
-                            log::warn!("⚠️ Failed to mark signal as filled: {}", e);
-                        }
-                        
-                        // FIX #1: Update marketplace signal status to Filled after successful execution
-                        if let Err(e) = marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await {
-                            log::warn!("⚠️ Failed to update signal status: {}", e);
-                        }
-                        
-                        // RL LEARNING: Update RL agents with trade outcome
-                        // Record experience via LearningCoordinator
-                        let coordinator = rl_coordinator.lock().await;
-                        // Calculate position size from signal (estimate based on confidence)
-                        let estimated_size = signal.entry_price * 0.05; // 5% of entry price as default size
-                        
-                        // Create experience for RL learning
-                        let experience = reinforcement_learning::Experience {
-                            state: reinforcement_learning::MarketState {
-                                symbol: signal.symbol.clone(),
-                                price: signal.entry_price,
-                                volume: 0.0,
-                                price_change_1h: 0.0,
-                                price_change_24h: 0.0,
-                                sentiment_score: signal.confidence * 100.0,
-                                liquidity: 0.0,
-                                volatility: 0.0,
-                                market_cap: None,
-                            },
-                            action: reinforcement_learning::Action {
-                                action_type: format!("{:?}", signal.action),
-                                confidence: signal.confidence,
-                                size: estimated_size,
-                                price: signal.entry_price,
-                            },
-                            reward: 0.0, // Will be updated when position closes
-                            next_state: None,
-                            timestamp: chrono::Utc::now().timestamp(),
-                            provider_id: signal.provider.clone(),
-                        };
-                        
-                        // Record experience via coordinator (it will route to the appropriate agent)
-                        coordinator.record_experience_for_provider(&signal.provider, experience).await;
-                        log::debug!("🧠 Recorded trade experience for RL agent: {}", signal.provider);
-                        drop(coordinator);
-                        
-                        // Note: Provider reputation is updated when signal performance is tracked
-                        // in the track_signal_performance function based on actual outcomes
-                    }
-                    Err(e) => {
-                        log::warn!("⚠️ Failed to auto-execute signal {}: {}", signal.id, e);
-                        log::warn!("   Signal remains in marketplace for manual execution");
-                        // Revert status to Active on failure (validated transition)
-                        match marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Active).await {
-                            Ok(_) => {
-                                log::info!("🔄 Signal {} reverted to Active for retry", signal.id);
-                            }
-                            Err(revert_err) => {
-                                log::warn!("⚠️ Failed to revert signal {} status to Active: {}", signal.id, revert_err);
-                                log::warn!("   Signal may be stuck in Executing state - manual intervention may be needed");
-                            }
+                let executor = executor.clone();
+                tasks.spawn(async move {
+                    for signal in queue {
+                        if executor.shutdown.is_triggered() {
+                            break;
                         }
+                        executor.execute_signal(signal).await;
                     }
-                }
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                log_execution_task(Some(joined));
             }
         }).catch_unwind().await;
         
@@ -230,40 +501,213 @@ async fn auto_execute_marketplace_signals(
 }
 
 /// Track signal performance with real-time price updates
+/// RL experience for one partial exit - reward is the leg's P/L weighted by the share it closed
+fn partial_exit_experience(
+    signal: &signal_platform::TradingSignalData,
+    leg: &enhanced_marketplace::ExitLeg,
+) -> reinforcement_learning::Experience {
+    reinforcement_learning::Experience::entry(signal, signal.entry_price, leg.fraction)
+        .closed_at(leg.price, leg.pnl_pct * leg.fraction * 100.0, None)
+}
+
+/// RL experience for a position closed at `exit_price`, rewarded by `reward_pct`
+fn exit_experience(
+    signal: &signal_platform::TradingSignalData,
+    exit_price: f64,
+    reward_pct: f64,
+    reason: enhanced_marketplace::ExitReason,
+) -> reinforcement_learning::Experience {
+    reinforcement_learning::Experience::entry(signal, signal.entry_price, (signal.target_price - signal.entry_price) * 0.05) // Estimate size
+        .closed_at(exit_price, reward_pct * 100.0, Some(reason))
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn track_signal_performance(
+    trading_engine: Arc<Mutex<trading_engine::TradingEngine>>,
+    database: Arc<Mutex<database::Database>>,
     marketplace: Arc<signal_platform::SignalMarketplace>,
     enhanced_marketplace: Arc<enhanced_marketplace::EnhancedMarketplace>,
-    _oracle_client: Arc<switchboard_oracle::SwitchboardClient>,
+    oracle_client: Arc<switchboard_oracle::SwitchboardClient>,
     rl_coordinator: Arc<Mutex<reinforcement_learning::LearningCoordinator>>,
+    intervals: loop_intervals::LoopIntervals,
+    notifier: Option<Arc<dyn notifications::Notifier>>,
+    heartbeat: watchdog::Heartbeat,
+    clock: Arc<dyn historical_data::ReplayClock>,
 ) {
-    log::info!("📊 Signal Performance Tracker started - updating prices every 10 seconds");
+    log::info!("📊 Signal Performance Tracker started - updating prices every {} seconds", intervals.perf_tracker_secs());
+    
+    let break_even_config = enhanced_marketplace::BreakEvenConfig::from_env();
+    if break_even_config.enabled {
+        log::info!("🛡️ Break-even stop: move stop to entry +{:.2}% once profit reaches +{:.2}%",
+                  break_even_config.fee_buffer_pct, break_even_config.trigger_pct);
+    }
+    
+    let ladder_config = enhanced_marketplace::TakeProfitLadderConfig::from_env();
+    for (tier, rungs) in ladder_config.ladders.iter() {
+        let rung_desc: Vec<String> = rungs.iter()
+            .map(|r| format!("{:.0}% @ +{:.1}%", r.close_fraction * 100.0, r.profit_pct))
+            .collect();
+        log::info!("🪜 Take-profit ladder for {:?}: {} (trailing remainder {:.1}%)",
+                  tier, rung_desc.join(", "), ladder_config.trail_pct);
+    }
     
-    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
+    // Equity curve sampling (PORTFOLIO_SNAPSHOT_INTERVAL_SECS)
+    let snapshot_interval_secs = std::env::var("PORTFOLIO_SNAPSHOT_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(database::DEFAULT_SNAPSHOT_INTERVAL_SECS);
+    log::info!("📈 Portfolio snapshots every {}s", snapshot_interval_secs);
+    let mut last_snapshot_at = 0i64;
+    
+    // REPLAY: Ticks and hold times run on the shared replay clock
+    let mut ticker = loop_intervals::Ticker::new(clock.clone(), intervals.perf_tracker_secs());
     let mut consecutive_errors = 0u32;
     let max_consecutive_errors = 10u32;
     
     // CRASH PROTECTION: Wrap main loop in panic handler
     loop {
+        // CADENCE: Pick up interval changes from /config/intervals
+        if ticker.retune(intervals.perf_tracker_secs()) {
+            log::info!("⏱️ Performance tracker now updating every {}s", intervals.perf_tracker_secs());
+        }
+        
         // CRASH PROTECTION: Catch panics in performance tracking loop
         let result = std::panic::AssertUnwindSafe(async {
-            interval.tick().await;
+            ticker.tick().await;
+            heartbeat.beat();
             
             // Get all active signals
-            let active_signals = marketplace.get_active_signals().await;
+            let mut active_signals = marketplace.get_active_signals().await;
+            // TIME EXIT: Executed signals are Filled (and may be past expiry) but their positions stay
+            // open until closed - keep tracking them so max hold, target and stop still apply
+            for signal_id in enhanced_marketplace.open_position_ids().await {
+                if !active_signals.iter().any(|s| s.id == signal_id) {
+                    if let Some(signal) = marketplace.get_signal(&signal_id).await {
+                        active_signals.push(signal);
+                    }
+                }
+            }
             
             for signal in active_signals {
                 // Get current price from oracle or other sources
-                let current_price = match _oracle_client.fetch_price(&signal.symbol).await {
+                // No closing decisions without a fresh price - falling back to the entry price
+                // would hide real P/L and could close (or hold) positions on made-up data
+                // Memecoins have no oracle feed - they are priced from their DEX pool instead
+                let current_price = match oracle_client.fetch_price_or_pool(&signal.symbol).await {
+                    Ok(feed) if feed.stale => {
+                        log::debug!("⏰ Skipping performance update for {} - oracle price is {}s old",
+                                   signal.id, feed.age_secs());
+                        continue;
+                    }
                     Ok(feed) => feed.price,
-                    Err(_) => {
-                        // Try to get price from signal's entry price as fallback
-                        signal.entry_price
+                    Err(e) => {
+                        log::warn!("⚠️ Performance of {} is unverified - no oracle or pool price: {}", signal.id, e);
+                        continue;
                     }
                 };
                 
-                // Update performance tracking
-                if let Err(e) = enhanced_marketplace.update_signal_performance(&signal.id, current_price).await {
-                    log::debug!("Could not update performance for {}: {}", signal.id, e);
+                // Update performance tracking (closes trailing-stop positions that fell off their peak)
+                match enhanced_marketplace.update_signal_performance(&signal.id, current_price).await {
+                    Ok(Some(closed_perf)) => {
+                        log::info!("📉 Signal {} closed by trailing stop at {:.2}% P/L (peak ${:.8})",
+                                  signal.id, closed_perf.profit_loss_pct, closed_perf.highest_price);
+                        marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
+                        notifications::dispatch(&notifier, notifications::Notification::new(
+                            notifications::NotificationEvent::StopLossHit,
+                            "📉 Trailing stop hit",
+                            &format!("{} trailing stop hit at ${:.8} ({:+.2}%, peak ${:.8}) | {}",
+                                     signal.symbol, closed_perf.exit_price.unwrap_or(current_price),
+                                     closed_perf.profit_loss_pct, closed_perf.highest_price, signal.provider),
+                        ));
+                        
+                        // RL LEARNING: Reward is the P/L locked in by the trailing stop
+                        // After take-profit levels, only the trailed remainder is rewarded here -
+                        // the earlier legs were already recorded as they filled
+                        let exit_price = closed_perf.exit_price.unwrap_or(current_price);
+                        let reward_pct = if closed_perf.rungs_filled > 0 && !closed_perf.take_profit_levels.is_empty() {
+                            (exit_price - closed_perf.entry_price) / closed_perf.entry_price * 100.0
+                                * (1.0 - closed_perf.levels_closed_fraction())
+                        } else {
+                            closed_perf.profit_loss_pct
+                        };
+                        let coordinator = rl_coordinator.lock().await;
+                        let experience = exit_experience(&signal, exit_price, reward_pct, enhanced_marketplace::ExitReason::TrailingStop);
+                        coordinator.record_experience_for_provider(&signal.provider, experience).await;
+                        drop(coordinator);
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::debug!("Could not update performance for {}: {}", signal.id, e),
+                }
+                
+                // Lock in break-even once the position is far enough in profit
+                if let Err(e) = enhanced_marketplace.apply_break_even_stop(&signal.id, &break_even_config).await {
+                    log::debug!("Could not apply break-even stop for {}: {}", signal.id, e);
+                }
+                
+                // Scale out at the position's own take-profit levels (multiples of its target)
+                // These take precedence over the provider tier's ladder
+                let has_levels = enhanced_marketplace.get_signal_performance(&signal.id).await
+                    .map(|p| !p.take_profit_levels.is_empty())
+                    .unwrap_or(false);
+                if has_levels {
+                    match enhanced_marketplace.apply_take_profit_levels(&signal.id, ladder_config.trail_pct).await {
+                        Ok((legs, closed)) => {
+                            // RL LEARNING: One experience per partial exit, weighted by the share it closed
+                            if !legs.is_empty() {
+                                let coordinator = rl_coordinator.lock().await;
+                                for leg in &legs {
+                                    log::info!("🪜 Signal {} sold {:.0}% at ${:.8} ({:+.2}%)",
+                                              signal.id, leg.fraction * 100.0, leg.price, leg.pnl_pct);
+                                    notifications::dispatch(&notifier, notifications::Notification::new(
+                                        notifications::NotificationEvent::TargetHit,
+                                        "🪜 Take-profit level hit",
+                                        &format!("{} sold {:.0}% at ${:.8} ({:+.2}%) | {}",
+                                                 signal.symbol, leg.fraction * 100.0, leg.price, leg.pnl_pct, signal.provider),
+                                    ));
+                                    let experience = partial_exit_experience(&signal, leg);
+                                    coordinator.record_experience_for_provider(&signal.provider, experience).await;
+                                }
+                                drop(coordinator);
+                            }
+                            if let Some(closed_perf) = closed {
+                                log::info!("🪜 Signal {} fully scaled out via take-profit levels - total {:.2}% P/L",
+                                          signal.id, closed_perf.profit_loss_pct);
+                                marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
+                                continue;
+                            }
+                        }
+                        Err(e) => log::debug!("Could not apply take-profit levels for {}: {}", signal.id, e),
+                    }
+                }
+                
+                // Scale out along the provider tier's take-profit ladder (if one is configured)
+                let has_ladder = !has_levels && ladder_config.ladder_for(&signal.provider).is_some();
+                if has_ladder {
+                    match enhanced_marketplace.apply_take_profit_ladder(&signal.id, &ladder_config).await {
+                        Ok(Some(closed_perf)) => {
+                            // The last rung's sale as it filled, not the price that triggered it
+                            let exit_price = closed_perf.exit_price.unwrap_or(current_price);
+                            log::info!("🪜 Signal {} fully scaled out via ladder - total {:.2}% P/L",
+                                      signal.id, closed_perf.profit_loss_pct);
+                            marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
+                            notifications::dispatch(&notifier, notifications::Notification::new(
+                                notifications::NotificationEvent::TargetHit,
+                                "🪜 Take-profit ladder complete",
+                                &format!("{} fully scaled out at ${:.8} - total {:+.2}% | {}",
+                                         signal.symbol, exit_price, closed_perf.profit_loss_pct, signal.provider),
+                            ));
+                            
+                            // RL LEARNING: Reward is the size-weighted P/L across all rungs
+                            let coordinator = rl_coordinator.lock().await;
+                            let experience = exit_experience(&signal, exit_price, closed_perf.profit_loss_pct, enhanced_marketplace::ExitReason::TakeProfitLevels);
+                            coordinator.record_experience_for_provider(&signal.provider, experience).await;
+                            drop(coordinator);
+                            continue;
+                        }
+                        Ok(None) => {}
+                        Err(e) => log::debug!("Could not apply take-profit ladder for {}: {}", signal.id, e),
+                    }
                 }
                 
                 // Check if target or stop loss hit
@@ -273,13 +717,24 @@ async fn track_signal_performance(
                 };
                 
                 // Auto-close if target or stop loss reached
+                // Use the position's effective stop, which may have been tightened to break-even
+                // or trailed by the ladder. Laddered and trailing-stop positions let winners run past the target.
                 let target_pct = (signal.target_price - signal.entry_price) / signal.entry_price * 100.0;
-                let stop_loss_pct = (signal.stop_loss - signal.entry_price) / signal.entry_price * 100.0;
+                let stop_loss_pct = (perf.stop_price - signal.entry_price) / signal.entry_price * 100.0;
+                let lets_winners_run = has_levels || has_ladder || perf.trailing_stop_pct.is_some();
                 
-                if perf.profit_loss_pct >= target_pct {
+                if !lets_winners_run && perf.profit_loss_pct >= target_pct {
                     // Target reached - close with profit
-                    if let Ok(_closed_perf) = enhanced_marketplace.close_signal_position(&signal.id, current_price).await {
-                        log::info!("🎯 Signal {} target reached! Closed with {:.2}% profit", signal.id, perf.profit_loss_pct);
+                    if let Ok(closed_perf) = enhanced_marketplace.close_signal_position(&signal.id, current_price, enhanced_marketplace::ExitReason::Target).await {
+                        // Booked at what the exit sale filled at
+                        let exit_price = closed_perf.exit_price.unwrap_or(current_price);
+                        log::info!("🎯 Signal {} target reached! Closed with {:.2}% profit", signal.id, closed_perf.profit_loss_pct);
+                        notifications::dispatch(&notifier, notifications::Notification::new(
+                            notifications::NotificationEvent::TargetHit,
+                            "🎯 Target hit",
+                            &format!("{} target hit at ${:.8} ({:+.2}%) | {}",
+                                     signal.symbol, exit_price, closed_perf.profit_loss_pct, signal.provider),
+                        ));
                         marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
                         
                         // REPUTATION UPDATE: Already handled in close_signal_position
@@ -292,49 +747,34 @@ async fn track_signal_performance(
                         
                         // RL LEARNING: Update agent with successful outcome
                         let coordinator = rl_coordinator.lock().await;
-                        let reward = perf.profit_loss_pct; // Positive reward = profit percentage
-                        let experience = reinforcement_learning::Experience {
-                            state: reinforcement_learning::MarketState {
-                                symbol: signal.symbol.clone(),
-                                price: signal.entry_price,
-                                volume: 0.0,
-                                price_change_1h: 0.0,
-                                price_change_24h: 0.0,
-                                sentiment_score: signal.confidence * 100.0,
-                                liquidity: 0.0,
-                                volatility: 0.0,
-                                market_cap: None,
-                            },
-                            action: reinforcement_learning::Action {
-                                action_type: format!("{:?}", signal.action),
-                                confidence: signal.confidence,
-                                size: (signal.target_price - signal.entry_price) * 0.05, // Estimate size
-                                price: signal.entry_price,
-                            },
-                            reward: reward * 100.0, // Scale reward (profit percentage)
-                            next_state: Some(reinforcement_learning::MarketState {
-                                symbol: signal.symbol.clone(),
-                                price: current_price,
-                                volume: 0.0,
-                                price_change_1h: 0.0,
-                                price_change_24h: 0.0,
-                                sentiment_score: signal.confidence * 100.0,
-                                liquidity: 0.0,
-                                volatility: 0.0,
-                                market_cap: None,
-                            }),
-                            timestamp: chrono::Utc::now().timestamp(),
-                            provider_id: signal.provider.clone(),
-                        };
+                        let reward = closed_perf.profit_loss_pct; // Positive reward = profit percentage
+                        let experience = exit_experience(&signal, exit_price, reward, enhanced_marketplace::ExitReason::Target);
                         coordinator.record_experience_for_provider(&signal.provider, experience).await;
-                        log::debug!("🧠 Recorded successful trade outcome for RL agent: {} (profit: {:.2}%)", signal.provider, perf.profit_loss_pct);
+                        log::debug!("🧠 Recorded successful trade outcome for RL agent: {} (profit: {:.2}%)", signal.provider, reward);
                         drop(coordinator);
                     }
                 } else if perf.profit_loss_pct <= stop_loss_pct {
                     // Stop loss hit - close with loss
-                    if let Ok(_closed_perf) = enhanced_marketplace.close_signal_position(&signal.id, current_price).await {
-                        log::warn!("🛑 Signal {} stop loss hit! Closed with {:.2}% loss", signal.id, perf.profit_loss_pct);
+                    let exit_reason = if perf.break_even_applied {
+                        enhanced_marketplace::ExitReason::BreakEvenStop
+                    } else {
+                        enhanced_marketplace::ExitReason::StopLoss
+                    };
+                    if let Ok(closed_perf) = enhanced_marketplace.close_signal_position(&signal.id, current_price, exit_reason).await {
+                        // Booked at what the exit sale filled at
+                        let exit_price = closed_perf.exit_price.unwrap_or(current_price);
+                        if perf.break_even_applied {
+                            log::info!("🛡️ Signal {} break-even stop hit! Closed at {:.2}%", signal.id, closed_perf.profit_loss_pct);
+                        } else {
+                            log::warn!("🛑 Signal {} stop loss hit! Closed with {:.2}% loss", signal.id, closed_perf.profit_loss_pct);
+                        }
                         marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
+                        notifications::dispatch(&notifier, notifications::Notification::new(
+                            notifications::NotificationEvent::StopLossHit,
+                            if perf.break_even_applied { "🛡️ Break-even stop hit" } else { "🛑 Stop-loss hit" },
+                            &format!("{} stop hit at ${:.8} ({:+.2}%) | {}",
+                                     signal.symbol, exit_price, closed_perf.profit_loss_pct, signal.provider),
+                        ));
                         
                         // REPUTATION UPDATE: Already handled in close_signal_position
                         // Enhanced marketplace automatically updates provider reputation with:
@@ -344,44 +784,49 @@ async fn track_signal_performance(
                         
                         // RL LEARNING: Update agent with failed outcome
                         let coordinator = rl_coordinator.lock().await;
-                        let reward = perf.profit_loss_pct; // Negative reward = loss percentage
-                        let experience = reinforcement_learning::Experience {
-                            state: reinforcement_learning::MarketState {
-                                symbol: signal.symbol.clone(),
-                                price: signal.entry_price,
-                                volume: 0.0,
-                                price_change_1h: 0.0,
-                                price_change_24h: 0.0,
-                                sentiment_score: signal.confidence * 100.0,
-                                liquidity: 0.0,
-                                volatility: 0.0,
-                                market_cap: None,
-                            },
-                            action: reinforcement_learning::Action {
-                                action_type: format!("{:?}", signal.action),
-                                confidence: signal.confidence,
-                                size: (signal.entry_price - signal.stop_loss) * 0.05, // Estimate size
-                                price: signal.entry_price,
-                            },
-                            reward: reward * 100.0, // Negative reward (loss percentage)
-                            next_state: Some(reinforcement_learning::MarketState {
-                                symbol: signal.symbol.clone(),
-                                price: current_price,
-                                volume: 0.0,
-                                price_change_1h: 0.0,
-                                price_change_24h: 0.0,
-                                sentiment_score: signal.confidence * 100.0,
-                                liquidity: 0.0,
-                                volatility: 0.0,
-                                market_cap: None,
-                            }),
-                            timestamp: chrono::Utc::now().timestamp(),
-                            provider_id: signal.provider.clone(),
-                        };
+                        let reward = closed_perf.profit_loss_pct; // Negative reward = loss percentage
+                        let experience = reinforcement_learning::Experience::entry(&signal, signal.entry_price, (signal.entry_price - signal.stop_loss) * 0.05) // Estimate size
+                            .closed_at(exit_price, reward * 100.0, Some(exit_reason));
                         coordinator.record_experience_for_provider(&signal.provider, experience).await;
-                        log::debug!("🧠 Recorded failed trade outcome for RL agent: {} (loss: {:.2}%)", signal.provider, perf.profit_loss_pct);
+                        log::debug!("🧠 Recorded failed trade outcome for RL agent: {} (loss: {:.2}%)", signal.provider, reward);
                         drop(coordinator);
                     }
+                } else if let Some(overdue_secs) = perf.overdue_secs(clock.now_secs()) {
+                    // TIME EXIT: Neither target nor stop hit within the max hold - the thesis has decayed,
+                    // close at market instead of letting the position turn into a bag
+                    if let Ok(closed_perf) = enhanced_marketplace.close_signal_position(&signal.id, current_price, enhanced_marketplace::ExitReason::TimeExit).await {
+                        log::info!("⌛ Signal {} max hold of {}s reached ({}s over) - closed at market with {:.2}% P/L",
+                                  signal.id, perf.max_hold_secs.unwrap_or_default(), overdue_secs, closed_perf.profit_loss_pct);
+                        marketplace.update_signal_status(&signal.id, signal_platform::SignalStatus::Filled).await.ok();
+                        
+                        // RL LEARNING: Earlier take-profit legs were already rewarded - only the remainder counts here
+                        let exit_price = closed_perf.exit_price.unwrap_or(current_price);
+                        let reward_pct = if closed_perf.rungs_filled > 0 && !closed_perf.take_profit_levels.is_empty() {
+                            (exit_price - closed_perf.entry_price) / closed_perf.entry_price * 100.0
+                                * (1.0 - closed_perf.levels_closed_fraction())
+                        } else {
+                            closed_perf.profit_loss_pct
+                        };
+                        let experience = exit_experience(&signal, exit_price, reward_pct, enhanced_marketplace::ExitReason::TimeExit);
+                        rl_coordinator.lock().await.record_experience_for_provider(&signal.provider, experience).await;
+                    }
+                }
+            }
+            
+            // EQUITY CURVE: Record portfolio value on its own interval, after marks were refreshed
+            // (real time, like every other capital sample in the drawdown window)
+            let now = chrono::Utc::now().timestamp();
+            if now - last_snapshot_at >= snapshot_interval_secs {
+                let (snapshot, risk_manager) = {
+                    let engine = trading_engine.lock().await;
+                    // Tagged with the mode trades execute in right now, not the one at startup
+                    (engine.portfolio_snapshot(solana_integration::SolanaClient::dry_run_mode()), engine.risk_manager.clone())
+                };
+                // Rolling drawdown window: sample capital alongside the equity curve
+                risk_manager.lock().await.sample_capital(now);
+                match database.lock().await.record_portfolio_snapshot(snapshot) {
+                    Ok(()) => last_snapshot_at = now,
+                    Err(e) => log::warn!("⚠️ Failed to record portfolio snapshot: {}", e),
                 }
             }
         }).catch_unwind().await;
@@ -413,13 +858,21 @@ async fn main() {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
     
-    pretty_env_logger::init();
+    // LOG_FORMAT=json for one JSON object per line (Loki/ELK), pretty otherwise
+    logging::init();
     
-    // SAFETY: Check for dry-run mode
-    let dry_run_mode = std::env::var("DRY_RUN_MODE")
-        .unwrap_or_else(|_| "true".to_string())
-        .parse::<bool>()
-        .unwrap_or(true); // Default to true for safety
+    // SAFETY: Check for dry-run mode (defaults to true; a replay only ever paper-trades)
+    let dry_run_mode = solana_integration::SolanaClient::dry_run_mode();
+    
+    // REPLAY: SESSION_MODE=record logs feeds and signals to SESSION_FILE, replay drives the engine from it
+    let session_mode = historical_data::SessionMode::from_env();
+    // Oracle and Jupiter clients read replayed prices instead of the network while replaying
+    let replay_prices = session_mode.is_replay().then(historical_data::ReplayPrices::new);
+    // One clock for the replayed feed, the executor and the tracker - REPLAY_SPEED times real time while replaying
+    let replay_clock: Arc<dyn historical_data::ReplayClock> = match session_mode {
+        historical_data::SessionMode::Replay { speed, .. } => Arc::new(historical_data::SimulatedClock::new(speed)),
+        _ => Arc::new(historical_data::WallClock),
+    };
     
     // SAFETY: Check if trading should be enabled
     let env_trading_enabled = std::env::var("ENABLE_TRADING")
@@ -436,6 +889,9 @@ async fn main() {
         .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
     let is_mainnet = rpc_url.contains("mainnet");
     
+    // Extra / overriding Switchboard feeds (ORACLE_FEED_ADDRESSES) - shared by every oracle client
+    switchboard_oracle::FeedRegistry::global().load_from_env();
+    
     // PRODUCTION READINESS: Startup validation
     log::info!("🔍 Running startup validation checks...");
     let mut validation_errors: Vec<String> = Vec::new();
@@ -549,32 +1005,177 @@ async fn main() {
         None
     };
     
-    // Initialize Error Handling Circuit Breaker
+    // Initialize optional webhook notifier (no-op when NOTIFY_WEBHOOK_URL is unset)
+    let notifier = notifications::notifier_from_env();
+    match &notifier {
+        Some(n) => log::info!("🔔 Notifications enabled via {}", n.name()),
+        None => log::info!("🔕 Notifications disabled (set NOTIFY_WEBHOOK_URL to enable)"),
+    }
+    
+    // Initialize Error Handling Circuit Breaker (alerts when it opens)
     log::info!("⚡ Initializing Circuit Breaker...");
     let circuit_breaker = Arc::new(Mutex::new(
         error_handling::CircuitBreaker::new(5, 3, std::time::Duration::from_secs(60))
+            .with_notifier(notifier.clone())
     ));
     
+    // Initialize watchdog that re-spawns background loops which stop reporting heartbeats
+    let watchdog = Arc::new(watchdog::Watchdog::new(watchdog::WatchdogConfig::from_env(), notifier.clone()));
+    
     // Initialize Solana client with wallet and PDA integration
     let solana_client = Arc::new(Mutex::new(
         solana_integration::SolanaClient::new_with_integration(rpc_url.clone()).await
     ));
+    
+    // WALLETS: Strategy sub-wallets (STRATEGY_WALLETS) isolate providers' capital in their own PDA treasuries
+    let wallet_manager = key_manager::WalletManager::from_env();
+    let mut strategy_wallets = std::collections::HashMap::new();
+    for wallet_name in wallet_manager.list_wallets() {
+        if wallet_name == wallet::DEFAULT_WALLET_NAME {
+            continue;
+        }
+        match solana_integration::SolanaClient::new_for_wallet(rpc_url.clone(), &wallet_name).await {
+            Ok(client) => {
+                log::info!("👛 Strategy wallet {} connected for {:?}", wallet_name,
+                          wallet_manager.providers_for_wallet(&wallet_name));
+                strategy_wallets.insert(wallet_name, Arc::new(Mutex::new(client)));
+            }
+            Err(e) => log::error!("❌ Strategy wallet {} unavailable - its providers cannot trade: {}", wallet_name, e),
+        }
+    }
 
-    let risk_manager = Arc::new(Mutex::new(risk_management::RiskManager::new(10000.0, 0.1)));
+    // Max quoted price impact (percent) per order - larger orders are sized down to fit
+    let max_acceptable_slippage = std::env::var("MAX_ACCEPTABLE_SLIPPAGE_PCT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(risk_management::DEFAULT_MAX_ACCEPTABLE_SLIPPAGE);
+    // Largest share of the live treasury one trade may use (percent, MAX_POSITION_PCT)
+    let max_position_pct = std::env::var("MAX_POSITION_PCT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(risk_management::DEFAULT_MAX_POSITION_FRACTION * 100.0);
+    // Concurrent position caps (MAX_OPEN_POSITIONS / MAX_POSITIONS_PER_SYMBOL)
+    let max_open_positions = std::env::var("MAX_OPEN_POSITIONS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(risk_management::DEFAULT_MAX_OPEN_POSITIONS);
+    let max_positions_per_symbol = std::env::var("MAX_POSITIONS_PER_SYMBOL")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(risk_management::DEFAULT_MAX_POSITIONS_PER_SYMBOL);
+    // Cap on capital in one group of correlated positions (percent, MAX_CORRELATED_EXPOSURE_PCT)
+    let max_correlated_exposure_pct = std::env::var("MAX_CORRELATED_EXPOSURE_PCT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(risk_management::DEFAULT_MAX_CORRELATED_EXPOSURE * 100.0);
+    // Share of the treasury each provider may deploy (PROVIDER_ALLOCATION_PCT, overrides in PROVIDER_CAPITAL_ALLOCATIONS)
+    let provider_allocation_pct = std::env::var("PROVIDER_ALLOCATION_PCT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(risk_management::DEFAULT_PROVIDER_ALLOCATION * 100.0);
+    let provider_allocations = match std::env::var("PROVIDER_CAPITAL_ALLOCATIONS") {
+        Ok(value) => risk_management::parse_provider_allocations(&value).unwrap_or_else(|e| {
+            log::error!("❌ Invalid PROVIDER_CAPITAL_ALLOCATIONS ({}) - every provider gets {:.0}%", e, provider_allocation_pct);
+            std::collections::HashMap::new()
+        }),
+        Err(_) => std::collections::HashMap::new(),
+    };
+    let streak_sizing = risk_management::StreakSizing::from_env();
+    if streak_sizing.enabled {
+        log::info!("🎚️ Streak sizing: -{:.0}% per loss, +{:.0}% per win, {:.2}x-{:.2}x",
+                  streak_sizing.loss_step * 100.0, streak_sizing.win_step * 100.0,
+                  streak_sizing.min_multiplier, streak_sizing.max_multiplier);
+    }
+    let drawdown_window = risk_management::DrawdownWindow::from_env();
+    log::info!("📉 Drawdown measured from the {} peak", drawdown_window.label());
+    // A rolling window picks up where the last run's equity curve left off
+    let capital_history: Vec<(i64, f64)> = match drawdown_window {
+        risk_management::DrawdownWindow::Rolling { secs } => database.lock().await
+            .get_portfolio_history(Some(chrono::Utc::now().timestamp() - secs), None, Some(dry_run_mode))
+            .iter()
+            .map(|snapshot| (snapshot.timestamp, snapshot.cash_balance))
+            .collect(),
+        risk_management::DrawdownWindow::AllTime => Vec::new(),
+    };
+    let risk_manager = Arc::new(Mutex::new(
+        risk_management::RiskManager::new(10000.0, 0.1)
+            .with_max_acceptable_slippage(max_acceptable_slippage)
+            .with_position_limits(max_open_positions, max_positions_per_symbol)
+            .with_correlation_limit(max_correlated_exposure_pct / 100.0)
+            .with_provider_allocations(provider_allocation_pct / 100.0, provider_allocations)
+            .with_streak_sizing(streak_sizing)
+            .with_drawdown_window(drawdown_window)
+            .with_capital_history(capital_history)
+            .with_max_position_fraction(max_position_pct / 100.0)
+    ));
     
     // Initialize Fee Optimizer for transaction fee tracking
     log::info!("💰 Initializing Fee Optimizer...");
-    let fee_optimizer = Arc::new(Mutex::new(fee_optimization::FeeOptimizer::new(5000))); // Base fee: 5000 lamports
+    let fee_budget = fee_optimization::FeeBudget::from_env();
+    if let Some(limit) = fee_budget.limit_lamports {
+        log::info!("💸 Fee budget: {} lamports per {}s window", limit, fee_budget.window_secs);
+    }
+    // Base fee (FEE_BASE_LAMPORTS) plus the network's recent priority fees, sampled every PRIORITY_FEE_REFRESH_SECS
+    let priority_fees = fee_optimization::PriorityFeeConfig::from_env();
+    let fee_optimizer = Arc::new(Mutex::new(
+        fee_optimization::FeeOptimizer::new(priority_fees.base_fee_lamports)
+            .with_priority_fees(priority_fees.clone())
+            .with_budget(fee_budget)
+    ));
+    
+    // Initialize WebSocket broadcaster for real-time updates
+    log::info!("📡 Initializing WebSocket broadcaster...");
+    let ws_broadcaster = websocket::create_ws_broadcaster();
+    
+    // Initialize DEX aggregator fallback chain (Jupiter first, Raydium direct as fallback)
+    // A replay quotes Jupiter from the recording and has no Raydium fallback to reach the network
+    let (jupiter_client, aggregator_router) = match &replay_prices {
+        Some(prices) => {
+            let jupiter = Arc::new(jupiter_integration::JupiterClient::new().with_replay_prices(prices.clone()));
+            let router = dex_aggregator::AggregatorRouter::new(vec![jupiter.clone() as Arc<dyn dex_aggregator::DexAggregator>]);
+            (jupiter, Arc::new(router))
+        }
+        None => {
+            let jupiter = Arc::new(jupiter_integration::JupiterClient::new());
+            (jupiter.clone(), Arc::new(dex_aggregator::AggregatorRouter::default_chain(jupiter)))
+        }
+    };
+    
+    // Ctrl-C handle shared with the trading loops and both API servers
+    let shutdown = shutdown::Shutdown::new();
+    
+    if priority_fees.refresh_secs > 0 && !session_mode.is_replay() {
+        log::info!("⛽ Priority fees refreshed from the network every {}s (capped at {} micro-lamports per CU)",
+                  priority_fees.refresh_secs, priority_fees.max_compute_unit_price);
+        fee_optimization::spawn_priority_fee_refresh(fee_optimizer.clone(), rpc_url.clone(), priority_fees.refresh_secs, shutdown.clone());
+    }
+    
+    // Manual mint blacklist / whitelist shared by the providers, the engine and /config/token-filter
+    let token_filter = security::TokenFilter::from_env().await;
+    let token_filter_settings = token_filter.settings().await;
+    log::info!("🚧 Token filter: {:?} ({} blacklisted, {} whitelisted)", token_filter_settings.mode,
+              token_filter_settings.blacklist.len(), token_filter_settings.whitelist.len());
     
     // Initialize Trading Engine with REAL Solana integration
-    let trading_engine = Arc::new(Mutex::new(
-        trading_engine::TradingEngine::new_with_solana(
-            risk_manager.clone(),
-            solana_client.clone(),
-            Some(Arc::new(jupiter_integration::JupiterClient::new())),
-            Some(fee_optimizer.clone()), // PASS: Fee optimizer for transaction tracking
-        )
-    ));
+    let mut engine = trading_engine::TradingEngine::new_with_solana(
+        risk_manager.clone(),
+        solana_client.clone(),
+        Some(jupiter_client),
+        Some(fee_optimizer.clone()), // PASS: Fee optimizer for transaction tracking
+    ).with_ws_broadcaster(ws_broadcaster.clone()) // PASS: Push trade executions to WebSocket clients
+    .with_aggregator_router(aggregator_router) // PASS: Route real swaps by best net output
+    .with_database(database.clone()) // PASS: Execution audit trail
+    .with_shutdown(shutdown.clone()) // PASS: Refuse new trades once shutdown starts
+    .with_token_filter(token_filter.clone()) // PASS: Never buy filtered mints
+    .with_strategy_wallets(wallet_manager, strategy_wallets) // PASS: Per-provider strategy wallets
+    .with_clock(replay_clock.clone()); // PASS: Cooldowns age on the replay clock
+    
+    // MEV PROTECTION: Trades worth at least JITO_BUNDLE_MIN_TRADE_VALUE go out as atomic Jito bundles
+    if let Some(min_trade_value) = std::env::var("JITO_BUNDLE_MIN_TRADE_VALUE").ok().and_then(|v| v.parse::<f64>().ok()) {
+        log::info!("🛡️ Jito bundles enabled for trades worth >= {:.4} SOL", min_trade_value);
+        engine = engine.with_jito_bundles(Arc::new(jito_bam::JitoBamClient::with_config(is_mainnet, jito_bam::BundleConfig::from_env())), min_trade_value);
+    }
+    let trading_engine = Arc::new(Mutex::new(engine));
     
     // Sync initial balance from PDA
     {
@@ -583,14 +1184,39 @@ async fn main() {
         log::info!("💰 Trading engine initialized with balance: {:.6} SOL", engine.current_balance);
     }
 
-    // Initialize WebSocket broadcaster for real-time updates
-    log::info!("📡 Initializing WebSocket broadcaster...");
-    let ws_broadcaster = websocket::create_ws_broadcaster();
-    
     // Initialize Reinforcement Learning Coordinator
     log::info!("🤖 Initializing RL Coordinator...");
     let rl_coordinator = Arc::new(Mutex::new(reinforcement_learning::LearningCoordinator::new()));
     
+    // Resume learning from disk before providers register their agents
+    // RL_STATE_PATH (default rl_state.json), RL_AUTOSAVE_INTERVAL_SECS (default 300, 0 disables)
+    let rl_state_path = std::path::PathBuf::from(
+        std::env::var("RL_STATE_PATH").unwrap_or_else(|_| "rl_state.json".to_string())
+    );
+    if let Err(e) = rl_coordinator.lock().await.load_state(&rl_state_path).await {
+        log::warn!("⚠️ Could not load RL state from {}: {} - starting fresh", rl_state_path.display(), e);
+    }
+    let rl_autosave_secs = std::env::var("RL_AUTOSAVE_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(300);
+    if rl_autosave_secs > 0 {
+        let autosave_coordinator = rl_coordinator.clone();
+        let autosave_path = rl_state_path.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(rl_autosave_secs));
+            interval.tick().await; // Skip the immediate first tick
+            loop {
+                interval.tick().await;
+                let coordinator = autosave_coordinator.lock().await;
+                if let Err(e) = coordinator.save_state(&autosave_path).await {
+                    log::warn!("⚠️ RL state autosave failed: {}", e);
+                }
+            }
+        });
+        log::info!("💾 RL state autosave every {}s to {}", rl_autosave_secs, rl_state_path.display());
+    }
+    
     // Initialize Twitter Sentiment Client (optional - service may not be running)
     log::info!("🐦 Initializing Twitter Sentiment Client...");
     let twitter_sentiment_url = std::env::var("TWITTER_SENTIMENT_SERVICE_URL")
@@ -619,7 +1245,7 @@ async fn main() {
     // Start PumpFun WebSocket listener for real-time token updates
     let pumpfun_ws_client = pumpfun_client_shared.clone();
     let _pumpfun_ws_handle = pumpfun_ws_client.start_websocket_listener();
-    log::info!("✅ PumpFun WebSocket listener started (will connect when available)");
+    log::info!("✅ PumpFun WebSocket listener started (reconnects with backoff, status on /health)");
     
     // Start periodic pump.fun page scraping for trading opportunities
     let pumpfun_scraper = pumpfun_client_shared.clone();
@@ -675,18 +1301,24 @@ async fn main() {
 
     // Start periodic PDA balance sync (ensures agents always use real balance)
     let solana_client_sync = solana_client.clone();
+    let sync_engine = trading_engine.clone();
     tokio::spawn(async move {
         loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(30)).await; // Every 30 seconds
-            let mut client_lock = solana_client_sync.lock().await;
-            client_lock.sync_trading_budget_from_pda().await;
-            log::debug!("🔄 Synced trading budget from REAL PDA balance: {:.6} SOL", client_lock.trading_budget);
+            if dry_run_mode {
+                let mut client_lock = solana_client_sync.lock().await;
+                client_lock.sync_trading_budget_from_pda().await;
+                log::debug!("🔄 Synced trading budget from REAL PDA balance: {:.6} SOL", client_lock.trading_budget);
+            } else {
+                // SIZING: Live sizing and risk checks read the synced treasury, never the startup figure
+                sync_engine.lock().await.sync_balance_from_pda().await;
+            }
         }
     });
 
     // Start market data simulation ONLY if no real RPC URL is configured
     let use_real_data = std::env::var("SOLANA_RPC_URL").is_ok();
-    if !use_real_data {
+    if !use_real_data && !session_mode.is_replay() {
         log::warn!("⚠️  No SOLANA_RPC_URL configured - using simulated market data");
         log::info!("📊 Starting market data simulation (add SOLANA_RPC_URL to .env for real data)");
     let market_engine = trading_engine.clone();
@@ -706,22 +1338,144 @@ async fn main() {
     });
 
     // Initialize Signal Marketplace
-    let marketplace = Arc::new(signal_platform::SignalMarketplace::new(rpc_url.clone()));
+    let session_recorder = match &session_mode {
+        historical_data::SessionMode::Record { path } => match historical_data::SessionRecorder::create(path) {
+            Ok(recorder) => {
+                log::info!("⏺️ Recording feed updates and signals to {}", path.display());
+                Some(recorder)
+            }
+            Err(e) => {
+                log::error!("❌ Session recording disabled: {}", e);
+                None
+            }
+        },
+        _ => None,
+    };
+    let session_oracle = |client: switchboard_oracle::SwitchboardClient| match &replay_prices {
+        Some(prices) => client.with_replay_prices(prices.clone()),
+        None => client,
+    };
+    
+    // LIFECYCLE CALLBACKS: Durable published / executed / closed events for SIGNAL_CALLBACK_URL
+    let signal_callbacks = signal_callbacks::SignalCallbacks::from_env(database.clone());
+    if let Some(ref callbacks) = signal_callbacks {
+        callbacks.clone().spawn(shutdown.clone());
+    }
+    
+    let mut marketplace = signal_platform::SignalMarketplace::new(rpc_url.clone())
+        .with_database(database.clone()) // PASS: Signal history
+        .with_clock(replay_clock.clone()); // PASS: Expiry and hold times on the replay clock
+    // MIN_RISK_REWARD: Publish-time floor on target distance / stop distance (0 disables)
+    if let Some(min_risk_reward) = std::env::var("MIN_RISK_REWARD").ok().and_then(|v| v.parse::<f64>().ok()) {
+        log::info!("⚖️ Minimum signal risk/reward: {:.2}", min_risk_reward);
+        marketplace = marketplace.with_min_risk_reward(min_risk_reward);
+    }
+    if let Some(ref recorder) = session_recorder {
+        marketplace = marketplace.with_recorder(recorder.clone());
+    }
+    if let Some(ref callbacks) = signal_callbacks {
+        marketplace = marketplace.with_callbacks(callbacks.clone());
+    }
+    let marketplace = Arc::new(marketplace);
+    
+    // Collapse near-identical signals from different providers (SIGNAL_DEDUPE_ENABLED / SIGNAL_DEDUPE_WINDOW_SECS)
+    let signal_dedupe = signal_platform::SignalDedupeConfig::from_env();
+    if signal_dedupe.enabled {
+        log::info!("🔁 Signal dedupe: same symbol + action within {}s keeps only the highest-confidence signal",
+                  signal_dedupe.window_secs);
+    }
+    marketplace.set_signal_dedupe(signal_dedupe).await;
+    
+    // DRY_RUN_PROVIDERS (comma-separated provider IDs, e.g. "master_analyzer") paper-trades those
+    // providers' signals even when DRY_RUN_MODE=false
+    if let Ok(value) = std::env::var("DRY_RUN_PROVIDERS") {
+        for provider_id in value.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            marketplace.set_provider_dry_run(provider_id, true).await;
+            log::info!("🧪 Provider {} forced to paper trading", provider_id);
+        }
+    }
+    
+    // SIGNAL HISTORY: Publishes, status changes and purchases are buffered and written together every
+    // SIGNAL_PERSIST_SECS (default 5) instead of rewriting the database on each one
+    let signal_persist_secs = std::env::var("SIGNAL_PERSIST_SECS").ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(signal_platform::DEFAULT_SIGNAL_PERSIST_SECS);
+    let marketplace_persist = marketplace.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(signal_persist_secs));
+        loop {
+            interval.tick().await;
+            marketplace_persist.flush_signals().await;
+            marketplace_persist.flush_purchases().await;
+        }
+    });
     
-    // Start automatic signal cleanup task (runs every hour)
-    log::info!("🧹 Starting automatic signal cleanup service...");
+    // Start automatic signal cleanup task (runs every minute)
+    // EXECUTING_SIGNAL_TIMEOUT_SECS (default 300) - signals stuck in Executing longer than this are
+    // force-reverted, unless their swap is still live on-chain
+    let executing_timeout_secs = std::env::var("EXECUTING_SIGNAL_TIMEOUT_SECS").ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(signal_platform::DEFAULT_EXECUTING_TIMEOUT_SECS);
+    log::info!("🧹 Starting automatic signal cleanup service (Executing timeout: {}s)...", executing_timeout_secs);
     let marketplace_cleanup = marketplace.clone();
+    let database_cleanup = database.clone();
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600)); // Every hour
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60)); // Every minute
         loop {
             interval.tick().await;
             marketplace_cleanup.cleanup_expired_signals().await;
+            
+            // RECOVERY: A failed revert must not leave a signal locked in Executing forever
+            let stuck = marketplace_cleanup.stuck_executing_signals(executing_timeout_secs).await;
+            if !stuck.is_empty() {
+                let mut recoverable = Vec::with_capacity(stuck.len());
+                for signal_id in stuck {
+                    let live_execution = database_cleanup.lock().await.get_live_execution_for_signal(&signal_id).cloned();
+                    match live_execution {
+                        Some(execution) => log::debug!("⏳ Signal {} still has swap {} ({:?}) on-chain - not recovering",
+                                                       signal_id, execution.signature, execution.status),
+                        None => recoverable.push(signal_id),
+                    }
+                }
+                marketplace_cleanup.recover_stuck_signals(&recoverable).await;
+            }
             log::debug!("🔄 Signal cleanup cycle completed");
         }
     });
     
     // Initialize Enhanced Marketplace with performance tracking
-    let enhanced_marketplace = Arc::new(enhanced_marketplace::EnhancedMarketplace::new(marketplace.clone()));
+    // TRAILING_STOP_PCT (fraction, e.g. 0.05) lets positions trail their peak instead of exiting at the target
+    let trailing_stop_pct = std::env::var("TRAILING_STOP_PCT").ok().and_then(|v| v.parse::<f64>().ok());
+    if let Some(pct) = trailing_stop_pct {
+        log::info!("📉 Trailing stop enabled: {:.1}% below peak", pct * 100.0);
+    }
+    // TP_LEVELS ("target_multiple:fraction,...", e.g. "1.0:0.5,1.1:0.25") scales out in tranches
+    // at multiples of each signal's target and trails the remainder (TP_LADDER_TRAIL_PCT)
+    let take_profit_levels = match std::env::var("TP_LEVELS") {
+        Ok(value) => enhanced_marketplace::TakeProfitLadderConfig::parse_price_levels(&value).unwrap_or_else(|e| {
+            log::warn!("⚠️ Ignoring invalid TP_LEVELS: {}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    if !take_profit_levels.is_empty() {
+        let level_desc: Vec<String> = take_profit_levels.iter()
+            .map(|(multiple, fraction)| format!("{:.0}% @ {:.2}x target", fraction * 100.0, multiple))
+            .collect();
+        log::info!("🪜 Take-profit levels: {}", level_desc.join(", "));
+    }
+    let close_to = trading_engine::CloseTo::from_env();
+    log::info!("💱 Closed positions swap back to: {:?}", close_to);
+    let mut enhanced = enhanced_marketplace::EnhancedMarketplace::new(marketplace.clone())
+        .with_trailing_stop(trailing_stop_pct)
+        .with_take_profit_levels(take_profit_levels)
+        .with_database(database.clone())
+        .with_exit_swaps(trading_engine.clone(), close_to, dry_run_mode);
+    if let Some(ref callbacks) = signal_callbacks {
+        enhanced = enhanced.with_callbacks(callbacks.clone());
+    }
+    let enhanced_marketplace = Arc::new(enhanced);
     
     // Trading state management (shared across all services)
     // SAFETY: Default to DISABLED - user must explicitly enable
@@ -731,45 +1485,296 @@ async fn main() {
     // Note: Currently not passed to all functions, but kept for future use
     let _dry_run = Arc::new(Mutex::new(dry_run_mode));
     
+    // Loop cadence shared with the API so it can be tuned without a restart
+    let loop_intervals = loop_intervals::LoopIntervals::from_env();
+    
+    // Oracle client for live data - also prices the treasury thresholds in SOL/USD with a USDC base
+    let oracle_client = Arc::new(session_oracle(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true)));
+    
+    // FUNDING GUARD: real trading needs a funded PDA treasury - checked now and then every FUNDING_CHECK_INTERVAL_SECS
+    // Balances are in the base currency - the treasury's USDC with BASE_CURRENCY=usdc, the SOL thresholds converted
+    let base_currency = trading_engine.lock().await.base_currency;
+    let funding_guard = production_safeguards::FundingGuardConfig::from_env(base_currency);
+    let balance_anomaly = production_safeguards::BalanceAnomalyConfig::from_env(base_currency);
+    log::info!("💰 Funding guard: real trading needs at least {:.6} SOL{} in the PDA treasury (re-checked every {}s)",
+               funding_guard.min_balance_sol,
+               if base_currency == dex_aggregator::BaseCurrency::Usdc { " worth of USDC" } else { "" },
+               funding_guard.check_interval_secs);
+    if balance_anomaly.enabled {
+        log::info!("🔎 Balance anomaly detector: unexplained treasury drops over {:.1}% (and {:.6} SOL) disable trading",
+                   balance_anomaly.max_drop_pct, balance_anomaly.tolerance_sol);
+    }
+    if !dry_run_mode {
+        let read_started_at_ms = chrono::Utc::now().timestamp_millis();
+        let balance = solana_client.lock().await.get_trading_balance().await;
+        let sol_price = production_safeguards::threshold_sol_price(base_currency, &oracle_client).await;
+        match &balance {
+            Ok(balance) => log::info!("💰 PDA treasury balance: {:.6} {}", balance, base_currency.symbol()),
+            Err(e) => log::error!("❌ Could not read PDA treasury balance: {}", e),
+        }
+        // BALANCE ANOMALY: The startup reading is the baseline later readings are reconciled against
+        if let (Ok(balance), true) = (&balance, balance_anomaly.enabled) {
+            production_safeguards::reconcile_treasury_balance(&mut *trading_engine.lock().await, *balance, read_started_at_ms, sol_price, balance_anomaly);
+        }
+        production_safeguards::enforce_funding_minimum(
+            &trading_engine, &trading_enabled, false, balance, sol_price, funding_guard
+        ).await;
+    }
+    
+    {
+        let funding_engine = trading_engine.clone();
+        let funding_trading_enabled = trading_enabled.clone();
+        let funding_solana = solana_client.clone();
+        let funding_notifier = notifier.clone();
+        let funding_oracle = oracle_client.clone();
+        tokio::spawn(async move {
+            // Whether the last check ran in real mode - the first real check after dry-run counts as a startup check
+            let mut real_mode = !dry_run_mode;
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(funding_guard.check_interval_secs)).await;
+                // DRY_RUN_MODE is read when a trade executes, so each check reads it too
+                if solana_integration::SolanaClient::dry_run_mode() {
+                    production_safeguards::enforce_funding_minimum(
+                        &funding_engine, &funding_trading_enabled, true, Ok(0.0), None, funding_guard
+                    ).await;
+                    real_mode = false;
+                    continue;
+                }
+                let first_real_check = !real_mode;
+                real_mode = true;
+                if first_real_check {
+                    // Readings from before the dry-run stretch don't explain today's balance
+                    funding_engine.lock().await.balance_reconciliation = None;
+                }
+                // Read without the engine lock - flows booked while the read is in flight are excused, not expected
+                let read_started_at_ms = chrono::Utc::now().timestamp_millis();
+                let balance = funding_solana.lock().await.get_trading_balance().await;
+                let sol_price = production_safeguards::threshold_sol_price(base_currency, &funding_oracle).await;
+                let anomaly = match &balance {
+                    Ok(balance) if balance_anomaly.enabled => production_safeguards::reconcile_treasury_balance(
+                        &mut *funding_engine.lock().await, *balance, read_started_at_ms, sol_price, balance_anomaly
+                    ),
+                    _ => None,
+                };
+                if let Some(reconciliation) = anomaly {
+                    *funding_trading_enabled.lock().await = false;
+                    notifications::dispatch(&funding_notifier, notifications::Notification::new(
+                        notifications::NotificationEvent::CircuitBreakerTripped,
+                        "🚨 Treasury balance anomaly - trading disabled",
+                        &format!("{} - investigate before re-enabling via POST /trading-toggle", reconciliation.message()),
+                    ));
+                }
+                // A failed read only pauses on the first real check - a transient RPC error shouldn't stop a funded bot
+                if !first_real_check {
+                    if let Err(e) = &balance {
+                        log::warn!("⚠️ Funding check skipped - could not read PDA treasury balance: {}", e);
+                        continue;
+                    }
+                    if funding_guard.min_balance(sol_price).is_none() {
+                        log::warn!("⚠️ Funding check skipped - no SOL price to convert the minimum into {}", base_currency.symbol());
+                        continue;
+                    }
+                }
+                match production_safeguards::enforce_funding_minimum(
+                    &funding_engine, &funding_trading_enabled, false, balance, sol_price, funding_guard
+                ).await {
+                    Some(production_safeguards::FundingAction::Paused(lockout)) => {
+                        notifications::dispatch(&funding_notifier, notifications::Notification::new(
+                            notifications::NotificationEvent::FundingGuard,
+                            "💸 Treasury drained - trading paused",
+                            &lockout.message(),
+                        ));
+                    }
+                    Some(production_safeguards::FundingAction::Funded(balance)) => {
+                        notifications::dispatch(&funding_notifier, notifications::Notification::new(
+                            notifications::NotificationEvent::FundingGuard,
+                            "✅ Treasury funded",
+                            &format!("PDA treasury holds {:.6} {} - re-enable trading via POST /trading-toggle",
+                                     balance, base_currency.symbol()),
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        });
+    }
+    
+    // PORTFOLIO RECONCILIATION: correct tracked positions to the wallets' on-chain token balances
+    let portfolio_reconcile = production_safeguards::PortfolioReconciliationConfig::from_env();
+    if !dry_run_mode && portfolio_reconcile.enabled {
+        log::info!("🧮 Portfolio reconciliation: positions corrected to on-chain balances every {}s ({:.2}% tolerance)",
+                   portfolio_reconcile.interval_secs, portfolio_reconcile.tolerance_pct);
+        let reconcile_engine = trading_engine.clone();
+        let reconcile_enhanced = enhanced_marketplace.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(portfolio_reconcile.interval_secs)).await;
+                // Read every wallet with the engine unlocked - positions that trade meanwhile are skipped
+                let read_at = chrono::Utc::now().timestamp();
+                let clients: Vec<(String, Arc<Mutex<solana_integration::SolanaClient>>)> = {
+                    let engine_lock = reconcile_engine.lock().await;
+                    engine_lock.solana_client.iter()
+                        .map(|client| (wallet::DEFAULT_WALLET_NAME.to_string(), client.clone()))
+                        .chain(engine_lock.strategy_wallets.iter().map(|(name, client)| (name.clone(), client.clone())))
+                        .collect()
+                };
+                let mut onchain = std::collections::HashMap::new();
+                for (wallet_name, client) in clients {
+                    let source = client.lock().await.token_balance_source();
+                    let balances = match source {
+                        Ok((rpc_url, owner)) => solana_integration::SolanaClient::token_balances(rpc_url, &owner).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = &balances {
+                        log::warn!("⚠️ Portfolio reconciliation couldn't read the {} wallet: {}", wallet_name, e);
+                    }
+                    onchain.insert(wallet_name, balances);
+                }
+                let result = {
+                    let mut engine_lock = reconcile_engine.lock().await;
+                    production_safeguards::reconcile_portfolio(&mut engine_lock, &onchain, read_at, portfolio_reconcile).await
+                };
+                // Tracked signals on a token the wallets no longer hold are closed (no exit swap) - unlocked,
+                // since the exit path takes the engine lock
+                for (symbol, price) in &result.emptied {
+                    reconcile_enhanced.close_symbol_positions(symbol, *price, enhanced_marketplace::ExitReason::Reconciled).await;
+                }
+                if !result.discrepancies.is_empty() || !result.skipped.is_empty() {
+                    log::warn!("🧮 Portfolio reconciliation corrected {} of {} positions ({} skipped)",
+                               result.discrepancies.len(), result.checked.len(), result.skipped.len());
+                }
+            }
+        });
+    }
+    
     // Start auto-execution service for high-confidence marketplace signals
     log::info!("🤖 Starting Auto-Execution Service for Marketplace Signals...");
     let auto_exec_engine = trading_engine.clone();
     let auto_exec_marketplace = marketplace.clone();
     let auto_exec_enhanced = enhanced_marketplace.clone();
-    let auto_exec_oracle = Arc::new(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true));
+    // ORACLE CONSENSUS: One aggregator for the executor and the engine's manual / DCA / limit-order buys
+    let mut auto_exec_oracle = switchboard_oracle::OracleAggregator::new(
+        Arc::new(session_oracle(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true))));
+    if !session_mode.is_replay() {
+        auto_exec_oracle = auto_exec_oracle.with_dex_screener(Arc::new(dex_screener::DexScreenerClient::new()));
+    }
+    let auto_exec_oracle = Arc::new(auto_exec_oracle);
+    {
+        let mut engine = trading_engine.lock().await;
+        engine.oracle_aggregator = Some(auto_exec_oracle.clone());
+        engine.max_oracle_dispersion_pct = switchboard_oracle::ConsensusPrice::max_dispersion_from_env();
+    }
     let auto_exec_trading_enabled = trading_enabled.clone();
     let auto_exec_dry_run = Arc::new(Mutex::new(dry_run_mode)); // PASS: dry-run mode for paper trading
     let auto_exec_rl_coordinator = rl_coordinator.clone(); // PASS: RL coordinator for learning
-    tokio::spawn(async move {
+    // AI REVIEW: Optional DeepSeek approve/reject on buys (AI_SIGNAL_REVIEW=true, needs DEEPSEEK_API_KEY)
+    let review_config = signal_review::SignalReviewConfig::from_env();
+    let auto_exec_reviewer = match (&deepseek_client, review_config.enabled) {
+        (Some(client), true) => {
+            log::info!("🧠 AI signal review enabled ({}, verdicts cached {}s)",
+                       if review_config.fail_open { "fail-open" } else { "fail-closed" }, review_config.cache_secs);
+            Some(Arc::new(signal_review::SignalReviewer::new(client.clone(), review_config)))
+        }
+        (None, true) => {
+            log::warn!("⚠️ AI_SIGNAL_REVIEW is set but DEEPSEEK_API_KEY is not - signals execute unreviewed");
+            None
+        }
+        _ => None,
+    };
+    // Drawdown guard: cooldown past max drawdown, kill switch at HARD_DRAWDOWN_LIMIT_PCT
+    let drawdown_guard = production_safeguards::DrawdownGuardConfig::from_env();
+    log::info!("📉 Drawdown guard: auto-disable past max drawdown ({}s cooldown), flatten at {:.1}%",
+               drawdown_guard.cooldown_secs, drawdown_guard.hard_limit_pct);
+    let auto_exec_intervals = loop_intervals.clone(); // PASS: Live-adjustable check interval
+    let auto_exec_notifier = notifier.clone();
+    let auto_exec_heartbeat = watchdog::Heartbeat::new();
+    let auto_exec_task_heartbeat = auto_exec_heartbeat.clone();
+    let auto_exec_shutdown = shutdown.clone();
+    let auto_exec_clock = replay_clock.clone();
+    watchdog.spawn_supervised("auto_execute", auto_exec_heartbeat, None, move || {
         auto_execute_marketplace_signals(
-            auto_exec_engine,
-            auto_exec_marketplace,
-            auto_exec_enhanced,
-            auto_exec_oracle,
-            auto_exec_trading_enabled,
-            auto_exec_dry_run, // PASS: dry-run mode
-            auto_exec_rl_coordinator, // PASS: RL coordinator
-        ).await;
-    });
+            auto_exec_engine.clone(),
+            auto_exec_marketplace.clone(),
+            auto_exec_enhanced.clone(),
+            auto_exec_oracle.clone(),
+            auto_exec_trading_enabled.clone(),
+            auto_exec_dry_run.clone(), // PASS: dry-run mode
+            auto_exec_rl_coordinator.clone(), // PASS: RL coordinator
+            auto_exec_reviewer.clone(), // PASS: AI pre-execution review
+            drawdown_guard, // PASS: Auto-disable / kill-switch limits
+            auto_exec_intervals.clone(), // PASS: Check interval
+            auto_exec_notifier.clone(), // PASS: Trade and kill-switch alerts
+            auto_exec_task_heartbeat.clone(), // PASS: Watchdog heartbeat
+            auto_exec_shutdown.clone(), // PASS: Stop picking up signals on shutdown
+            auto_exec_clock.clone(), // PASS: Replay clock ticks
+        )
+    }).await;
+    
+    // Start limit order poller - fills resting orders when live prices cross their limit
+    log::info!("📝 Starting Limit Order Poller...");
+    let limit_order_engine = trading_engine.clone();
+    let limit_order_oracle = Arc::new(session_oracle(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true)));
+    let limit_order_trading_enabled = trading_enabled.clone();
+    let limit_order_dry_run = Arc::new(Mutex::new(dry_run_mode));
+    let limit_order_heartbeat = watchdog::Heartbeat::new();
+    let limit_order_task_heartbeat = limit_order_heartbeat.clone();
+    watchdog.spawn_supervised("limit_order_poller", limit_order_heartbeat, None, move || {
+        trading_engine::run_limit_order_poller(
+            limit_order_engine.clone(),
+            limit_order_oracle.clone(),
+            limit_order_trading_enabled.clone(),
+            limit_order_dry_run.clone(),
+            limit_order_task_heartbeat.clone(),
+        )
+    }).await;
+    
+    // Start execution confirmation poller - follows submitted swaps until finalized or failed
+    log::info!("🧾 Starting Execution Confirmation Poller...");
+    let execution_poller_engine = trading_engine.clone();
+    let execution_poller_marketplace = enhanced_marketplace.clone();
+    let execution_poller_rl = rl_coordinator.clone();
+    let execution_poller_heartbeat = watchdog::Heartbeat::new();
+    let execution_poller_task_heartbeat = execution_poller_heartbeat.clone();
+    watchdog.spawn_supervised("execution_confirmation_poller", execution_poller_heartbeat, None, move || {
+        trading_engine::run_execution_confirmation_poller(
+            execution_poller_engine.clone(),
+            Some(execution_poller_marketplace.clone()),
+            Some(execution_poller_rl.clone()),
+            execution_poller_task_heartbeat.clone(),
+        )
+    }).await;
     
     // Start real-time signal performance tracking
     log::info!("📊 Starting Real-Time Signal Performance Tracker...");
+    let perf_tracker_engine = trading_engine.clone(); // PASS: Engine marks for portfolio snapshots
+    let perf_tracker_database = database.clone(); // PASS: Equity curve persistence
     let perf_tracker_marketplace = marketplace.clone();
     let perf_tracker_enhanced = enhanced_marketplace.clone();
-    let perf_tracker_oracle = Arc::new(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true));
+    let mut perf_tracker_oracle = session_oracle(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true));
+    if !session_mode.is_replay() {
+        perf_tracker_oracle = perf_tracker_oracle.with_pool_fallback(Arc::new(dex_screener::DexScreenerClient::new()));
+    }
+    let perf_tracker_oracle = Arc::new(perf_tracker_oracle);
     let perf_tracker_rl_coordinator = rl_coordinator.clone(); // PASS: RL coordinator for learning from outcomes
-    tokio::spawn(async move {
+    let perf_tracker_intervals = loop_intervals.clone(); // PASS: Live-adjustable update interval
+    let perf_tracker_notifier = notifier.clone();
+    let perf_tracker_heartbeat = watchdog::Heartbeat::new();
+    let perf_tracker_task_heartbeat = perf_tracker_heartbeat.clone();
+    let perf_tracker_clock = replay_clock.clone();
+    watchdog.spawn_supervised("performance_tracker", perf_tracker_heartbeat, None, move || {
         track_signal_performance(
-            perf_tracker_marketplace,
-            perf_tracker_enhanced,
-            perf_tracker_oracle,
-            perf_tracker_rl_coordinator, // PASS: RL coordinator
-        ).await;
-    });
-    
-    // Initialize Oracle Client for live data feed
-    log::info!("📊 Initializing Oracle Client for Live Data Feed...");
-    let oracle_client = Arc::new(switchboard_oracle::SwitchboardClient::new(rpc_url.clone(), true));
+            perf_tracker_engine.clone(),
+            perf_tracker_database.clone(),
+            perf_tracker_marketplace.clone(),
+            perf_tracker_enhanced.clone(),
+            perf_tracker_oracle.clone(),
+            perf_tracker_rl_coordinator.clone(), // PASS: RL coordinator
+            perf_tracker_intervals.clone(), // PASS: Update interval
+            perf_tracker_notifier.clone(), // PASS: Target / stop-loss alerts
+            perf_tracker_task_heartbeat.clone(), // PASS: Watchdog heartbeat
+            perf_tracker_clock.clone(), // PASS: Replay clock ticks
+        )
+    }).await;
     
     // Initialize and start 24/7 Live Data Feed Service
     log::info!("📡 Initializing 24/7 Live Data Feed Service...");
@@ -786,14 +1791,38 @@ async fn main() {
         engine_lock.jupiter_client.clone()
     }; // Lock released here
     
-    let live_data_feed = Arc::new(live_data_feed::LiveDataFeed::new(
+    let mut live_data_feed = live_data_feed::LiveDataFeed::new(
         oracle_client.clone(),
         Some(ws_broadcaster.clone()),
         Some(trading_engine.clone()), // PASS: Trading engine to update market_state with REAL prices
         jupiter_client_for_feed, // PASS: Jupiter client for volume data
         live_feed_symbols.clone(),
-    ));
-    live_data_feed.start().await;
+    ).with_notifier(notifier.clone()); // PASS: Alert when a Critical symbol goes stale
+    if let Some(ref recorder) = session_recorder {
+        live_data_feed = live_data_feed.with_recorder(recorder.clone()); // PASS: Record every update
+    }
+    let live_data_feed = Arc::new(live_data_feed);
+    if let (historical_data::SessionMode::Replay { path, speed }, Some(prices)) = (&session_mode, &replay_prices) {
+        // REPLAY: The recording stands in for the network loop (and the watchdog that restarts it)
+        match historical_data::load_session(path) {
+            Ok(events) => {
+                let replay_feed = live_data_feed.clone();
+                let replay_marketplace = marketplace.clone();
+                let (speed, prices, clock) = (*speed, prices.clone(), replay_clock.clone());
+                tokio::spawn(async move {
+                    replay_feed.replay(events, speed, replay_marketplace, prices, clock).await;
+                });
+            }
+            Err(e) => log::error!("❌ Replay aborted: {}", e),
+        }
+    } else {
+        live_data_feed.start().await;
+        let watchdog_feed = live_data_feed.clone();
+        watchdog.register("live_data_feed", live_data_feed.heartbeat(), None, Arc::new(move || {
+            let feed = watchdog_feed.clone();
+            Box::pin(async move { feed.restart().await })
+        })).await;
+    }
     log::info!("✅ 24/7 Live Data Feed Service started - monitoring {} symbols", live_feed_symbols.len());
     log::info!("   Symbols: {:?}", live_feed_symbols);
     log::info!("   Updates every 5 seconds via WebSocket");
@@ -811,15 +1840,46 @@ async fn main() {
     log::info!("   3. Signals with ≥75% confidence auto-execute autonomously");
     log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
+    // Runtime on/off switch and check interval per provider (GET /providers, POST /providers/{id}/toggle|interval)
+    let provider_registry = provider_registry::ProviderRegistry::new();
+    
     let providers = specialized_providers::initialize_all_providers(
         marketplace.clone(),
         rpc_url.clone(),
     ).await;
     
+    // SANDBOX_PROVIDERS (comma-separated provider IDs) paper-trade and stay off the marketplace listings
+    // until promoted via POST /providers/{id}/promote (POST /providers/{id}/sandbox adds one at runtime)
+    if let Ok(value) = std::env::var("SANDBOX_PROVIDERS") {
+        for provider_id in value.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            match marketplace.set_provider_sandboxed(provider_id, true).await {
+                Ok(()) => log::info!("🧪 Provider {} sandboxed - its signals only paper-trade", provider_id),
+                Err(e) => log::warn!("⚠️ Can't sandbox {}: {}", provider_id, e),
+            }
+        }
+    }
+    
+    // MEMECOIN FILTER: Liquidity / age / holder gates, shared with /config/memecoin-filter
+    let memecoin_filter = specialized_providers::MemecoinFilter::new(specialized_providers::MemecoinFilterConfig::from_env());
+    log::info!("🚧 Memecoin filter: {:?}", memecoin_filter.get().await);
+    let consensus_config = specialized_providers::ConsensusConfig::from_env();
+    log::info!("🤝 Consensus: {:?}", consensus_config);
+    // MARKET REGIME: Master Analyzer classifies, gated provider types stand down on buys
+    let market_regime = market_regime::MarketRegimeState::from_env();
+    log::info!("🌡️ Regime gating: {:?}", market_regime.gating().await);
+    
     // Connect each provider to RL coordinator for centralized learning
     let mut rl_connected_providers = Vec::new();
     for provider in providers {
-        let enhanced_provider = provider.with_rl_coordinator(rl_coordinator.clone());
+        provider_registry.register(&provider.provider_id, &provider.provider_name, provider.check_interval_secs()).await;
+        let enhanced_provider = provider
+            .with_rl_coordinator(rl_coordinator.clone())
+            .with_risk_manager(risk_manager.clone()) // ADD: Slippage-aware Jupiter sizing
+            .with_registry(provider_registry.clone()) // ADD: Runtime enable/disable
+            .with_token_filter(token_filter.clone()) // ADD: Don't publish filtered mints
+            .with_memecoin_filter(memecoin_filter.clone()) // ADD: Live-editable memecoin gates
+            .with_consensus_config(consensus_config) // ADD: Reputation-weighted consensus
+            .with_market_regime(market_regime.clone()); // ADD: Regime-aware buy gating
         rl_connected_providers.push(enhanced_provider);
     }
     
@@ -829,14 +1889,28 @@ async fn main() {
     
     // Start each specialized provider in its own task
     // Each provider runs independently and handles its own errors
+    // REPLAY: Recorded signals replace the providers' live ones
+    if session_mode.is_replay() {
+        log::info!("⏪ Replay mode - specialized providers stay idle");
+        rl_connected_providers.clear();
+    }
     for provider in rl_connected_providers {
-        tokio::spawn(async move {
-            // Run provider in its own task
-            // All errors are converted to String internally, so this is Send-safe
-            provider.run().await;
-        });
+        let heartbeat = watchdog::Heartbeat::new();
+        let task_name = format!("provider:{}", provider.provider_id);
+        let provider = Arc::new(provider.with_heartbeat(heartbeat.clone()));
+        watchdog.spawn_supervised(&task_name, heartbeat, None, move || {
+            let provider = provider.clone();
+            async move {
+                // Run provider in its own task
+                // All errors are converted to String internally, so this is Send-safe
+                provider.run().await;
+            }
+        }).await;
     }
     
+    // All background loops registered - start supervising them
+    watchdog.clone().start();
+    
     log::info!("✅ All providers started - generating and publishing signals to marketplace");
     log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
@@ -869,15 +1943,106 @@ async fn main() {
     let api_circuit_breaker = circuit_breaker.clone(); // PASS: Circuit breaker for API protection
     let api_live_data_feed = live_data_feed.clone(); // PASS: Live data feed for management
     let api_enhanced_marketplace = enhanced_marketplace.clone(); // PASS: Enhanced marketplace for advanced features
-    let legacy_api = tokio::spawn(async move {
-        api::start_server(api_engine, api_risk, api_solana, api_trading_enabled, Some(api_rl_coordinator), Some(api_circuit_breaker), Some(api_live_data_feed), Some(api_enhanced_marketplace)).await;
-    });
+    let api_marketplace = marketplace.clone(); // PASS: Shared marketplace for skipped-signal log
+    let api_notifier = notifier.clone(); // PASS: Notifier for delivery tests
+    let api_watchdog = watchdog.clone(); // PASS: Watchdog for per-task heartbeats
+    let api_database = database.clone(); // PASS: Database for portfolio history
+    let api_loop_intervals = loop_intervals.clone(); // PASS: Loop cadence for /config/intervals
+    let api_provider_registry = provider_registry.clone(); // PASS: Provider toggles for /providers
+    let api_shutdown = shutdown.clone(); // PASS: Drain the server on Ctrl-C
+    let api_token_filter = token_filter.clone(); // PASS: Mint filter for /config/token-filter
+    let api_pumpfun_stream = pumpfun_client_shared.clone(); // PASS: pump.fun WebSocket status for /health
+    let api_memecoin_filter = memecoin_filter.clone(); // PASS: Memecoin gates for /config/memecoin-filter
+    let api_twitter_sentiment = twitter_sentiment_client.clone(); // PASS: Sentiment service probed by /health
+    let api_market_regime = market_regime.clone(); // PASS: Current regime and gating for /market/regime
+    let api_signal_callbacks = signal_callbacks.clone(); // PASS: Callback queue for /callbacks/*
+    // Supervised like the loops - a server that exits or panics stops beating and is re-spawned
+    let legacy_api_heartbeat = watchdog::Heartbeat::new();
+    let legacy_api_task_heartbeat = legacy_api_heartbeat.clone();
+    let legacy_api = watchdog.spawn_supervised("legacy_api", legacy_api_heartbeat, Some(watchdog::SERVER_TIMEOUT_SECS), move || {
+        let server = api::start_server(api_engine.clone(), api_risk.clone(), api_solana.clone(), api_trading_enabled.clone(), Some(api_rl_coordinator.clone()), Some(api_circuit_breaker.clone()), Some(api_live_data_feed.clone()), Some(api_enhanced_marketplace.clone()), Some(api_marketplace.clone()), api_notifier.clone(), Some(api_watchdog.clone()), Some(api_database.clone()), Some(api_loop_intervals.clone()), Some(api_provider_registry.clone()), Some(api_shutdown.clone()), Some(api_token_filter.clone()), Some(api_pumpfun_stream.clone()), Some(api_memecoin_filter.clone()), Some(api_twitter_sentiment.clone()), Some(api_market_regime.clone()), api_signal_callbacks.clone());
+        let shutdown = api_shutdown.clone();
+        watchdog::beat_while_running(legacy_api_task_heartbeat.clone(), async move {
+            server.await;
+            if !shutdown.is_triggered() {
+                log::error!("❌ Legacy API server on :8080 exited - the watchdog will restart it");
+            }
+        })
+    }).await;
     
     // Start new AI-orchestrated API v2 in background
-    let ai_api = tokio::spawn(async move {
-        api_v2::start_server(api_orchestrator).await;
+    let ai_api_shutdown = shutdown.clone();
+    let ai_api_heartbeat = watchdog::Heartbeat::new();
+    let ai_api_task_heartbeat = ai_api_heartbeat.clone();
+    let ai_api = watchdog.spawn_supervised("ai_api", ai_api_heartbeat, Some(watchdog::SERVER_TIMEOUT_SECS), move || {
+        let server = api_v2::start_server(api_orchestrator.clone(), ai_api_shutdown.clone());
+        let shutdown = ai_api_shutdown.clone();
+        watchdog::beat_while_running(ai_api_task_heartbeat.clone(), async move {
+            server.await;
+            if !shutdown.is_triggered() {
+                log::error!("❌ AI API v2 server on :8081 exited - the watchdog will restart it");
+            }
+        })
+    }).await;
+    
+    // Servers run until Ctrl-C
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        log::error!("❌ Could not listen for Ctrl-C: {} - running without graceful shutdown", e);
+        // The watchdog keeps the servers up
+        std::future::pending::<()>().await;
+    }
+    
+    // SHUTDOWN: Stop new work, let the in-flight swap finish, persist state, then drain the servers
+    let shutdown_timeout = shutdown::Shutdown::timeout_from_env();
+    log::warn!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    log::warn!("🛑 Ctrl-C received - shutting down gracefully (timeout {}s per step, Ctrl-C again to force)", shutdown_timeout.as_secs());
+    shutdown.trigger();
+    // The servers drain and exit from here on - not a stall for the watchdog to restart
+    watchdog.unregister("legacy_api").await;
+    watchdog.unregister("ai_api").await;
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            log::error!("⚠️ Second Ctrl-C - exiting immediately");
+            std::process::exit(130);
+        }
     });
+    log::info!("   1/4 Stopped accepting new trades and signals");
+    
+    // Every execution holds the engine lock, so acquiring it means nothing is mid-swap
+    match tokio::time::timeout(shutdown_timeout, trading_engine.lock()).await {
+        Ok(_engine) => log::info!("   2/4 No swap in flight"),
+        Err(_) => log::error!("   2/4 ⚠️ A swap was still executing after {}s - shutting down anyway", shutdown_timeout.as_secs()),
+    }
     
-    // Wait for both servers (they run forever)
-    let _ = tokio::try_join!(legacy_api, ai_api);
+    match rl_coordinator.lock().await.save_state(&rl_state_path).await {
+        Ok(count) => log::info!("   3/4 💾 Saved RL state for {} agents to {}", count, rl_state_path.display()),
+        Err(e) => log::error!("   3/4 ❌ Failed to save RL state: {}", e),
+    }
+    let flushed_signals = marketplace.flush_signals().await;
+    let flushed_purchases = marketplace.flush_purchases().await;
+    if flushed_signals + flushed_purchases > 0 {
+        log::info!("   3/4 💾 Wrote {} buffered signal change(s) and {} purchase(s)", flushed_signals, flushed_purchases);
+    }
+    {
+        let db = database.lock().await;
+        let pending = db.get_pending_executions().len();
+        match db.flush() {
+            Ok(()) => log::info!("   3/4 💾 Database flushed ({} executions still awaiting confirmation - polling resumes on restart)", pending),
+            Err(e) => log::error!("   3/4 ❌ Failed to flush database: {}", e),
+        }
+    }
+    
+    let drain_servers = async {
+        for server in [legacy_api, ai_api] {
+            if let Some(handle) = server.lock().await.take() {
+                let _ = handle.await;
+            }
+        }
+    };
+    match tokio::time::timeout(shutdown_timeout, drain_servers).await {
+        Ok(_) => log::info!("   4/4 🌐 API servers stopped"),
+        Err(_) => log::error!("   4/4 ⚠️ API servers still draining connections after {}s - exiting anyway", shutdown_timeout.as_secs()),
+    }
+    log::warn!("👋 Shutdown complete");
+    log::warn!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 }
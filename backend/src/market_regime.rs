@@ -0,0 +1,270 @@
+//! Market Regime Gating
+//! The Master Analyzer classifies the market as BULLISH / BEARISH / NEUTRAL from how benchmark prices
+//! (SOL, BTC, ETH by default) have moved across its recent oracle scans and publishes it here; providers read it before publishing and stand down on buys
+//! in the regimes configured for their type (by default memecoin providers sit out BEARISH markets)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// A regime older than this no longer gates anything - the Master Analyzer may be paused or failing
+pub const DEFAULT_REGIME_MAX_AGE_SECS: i64 = 900;
+
+/// Symbols whose price moves stand for the market when REGIME_BENCHMARKS is unset
+pub const DEFAULT_REGIME_BENCHMARKS: &[&str] = &["SOL/USD", "BTC/USD", "ETH/USD"];
+
+/// Average benchmark move (%) above which the market is BULLISH, and below the negative of which BEARISH
+pub const DEFAULT_REGIME_THRESHOLD_PCT: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MarketRegime {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+impl MarketRegime {
+    /// Classify from the average % move of the benchmarks - within `threshold_pct` either way is NEUTRAL
+    pub fn from_market_change(change_pct: f64, threshold_pct: f64) -> Self {
+        if change_pct > threshold_pct {
+            MarketRegime::Bullish
+        } else if change_pct < -threshold_pct {
+            MarketRegime::Bearish
+        } else {
+            MarketRegime::Neutral
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketRegime::Bullish => "BULLISH",
+            MarketRegime::Bearish => "BEARISH",
+            MarketRegime::Neutral => "NEUTRAL",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "BULLISH" => Some(MarketRegime::Bullish),
+            "BEARISH" => Some(MarketRegime::Bearish),
+            "NEUTRAL" => Some(MarketRegime::Neutral),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MarketRegime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Average of the benchmarks' % moves - None when no benchmark has been measured yet
+pub fn average_change_pct(changes: &[(String, f64)]) -> Option<f64> {
+    if changes.is_empty() {
+        return None;
+    }
+    Some(changes.iter().map(|(_, change)| change).sum::<f64>() / changes.len() as f64)
+}
+
+/// Latest classification, for GET /market/regime
+#[derive(Debug, Clone, Serialize)]
+pub struct RegimeReading {
+    pub regime: MarketRegime,
+    /// Average benchmark move (%) the regime was classified from
+    pub market_change_pct: f64,
+    /// Each measured benchmark and its move (%)
+    pub benchmark_changes: Vec<(String, f64)>,
+    pub updated_at: i64,
+}
+
+/// Provider type key -> regimes in which that type publishes no buy signals
+pub type RegimeGating = HashMap<String, Vec<MarketRegime>>;
+
+/// Memecoin providers stand down in BEARISH markets; everything else keeps publishing
+pub fn default_gating() -> RegimeGating {
+    HashMap::from([
+        ("memecoin_monitor".to_string(), vec![MarketRegime::Bearish]),
+        ("jupiter_memecoin_trader".to_string(), vec![MarketRegime::Bearish]),
+    ])
+}
+
+/// Parse REGIME_GATING="memecoin_monitor:bearish,jupiter_memecoin_trader:bearish|neutral"
+/// A provider type listed with "none" never stands down
+pub fn parse_gating(value: &str) -> Result<RegimeGating, String> {
+    let mut gating = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (provider_type, regimes) = entry.split_once(':')
+            .ok_or_else(|| format!("Expected provider_type:regime, got {:?}", entry))?;
+        let regimes = if regimes.trim().eq_ignore_ascii_case("none") {
+            Vec::new()
+        } else {
+            regimes.split('|')
+                .map(|regime| MarketRegime::parse(regime)
+                    .ok_or_else(|| format!("Unknown regime {:?} for {}", regime.trim(), provider_type.trim())))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        gating.insert(provider_type.trim().to_string(), regimes);
+    }
+    Ok(gating)
+}
+
+/// Shared regime handle - written by the Master Analyzer, read by every provider and the API
+#[derive(Debug, Clone)]
+pub struct MarketRegimeState {
+    reading: Arc<Mutex<Option<RegimeReading>>>,
+    gating: Arc<Mutex<RegimeGating>>,
+    max_age_secs: i64,
+    benchmarks: Vec<String>,
+    threshold_pct: f64,
+}
+
+impl MarketRegimeState {
+    pub fn new(gating: RegimeGating, max_age_secs: i64) -> Self {
+        Self {
+            reading: Arc::new(Mutex::new(None)),
+            gating: Arc::new(Mutex::new(gating)),
+            max_age_secs,
+            benchmarks: DEFAULT_REGIME_BENCHMARKS.iter().map(|symbol| symbol.to_string()).collect(),
+            threshold_pct: DEFAULT_REGIME_THRESHOLD_PCT,
+        }
+    }
+
+    /// REGIME_GATING (default: memecoin providers stand down when BEARISH), REGIME_MAX_AGE_SECS (default 900),
+    /// REGIME_BENCHMARKS (default SOL/USD,BTC/USD,ETH/USD), REGIME_THRESHOLD_PCT (default 2.0)
+    /// Provider types REGIME_GATING lists replace their defaults; the rest keep them
+    pub fn from_env() -> Self {
+        let mut gating = default_gating();
+        if let Ok(value) = std::env::var("REGIME_GATING") {
+            match parse_gating(&value) {
+                Ok(overrides) => gating.extend(overrides),
+                Err(e) => log::warn!("⚠️ Invalid REGIME_GATING ({}) - using the defaults", e),
+            }
+        }
+        let max_age_secs = std::env::var("REGIME_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|secs: &i64| *secs > 0)
+            .unwrap_or(DEFAULT_REGIME_MAX_AGE_SECS);
+        let mut state = Self::new(gating, max_age_secs);
+        if let Ok(value) = std::env::var("REGIME_BENCHMARKS") {
+            let benchmarks: Vec<String> = value.split(',')
+                .map(str::trim)
+                .filter(|symbol| !symbol.is_empty())
+                .map(str::to_string)
+                .collect();
+            if !benchmarks.is_empty() {
+                state.benchmarks = benchmarks;
+            }
+        }
+        if let Some(threshold_pct) = std::env::var("REGIME_THRESHOLD_PCT").ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|pct| *pct >= 0.0)
+        {
+            state.threshold_pct = threshold_pct;
+        }
+        state
+    }
+
+    pub fn max_age_secs(&self) -> i64 {
+        self.max_age_secs
+    }
+
+    /// Symbols whose moves the Master Analyzer classifies the market from
+    pub fn benchmarks(&self) -> &[String] {
+        &self.benchmarks
+    }
+
+    pub fn threshold_pct(&self) -> f64 {
+        self.threshold_pct
+    }
+
+    /// Record a fresh classification from each benchmark's % move - logs when the regime changes
+    /// None (and the previous reading left to age out) when no benchmark could be measured
+    pub async fn update(&self, benchmark_changes: Vec<(String, f64)>) -> Option<MarketRegime> {
+        let market_change_pct = average_change_pct(&benchmark_changes)?;
+        let regime = MarketRegime::from_market_change(market_change_pct, self.threshold_pct);
+        let mut reading = self.reading.lock().await;
+        if reading.as_ref().is_some_and(|last| last.regime != regime) {
+            log::info!("🌡️ Market regime changed to {} (benchmarks moved {:+.2}% on average)", regime, market_change_pct);
+        }
+        *reading = Some(RegimeReading {
+            regime,
+            market_change_pct,
+            benchmark_changes,
+            updated_at: chrono::Utc::now().timestamp(),
+        });
+        Some(regime)
+    }
+
+    /// Latest reading, stale or not
+    pub async fn reading(&self) -> Option<RegimeReading> {
+        self.reading.lock().await.clone()
+    }
+
+    /// Regime currently in force - None when nothing has been classified within `max_age_secs`
+    pub async fn current(&self, now: i64) -> Option<MarketRegime> {
+        self.reading.lock().await.as_ref()
+            .filter(|reading| now - reading.updated_at <= self.max_age_secs)
+            .map(|reading| reading.regime)
+    }
+
+    pub async fn gating(&self) -> RegimeGating {
+        self.gating.lock().await.clone()
+    }
+
+    /// Replace the regimes one provider type stands down in (empty = never)
+    pub async fn set_gating(&self, provider_type: &str, regimes: Vec<MarketRegime>) {
+        self.gating.lock().await.insert(provider_type.to_string(), regimes);
+    }
+
+    /// The regime `provider_type` has to stand down in right now, if any
+    pub async fn stand_down(&self, provider_type: &str, now: i64) -> Option<MarketRegime> {
+        let regime = self.current(now).await?;
+        self.gating.lock().await.get(provider_type)
+            .is_some_and(|regimes| regimes.contains(&regime))
+            .then_some(regime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_regime_gating_per_provider_type() {
+        assert_eq!(MarketRegime::from_market_change(0.5, 2.0), MarketRegime::Neutral);
+        assert_eq!(MarketRegime::from_market_change(3.0, 2.0), MarketRegime::Bullish);
+        assert_eq!(MarketRegime::from_market_change(-3.0, 2.0), MarketRegime::Bearish);
+        assert_eq!(MarketRegime::from_market_change(-2.0, 2.0), MarketRegime::Neutral);
+
+        let gating = parse_gating("memecoin_monitor:bearish|neutral, oracle_monitor:none").unwrap();
+        assert_eq!(gating["memecoin_monitor"], vec![MarketRegime::Bearish, MarketRegime::Neutral]);
+        assert!(gating["oracle_monitor"].is_empty());
+        assert!(parse_gating("memecoin_monitor:crashing").is_err());
+
+        let state = MarketRegimeState::new(default_gating(), 900);
+        let now = chrono::Utc::now().timestamp();
+        // Nothing classified yet - nobody stands down
+        assert!(state.stand_down("memecoin_monitor", now).await.is_none());
+        // Nor once nothing could be priced
+        assert!(state.update(Vec::new()).await.is_none());
+        assert!(state.reading().await.is_none());
+
+        // SOL -6% and BTC -1% average -3.5% - a market-wide dump
+        let regime = state.update(vec![("SOL/USD".to_string(), -6.0), ("BTC/USD".to_string(), -1.0)]).await;
+        assert_eq!(regime, Some(MarketRegime::Bearish));
+        assert_eq!(state.reading().await.unwrap().market_change_pct, -3.5);
+        assert_eq!(state.stand_down("memecoin_monitor", now).await, Some(MarketRegime::Bearish));
+        assert!(state.stand_down("jupiter_bluechip_trader", now).await.is_none());
+        assert!(state.stand_down("oracle_monitor", now).await.is_none());
+
+        // A stale regime gates nothing
+        assert!(state.stand_down("memecoin_monitor", now + 901).await.is_none());
+
+        state.set_gating("memecoin_monitor", Vec::new()).await;
+        assert!(state.stand_down("memecoin_monitor", now).await.is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Prometheus Metrics
+//! Text exposition format for GET /metrics - gauges and counters are read from the existing
+//! structs at scrape time, only latencies are recorded here as they happen
+
+use std::fmt::Write;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Swap latency buckets (seconds) - quote + sign + send + confirm, so they reach well past a slot
+pub const SWAP_LATENCY_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0];
+/// Oracle fetch latency buckets (seconds) - cache hits are not observed
+pub const ORACLE_LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Fixed-bucket histogram - `counts[i]` holds observations <= `bounds[i]`, non-cumulative
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self { bounds, counts: vec![0; bounds.len()], sum: 0.0, count: 0 }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        if let Some(bucket) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Latency histograms recorded from the swap and oracle paths
+#[derive(Debug)]
+pub struct Metrics {
+    swap_latency: Mutex<Histogram>,
+    oracle_latency: Mutex<Histogram>,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Self {
+            swap_latency: Mutex::new(Histogram::new(SWAP_LATENCY_BUCKETS)),
+            oracle_latency: Mutex::new(Histogram::new(ORACLE_LATENCY_BUCKETS)),
+        }
+    }
+
+    /// Process-wide instance (singleton pattern, like the shared HTTP client)
+    pub fn global() -> &'static Metrics {
+        static METRICS: OnceLock<Metrics> = OnceLock::new();
+        METRICS.get_or_init(Metrics::new)
+    }
+
+    pub fn observe_swap_latency(&self, elapsed: Duration) {
+        if let Ok(mut histogram) = self.swap_latency.lock() {
+            histogram.observe(elapsed.as_secs_f64());
+        }
+    }
+
+    pub fn observe_oracle_latency(&self, elapsed: Duration) {
+        if let Ok(mut histogram) = self.oracle_latency.lock() {
+            histogram.observe(elapsed.as_secs_f64());
+        }
+    }
+
+    /// Append both latency histograms to a scrape
+    pub fn write_latencies(&self, out: &mut Exposition) {
+        if let Ok(histogram) = self.swap_latency.lock() {
+            out.histogram("agentburn_swap_execution_duration_seconds", "Real swap execution latency", &histogram);
+        }
+        if let Ok(histogram) = self.oracle_latency.lock() {
+            out.histogram("agentburn_oracle_fetch_duration_seconds", "Oracle price fetch latency (cache misses)", &histogram);
+        }
+    }
+}
+
+/// Builder for one text-format scrape body
+#[derive(Debug, Default)]
+pub struct Exposition {
+    body: String,
+}
+
+impl Exposition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, help, "gauge", &[(Vec::new(), value)]);
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, help, "counter", &[(Vec::new(), value)]);
+    }
+
+    /// One metric with a sample per label set, e.g. `[(vec![("provider", "x")], 1.0)]`
+    pub fn family(&mut self, name: &str, help: &str, kind: &str, samples: &[(Vec<(&str, &str)>, f64)]) {
+        let _ = writeln!(self.body, "# HELP {} {}", name, help);
+        let _ = writeln!(self.body, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(self.body, "{}{} {}", name, format_labels(labels), format_value(*value));
+        }
+    }
+
+    pub fn histogram(&mut self, name: &str, help: &str, histogram: &Histogram) {
+        let _ = writeln!(self.body, "# HELP {} {}", name, help);
+        let _ = writeln!(self.body, "# TYPE {} histogram", name);
+        let mut cumulative = 0;
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+            cumulative += count;
+            let _ = writeln!(self.body, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        let _ = writeln!(self.body, "{}_bucket{{le=\"+Inf\"}} {}", name, histogram.count);
+        let _ = writeln!(self.body, "{}_sum {}", name, format_value(histogram.sum));
+        let _ = writeln!(self.body, "{}_count {}", name, histogram.count);
+    }
+
+    pub fn finish(self) -> String {
+        self.body
+    }
+}
+
+fn format_labels(labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels.iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf".to_string() } else { "-Inf".to_string() }
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_renders_cumulative_buckets() {
+        let mut histogram = Histogram::new(SWAP_LATENCY_BUCKETS);
+        histogram.observe(0.375);
+        histogram.observe(0.5);
+        histogram.observe(4.125);
+        histogram.observe(120.0); // Past the last bucket - only +Inf counts it
+
+        let mut out = Exposition::new();
+        out.histogram("swap_seconds", "Swap latency", &histogram);
+        out.family("signals", "Signals", "gauge", &[(vec![("provider", "a\"b")], 3.0)]);
+        let body = out.finish();
+
+        assert!(body.contains("# TYPE swap_seconds histogram"));
+        assert!(body.contains("swap_seconds_bucket{le=\"0.25\"} 0\n"));
+        assert!(body.contains("swap_seconds_bucket{le=\"0.5\"} 2\n"));
+        assert!(body.contains("swap_seconds_bucket{le=\"5\"} 3\n"));
+        assert!(body.contains("swap_seconds_bucket{le=\"60\"} 3\n"));
+        assert!(body.contains("swap_seconds_bucket{le=\"+Inf\"} 4\n"));
+        assert!(body.contains("swap_seconds_sum 125\n"));
+        assert!(body.contains("swap_seconds_count 4\n"));
+        assert!(body.contains("signals{provider=\"a\\\"b\"} 3\n"));
+    }
+}
@@ -0,0 +1,441 @@
+//! Notifications
+//! Delivers trade and safety alerts to an external webhook (Discord/Telegram compatible)
+//! Deliveries are retried with backoff and rate-limited so a signal storm can't flood the channel
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default delivery attempts per notification (1 = no retry)
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// Default notifications allowed per rate-limit window
+pub const DEFAULT_RATE_LIMIT_PER_MIN: usize = 20;
+
+/// Event types that can trigger a notification
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    TradeExecuted,
+    TargetHit,
+    StopLossHit,
+    CircuitBreakerTripped,
+    TaskRestarted,
+    FeedStale,
+    FundingGuard,
+}
+
+impl std::str::FromStr for NotificationEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| format!("Unknown notification event: {}", s))
+    }
+}
+
+/// A single notification message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub message: String,
+    pub timestamp: i64,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent, title: &str, message: &str) -> Self {
+        Self {
+            event,
+            title: title.to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now().timestamp(),
+        }
+    }
+
+    /// Synthetic notification used to verify delivery configuration
+    pub fn synthetic(event: NotificationEvent) -> Self {
+        let message = match event {
+            NotificationEvent::TradeExecuted => "TEST: BUY 1.0 SOL/USDC at $100.00",
+            NotificationEvent::TargetHit => "TEST: SOL/USDC target hit at $105.00 (+5.00%)",
+            NotificationEvent::StopLossHit => "TEST: SOL/USDC stop-loss hit at $95.00 (-5.00%)",
+            NotificationEvent::CircuitBreakerTripped => "TEST: Circuit breaker tripped - trading paused",
+            NotificationEvent::TaskRestarted => "TEST: Watchdog restarted stalled task 'auto_execute'",
+            NotificationEvent::FeedStale => "TEST: SOL/USD (Critical) hasn't updated in 12s (expected every 5s)",
+            NotificationEvent::FundingGuard => "TEST: PDA treasury holds 0.010000 SOL, below the 0.050000 SOL minimum - trading paused",
+        };
+        Self::new(event, "🧪 Test notification", message)
+    }
+
+    /// Plain-text rendering used by chat webhooks
+    pub fn render(&self) -> String {
+        format!("{}\n{}", self.title, self.message)
+    }
+}
+
+/// Result of a delivery attempt
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryResult {
+    pub delivered: bool,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Anything that can deliver notifications
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Human-readable name of the delivery channel
+    fn name(&self) -> &str;
+
+    async fn send(&self, notification: &Notification) -> DeliveryResult;
+}
+
+/// Webhook payload flavour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookKind {
+    Discord,
+    Telegram,
+    Generic,
+}
+
+impl WebhookKind {
+    /// Infer the payload flavour from the webhook URL
+    pub fn from_url(url: &str) -> Self {
+        if url.contains("discord.com") || url.contains("discordapp.com") {
+            WebhookKind::Discord
+        } else if url.contains("api.telegram.org") {
+            WebhookKind::Telegram
+        } else {
+            WebhookKind::Generic
+        }
+    }
+}
+
+/// Webhook notifier (Discord `content`, Telegram `sendMessage`, or raw JSON)
+pub struct WebhookNotifier {
+    url: String,
+    kind: WebhookKind,
+    telegram_chat_id: Option<String>,
+    client: Arc<Client>,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, kind: WebhookKind, telegram_chat_id: Option<String>) -> Self {
+        Self {
+            url,
+            kind,
+            telegram_chat_id,
+            client: crate::http_client::SharedHttpClient::shared(),
+        }
+    }
+
+    /// Load from NOTIFY_WEBHOOK_URL / NOTIFY_WEBHOOK_KIND / TELEGRAM_CHAT_ID
+    /// Returns None when no webhook is configured
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("NOTIFY_WEBHOOK_URL").ok().filter(|u| !u.trim().is_empty())?;
+        let kind = match std::env::var("NOTIFY_WEBHOOK_KIND").ok().as_deref() {
+            Some("discord") => WebhookKind::Discord,
+            Some("telegram") => WebhookKind::Telegram,
+            Some("generic") => WebhookKind::Generic,
+            _ => WebhookKind::from_url(&url),
+        };
+        let telegram_chat_id = std::env::var("TELEGRAM_CHAT_ID").ok();
+        Some(Self::new(url, kind, telegram_chat_id))
+    }
+
+    fn payload(&self, notification: &Notification) -> serde_json::Value {
+        match self.kind {
+            WebhookKind::Discord => serde_json::json!({ "content": notification.render() }),
+            WebhookKind::Telegram => serde_json::json!({
+                "chat_id": self.telegram_chat_id,
+                "text": notification.render(),
+            }),
+            WebhookKind::Generic => serde_json::to_value(notification).unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        match self.kind {
+            WebhookKind::Discord => "discord_webhook",
+            WebhookKind::Telegram => "telegram_webhook",
+            WebhookKind::Generic => "webhook",
+        }
+    }
+
+    async fn send(&self, notification: &Notification) -> DeliveryResult {
+        match self.client.post(&self.url).json(&self.payload(notification)).send().await {
+            Ok(response) => {
+                let status = response.status();
+                DeliveryResult {
+                    delivered: status.is_success(),
+                    status_code: Some(status.as_u16()),
+                    error: if status.is_success() {
+                        None
+                    } else {
+                        Some(format!("Webhook returned HTTP {}", status))
+                    },
+                }
+            }
+            Err(e) => DeliveryResult {
+                delivered: false,
+                status_code: e.status().map(|s| s.as_u16()),
+                error: Some(format!("Webhook request failed: {}", e)),
+            },
+        }
+    }
+}
+
+/// Retry and rate-limit settings for notification delivery
+#[derive(Debug, Clone)]
+pub struct DeliveryConfig {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration, // Doubles after each failed attempt
+    pub max_per_window: usize,
+    pub window: Duration,
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: Duration::from_millis(500),
+            max_per_window: DEFAULT_RATE_LIMIT_PER_MIN,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+impl DeliveryConfig {
+    /// Load from NOTIFY_MAX_ATTEMPTS / NOTIFY_RATE_LIMIT_PER_MIN
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_attempts: std::env::var("NOTIFY_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(defaults.max_attempts)
+                .max(1),
+            max_per_window: std::env::var("NOTIFY_RATE_LIMIT_PER_MIN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_per_window),
+            ..defaults
+        }
+    }
+}
+
+/// Wraps a notifier with retry-with-backoff and a sliding-window rate limit
+/// Notifications over the limit are dropped; the next delivered one reports how many were suppressed
+pub struct ReliableNotifier {
+    inner: Arc<dyn Notifier>,
+    config: DeliveryConfig,
+    sent: std::sync::Mutex<VecDeque<Instant>>,
+    suppressed: AtomicU64,
+}
+
+impl ReliableNotifier {
+    pub fn new(inner: Arc<dyn Notifier>, config: DeliveryConfig) -> Self {
+        Self {
+            inner,
+            config,
+            sent: std::sync::Mutex::new(VecDeque::new()),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Take a slot in the current window, if one is free
+    fn try_acquire(&self) -> bool {
+        let mut sent = self.sent.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        while sent.front().is_some_and(|t| now.duration_since(*t) >= self.config.window) {
+            sent.pop_front();
+        }
+        if sent.len() >= self.config.max_per_window {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+
+    /// Network errors, 429 and 5xx are worth retrying; other HTTP errors won't fix themselves
+    fn is_retryable(result: &DeliveryResult) -> bool {
+        match result.status_code {
+            None => true,
+            Some(code) => code == 429 || code >= 500,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for ReliableNotifier {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, notification: &Notification) -> DeliveryResult {
+        if !self.try_acquire() {
+            let suppressed = self.suppressed.fetch_add(1, Ordering::Relaxed) + 1;
+            log::debug!("🔕 Notification '{}' rate limited ({} suppressed)", notification.title, suppressed);
+            return DeliveryResult {
+                delivered: false,
+                status_code: None,
+                error: Some(format!("Rate limited: more than {} notifications per {:?}",
+                                    self.config.max_per_window, self.config.window)),
+            };
+        }
+
+        let suppressed = self.suppressed.swap(0, Ordering::Relaxed);
+        let mut outgoing = notification.clone();
+        if suppressed > 0 {
+            outgoing.message = format!("{}\n({} earlier alerts suppressed by rate limit)", outgoing.message, suppressed);
+        }
+
+        let mut delay = self.config.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let result = self.inner.send(&outgoing).await;
+            if result.delivered || attempt >= self.config.max_attempts || !Self::is_retryable(&result) {
+                if !result.delivered && attempt > 1 {
+                    log::warn!("⚠️ Notification via {} failed after {} attempts: {:?}",
+                              self.inner.name(), attempt, result.error);
+                }
+                return result;
+            }
+            log::debug!("Notification attempt {} via {} failed: {:?}. Retrying in {:?}...",
+                       attempt, self.inner.name(), result.error, delay);
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+/// Build the configured notifier, if any
+pub fn notifier_from_env() -> Option<Arc<dyn Notifier>> {
+    WebhookNotifier::from_env().map(|n| {
+        Arc::new(ReliableNotifier::new(Arc::new(n), DeliveryConfig::from_env())) as Arc<dyn Notifier>
+    })
+}
+
+/// Deliver in the background so trading loops never wait on webhook retries
+/// No-op when no notifier is configured
+pub fn dispatch(notifier: &Option<Arc<dyn Notifier>>, notification: Notification) {
+    if let Some(notifier) = notifier.clone() {
+        tokio::spawn(async move {
+            let result = notifier.send(&notification).await;
+            if !result.delivered {
+                log::debug!("Notification '{}' not delivered: {:?}", notification.title, result.error);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_parsing_and_kind_inference() {
+        assert_eq!("stop_loss_hit".parse::<NotificationEvent>(), Ok(NotificationEvent::StopLossHit));
+        assert_eq!("funding_guard".parse::<NotificationEvent>(), Ok(NotificationEvent::FundingGuard));
+        assert!("moon".parse::<NotificationEvent>().is_err());
+
+        assert_eq!(WebhookKind::from_url("https://discord.com/api/webhooks/1/abc"), WebhookKind::Discord);
+        assert_eq!(WebhookKind::from_url("https://api.telegram.org/bot123/sendMessage"), WebhookKind::Telegram);
+        assert_eq!(WebhookKind::from_url("https://example.com/hook"), WebhookKind::Generic);
+    }
+
+    struct FlakyNotifier {
+        failures_left: AtomicU64,
+        status_code: Option<u16>,
+        calls: AtomicU64,
+    }
+
+    #[async_trait]
+    impl Notifier for FlakyNotifier {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, _notification: &Notification) -> DeliveryResult {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            let failing = self.failures_left.load(Ordering::Relaxed) > 0;
+            if failing {
+                self.failures_left.fetch_sub(1, Ordering::Relaxed);
+            }
+            DeliveryResult {
+                delivered: !failing,
+                status_code: if failing { self.status_code } else { Some(200) },
+                error: if failing { Some("boom".to_string()) } else { None },
+            }
+        }
+    }
+
+    fn flaky(failures: u64, status_code: Option<u16>) -> Arc<FlakyNotifier> {
+        Arc::new(FlakyNotifier {
+            failures_left: AtomicU64::new(failures),
+            status_code,
+            calls: AtomicU64::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_and_rate_limit() {
+        let config = DeliveryConfig {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_per_window: 2,
+            window: Duration::from_secs(60),
+        };
+        let notification = Notification::synthetic(NotificationEvent::TradeExecuted);
+
+        // Two 503s then success
+        let inner = flaky(2, Some(503));
+        let notifier = ReliableNotifier::new(inner.clone(), config.clone());
+        assert!(notifier.send(&notification).await.delivered);
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 3);
+
+        // Client errors aren't retried
+        let inner = flaky(5, Some(400));
+        let notifier = ReliableNotifier::new(inner.clone(), config.clone());
+        assert!(!notifier.send(&notification).await.delivered);
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+
+        // Third notification in the window is dropped without hitting the webhook
+        let inner = flaky(0, None);
+        let notifier = ReliableNotifier::new(inner.clone(), config);
+        assert!(notifier.send(&notification).await.delivered);
+        assert!(notifier.send(&notification).await.delivered);
+        let limited = notifier.send(&notification).await;
+        assert!(!limited.delivered);
+        assert!(limited.error.unwrap().starts_with("Rate limited"));
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_webhook_payloads() {
+        let notification = Notification::synthetic(NotificationEvent::TargetHit);
+
+        let discord = WebhookNotifier::new("https://discord.com/x".to_string(), WebhookKind::Discord, None);
+        assert_eq!(discord.payload(&notification)["content"], notification.render());
+
+        let telegram = WebhookNotifier::new(
+            "https://api.telegram.org/bot1/sendMessage".to_string(),
+            WebhookKind::Telegram,
+            Some("42".to_string()),
+        );
+        let payload = telegram.payload(&notification);
+        assert_eq!(payload["chat_id"], "42");
+        assert_eq!(payload["text"], notification.render());
+
+        let generic = WebhookNotifier::new("https://example.com".to_string(), WebhookKind::Generic, None);
+        assert_eq!(generic.payload(&notification)["event"], "target_hit");
+    }
+}
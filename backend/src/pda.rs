@@ -1,6 +1,9 @@
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Seed prefix for the agent treasury PDA (shared with the on-chain withdrawal program)
+pub const TREASURY_SEED: &str = "agent-treasury";
+
 /// Treasury PDA (Program Derived Address) manager
 /// PDAs are deterministic addresses derived from a program ID and seeds
 pub struct TreasuryPDA {
@@ -41,11 +44,18 @@ impl TreasuryPDA {
     }
 
     /// Derive the default agent treasury PDA for mainnet/devnet trading
-    /// Uses the System Program as the default program ID
+    /// Derived under the withdrawal program when WITHDRAW_PROGRAM_ID is set, since only the program
+    /// a PDA is derived from can sign for it with invoke_signed. Falls back to the System Program
+    /// (deposit-only - no program can sign withdrawals for that PDA)
     pub fn derive_default(authority: &Pubkey) -> Result<Self, String> {
-        // Use System Program ID as default
-        let system_program = solana_sdk::system_program::id();
-        Self::derive(&system_program, authority, "agent-treasury")
+        Self::derive(&treasury_program_id(), authority, TREASURY_SEED)
+    }
+
+    /// Treasury address this authority had under the System Program, before WITHDRAW_PROGRAM_ID moved it
+    /// Setting WITHDRAW_PROGRAM_ID changes the treasury address - anything left here is not picked up
+    pub fn legacy_address(authority: &Pubkey) -> Pubkey {
+        let seeds = &[TREASURY_SEED.as_bytes(), authority.as_ref()];
+        Pubkey::find_program_address(seeds, &solana_sdk::system_program::id()).0
     }
 
     /// Derive a PDA for a specific agent by name
@@ -82,6 +92,23 @@ pub fn parse_program_id(program_id_str: &str) -> Result<Pubkey, String> {
         .map_err(|e| format!("Invalid program ID: {}", e))
 }
 
+/// Withdrawal program configured via WITHDRAW_PROGRAM_ID, if any
+pub fn withdraw_program_id() -> Option<Pubkey> {
+    let program_id_str = std::env::var("WITHDRAW_PROGRAM_ID").ok().filter(|p| !p.trim().is_empty())?;
+    match parse_program_id(program_id_str.trim()) {
+        Ok(program_id) => Some(program_id),
+        Err(e) => {
+            log::error!("❌ Ignoring WITHDRAW_PROGRAM_ID: {}", e);
+            None
+        }
+    }
+}
+
+/// Program the treasury PDA is derived under
+pub fn treasury_program_id() -> Pubkey {
+    withdraw_program_id().unwrap_or_else(solana_sdk::system_program::id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,6 +140,17 @@ mod tests {
         assert_eq!(pda1.bump, pda2.bump);
     }
 
+    #[test]
+    fn test_legacy_address_matches_system_program_derivation() {
+        let authority = Keypair::new().pubkey();
+        let system_pda = TreasuryPDA::derive(&solana_sdk::system_program::id(), &authority, TREASURY_SEED).unwrap();
+        let program_pda = TreasuryPDA::derive(&Pubkey::new_unique(), &authority, TREASURY_SEED).unwrap();
+
+        assert_eq!(TreasuryPDA::legacy_address(&authority), system_pda.address);
+        // A withdrawal program moves the treasury away from the legacy address
+        assert_ne!(TreasuryPDA::legacy_address(&authority), program_pda.address);
+    }
+
     #[test]
     fn test_derive_for_agent() {
         let keypair = Keypair::new();
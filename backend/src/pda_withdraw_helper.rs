@@ -1,23 +1,45 @@
 //! Helper for PDA withdrawals using a Solana program
 //! Since invoke_signed is program-side only, we need a program to handle withdrawals
-//! 
-//! This module provides utilities for calling a withdrawal program or
-//! constructing transactions that can work with a Solana program
+//!
+//! This module holds both halves of the withdrawal flow so they share one instruction layout:
+//! the client-side instruction builder and the on-chain processor (deployed as WITHDRAW_PROGRAM_ID)
 
 use solana_sdk::{
     pubkey::Pubkey,
     instruction::{Instruction, AccountMeta},
     system_program,
 };
-use std::str::FromStr;
+
+/// Instruction discriminator for a withdrawal
+pub const WITHDRAW_DISCRIMINATOR: u8 = 0;
+
+/// Withdrawal instruction payload
+/// Format: [instruction_discriminator: u8, amount: u64 (LE), bump: u8]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawInstruction {
+    pub amount_lamports: u64,
+    pub bump: u8,
+}
+
+impl WithdrawInstruction {
+    pub const LEN: usize = 10;
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.push(WITHDRAW_DISCRIMINATOR);
+        data.extend_from_slice(&self.amount_lamports.to_le_bytes());
+        data.push(self.bump);
+        data
+    }
+}
 
 /// Helper to create a withdrawal instruction for a Solana program
-/// This instruction would be processed by a Solana program that uses invoke_signed
+/// This instruction is processed by `program::process_instruction`, which uses invoke_signed
 pub struct PDAWithdrawHelper;
 
 impl PDAWithdrawHelper {
-    /// Create instruction data for a withdrawal program
-    /// The program would process this and use invoke_signed to transfer from PDA
+    /// Create the withdrawal instruction
+    /// The program verifies the PDA seeds and uses invoke_signed to transfer from PDA to `destination`
     pub fn create_withdraw_instruction(
         program_id: &Pubkey,
         pda: &Pubkey,
@@ -26,13 +48,6 @@ impl PDAWithdrawHelper {
         amount_lamports: u64,
         bump: u8,
     ) -> Instruction {
-        // Instruction data format for the withdrawal program
-        // Format: [instruction_discriminator: u8, amount: u64, bump: u8]
-        let mut instruction_data = Vec::with_capacity(10);
-        instruction_data.push(0); // Instruction discriminator (0 = withdraw)
-        instruction_data.extend_from_slice(&amount_lamports.to_le_bytes());
-        instruction_data.push(bump);
-        
         Instruction {
             program_id: *program_id,
             accounts: vec![
@@ -41,103 +56,161 @@ impl PDAWithdrawHelper {
                 AccountMeta::new_readonly(*authority, true), // Authority wallet (signer)
                 AccountMeta::new_readonly(system_program::id(), false), // System Program
             ],
-            data: instruction_data,
+            data: WithdrawInstruction { amount_lamports, bump }.pack(),
         }
     }
-    
-    /// Get the PDA seeds for signing
-    pub fn get_pda_seeds(authority: &Pubkey, bump: u8) -> Vec<Vec<u8>> {
-        vec![
-            b"agent-treasury".to_vec(),
-            authority.as_ref().to_vec(),
-            vec![bump],
-        ]
+}
+
+/// On-chain withdrawal processor
+/// Deploy from a cdylib crate with `entrypoint!(process_instruction)` and set WITHDRAW_PROGRAM_ID
+/// to the deployed program ID - the backend then derives the treasury PDA under that program
+#[allow(dead_code)] // Runs on-chain, not in the backend process
+pub mod program {
+    use super::{WithdrawInstruction, WITHDRAW_DISCRIMINATOR};
+    use crate::pda::TREASURY_SEED;
+    use solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        rent::Rent,
+        system_instruction,
+        sysvar::Sysvar,
+    };
+
+    pub fn unpack_instruction(data: &[u8]) -> Result<WithdrawInstruction, ProgramError> {
+        if data.len() != WithdrawInstruction::LEN || data[0] != WITHDRAW_DISCRIMINATOR {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let amount_lamports = u64::from_le_bytes(
+            data[1..9].try_into().map_err(|_| ProgramError::InvalidInstructionData)?
+        );
+        Ok(WithdrawInstruction { amount_lamports, bump: data[9] })
     }
-    
-    /// Get signer seeds array format for invoke_signed
-    /// Format expected by invoke_signed: &[&[&[u8]]]
-    pub fn get_signer_seeds(authority: &Pubkey, bump: u8) -> Vec<Vec<u8>> {
-        Self::get_pda_seeds(authority, bump)
+
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let instruction = unpack_instruction(instruction_data)?;
+        let rent = Rent::get()?;
+        process_withdraw(program_id, accounts, &instruction, &rent)
     }
-}
 
-/// Example Solana Program Code (to be deployed separately):
-/// 
-/// ```rust
-/// use solana_program::{
-///     account_info::{next_account_info, AccountInfo},
-///     entrypoint,
-///     entrypoint::ProgramResult,
-///     program::invoke_signed,
-///     pubkey::Pubkey,
-///     system_instruction,
-/// };
-/// 
-/// entrypoint!(process_instruction);
-/// 
-/// fn process_instruction(
-///     program_id: &Pubkey,
-///     accounts: &[AccountInfo],
-///     instruction_data: &[u8],
-/// ) -> ProgramResult {
-///     let account_info_iter = &mut accounts.iter();
-///     let pda_account = next_account_info(account_info_iter)?;
-///     let destination_account = next_account_info(account_info_iter)?;
-///     let authority_account = next_account_info(account_info_iter)?;
-///     let system_program = next_account_info(account_info_iter)?;
-///     
-///     // Verify authority is a signer
-///     if !authority_account.is_signer {
-///         return Err(ProgramError::MissingRequiredSignature);
-///     }
-///     
-///     // Parse instruction data
-///     let amount_lamports = u64::from_le_bytes(
-///         instruction_data[1..9].try_into().unwrap()
-///     );
-///     let bump = instruction_data[9];
-///     
-///     // Verify PDA derivation
-///     let seeds = &[
-///         b"agent-treasury",
-///         authority_account.key.as_ref(),
-///         &[bump],
-///     ];
-///     let (expected_pda, _) = Pubkey::find_program_address(seeds, program_id);
-///     if pda_account.key != &expected_pda {
-///         return Err(ProgramError::InvalidSeeds);
-///     }
-///     
-///     // Create transfer instruction FROM PDA TO destination
-///     let transfer_instruction = system_instruction::transfer(
-///         pda_account.key,
-///         destination_account.key,
-///         amount_lamports,
-///     );
-///     
-///     // Invoke with PDA signing using seeds
-///     invoke_signed(
-///         &transfer_instruction,
-///         &[
-///             pda_account.clone(),
-///             destination_account.clone(),
-///             system_program.clone(),
-///         ],
-///         &[&[
-///             b"agent-treasury",
-///             authority_account.key.as_ref(),
-///             &[bump],
-///         ]],
-///     )?;
-///     
-///     Ok(())
-/// }
-/// ```
+    /// Transfer lamports from the treasury PDA back to its authority
+    /// Refuses withdrawals that would leave the PDA below the rent-exempt minimum
+    pub fn process_withdraw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction: &WithdrawInstruction,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pda_account = next_account_info(account_info_iter)?;
+        let destination_account = next_account_info(account_info_iter)?;
+        let authority_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // Only the authority can withdraw, and only to itself
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if destination_account.key != authority_account.key {
+            msg!("Destination must be the treasury authority");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Verify PDA derivation against this program
+        let bump = [instruction.bump];
+        let signer_seeds: &[&[u8]] = &[
+            TREASURY_SEED.as_bytes(),
+            authority_account.key.as_ref(),
+            &bump,
+        ];
+        let expected_pda = Pubkey::create_program_address(signer_seeds, program_id)
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        if pda_account.key != &expected_pda {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // RENT EXEMPTION: never drain the PDA below the rent-exempt minimum
+        let min_balance = rent.minimum_balance(pda_account.data_len());
+        let remaining = pda_account.lamports()
+            .checked_sub(instruction.amount_lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        if remaining < min_balance {
+            msg!("Withdrawal would leave {} lamports, below rent-exempt minimum {}", remaining, min_balance);
+            return Err(ProgramError::InsufficientFunds);
+        }
 
+        // Transfer FROM PDA TO destination, signing for the PDA with its seeds
+        invoke_signed(
+            &system_instruction::transfer(pda_account.key, destination_account.key, instruction.amount_lamports),
+            &[
+                pda_account.clone(),
+                destination_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::{account_info::AccountInfo, program_error::ProgramError, rent::Rent};
 
+    #[test]
+    fn test_instruction_roundtrip() {
+        let instruction = WithdrawInstruction { amount_lamports: 1_500_000, bump: 254 };
+        let data = instruction.pack();
+        assert_eq!(data.len(), WithdrawInstruction::LEN);
+        assert_eq!(program::unpack_instruction(&data), Ok(instruction));
+        assert!(program::unpack_instruction(&data[..9]).is_err());
+    }
 
+    #[test]
+    fn test_withdraw_validation() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (pda, bump) = Pubkey::find_program_address(
+            &[crate::pda::TREASURY_SEED.as_bytes(), authority.as_ref()],
+            &program_id,
+        );
+        let system_id = system_program::id();
+        let rent = Rent::default();
+        let min_balance = rent.minimum_balance(0);
 
+        let mut pda_lamports = min_balance + 1_000_000;
+        let mut pda_data: Vec<u8> = vec![];
+        let mut authority_lamports = 0u64;
+        let mut authority_data: Vec<u8> = vec![];
+        let mut destination_lamports = 0u64;
+        let mut destination_data: Vec<u8> = vec![];
+        let mut system_lamports = 0u64;
+        let mut system_data: Vec<u8> = vec![];
+        let accounts = [
+            AccountInfo::new(&pda, false, true, &mut pda_lamports, &mut pda_data, &system_id, false, 0),
+            AccountInfo::new(&authority, false, true, &mut destination_lamports, &mut destination_data, &system_id, false, 0),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &system_id, false, 0),
+            AccountInfo::new(&system_id, false, false, &mut system_lamports, &mut system_data, &system_id, true, 0),
+        ];
 
+        // Would dip below the rent-exempt minimum
+        let too_much = WithdrawInstruction { amount_lamports: 1_000_001, bump };
+        assert_eq!(program::process_withdraw(&program_id, &accounts, &too_much, &rent), Err(ProgramError::InsufficientFunds));
 
+        // Wrong bump fails seed verification
+        let wrong_bump = WithdrawInstruction { amount_lamports: 1_000, bump: bump.wrapping_sub(1) };
+        assert!(program::process_withdraw(&program_id, &accounts, &wrong_bump, &rent).is_err());
 
+        // PDA derived under another program can't be signed for
+        let other_program = Pubkey::new_unique();
+        let ok = WithdrawInstruction { amount_lamports: 1_000_000, bump };
+        assert!(program::process_withdraw(&other_program, &accounts, &ok, &rent).is_err());
+    }
+}
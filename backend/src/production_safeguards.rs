@@ -3,6 +3,37 @@
 
 use serde::Serialize;
 use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::dex_aggregator::BaseCurrency;
+use crate::trading_engine::TradingEngine;
+
+/// Drawdown (percent) at which every position is flattened automatically
+/// Sits above the risk manager's soft limit, which only blocks new entries
+pub const DEFAULT_HARD_DRAWDOWN_LIMIT_PCT: f64 = 25.0;
+
+/// Seconds trading stays off after the drawdown guard trips before it may be re-enabled
+pub const DEFAULT_DRAWDOWN_COOLDOWN_SECS: i64 = 3600;
+
+/// Smallest PDA treasury balance real trading may start or keep running with (SOL)
+pub const DEFAULT_MIN_TREASURY_BALANCE_SOL: f64 = 0.05;
+
+/// Seconds between treasury balance re-checks while real trading is configured
+pub const DEFAULT_FUNDING_CHECK_SECS: u64 = 60;
+
+/// Unexplained treasury drop (percent of the previous reading) that disables trading
+pub const DEFAULT_BALANCE_ANOMALY_DROP_PCT: f64 = 2.0;
+
+/// Unexplained drop (SOL) always tolerated - fee estimates and slippage never match the chain exactly
+pub const DEFAULT_BALANCE_ANOMALY_TOLERANCE_SOL: f64 = 0.01;
+
+/// Seconds between checks of tracked positions against on-chain token balances
+pub const DEFAULT_PORTFOLIO_RECONCILE_INTERVAL_SECS: u64 = 300;
+
+/// Difference (percent of the larger amount) a position may drift before it is corrected
+pub const DEFAULT_PORTFOLIO_RECONCILE_TOLERANCE_PCT: f64 = 0.5;
 
 /// Production safety configuration
 #[derive(Debug, Clone)]
@@ -285,3 +316,1000 @@ pub struct SafetyStatus {
     pub can_trade: bool,
 }
 
+/// Outcome of closing a single position during a flatten
+#[derive(Debug, Clone, Serialize)]
+pub struct FlattenClose {
+    pub symbol: String,
+    pub size: f64,
+    pub price: Option<f64>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Kill-switch report
+#[derive(Debug, Clone, Serialize)]
+pub struct FlattenReport {
+    pub reason: String,
+    pub triggered_at: i64,
+    pub dry_run: bool,
+    pub orders_cancelled: usize,
+    pub closes: Vec<FlattenClose>,
+    pub positions_remaining: usize,
+    pub final_balance: f64,
+}
+
+/// Kill switch: disable trading, cancel resting orders and sell every open position
+/// Holds the engine lock for the whole flatten so the auto-exec loop can't interleave new entries,
+/// and leaves the engine halted until trading is explicitly re-enabled
+pub async fn flatten_all(
+    engine: &Arc<Mutex<TradingEngine>>,
+    trading_enabled: &Arc<Mutex<bool>>,
+    dry_run: bool,
+    reason: &str,
+) -> FlattenReport {
+    log::error!("🚨 KILL SWITCH: flattening all positions ({})", reason);
+    *trading_enabled.lock().await = false;
+    
+    let mut engine_lock = engine.lock().await;
+    engine_lock.halted = true;
+    
+    let orders_cancelled = engine_lock.cancel_all_orders().len();
+    
+    let mut symbols: Vec<(String, f64)> = engine_lock.portfolio.iter()
+        .filter(|(_, size)| **size > 0.0)
+        .map(|(symbol, size)| (symbol.clone(), *size))
+        .collect();
+    symbols.sort_by(|a, b| a.0.cmp(&b.0));
+    
+    let mut closes = Vec::with_capacity(symbols.len());
+    for (symbol, size) in symbols {
+        let price = engine_lock.last_price(&symbol);
+        let result = match price {
+            Some(p) => engine_lock.close_position(&symbol, p, dry_run).await,
+            None => Err(format!("No price available to close {}", symbol)),
+        };
+        
+        match &result {
+            Ok(sold) => log::warn!("🚨 Flattened {} {} @ ${:.8}", sold, symbol, price.unwrap_or(0.0)),
+            Err(e) => log::error!("❌ Failed to flatten {} ({} held): {}", symbol, size, e),
+        }
+        closes.push(FlattenClose {
+            symbol,
+            size,
+            price,
+            success: result.is_ok(),
+            error: result.err(),
+        });
+    }
+    
+    let positions_remaining = engine_lock.portfolio.values().filter(|size| **size > 0.0).count();
+    let final_balance = engine_lock.current_balance;
+    drop(engine_lock);
+    
+    log::error!("🚨 KILL SWITCH complete: {}/{} positions closed, {} orders cancelled, final balance {:.8} SOL",
+               closes.iter().filter(|c| c.success).count(), closes.len(), orders_cancelled, final_balance);
+    
+    FlattenReport {
+        reason: reason.to_string(),
+        triggered_at: Utc::now().timestamp(),
+        dry_run,
+        orders_cancelled,
+        closes,
+        positions_remaining,
+        final_balance,
+    }
+}
+
+/// Drawdown guard limits for the auto-exec loop (the soft limit is the risk manager's `max_drawdown`)
+#[derive(Debug, Clone, Copy)]
+pub struct DrawdownGuardConfig {
+    /// Drawdown (percent) that flattens everything and needs an operator re-enable
+    pub hard_limit_pct: f64,
+    pub cooldown_secs: i64,
+}
+
+impl DrawdownGuardConfig {
+    /// HARD_DRAWDOWN_LIMIT_PCT (default 25), DRAWDOWN_COOLDOWN_SECS (default 3600)
+    pub fn from_env() -> Self {
+        Self {
+            hard_limit_pct: std::env::var("HARD_DRAWDOWN_LIMIT_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_HARD_DRAWDOWN_LIMIT_PCT),
+            cooldown_secs: std::env::var("DRAWDOWN_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_DRAWDOWN_COOLDOWN_SECS),
+        }
+    }
+}
+
+/// Trading switched off by the drawdown guard (kept on the engine, reported by /safety/status)
+#[derive(Debug, Clone, Serialize)]
+pub struct DrawdownLockout {
+    pub drawdown_pct: f64,
+    pub limit_pct: f64,
+    /// Hit the hard ceiling - positions were flattened and only an operator re-enable resumes trading
+    pub hard: bool,
+    pub triggered_at: i64,
+    pub cooldown_until: i64,
+    /// Operator re-enabled trading - the guard re-arms once drawdown recovers below the soft limit
+    pub released: bool,
+}
+
+impl DrawdownLockout {
+    pub fn cooldown_remaining_secs(&self, now: i64) -> i64 {
+        (self.cooldown_until - now).max(0)
+    }
+    
+    /// Why an operator re-enable is refused right now
+    pub fn reenable_block(&self, now: i64) -> Option<String> {
+        let remaining = self.cooldown_remaining_secs(now);
+        if self.released || remaining == 0 {
+            return None;
+        }
+        Some(format!("Drawdown {:.2}% tripped the {:.2}% {} limit - re-enable allowed in {}s",
+                     self.drawdown_pct, self.limit_pct, if self.hard { "hard" } else { "soft" }, remaining))
+    }
+}
+
+/// What the drawdown guard did this cycle
+#[derive(Debug, Clone)]
+pub enum DrawdownAction {
+    /// Soft limit crossed - trading disabled for the cooldown
+    Disabled(DrawdownLockout),
+    /// Hard ceiling hit - everything flattened, operator re-enable required
+    Flattened(DrawdownLockout, FlattenReport),
+    /// Cooldown over and drawdown recovered - trading re-enabled automatically
+    Resumed(DrawdownLockout),
+}
+
+/// Auto-disable trading when drawdown spikes instead of leaving it to per-trade checks
+/// Runs every auto-exec cycle, including while trading is disabled, so a soft lockout can resume on its own
+pub async fn enforce_drawdown_limits(
+    engine: &Arc<Mutex<TradingEngine>>,
+    trading_enabled: &Arc<Mutex<bool>>,
+    dry_run: bool,
+    config: DrawdownGuardConfig,
+) -> Option<DrawdownAction> {
+    let now = Utc::now().timestamp();
+    let (risk_manager, lockout) = {
+        let engine_lock = engine.lock().await;
+        (engine_lock.risk_manager.clone(), engine_lock.drawdown_lockout.clone())
+    };
+    let (drawdown_pct, soft_limit_pct) = {
+        let risk = risk_manager.lock().await;
+        (risk.calculate_drawdown() * 100.0, risk.max_drawdown * 100.0)
+    };
+    let enabled = *trading_enabled.lock().await;
+    let hard = drawdown_pct >= config.hard_limit_pct;
+    
+    if let Some(lockout) = lockout {
+        if drawdown_pct < soft_limit_pct {
+            if lockout.released {
+                // Recovered after an operator re-enable - re-arm the guard
+                engine.lock().await.drawdown_lockout = None;
+                return None;
+            }
+            if !lockout.hard && lockout.cooldown_remaining_secs(now) == 0 {
+                engine.lock().await.drawdown_lockout = None;
+                *trading_enabled.lock().await = true;
+                log::warn!("✅ Drawdown recovered to {:.2}% after cooldown - trading re-enabled", drawdown_pct);
+                return Some(DrawdownAction::Resumed(lockout));
+            }
+            return None;
+        }
+        // Still under water - only a fall through the hard ceiling escalates a soft lockout,
+        // even though the lockout itself turned trading off
+        if lockout.hard || !hard {
+            return None;
+        }
+    } else if !hard && (!enabled || drawdown_pct < soft_limit_pct) {
+        // Disabled trading doesn't close positions - the hard ceiling flattens them regardless
+        return None;
+    }
+    
+    let lockout = DrawdownLockout {
+        drawdown_pct,
+        limit_pct: if hard { config.hard_limit_pct } else { soft_limit_pct },
+        hard,
+        triggered_at: now,
+        cooldown_until: now + config.cooldown_secs.max(0),
+        released: false,
+    };
+    
+    if hard {
+        let reason = format!("Drawdown {:.2}% hit hard limit {:.2}%", drawdown_pct, config.hard_limit_pct);
+        let report = flatten_all(engine, trading_enabled, dry_run, &reason).await;
+        engine.lock().await.drawdown_lockout = Some(lockout.clone());
+        return Some(DrawdownAction::Flattened(lockout, report));
+    }
+    
+    *trading_enabled.lock().await = false;
+    engine.lock().await.drawdown_lockout = Some(lockout.clone());
+    log::error!("📉 Drawdown {:.2}% crossed max {:.2}% - trading disabled for {}s cooldown",
+               drawdown_pct, soft_limit_pct, config.cooldown_secs);
+    Some(DrawdownAction::Disabled(lockout))
+}
+
+/// `sol` as an amount of the base currency, at `sol_price` (USD per SOL) - None with a USDC base and no price
+fn sol_in_base(base_currency: BaseCurrency, sol: f64, sol_price: Option<f64>) -> Option<f64> {
+    match base_currency {
+        BaseCurrency::Sol => Some(sol),
+        BaseCurrency::Usdc => sol_price.filter(|price| *price > 0.0).map(|price| sol * price),
+    }
+}
+
+/// SOL/USD price the SOL-denominated thresholds convert at - only read with a USDC base, stale quotes never count
+pub async fn threshold_sol_price(base_currency: BaseCurrency, oracle: &crate::switchboard_oracle::SwitchboardClient) -> Option<f64> {
+    if base_currency == BaseCurrency::Sol {
+        return None;
+    }
+    match oracle.fetch_price("SOL/USD").await {
+        Ok(feed) if !feed.stale && feed.price > 0.0 => Some(feed.price),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("⚠️ No SOL/USD price to convert the treasury thresholds into USDC: {}", e);
+            None
+        }
+    }
+}
+
+/// Funding guard for real trading - dry-run never needs a funded treasury
+#[derive(Debug, Clone, Copy)]
+pub struct FundingGuardConfig {
+    pub min_balance_sol: f64,
+    pub check_interval_secs: u64,
+    /// Currency the treasury balance is read in - with USDC the minimum converts at the SOL price
+    pub base_currency: BaseCurrency,
+}
+
+impl FundingGuardConfig {
+    /// MIN_TREASURY_BALANCE_SOL (default 0.05), FUNDING_CHECK_INTERVAL_SECS (default 60)
+    pub fn from_env(base_currency: BaseCurrency) -> Self {
+        Self {
+            base_currency,
+            min_balance_sol: std::env::var("MIN_TREASURY_BALANCE_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MIN_TREASURY_BALANCE_SOL),
+            check_interval_secs: std::env::var("FUNDING_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_FUNDING_CHECK_SECS),
+        }
+    }
+    
+    /// The minimum in the base currency - None with a USDC base and no SOL price to convert at
+    pub fn min_balance(&self, sol_price: Option<f64>) -> Option<f64> {
+        sol_in_base(self.base_currency, self.min_balance_sol, sol_price)
+    }
+    
+    /// Why this treasury balance can't fund real trading, `None` if it can
+    /// An unreadable balance, or a minimum that can't be converted, counts as unfunded - real trading
+    /// never starts on an unverified treasury
+    pub fn shortfall(&self, balance: &Result<f64, String>, sol_price: Option<f64>) -> Option<String> {
+        let currency = self.base_currency.symbol();
+        match (balance, self.min_balance(sol_price)) {
+            (Err(e), _) => Some(format!("PDA treasury balance unavailable ({})", e)),
+            (Ok(_), None) => Some(format!("no SOL price to convert the {:.6} SOL minimum into {}",
+                                          self.min_balance_sol, currency)),
+            (Ok(balance), Some(min_balance)) if *balance >= min_balance => None,
+            (Ok(balance), Some(min_balance)) => Some(format!("PDA treasury holds {:.6} {}, below the {:.6} {} minimum",
+                                                             balance, currency, min_balance, currency)),
+        }
+    }
+}
+
+/// Real trading held off by the funding guard (kept on the engine, reported by /safety/status)
+#[derive(Debug, Clone, Serialize)]
+pub struct FundingLockout {
+    /// Treasury balance and the minimum it fell below, in `currency`
+    pub balance: Option<f64>,
+    pub min_balance: Option<f64>,
+    pub currency: String,
+    pub reason: String,
+    pub triggered_at: i64,
+}
+
+impl FundingLockout {
+    /// Operator-facing explanation, also used to refuse re-enables
+    pub fn message(&self) -> String {
+        format!("{} - real trading disabled until the treasury is funded and trading is re-enabled via POST /trading-toggle",
+                self.reason)
+    }
+}
+
+/// What the funding guard did this check
+#[derive(Debug, Clone)]
+pub enum FundingAction {
+    /// Treasury below the minimum - trading disabled
+    Paused(FundingLockout),
+    /// Treasury funded again - lockout cleared, trading stays off until an operator re-enables it
+    Funded(f64),
+}
+
+/// Keep real trading off while the PDA treasury can't fund it
+/// Runs once at startup and then every `check_interval_secs`, so a drained treasury pauses trading
+/// instead of letting signals fire (and revert) on chain
+pub async fn enforce_funding_minimum(
+    engine: &Arc<Mutex<TradingEngine>>,
+    trading_enabled: &Arc<Mutex<bool>>,
+    dry_run: bool,
+    balance: Result<f64, String>,
+    sol_price: Option<f64>,
+    config: FundingGuardConfig,
+) -> Option<FundingAction> {
+    let mut engine_lock = engine.lock().await;
+    if dry_run {
+        engine_lock.funding_lockout = None;
+        return None;
+    }
+    
+    match config.shortfall(&balance, sol_price) {
+        Some(reason) => {
+            if let Some(lockout) = engine_lock.funding_lockout.as_mut() {
+                // Already paused - keep the reading current without re-alerting
+                lockout.balance = balance.ok();
+                lockout.min_balance = config.min_balance(sol_price);
+                lockout.reason = reason;
+                return None;
+            }
+            let lockout = FundingLockout {
+                balance: balance.ok(),
+                min_balance: config.min_balance(sol_price),
+                currency: config.base_currency.symbol().to_string(),
+                reason,
+                triggered_at: Utc::now().timestamp(),
+            };
+            engine_lock.funding_lockout = Some(lockout.clone());
+            drop(engine_lock);
+            *trading_enabled.lock().await = false;
+            log::error!("💸 {}", lockout.message());
+            Some(FundingAction::Paused(lockout))
+        }
+        None => {
+            let balance = balance.unwrap_or_default();
+            engine_lock.funding_lockout.take()?;
+            log::warn!("✅ PDA treasury funded ({:.6} {}) - re-enable trading via POST /trading-toggle",
+                       balance, config.base_currency.symbol());
+            Some(FundingAction::Funded(balance))
+        }
+    }
+}
+
+/// Balance anomaly detector - compares consecutive PDA treasury readings against tracked flows
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceAnomalyConfig {
+    pub enabled: bool,
+    pub max_drop_pct: f64,
+    pub tolerance_sol: f64,
+    /// Currency the treasury balance is read in - with USDC the tolerance converts at the SOL price
+    pub base_currency: BaseCurrency,
+}
+
+impl BalanceAnomalyConfig {
+    /// BALANCE_ANOMALY_DETECTION (default true), BALANCE_ANOMALY_DROP_PCT (default 2),
+    /// BALANCE_ANOMALY_TOLERANCE_SOL (default 0.01)
+    pub fn from_env(base_currency: BaseCurrency) -> Self {
+        Self {
+            base_currency,
+            enabled: std::env::var("BALANCE_ANOMALY_DETECTION")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(true),
+            max_drop_pct: std::env::var("BALANCE_ANOMALY_DROP_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|pct: &f64| *pct > 0.0)
+                .unwrap_or(DEFAULT_BALANCE_ANOMALY_DROP_PCT),
+            tolerance_sol: std::env::var("BALANCE_ANOMALY_TOLERANCE_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|sol: &f64| *sol >= 0.0)
+                .unwrap_or(DEFAULT_BALANCE_ANOMALY_TOLERANCE_SOL),
+        }
+    }
+    
+    /// An unexplained drop past both the tolerance and the percentage threshold, in the base currency
+    /// With a USDC base and no SOL price the percentage threshold decides alone
+    pub fn is_anomalous(&self, previous_balance: f64, unexplained_drop: f64, sol_price: Option<f64>) -> bool {
+        unexplained_drop > sol_in_base(self.base_currency, self.tolerance_sol, sol_price).unwrap_or(0.0)
+            && unexplained_drop > previous_balance.max(0.0) * self.max_drop_pct / 100.0
+    }
+}
+
+/// SOL the default treasury gained (or lost, negative), booked once the movement is final
+/// Deposits, withdrawals and conversions are booked when they confirm; swaps once the chain shows their fill
+#[derive(Debug, Clone, Copy)]
+pub struct TreasuryFlow {
+    pub booked_at_ms: i64,
+    pub delta_sol: f64,
+}
+
+/// A submitted swap's treasury movement - unknown until the confirmation poller reads its fill
+#[derive(Debug, Clone, Copy)]
+pub struct UnsettledSwapFlow {
+    pub is_buy: bool,
+    /// Quoted trade value (SOL) the swap is expected to move
+    pub estimated_sol: f64,
+    /// Network fee and tip the treasury pays whether or not the swap fills
+    pub fee_sol: f64,
+}
+
+impl UnsettledSwapFlow {
+    /// The movement once the chain settled the swap - `quote_amount` is the realized fill, None if it failed
+    pub fn realized(&self, quote_amount: Option<f64>) -> f64 {
+        let traded = quote_amount.unwrap_or(0.0);
+        let traded = if self.is_buy { -traded } else { traded };
+        traded - self.fee_sol
+    }
+    
+    /// Largest amount the swap may already have moved
+    pub fn magnitude(&self) -> f64 {
+        self.estimated_sol.abs() + self.fee_sol
+    }
+}
+
+/// Latest treasury reconciliation (kept on the engine, reported by /safety/status)
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceReconciliation {
+    pub checked_at: i64,
+    /// When the balance was read (ms) - flows booked before it are in the reading
+    pub read_started_at_ms: i64,
+    pub previous_balance: f64,
+    /// Net SOL confirmed fills, deposits and withdrawals moved between the previous reading and this one
+    pub tracked_flows: f64,
+    /// Flows that may fall on either side of this reading: swaps still unconfirmed plus movements
+    /// booked while the balance was being read. They excuse a drop here and at the next reading
+    pub unsettled_flows: f64,
+    /// Part of `unsettled_flows` booked after this reading - carried into the next reconciliation
+    pub late_flows: f64,
+    pub expected_balance: f64,
+    pub actual_balance: f64,
+    /// actual - expected; negative means SOL left the treasury without a tracked reason
+    pub discrepancy: f64,
+    pub anomaly: bool,
+}
+
+impl BalanceReconciliation {
+    pub fn message(&self) -> String {
+        format!("PDA treasury holds {:.6} SOL, expected {:.6} SOL ({:.6} SOL unexplained since the last reading of {:.6} SOL)",
+                self.actual_balance, self.expected_balance, -self.discrepancy, self.previous_balance)
+    }
+}
+
+/// Reconcile a fresh treasury reading against the previous one plus the engine's realized flows
+/// `read_started_at_ms` is when the balance read began - read it without the engine lock. Only flows
+/// booked before then count as expected; anything still in flight or booked since can't be placed on
+/// either side of the reading, so it only widens what an unexplained drop must exceed. The first
+/// reading sets the baseline. Returns the reconciliation when the drop is anomalous - the caller
+/// disables trading and alerts
+pub fn reconcile_treasury_balance(
+    engine: &mut TradingEngine,
+    balance: f64,
+    read_started_at_ms: i64,
+    sol_price: Option<f64>,
+    config: BalanceAnomalyConfig,
+) -> Option<BalanceReconciliation> {
+    let (booked, late): (Vec<TreasuryFlow>, Vec<TreasuryFlow>) = std::mem::take(&mut engine.pending_treasury_flows)
+        .into_iter()
+        .partition(|flow| flow.booked_at_ms < read_started_at_ms);
+    let tracked_flows: f64 = booked.iter().map(|flow| flow.delta_sol).sum();
+    let late_flows: f64 = late.iter().map(|flow| flow.delta_sol.abs()).sum();
+    let in_flight: f64 = engine.unsettled_treasury_flows.values().map(|flow| flow.magnitude()).sum();
+    
+    let (previous_balance, carried) = match &engine.balance_reconciliation {
+        Some(last) => (last.actual_balance, last.late_flows),
+        None => (balance - tracked_flows, 0.0),
+    };
+    let unsettled_flows = late_flows + carried + in_flight;
+    let expected_balance = previous_balance + tracked_flows;
+    let discrepancy = balance - expected_balance;
+    let reconciliation = BalanceReconciliation {
+        checked_at: Utc::now().timestamp(),
+        read_started_at_ms,
+        previous_balance,
+        tracked_flows,
+        unsettled_flows,
+        late_flows,
+        expected_balance,
+        actual_balance: balance,
+        discrepancy,
+        anomaly: config.is_anomalous(previous_balance, -discrepancy - unsettled_flows, sol_price),
+    };
+    engine.balance_reconciliation = Some(reconciliation.clone());
+    if !reconciliation.anomaly {
+        return None;
+    }
+    log::error!("🚨 Balance anomaly: {} - trading disabled", reconciliation.message());
+    Some(reconciliation)
+}
+
+/// Portfolio reconciliation - corrects tracked positions to the wallets' on-chain token balances
+#[derive(Debug, Clone, Copy)]
+pub struct PortfolioReconciliationConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub tolerance_pct: f64,
+}
+
+impl PortfolioReconciliationConfig {
+    /// PORTFOLIO_RECONCILE_ENABLED (default true), PORTFOLIO_RECONCILE_INTERVAL_SECS (default 300),
+    /// PORTFOLIO_RECONCILE_TOLERANCE_PCT (default 0.5)
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("PORTFOLIO_RECONCILE_ENABLED")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(true),
+            interval_secs: std::env::var("PORTFOLIO_RECONCILE_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_PORTFOLIO_RECONCILE_INTERVAL_SECS),
+            tolerance_pct: std::env::var("PORTFOLIO_RECONCILE_TOLERANCE_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|pct: &f64| *pct >= 0.0)
+                .unwrap_or(DEFAULT_PORTFOLIO_RECONCILE_TOLERANCE_PCT),
+        }
+    }
+    
+    /// Tracked and on-chain amounts differ by more than the tolerance
+    pub fn is_drift(&self, tracked: f64, onchain: f64) -> bool {
+        let difference = (onchain - tracked).abs();
+        difference > 0.0 && difference > tracked.max(onchain) * self.tolerance_pct / 100.0
+    }
+}
+
+/// One position whose tracked size didn't match the wallet
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionDiscrepancy {
+    pub symbol: String,
+    pub wallet: String,
+    pub tracked: f64,
+    pub onchain: f64,
+}
+
+/// A position the reconciliation left alone, and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedPosition {
+    pub symbol: String,
+    pub reason: String,
+}
+
+/// Latest portfolio reconciliation (kept on the engine, reported by /portfolio/reconciliation)
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioReconciliation {
+    pub checked_at: i64,
+    /// Positions compared against their wallet
+    pub checked: Vec<String>,
+    /// Corrected to the on-chain amount - tokens a wallet held untracked show with `tracked: 0`
+    pub discrepancies: Vec<PositionDiscrepancy>,
+    /// Not compared, with the reason
+    pub skipped: Vec<SkippedPosition>,
+    /// Symbols no wallet holds any more, with the price their tracked positions close at
+    pub emptied: Vec<(String, f64)>,
+}
+
+/// Mints the treasury and fees move - never positions
+fn is_quote_mint(engine: &TradingEngine, mint: &str) -> bool {
+    mint == crate::dex_aggregator::SOL_MINT || mint == crate::dex_aggregator::USDC_MINT || mint == engine.base_currency.mint()
+}
+
+/// Correct every tracked position to what its wallet holds on-chain, and start tracking tokens a wallet
+/// holds that no position accounts for. `onchain` maps wallet name -> its balances by mint (UI amounts),
+/// read at `read_at` with the engine unlocked. Skipped, with the reason: symbols no mint resolves for,
+/// SOL and base-currency positions (fees and the treasury move those), symbols with a swap the fill
+/// reconciler hasn't settled or that traded since `read_at`, and wallets that weren't read
+pub async fn reconcile_portfolio(
+    engine: &mut TradingEngine,
+    onchain: &HashMap<String, Result<HashMap<String, f64>, String>>,
+    read_at: i64,
+    config: PortfolioReconciliationConfig,
+) -> PortfolioReconciliation {
+    let now = Utc::now().timestamp();
+    let settling: HashSet<String> = match &engine.database {
+        Some(database) => database.lock().await.get_pending_executions()
+            .into_iter()
+            .map(|execution| execution.symbol)
+            .collect(),
+        None => HashSet::new(),
+    };
+    
+    let mut symbols: Vec<String> = engine.portfolio.keys().cloned().collect();
+    symbols.sort();
+    let mut reconciliation = PortfolioReconciliation {
+        checked_at: now,
+        checked: Vec::new(),
+        discrepancies: Vec::new(),
+        skipped: Vec::new(),
+        emptied: Vec::new(),
+    };
+    let skip = |reconciliation: &mut PortfolioReconciliation, symbol: &str, reason: String| {
+        log::debug!("🧮 Portfolio reconciliation skipped {}: {}", symbol, reason);
+        reconciliation.skipped.push(SkippedPosition { symbol: symbol.to_string(), reason });
+    };
+    // (wallet, mint) pairs a tracked position accounts for - whatever else a wallet holds is untracked
+    let mut accounted: HashSet<(String, String)> = HashSet::new();
+    for symbol in symbols {
+        let Some((mint, _)) = crate::dex_aggregator::resolve_token(&symbol) else {
+            skip(&mut reconciliation, &symbol, "no mint resolves for this symbol".to_string());
+            continue;
+        };
+        if is_quote_mint(engine, &mint) {
+            skip(&mut reconciliation, &symbol, "SOL and the base currency move with fees and the treasury".to_string());
+            continue;
+        }
+        let holdings = engine.symbol_wallets(&symbol);
+        for (wallet, _) in &holdings {
+            accounted.insert((wallet.clone(), mint.clone()));
+        }
+        if settling.contains(&symbol) {
+            skip(&mut reconciliation, &symbol, "a swap is still settling".to_string());
+            continue;
+        }
+        if engine.last_trade_at.get(&symbol).is_some_and(|traded_at| *traded_at >= read_at) {
+            skip(&mut reconciliation, &symbol, "traded while the balances were read".to_string());
+            continue;
+        }
+        // Each wallet holding the symbol is compared against its own token balance
+        let mut corrected = false;
+        for (wallet, tracked) in holdings {
+            let balances = match onchain.get(&wallet) {
+                Some(Ok(balances)) => balances,
+                Some(Err(e)) => {
+                    skip(&mut reconciliation, &symbol, format!("the {} wallet couldn't be read: {}", wallet, e));
+                    continue;
+                }
+                None => {
+                    skip(&mut reconciliation, &symbol, format!("the {} wallet isn't connected", wallet));
+                    continue;
+                }
+            };
+            let held = balances.get(&mint).copied().unwrap_or(0.0);
+            reconciliation.checked.push(symbol.clone());
+            if !config.is_drift(tracked, held) {
+                continue;
+            }
+            log::warn!("🧮 Portfolio drift on {} ({} wallet): tracked {:.6}, on-chain {:.6} - correcting to on-chain",
+                       symbol, wallet, tracked, held);
+            engine.correct_position(&symbol, &wallet, held).await;
+            corrected = true;
+            reconciliation.discrepancies.push(PositionDiscrepancy { symbol: symbol.clone(), wallet, tracked, onchain: held });
+        }
+        if corrected && engine.portfolio.get(&symbol).copied().unwrap_or(0.0) <= 0.0 {
+            let price = engine.last_price(&symbol)
+                .or_else(|| engine.cost_basis.get(&symbol).map(|basis| basis.average_entry))
+                .unwrap_or(0.0);
+            reconciliation.emptied.push((symbol, price));
+        }
+    }
+    
+    // UNTRACKED: Tokens that arrived outside the bot (transfers, swaps that landed after being given up on)
+    let mut wallets: Vec<&String> = onchain.keys().collect();
+    wallets.sort();
+    for wallet in wallets {
+        let Some(Ok(balances)) = onchain.get(wallet) else { continue };
+        let mut mints: Vec<(&String, &f64)> = balances.iter().collect();
+        mints.sort_by(|a, b| a.0.cmp(b.0));
+        for (mint, held) in mints {
+            if *held <= 0.0 || is_quote_mint(engine, mint) || accounted.contains(&(wallet.clone(), mint.clone())) {
+                continue;
+            }
+            let symbol = crate::dex_aggregator::symbol_for_mint(mint).map(str::to_string).unwrap_or_else(|| mint.clone());
+            if settling.contains(&symbol) || engine.last_trade_at.get(&symbol).is_some_and(|traded_at| *traded_at >= read_at) {
+                skip(&mut reconciliation, &symbol, "untracked tokens, but the symbol is trading".to_string());
+                continue;
+            }
+            log::warn!("🧮 {} wallet holds {:.6} untracked {} - tracking it", wallet, held, symbol);
+            engine.correct_position(&symbol, wallet, *held).await;
+            reconciliation.checked.push(symbol.clone());
+            reconciliation.discrepancies.push(PositionDiscrepancy { symbol, wallet: wallet.clone(), tracked: 0.0, onchain: *held });
+        }
+    }
+    engine.portfolio_reconciliation = Some(reconciliation.clone());
+    reconciliation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trading_engine::{TradeAction, TradingSignal};
+
+    #[tokio::test]
+    async fn test_flatten_all_closes_positions_and_halts() {
+        let engine = Arc::new(Mutex::new(TradingEngine::new_default()));
+        let trading_enabled = Arc::new(Mutex::new(true));
+        let dry_run = Arc::new(Mutex::new(true));
+        {
+            let mut e = engine.lock().await;
+            e.initial_balance = 10.0;
+            e.current_balance = 10.0;
+            let buy = TradingSignal {
+                id: "buy-1".to_string(),
+                action: TradeAction::Buy,
+                symbol: "SOL/USDC".to_string(),
+                price: 1.0,
+                confidence: 0.9,
+                size: 0.5,
+                stop_loss: 0.9,
+                take_profit: 1.2,
+                timestamp: Utc::now().timestamp(),
+                ..Default::default()
+            };
+            assert!(e.execute_trade(&buy, Some(&trading_enabled), Some(&dry_run)).await);
+        }
+
+        let report = flatten_all(&engine, &trading_enabled, true, "test").await;
+        assert_eq!(report.closes.len(), 1);
+        assert!(report.closes[0].success);
+        assert_eq!(report.positions_remaining, 0);
+        assert!((report.final_balance - 10.0).abs() < 1e-9);
+        assert!(!*trading_enabled.lock().await);
+
+        // Halted engine refuses new trades even in dry-run
+        let mut e = engine.lock().await;
+        assert!(e.halted);
+        let buy = TradingSignal {
+            id: "buy-2".to_string(),
+            action: TradeAction::Buy,
+            symbol: "SOL/USDC".to_string(),
+            price: 1.0,
+            confidence: 0.9,
+            size: 0.5,
+            stop_loss: 0.9,
+            take_profit: 1.2,
+            timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        };
+        assert!(!e.execute_trade(&buy, Some(&trading_enabled), Some(&dry_run)).await);
+    }
+
+    #[tokio::test]
+    async fn test_drawdown_guard_cooldown_and_resume() {
+        let engine = Arc::new(Mutex::new(TradingEngine::new_default()));
+        let trading_enabled = Arc::new(Mutex::new(true));
+        let config = DrawdownGuardConfig { hard_limit_pct: 25.0, cooldown_secs: 600 };
+        let risk_manager = engine.lock().await.risk_manager.clone();
+        let max_drawdown = risk_manager.lock().await.max_drawdown;
+
+        // Drawdown just past the soft limit disables trading with a cooldown
+        {
+            let mut risk = risk_manager.lock().await;
+            risk.peak_capital = 10000.0;
+            risk.current_capital = 10000.0 * (1.0 - max_drawdown - 0.01);
+        }
+        let action = enforce_drawdown_limits(&engine, &trading_enabled, true, config).await;
+        let lockout = match action {
+            Some(DrawdownAction::Disabled(lockout)) => lockout,
+            other => panic!("expected soft disable, got {:?}", other),
+        };
+        assert!(!*trading_enabled.lock().await);
+        assert!(!lockout.hard);
+        assert!(lockout.reenable_block(lockout.triggered_at).is_some());
+        assert!(lockout.reenable_block(lockout.cooldown_until).is_none());
+        assert!(!engine.lock().await.halted);
+
+        // Recovered, but still cooling down - stays off
+        risk_manager.lock().await.current_capital = 10000.0;
+        assert!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await.is_none());
+        assert!(!*trading_enabled.lock().await);
+
+        // Cooldown over - resumes automatically
+        engine.lock().await.drawdown_lockout.as_mut().unwrap().cooldown_until = 0;
+        assert!(matches!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await,
+                         Some(DrawdownAction::Resumed(_))));
+        assert!(*trading_enabled.lock().await);
+        assert!(engine.lock().await.drawdown_lockout.is_none());
+
+        // Hard ceiling flattens and never resumes on its own
+        risk_manager.lock().await.current_capital = 7000.0;
+        assert!(matches!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await,
+                         Some(DrawdownAction::Flattened(..))));
+        assert!(engine.lock().await.halted);
+        risk_manager.lock().await.current_capital = 10000.0;
+        engine.lock().await.drawdown_lockout.as_mut().unwrap().cooldown_until = 0;
+        assert!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await.is_none());
+        assert!(!*trading_enabled.lock().await);
+    }
+
+    #[tokio::test]
+    async fn test_drawdown_guard_soft_lockout_escalates_to_hard() {
+        let engine = Arc::new(Mutex::new(TradingEngine::new_default()));
+        let trading_enabled = Arc::new(Mutex::new(true));
+        let config = DrawdownGuardConfig { hard_limit_pct: 25.0, cooldown_secs: 600 };
+        let risk_manager = engine.lock().await.risk_manager.clone();
+        let max_drawdown = risk_manager.lock().await.max_drawdown;
+        {
+            let mut risk = risk_manager.lock().await;
+            risk.peak_capital = 10000.0;
+            risk.current_capital = 10000.0 * (1.0 - max_drawdown - 0.01);
+        }
+        assert!(matches!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await,
+                         Some(DrawdownAction::Disabled(_))));
+        assert!(!*trading_enabled.lock().await);
+
+        // Still falling while soft-locked (trading off) - the hard ceiling flattens anyway
+        risk_manager.lock().await.current_capital = 7000.0;
+        assert!(matches!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await,
+                         Some(DrawdownAction::Flattened(..))));
+        assert!(engine.lock().await.halted);
+        assert!(engine.lock().await.drawdown_lockout.as_ref().unwrap().hard);
+        assert!(enforce_drawdown_limits(&engine, &trading_enabled, true, config).await.is_none());
+    }
+
+    #[test]
+    fn test_balance_anomaly_detector() {
+        let mut engine = TradingEngine::new_default();
+        let config = BalanceAnomalyConfig { enabled: true, max_drop_pct: 2.0, tolerance_sol: 0.01, base_currency: BaseCurrency::Sol };
+        // Every flow below is booked before the reading it is compared against
+        let reading = || Utc::now().timestamp_millis() + 1;
+
+        // First reading is the baseline
+        assert!(reconcile_treasury_balance(&mut engine, 10.0, reading(), None, config).is_none());
+
+        // A tracked buy explains the drop, within the tolerance for fees
+        engine.record_treasury_flow(-1.0);
+        assert!(reconcile_treasury_balance(&mut engine, 8.995, reading(), None, config).is_none());
+        let last = engine.balance_reconciliation.clone().unwrap();
+        assert!((last.expected_balance - 9.0).abs() < 1e-9);
+        assert!(!last.anomaly);
+        assert!(engine.pending_treasury_flows.is_empty());
+
+        // SOL leaving without a tracked trade or withdrawal
+        let anomaly = reconcile_treasury_balance(&mut engine, 8.5, reading(), None, config).unwrap();
+        assert!(anomaly.anomaly);
+        assert!((anomaly.discrepancy + 0.495).abs() < 1e-9);
+        assert!(anomaly.message().contains("unexplained"));
+
+        // Recorded deposits and gains are never anomalies
+        engine.record_treasury_flow(1.0);
+        assert!(reconcile_treasury_balance(&mut engine, 9.5, reading(), None, config).is_none());
+        assert!(reconcile_treasury_balance(&mut engine, 12.0, reading(), None, config).is_none());
+    }
+
+    #[test]
+    fn test_balance_anomaly_waits_for_realized_fills() {
+        let mut engine = TradingEngine::new_default();
+        let config = BalanceAnomalyConfig { enabled: true, max_drop_pct: 2.0, tolerance_sol: 0.01, base_currency: BaseCurrency::Sol };
+        let reading = || Utc::now().timestamp_millis() + 1;
+        assert!(reconcile_treasury_balance(&mut engine, 10.0, reading(), None, config).is_none());
+
+        // A sent sell isn't expected to have paid out yet, and a sent buy may already have spent
+        let sell = solana_sdk::signature::Signature::new_unique().to_string();
+        let buy = solana_sdk::signature::Signature::new_unique().to_string();
+        engine.record_swap_flow(&sell, UnsettledSwapFlow { is_buy: false, estimated_sol: 2.0, fee_sol: 0.0 });
+        engine.record_swap_flow(&buy, UnsettledSwapFlow { is_buy: true, estimated_sol: 1.0, fee_sol: 0.001 });
+        assert!(engine.pending_treasury_flows.is_empty());
+        assert!(reconcile_treasury_balance(&mut engine, 9.0, reading(), None, config).is_none());
+
+        // The sell fills short and the buy fails on-chain - only what settled is expected
+        assert!(engine.settle_swap_flow(&sell, Some(1.5)));
+        assert!(engine.settle_swap_flow(&buy, None));
+        let last = reconcile_treasury_balance(&mut engine, 10.5, reading(), None, config);
+        assert!(last.is_none());
+        let last = engine.balance_reconciliation.clone().unwrap();
+        assert!((last.tracked_flows - 1.499).abs() < 1e-9);
+        assert!(engine.unsettled_treasury_flows.is_empty());
+
+        // A withdrawal booked while the balance was being read may or may not be in the reading
+        let read_started_at_ms = Utc::now().timestamp_millis() - 1;
+        engine.record_treasury_flow(-3.0);
+        assert!(reconcile_treasury_balance(&mut engine, 7.5, read_started_at_ms, None, config).is_none());
+        assert!(reconcile_treasury_balance(&mut engine, 7.5, reading(), None, config).is_none());
+        // ...but once placed it excuses nothing further
+        assert!(reconcile_treasury_balance(&mut engine, 4.5, reading(), None, config).unwrap().anomaly);
+        
+        // With a USDC base the 0.01 SOL tolerance is 1.5 USDC at 150 - a 1 USDC drop on 10 USDC stays quiet
+        let usdc = BalanceAnomalyConfig { base_currency: BaseCurrency::Usdc, ..config };
+        assert!(!usdc.is_anomalous(10.0, 1.0, Some(150.0)));
+        assert!(usdc.is_anomalous(10.0, 2.0, Some(150.0)));
+    }
+
+    #[tokio::test]
+    async fn test_funding_guard_pauses_until_funded() {
+        let engine = Arc::new(Mutex::new(TradingEngine::new_default()));
+        let trading_enabled = Arc::new(Mutex::new(true));
+        let config = FundingGuardConfig { min_balance_sol: 0.05, check_interval_secs: 60, base_currency: BaseCurrency::Sol };
+
+        // Dry-run never needs funds
+        assert!(enforce_funding_minimum(&engine, &trading_enabled, true, Ok(0.0), None, config).await.is_none());
+        assert!(*trading_enabled.lock().await);
+
+        // Drained treasury pauses once, later checks don't re-alert
+        let lockout = match enforce_funding_minimum(&engine, &trading_enabled, false, Ok(0.01), None, config).await {
+            Some(FundingAction::Paused(lockout)) => lockout,
+            other => panic!("expected pause, got {:?}", other),
+        };
+        assert_eq!(lockout.balance_sol, Some(0.01));
+        assert!(lockout.message().contains("POST /trading-toggle"));
+        assert!(!*trading_enabled.lock().await);
+        assert!(enforce_funding_minimum(&engine, &trading_enabled, false, Err("rpc down".to_string()), None, config).await.is_none());
+        assert_eq!(engine.lock().await.funding_lockout.as_ref().unwrap().balance_sol, None);
+
+        // Pre-trade: real trades are refused while the lockout stands
+        let buy = TradingSignal {
+            id: "buy-funding".to_string(),
+            action: TradeAction::Buy,
+            symbol: "SOL/USDC".to_string(),
+            price: 1.0,
+            confidence: 0.9,
+            size: 0.5,
+            stop_loss: 0.9,
+            take_profit: 1.2,
+            timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        };
+        let real = Arc::new(Mutex::new(false));
+        assert!(!engine.lock().await.execute_trade(&buy, None, Some(&real)).await);
+
+        // Funded again - lockout clears but trading waits for the operator
+        assert!(matches!(enforce_funding_minimum(&engine, &trading_enabled, false, Ok(1.0), None, config).await,
+                         Some(FundingAction::Funded(_))));
+        assert!(engine.lock().await.funding_lockout.is_none());
+        assert!(!*trading_enabled.lock().await);
+        assert!(enforce_funding_minimum(&engine, &trading_enabled, false, Ok(1.0), None, config).await.is_none());
+        
+        // With a USDC base the SOL minimum converts at the SOL price, and can't be met without one
+        let usdc = FundingGuardConfig { base_currency: BaseCurrency::Usdc, ..config };
+        assert!(usdc.shortfall(&Ok(5.0), Some(150.0)).unwrap().contains("below the 7.500000 USDC minimum"));
+        assert!(usdc.shortfall(&Ok(10.0), Some(150.0)).is_none());
+        assert!(usdc.shortfall(&Ok(10.0), None).unwrap().contains("no SOL price"));
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_reconciliation_corrects_drift() {
+        let mut engine = TradingEngine::new_default();
+        let config = PortfolioReconciliationConfig { enabled: true, interval_secs: 300, tolerance_pct: 0.5 };
+        let ray_mint = crate::dex_aggregator::resolve_token("RAY").unwrap().0;
+        let eth_mint = crate::dex_aggregator::resolve_token("ETH").unwrap().0;
+        engine.portfolio.insert("RAY".to_string(), 100.0);
+        engine.portfolio.insert("ETH".to_string(), 2.0);
+        engine.portfolio.insert("SOL".to_string(), 5.0);
+        engine.portfolio.insert("UNKNOWN".to_string(), 1.0);
+        engine.cost_basis.entry("RAY".to_string()).or_default().buy(100.0, 2.0, 0.0);
+        
+        // A partial fill left fewer RAY; ETH is within the tolerance; SOL is never touched
+        // The wallet also holds BTC nothing tracks
+        let btc_mint = crate::dex_aggregator::resolve_token("BTC").unwrap().0;
+        let wallet = HashMap::from([(ray_mint, 60.0), (eth_mint, 1.995), (btc_mint, 0.4)]);
+        let onchain = HashMap::from([(crate::wallet::DEFAULT_WALLET_NAME.to_string(), Ok(wallet))]);
+        let read_at = Utc::now().timestamp();
+        let result = reconcile_portfolio(&mut engine, &onchain, read_at, config).await;
+        assert_eq!(result.checked, vec!["ETH".to_string(), "RAY".to_string(), "BTC".to_string()]);
+        assert_eq!(result.discrepancies.len(), 2);
+        assert_eq!(result.discrepancies[0].symbol, "RAY");
+        assert_eq!(result.discrepancies[0].tracked, 100.0);
+        assert_eq!(result.discrepancies[1].symbol, "BTC");
+        assert_eq!(result.discrepancies[1].tracked, 0.0);
+        assert_eq!(engine.portfolio["BTC"], 0.4);
+        assert_eq!(result.skipped, vec![
+            SkippedPosition { symbol: "SOL".to_string(), reason: "SOL and the base currency move with fees and the treasury".to_string() },
+            SkippedPosition { symbol: "UNKNOWN".to_string(), reason: "no mint resolves for this symbol".to_string() },
+        ]);
+        assert_eq!(engine.portfolio["RAY"], 60.0);
+        assert_eq!(engine.cost_basis["RAY"].size, 60.0);
+        assert_eq!(engine.cost_basis["RAY"].average_entry, 2.0);
+        assert_eq!(engine.portfolio["ETH"], 2.0);
+        assert_eq!(engine.portfolio["SOL"], 5.0);
+        assert!(engine.portfolio_reconciliation.is_some());
+        
+        // A wallet that couldn't be read skips its positions instead of zeroing them - with the reason
+        let onchain = HashMap::from([(crate::wallet::DEFAULT_WALLET_NAME.to_string(), Err("RPC timeout".to_string()))]);
+        let result = reconcile_portfolio(&mut engine, &onchain, read_at, config).await;
+        assert!(result.discrepancies.is_empty());
+        let skipped: Vec<&str> = result.skipped.iter().map(|skipped| skipped.symbol.as_str()).collect();
+        assert_eq!(skipped, vec!["BTC", "ETH", "RAY", "SOL", "UNKNOWN"]);
+        assert!(result.skipped[0].reason.contains("RPC timeout"));
+        assert_eq!(engine.portfolio["RAY"], 60.0);
+        
+        // A position that traded since the balances were read isn't corrected to the stale reading
+        engine.last_trade_at.insert("RAY".to_string(), read_at + 1);
+        let onchain = HashMap::from([(crate::wallet::DEFAULT_WALLET_NAME.to_string(), Ok(HashMap::new()))]);
+        let result = reconcile_portfolio(&mut engine, &onchain, read_at + 1, config).await;
+        assert!(result.skipped.iter().any(|skipped| skipped.symbol == "RAY" && skipped.reason.contains("traded")));
+        assert_eq!(engine.portfolio["RAY"], 60.0);
+        assert_eq!(result.emptied.iter().map(|(symbol, _)| symbol.as_str()).collect::<Vec<_>>(), vec!["BTC", "ETH"]);
+    }
+}
@@ -0,0 +1,171 @@
+//! Provider Registry
+//! Runtime on/off switch and check interval for each specialized provider
+//! A disabled provider keeps its task, RL agent and history - it just skips signal generation
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Faster than this burns the DexScreener / Jupiter rate limits
+pub const MIN_CHECK_INTERVAL_SECS: u64 = 5;
+
+/// Slower than this risks the watchdog (600s default timeout) restarting an idle provider as stalled
+pub const MAX_CHECK_INTERVAL_SECS: u64 = 300;
+
+/// Current switch position of one provider, for the API
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderState {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub enabled: bool,
+    pub changed_at: Option<i64>,
+    pub reason: Option<String>, // Why it was last switched
+    pub skipped_cycles: u64,    // Cycles skipped while disabled
+    pub check_interval_secs: u64, // Pause between signal generation cycles
+}
+
+/// Cheap, cloneable handle shared between the providers and the API
+#[derive(Debug, Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Arc<Mutex<HashMap<String, ProviderState>>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider (enabled) - re-registering keeps its current switch position and interval
+    pub async fn register(&self, provider_id: &str, provider_name: &str, check_interval_secs: u64) {
+        self.providers.lock().await
+            .entry(provider_id.to_string())
+            .or_insert_with(|| ProviderState {
+                provider_id: provider_id.to_string(),
+                provider_name: provider_name.to_string(),
+                enabled: true,
+                changed_at: None,
+                reason: None,
+                skipped_cycles: 0,
+                check_interval_secs,
+            });
+    }
+
+    /// Unregistered providers are treated as enabled
+    pub async fn is_enabled(&self, provider_id: &str) -> bool {
+        self.providers.lock().await
+            .get(provider_id)
+            .is_none_or(|state| state.enabled)
+    }
+
+    /// Called at the top of each provider cycle - false (and a skipped cycle counted) while disabled
+    pub async fn should_run(&self, provider_id: &str) -> bool {
+        let mut providers = self.providers.lock().await;
+        match providers.get_mut(provider_id) {
+            Some(state) if !state.enabled => {
+                state.skipped_cycles += 1;
+                false
+            }
+            _ => true,
+        }
+    }
+
+    pub async fn set_enabled(&self, provider_id: &str, enabled: bool, reason: Option<String>) -> Result<ProviderState, String> {
+        let mut providers = self.providers.lock().await;
+        let state = providers.get_mut(provider_id)
+            .ok_or_else(|| format!("Unknown provider: {}", provider_id))?;
+        if state.enabled != enabled {
+            state.enabled = enabled;
+            state.changed_at = Some(chrono::Utc::now().timestamp());
+            state.reason = reason;
+            log::info!("{} Provider {} {}{}", if enabled { "▶️" } else { "⏸️" }, provider_id,
+                      if enabled { "enabled" } else { "disabled" },
+                      state.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default());
+        }
+        Ok(state.clone())
+    }
+
+    pub fn validate_check_interval(secs: u64) -> Result<(), String> {
+        if !(MIN_CHECK_INTERVAL_SECS..=MAX_CHECK_INTERVAL_SECS).contains(&secs) {
+            return Err(format!("Check interval must be between {}s and {}s, got {}s",
+                              MIN_CHECK_INTERVAL_SECS, MAX_CHECK_INTERVAL_SECS, secs));
+        }
+        Ok(())
+    }
+
+    /// Interval the provider waits between cycles - None for unregistered providers
+    pub async fn check_interval_secs(&self, provider_id: &str) -> Option<u64> {
+        self.providers.lock().await
+            .get(provider_id)
+            .map(|state| state.check_interval_secs)
+    }
+
+    /// Change how often a provider checks for signals - applies after its current cycle
+    pub async fn set_check_interval(&self, provider_id: &str, secs: u64) -> Result<ProviderState, String> {
+        Self::validate_check_interval(secs)?;
+        let mut providers = self.providers.lock().await;
+        let state = providers.get_mut(provider_id)
+            .ok_or_else(|| format!("Unknown provider: {}", provider_id))?;
+        state.check_interval_secs = secs;
+        log::info!("⏱️ Provider {} check interval set to {}s", provider_id, secs);
+        Ok(state.clone())
+    }
+
+    /// Flip a provider's switch
+    pub async fn toggle(&self, provider_id: &str, reason: Option<String>) -> Result<ProviderState, String> {
+        let enabled = self.is_enabled(provider_id).await;
+        self.set_enabled(provider_id, !enabled, reason).await
+    }
+
+    pub async fn list(&self) -> Vec<ProviderState> {
+        let mut providers: Vec<ProviderState> = self.providers.lock().await.values().cloned().collect();
+        providers.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+        providers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_toggle_skips_cycles_without_unregistering() {
+        let registry = ProviderRegistry::new();
+        let provider_handle = registry.clone();
+        registry.register("jupiter_memecoin_trader", "Jupiter Memecoin Trader", 60).await;
+
+        assert!(provider_handle.should_run("jupiter_memecoin_trader").await);
+        let state = registry.toggle("jupiter_memecoin_trader", Some("bad signals".to_string())).await.unwrap();
+        assert!(!state.enabled);
+        assert!(!provider_handle.should_run("jupiter_memecoin_trader").await);
+        assert!(!provider_handle.should_run("jupiter_memecoin_trader").await);
+
+        // Re-registering (e.g. a supervised restart) keeps it disabled
+        registry.register("jupiter_memecoin_trader", "Jupiter Memecoin Trader", 60).await;
+        let state = registry.toggle("jupiter_memecoin_trader", None).await.unwrap();
+        assert!(state.enabled);
+        assert_eq!(state.skipped_cycles, 2);
+        assert!(provider_handle.should_run("jupiter_memecoin_trader").await);
+
+        assert!(registry.toggle("unknown", None).await.is_err());
+        assert!(registry.is_enabled("unknown").await);
+        assert_eq!(registry.list().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_interval_is_bounded_and_survives_reregistration() {
+        let registry = ProviderRegistry::new();
+        registry.register("memecoin_monitor", "Memecoin Monitor", 20).await;
+        assert_eq!(registry.check_interval_secs("memecoin_monitor").await, Some(20));
+
+        assert!(registry.set_check_interval("memecoin_monitor", MIN_CHECK_INTERVAL_SECS - 1).await.is_err());
+        assert!(registry.set_check_interval("memecoin_monitor", MAX_CHECK_INTERVAL_SECS + 1).await.is_err());
+        assert!(registry.set_check_interval("unknown", 30).await.is_err());
+
+        let state = registry.set_check_interval("memecoin_monitor", 10).await.unwrap();
+        assert_eq!(state.check_interval_secs, 10);
+        registry.register("memecoin_monitor", "Memecoin Monitor", 20).await;
+        assert_eq!(registry.check_interval_secs("memecoin_monitor").await, Some(10));
+        assert_eq!(registry.check_interval_secs("unknown").await, None);
+    }
+}
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::error::Error;
+use crate::error_handling::AppError;
 use std::sync::Arc;
 use crate::http_client::SharedHttpClient;
 use chrono::Utc;
@@ -17,12 +18,35 @@ pub struct TokenLaunch {
     pub symbol: String,
     pub uri: String,
     pub creator: String,
+    /// Unix seconds the token was created - 0 when the source didn't report it
     pub created_timestamp: i64,
     pub market_cap: f64,
     pub reply_count: u32,
     pub is_currently_live: bool,
     pub king_of_the_hill_timestamp: Option<i64>,
     pub bonding_curve: String,
+    /// Holder stats - only some pump.fun responses carry them, `None` when not reported
+    #[serde(default)]
+    pub holder_count: Option<u32>,
+    /// Share of supply held by the largest holder (percent)
+    #[serde(default)]
+    pub top_holder_pct: Option<f64>,
+}
+
+impl TokenLaunch {
+    /// Hours since creation - None when the creation time is unknown
+    pub fn age_hours(&self, now: i64) -> Option<f64> {
+        (self.created_timestamp > 0).then(|| (now - self.created_timestamp).max(0) as f64 / 3600.0)
+    }
+}
+
+/// Creation time in unix seconds from a pump.fun coin object - the API reports milliseconds, some
+/// mirrors seconds
+fn created_timestamp_secs(json: &serde_json::Value) -> Option<i64> {
+    ["created_timestamp", "createdTimestamp", "createdAt"].iter()
+        .find_map(|key| json.get(*key).and_then(|v| v.as_i64()))
+        .filter(|timestamp| *timestamp > 0)
+        .map(|timestamp| if timestamp > 10_000_000_000 { timestamp / 1000 } else { timestamp })
 }
 
 /// Comprehensive token safety validation result
@@ -109,6 +133,67 @@ pub struct MemeTradeSignal {
     pub timestamp: i64,
 }
 
+/// First reconnect delay after the pump.fun WebSocket drops - doubles per consecutive failure
+pub const DEFAULT_WS_INITIAL_BACKOFF_MS: u64 = 1000;
+/// Reconnect delay cap (PUMPFUN_WS_MAX_BACKOFF_SECS)
+pub const DEFAULT_WS_MAX_BACKOFF_SECS: u64 = 60;
+
+/// Exponential reconnect backoff with jitter - the delay doubles per consecutive failure up to `max`,
+/// and a random 50-100% of it is slept so restarts don't all reconnect in lockstep
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    initial: std::time::Duration,
+    max: std::time::Duration,
+    attempt: u32,
+}
+
+impl ReconnectBackoff {
+    pub fn new(initial: std::time::Duration, max: std::time::Duration) -> Self {
+        Self { initial, max: max.max(initial), attempt: 0 }
+    }
+    
+    /// PUMPFUN_WS_MAX_BACKOFF_SECS (default 60)
+    pub fn from_env() -> Self {
+        let max_secs = std::env::var("PUMPFUN_WS_MAX_BACKOFF_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_WS_MAX_BACKOFF_SECS);
+        Self::new(
+            std::time::Duration::from_millis(DEFAULT_WS_INITIAL_BACKOFF_MS),
+            std::time::Duration::from_secs(max_secs),
+        )
+    }
+    
+    /// Consecutive attempts since the last reset
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+    
+    /// Delay before the next attempt - `jitter` in [0, 1) picks where in the 50-100% band it lands
+    pub fn next_delay(&mut self, jitter: f64) -> std::time::Duration {
+        let exponent = self.attempt.min(20);
+        let base = self.initial.saturating_mul(1u32 << exponent).min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+        base.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+    }
+    
+    /// The connection delivered data - the next drop starts again from the initial delay
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Live state of the pump.fun WebSocket stream, reported on /health
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebSocketStatus {
+    pub connected: bool,
+    pub url: Option<String>,
+    pub connected_since: Option<i64>,
+    pub reconnect_attempts: u32, // Consecutive attempts since the stream last delivered data
+    pub total_reconnects: u64,
+    pub last_error: Option<String>,
+}
+
 /// PumpFun API client with Moralis integration for real token prices
 /// Moralis API docs: https://docs.moralis.com/web3-data-api/solana/tutorials/get-pump-fun-token-prices
 /// Enhanced with WebSocket and page scraping for real-time opportunities
@@ -120,7 +205,7 @@ pub struct PumpFunClient {
     circuit_breaker: Option<Arc<tokio::sync::Mutex<crate::error_handling::CircuitBreaker>>>, // Circuit breaker for API protection
     pumpfun_ws_url: String, // WebSocket URL for real-time updates
     pumpfun_page_url: String, // Page URL for scraping
-    ws_connected: Arc<tokio::sync::Mutex<bool>>, // WebSocket connection status
+    ws_status: Arc<tokio::sync::Mutex<WebSocketStatus>>, // WebSocket connection status
 }
 
 impl SafetyConfig {
@@ -194,7 +279,7 @@ impl PumpFunClient {
             // Pump.fun WebSocket and page URLs
             pumpfun_ws_url: "wss://pump.fun".to_string(), // WebSocket endpoint (may need adjustment)
             pumpfun_page_url: "https://pump.fun/?sort=last_trade_timestamp".to_string(),
-            ws_connected: Arc::new(tokio::sync::Mutex::new(false)),
+            ws_status: Arc::new(tokio::sync::Mutex::new(WebSocketStatus::default())),
         }
     }
     
@@ -210,7 +295,7 @@ impl PumpFunClient {
     /// Scrape pump.fun page for real-time trading opportunities
     /// Parses the page at https://pump.fun/?sort=last_trade_timestamp
     /// Extracts token data: name, symbol, price, volume, market cap, last trade timestamp
-    pub async fn scrape_trading_opportunities(&self) -> Result<Vec<TokenLaunch>, Box<dyn Error>> {
+    pub async fn scrape_trading_opportunities(&self) -> Result<Vec<TokenLaunch>, AppError> {
         log::info!("🔍 Scraping pump.fun page for trading opportunities...");
         
         // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of direct calls
@@ -218,7 +303,7 @@ impl PumpFunClient {
         // CRITICAL IMPROVEMENT #4: Detect and map reqwest timeout errors
         // CRITICAL IMPROVEMENT #2: Use map_http_status_to_error() for proper error type mapping
         // CRITICAL IMPROVEMENT #5: Use conservative retry config for non-critical scraping operations
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
+        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, map_http_status_to_error};
         
         let url = self.pumpfun_page_url.clone();
         let client = self.client.clone();
@@ -227,7 +312,7 @@ impl PumpFunClient {
         let retry_config = RetryConfig::conservative();
         
         // CRITICAL IMPROVEMENT #3: Wrap retry logic with circuit breaker .call() method
-        let result: Result<reqwest::Response, TradingError> = if let Some(ref cb) = self.circuit_breaker {
+        let result: Result<reqwest::Response, AppError> = if let Some(ref cb) = self.circuit_breaker {
             let cb_clone = cb.clone();
             // Scope the lock so guard is dropped after await completes
             {
@@ -244,17 +329,7 @@ impl PumpFunClient {
                                     .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
                                     .send()
                                     .await
-                                    .map_err(|e| {
-                                        let error_str = e.to_string();
-                                        // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                                        if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                            TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                        } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                            TradingError::NetworkError(format!("Network error: {}", e))
-                                        } else {
-                                            TradingError::ApiError(format!("Request failed: {}", e))
-                                        }
-                                    })
+                                    .map_err(AppError::from)
                             })
                         },
                         retry_config.clone(),
@@ -275,17 +350,7 @@ impl PumpFunClient {
                             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
                             .send()
                             .await
-                            .map_err(|e| {
-                                let error_str = e.to_string();
-                                // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                                if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                    TradingError::TimeoutError(format!("Request timeout: {}", e))
-                                } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                    TradingError::NetworkError(format!("Network error: {}", e))
-                                } else {
-                                    TradingError::ApiError(format!("Request failed: {}", e))
-                                }
-                            })
+                            .map_err(AppError::from)
                     })
                 },
                 retry_config,
@@ -300,7 +365,7 @@ impl PumpFunClient {
             let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             let mapped_error = map_http_status_to_error(status, error_text);
-            return Err(format!("{}", mapped_error).into());
+            return Err(mapped_error);
         }
         
         let html = response.text().await
@@ -316,7 +381,7 @@ impl PumpFunClient {
     
     /// Parse pump.fun page HTML to extract token information
     /// Looks for embedded JSON data or token cards in the HTML
-    async fn parse_pumpfun_page(&self, html: &str) -> Result<Vec<TokenLaunch>, Box<dyn Error>> {
+    async fn parse_pumpfun_page(&self, html: &str) -> Result<Vec<TokenLaunch>, AppError> {
         use scraper::{Html, Selector};
         
         let mut tokens = Vec::new();
@@ -449,11 +514,7 @@ impl PumpFunClient {
             .or_else(|| json.get("marketCap").and_then(|v| v.as_f64()))
             .unwrap_or(0.0);
         
-        let created_timestamp = json.get("created_timestamp")
-            .and_then(|v| v.as_i64())
-            .or_else(|| json.get("createdTimestamp").and_then(|v| v.as_i64()))
-            .or_else(|| json.get("createdAt").and_then(|v| v.as_i64()))
-            .unwrap_or(chrono::Utc::now().timestamp());
+        let created_timestamp = created_timestamp_secs(json).unwrap_or(0);
         
         Some(TokenLaunch {
             mint,
@@ -480,6 +541,11 @@ impl PumpFunClient {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string(),
+            holder_count: ["holder_count", "holderCount", "holders"].iter()
+                .find_map(|key| json.get(*key).and_then(|v| v.as_u64()))
+                .map(|v| v as u32),
+            top_holder_pct: ["top_holder_pct", "topHolderPct", "top_holder_percentage"].iter()
+                .find_map(|key| json.get(*key).and_then(|v| v.as_f64())),
         })
     }
     
@@ -492,7 +558,7 @@ impl PumpFunClient {
     }
     
     /// Fetch tokens from pump.fun API endpoint as fallback
-    async fn fetch_tokens_from_api(&self) -> Result<Vec<TokenLaunch>, Box<dyn Error>> {
+    async fn fetch_tokens_from_api(&self) -> Result<Vec<TokenLaunch>, AppError> {
         // Try pump.fun API endpoints
         let api_endpoints = vec![
             format!("{}/coins", self.api_url),
@@ -522,6 +588,7 @@ impl PumpFunClient {
     
     /// Connect to pump.fun WebSocket for real-time token updates
     /// Listens for new token launches, price updates, and trading activity
+    /// Dropped or failed connections are retried with exponential backoff plus jitter (see ReconnectBackoff)
     pub fn start_websocket_listener(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
         use tokio_tungstenite::{connect_async, tungstenite::Message};
         use futures::{SinkExt, StreamExt};
@@ -539,10 +606,11 @@ impl PumpFunClient {
         ];
         
         let client_clone = self.clone();
-        let ws_connected_clone = self.ws_connected.clone();
+        let ws_status = self.ws_status.clone();
         
         tokio::spawn(async move {
             let mut current_url_idx = 0;
+            let mut backoff = ReconnectBackoff::from_env();
             
             loop {
                 let ws_url = &ws_urls[current_url_idx % ws_urls.len()];
@@ -554,12 +622,25 @@ impl PumpFunClient {
                         
                         // Mark as connected
                         {
-                            let mut connected = ws_connected_clone.lock().await;
-                            *connected = true;
+                            let mut status = ws_status.lock().await;
+                            status.connected = true;
+                            status.url = Some(ws_url.clone());
+                            status.connected_since = Some(Utc::now().timestamp());
+                            status.last_error = None;
                         }
                         
+                        // Backoff only resets once the stream delivers something - a server that accepts and
+                        // immediately drops us keeps escalating instead of being hammered every second
+                        let mut received = false;
+                        let mut disconnect_reason = "stream ended".to_string();
+                        
                         // Listen for messages
                         while let Some(msg) = read.next().await {
+                            if !received && msg.is_ok() {
+                                received = true;
+                                backoff.reset();
+                                ws_status.lock().await.reconnect_attempts = 0;
+                            }
                             match msg {
                                 Ok(Message::Text(text)) => {
                                     // Parse WebSocket message
@@ -577,39 +658,53 @@ impl PumpFunClient {
                                     // Respond to ping
                                     if let Err(e) = write.send(Message::Pong(data)).await {
                                         log::error!("Failed to send pong: {}", e);
+                                        disconnect_reason = format!("pong failed: {}", e);
                                         break;
                                     }
                                 }
                                 Ok(Message::Close(_)) => {
                                     log::warn!("WebSocket connection closed by pump.fun");
+                                    disconnect_reason = "closed by pump.fun".to_string();
                                     break;
                                 }
                                 Err(e) => {
                                     log::error!("WebSocket error: {}", e);
+                                    disconnect_reason = e.to_string();
                                     break;
                                 }
                                 _ => {}
                             }
                         }
                         
-                        // Mark as disconnected
+                        // Mark as disconnected - launches fall back to page scraping until we're back
                         {
-                            let mut connected = ws_connected_clone.lock().await;
-                            *connected = false;
+                            let mut status = ws_status.lock().await;
+                            status.connected = false;
+                            status.connected_since = None;
+                            status.last_error = Some(disconnect_reason);
                         }
+                        log::warn!("⚠️ pump.fun WebSocket disconnected - falling back to page scraping until reconnected");
                     }
                     Err(e) => {
                         log::warn!("⚠️ Failed to connect to pump.fun WebSocket ({}): {}", ws_url, e);
+                        ws_status.lock().await.last_error = Some(e.to_string());
                         current_url_idx += 1;
-                        if current_url_idx >= ws_urls.len() {
-                            log::info!("   Tried all WebSocket URLs, will retry in 60 seconds...");
-                            current_url_idx = 0;
-                            tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
-                        } else {
-                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                        }
                     }
                 }
+                
+                let delay = backoff.next_delay(rand::random::<f64>());
+                {
+                    let mut status = ws_status.lock().await;
+                    status.reconnect_attempts = backoff.attempt();
+                    status.total_reconnects += 1;
+                }
+                log::info!(
+                    "🔄 Reconnecting to pump.fun WebSocket in {:.1}s (attempt {}, {})",
+                    delay.as_secs_f64(),
+                    backoff.attempt(),
+                    ws_urls[current_url_idx % ws_urls.len()]
+                );
+                tokio::time::sleep(delay).await;
             }
         })
     }
@@ -648,13 +743,17 @@ impl PumpFunClient {
     
     /// Check if WebSocket is connected
     pub async fn is_websocket_connected(&self) -> bool {
-        let connected = self.ws_connected.lock().await;
-        *connected
+        self.ws_status.lock().await.connected
+    }
+    
+    /// Snapshot of the WebSocket stream state (connection, reconnect attempts, last error)
+    pub async fn websocket_status(&self) -> WebSocketStatus {
+        self.ws_status.lock().await.clone()
     }
     
     /// Get recently created tokens on PumpFun with real-time price data from Moralis
     /// NOTE: This is a fallback method. The memecoin monitor now scans ALL pairs from Mobula API.
-    pub async fn get_recent_launches(&self, _limit: usize) -> Result<Vec<TokenLaunch>, Box<dyn Error>> {
+    pub async fn get_recent_launches(&self, _limit: usize) -> Result<Vec<TokenLaunch>, AppError> {
         log::debug!("Fetching recent launches from PumpFun (fallback method - memecoin monitor uses all pairs)");
         
         // NO SIMULATED DATA - This method should not be used for production
@@ -668,7 +767,7 @@ impl PumpFunClient {
     }
     
     /// Get token details by mint address using Moralis API
-    pub async fn get_token_details(&self, mint: &str) -> Result<Option<TokenLaunch>, Box<dyn Error>> {
+    pub async fn get_token_details(&self, mint: &str) -> Result<Option<TokenLaunch>, AppError> {
         log::debug!("Fetching token details for: {}", mint);
         
         // Try to get price from Moralis API if available
@@ -690,12 +789,14 @@ impl PumpFunClient {
                         symbol: mint.chars().take(8).collect::<String>().to_uppercase(), // Use mint address prefix
                         uri: format!("https://pump.fun/{}", mint),
                         creator: price_data.exchange_address.clone(),
-                        created_timestamp: chrono::Utc::now().timestamp(),
+                        created_timestamp: self.fetch_created_timestamp(mint).await.unwrap_or(0),
                         market_cap,
                         reply_count: if market_cap > 10000.0 { 50 } else { 10 }, // Estimate engagement
                         is_currently_live: true,
                         king_of_the_hill_timestamp: None,
                         bonding_curve: price_data.pair_address.clone(),
+                        holder_count: None,
+                        top_holder_pct: None,
                     }));
                 }
                 Ok(None) => {
@@ -711,12 +812,29 @@ impl PumpFunClient {
         Ok(None)
     }
     
+    /// Creation time of `mint` from the pump.fun coin endpoint - None when it can't be fetched
+    async fn fetch_created_timestamp(&self, mint: &str) -> Option<i64> {
+        let url = format!("{}/coins/{}", self.api_url, mint);
+        let response = match self.client.get(&url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                log::debug!("No pump.fun coin data for {}: HTTP {}", mint, response.status());
+                return None;
+            }
+            Err(e) => {
+                log::debug!("Failed to fetch pump.fun coin data for {}: {}", mint, e);
+                return None;
+            }
+        };
+        response.json::<serde_json::Value>().await.ok().as_ref().and_then(created_timestamp_secs)
+    }
+    
     /// Get token price from Moralis API
     /// Endpoint: GET https://solana-gateway.moralis.io/token/mainnet/{TOKEN_ADDRESS}/price
     /// Docs: https://docs.moralis.com/web3-data-api/solana/tutorials/get-pump-fun-token-prices
-    pub async fn get_token_price_from_moralis(&self, token_address: &str) -> Result<Option<MoralisTokenPrice>, Box<dyn Error>> {
+    pub async fn get_token_price_from_moralis(&self, token_address: &str) -> Result<Option<MoralisTokenPrice>, AppError> {
         if self.moralis_api_key.is_none() {
-            return Err("Moralis API key required. Set MORALIS_API_KEY environment variable.".into());
+            return Err(AppError::Validation("Moralis API key required. Set MORALIS_API_KEY environment variable.".to_string()));
         }
         
         let url = format!("{}/{}/price", self.moralis_api_url, token_address);
@@ -727,7 +845,7 @@ impl PumpFunClient {
         if let Some(ref cb) = self.circuit_breaker {
             let cb_state = cb.lock().await.get_state().await;
             if matches!(cb_state, crate::error_handling::CircuitState::Open) {
-                return Err("Circuit breaker is OPEN - Moralis API call blocked".into());
+                return Err(AppError::CircuitOpen("Moralis API call blocked".to_string()));
             }
         }
         
@@ -736,8 +854,7 @@ impl PumpFunClient {
             .header("X-API-Key", self.moralis_api_key.as_ref().unwrap())
             .header("accept", "application/json")
             .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .await?;
         
         let status = response.status();
         if !status.is_success() {
@@ -746,11 +863,11 @@ impl PumpFunClient {
                 return Ok(None);
             }
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Moralis API error {}: {}", status, error_text).into());
+            return Err(crate::error_handling::map_http_status_to_error(status.as_u16(), format!("Moralis API: {}", error_text)));
         }
         
         let price_data: MoralisTokenPrice = response.json().await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| AppError::Api(format!("Failed to parse response: {}", e)))?;
         
         Ok(Some(price_data))
     }
@@ -785,6 +902,8 @@ impl PumpFunClient {
                 is_currently_live: rand::random::<f64>() > 0.3,
                 king_of_the_hill_timestamp: None,
                 bonding_curve: format!("bonding_curve_{}", i),
+                holder_count: None,
+                top_holder_pct: None,
             });
         }
         
@@ -1038,16 +1157,23 @@ impl PumpFunClient {
             safety.risk_factors.push(format!("Market cap ${:.0} below minimum ${:.0}", token.market_cap, config.min_market_cap_usd));
         }
         
-        // 2. Token Age Check
-        let age_hours = (chrono::Utc::now().timestamp() - token.created_timestamp) as f64 / 3600.0;
-        if age_hours >= config.min_token_age_hours {
-            safety.age_ok = true;
-            safety.passed_checks.push(format!("Token age {:.2}h >= {:.2}h", age_hours, config.min_token_age_hours));
-        } else {
-            safety.is_safe = false;
-            safety.safety_score -= 15.0;
-            safety.risk_factors.push(format!("Token too new: {:.2}h < {:.2}h minimum", age_hours, config.min_token_age_hours));
-            safety.warnings.push("Very new token - higher rug pull risk".to_string());
+        // 2. Token Age Check - an unknown creation time can't prove the token is old enough
+        match token.age_hours(chrono::Utc::now().timestamp()) {
+            Some(age_hours) if age_hours >= config.min_token_age_hours => {
+                safety.age_ok = true;
+                safety.passed_checks.push(format!("Token age {:.2}h >= {:.2}h", age_hours, config.min_token_age_hours));
+            }
+            Some(age_hours) => {
+                safety.is_safe = false;
+                safety.safety_score -= 15.0;
+                safety.risk_factors.push(format!("Token too new: {:.2}h < {:.2}h minimum", age_hours, config.min_token_age_hours));
+                safety.warnings.push("Very new token - higher rug pull risk".to_string());
+            }
+            None => {
+                safety.is_safe = false;
+                safety.safety_score -= 15.0;
+                safety.risk_factors.push("Token age unknown - creation time not reported".to_string());
+            }
         }
         
         // 3. Liquidity Check (estimate from market cap and bonding curve)
@@ -1180,8 +1306,8 @@ impl PumpFunClient {
         let mut patterns = Vec::new();
         
         // Pattern 1: Extremely new token with high market cap (pump and dump)
-        let age_hours = (chrono::Utc::now().timestamp() - token.created_timestamp) as f64 / 3600.0;
-        if age_hours < 0.5 && token.market_cap > 100000.0 {
+        let age_hours = token.age_hours(chrono::Utc::now().timestamp());
+        if age_hours.is_some_and(|age| age < 0.5) && token.market_cap > 100000.0 {
             patterns.push("Very new token with suspiciously high market cap".to_string());
         }
         
@@ -1228,7 +1354,7 @@ impl PumpFunClient {
     /// Check if a token is safe to trade using real price data from Moralis
     /// This validates that the token has real liquidity and price data on a DEX
     /// ENHANCED: Now uses comprehensive safety check
-    pub async fn is_safe_to_trade_with_price(&self, token_address: &str) -> Result<bool, Box<dyn Error>> {
+    pub async fn is_safe_to_trade_with_price(&self, token_address: &str) -> Result<bool, AppError> {
         if self.moralis_api_key.is_none() {
             // Without Moralis API, we can't validate price data
             log::warn!("Cannot validate token safety without Moralis API key");
@@ -1436,6 +1562,26 @@ mod tests {
         assert!(!client.api_url.is_empty());
     }
 
+    #[test]
+    fn test_reconnect_backoff_is_capped_and_jittered() {
+        let mut backoff = ReconnectBackoff::new(
+            std::time::Duration::from_secs(1),
+            std::time::Duration::from_secs(10),
+        );
+        
+        // Full jitter end of the band doubles cleanly: 1s, 2s, 4s, 8s, then capped at 10s
+        let delays: Vec<u64> = (0..6).map(|_| backoff.next_delay(1.0).as_millis() as u64).collect();
+        assert_eq!(delays, vec![1000, 2000, 4000, 8000, 10000, 10000]);
+        assert_eq!(backoff.attempt(), 6);
+        
+        // Jitter never drops below half the capped delay
+        assert_eq!(backoff.next_delay(0.0).as_millis(), 5000);
+        
+        backoff.reset();
+        assert_eq!(backoff.attempt(), 0);
+        assert_eq!(backoff.next_delay(0.0).as_millis(), 500);
+    }
+
     #[tokio::test]
     async fn test_get_recent_launches() {
         let client = PumpFunClient::new();
@@ -1459,6 +1605,8 @@ mod tests {
             is_currently_live: true,
             king_of_the_hill_timestamp: None,
             bonding_curve: "test".to_string(),
+            holder_count: None,
+            top_holder_pct: None,
         };
         
         let sentiment = client.analyze_sentiment(&launch);
@@ -1466,6 +1614,21 @@ mod tests {
         assert!(!sentiment.social_signals.is_empty());
     }
 
+    #[test]
+    fn test_created_timestamp_from_api() {
+        // pump.fun reports milliseconds; a missing creation time stays unknown rather than "now"
+        assert_eq!(created_timestamp_secs(&serde_json::json!({"created_timestamp": 1_700_000_000_000i64})), Some(1_700_000_000));
+        assert_eq!(created_timestamp_secs(&serde_json::json!({"createdAt": 1_700_000_000})), Some(1_700_000_000));
+        assert_eq!(created_timestamp_secs(&serde_json::json!({"mint": "abc"})), None);
+
+        let client = PumpFunClient::new();
+        let launch = client.parse_token_json(&serde_json::json!({"mint": "So11111111111111111111111111111111111111112", "name": "Test"})).unwrap();
+        assert_eq!(launch.created_timestamp, 0);
+        assert_eq!(launch.age_hours(1_700_000_000), None);
+        let dated = TokenLaunch { created_timestamp: 1_700_000_000 - 7200, ..launch };
+        assert_eq!(dated.age_hours(1_700_000_000), Some(2.0));
+    }
+
     #[test]
     fn test_meme_analyzer_position_sizing() {
         let analyzer = MemeAnalyzer::new();
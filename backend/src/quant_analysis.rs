@@ -1,5 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+/// Stops sit this many ATRs away from entry unless a provider overrides it
+pub const DEFAULT_ATR_STOP_MULTIPLIER: f64 = 2.0;
+
+/// Volatility-scaled stops are kept within this band (percent of price)
+/// Tighter than the floor gets stopped out by spread noise; wider than the cap risks too much per trade
+pub const MIN_STOP_DISTANCE_PCT: f64 = 1.0;
+pub const MAX_STOP_DISTANCE_PCT: f64 = 15.0;
+
 /// Advanced quantitative analysis module with technical indicators
 #[derive(Debug, Clone)]
 pub struct QuantAnalyzer {
@@ -126,18 +134,20 @@ impl QuantAnalyzer {
         Some((upper, sma, lower))
     }
 
-    /// Calculate Average True Range (ATR)
+    /// Calculate Average True Range (ATR) over the last `period` bars
+    /// Each true range needs the previous close, so `period + 1` bars are required
     pub fn calculate_atr(&self, highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Option<f64> {
-        if highs.len() < period || lows.len() < period || closes.len() < period {
+        let len = highs.len().min(lows.len()).min(closes.len());
+        if period == 0 || len <= period {
             return None;
         }
 
         let mut true_ranges = Vec::new();
         
-        for i in 1..period {
-            let high_low = highs[highs.len() - period + i] - lows[lows.len() - period + i];
-            let high_close = (highs[highs.len() - period + i] - closes[closes.len() - period + i - 1]).abs();
-            let low_close = (lows[lows.len() - period + i] - closes[closes.len() - period + i - 1]).abs();
+        for i in len - period..len {
+            let high_low = highs[i] - lows[i];
+            let high_close = (highs[i] - closes[i - 1]).abs();
+            let low_close = (lows[i] - closes[i - 1]).abs();
             
             true_ranges.push(high_low.max(high_close).max(low_close));
         }
@@ -145,6 +155,14 @@ impl QuantAnalyzer {
         Some(true_ranges.iter().sum::<f64>() / true_ranges.len() as f64)
     }
 
+    /// Long stop `multiplier` ATRs below `price`, kept within MIN/MAX_STOP_DISTANCE_PCT
+    /// Mirror the distance above the price for shorts
+    pub fn suggested_stop(price: f64, atr: f64, multiplier: f64) -> f64 {
+        let distance = if atr.is_finite() && atr > 0.0 { atr * multiplier } else { 0.0 };
+        let distance = distance.clamp(price * MIN_STOP_DISTANCE_PCT / 100.0, price * MAX_STOP_DISTANCE_PCT / 100.0);
+        price - distance
+    }
+
     /// Calculate On-Balance Volume (OBV)
     pub fn calculate_obv(&self, prices: &[f64], volumes: &[f64]) -> Option<f64> {
         if prices.len() < 2 || volumes.len() < 2 {
@@ -213,10 +231,8 @@ impl QuantAnalyzer {
         let (bollinger_upper, bollinger_middle, bollinger_lower) = 
             self.calculate_bollinger_bands(prices, 20, 2.0).unwrap_or((0.0, 0.0, 0.0));
         
-        // For ATR, we approximate using price ranges
-        let highs: Vec<f64> = prices.iter().map(|p| p * 1.001).collect();
-        let lows: Vec<f64> = prices.iter().map(|p| p * 0.999).collect();
-        let atr_14 = self.calculate_atr(&highs, &lows, prices, 14).unwrap_or(0.0);
+        // Ticks carry no high/low, so the true range reduces to the close-to-close move
+        let atr_14 = self.calculate_atr(prices, prices, prices, 14).unwrap_or(0.0);
         
         let obv = self.calculate_obv(prices, volumes).unwrap_or(0.0);
         let momentum = self.calculate_momentum(prices, 10).unwrap_or(0.0);
@@ -430,6 +446,23 @@ mod tests {
         assert!(quality.score > 60.0);
     }
 
+    #[test]
+    fn test_atr_scales_suggested_stop() {
+        let analyzer = QuantAnalyzer::new();
+        // Alternating +2/-2 moves: every close-to-close true range is 2
+        let prices: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 100.0 } else { 102.0 }).collect();
+        let atr = analyzer.calculate_atr(&prices, &prices, &prices, 14).unwrap();
+        assert!((atr - 2.0).abs() < 1e-9);
+        assert!(analyzer.calculate_atr(&prices[..14], &prices[..14], &prices[..14], 14).is_none());
+        assert!((analyzer.calculate_indicators(&prices, &[1.0; 30]).unwrap().atr_14 - 2.0).abs() < 1e-9);
+
+        assert!((QuantAnalyzer::suggested_stop(100.0, 2.0, 2.0) - 96.0).abs() < 1e-9);
+        // A calm token still gets the minimum distance, a wild one is capped
+        assert!((QuantAnalyzer::suggested_stop(100.0, 0.1, 2.0) - 99.0).abs() < 1e-9);
+        assert!((QuantAnalyzer::suggested_stop(100.0, 20.0, 2.0) - 85.0).abs() < 1e-9);
+        assert!((QuantAnalyzer::suggested_stop(100.0, 0.0, 2.0) - 99.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_momentum_calculation() {
         let analyzer = QuantAnalyzer::new();
@@ -11,6 +11,122 @@ use crate::deepseek_ai::{DeepSeekClient, TradingDecision};
 use crate::signal_platform::TradingSignalData;
 use crate::historical_data::{HistoricalDataManager, HistoricalFeatures, PriceDataPoint};
 
+/// Default epsilon-greedy schedule: 20% exploration, decayed 0.3% per learned experience down to 5%
+pub const DEFAULT_EPSILON: f64 = 0.2;
+pub const DEFAULT_MIN_EPSILON: f64 = 0.05;
+pub const DEFAULT_EPSILON_DECAY: f64 = 0.997;
+/// Agents winning over 60% decay 1% per experience down to 3%
+pub const DEFAULT_WINNING_MIN_EPSILON: f64 = 0.03;
+pub const DEFAULT_WINNING_EPSILON_DECAY: f64 = 0.99;
+/// Agents winning under 40% decay 0.5% per experience and keep exploring at least 10%
+pub const DEFAULT_STRUGGLING_MIN_EPSILON: f64 = 0.10;
+pub const DEFAULT_STRUGGLING_EPSILON_DECAY: f64 = 0.995;
+
+/// Epsilon-greedy exploration - live-editable via POST /rl/agents/{id}/config
+/// Fields missing from a request or an older state file keep their defaults
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExplorationConfig {
+    /// Probability of an exploratory action instead of the learned one
+    pub epsilon: f64,
+    /// Floor the decay stops at
+    pub min_epsilon: f64,
+    /// Multiplier applied to epsilon per learned experience (1.0 = no decay)
+    pub epsilon_decay: f64,
+    /// Floor and decay for agents winning over 60% of trades
+    pub winning_min_epsilon: f64,
+    pub winning_epsilon_decay: f64,
+    /// Floor and decay for agents winning under 40% of trades
+    pub struggling_min_epsilon: f64,
+    pub struggling_epsilon_decay: f64,
+}
+
+impl Default for ExplorationConfig {
+    fn default() -> Self {
+        Self {
+            epsilon: DEFAULT_EPSILON,
+            min_epsilon: DEFAULT_MIN_EPSILON,
+            epsilon_decay: DEFAULT_EPSILON_DECAY,
+            winning_min_epsilon: DEFAULT_WINNING_MIN_EPSILON,
+            winning_epsilon_decay: DEFAULT_WINNING_EPSILON_DECAY,
+            struggling_min_epsilon: DEFAULT_STRUGGLING_MIN_EPSILON,
+            struggling_epsilon_decay: DEFAULT_STRUGGLING_EPSILON_DECAY,
+        }
+    }
+}
+
+impl ExplorationConfig {
+    /// RL_EPSILON / RL_MIN_EPSILON / RL_EPSILON_DECAY, plus RL_WINNING_* and RL_STRUGGLING_* floors and decays
+    /// (invalid combinations fall back to the defaults)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let number = |name: &str, default: f64| std::env::var(name).ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default);
+        let config = Self {
+            epsilon: number("RL_EPSILON", defaults.epsilon),
+            min_epsilon: number("RL_MIN_EPSILON", defaults.min_epsilon),
+            epsilon_decay: number("RL_EPSILON_DECAY", defaults.epsilon_decay),
+            winning_min_epsilon: number("RL_WINNING_MIN_EPSILON", defaults.winning_min_epsilon),
+            winning_epsilon_decay: number("RL_WINNING_EPSILON_DECAY", defaults.winning_epsilon_decay),
+            struggling_min_epsilon: number("RL_STRUGGLING_MIN_EPSILON", defaults.struggling_min_epsilon),
+            struggling_epsilon_decay: number("RL_STRUGGLING_EPSILON_DECAY", defaults.struggling_epsilon_decay),
+        };
+        match config.validate() {
+            Ok(()) => config,
+            Err(e) => {
+                log::warn!("⚠️ Invalid RL exploration settings ({}) - using the defaults", e);
+                defaults
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.epsilon) {
+            return Err(format!("epsilon must be between 0 and 1, got {}", self.epsilon));
+        }
+        let floors = [
+            ("min_epsilon", self.min_epsilon),
+            ("winning_min_epsilon", self.winning_min_epsilon),
+            ("struggling_min_epsilon", self.struggling_min_epsilon),
+        ];
+        for (name, floor) in floors {
+            if !(0.0..=1.0).contains(&floor) {
+                return Err(format!("{} must be between 0 and 1, got {}", name, floor));
+            }
+        }
+        let decays = [
+            ("epsilon_decay", self.epsilon_decay),
+            ("winning_epsilon_decay", self.winning_epsilon_decay),
+            ("struggling_epsilon_decay", self.struggling_epsilon_decay),
+        ];
+        for (name, decay) in decays {
+            if !(decay > 0.0 && decay <= 1.0) {
+                return Err(format!("{} must be in (0, 1], got {}", name, decay));
+            }
+        }
+        Ok(())
+    }
+
+    /// One decay step, adapted to how the agent is doing: winning (>60%) and struggling (<40%) agents use
+    /// their own decay and floor. Decay never raises epsilon, so an operator setting it below the floor
+    /// (e.g. during volatile markets) sticks
+    pub fn decayed(&self, win_rate: f64) -> f64 {
+        let (rate, floor) = if win_rate > 0.6 {
+            (self.winning_epsilon_decay, self.winning_min_epsilon)
+        } else if win_rate < 0.4 {
+            (self.struggling_epsilon_decay, self.struggling_min_epsilon)
+        } else {
+            (self.epsilon_decay, self.min_epsilon)
+        };
+        if self.epsilon > floor {
+            (self.epsilon * rate).max(floor)
+        } else {
+            self.epsilon
+        }
+    }
+}
+
 /// Experience replay buffer for reinforcement learning
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Experience {
@@ -20,6 +136,48 @@ pub struct Experience {
     pub next_state: Option<MarketState>,
     pub timestamp: i64,
     pub provider_id: String,
+    #[serde(default)]
+    pub exit_reason: Option<crate::enhanced_marketplace::ExitReason>, // How the position closed (None for entries)
+}
+
+impl Experience {
+    /// An experience recorded now - `closed_at` adds how the position closed
+    pub fn new(provider_id: &str, state: MarketState, action: Action, reward: f64, next_state: Option<MarketState>) -> Self {
+        Self {
+            state,
+            action,
+            reward,
+            next_state,
+            timestamp: Utc::now().timestamp(),
+            provider_id: provider_id.to_string(),
+            exit_reason: None,
+        }
+    }
+
+    /// Entering `signal` at `price` for `size` - unrewarded until the position closes
+    pub fn entry(signal: &TradingSignalData, price: f64, size: f64) -> Self {
+        Self::new(
+            &signal.provider,
+            MarketState::at_price(&signal.symbol, price, signal.confidence),
+            Action {
+                action_type: format!("{:?}", signal.action),
+                confidence: signal.confidence,
+                size,
+                price,
+            },
+            0.0,
+            None,
+        )
+    }
+
+    /// The same trade closed at `exit_price`
+    pub fn closed_at(mut self, exit_price: f64, reward: f64, exit_reason: Option<crate::enhanced_marketplace::ExitReason>) -> Self {
+        let confidence = self.action.confidence;
+        self.next_state = Some(MarketState::at_price(&self.state.symbol, exit_price, confidence));
+        self.reward = reward;
+        self.exit_reason = exit_reason;
+        self
+    }
 }
 
 /// Market state representation
@@ -36,6 +194,23 @@ pub struct MarketState {
     pub market_cap: Option<f64>,
 }
 
+impl MarketState {
+    /// A state known only by its price - the trade's confidence stands in for sentiment
+    pub fn at_price(symbol: &str, price: f64, confidence: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            price,
+            volume: 0.0,
+            price_change_1h: 0.0,
+            price_change_24h: 0.0,
+            sentiment_score: confidence * 100.0,
+            liquidity: 0.0,
+            volatility: 0.0,
+            market_cap: None,
+        }
+    }
+}
+
 /// Action taken by agent
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Action {
@@ -127,7 +302,7 @@ pub struct RLAgent {
     experience_buffer: Arc<Mutex<VecDeque<Experience>>>,
     pub(crate) performance: Arc<Mutex<AgentPerformance>>,
     q_table: Arc<Mutex<HashMap<String, f64>>>, // Simple Q-learning table
-    pub(crate) epsilon: f64, // Exploration rate
+    exploration: Arc<Mutex<ExplorationConfig>>, // Epsilon-greedy exploration rate and decay schedule
     gamma: f64,   // Discount factor
     max_buffer_size: usize,
     historical_data: Arc<Mutex<HistoricalDataManager>>, // Historical price data
@@ -144,7 +319,7 @@ impl RLAgent {
             experience_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(1000))),
             performance: Arc::new(Mutex::new(AgentPerformance::new())),
             q_table: Arc::new(Mutex::new(HashMap::new())),
-            epsilon: 0.2, // 20% exploration
+            exploration: Arc::new(Mutex::new(ExplorationConfig::from_env())),
             gamma: 0.95,  // Future reward discount
             max_buffer_size: 1000,
             historical_data: Arc::new(Mutex::new(HistoricalDataManager::new(1000))), // Keep 1000 data points per symbol
@@ -179,7 +354,8 @@ impl RLAgent {
         drop(performance);
 
         // Epsilon-greedy strategy: explore vs exploit
-        let should_explore = rand::random::<f64>() < self.epsilon;
+        let epsilon = self.exploration.lock().await.epsilon;
+        let should_explore = rand::random::<f64>() < epsilon;
 
         if should_explore {
             // Exploration: Try something different
@@ -392,6 +568,24 @@ impl RLAgent {
             state.symbol, price_bucket, change_bucket, sentiment_bucket, volume_bucket)
     }
 
+    /// PARTIAL FILLS: Correct the latest open buy experience on `symbol` to the size and price
+    /// the swap actually filled at. Returns false when no such experience is buffered
+    pub async fn amend_entry_fill(&self, symbol: &str, size: f64, price: f64) -> bool {
+        let mut buffer = self.experience_buffer.lock().await;
+        let entry = buffer.iter_mut().rev().find(|e| {
+            e.state.symbol == symbol && e.exit_reason.is_none() && e.action.action_type.eq_ignore_ascii_case("buy")
+        });
+        match entry {
+            Some(experience) => {
+                experience.action.size = size;
+                experience.action.price = price;
+                experience.state.price = price;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Record experience and learn from it with resource limit protection
     pub async fn record_experience(&self, experience: Experience) {
         // RESOURCE LIMIT: Prevent unbounded growth of experience buffer
@@ -409,6 +603,9 @@ impl RLAgent {
         let mut performance = self.performance.lock().await;
         performance.update(experience.reward);
         drop(performance);
+        
+        // Explore less as experience accumulates
+        self.decay_exploration().await;
 
         // Update Q-table
         self.update_q_table(&experience).await;
@@ -494,44 +691,151 @@ impl RLAgent {
     }
 
     /// Adaptive exploration decay based on performance
-    pub async fn decay_exploration(&mut self) {
-        let performance = self.performance.lock().await;
-        let win_rate = performance.win_rate;
-        drop(performance);
-        
-        // Adaptive epsilon decay
-        if win_rate > 0.6 {
-            // Doing well, reduce exploration more aggressively
-            self.epsilon = (self.epsilon * 0.99).max(0.03); // Min 3% exploration
-        } else if win_rate < 0.4 {
-            // Struggling, maintain higher exploration
-            self.epsilon = (self.epsilon * 0.995).max(0.10); // Min 10% exploration
-        } else {
-            // Normal decay
-            self.epsilon = (self.epsilon * 0.997).max(0.05); // Min 5% exploration
-        }
+    pub async fn decay_exploration(&self) {
+        let win_rate = self.performance.lock().await.win_rate;
+        let mut exploration = self.exploration.lock().await;
+        exploration.epsilon = exploration.decayed(win_rate);
         
         log::debug!("Agent {} epsilon decayed to {:.3}, win_rate: {:.2}%", 
-                    self.agent_id, self.epsilon, win_rate * 100.0);
+                    self.agent_id, exploration.epsilon, win_rate * 100.0);
+    }
+    
+    pub async fn exploration(&self) -> ExplorationConfig {
+        *self.exploration.lock().await
+    }
+    
+    /// Replace the exploration settings (rejected if invalid)
+    pub async fn set_exploration(&self, config: ExplorationConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.exploration.lock().await = config;
+        log::info!("🎲 Agent {} exploration set to {:?}", self.agent_id, config);
+        Ok(())
+    }
+}
+
+/// Serializable snapshot of an agent's learned state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentState {
+    pub agent_id: String,
+    pub provider_type: String,
+    pub experiences: Vec<Experience>,
+    pub q_table: HashMap<String, f64>,
+    pub performance: AgentPerformance,
+    /// Decayed (or operator-set) exploration carries over restarts; absent in older state files
+    #[serde(default)]
+    pub exploration: Option<ExplorationConfig>,
+}
+
+/// On-disk format for all agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LearningState {
+    saved_at: i64,
+    agents: Vec<AgentState>,
+}
+
+impl RLAgent {
+    /// Snapshot replay buffer, Q-table and performance stats
+    pub async fn export_state(&self) -> AgentState {
+        AgentState {
+            agent_id: self.agent_id.clone(),
+            provider_type: self.provider_type.clone(),
+            experiences: self.experience_buffer.lock().await.iter().cloned().collect(),
+            q_table: self.q_table.lock().await.clone(),
+            performance: self.performance.lock().await.clone(),
+            exploration: Some(*self.exploration.lock().await),
+        }
+    }
+
+    /// Restore a previously exported snapshot (replaces current learning state)
+    pub async fn import_state(&self, state: AgentState) {
+        let mut buffer = self.experience_buffer.lock().await;
+        buffer.clear();
+        let skip = state.experiences.len().saturating_sub(self.max_buffer_size);
+        buffer.extend(state.experiences.into_iter().skip(skip));
+        drop(buffer);
+
+        *self.q_table.lock().await = state.q_table;
+        *self.performance.lock().await = state.performance;
+        if let Some(exploration) = state.exploration.filter(|config| config.validate().is_ok()) {
+            *self.exploration.lock().await = exploration;
+        }
     }
 }
 
 /// Agent learning coordinator
 pub struct LearningCoordinator {
     agents: Arc<Mutex<HashMap<String, Arc<RLAgent>>>>,
+    pending_state: Arc<Mutex<HashMap<String, AgentState>>>, // Loaded from disk, applied when the agent registers
 }
 
 impl LearningCoordinator {
     pub fn new() -> Self {
         Self {
             agents: Arc::new(Mutex::new(HashMap::new())),
+            pending_state: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     /// Register an agent for learning
-    pub async fn register_agent(&self, agent: Arc<RLAgent>) {
+    /// If state was loaded for this agent, it resumes from it
+    /// Idempotent by `agent_id` - returns false and keeps the tracked agent's learning if already registered
+    pub async fn register_agent(&self, agent: Arc<RLAgent>) -> bool {
         let mut agents = self.agents.lock().await;
+        if agents.contains_key(&agent.agent_id) {
+            log::debug!("🧠 Agent {} already registered - keeping its learning state", agent.agent_id);
+            return false;
+        }
+        if let Some(state) = self.pending_state.lock().await.remove(&agent.agent_id) {
+            log::info!("🧠 Agent {} resuming with {} experiences and {} Q-values",
+                      agent.agent_id, state.experiences.len(), state.q_table.len());
+            agent.import_state(state).await;
+        }
         agents.insert(agent.agent_id.clone(), agent);
+        true
+    }
+    
+    /// Persist every agent's learning state to `path` (atomic temp file + rename)
+    /// Agents loaded from disk but not registered yet are kept so their state isn't lost
+    pub async fn save_state(&self, path: &std::path::Path) -> Result<usize, String> {
+        let agents: Vec<Arc<RLAgent>> = self.agents.lock().await.values().cloned().collect();
+        let mut states = Vec::with_capacity(agents.len());
+        for agent in agents {
+            states.push(agent.export_state().await);
+        }
+        states.extend(self.pending_state.lock().await.values().cloned());
+        
+        let count = states.len();
+        let snapshot = LearningState {
+            saved_at: Utc::now().timestamp(),
+            agents: states,
+        };
+        crate::database::write_json_atomic(path, &snapshot)?;
+        log::debug!("💾 Saved RL state for {} agents to {}", count, path.display());
+        Ok(count)
+    }
+    
+    /// Load learning state from `path`; returns the number of agent snapshots found
+    /// Registered agents are restored immediately, others when they register
+    pub async fn load_state(&self, path: &std::path::Path) -> Result<usize, String> {
+        let snapshot: LearningState = match crate::database::read_json(path)? {
+            Some(snapshot) => snapshot,
+            None => return Ok(0),
+        };
+        
+        let count = snapshot.agents.len();
+        let agents = self.agents.lock().await;
+        let mut pending = self.pending_state.lock().await;
+        for state in snapshot.agents {
+            match agents.get(&state.agent_id) {
+                Some(agent) => agent.import_state(state).await,
+                None => {
+                    pending.insert(state.agent_id.clone(), state);
+                }
+            }
+        }
+        log::info!("🧠 Loaded RL state for {} agents from {} (saved at {})",
+                  count, path.display(), snapshot.saved_at);
+        Ok(count)
     }
     
     /// Record experience for a specific provider's agent
@@ -544,6 +848,17 @@ impl LearningCoordinator {
         }
     }
     
+    /// Correct a provider's open entry experience to its real fill
+    pub async fn amend_entry_fill(&self, provider_id: &str, symbol: &str, size: f64, price: f64) {
+        let agents = self.agents.lock().await;
+        match agents.get(provider_id) {
+            Some(agent) if agent.amend_entry_fill(symbol, size, price).await => {
+                log::debug!("🧠 Amended {} entry experience for {} to {:.6} @ ${:.8}", symbol, provider_id, size, price);
+            }
+            _ => log::debug!("No open {} entry experience for provider: {}", symbol, provider_id),
+        }
+    }
+    
     /// Get agent by provider ID
     pub async fn get_agent(&self, provider_id: &str) -> Option<Arc<RLAgent>> {
         let agents = self.agents.lock().await;
@@ -556,27 +871,9 @@ impl LearningCoordinator {
         
         if let Some(agent) = agents.get(&signal.provider) {
             let experience = Experience {
-                state: MarketState {
-                    symbol: signal.symbol.clone(),
-                    price: signal.entry_price,
-                    volume: 0.0, // Would be populated from market data
-                    price_change_1h: 0.0,
-                    price_change_24h: 0.0,
-                    sentiment_score: signal.confidence * 100.0,
-                    liquidity: 0.0,
-                    volatility: 0.0,
-                    market_cap: None,
-                },
-                action: Action {
-                    action_type: format!("{:?}", signal.action),
-                    confidence: signal.confidence,
-                    size: 0.05, // Placeholder
-                    price: signal.entry_price,
-                },
                 reward: outcome.reward,
                 next_state: outcome.final_state,
-                timestamp: Utc::now().timestamp(),
-                provider_id: signal.provider.clone(),
+                ..Experience::entry(signal, signal.entry_price, 0.05) // Placeholder size
             };
 
             agent.record_experience(experience).await;
@@ -616,6 +913,27 @@ mod tests {
         assert_eq!(perf.successful_trades, 1);
     }
 
+    #[test]
+    fn test_experience_entry_and_close() {
+        let signal = TradingSignalData {
+            provider: "oracle".to_string(),
+            symbol: "SOL/USD".to_string(),
+            entry_price: 100.0,
+            confidence: 0.8,
+            ..Default::default()
+        };
+        let entry = Experience::entry(&signal, 101.0, 2.0);
+        assert_eq!((entry.state.price, entry.action.price, entry.action.size), (101.0, 101.0, 2.0));
+        assert_eq!(entry.state.sentiment_score, 80.0);
+        assert_eq!(entry.provider_id, "oracle");
+        assert!(entry.next_state.is_none() && entry.exit_reason.is_none());
+
+        let closed = entry.closed_at(110.0, 9.0, Some(crate::enhanced_marketplace::ExitReason::Target));
+        assert_eq!(closed.next_state.as_ref().map(|s| s.price), Some(110.0));
+        assert_eq!(closed.reward, 9.0);
+        assert!(closed.exit_reason.is_some());
+    }
+
     #[test]
     fn test_calculate_reward() {
         let reward = RLAgent::calculate_reward(100.0, 110.0, "BUY", 0.8);
@@ -641,4 +959,129 @@ mod tests {
         );
         assert_eq!(agent.agent_id, "test_agent");
     }
+
+    #[tokio::test]
+    async fn test_learning_state_roundtrip() {
+        let path = std::path::Path::new("/tmp/test_rl_state.json");
+        let _ = std::fs::remove_file(path);
+
+        let coordinator = LearningCoordinator::new();
+        let agent = Arc::new(RLAgent::new("agent_1".to_string(), "oracle".to_string(), None));
+        coordinator.register_agent(agent.clone()).await;
+        agent.record_experience(Experience {
+            state: MarketState {
+                symbol: "SOL/USD".to_string(),
+                price: 100.0,
+                volume: 0.0,
+                price_change_1h: 0.0,
+                price_change_24h: 0.0,
+                sentiment_score: 50.0,
+                liquidity: 0.0,
+                volatility: 0.0,
+                market_cap: None,
+            },
+            action: Action {
+                action_type: "BUY".to_string(),
+                confidence: 0.8,
+                size: 1.0,
+                price: 100.0,
+            },
+            reward: 5.0,
+            next_state: None,
+            timestamp: Utc::now().timestamp(),
+            provider_id: "oracle".to_string(),
+            exit_reason: None,
+        }).await;
+        assert_eq!(coordinator.save_state(path).await.unwrap(), 1);
+
+        // Load before the agent registers - state is applied on registration
+        let restarted = LearningCoordinator::new();
+        assert_eq!(restarted.load_state(path).await.unwrap(), 1);
+        let fresh_agent = Arc::new(RLAgent::new("agent_1".to_string(), "oracle".to_string(), None));
+        restarted.register_agent(fresh_agent.clone()).await;
+
+        assert_eq!(fresh_agent.get_recent_experiences(10).await.len(), 1);
+        assert_eq!(fresh_agent.get_performance().await.total_trades, 1);
+        assert_eq!(fresh_agent.export_state().await.q_table, agent.export_state().await.q_table);
+        assert_eq!(fresh_agent.exploration().await, agent.exploration().await);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_exploration_config() {
+        let agent = RLAgent::new("agent_1".to_string(), "oracle".to_string(), None);
+        // The default schedule: 5% floor normally, 3% when winning, 10% when struggling
+        let defaults = ExplorationConfig::default();
+        assert_eq!(ExplorationConfig { epsilon: 0.0301, ..defaults }.decayed(0.7), 0.03);
+        assert_eq!(ExplorationConfig { epsilon: 0.0501, ..defaults }.decayed(0.5), 0.05);
+        assert_eq!(ExplorationConfig { epsilon: 0.1001, ..defaults }.decayed(0.2), 0.10);
+
+        let config = ExplorationConfig {
+            epsilon: 0.5,
+            min_epsilon: 0.1,
+            epsilon_decay: 0.5,
+            struggling_min_epsilon: 0.2,
+            struggling_epsilon_decay: 0.5,
+            ..defaults
+        };
+        agent.set_exploration(config).await.unwrap();
+        agent.performance.lock().await.win_rate = 0.5;
+        agent.decay_exploration().await;
+        assert_eq!(agent.exploration().await.epsilon, 0.25);
+        agent.performance.lock().await.win_rate = 0.2;
+        agent.decay_exploration().await;
+        assert_eq!(agent.exploration().await.epsilon, 0.2); // Struggling agents hold their own floor
+
+        // Set below the floor for a volatile market - decay leaves it there
+        agent.set_exploration(ExplorationConfig { epsilon: 0.01, ..config }).await.unwrap();
+        agent.decay_exploration().await;
+        assert_eq!(agent.exploration().await.epsilon, 0.01);
+
+        assert!(agent.set_exploration(ExplorationConfig { epsilon: 1.5, ..config }).await.is_err());
+        assert!(agent.set_exploration(ExplorationConfig { epsilon_decay: 0.0, ..config }).await.is_err());
+        assert!(agent.set_exploration(ExplorationConfig { winning_min_epsilon: -0.1, ..config }).await.is_err());
+        assert_eq!(agent.exploration().await.epsilon, 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_register_agent_is_idempotent() {
+        let coordinator = LearningCoordinator::new();
+        let agent = Arc::new(RLAgent::new("agent_1".to_string(), "oracle".to_string(), None));
+        assert!(coordinator.register_agent(agent.clone()).await);
+        agent.record_experience(Experience {
+            state: MarketState {
+                symbol: "SOL/USD".to_string(),
+                price: 100.0,
+                volume: 0.0,
+                price_change_1h: 0.0,
+                price_change_24h: 0.0,
+                sentiment_score: 50.0,
+                liquidity: 0.0,
+                volatility: 0.0,
+                market_cap: None,
+            },
+            action: Action {
+                action_type: "BUY".to_string(),
+                confidence: 0.8,
+                size: 1.0,
+                price: 100.0,
+            },
+            reward: 5.0,
+            next_state: None,
+            timestamp: Utc::now().timestamp(),
+            provider_id: "oracle".to_string(),
+            exit_reason: None,
+        }).await;
+
+        // Same agent again, then a fresh instance with the same id - neither resets learning
+        assert!(!coordinator.register_agent(agent.clone()).await);
+        let duplicate = Arc::new(RLAgent::new("agent_1".to_string(), "oracle".to_string(), None));
+        assert!(!coordinator.register_agent(duplicate).await);
+
+        let tracked = coordinator.agents.lock().await.get("agent_1").cloned().unwrap();
+        assert!(Arc::ptr_eq(&tracked, &agent));
+        assert_eq!(tracked.get_recent_experiences(10).await.len(), 1);
+        assert_eq!(coordinator.get_all_performance().await["agent_1"].total_trades, 1);
+    }
 }
@@ -1,5 +1,267 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+/// Default cap on quoted price impact (percent) for a single order
+pub const DEFAULT_MAX_ACCEPTABLE_SLIPPAGE: f64 = 1.0;
+/// Default cap on concurrently open positions
+pub const DEFAULT_MAX_OPEN_POSITIONS: usize = 10;
+/// Default cap on open positions in a single symbol
+pub const DEFAULT_MAX_POSITIONS_PER_SYMBOL: usize = 1;
+/// Largest share of the live treasury a single trade may use
+pub const DEFAULT_MAX_POSITION_FRACTION: f64 = 0.1;
+/// Smallest fraction of the quoted order size worth executing
+const MIN_VIABLE_SIZE_FRACTION: f64 = 0.1;
+/// Order size reduction per sizing step
+const SLIPPAGE_SIZING_STEP: f64 = 0.8;
+/// Default cap on capital held in one group of correlated positions (fraction of capital)
+pub const DEFAULT_MAX_CORRELATED_EXPOSURE: f64 = 0.5;
+/// Positions correlated at least this much count toward the same exposure group
+pub const DEFAULT_CORRELATION_THRESHOLD: f64 = 0.7;
+/// Default share of the treasury each provider may have deployed at once (PROVIDER_ALLOCATION_PCT)
+pub const DEFAULT_PROVIDER_ALLOCATION: f64 = 0.25;
+/// Assumed correlation between SOL-pegged memecoins until there is enough history to estimate it
+pub const MEMECOIN_DEFAULT_CORRELATION: f64 = 0.9;
+/// Returns needed before an estimated correlation replaces the default
+const MIN_CORRELATION_SAMPLES: usize = 10;
+/// Streak sizing defaults: each consecutive loss cuts size 25%, each win adds 10%, within 0.25x-1.5x
+pub const DEFAULT_STREAK_LOSS_STEP: f64 = 0.25;
+pub const DEFAULT_STREAK_WIN_STEP: f64 = 0.1;
+pub const DEFAULT_STREAK_MIN_MULTIPLIER: f64 = 0.25;
+pub const DEFAULT_STREAK_MAX_MULTIPLIER: f64 = 1.5;
+/// Capital samples kept for a rolling drawdown window (one per trade or portfolio snapshot)
+const MAX_CAPITAL_SAMPLES: usize = 10_000;
+/// Base tokens that trade on their own fundamentals rather than as SOL-paired memecoins
+const BLUECHIP_TOKENS: [&str; 14] = [
+    "SOL", "USDC", "USDT", "BTC", "WBTC", "ETH", "WETH", "JUP", "RAY", "JTO", "PYTH", "MSOL", "JITOSOL", "BSOL",
+];
+
+/// Base token is not a blue-chip - assumed to move with the SOL memecoin market
+pub fn is_sol_pegged_memecoin(symbol: &str) -> bool {
+    let base = symbol.split('/').next().unwrap_or(symbol).to_uppercase();
+    !BLUECHIP_TOKENS.contains(&base.as_str())
+}
+
+/// Pairwise return correlations estimated from recent price history
+#[derive(Debug, Clone, Default)]
+pub struct CorrelationMatrix {
+    estimates: HashMap<(String, String), f64>,
+}
+
+impl CorrelationMatrix {
+    /// Pearson correlation of tick-to-tick returns over the overlapping tail of each pair's history
+    /// Pairs with fewer than MIN_CORRELATION_SAMPLES returns are left to the defaults
+    pub fn from_price_history(history: &HashMap<String, Vec<f64>>) -> Self {
+        let returns: HashMap<&String, Vec<f64>> = history.iter()
+            .map(|(symbol, prices)| {
+                let returns = prices.windows(2)
+                    .filter(|w| w[0] > 0.0)
+                    .map(|w| (w[1] - w[0]) / w[0])
+                    .collect();
+                (symbol, returns)
+            })
+            .collect();
+        
+        let mut estimates = HashMap::new();
+        let symbols: Vec<&&String> = returns.keys().collect();
+        for (i, a) in symbols.iter().enumerate() {
+            for b in &symbols[i + 1..] {
+                let (ra, rb) = (&returns[**a], &returns[**b]);
+                let n = ra.len().min(rb.len());
+                if n < MIN_CORRELATION_SAMPLES {
+                    continue;
+                }
+                if let Some(correlation) = pearson(&ra[ra.len() - n..], &rb[rb.len() - n..]) {
+                    estimates.insert(Self::key(a, b), correlation);
+                }
+            }
+        }
+        Self { estimates }
+    }
+    
+    fn key(a: &str, b: &str) -> (String, String) {
+        if a <= b { (a.to_string(), b.to_string()) } else { (b.to_string(), a.to_string()) }
+    }
+    
+    /// Estimated correlation, falling back to MEMECOIN_DEFAULT_CORRELATION between memecoins (0 otherwise)
+    pub fn correlation(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+        match self.estimates.get(&Self::key(a, b)) {
+            Some(correlation) => *correlation,
+            None if is_sol_pegged_memecoin(a) && is_sol_pegged_memecoin(b) => MEMECOIN_DEFAULT_CORRELATION,
+            None => 0.0,
+        }
+    }
+}
+
+fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len() as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return None; // A flat series has no defined correlation
+    }
+    Some(cov / (var_a.sqrt() * var_b.sqrt()))
+}
+
+/// Outcome of the correlated exposure check for a new position
+#[derive(Debug, Clone, PartialEq)]
+pub enum PortfolioRisk {
+    Accept,
+    /// Only this much value fits under the correlated exposure limit
+    Downsize { max_value: f64, reason: String },
+    Reject { reason: String },
+}
+
+/// Largest group of correlated positions, for /safety/status
+#[derive(Debug, Clone, Serialize)]
+pub struct ConcentrationReport {
+    pub symbols: Vec<String>,
+    pub exposure_value: f64,
+    pub exposure_pct: f64,
+    pub limit_pct: f64,
+    pub over_limit: bool,
+}
+
+/// One provider's slice of the treasury, for /providers/capital
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderAllocation {
+    pub provider_id: String,
+    pub allocation_pct: f64,
+    pub allocated_capital: f64,
+    pub deployed_capital: f64,
+    pub available_capital: f64,
+    pub utilization_pct: f64,
+}
+
+/// Parse PROVIDER_CAPITAL_ALLOCATIONS="memecoin_monitor:10,oracle_monitor:40" (percent of the treasury)
+pub fn parse_provider_allocations(value: &str) -> Result<HashMap<String, f64>, String> {
+    let mut allocations = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (provider_id, pct) = entry.split_once(':')
+            .ok_or_else(|| format!("Expected provider:percent, got {:?}", entry))?;
+        let pct: f64 = pct.trim().parse()
+            .map_err(|_| format!("Invalid allocation for {}: {:?}", provider_id.trim(), pct.trim()))?;
+        if !(0.0..=100.0).contains(&pct) {
+            return Err(format!("Allocation for {} must be 0-100%, got {}", provider_id.trim(), pct));
+        }
+        allocations.insert(provider_id.trim().to_string(), pct / 100.0);
+    }
+    Ok(allocations)
+}
+
+/// Anti-martingale sizing: shrink after consecutive losing closes, grow cautiously after wins
+/// STREAK_SIZING / STREAK_LOSS_STEP_PCT / STREAK_WIN_STEP_PCT / STREAK_MIN_MULTIPLIER / STREAK_MAX_MULTIPLIER
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StreakSizing {
+    pub enabled: bool,
+    /// Fraction of size removed per consecutive loss (compounding)
+    pub loss_step: f64,
+    /// Fraction of size added per consecutive win
+    pub win_step: f64,
+    pub min_multiplier: f64,
+    pub max_multiplier: f64,
+}
+
+impl Default for StreakSizing {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            loss_step: DEFAULT_STREAK_LOSS_STEP,
+            win_step: DEFAULT_STREAK_WIN_STEP,
+            min_multiplier: DEFAULT_STREAK_MIN_MULTIPLIER,
+            max_multiplier: DEFAULT_STREAK_MAX_MULTIPLIER,
+        }
+    }
+}
+
+impl StreakSizing {
+    pub fn from_env() -> Self {
+        let number = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<f64>().ok()).filter(|v| *v >= 0.0);
+        let defaults = Self::default();
+        let min_multiplier = number("STREAK_MIN_MULTIPLIER").unwrap_or(defaults.min_multiplier).min(1.0);
+        Self {
+            enabled: std::env::var("STREAK_SIZING")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            loss_step: number("STREAK_LOSS_STEP_PCT").map_or(defaults.loss_step, |pct| (pct / 100.0).min(1.0)),
+            win_step: number("STREAK_WIN_STEP_PCT").map_or(defaults.win_step, |pct| pct / 100.0),
+            min_multiplier,
+            max_multiplier: number("STREAK_MAX_MULTIPLIER").unwrap_or(defaults.max_multiplier).max(1.0),
+        }
+    }
+
+    /// Size multiplier after `wins` or `losses` consecutive closes (only one is ever non-zero)
+    pub fn multiplier(&self, wins: u32, losses: u32) -> f64 {
+        if !self.enabled {
+            return 1.0;
+        }
+        let multiplier = if losses > 0 {
+            (1.0 - self.loss_step).powi(losses as i32)
+        } else {
+            1.0 + self.win_step * wins as f64
+        };
+        multiplier.clamp(self.min_multiplier, self.max_multiplier)
+    }
+}
+
+/// Peak the drawdown is measured from: the all-time high, or the highest capital within a rolling window
+/// A rolling window lets an old peak age out once it's behind you instead of blocking trading forever
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawdownWindow {
+    #[default]
+    AllTime,
+    Rolling { secs: i64 },
+}
+
+impl DrawdownWindow {
+    /// "all" / "all_time", or a span like "24h", "7d", "90m" or plain seconds
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim().to_lowercase();
+        if value == "all" || value == "all_time" || value == "alltime" {
+            return Ok(DrawdownWindow::AllTime);
+        }
+        let (number, unit_secs) = match value.chars().last() {
+            Some('d') => (&value[..value.len() - 1], 86_400),
+            Some('h') => (&value[..value.len() - 1], 3_600),
+            Some('m') => (&value[..value.len() - 1], 60),
+            Some('s') => (&value[..value.len() - 1], 1),
+            _ => (value.as_str(), 1),
+        };
+        match number.trim().parse::<i64>() {
+            Ok(n) if n > 0 => Ok(DrawdownWindow::Rolling { secs: n * unit_secs }),
+            _ => Err(format!("Invalid drawdown window {:?} (expected all, 24h, 7d, ...)", value)),
+        }
+    }
+
+    /// DRAWDOWN_WINDOW (default all-time)
+    pub fn from_env() -> Self {
+        match std::env::var("DRAWDOWN_WINDOW") {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|e| {
+                log::warn!("⚠️ {} - measuring drawdown from the all-time peak", e);
+                DrawdownWindow::AllTime
+            }),
+            Err(_) => DrawdownWindow::AllTime,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            DrawdownWindow::AllTime => "all_time".to_string(),
+            DrawdownWindow::Rolling { secs } if secs % 86_400 == 0 => format!("{}d", secs / 86_400),
+            DrawdownWindow::Rolling { secs } if secs % 3_600 == 0 => format!("{}h", secs / 3_600),
+            DrawdownWindow::Rolling { secs } => format!("{}s", secs),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
@@ -22,6 +284,37 @@ pub struct RiskManager {
     pub trade_history: Vec<Trade>,
     pub daily_pnl: f64,
     pub total_pnl: f64,
+    /// Maximum quoted price impact (percent) an order may have
+    pub max_acceptable_slippage: f64,
+    /// Max position value per symbol that keeps the quoted price impact under the cap
+    pub slippage_caps: HashMap<String, f64>,
+    /// Maximum number of concurrently open positions
+    pub max_open_positions: usize,
+    /// Maximum number of open positions in the same symbol
+    pub max_positions_per_symbol: usize,
+    /// Maximum fraction of capital in one group of correlated positions
+    pub max_correlated_exposure: f64,
+    /// Correlation at which two positions count toward the same group
+    pub correlation_threshold: f64,
+    /// Share of the treasury (fraction) a provider may have deployed - overrides the default per provider
+    pub provider_allocations: HashMap<String, f64>,
+    pub default_provider_allocation: f64,
+    /// Cost basis each provider has deployed, per symbol (provider -> symbol -> value)
+    provider_positions: HashMap<String, HashMap<String, f64>>,
+    pub streak_sizing: StreakSizing,
+    /// Consecutive winning / losing closed positions (one of them is always 0)
+    pub win_streak: u32,
+    pub loss_streak: u32,
+    /// Peak calculate_drawdown measures from
+    pub drawdown_window: DrawdownWindow,
+    /// (timestamp, capital) samples for the rolling window - empty when measuring from the all-time peak
+    capital_samples: VecDeque<(i64, f64)>,
+    /// Newest capital sample loaded from persisted portfolio snapshots - samples up to here predate this run
+    persisted_until: Option<i64>,
+    /// Largest share of current_capital one position may take
+    pub max_position_fraction: f64,
+    /// Whether current_capital has been set from the real treasury yet (until then it's the startup placeholder)
+    treasury_synced: bool,
 }
 
 impl RiskManager {
@@ -35,7 +328,285 @@ impl RiskManager {
             trade_history: Vec::new(),
             daily_pnl: 0.0,
             total_pnl: 0.0,
+            max_acceptable_slippage: DEFAULT_MAX_ACCEPTABLE_SLIPPAGE,
+            slippage_caps: HashMap::new(),
+            max_open_positions: DEFAULT_MAX_OPEN_POSITIONS,
+            max_positions_per_symbol: DEFAULT_MAX_POSITIONS_PER_SYMBOL,
+            max_correlated_exposure: DEFAULT_MAX_CORRELATED_EXPOSURE,
+            correlation_threshold: DEFAULT_CORRELATION_THRESHOLD,
+            provider_allocations: HashMap::new(),
+            default_provider_allocation: DEFAULT_PROVIDER_ALLOCATION,
+            provider_positions: HashMap::new(),
+            streak_sizing: StreakSizing::default(),
+            win_streak: 0,
+            loss_streak: 0,
+            drawdown_window: DrawdownWindow::AllTime,
+            capital_samples: VecDeque::new(),
+            persisted_until: None,
+            max_position_fraction: DEFAULT_MAX_POSITION_FRACTION,
+            treasury_synced: false,
+        }
+    }
+    
+    /// Cap each trade at `fraction` of the live treasury (clamped to (0, 1])
+    pub fn with_max_position_fraction(mut self, fraction: f64) -> Self {
+        self.max_position_fraction = if fraction > 0.0 { fraction.min(1.0) } else { DEFAULT_MAX_POSITION_FRACTION };
+        self
+    }
+    
+    /// Most a single trade may cost out of `treasury`
+    pub fn max_position_value(&self, treasury: f64) -> f64 {
+        treasury.max(0.0) * self.max_position_fraction
+    }
+    
+    /// Size and validate against the synced treasury - the first sync replaces the startup placeholder
+    /// capital entirely so drawdown isn't measured from a figure the treasury never held
+    pub fn sync_treasury(&mut self, balance: f64) {
+        if !self.treasury_synced {
+            self.initial_capital = balance;
+            self.current_capital = balance;
+            self.peak_capital = balance;
+            // Samples taken this run measured the placeholder - persisted ones measured the real treasury
+            let persisted_until = self.persisted_until;
+            self.capital_samples.retain(|(at, _)| persisted_until.is_some_and(|until| *at <= until));
+            self.sample_capital(chrono::Utc::now().timestamp());
+            self.treasury_synced = true;
+        }
+        self.current_capital = balance;
+    }
+    
+    /// Start drawdown over from the current capital - the rolling window's history goes with the old peak
+    pub fn reset_peak(&mut self) {
+        self.peak_capital = self.current_capital;
+        self.capital_samples.clear();
+        self.persisted_until = None;
+        self.sample_capital(chrono::Utc::now().timestamp());
+    }
+    
+    /// Open the rolling window with capital recorded before this run (portfolio snapshots' cash balance)
+    pub fn with_capital_history(mut self, mut history: Vec<(i64, f64)>) -> Self {
+        if !matches!(self.drawdown_window, DrawdownWindow::Rolling { .. }) {
+            return self;
+        }
+        history.sort_by_key(|(at, _)| *at);
+        let skip = history.len().saturating_sub(MAX_CAPITAL_SAMPLES);
+        self.persisted_until = history.last().map(|(at, _)| *at);
+        self.capital_samples = history.into_iter().skip(skip).collect();
+        self
+    }
+    
+    pub fn with_drawdown_window(mut self, drawdown_window: DrawdownWindow) -> Self {
+        self.drawdown_window = drawdown_window;
+        self
+    }
+    
+    pub fn with_streak_sizing(mut self, streak_sizing: StreakSizing) -> Self {
+        self.streak_sizing = streak_sizing;
+        self
+    }
+    
+    /// Current streak sizing multiplier (1.0 when streak sizing is off)
+    pub fn streak_multiplier(&self) -> f64 {
+        self.streak_sizing.multiplier(self.win_streak, self.loss_streak)
+    }
+    
+    /// Largest buy the streak allows out of `treasury` - a neutral streak sizes at limit / max multiplier so a
+    /// full winning run reaches the position limit without breaching it
+    pub fn streak_position_value(&self, treasury: f64) -> f64 {
+        let limit = self.max_position_value(treasury);
+        if !self.streak_sizing.enabled {
+            return limit;
         }
+        (limit * self.streak_multiplier() / self.streak_sizing.max_multiplier).min(limit)
+    }
+    
+    /// Give every provider `default_allocation` of the treasury, with per-provider overrides (fractions)
+    pub fn with_provider_allocations(mut self, default_allocation: f64, allocations: HashMap<String, f64>) -> Self {
+        self.default_provider_allocation = default_allocation.clamp(0.0, 1.0);
+        self.provider_allocations = allocations;
+        self
+    }
+    
+    pub fn provider_allocation(&self, provider_id: &str) -> f64 {
+        self.provider_allocations.get(provider_id).copied().unwrap_or(self.default_provider_allocation)
+    }
+    
+    /// Cost basis of `provider_id`'s open positions
+    pub fn provider_deployed(&self, provider_id: &str) -> f64 {
+        self.provider_positions.get(provider_id).map(|positions| positions.values().sum()).unwrap_or(0.0)
+    }
+    
+    /// Cost basis of every provider's open positions
+    pub fn total_provider_deployed(&self) -> f64 {
+        self.provider_positions.values().flat_map(|positions| positions.values()).sum()
+    }
+    
+    /// Capital `provider_id` may still deploy out of a treasury worth `treasury`
+    pub fn provider_capacity(&self, provider_id: &str, treasury: f64) -> f64 {
+        (treasury * self.provider_allocation(provider_id) - self.provider_deployed(provider_id)).max(0.0)
+    }
+    
+    /// Charge a filled buy to the provider that signaled it
+    pub fn record_provider_buy(&mut self, provider_id: &str, symbol: &str, value: f64) {
+        *self.provider_positions.entry(provider_id.to_string()).or_default()
+            .entry(symbol.to_string()).or_insert(0.0) += value;
+    }
+    
+    /// Selling `fraction` of the open position in `symbol` frees that share of every provider's stake in it
+    pub fn release_provider_exposure(&mut self, symbol: &str, fraction: f64) {
+        let fraction = fraction.clamp(0.0, 1.0);
+        for positions in self.provider_positions.values_mut() {
+            if let Some(value) = positions.get_mut(symbol) {
+                *value *= 1.0 - fraction;
+                if *value <= f64::EPSILON {
+                    positions.remove(symbol);
+                }
+            }
+        }
+        self.provider_positions.retain(|_, positions| !positions.is_empty());
+    }
+    
+    /// Allocation and utilization for `providers` plus any other provider with capital deployed
+    pub fn provider_allocation_report(&self, providers: &[String], treasury: f64) -> Vec<ProviderAllocation> {
+        let mut provider_ids: Vec<String> = providers.to_vec();
+        provider_ids.extend(self.provider_positions.keys().cloned());
+        provider_ids.sort();
+        provider_ids.dedup();
+        provider_ids.into_iter().map(|provider_id| {
+            let allocated_capital = treasury * self.provider_allocation(&provider_id);
+            let deployed_capital = self.provider_deployed(&provider_id);
+            ProviderAllocation {
+                allocation_pct: self.provider_allocation(&provider_id) * 100.0,
+                available_capital: (allocated_capital - deployed_capital).max(0.0),
+                utilization_pct: if allocated_capital > 0.0 { deployed_capital / allocated_capital * 100.0 } else { 0.0 },
+                allocated_capital,
+                deployed_capital,
+                provider_id,
+            }
+        }).collect()
+    }
+    
+    /// Override the correlated exposure cap (fraction of capital)
+    pub fn with_correlation_limit(mut self, max_correlated_exposure: f64) -> Self {
+        self.max_correlated_exposure = max_correlated_exposure.clamp(0.0, 1.0);
+        self
+    }
+    
+    /// Value already held in positions correlated with `symbol` (including `symbol` itself)
+    fn correlated_exposure(&self, symbol: &str, exposures: &HashMap<String, f64>, correlations: &CorrelationMatrix) -> (f64, Vec<String>) {
+        let mut group: Vec<String> = exposures.iter()
+            .filter(|(held, value)| **value > 0.0 && correlations.correlation(symbol, held) >= self.correlation_threshold)
+            .map(|(held, _)| held.clone())
+            .collect();
+        group.sort();
+        let value = group.iter().map(|held| exposures[held]).sum();
+        (value, group)
+    }
+    
+    /// Check a new position of `value` in `symbol` against the correlated exposure cap
+    /// `exposures` is the value currently held per symbol. Downsizes when only part of the
+    /// position fits, rejects when less than MIN_VIABLE_SIZE_FRACTION of it would
+    pub fn portfolio_risk(&self, symbol: &str, value: f64, exposures: &HashMap<String, f64>, correlations: &CorrelationMatrix) -> PortfolioRisk {
+        let limit = self.current_capital * self.max_correlated_exposure;
+        let (held, group) = self.correlated_exposure(symbol, exposures, correlations);
+        if held + value <= limit {
+            return PortfolioRisk::Accept;
+        }
+        
+        let room = (limit - held).max(0.0);
+        let reason = format!("Correlated exposure {:.2}% with [{}] would exceed {:.0}% limit",
+            (held + value) / self.current_capital.max(0.01) * 100.0, group.join(", "), self.max_correlated_exposure * 100.0);
+        if room < value * MIN_VIABLE_SIZE_FRACTION {
+            PortfolioRisk::Reject { reason }
+        } else {
+            PortfolioRisk::Downsize { max_value: room, reason }
+        }
+    }
+    
+    /// Largest correlated group among current holdings
+    pub fn concentration(&self, exposures: &HashMap<String, f64>, correlations: &CorrelationMatrix) -> ConcentrationReport {
+        let (exposure_value, symbols) = exposures.keys()
+            .map(|symbol| self.correlated_exposure(symbol, exposures, correlations))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap_or_default();
+        let exposure_pct = exposure_value / self.current_capital.max(0.01) * 100.0;
+        let limit_pct = self.max_correlated_exposure * 100.0;
+        ConcentrationReport {
+            symbols,
+            exposure_value,
+            exposure_pct,
+            limit_pct,
+            over_limit: exposure_pct > limit_pct,
+        }
+    }
+    
+    /// Override the open position caps
+    pub fn with_position_limits(mut self, max_open_positions: usize, max_positions_per_symbol: usize) -> Self {
+        self.max_open_positions = max_open_positions.max(1);
+        self.max_positions_per_symbol = max_positions_per_symbol.max(1);
+        self
+    }
+    
+    /// Explain why opening another position would exceed the position caps (None if it fits)
+    /// `open_positions` counts symbols currently held; `symbol_positions` counts open positions in `symbol`
+    pub fn position_limit_reason(&self, symbol: &str, open_positions: usize, symbol_positions: usize) -> Option<String> {
+        if symbol_positions >= self.max_positions_per_symbol {
+            Some(format!("Per-symbol position limit reached for {} ({}/{})",
+                symbol, symbol_positions, self.max_positions_per_symbol))
+        } else if symbol_positions == 0 && open_positions >= self.max_open_positions {
+            // Adding to an already-held symbol doesn't open a new slot
+            Some(format!("Open position limit reached ({}/{})", open_positions, self.max_open_positions))
+        } else {
+            None
+        }
+    }
+    
+    /// Override the price impact cap (percent)
+    pub fn with_max_acceptable_slippage(mut self, max_slippage_pct: f64) -> Self {
+        self.max_acceptable_slippage = max_slippage_pct.max(0.0);
+        self
+    }
+    
+    /// Largest input amount for `quote` whose estimated price impact stays under `target_impact` (percent)
+    /// The target is clamped to `max_acceptable_slippage`. Impact is assumed to scale linearly with size,
+    /// which holds for trades that are small relative to pool depth. Errors if even the minimum viable
+    /// size would exceed the cap
+    pub fn size_for_slippage(&self, quote: &crate::jupiter_integration::JupiterQuote, target_impact: f64) -> Result<u64, String> {
+        let quoted_amount: u64 = quote.in_amount.parse()
+            .map_err(|_| format!("Invalid quote input amount: {}", quote.in_amount))?;
+        let max_impact = target_impact.min(self.max_acceptable_slippage);
+        let quoted_impact = quote.price_impact_pct.abs();
+        
+        if quoted_impact <= max_impact {
+            return Ok(quoted_amount);
+        }
+        
+        let min_amount = quoted_amount as f64 * MIN_VIABLE_SIZE_FRACTION;
+        let mut amount = quoted_amount as f64;
+        let mut estimated_impact = quoted_impact;
+        while estimated_impact > max_impact {
+            amount *= SLIPPAGE_SIZING_STEP;
+            if amount < min_amount {
+                return Err(format!(
+                    "Price impact {:.2}% exceeds {:.2}% cap even at minimum viable size ({:.0}% of quoted order)",
+                    quoted_impact * MIN_VIABLE_SIZE_FRACTION, max_impact, MIN_VIABLE_SIZE_FRACTION * 100.0
+                ));
+            }
+            estimated_impact = quoted_impact * amount / quoted_amount as f64;
+        }
+        
+        log::info!("📉 Reduced order {} -> {:.0} to keep price impact {:.2}% under {:.2}% cap",
+                  quoted_amount, amount, estimated_impact, max_impact);
+        Ok(amount as u64)
+    }
+    
+    /// Record the slippage-safe position value for a symbol
+    pub fn set_slippage_cap(&mut self, symbol: &str, max_position_value: f64) {
+        self.slippage_caps.insert(symbol.to_string(), max_position_value);
+    }
+    
+    pub fn slippage_cap(&self, symbol: &str) -> Option<f64> {
+        self.slippage_caps.get(symbol).copied()
     }
     
     /// Sync capital with trading engine balance (for paper trading)
@@ -46,34 +617,24 @@ impl RiskManager {
             // This prevents false drawdown calculations (e.g., 10000 -> 10 causing 99.9% drawdown)
             self.initial_capital = initial_balance;
             self.current_capital = balance;
-            self.peak_capital = balance; // Reset peak to current balance for fresh start
+            self.reset_peak(); // Reset peak to current balance for fresh start
             log::debug!("   🔄 Reset peak_capital to {:.8} for paper trading", balance);
         } else {
             // Just update current capital to match balance
             self.current_capital = balance;
             self.peak_capital = balance.max(self.peak_capital);
+            self.sample_capital(chrono::Utc::now().timestamp());
         }
     }
     
     pub fn validate_trade(&self, symbol: &str, size: f64, price: f64, confidence: f64) -> bool {
         let position_value = size * price;
-        let current_drawdown = self.calculate_drawdown();
-        
-        // CRITICAL FIX: In paper trading, if peak_capital is way out of sync (e.g., 10000 vs 10),
-        // recalculate drawdown based on current capital only to prevent false rejections
-        let effective_drawdown = if self.peak_capital > self.current_capital * 10.0 && self.current_capital < 1000.0 {
-            // Likely paper trading with mismatched peak_capital - use 0% drawdown for fresh start
-            log::debug!("   ⚠️ Peak capital ({:.2}) much larger than current ({:.2}) - assuming paper trading reset, using 0% drawdown", 
-                       self.peak_capital, self.current_capital);
-            0.0
-        } else {
-            current_drawdown
-        };
+        let effective_drawdown = self.effective_drawdown();
         
         let is_valid = position_value > 0.0 
             && effective_drawdown < self.max_drawdown
             && confidence > 0.5
-            && position_value <= self.current_capital * 0.1;
+            && position_value <= self.max_position_value(self.current_capital);
         
         if !is_valid {
             log::warn!("🔍 Trade validation FAILED for {}: size=${:.2}, drawdown={:.2}%, max_drawdown={:.2}%, confidence={:.2}%, position_pct={:.2}%", 
@@ -86,9 +647,9 @@ impl RiskManager {
             if confidence <= 0.5 {
                 log::warn!("   ❌ REJECTED: Confidence {:.2}% below minimum 50%", confidence * 100.0);
             }
-            if position_value > self.current_capital * 0.1 {
-                log::warn!("   ❌ REJECTED: Position size {:.2}% exceeds 10% limit", 
-                          (position_value / self.current_capital.max(0.01)) * 100.0);
+            if position_value > self.max_position_value(self.current_capital) {
+                log::warn!("   ❌ REJECTED: Position size {:.2}% exceeds {:.0}% limit", 
+                          (position_value / self.current_capital.max(0.01)) * 100.0, self.max_position_fraction * 100.0);
             }
         } else {
             log::info!("🔍 Trade validation PASSED for {}: size=${:.2}, drawdown={:.2}%, confidence={:.2}%", 
@@ -98,6 +659,39 @@ impl RiskManager {
         is_valid
     }
     
+    /// Drawdown used for trade validation
+    /// CRITICAL FIX: In paper trading, if peak_capital is way out of sync (e.g., 10000 vs 10),
+    /// recalculate drawdown based on current capital only to prevent false rejections
+    fn effective_drawdown(&self) -> f64 {
+        if self.peak_capital > self.current_capital * 10.0 && self.current_capital < 1000.0 {
+            // Likely paper trading with mismatched peak_capital - use 0% drawdown for fresh start
+            log::debug!("   ⚠️ Peak capital ({:.2}) much larger than current ({:.2}) - assuming paper trading reset, using 0% drawdown", 
+                       self.peak_capital, self.current_capital);
+            0.0
+        } else {
+            self.calculate_drawdown()
+        }
+    }
+    
+    /// Explain why validate_trade would reject a trade (None if it would pass)
+    pub fn rejection_reason(&self, size: f64, price: f64, confidence: f64) -> Option<String> {
+        let position_value = size * price;
+        let effective_drawdown = self.effective_drawdown();
+        
+        if effective_drawdown >= self.max_drawdown {
+            Some(format!("Drawdown {:.2}% exceeds max {:.2}%", effective_drawdown * 100.0, self.max_drawdown * 100.0))
+        } else if position_value > self.max_position_value(self.current_capital) {
+            Some(format!("Position size {:.2}% exceeds {:.0}% position limit",
+                (position_value / self.current_capital.max(0.01)) * 100.0, self.max_position_fraction * 100.0))
+        } else if confidence <= 0.5 {
+            Some(format!("Confidence {:.2}% below minimum 50%", confidence * 100.0))
+        } else if position_value <= 0.0 {
+            Some("Position value must be positive".to_string())
+        } else {
+            None
+        }
+    }
+    
     pub fn calculate_position_size(&self, confidence: f64, price: f64) -> f64 {
         // Improved Kelly Criterion with win rate consideration
         let historical_win_rate = if self.trade_history.len() > 10 {
@@ -110,22 +704,31 @@ impl RiskManager {
         // Kelly formula: f = (bp - q) / b where b=1, p=win_rate, q=1-win_rate
         let kelly_fraction = ((historical_win_rate * 2.0) - 1.0).max(0.0);
         
-        // Apply confidence multiplier and safety factor (50% Kelly)
-        let adjusted_kelly = kelly_fraction * confidence * 0.5;
+        // Apply confidence multiplier, safety factor (50% Kelly) and the win/loss streak
+        let adjusted_kelly = kelly_fraction * confidence * 0.5 * self.streak_multiplier();
         
         // Calculate portfolio heat (total exposure)
         let total_exposure: f64 = self.position_sizes.values().sum();
         let available_capacity = (self.current_capital * 0.3) - total_exposure; // Max 30% total exposure
         
-        // Position size limited by available capacity and individual position limit (10%)
-        let max_position_value = (self.current_capital * adjusted_kelly).min(self.current_capital * 0.1);
+        // Position size limited by available capacity and individual position limit (max_position_fraction)
+        let max_position_value = (self.current_capital * adjusted_kelly).min(self.max_position_value(self.current_capital));
         let capacity_limited = max_position_value.min(available_capacity.max(0.0));
         
         let shares = capacity_limited / price;
         shares.max(0.0)
     }
     
+    /// Drawdown from the peak `drawdown_window` selects - what trade validation and the drawdown guard act on
     pub fn calculate_drawdown(&self) -> f64 {
+        match self.drawdown_window {
+            DrawdownWindow::AllTime => self.all_time_drawdown(),
+            DrawdownWindow::Rolling { .. } => self.windowed_drawdown(chrono::Utc::now().timestamp()).unwrap_or(0.0),
+        }
+    }
+    
+    /// Drawdown from the all-time peak, whatever the configured window
+    pub fn all_time_drawdown(&self) -> f64 {
         if self.peak_capital > 0.0 {
             (self.peak_capital - self.current_capital) / self.peak_capital
         } else {
@@ -133,6 +736,48 @@ impl RiskManager {
         }
     }
     
+    /// Highest capital seen within the rolling window (None when measuring from the all-time peak)
+    pub fn window_peak_capital(&self, now: i64) -> Option<f64> {
+        let DrawdownWindow::Rolling { secs } = self.drawdown_window else {
+            return None;
+        };
+        let window_start = now - secs;
+        // Capital recorded just before the window opened was still in effect when it did
+        let at_window_start = self.capital_samples.iter().rev()
+            .find(|(at, _)| *at < window_start)
+            .map(|(_, capital)| *capital);
+        let peak = self.capital_samples.iter()
+            .filter(|(at, _)| *at >= window_start)
+            .map(|(_, capital)| *capital)
+            .chain(at_window_start)
+            .fold(self.current_capital, f64::max);
+        Some(peak)
+    }
+    
+    /// Drawdown from the rolling window's peak (None when measuring from the all-time peak)
+    pub fn windowed_drawdown(&self, now: i64) -> Option<f64> {
+        self.window_peak_capital(now).map(|peak| if peak > 0.0 {
+            (peak - self.current_capital) / peak
+        } else {
+            0.0
+        })
+    }
+    
+    /// Add the current capital to the rolling window's history - called on every portfolio snapshot and trade
+    pub fn sample_capital(&mut self, now: i64) {
+        let DrawdownWindow::Rolling { secs } = self.drawdown_window else {
+            return;
+        };
+        // Keep the newest sample from before the window - it's the capital the window opened with
+        while self.capital_samples.len() > 1 && self.capital_samples[1].0 < now - secs {
+            self.capital_samples.pop_front();
+        }
+        if self.capital_samples.len() >= MAX_CAPITAL_SAMPLES {
+            self.capital_samples.pop_front();
+        }
+        self.capital_samples.push_back((now, self.current_capital));
+    }
+    
     /// Calculate time-weighted drawdown (recent losses weighted more heavily)
     pub fn calculate_time_weighted_drawdown(&self) -> f64 {
         if self.trade_history.len() < 2 {
@@ -166,11 +811,28 @@ impl RiskManager {
         self.total_pnl += trade.pnl;
         self.daily_pnl += trade.pnl;
         self.peak_capital = self.peak_capital.max(self.current_capital);
+        self.sample_capital(chrono::Utc::now().timestamp());
+        
+        // STREAKS: Only closes carry P&L
+        if trade.action.eq_ignore_ascii_case("SELL") {
+            self.record_close(trade.pnl);
+        }
         
         log::info!("📝 Recorded trade: {} {} {} PnL: ${:.2}", 
                  trade.action, trade.size, trade.symbol, trade.pnl);
     }
     
+    /// Count a close's realized P&L toward the win/loss streak - a breakeven close leaves the streak as it was
+    pub fn record_close(&mut self, pnl: f64) {
+        if pnl > 0.0 {
+            self.win_streak += 1;
+            self.loss_streak = 0;
+        } else if pnl < 0.0 {
+            self.loss_streak += 1;
+            self.win_streak = 0;
+        }
+    }
+    
     pub fn get_performance_metrics(&self) -> HashMap<String, f64> {
         let total_return = if self.initial_capital > 0.0 {
             (self.current_capital - self.initial_capital) / self.initial_capital * 100.0
@@ -188,7 +850,7 @@ impl RiskManager {
         let mut metrics = HashMap::new();
         metrics.insert("total_return".to_string(), total_return);
         metrics.insert("current_capital".to_string(), self.current_capital);
-        metrics.insert("max_drawdown".to_string(), self.calculate_drawdown() * 100.0);
+        metrics.insert("max_drawdown".to_string(), self.all_time_drawdown() * 100.0);
         metrics.insert("sharpe_ratio".to_string(), 1.5);
         metrics.insert("win_rate".to_string(), win_rate);
         metrics.insert("daily_pnl".to_string(), self.daily_pnl);
@@ -198,3 +860,216 @@ impl RiskManager {
         metrics
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jupiter_integration::JupiterQuote;
+
+    fn quote(in_amount: u64, price_impact_pct: f64) -> JupiterQuote {
+        JupiterQuote {
+            input_mint: "So11111111111111111111111111111111111111112".to_string(),
+            output_mint: "MEME".to_string(),
+            in_amount: in_amount.to_string(),
+            out_amount: "1000000".to_string(),
+            price_impact_pct,
+            route_plan: vec![],
+        }
+    }
+
+    #[test]
+    fn test_size_for_slippage() {
+        let risk_manager = RiskManager::new(10000.0, 0.1).with_max_acceptable_slippage(1.0);
+
+        // Already within the cap - full size
+        assert_eq!(risk_manager.size_for_slippage(&quote(100_000_000, 0.5), 5.0), Ok(100_000_000));
+
+        // 4% impact shrinks to roughly a quarter of the order; the 5% target is clamped to the 1% cap
+        let sized = risk_manager.size_for_slippage(&quote(100_000_000, 4.0), 5.0).unwrap();
+        assert!(sized < 25_000_000 && sized > 15_000_000, "sized = {}", sized);
+
+        // 15% impact would still be 1.5% at the minimum viable size
+        assert!(risk_manager.size_for_slippage(&quote(100_000_000, 15.0), 5.0).is_err());
+    }
+
+    #[test]
+    fn test_correlated_exposure_limit() {
+        let risk_manager = RiskManager::new(100.0, 0.1).with_correlation_limit(0.5);
+        let correlations = CorrelationMatrix::default();
+        let mut exposures = HashMap::new();
+        exposures.insert("BONK".to_string(), 30.0);
+        exposures.insert("SOL/USDC".to_string(), 40.0);
+
+        // Memecoins default to highly correlated; SOL is not in their group
+        assert!(is_sol_pegged_memecoin("WIF/SOL") && !is_sol_pegged_memecoin("SOL/USDC"));
+        assert_eq!(risk_manager.portfolio_risk("WIF", 10.0, &exposures, &correlations), PortfolioRisk::Accept);
+        match risk_manager.portfolio_risk("WIF", 30.0, &exposures, &correlations) {
+            PortfolioRisk::Downsize { max_value, .. } => assert!((max_value - 20.0).abs() < 1e-9),
+            other => panic!("expected downsize, got {:?}", other),
+        }
+        exposures.insert("POPCAT".to_string(), 19.0);
+        assert!(matches!(risk_manager.portfolio_risk("WIF", 30.0, &exposures, &correlations), PortfolioRisk::Reject { .. }));
+        assert_eq!(risk_manager.portfolio_risk("ETH/USDC", 30.0, &exposures, &correlations), PortfolioRisk::Accept);
+
+        let report = risk_manager.concentration(&exposures, &correlations);
+        assert_eq!(report.symbols, vec!["BONK".to_string(), "POPCAT".to_string()]);
+        assert!((report.exposure_pct - 49.0).abs() < 1e-9 && !report.over_limit);
+
+        // Enough history replaces the default: these two move in opposite directions
+        let up: Vec<f64> = (0..20).map(|i| 1.0 + if i % 2 == 0 { 0.0 } else { 0.1 }).collect();
+        let down: Vec<f64> = (0..20).map(|i| 1.0 + if i % 2 == 0 { 0.1 } else { 0.0 }).collect();
+        let history = HashMap::from([("BONK".to_string(), up), ("WIF".to_string(), down)]);
+        let estimated = CorrelationMatrix::from_price_history(&history);
+        assert!(estimated.correlation("BONK", "WIF") < -0.9);
+        assert!((estimated.correlation("WIF", "POPCAT") - MEMECOIN_DEFAULT_CORRELATION).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_position_limits() {
+        let risk_manager = RiskManager::new(10000.0, 0.1).with_position_limits(3, 2);
+
+        assert!(risk_manager.position_limit_reason("BONK", 2, 0).is_none());
+        assert!(risk_manager.position_limit_reason("BONK", 3, 0).unwrap().contains("Open position limit"));
+        // Stacking onto a held symbol is bounded by the per-symbol cap, not the global one
+        assert!(risk_manager.position_limit_reason("BONK", 3, 1).is_none());
+        assert!(risk_manager.position_limit_reason("BONK", 3, 2).unwrap().contains("Per-symbol position limit"));
+    }
+
+    #[test]
+    fn test_provider_capital_allocation() {
+        let allocations = parse_provider_allocations("memecoin_monitor:10, oracle_monitor:40").unwrap();
+        assert!(parse_provider_allocations("memecoin_monitor:140").is_err());
+        let mut risk_manager = RiskManager::new(10000.0, 0.1).with_provider_allocations(0.25, allocations);
+
+        // 10% of a 100 SOL treasury, partly used by an open position
+        assert!((risk_manager.provider_capacity("memecoin_monitor", 100.0) - 10.0).abs() < 1e-9);
+        risk_manager.record_provider_buy("memecoin_monitor", "BONK", 6.0);
+        risk_manager.record_provider_buy("oracle_monitor", "BONK", 4.0);
+        assert!((risk_manager.provider_capacity("memecoin_monitor", 100.0) - 4.0).abs() < 1e-9);
+        // Other providers keep their own slice, unlisted ones get the default
+        assert!((risk_manager.provider_capacity("oracle_monitor", 100.0) - 36.0).abs() < 1e-9);
+        assert!((risk_manager.provider_capacity("signal_trader", 100.0) - 25.0).abs() < 1e-9);
+
+        // Selling half the position frees half of each provider's stake
+        risk_manager.release_provider_exposure("BONK", 0.5);
+        assert!((risk_manager.provider_deployed("memecoin_monitor") - 3.0).abs() < 1e-9);
+        risk_manager.release_provider_exposure("BONK", 1.0);
+        assert_eq!(risk_manager.total_provider_deployed(), 0.0);
+
+        let report = risk_manager.provider_allocation_report(&["memecoin_monitor".to_string()], 100.0);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].allocation_pct, 10.0);
+        assert_eq!(report[0].utilization_pct, 0.0);
+    }
+
+    #[test]
+    fn test_rolling_drawdown_window() {
+        assert_eq!(DrawdownWindow::parse("all"), Ok(DrawdownWindow::AllTime));
+        assert_eq!(DrawdownWindow::parse("24h"), Ok(DrawdownWindow::Rolling { secs: 86_400 }));
+        assert_eq!(DrawdownWindow::parse("7d").unwrap().label(), "7d");
+        assert!(DrawdownWindow::parse("soon").is_err());
+
+        let mut risk_manager = RiskManager::new(100.0, 0.1)
+            .with_drawdown_window(DrawdownWindow::Rolling { secs: 86_400 });
+        let start = 1_000_000;
+        // Early spike to 200, then back down to 150 and holding there
+        risk_manager.current_capital = 200.0;
+        risk_manager.peak_capital = 200.0;
+        risk_manager.sample_capital(start);
+        risk_manager.current_capital = 150.0;
+        risk_manager.sample_capital(start + 3_600);
+
+        // Inside the window the spike still counts
+        assert!((risk_manager.windowed_drawdown(start + 7_200).unwrap() - 0.25).abs() < 1e-9);
+        // Two days on the spike has aged out; the 150 it fell to opened the window
+        let later = start + 2 * 86_400;
+        assert_eq!(risk_manager.window_peak_capital(later), Some(150.0));
+        assert_eq!(risk_manager.windowed_drawdown(later), Some(0.0));
+        assert!((risk_manager.all_time_drawdown() - 0.25).abs() < 1e-9);
+
+        let all_time = RiskManager::new(100.0, 0.1);
+        assert!(all_time.windowed_drawdown(later).is_none());
+
+        // A peak reset takes the window's history with it
+        risk_manager.current_capital = 100.0;
+        risk_manager.reset_peak();
+        assert_eq!(risk_manager.windowed_drawdown(chrono::Utc::now().timestamp()), Some(0.0));
+
+        // Persisted history opens the window and survives the first treasury sync; placeholder samples don't
+        let now = chrono::Utc::now().timestamp();
+        let mut restarted = RiskManager::new(10000.0, 0.1)
+            .with_drawdown_window(DrawdownWindow::Rolling { secs: 86_400 })
+            .with_capital_history(vec![(now - 600, 200.0), (now - 3_600, 180.0)]);
+        restarted.sample_capital(now - 60); // The 10000 placeholder
+        restarted.sync_treasury(150.0);
+        assert_eq!(restarted.window_peak_capital(now), Some(200.0));
+        assert!((restarted.windowed_drawdown(now).unwrap() - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_streak_sizing() {
+        let sizing = StreakSizing { enabled: true, ..StreakSizing::default() };
+        let mut risk_manager = RiskManager::new(10000.0, 0.1).with_streak_sizing(sizing);
+        let base = RiskManager::new(10000.0, 0.1).calculate_position_size(0.8, 100.0);
+        let close = |pnl: f64| Trade {
+            id: "t".to_string(),
+            symbol: "BONK".to_string(),
+            action: "SELL".to_string(),
+            size: 1.0,
+            price: 1.0,
+            timestamp: 0,
+            pnl,
+        };
+
+        // Two losses compound: 0.75 * 0.75
+        risk_manager.record_trade(close(-1.0));
+        risk_manager.record_trade(close(-1.0));
+        assert_eq!((risk_manager.win_streak, risk_manager.loss_streak), (0, 2));
+        assert!((risk_manager.streak_multiplier() - 0.5625).abs() < 1e-9);
+        assert!(risk_manager.calculate_position_size(0.8, 100.0) < base);
+
+        // Buys and breakeven closes don't touch the streak; a win resets the losing run
+        risk_manager.record_trade(Trade { action: "BUY".to_string(), ..close(0.0) });
+        risk_manager.record_trade(close(0.0));
+        assert_eq!(risk_manager.loss_streak, 2);
+        risk_manager.record_trade(close(1.0));
+        assert_eq!((risk_manager.win_streak, risk_manager.loss_streak), (1, 0));
+        assert!((risk_manager.streak_multiplier() - 1.1).abs() < 1e-9);
+
+        // A winning run grows marketplace buys toward the position limit, never past it
+        let limit = risk_manager.max_position_value(10000.0);
+        assert!((risk_manager.streak_position_value(10000.0) - limit * 1.1 / DEFAULT_STREAK_MAX_MULTIPLIER).abs() < 1e-9);
+        for _ in 0..20 {
+            risk_manager.record_close(1.0);
+        }
+        assert!((risk_manager.streak_position_value(10000.0) - limit).abs() < 1e-9);
+        risk_manager.record_close(-1.0);
+        assert_eq!((risk_manager.win_streak, risk_manager.loss_streak), (0, 1));
+        assert_eq!(RiskManager::new(10000.0, 0.1).streak_position_value(10000.0), limit);
+        
+        // Bounded on both sides; disabled sizing is always 1x
+        assert_eq!(sizing.multiplier(20, 0), DEFAULT_STREAK_MAX_MULTIPLIER);
+        assert_eq!(sizing.multiplier(0, 20), DEFAULT_STREAK_MIN_MULTIPLIER);
+        assert_eq!(StreakSizing::default().multiplier(0, 5), 1.0);
+    }
+
+    #[test]
+    fn test_position_limit_follows_synced_treasury() {
+        let mut risk_manager = RiskManager::new(10000.0, 0.1).with_max_position_fraction(0.05);
+        // Synced treasury is $500, not the $10k startup figure
+        risk_manager.sync_treasury(500.0);
+        assert_eq!(risk_manager.peak_capital, 500.0);
+        assert!((risk_manager.max_position_value(risk_manager.current_capital) - 25.0).abs() < 1e-9);
+        assert!(risk_manager.validate_trade("SOL", 0.2, 100.0, 0.9));
+        assert!(!risk_manager.validate_trade("SOL", 0.3, 100.0, 0.9));
+        assert!(risk_manager.rejection_reason(0.3, 100.0, 0.9).unwrap().contains("5% position limit"));
+
+        // Later syncs track the treasury without resetting the drawdown peak
+        risk_manager.sync_treasury(480.0);
+        assert_eq!(risk_manager.current_capital, 480.0);
+        assert_eq!(risk_manager.peak_capital, 500.0);
+
+        assert_eq!(RiskManager::new(100.0, 0.1).with_max_position_fraction(0.0).max_position_fraction, DEFAULT_MAX_POSITION_FRACTION);
+        assert_eq!(RiskManager::new(100.0, 0.1).with_max_position_fraction(3.0).max_position_fraction, 1.0);
+    }
+}
@@ -6,6 +6,102 @@ use solana_sdk::{
     transaction::Transaction,
     native_token::LAMPORTS_PER_SOL,
 };
+use std::collections::HashMap;
+
+/// Owner programs of SPL token accounts - pump.fun and newer mints live under Token-2022
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Where a transaction stands on-chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureConfirmation {
+    pub slot: u64,
+    pub confirmed: bool,
+    pub finalized: bool,
+    pub error: Option<String>, // Landed but the transaction failed
+}
+
+/// What a confirmed swap actually moved for the wallet, in UI units (both positive)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapFill {
+    pub token_amount: f64,
+    pub quote_amount: f64,
+    /// Network fee the transaction paid (lamports) - base plus priority fee, from its meta
+    pub fee_lamports: u64,
+}
+
+impl SwapFill {
+    /// Average price per token, in the quote token
+    pub fn average_price(&self) -> Option<f64> {
+        (self.token_amount > 0.0).then(|| self.quote_amount / self.token_amount)
+    }
+}
+
+/// Read a swap's fill from a `getTransaction` result (json encoding) - the balance changes of
+/// `owner` in `token_mint` and `quote_mint`. Native SOL counts lamports (net of the fee the
+/// owner paid as fee payer) plus any wrapped SOL
+pub fn parse_swap_fill(transaction: &serde_json::Value, owner: &str, token_mint: &str, quote_mint: &str) -> Option<SwapFill> {
+    let meta = transaction.get("meta")?;
+    if meta.get("err").is_some_and(|err| !err.is_null()) {
+        return None;
+    }
+    let token_amount = owner_balance_delta(transaction, owner, token_mint)?.abs();
+    let quote_amount = owner_balance_delta(transaction, owner, quote_mint)?.abs();
+    let fee_lamports = meta["fee"].as_u64().unwrap_or(0);
+    (token_amount > 0.0).then_some(SwapFill { token_amount, quote_amount, fee_lamports })
+}
+
+/// Total UI balance across the token accounts in a `getTokenAccountsByOwner` result (jsonParsed encoding)
+/// No accounts means a zero balance, not an error - the account is created on the first deposit
+pub fn parse_token_balance(response: &serde_json::Value) -> Option<f64> {
+    let accounts = response.get("value")?.as_array()?;
+    Some(accounts.iter()
+        .filter_map(|account| account["account"]["data"]["parsed"]["info"]["tokenAmount"]["uiAmountString"]
+            .as_str()?.parse::<f64>().ok())
+        .sum())
+}
+
+/// UI balance per mint across every token account in a `getTokenAccountsByOwner` result (jsonParsed encoding)
+pub fn parse_token_balances(response: &serde_json::Value) -> Option<HashMap<String, f64>> {
+    let accounts = response.get("value")?.as_array()?;
+    let mut balances = HashMap::new();
+    for account in accounts {
+        let info = &account["account"]["data"]["parsed"]["info"];
+        let (Some(mint), Some(amount)) = (
+            info["mint"].as_str(),
+            info["tokenAmount"]["uiAmountString"].as_str().and_then(|a| a.parse::<f64>().ok()),
+        ) else {
+            continue;
+        };
+        *balances.entry(mint.to_string()).or_insert(0.0) += amount;
+    }
+    Some(balances)
+}
+
+fn owner_balance_delta(transaction: &serde_json::Value, owner: &str, mint: &str) -> Option<f64> {
+    let meta = transaction.get("meta")?;
+    let token_total = |field: &str| -> f64 {
+        meta.get(field)
+            .and_then(|balances| balances.as_array())
+            .map(|balances| balances.iter()
+                .filter(|b| b["owner"].as_str() == Some(owner) && b["mint"].as_str() == Some(mint))
+                .filter_map(|b| b["uiTokenAmount"]["uiAmountString"].as_str()?.parse::<f64>().ok())
+                .sum())
+            .unwrap_or(0.0)
+    };
+    let mut delta = token_total("postTokenBalances") - token_total("preTokenBalances");
+    
+    if mint == crate::dex_aggregator::SOL_MINT {
+        let account_keys = transaction["transaction"]["message"]["accountKeys"].as_array()?;
+        let index = account_keys.iter().position(|key| key.as_str() == Some(owner))?;
+        let pre = meta["preBalances"].get(index)?.as_i64()?;
+        let post = meta["postBalances"].get(index)?.as_i64()?;
+        // The fee payer (first key) paid the network fee - that isn't part of the swap
+        let fee = if index == 0 { meta["fee"].as_i64().unwrap_or(0) } else { 0 };
+        delta += (post - pre + fee) as f64 / LAMPORTS_PER_SOL as f64;
+    }
+    Some(delta)
+}
 
 /// Solana RPC client wrapper for blockchain operations
 pub struct SolanaRpcClient {
@@ -128,6 +224,15 @@ impl SolanaRpcClient {
             .map_err(|e| format!("Failed to get slot: {}", e))
     }
 
+    /// Per-slot prioritization fees (micro-lamports per CU) over the last ~150 slots
+    /// for transactions locking `accounts` - empty means network-wide
+    pub async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> Result<Vec<u64>, String> {
+        self.client
+            .get_recent_prioritization_fees(accounts)
+            .map(|fees| fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+            .map_err(|e| format!("Failed to get recent prioritization fees: {}", e))
+    }
+
     /// Get block time for a slot
     pub async fn get_block_time(&self, slot: u64) -> Result<i64, String> {
         self.client
@@ -149,6 +254,85 @@ impl SolanaRpcClient {
         }
     }
 
+    /// On-chain outcome of a transaction: None if the cluster hasn't seen it,
+    /// Some(true) if it executed successfully, Some(false) if it landed but failed
+    pub async fn get_signature_result(&self, signature: &Signature) -> Result<Option<bool>, String> {
+        self.client
+            .get_signature_status(signature)
+            .map(|status| status.map(|result| result.is_ok()))
+            .map_err(|e| format!("Failed to get signature status: {}", e))
+    }
+
+    /// Slot and commitment level of a transaction, None if the cluster hasn't seen it
+    pub async fn get_signature_confirmation(&self, signature: &Signature) -> Result<Option<SignatureConfirmation>, String> {
+        let statuses = self.client
+            .get_signature_statuses(&[*signature])
+            .map_err(|e| format!("Failed to get signature status: {}", e))?;
+        
+        Ok(statuses.value.into_iter().next().flatten().map(|status| SignatureConfirmation {
+            slot: status.slot,
+            confirmed: status.satisfies_commitment(CommitmentConfig::confirmed()),
+            finalized: status.satisfies_commitment(CommitmentConfig::finalized()),
+            error: status.err.map(|e| e.to_string()),
+        }))
+    }
+
+    /// Token and quote amounts a confirmed swap actually moved for `owner`
+    /// None if the transaction isn't available yet or moved nothing in `token_mint`
+    pub async fn get_swap_fill(&self, signature: &Signature, owner: &str, token_mint: &str, quote_mint: &str) -> Result<Option<SwapFill>, String> {
+        let transaction: serde_json::Value = self.client
+            .send(
+                solana_client::rpc_request::RpcRequest::GetTransaction,
+                serde_json::json!([signature.to_string(), {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }]),
+            )
+            .map_err(|e| format!("Failed to get transaction: {}", e))?;
+        if transaction.is_null() {
+            return Ok(None);
+        }
+        Ok(parse_swap_fill(&transaction, owner, token_mint, quote_mint))
+    }
+
+    /// Balance of `owner` in SPL token `mint`, in UI units
+    pub async fn get_token_balance(&self, owner: &Pubkey, mint: &str) -> Result<f64, String> {
+        let response: serde_json::Value = self.client
+            .send(
+                solana_client::rpc_request::RpcRequest::GetTokenAccountsByOwner,
+                serde_json::json!([owner.to_string(), {"mint": mint}, {
+                    "encoding": "jsonParsed",
+                    "commitment": "confirmed",
+                }]),
+            )
+            .map_err(|e| format!("Failed to get token accounts: {}", e))?;
+        parse_token_balance(&response)
+            .ok_or_else(|| format!("Unexpected getTokenAccountsByOwner response for {}", owner))
+    }
+
+    /// Every SPL token (and Token-2022) balance `owner` holds, by mint, in UI units
+    pub async fn get_token_balances(&self, owner: &Pubkey) -> Result<HashMap<String, f64>, String> {
+        let mut balances = HashMap::new();
+        for program_id in [SPL_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            let response: serde_json::Value = self.client
+                .send(
+                    solana_client::rpc_request::RpcRequest::GetTokenAccountsByOwner,
+                    serde_json::json!([owner.to_string(), {"programId": program_id}, {
+                        "encoding": "jsonParsed",
+                        "commitment": "confirmed",
+                    }]),
+                )
+                .map_err(|e| format!("Failed to get token accounts: {}", e))?;
+            let program_balances = parse_token_balances(&response)
+                .ok_or_else(|| format!("Unexpected getTokenAccountsByOwner response for {}", owner))?;
+            for (mint, amount) in program_balances {
+                *balances.entry(mint).or_insert(0.0) += amount;
+            }
+        }
+        Ok(balances)
+    }
+
     /// Get the RPC client reference
     pub fn client(&self) -> &RpcClient {
         &self.client
@@ -165,6 +349,59 @@ mod tests {
         assert_eq!(client.commitment, CommitmentConfig::confirmed());
     }
 
+    #[test]
+    fn test_parse_swap_fill_from_balance_deltas() {
+        let owner = "Owner1111111111111111111111111111111111111";
+        let mint = "MemeMint11111111111111111111111111111111111";
+        let token_balance = |amount: &str| serde_json::json!({
+            "accountIndex": 2, "mint": mint, "owner": owner,
+            "uiTokenAmount": {"amount": "0", "decimals": 6, "uiAmountString": amount}
+        });
+        // Asked for 1000 tokens, got 800 for 0.5 SOL (+5000 lamport fee)
+        let transaction = serde_json::json!({
+            "meta": {
+                "err": null,
+                "fee": 5000,
+                "preBalances": [2_000_000_000u64, 0, 0],
+                "postBalances": [1_499_995_000u64, 0, 0],
+                "preTokenBalances": [token_balance("100")],
+                "postTokenBalances": [token_balance("900")],
+            },
+            "transaction": {"message": {"accountKeys": [owner, "Pool", "Ata"]}}
+        });
+        let fill = parse_swap_fill(&transaction, owner, mint, crate::dex_aggregator::SOL_MINT).unwrap();
+        assert!((fill.token_amount - 800.0).abs() < 1e-9);
+        assert!((fill.quote_amount - 0.5).abs() < 1e-9);
+        assert_eq!(fill.fee_lamports, 5000);
+        assert!((fill.average_price().unwrap() - 0.000625).abs() < 1e-12);
+
+        // Failed transactions moved nothing
+        let mut failed = transaction.clone();
+        failed["meta"]["err"] = serde_json::json!({"InstructionError": [0, "Custom"]});
+        assert!(parse_swap_fill(&failed, owner, mint, crate::dex_aggregator::SOL_MINT).is_none());
+    }
+
+    #[test]
+    fn test_parse_token_balance() {
+        let account = |amount: &str| serde_json::json!({
+            "pubkey": "Ata",
+            "account": {"data": {"parsed": {"info": {"tokenAmount": {"uiAmountString": amount}}}}}
+        });
+        let response = serde_json::json!({"context": {"slot": 1}, "value": [account("125.5"), account("4.5")]});
+        assert_eq!(parse_token_balance(&response), Some(130.0));
+        assert_eq!(parse_token_balance(&serde_json::json!({"value": []})), Some(0.0));
+        assert!(parse_token_balance(&serde_json::json!({"error": "x"})).is_none());
+
+        // Per-mint totals for the portfolio reconciliation
+        let minted = |mint: &str, amount: &str| serde_json::json!({
+            "account": {"data": {"parsed": {"info": {"mint": mint, "tokenAmount": {"uiAmountString": amount}}}}}
+        });
+        let response = serde_json::json!({"value": [minted("A", "1.5"), minted("B", "2"), minted("A", "0.5")]});
+        let balances = parse_token_balances(&response).unwrap();
+        assert_eq!(balances.get("A"), Some(&2.0));
+        assert_eq!(balances.get("B"), Some(&2.0));
+    }
+
     #[test]
     fn test_rpc_client_interface() {
         // Just test that we can create a client without connecting
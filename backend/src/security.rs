@@ -1,7 +1,19 @@
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
 use std::time::{Duration, Instant};
+use warp::http::{Method, StatusCode};
+use warp::{Filter, Rejection, Reply};
+
+/// Requests per client IP per minute on the HTTP APIs (API_RATE_LIMIT_PER_MIN)
+pub const DEFAULT_API_RATE_LIMIT_PER_MIN: usize = 120;
+/// Requests per client IP per minute on mutating or expensive routes (API_STRICT_RATE_LIMIT_PER_MIN)
+pub const DEFAULT_API_STRICT_RATE_LIMIT_PER_MIN: usize = 20;
+
+/// GET routes that call out to paid or rate-limited upstreams - limited like mutations
+const EXPENSIVE_READ_PREFIXES: &[&str] = &["/jupiter/", "/oracle/"];
 
 /// Simple rate limiter for API endpoints
 pub struct RateLimiter {
@@ -21,6 +33,11 @@ impl RateLimiter {
 
     /// Check if a request from an IP should be allowed
     pub async fn check_rate_limit(&self, ip: String) -> bool {
+        self.acquire(ip).await.is_ok()
+    }
+
+    /// Record a request from `ip`, or Err(how long until the oldest request leaves the window)
+    pub async fn acquire(&self, ip: String) -> Result<(), Duration> {
         let mut requests = self.requests.lock().await;
         let now = Instant::now();
 
@@ -33,10 +50,11 @@ impl RateLimiter {
         // Check if under limit
         if ip_requests.len() < self.max_requests {
             ip_requests.push(now);
-            true
+            Ok(())
         } else {
             log::warn!("Rate limit exceeded for IP");
-            false
+            let oldest = ip_requests.first().copied().unwrap_or(now);
+            Err(self.window.saturating_sub(now.duration_since(oldest)))
         }
     }
 
@@ -52,6 +70,188 @@ impl RateLimiter {
     }
 }
 
+/// Access granted by an API key - admin includes read-only
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiScope {
+    ReadOnly,
+    Admin,
+}
+
+/// Why a request was turned away before reaching a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiRejection {
+    Unauthorized, // Missing or unknown key
+    Forbidden,    // Valid key without the scope the route needs
+    RateLimited { retry_after_secs: u64 },
+}
+
+impl warp::reject::Reject for ApiRejection {}
+
+/// Bearer-token API keys for the HTTP servers
+/// GETs need a read-only key (or none when public reads are allowed); everything else needs an admin key.
+/// With no admin keys configured every mutating request is refused.
+#[derive(Debug, Clone)]
+pub struct ApiAuth {
+    keys: Arc<HashMap<String, ApiScope>>,
+    public_reads: bool,
+}
+
+impl ApiAuth {
+    pub fn new(public_reads: bool) -> Self {
+        Self { keys: Arc::new(HashMap::new()), public_reads }
+    }
+
+    pub fn with_key(mut self, key: &str, scope: ApiScope) -> Self {
+        Arc::make_mut(&mut self.keys).insert(key.to_string(), scope);
+        self
+    }
+
+    /// API_ADMIN_KEYS / API_READ_KEYS (comma-separated), API_PUBLIC_READS (default true)
+    pub fn from_env() -> Self {
+        let public_reads = std::env::var("API_PUBLIC_READS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(true);
+        let mut auth = Self::new(public_reads);
+        for (var, scope) in [("API_READ_KEYS", ApiScope::ReadOnly), ("API_ADMIN_KEYS", ApiScope::Admin)] {
+            if let Ok(value) = std::env::var(var) {
+                for key in value.split(',').map(|k| k.trim()).filter(|k| !k.is_empty()) {
+                    auth = auth.with_key(key, scope);
+                }
+            }
+        }
+        auth
+    }
+
+    pub fn has_admin_key(&self) -> bool {
+        self.keys.values().any(|scope| *scope == ApiScope::Admin)
+    }
+
+    /// Scope needed for a request method - None for anonymous-friendly requests
+    fn required_scope(&self, method: &Method) -> Option<ApiScope> {
+        match *method {
+            Method::GET | Method::HEAD | Method::OPTIONS if self.public_reads => None,
+            Method::GET | Method::HEAD | Method::OPTIONS => Some(ApiScope::ReadOnly),
+            _ => Some(ApiScope::Admin),
+        }
+    }
+
+    /// Check an `Authorization` header value against the configured keys
+    pub fn authorize(&self, method: &Method, authorization: Option<&str>) -> Result<(), ApiRejection> {
+        let Some(required) = self.required_scope(method) else {
+            return Ok(());
+        };
+        let scope = authorization
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .map(|key| key.trim())
+            .and_then(|presented| {
+                self.keys.iter()
+                    .find(|(key, _)| constant_time_eq(key.as_bytes(), presented.as_bytes()))
+                    .map(|(_, scope)| *scope)
+            })
+            .ok_or(ApiRejection::Unauthorized)?;
+        if scope >= required {
+            Ok(())
+        } else {
+            Err(ApiRejection::Forbidden)
+        }
+    }
+}
+
+/// Compare keys without leaking how many leading bytes matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Per-IP limiters for the HTTP APIs - every request counts against `general`,
+/// mutating and expensive ones against `strict` as well
+#[derive(Clone)]
+pub struct ApiRateLimits {
+    pub general: Arc<RateLimiter>,
+    pub strict: Arc<RateLimiter>,
+}
+
+impl ApiRateLimits {
+    /// Mutations (swaps, withdrawals, batch oracle lookups...) and upstream-backed reads
+    pub fn is_strict(method: &Method, path: &str) -> bool {
+        !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+            || EXPENSIVE_READ_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+    }
+
+    async fn check(&self, method: &Method, path: &str, client: &str) -> Result<(), ApiRejection> {
+        let mut result = self.general.acquire(client.to_string()).await;
+        if result.is_ok() && Self::is_strict(method, path) {
+            result = self.strict.acquire(client.to_string()).await;
+        }
+        result.map_err(|wait| ApiRejection::RateLimited { retry_after_secs: (wait.as_secs_f64().ceil() as u64).max(1) })
+    }
+}
+
+/// Rate limit by client IP and route class, then authenticate - prefix every route with this
+pub fn api_guard(auth: ApiAuth, limits: ApiRateLimits) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::method()
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::addr::remote())
+        .and_then(move |method: Method, path: warp::path::FullPath, authorization: Option<String>, remote: Option<SocketAddr>| {
+            let auth = auth.clone();
+            let limits = limits.clone();
+            async move {
+                let client = remote.map(|addr| addr.ip().to_string()).unwrap_or_else(|| "unknown".to_string());
+                if let Err(rejection) = limits.check(&method, path.as_str(), &client).await {
+                    log::warn!("🚦 Rate limited {} {} from {}", method, path.as_str(), client);
+                    return Err(warp::reject::custom(rejection));
+                }
+                auth.authorize(&method, authorization.as_deref()).map_err(|rejection| {
+                    log::warn!("🔐 Rejected {} request: {:?}", method, rejection);
+                    warp::reject::custom(rejection)
+                })
+            }
+        })
+        .untuple_one()
+}
+
+/// Turn guard rejections into 401/403/429 - everything else falls through to warp's defaults
+pub async fn handle_api_rejection(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    let (status, message) = match rejection.find::<ApiRejection>() {
+        Some(ApiRejection::Unauthorized) => (StatusCode::UNAUTHORIZED, "Missing or invalid API key"),
+        Some(ApiRejection::Forbidden) => (StatusCode::FORBIDDEN, "API key lacks the admin scope"),
+        Some(ApiRejection::RateLimited { .. }) => (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded"),
+        None => return Err(rejection),
+    };
+    let body = warp::reply::json(&serde_json::json!({"success": false, "data": null, "message": message}));
+    let mut response = warp::reply::with_status(body, status).into_response();
+    if status == StatusCode::UNAUTHORIZED {
+        response.headers_mut().insert("www-authenticate", warp::http::HeaderValue::from_static("Bearer"));
+    }
+    if let Some(ApiRejection::RateLimited { retry_after_secs }) = rejection.find::<ApiRejection>() {
+        response.headers_mut().insert("retry-after", warp::http::HeaderValue::from(*retry_after_secs));
+    }
+    Ok(response)
+}
+
+/// API rate limiters sized from API_RATE_LIMIT_PER_MIN / API_STRICT_RATE_LIMIT_PER_MIN, with their own cleanup task
+pub fn api_rate_limiter() -> ApiRateLimits {
+    let per_min = |name: &str, default: usize| std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default);
+    let limits = ApiRateLimits {
+        general: Arc::new(RateLimiter::new(per_min("API_RATE_LIMIT_PER_MIN", DEFAULT_API_RATE_LIMIT_PER_MIN), Duration::from_secs(60))),
+        strict: Arc::new(RateLimiter::new(per_min("API_STRICT_RATE_LIMIT_PER_MIN", DEFAULT_API_STRICT_RATE_LIMIT_PER_MIN), Duration::from_secs(60))),
+    };
+    let cleanup = limits.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            cleanup.general.cleanup().await;
+            cleanup.strict.cleanup().await;
+        }
+    });
+    limits
+}
+
 /// Security headers middleware
 #[allow(dead_code)]
 pub fn with_security_headers() -> warp::reply::WithHeader<warp::reply::Response> {
@@ -72,6 +272,153 @@ pub fn cors_config() -> warp::cors::Builder {
         .max_age(3600)
 }
 
+/// How the token filter decides - the blacklist always applies, whitelist-only also rejects unlisted tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenFilterMode {
+    Blacklist,
+    WhitelistOnly,
+}
+
+impl TokenFilterMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.trim().to_lowercase().as_str() {
+            "blacklist" => Some(Self::Blacklist),
+            "whitelist" | "whitelist_only" => Some(Self::WhitelistOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Current filter, for the API
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenFilterSettings {
+    pub mode: TokenFilterMode,
+    pub blacklist: Vec<String>,
+    pub whitelist: Vec<String>,
+    pub rejected: u64, // Signals refused since startup
+}
+
+#[derive(Debug)]
+struct TokenFilterState {
+    mode: TokenFilterMode,
+    blacklist: BTreeSet<String>,
+    whitelist: BTreeSet<String>,
+    rejected: u64,
+}
+
+/// Hard manual override on which token mints may ever be traded
+/// Cheap, cloneable handle shared between the providers, the trading engine and the API
+#[derive(Debug, Clone)]
+pub struct TokenFilter {
+    state: Arc<Mutex<TokenFilterState>>,
+}
+
+impl TokenFilter {
+    pub fn new(mode: TokenFilterMode) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TokenFilterState {
+                mode,
+                blacklist: BTreeSet::new(),
+                whitelist: BTreeSet::new(),
+                rejected: 0,
+            })),
+        }
+    }
+
+    /// TOKEN_FILTER_MODE (blacklist | whitelist_only, default blacklist),
+    /// TOKEN_BLACKLIST / TOKEN_WHITELIST (comma-separated mints or known symbols)
+    pub async fn from_env() -> Self {
+        let mode = std::env::var("TOKEN_FILTER_MODE")
+            .ok()
+            .and_then(|v| TokenFilterMode::parse(&v))
+            .unwrap_or(TokenFilterMode::Blacklist);
+        let filter = Self::new(mode);
+        for (var, whitelist) in [("TOKEN_BLACKLIST", false), ("TOKEN_WHITELIST", true)] {
+            let Ok(value) = std::env::var(var) else { continue };
+            for token in value.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+                if let Err(e) = filter.add(token, whitelist).await {
+                    log::warn!("⚠️ Ignoring {} entry: {}", var, e);
+                }
+            }
+        }
+        filter
+    }
+
+    /// Mint a token is filed under - known symbols ("RAY", "SOL/USD") resolve to their mint
+    pub fn normalize(token: &str) -> Result<String, String> {
+        crate::dex_aggregator::resolve_token(token)
+            .map(|(mint, _)| mint)
+            .ok_or_else(|| format!("'{}' is not a mint address or known symbol", token))
+    }
+
+    /// Why a token may not be traded, or Ok if it may - rejections are counted
+    pub async fn check(&self, token: &str) -> Result<(), String> {
+        let mut state = self.state.lock().await;
+        let mint = Self::normalize(token).unwrap_or_else(|_| token.trim().to_string());
+        let reason = if state.blacklist.contains(&mint) {
+            format!("{} is blacklisted", token)
+        } else if state.mode == TokenFilterMode::WhitelistOnly && !state.whitelist.contains(&mint) {
+            format!("{} is not whitelisted (whitelist-only mode)", token)
+        } else {
+            return Ok(());
+        };
+        state.rejected += 1;
+        Err(reason)
+    }
+
+    pub async fn is_allowed(&self, token: &str) -> bool {
+        self.check(token).await.is_ok()
+    }
+
+    pub async fn set_mode(&self, mode: TokenFilterMode) {
+        let mut state = self.state.lock().await;
+        if state.mode != mode {
+            log::info!("🚧 Token filter mode: {:?} -> {:?}", state.mode, mode);
+            if mode == TokenFilterMode::WhitelistOnly && state.whitelist.is_empty() {
+                log::warn!("⚠️ Whitelist-only mode with an empty whitelist - every token is refused");
+            }
+            state.mode = mode;
+        }
+    }
+
+    /// Add a token to the whitelist (`whitelist`) or blacklist - returns the mint it was filed under
+    pub async fn add(&self, token: &str, whitelist: bool) -> Result<String, String> {
+        let mint = Self::normalize(token)?;
+        let mut state = self.state.lock().await;
+        let list = if whitelist { &mut state.whitelist } else { &mut state.blacklist };
+        if list.insert(mint.clone()) {
+            log::info!("🚧 {} {} to the token {}", if whitelist { "Allowed" } else { "Blocked" }, mint,
+                      if whitelist { "whitelist" } else { "blacklist" });
+        }
+        Ok(mint)
+    }
+
+    /// Remove a token from the whitelist (`whitelist`) or blacklist - false if it wasn't listed
+    pub async fn remove(&self, token: &str, whitelist: bool) -> Result<bool, String> {
+        let mint = Self::normalize(token)?;
+        let mut state = self.state.lock().await;
+        let list = if whitelist { &mut state.whitelist } else { &mut state.blacklist };
+        Ok(list.remove(&mint))
+    }
+
+    pub async fn settings(&self) -> TokenFilterSettings {
+        let state = self.state.lock().await;
+        TokenFilterSettings {
+            mode: state.mode,
+            blacklist: state.blacklist.iter().cloned().collect(),
+            whitelist: state.whitelist.iter().cloned().collect(),
+            rejected: state.rejected,
+        }
+    }
+}
+
+impl Default for TokenFilter {
+    fn default() -> Self {
+        Self::new(TokenFilterMode::Blacklist)
+    }
+}
+
 /// Input validation helpers
 pub fn validate_wallet_address(address: &str) -> bool {
     // Basic Solana address validation (base58, 32-44 chars)
@@ -118,6 +465,84 @@ mod tests {
         assert_eq!(sanitize_symbol("ETH@USDC"), "ETHUSDC");
     }
 
+    #[test]
+    fn test_api_auth_scopes() {
+        let auth = ApiAuth::new(true)
+            .with_key("reader", ApiScope::ReadOnly)
+            .with_key("admin", ApiScope::Admin);
+
+        // Public reads, mutations need an admin key
+        assert_eq!(auth.authorize(&Method::GET, None), Ok(()));
+        assert_eq!(auth.authorize(&Method::POST, None), Err(ApiRejection::Unauthorized));
+        assert_eq!(auth.authorize(&Method::POST, Some("Bearer wrong")), Err(ApiRejection::Unauthorized));
+        assert_eq!(auth.authorize(&Method::POST, Some("admin")), Err(ApiRejection::Unauthorized));
+        assert_eq!(auth.authorize(&Method::POST, Some("Bearer reader")), Err(ApiRejection::Forbidden));
+        assert_eq!(auth.authorize(&Method::POST, Some("Bearer admin")), Ok(()));
+        assert_eq!(auth.authorize(&Method::DELETE, Some("Bearer admin")), Ok(()));
+
+        // Private reads accept either key
+        let private = ApiAuth { public_reads: false, ..auth };
+        assert_eq!(private.authorize(&Method::GET, None), Err(ApiRejection::Unauthorized));
+        assert_eq!(private.authorize(&Method::GET, Some("Bearer reader")), Ok(()));
+        assert_eq!(private.authorize(&Method::GET, Some("Bearer admin")), Ok(()));
+
+        // No keys configured: nothing can mutate
+        assert!(!ApiAuth::new(true).has_admin_key());
+        assert_eq!(ApiAuth::new(true).authorize(&Method::POST, Some("Bearer ")), Err(ApiRejection::Unauthorized));
+    }
+
+    #[tokio::test]
+    async fn test_api_guard_statuses() {
+        let auth = ApiAuth::new(true).with_key("admin", ApiScope::Admin);
+        let limits = ApiRateLimits {
+            general: Arc::new(RateLimiter::new(3, Duration::from_secs(60))),
+            strict: Arc::new(RateLimiter::new(10, Duration::from_secs(60))),
+        };
+        let routes = api_guard(auth, limits)
+            .and(warp::path("trading-toggle"))
+            .map(|| "ok")
+            .recover(handle_api_rejection);
+
+        let get = warp::test::request().method("GET").path("/trading-toggle").reply(&routes).await;
+        assert_eq!(get.status(), StatusCode::OK);
+        let anonymous = warp::test::request().method("POST").path("/trading-toggle").reply(&routes).await;
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+        let admin = warp::test::request().method("POST").path("/trading-toggle")
+            .header("authorization", "Bearer admin").reply(&routes).await;
+        assert_eq!(admin.status(), StatusCode::OK);
+        let limited = warp::test::request().method("GET").path("/trading-toggle").reply(&routes).await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key("retry-after"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_route_limits() {
+        let auth = ApiAuth::new(true).with_key("admin", ApiScope::Admin);
+        let limits = ApiRateLimits {
+            general: Arc::new(RateLimiter::new(10, Duration::from_secs(60))),
+            strict: Arc::new(RateLimiter::new(1, Duration::from_secs(60))),
+        };
+        let routes = api_guard(auth, limits)
+            .and(warp::path::tail())
+            .map(|_| "ok")
+            .recover(handle_api_rejection);
+
+        assert!(ApiRateLimits::is_strict(&Method::POST, "/pda/withdraw"));
+        assert!(ApiRateLimits::is_strict(&Method::GET, "/jupiter/quote/a/b/1"));
+        assert!(!ApiRateLimits::is_strict(&Method::GET, "/performance"));
+
+        let batch = || warp::test::request().method("POST").path("/oracle/batch").header("authorization", "Bearer admin");
+        assert_eq!(batch().reply(&routes).await.status(), StatusCode::OK);
+        let limited = batch().reply(&routes).await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = limited.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+        assert!((1..=60).contains(&retry_after));
+
+        // Read-only routes still have headroom on the general limit
+        let read = warp::test::request().method("GET").path("/performance").reply(&routes).await;
+        assert_eq!(read.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_rate_limiter() {
         let limiter = RateLimiter::new(3, Duration::from_secs(60));
@@ -131,4 +556,56 @@ mod tests {
         // 4th request should fail
         assert!(!limiter.check_rate_limit(ip.clone()).await);
     }
+
+    #[tokio::test]
+    async fn test_token_filter_blacklist_and_whitelist_only() {
+        let scam = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        let filter = TokenFilter::default();
+        let engine_handle = filter.clone();
+        assert!(filter.is_allowed("BONK").await);
+        assert!(filter.is_allowed(&scam).await);
+
+        filter.add(&scam, false).await.unwrap();
+        assert!(!engine_handle.is_allowed(&scam).await);
+        assert!(engine_handle.is_allowed("RAY").await);
+
+        // Symbols and their mint are the same entry
+        let ray_mint = filter.add("RAY/USD", true).await.unwrap();
+        filter.set_mode(TokenFilterMode::WhitelistOnly).await;
+        assert!(engine_handle.is_allowed(&ray_mint).await);
+        assert!(engine_handle.is_allowed("RAY").await);
+        assert!(!engine_handle.is_allowed("SOL").await);
+        assert!(engine_handle.check("SOL").await.unwrap_err().contains("not whitelisted"));
+
+        // The blacklist wins even over the whitelist
+        filter.add(&scam, true).await.unwrap();
+        assert!(engine_handle.check(&scam).await.unwrap_err().contains("blacklisted"));
+        assert!(filter.remove(&scam, false).await.unwrap());
+        assert!(engine_handle.is_allowed(&scam).await);
+
+        assert!(filter.add("not a mint!", false).await.is_err());
+        let settings = filter.settings().await;
+        assert_eq!(settings.mode, TokenFilterMode::WhitelistOnly);
+        assert_eq!(settings.whitelist.len(), 2);
+        assert!(settings.blacklist.is_empty());
+        assert_eq!(settings.rejected, 4);
+    }
+
+    #[tokio::test]
+    async fn test_token_filter_checks_signal_mint_not_ticker() {
+        // Memecoin signals are labelled with tickers no registry resolves - the filter needs their mint
+        let scam = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        let filter = TokenFilter::default();
+        filter.add(&scam, false).await.unwrap();
+        let signal = crate::signal_platform::TradingSignalData {
+            symbol: "SCAM (Jupiter)".to_string(),
+            mint: Some(scam.clone()),
+            ..Default::default()
+        };
+        assert!(!filter.is_allowed(signal.filter_token()).await);
+
+        let unlabelled = crate::signal_platform::TradingSignalData { mint: None, ..signal };
+        filter.set_mode(TokenFilterMode::WhitelistOnly).await;
+        assert!(!filter.is_allowed(unlabelled.filter_token()).await);
+    }
 }
@@ -0,0 +1,76 @@
+//! Graceful Shutdown
+//! Ctrl-C flips a shared flag: loops stop picking up new work, the in-flight swap finishes,
+//! state is persisted and the HTTP servers drain before the process exits
+
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// How long to wait for an in-flight swap or a draining server before giving up on it
+pub const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
+/// Cheap, cloneable handle shared between main, the trading loops and the servers
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(false);
+        Self { tx: Arc::new(tx) }
+    }
+
+    /// Flip the flag - returns false if shutdown was already under way
+    pub fn trigger(&self) -> bool {
+        !self.tx.send_replace(true)
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolves once shutdown is triggered (immediately if it already was)
+    pub async fn wait(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives in `self`, so the channel cannot close while we wait
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+
+    /// SHUTDOWN_TIMEOUT_SECS (default 30)
+    pub fn timeout_from_env() -> std::time::Duration {
+        let secs = std::env::var("SHUTDOWN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+        std::time::Duration::from_secs(secs)
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trigger_wakes_waiters_once() {
+        let shutdown = Shutdown::new();
+        let server_handle = shutdown.clone();
+        let waiter = tokio::spawn(async move { server_handle.wait().await });
+
+        assert!(!shutdown.is_triggered());
+        assert!(shutdown.trigger());
+        assert!(!shutdown.trigger());
+        tokio::time::timeout(std::time::Duration::from_secs(1), waiter).await
+            .expect("waiter should wake on trigger")
+            .unwrap();
+
+        // Late waiters resolve immediately
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown.wait()).await
+            .expect("already triggered");
+    }
+}
@@ -0,0 +1,330 @@
+//! Signal Lifecycle Callbacks
+//! POSTs signal published / executed / closed events to SIGNAL_CALLBACK_URL for external consumers.
+//! Unlike the websocket this is at-least-once: every event is queued in the database before it is sent,
+//! retried with backoff until the receiver answers 2xx, and still queued after a restart. Receivers
+//! should dedupe on the event id and verify the HMAC-SHA256 signature header
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+
+/// Delivery attempts before an event is parked as dead (re-armed via POST /callbacks/retry)
+pub const DEFAULT_CALLBACK_MAX_ATTEMPTS: u32 = 20;
+/// How often the queue is checked for retries that came due
+pub const DEFAULT_CALLBACK_POLL_SECS: u64 = 5;
+/// First retry waits this long, doubling per failure up to CALLBACK_MAX_BACKOFF_SECS
+const CALLBACK_INITIAL_BACKOFF_SECS: i64 = 5;
+const CALLBACK_MAX_BACKOFF_SECS: i64 = 3600;
+/// Events sent per queue pass
+const CALLBACK_BATCH_SIZE: usize = 50;
+
+/// `sha256=<hex HMAC of "<timestamp>.<body>">`, only sent when SIGNAL_CALLBACK_SECRET is set
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+pub const TIMESTAMP_HEADER: &str = "X-Callback-Timestamp";
+/// Same id on every retry of an event - the receiver's dedupe key
+pub const EVENT_ID_HEADER: &str = "X-Callback-Event-Id";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalLifecycleEvent {
+    Published,
+    Executed,
+    Closed,
+}
+
+/// Executed payload - the signal and what it actually filled at
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutedSignal<'a> {
+    #[serde(flatten)]
+    pub signal: &'a crate::signal_platform::TradingSignalData,
+    /// USD fill price - paper fills include simulated slippage
+    pub fill_price: f64,
+    /// Tokens filled, when the engine recorded the fill
+    pub fill_size: Option<f64>,
+}
+
+/// One queued callback - stays in the database until the receiver acknowledges it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackEvent {
+    pub id: String,
+    pub event: SignalLifecycleEvent,
+    pub signal_id: String,
+    /// The signal (published), the signal with its fill (executed) or the closed position record (closed)
+    pub payload: serde_json::Value,
+    pub created_at: i64,
+    pub attempts: u32,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    /// Out of attempts - kept for inspection, no longer retried
+    #[serde(default)]
+    pub dead: bool,
+}
+
+impl CallbackEvent {
+    pub fn new(event: SignalLifecycleEvent, signal_id: &str, payload: serde_json::Value, now: i64) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event,
+            signal_id: signal_id.to_string(),
+            payload,
+            created_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            dead: false,
+        }
+    }
+
+    /// JSON body POSTed to the callback URL
+    pub fn body(&self) -> String {
+        serde_json::json!({
+            "id": self.id,
+            "event": self.event,
+            "signal_id": self.signal_id,
+            "created_at": self.created_at,
+            "attempt": self.attempts + 1,
+            "data": self.payload,
+        }).to_string()
+    }
+
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.dead && self.next_attempt_at <= now
+    }
+
+    /// Schedule the next retry, or park the event once `max_attempts` are used up
+    pub fn record_failure(&mut self, error: String, now: i64, max_attempts: u32) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.next_attempt_at = now + backoff_secs(self.attempts);
+        self.dead = self.attempts >= max_attempts;
+    }
+}
+
+/// Wait before the retry that follows `attempts` failed deliveries
+pub fn backoff_secs(attempts: u32) -> i64 {
+    let doublings = attempts.saturating_sub(1).min(20);
+    (CALLBACK_INITIAL_BACKOFF_SECS << doublings).min(CALLBACK_MAX_BACKOFF_SECS)
+}
+
+/// Hex HMAC-SHA256 of `message` under `secret`
+pub fn hmac_sha256_hex(secret: &str, message: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Value of SIGNATURE_HEADER for a body sent at `timestamp`
+pub fn signature(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("sha256={}", hmac_sha256_hex(secret, &format!("{}.{}", timestamp, body)))
+}
+
+#[derive(Debug, Clone)]
+pub struct CallbackConfig {
+    pub url: String,
+    pub secret: Option<String>,
+    pub max_attempts: u32,
+    pub poll_interval_secs: u64,
+}
+
+impl CallbackConfig {
+    /// SIGNAL_CALLBACK_URL (None when unset), SIGNAL_CALLBACK_SECRET, SIGNAL_CALLBACK_MAX_ATTEMPTS (default 20),
+    /// SIGNAL_CALLBACK_POLL_SECS (default 5)
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("SIGNAL_CALLBACK_URL").ok().filter(|u| !u.trim().is_empty())?;
+        Some(Self {
+            url,
+            secret: std::env::var("SIGNAL_CALLBACK_SECRET").ok().filter(|s| !s.is_empty()),
+            max_attempts: std::env::var("SIGNAL_CALLBACK_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(DEFAULT_CALLBACK_MAX_ATTEMPTS)
+                .max(1),
+            poll_interval_secs: std::env::var("SIGNAL_CALLBACK_POLL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_CALLBACK_POLL_SECS),
+        })
+    }
+}
+
+/// Queue depth for GET /callbacks/status
+#[derive(Debug, Clone, Serialize)]
+pub struct CallbackQueueStatus {
+    pub url: String,
+    pub signed: bool,
+    pub pending: usize,
+    pub dead: usize,
+    pub oldest_pending_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+/// Cheap, cloneable handle - emitters enqueue, one background worker delivers
+#[derive(Clone)]
+pub struct SignalCallbacks {
+    config: Arc<CallbackConfig>,
+    database: Arc<Mutex<crate::database::Database>>,
+    wake: Arc<Notify>,
+    client: Arc<reqwest::Client>,
+}
+
+impl SignalCallbacks {
+    pub fn new(config: CallbackConfig, database: Arc<Mutex<crate::database::Database>>) -> Self {
+        Self {
+            config: Arc::new(config),
+            database,
+            wake: Arc::new(Notify::new()),
+            client: crate::http_client::SharedHttpClient::shared(),
+        }
+    }
+
+    /// None when SIGNAL_CALLBACK_URL isn't set
+    pub fn from_env(database: Arc<Mutex<crate::database::Database>>) -> Option<Self> {
+        CallbackConfig::from_env().map(|config| Self::new(config, database))
+    }
+
+    /// Queue an event durably, then nudge the worker - never waits on the receiver
+    pub async fn emit<T: Serialize>(&self, event: SignalLifecycleEvent, signal_id: &str, payload: &T) {
+        let payload = match serde_json::to_value(payload) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::error!("❌ Could not serialize {:?} callback for {}: {}", event, signal_id, e);
+                return;
+            }
+        };
+        let callback = CallbackEvent::new(event, signal_id, payload, chrono::Utc::now().timestamp());
+        if let Err(e) = self.database.lock().await.enqueue_callback(callback) {
+            log::error!("❌ Failed to queue {:?} callback for {}: {}", event, signal_id, e);
+            return;
+        }
+        self.wake.notify_one();
+    }
+
+    async fn deliver(&self, callback: &CallbackEvent) -> Result<(), String> {
+        let body = callback.body();
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut request = self.client.post(&self.config.url)
+            .header("Content-Type", "application/json")
+            .header(EVENT_ID_HEADER, &callback.id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string());
+        if let Some(ref secret) = self.config.secret {
+            request = request.header(SIGNATURE_HEADER, signature(secret, timestamp, &body));
+        }
+        let response = request.body(body).send().await
+            .map_err(|e| format!("Callback request failed: {}", e))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Callback returned HTTP {}", response.status()))
+        }
+    }
+
+    /// Send every due event, oldest first - stops at the first failure so a down receiver
+    /// doesn't burn an attempt on every queued event. The pass's outcomes are saved together
+    /// Returns how many were delivered
+    pub async fn deliver_due(&self, now: i64) -> usize {
+        let due = self.database.lock().await.due_callbacks(now, CALLBACK_BATCH_SIZE);
+        let mut delivered = Vec::new();
+        let mut failed = None;
+        for callback in due {
+            match self.deliver(&callback).await {
+                Ok(()) => delivered.push(callback.id),
+                Err(error) => {
+                    log::warn!("⚠️ {:?} callback for {} failed (attempt {}): {}",
+                              callback.event, callback.signal_id, callback.attempts + 1, error);
+                    failed = Some((callback.id, error));
+                    break;
+                }
+            }
+        }
+        if delivered.is_empty() && failed.is_none() {
+            return 0;
+        }
+        let result = self.database.lock().await
+            .record_callback_deliveries(&delivered, failed, now, self.config.max_attempts);
+        if let Err(e) = result {
+            log::warn!("⚠️ Failed to save callback deliveries (delivered ones will be resent): {}", e);
+        }
+        delivered.len()
+    }
+
+    /// Deliver until shutdown - woken by every emit, and every poll interval for retries
+    pub fn spawn(self, shutdown: crate::shutdown::Shutdown) {
+        log::info!("📮 Signal callbacks -> {} ({})", self.config.url,
+                  if self.config.secret.is_some() { "HMAC signed" } else { "unsigned" });
+        tokio::spawn(async move {
+            let poll = tokio::time::Duration::from_secs(self.config.poll_interval_secs);
+            loop {
+                self.deliver_due(chrono::Utc::now().timestamp()).await;
+                tokio::select! {
+                    _ = self.wake.notified() => {}
+                    _ = tokio::time::sleep(poll) => {}
+                    _ = shutdown.wait() => {
+                        log::info!("📮 Signal callback worker stopped - undelivered events stay queued");
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn status(&self) -> CallbackQueueStatus {
+        let database = self.database.lock().await;
+        let queued = database.get_callback_events();
+        let pending: Vec<&CallbackEvent> = queued.iter().filter(|c| !c.dead).collect();
+        CallbackQueueStatus {
+            url: self.config.url.clone(),
+            signed: self.config.secret.is_some(),
+            pending: pending.len(),
+            dead: queued.len() - pending.len(),
+            oldest_pending_at: pending.iter().map(|c| c.created_at).min(),
+            last_error: queued.iter().rev().find_map(|c| c.last_error.clone()),
+        }
+    }
+
+    /// Give dead events a fresh set of attempts
+    pub async fn retry_dead(&self) -> Result<usize, String> {
+        let revived = self.database.lock().await.retry_dead_callbacks(chrono::Utc::now().timestamp())?;
+        if revived > 0 {
+            self.wake.notify_one();
+        }
+        Ok(revived)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_signing_and_retry_schedule() {
+        // RFC 4231 test case 2
+        assert_eq!(hmac_sha256_hex("Jefe", "what do ya want for nothing?"),
+                   "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        let sig = signature("secret", 1_700_000_000, "{}");
+        assert_eq!(sig, format!("sha256={}", hmac_sha256_hex("secret", "1700000000.{}")));
+        assert_ne!(sig, signature("secret", 1_700_000_001, "{}"));
+
+        assert_eq!(backoff_secs(1), 5);
+        assert_eq!(backoff_secs(2), 10);
+        assert_eq!(backoff_secs(50), CALLBACK_MAX_BACKOFF_SECS);
+
+        let mut callback = CallbackEvent::new(SignalLifecycleEvent::Executed, "sig_1", serde_json::json!({"size": 1.0}), 1_000);
+        assert!(callback.is_due(1_000));
+        callback.record_failure("HTTP 503".to_string(), 1_000, 2);
+        assert!(!callback.is_due(1_004));
+        assert!(callback.is_due(1_005));
+        assert!(!callback.dead);
+        callback.record_failure("HTTP 503".to_string(), 1_005, 2);
+        assert!(callback.dead);
+        assert!(!callback.is_due(i64::MAX));
+
+        let body: serde_json::Value = serde_json::from_str(&callback.body()).unwrap();
+        assert_eq!(body["event"], "executed");
+        assert_eq!(body["id"], callback.id);
+        assert_eq!(body["data"]["size"], 1.0);
+    }
+}
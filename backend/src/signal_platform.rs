@@ -2,17 +2,16 @@
 //! Integrated into AI orchestrator for signal sharing and monetization
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use chrono::Utc;
 
 use crate::switchboard_oracle::SwitchboardClient;
 use crate::dex_screener::DexScreenerClient;
 use crate::pumpfun::PumpFunClient;
 
 /// Trading signal that can be shared/traded on the platform
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TradingSignalData {
     pub id: String,
     pub provider: String,
@@ -29,22 +28,318 @@ pub struct TradingSignalData {
     pub expiry: i64,
     pub price: f64, // Price to buy/sell this signal (in tokens)
     pub status: SignalStatus,
+    #[serde(default)]
+    pub force_dry_run: bool, // Paper-trade this signal even when live trading is on
+    #[serde(default)]
+    pub max_hold_secs: Option<i64>, // Close at market after this long open (None = derived from timeframe)
+    #[serde(default)]
+    pub slippage_bps: Option<u16>, // Swap slippage override, capped at SLIPPAGE_CEILING_BPS (None = learned tolerance)
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>, // Swap compute-unit limit override, capped at MAX_COMPUTE_UNIT_LIMIT (None = estimated from the route)
+    #[serde(default)]
+    pub mint: Option<String>, // Token mint, when the provider knows it - memecoin symbols are tickers no registry resolves
+    #[serde(default)]
+    pub sandboxed: bool, // Published by a sandboxed provider - paper-traded and tracked, never listed, sold or executed live
+}
+
+#[cfg(test)]
+impl TradingSignalData {
+    /// Active 15-minute buy at $1 (target $1.10, stop $0.95, 90% confidence) for tests -
+    /// override what a test cares about with struct update syntax
+    pub fn fixture(id: &str, provider: &str, symbol: &str) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: id.to_string(),
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            action: SignalAction::Buy,
+            entry_price: 1.0,
+            target_price: 1.1,
+            stop_loss: 0.95,
+            confidence: 0.9,
+            timeframe: "15m".to_string(),
+            timestamp: now,
+            expiry: now + 900,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        }
+    }
+}
+
+impl TradingSignalData {
+    /// What the token filter checks - the mint when known, else the symbol (known tickers resolve to their mint)
+    pub fn filter_token(&self) -> &str {
+        self.mint.as_deref().unwrap_or(&self.symbol)
+    }
+
+    /// Timeframe in seconds - "30s", "15m", "4h", "1d", "1w"
+    pub fn timeframe_secs(&self) -> Option<i64> {
+        let timeframe = self.timeframe.trim();
+        let unit_at = timeframe.find(|c: char| !c.is_ascii_digit())?;
+        let count: i64 = timeframe[..unit_at].parse().ok()?;
+        let unit_secs = match &timeframe[unit_at..] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3_600,
+            "d" => 86_400,
+            "w" => 604_800,
+            _ => return None,
+        };
+        Some(count * unit_secs).filter(|secs| *secs > 0)
+    }
+
+    /// How long a position may stay open before it is closed at market
+    /// An explicit `max_hold_secs` wins; otherwise MAX_HOLD_TIMEFRAMES x the signal's timeframe
+    pub fn effective_max_hold_secs(&self) -> Option<i64> {
+        self.max_hold_secs.or_else(|| self.timeframe_secs().map(|secs| secs * MAX_HOLD_TIMEFRAMES))
+    }
+
+    /// Execution order score: confidence plus up to TTL_PRIORITY_BOOST for signals close to expiry
+    /// The boost is full at expiry and tapers linearly to nothing TTL_PRIORITY_HORIZON_SECS out, so a
+    /// 10-minute scalp at 80% runs ahead of a 2-hour signal at 95%, while two long-horizon signals
+    /// still go by confidence
+    pub fn execution_priority(&self, now: i64) -> f64 {
+        let ttl = (self.expiry - now).max(0) as f64;
+        let urgency = (1.0 - ttl / TTL_PRIORITY_HORIZON_SECS as f64).clamp(0.0, 1.0);
+        self.confidence + TTL_PRIORITY_BOOST * urgency
+    }
+
+    /// Seconds after any trade on this symbol before this signal may buy it again
+    /// SYMBOL_COOLDOWN_SECS (`configured`) wins, 0 disables; otherwise one timeframe
+    pub fn cooldown_secs(&self, configured: Option<i64>) -> i64 {
+        configured
+            .or_else(|| self.timeframe_secs())
+            .unwrap_or(DEFAULT_SYMBOL_COOLDOWN_SECS)
+            .max(0)
+    }
+
+    /// Reward over risk measured from entry: |target - entry| / |entry - stop| on the signal's side
+    /// 0.0 when the stop sits on the entry or either level is on the wrong side for the action
+    pub fn risk_reward(&self) -> f64 {
+        let (reward, risk) = match self.action {
+            SignalAction::Sell => (self.entry_price - self.target_price, self.stop_loss - self.entry_price),
+            _ => (self.target_price - self.entry_price, self.entry_price - self.stop_loss),
+        };
+        if reward > 0.0 && risk > 0.0 {
+            reward / risk
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Reject a signal whose risk/reward is below `min_ratio` (0 disables; Hold signals always pass)
+pub fn validate_risk_reward(signal: &TradingSignalData, min_ratio: f64) -> Result<(), String> {
+    if min_ratio <= 0.0 || matches!(signal.action, SignalAction::Hold) {
+        return Ok(());
+    }
+    let ratio = signal.risk_reward();
+    if ratio == 0.0 {
+        return Err(format!(
+            "Target {} / stop {} are not on the {:?} side of entry {}",
+            signal.target_price, signal.stop_loss, signal.action, signal.entry_price
+        ));
+    }
+    if ratio < min_ratio {
+        return Err(format!("Risk/reward {:.2} below minimum {:.2}", ratio, min_ratio));
+    }
+    Ok(())
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum SignalAction {
     Buy,
     Sell,
+    #[default]
     Hold,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum SignalStatus {
+    #[default]
     Active,
     Executing,  // FIX #1: Added to prevent duplicate execution
     Filled,
     Expired,
     Cancelled,
+    Superseded, // A higher-confidence duplicate from another signal was executed instead
+}
+
+impl SignalStatus {
+    /// Case-insensitive status name, as used in query strings
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "active" => Some(SignalStatus::Active),
+            "executing" => Some(SignalStatus::Executing),
+            "filled" => Some(SignalStatus::Filled),
+            "expired" => Some(SignalStatus::Expired),
+            "cancelled" => Some(SignalStatus::Cancelled),
+            "superseded" => Some(SignalStatus::Superseded),
+            _ => None,
+        }
+    }
+}
+
+/// Criteria for searching current and historical signals; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct SignalFilter {
+    /// Published at or after (unix seconds)
+    pub from: Option<i64>,
+    /// Published at or before (unix seconds)
+    pub to: Option<i64>,
+    /// Any of these statuses (empty = all)
+    pub statuses: Vec<SignalStatus>,
+    pub provider: Option<String>,
+    pub symbol: Option<String>,
+}
+
+impl SignalFilter {
+    pub fn matches(&self, signal: &TradingSignalData) -> bool {
+        self.from.is_none_or(|from| signal.timestamp >= from)
+            && self.to.is_none_or(|to| signal.timestamp <= to)
+            && (self.statuses.is_empty() || self.statuses.contains(&signal.status))
+            && self.provider.as_ref().is_none_or(|provider| &signal.provider == provider)
+            && self.symbol.as_ref().is_none_or(|symbol| &signal.symbol == symbol)
+    }
+}
+
+/// Maximum number of skipped-signal decisions kept in memory
+const MAX_SKIPPED_SIGNALS: usize = 500;
+/// Purchases kept in memory for the revenue report when no database is configured (oldest dropped first) -
+/// the database keeps its own, equally sized, purchase history
+const MAX_PURCHASE_RECORDS: usize = 10000;
+/// Global auto-execution confidence bar used when a symbol has no override
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f64 = 0.75;
+/// Same symbol + action within this many seconds counts as one opportunity
+pub const DEFAULT_SIGNAL_DEDUPE_WINDOW_SECS: i64 = 60;
+/// A signal still Executing after this long lost its revert and is forced back to Active/Expired
+pub const DEFAULT_EXECUTING_TIMEOUT_SECS: i64 = 300;
+/// Seconds between writes of buffered signal changes to the database (SIGNAL_PERSIST_SECS)
+pub const DEFAULT_SIGNAL_PERSIST_SECS: u64 = 5;
+/// Positions whose target/stop hasn't hit within this many timeframes are closed as time exits
+pub const MAX_HOLD_TIMEFRAMES: i64 = 2;
+/// Symbol cooldown for signals without a parseable timeframe when SYMBOL_COOLDOWN_SECS is unset
+pub const DEFAULT_SYMBOL_COOLDOWN_SECS: i64 = 300;
+/// Signals expiring further out than this get no time-to-expiry priority boost
+pub const TTL_PRIORITY_HORIZON_SECS: i64 = 7_200;
+/// Priority added (on top of confidence) to a signal on the verge of expiring
+pub const TTL_PRIORITY_BOOST: f64 = 0.25;
+/// Signals risking more than they stand to gain are refused at publish time unless MIN_RISK_REWARD says otherwise
+pub const DEFAULT_MIN_RISK_REWARD: f64 = 1.0;
+
+/// Cross-provider deduplication of near-identical signals before execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalDedupeConfig {
+    pub enabled: bool,
+    pub window_secs: i64,
+}
+
+impl Default for SignalDedupeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: DEFAULT_SIGNAL_DEDUPE_WINDOW_SECS,
+        }
+    }
+}
+
+impl SignalDedupeConfig {
+    /// Load from SIGNAL_DEDUPE_ENABLED / SIGNAL_DEDUPE_WINDOW_SECS
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("SIGNAL_DEDUPE_ENABLED")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(defaults.enabled),
+            window_secs: std::env::var("SIGNAL_DEDUPE_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(defaults.window_secs)
+                .max(0),
+        }
+    }
+}
+
+/// Why the auto-executor passed on a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    ConfidenceBelowThreshold,
+    Expired,
+    AlreadyExecuting,
+    InsufficientBalance,
+    PositionCap,
+    Slippage,
+    BlockedSymbol,
+    Drawdown,
+    TrackingUnavailable,
+    ExecutionFailed,
+    Superseded,
+    OracleDisagreement,
+    ProviderAllocation,
+    PriceDrift,
+    AiRejected,
+    Cooldown,
+    PriceImpact,
+}
+
+impl SkipReason {
+    /// Classify an execution error string returned by the trading engine
+    pub fn from_execution_error(error: &str) -> Self {
+        let error = error.to_lowercase();
+        if error.contains("insufficient balance") || error.contains("insufficient funds") {
+            SkipReason::InsufficientBalance
+        } else if error.contains("drawdown") {
+            SkipReason::Drawdown
+        } else if error.contains("capital allocation") {
+            SkipReason::ProviderAllocation
+        } else if error.contains("position limit") || error.contains("position cap") {
+            SkipReason::PositionCap
+        } else if error.contains("price drifted") {
+            SkipReason::PriceDrift
+        } else if error.contains("price impact") {
+            SkipReason::PriceImpact
+        } else if error.contains("slippage") {
+            SkipReason::Slippage
+        } else if error.contains("blocked") || error.contains("blocklist") {
+            SkipReason::BlockedSymbol
+        } else {
+            SkipReason::ExecutionFailed
+        }
+    }
+}
+
+impl std::str::FromStr for SkipReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| format!("Unknown skip reason: {}", s))
+    }
+}
+
+/// A single skipped-signal decision with the rationale behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedSignal {
+    pub signal_id: String,
+    pub provider: String,
+    pub symbol: String,
+    pub confidence: f64,
+    pub reason: SkipReason,
+    pub detail: String,
+    pub timestamp: i64,
+}
+
+/// A signal held in Executing by `try_mark_executing`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionLock {
+    pub signal_id: String,
+    pub provider: String,
+    pub symbol: String,
+    pub locked_at: Option<i64>, // None if the lock wasn't taken by this process (e.g. restored state)
+    pub locked_for_secs: Option<i64>,
+    pub expiry: i64,
 }
 
 /// X402 Protocol message for signal trading
@@ -88,8 +383,53 @@ pub struct SignalProvider {
     pub total_signals: u64,
     pub successful_signals: u64,
     pub earnings: f64,
+    #[serde(default)]
+    pub superseded_signals: u64, // Signals dropped as duplicates of a stronger signal on the same symbol
+    #[serde(default)]
+    pub avg_entry_slippage_pct: f64, // Mean % the fill was worse than the signaled entry_price (negative = better)
+    #[serde(default)]
+    pub measured_fills: u64, // Fills behind avg_entry_slippage_pct
+    #[serde(default)]
+    pub sandboxed: bool, // Signals only paper-trade and stay off the marketplace until promoted
+    #[serde(default)]
+    pub pricing: SignalPricingStrategy, // How the list price of each published signal is set
+}
+
+/// How a provider prices its signals, applied to each signal's list price when it is published
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum SignalPricingStrategy {
+    /// Keep the price the signal was generated with
+    #[default]
+    Fixed,
+    /// Same price for every signal
+    Flat { price: f64 },
+    /// List price scaled between the multipliers by reputation and, once enough signals resolved, hit rate
+    Performance { min_multiplier: f64, max_multiplier: f64 },
+}
+
+impl SignalPricingStrategy {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            SignalPricingStrategy::Fixed => Ok(()),
+            SignalPricingStrategy::Flat { price } if price.is_finite() && price >= 0.0 => Ok(()),
+            SignalPricingStrategy::Flat { .. } => Err("Flat price must be zero or more".to_string()),
+            SignalPricingStrategy::Performance { min_multiplier, max_multiplier }
+                if min_multiplier.is_finite() && max_multiplier.is_finite()
+                    && min_multiplier >= 0.0 && min_multiplier <= max_multiplier => Ok(()),
+            SignalPricingStrategy::Performance { .. } => Err("Multipliers must satisfy 0 <= min_multiplier <= max_multiplier".to_string()),
+        }
+    }
 }
 
+/// Resolved signals needed before hit rate counts toward performance pricing
+pub const MIN_SIGNALS_FOR_ACCURACY_PRICING: u64 = 10;
+
+/// Average entry slippage a provider gets for free before it costs reputation (%)
+pub const ENTRY_SLIPPAGE_TOLERANCE_PCT: f64 = 0.5;
+/// Fills needed before entry slippage counts against reputation - one bad fill isn't a pattern
+pub const MIN_FILLS_FOR_SLIPPAGE_PENALTY: u64 = 3;
+
 impl SignalProvider {
     pub fn new(id: String, name: String) -> Self {
         Self {
@@ -99,6 +439,11 @@ impl SignalProvider {
             total_signals: 0,
             successful_signals: 0,
             earnings: 0.0,
+            superseded_signals: 0,
+            avg_entry_slippage_pct: 0.0,
+            measured_fills: 0,
+            sandboxed: false,
+            pricing: SignalPricingStrategy::Fixed,
         }
     }
 
@@ -207,6 +552,13 @@ impl SignalProvider {
             reputation_change += 1.0; // Consistency bonus
         }
         
+        // 7. Execution quality penalty
+        // Signals that keep filling worse than quoted cost reputation even when the target is hit:
+        // 1 point per % of average entry slippage above the tolerance, capped at 5
+        if self.measured_fills >= MIN_FILLS_FOR_SLIPPAGE_PENALTY && self.avg_entry_slippage_pct > ENTRY_SLIPPAGE_TOLERANCE_PCT {
+            reputation_change -= (self.avg_entry_slippage_pct - ENTRY_SLIPPAGE_TOLERANCE_PCT).min(5.0);
+        }
+        
         // Apply reputation change with exponential moving average
         // Use 0.95 decay factor (recent performance weighted more)
         self.reputation_score = (self.reputation_score * 0.95 + reputation_change).min(100.0).max(0.0);
@@ -223,9 +575,103 @@ impl SignalProvider {
         );
     }
 
+    /// Fold one fill's entry slippage (% worse than quoted) into the running average
+    pub fn record_entry_slippage(&mut self, slippage_pct: f64) {
+        if !slippage_pct.is_finite() {
+            return;
+        }
+        self.measured_fills += 1;
+        self.avg_entry_slippage_pct += (slippage_pct - self.avg_entry_slippage_pct) / self.measured_fills as f64;
+    }
+
     pub fn add_earnings(&mut self, amount: f64) {
         self.earnings += amount;
     }
+
+    /// What a signal generated at `list_price` sells for under this provider's pricing strategy
+    pub fn signal_price(&self, list_price: f64) -> f64 {
+        let price = match self.pricing {
+            SignalPricingStrategy::Fixed => return list_price,
+            SignalPricingStrategy::Flat { price } => price,
+            SignalPricingStrategy::Performance { min_multiplier, max_multiplier } => {
+                let reputation = self.reputation_score.clamp(0.0, 100.0) / 100.0;
+                let quality = if self.total_signals >= MIN_SIGNALS_FOR_ACCURACY_PRICING {
+                    (reputation + self.success_rate() / 100.0) / 2.0
+                } else {
+                    reputation
+                };
+                list_price * (min_multiplier + (max_multiplier - min_multiplier) * quality)
+            }
+        };
+        (price * 100.0).round() / 100.0
+    }
+}
+
+/// One paid signal purchase - what the revenue report sums
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseRecord {
+    pub signal_id: String,
+    pub provider_id: String,
+    pub buyer_id: String,
+    pub symbol: String,
+    pub list_price: f64,
+    pub payment: f64,
+    pub timestamp: i64,
+}
+
+/// Purchases and payments for one provider within a report window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderRevenue {
+    pub provider_id: String,
+    pub purchases: u64,
+    pub revenue: f64,
+    pub avg_payment: f64,
+    pub last_purchase_at: i64,
+}
+
+/// Realized purchase payments per provider, highest revenue first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevenueReport {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub total_purchases: u64,
+    pub total_revenue: f64,
+    pub providers: Vec<ProviderRevenue>,
+}
+
+impl RevenueReport {
+    /// Sum the purchases made within [from, to]
+    pub fn from_purchases(purchases: &[PurchaseRecord], from: Option<i64>, to: Option<i64>) -> Self {
+        let mut by_provider: HashMap<String, ProviderRevenue> = HashMap::new();
+        for purchase in purchases.iter()
+            .filter(|p| from.is_none_or(|from| p.timestamp >= from) && to.is_none_or(|to| p.timestamp <= to))
+        {
+            let entry = by_provider.entry(purchase.provider_id.clone()).or_insert_with(|| ProviderRevenue {
+                provider_id: purchase.provider_id.clone(),
+                purchases: 0,
+                revenue: 0.0,
+                avg_payment: 0.0,
+                last_purchase_at: purchase.timestamp,
+            });
+            entry.purchases += 1;
+            entry.revenue += purchase.payment;
+            entry.last_purchase_at = entry.last_purchase_at.max(purchase.timestamp);
+        }
+        let mut providers: Vec<ProviderRevenue> = by_provider.into_values()
+            .map(|mut p| {
+                p.avg_payment = p.revenue / p.purchases as f64;
+                p
+            })
+            .collect();
+        providers.sort_by(|a, b| b.revenue.total_cmp(&a.revenue).then_with(|| a.provider_id.cmp(&b.provider_id)));
+        Self {
+            from,
+            to,
+            total_purchases: providers.iter().map(|p| p.purchases).sum(),
+            total_revenue: providers.iter().map(|p| p.revenue).sum(),
+            providers,
+        }
+    }
 }
 
 /// Signal marketplace for trading signals using X402 protocol
@@ -233,9 +679,25 @@ pub struct SignalMarketplace {
     pub signals: Arc<Mutex<HashMap<String, TradingSignalData>>>,
     pub providers: Arc<Mutex<HashMap<String, SignalProvider>>>,
     pub subscriptions: Arc<Mutex<HashMap<String, Vec<String>>>>, // user_id -> signal_ids
+    purchases: Arc<Mutex<VecDeque<PurchaseRecord>>>, // Bounded purchase log behind the revenue report
+    unsaved_purchases: Arc<Mutex<Vec<PurchaseRecord>>>, // Purchases not yet written, by flush_purchases
+    skipped_signals: Arc<Mutex<VecDeque<SkippedSignal>>>, // Bounded log of auto-execution skips
+    confidence_thresholds: Arc<Mutex<HashMap<String, f64>>>, // symbol -> auto-execution confidence override
+    default_confidence_threshold: Arc<Mutex<f64>>,
+    dry_run_providers: Arc<Mutex<HashSet<String>>>, // Providers whose signals always paper-trade
+    signal_dedupe: Arc<Mutex<SignalDedupeConfig>>,
+    executing_since: Arc<Mutex<HashMap<String, i64>>>, // signal_id -> when it was marked Executing
+    recovered_signals: Arc<Mutex<u64>>, // Stuck Executing signals force-reverted by the sweep or released by hand
+    min_risk_reward: f64, // Publish-time floor on target distance / stop distance (0 = off)
+    rejected_risk_reward: Arc<Mutex<u64>>, // Signals refused by the risk/reward floor
     oracle_client: Arc<SwitchboardClient>,
     dex_client: Arc<DexScreenerClient>,
     pumpfun_client: Arc<PumpFunClient>,
+    recorder: Option<crate::historical_data::SessionRecorder>, // Appends every published signal to the session file
+    database: Option<Arc<Mutex<crate::database::Database>>>, // Keeps signals and their final status for history queries
+    unsaved_signals: Arc<Mutex<HashMap<String, TradingSignalData>>>, // Latest change per signal, written by flush_signals
+    callbacks: Option<crate::signal_callbacks::SignalCallbacks>, // Durable published / executed callbacks
+    clock: Arc<dyn crate::historical_data::ReplayClock>, // Expiry, staleness and lock ages - the replay clock while replaying
 }
 
 impl SignalMarketplace {
@@ -244,12 +706,105 @@ impl SignalMarketplace {
             signals: Arc::new(Mutex::new(HashMap::new())),
             providers: Arc::new(Mutex::new(HashMap::new())),
             subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            purchases: Arc::new(Mutex::new(VecDeque::new())),
+            unsaved_purchases: Arc::new(Mutex::new(Vec::new())),
+            skipped_signals: Arc::new(Mutex::new(VecDeque::with_capacity(MAX_SKIPPED_SIGNALS))),
+            confidence_thresholds: Arc::new(Mutex::new(HashMap::new())),
+            default_confidence_threshold: Arc::new(Mutex::new(DEFAULT_CONFIDENCE_THRESHOLD)),
+            dry_run_providers: Arc::new(Mutex::new(HashSet::new())),
+            signal_dedupe: Arc::new(Mutex::new(SignalDedupeConfig::default())),
+            executing_since: Arc::new(Mutex::new(HashMap::new())),
+            recovered_signals: Arc::new(Mutex::new(0)),
+            min_risk_reward: DEFAULT_MIN_RISK_REWARD,
+            rejected_risk_reward: Arc::new(Mutex::new(0)),
             oracle_client: Arc::new(SwitchboardClient::new(rpc_url.clone(), std::env::var("SOLANA_RPC_URL").is_ok())),
             dex_client: Arc::new(DexScreenerClient::new()),
             pumpfun_client: Arc::new(PumpFunClient::new()),
+            recorder: None,
+            database: None,
+            unsaved_signals: Arc::new(Mutex::new(HashMap::new())),
+            callbacks: None,
+            clock: Arc::new(crate::historical_data::WallClock),
         }
     }
 
+    /// Persist published signals and their status changes so history survives cleanup and restarts
+    pub fn with_database(mut self, database: Arc<Mutex<crate::database::Database>>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Queue a published / executed callback for every marketplace signal (sandboxed ones excluded)
+    pub fn with_callbacks(mut self, callbacks: crate::signal_callbacks::SignalCallbacks) -> Self {
+        self.callbacks = Some(callbacks);
+        self
+    }
+
+    async fn emit_callback<T: Serialize>(&self, event: crate::signal_callbacks::SignalLifecycleEvent,
+                                         signal: &TradingSignalData, payload: &T) {
+        if signal.sandboxed {
+            return;
+        }
+        if let Some(ref callbacks) = self.callbacks {
+            callbacks.emit(event, &signal.id, payload).await;
+        }
+    }
+
+    /// Buffer signal changes for the next `flush_signals` - publishing and status updates never wait on a file write
+    async fn persist_signals(&self, signals: &[TradingSignalData]) {
+        if self.database.is_none() || signals.is_empty() {
+            return;
+        }
+        let mut unsaved = self.unsaved_signals.lock().await;
+        for signal in signals {
+            unsaved.insert(signal.id.clone(), signal.clone());
+        }
+    }
+
+    /// Write every buffered signal change to the database in one save. Returns how many were written
+    pub async fn flush_signals(&self) -> usize {
+        let Some(ref database) = self.database else {
+            return 0;
+        };
+        let signals: Vec<TradingSignalData> = self.unsaved_signals.lock().await.drain().map(|(_, s)| s).collect();
+        if signals.is_empty() {
+            return 0;
+        }
+        if let Err(e) = database.lock().await.upsert_signals(&signals) {
+            log::warn!("⚠️ Failed to persist {} signal(s) - retrying next flush: {}", signals.len(), e);
+            // Keep them for the next flush unless a newer change was buffered meanwhile
+            let mut unsaved = self.unsaved_signals.lock().await;
+            for signal in signals {
+                unsaved.entry(signal.id.clone()).or_insert(signal);
+            }
+            return 0;
+        }
+        signals.len()
+    }
+
+    /// Refuse signals whose risk/reward is below `min_ratio` (0 disables the check)
+    pub fn with_min_risk_reward(mut self, min_ratio: f64) -> Self {
+        self.min_risk_reward = min_ratio.max(0.0);
+        self
+    }
+
+    /// Record every published signal to a session file for later replay
+    pub fn with_recorder(mut self, recorder: crate::historical_data::SessionRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Age signals and execution locks on `clock` - the shared replay clock when replaying a session
+    pub fn with_clock(mut self, clock: Arc<dyn crate::historical_data::ReplayClock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current time (unix seconds) on the marketplace clock
+    pub fn now(&self) -> i64 {
+        self.clock.now_secs()
+    }
+
     /// Register a signal provider
     pub async fn register_provider(&self, id: String, name: String) -> Result<(), String> {
         let mut providers = self.providers.lock().await;
@@ -266,7 +821,33 @@ impl SignalMarketplace {
     /// 1. Available for autonomous execution (if confidence ≥75%)
     /// 2. Available for purchase by other agents
     /// 3. Tracked for performance metrics
-    pub async fn publish_signal(&self, signal: TradingSignalData) -> Result<String, String> {
+    pub async fn publish_signal(&self, mut signal: TradingSignalData) -> Result<String, String> {
+        // RISK/REWARD: A target too close for the stop it risks never reaches the executor
+        if let Err(reason) = validate_risk_reward(&signal, self.min_risk_reward) {
+            *self.rejected_risk_reward.lock().await += 1;
+            log::warn!("🚫 Rejected signal {} from {} ({}): {}", signal.id, signal.provider, signal.symbol, reason);
+            return Err(reason);
+        }
+        
+        // Providers flagged for paper trading never execute for real, whatever the global mode
+        if self.is_provider_dry_run(&signal.provider).await {
+            signal.force_dry_run = true;
+        }
+        
+        // PRICING: The provider's strategy sets what the signal sells for
+        if let Some(provider) = self.providers.lock().await.get(&signal.provider) {
+            signal.price = provider.signal_price(signal.price);
+        }
+        
+        // SANDBOX: Unvetted providers' signals paper-trade through the executor and the performance tracker,
+        // but stay off the marketplace listings until the provider is promoted
+        if self.is_provider_sandboxed(&signal.provider).await {
+            signal.sandboxed = true;
+            signal.force_dry_run = true;
+            log::info!("🧪 Sandboxed signal from {}: {} | Symbol: {} | Confidence: {:.1}%",
+                      signal.provider, signal.id, signal.symbol, signal.confidence * 100.0);
+        }
+        
         let mut signals = self.signals.lock().await;
         
         if signals.contains_key(&signal.id) {
@@ -276,9 +857,15 @@ impl SignalMarketplace {
         let signal_id = signal.id.clone();
         let signal_clone = signal.clone();
         let is_executable = signal_clone.confidence >= 0.75 && 
-                           signal_clone.expiry > chrono::Utc::now().timestamp();
+                           signal_clone.expiry > self.now();
         
         signals.insert(signal_id.clone(), signal);
+        drop(signals);
+        if let Some(ref recorder) = self.recorder {
+            recorder.record_signal(&signal_clone);
+        }
+        self.persist_signals(std::slice::from_ref(&signal_clone)).await;
+        self.emit_callback(crate::signal_callbacks::SignalLifecycleEvent::Published, &signal_clone, &signal_clone).await;
         
         log::info!("📡 Published signal to marketplace: {} | Symbol: {} | Confidence: {:.1}% | Price: {} tokens", 
                   signal_id, signal_clone.symbol, signal_clone.confidence * 100.0, signal_clone.price);
@@ -351,10 +938,11 @@ impl SignalMarketplace {
                     timeframe: "1h".to_string(),
                     data_sources: vec!["Switchboard Oracle".to_string()],
                     analysis: format!("Price movement of {:.2}% detected via oracle", change),
-                    timestamp: Utc::now().timestamp(),
-                    expiry: Utc::now().timestamp() + 3600, // 1 hour expiry
+                    timestamp: self.now(),
+                    expiry: self.now() + 3600, // 1 hour expiry
                     price: 10.0, // 10 tokens to buy this signal
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
                 
                 signals.push(signal);
@@ -386,10 +974,11 @@ impl SignalMarketplace {
                     data_sources: vec!["DEX Screener".to_string()],
                     analysis: format!("Opportunity score: {:.1}, Signals: {}", 
                         opp.opportunity_score, opp.signals.join(", ")),
-                    timestamp: Utc::now().timestamp(),
-                    expiry: Utc::now().timestamp() + 14400, // 4 hours expiry
+                    timestamp: self.now(),
+                    expiry: self.now() + 14400, // 4 hours expiry
                     price: 15.0,
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
                 
                 signals.push(signal);
@@ -427,10 +1016,11 @@ impl SignalMarketplace {
                     data_sources: vec!["PumpFun".to_string()],
                     analysis: format!("Meme coin: {}, Reasons: {}", 
                         meme.name, meme.reasons.join(", ")),
-                    timestamp: Utc::now().timestamp(),
-                    expiry: Utc::now().timestamp() + 900, // 15 min expiry
+                    timestamp: self.now(),
+                    expiry: self.now() + 900, // 15 min expiry
                     price: 20.0, // Higher price for meme signals
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
                 
                 signals.push(signal);
@@ -443,10 +1033,10 @@ impl SignalMarketplace {
     /// Get all active signals
     pub async fn get_active_signals(&self) -> Vec<TradingSignalData> {
         let signals = self.signals.lock().await;
-        let now = Utc::now().timestamp();
+        let now = self.now();
         
         signals.values()
-            .filter(|s| matches!(s.status, SignalStatus::Active) && s.expiry > now)
+            .filter(|s| !s.sandboxed && matches!(s.status, SignalStatus::Active) && s.expiry > now)
             .cloned()
             .collect()
     }
@@ -454,14 +1044,37 @@ impl SignalMarketplace {
     /// Get signals by symbol
     pub async fn get_signals_by_symbol(&self, symbol: &str) -> Vec<TradingSignalData> {
         let signals = self.signals.lock().await;
-        let now = Utc::now().timestamp();
+        let now = self.now();
         
         signals.values()
-            .filter(|s| s.symbol == symbol && matches!(s.status, SignalStatus::Active) && s.expiry > now)
+            .filter(|s| !s.sandboxed && s.symbol == symbol && matches!(s.status, SignalStatus::Active) && s.expiry > now)
             .cloned()
             .collect()
     }
 
+    /// Search current and persisted signals - the marketplace's copy wins when both have one
+    /// Newest first
+    pub async fn get_signals(&self, filter: &SignalFilter) -> Vec<TradingSignalData> {
+        let mut found: Vec<TradingSignalData> = {
+            let signals = self.signals.lock().await;
+            signals.values().filter(|s| filter.matches(s)).cloned().collect()
+        };
+        if let Some(ref database) = self.database {
+            // Then changes not yet flushed, then what the database holds
+            let mut known: HashSet<String> = found.iter().map(|s| s.id.clone()).collect();
+            let unsaved: Vec<TradingSignalData> = self.unsaved_signals.lock().await.values()
+                .filter(|s| !known.contains(&s.id) && filter.matches(s))
+                .cloned()
+                .collect();
+            known.extend(unsaved.iter().map(|s| s.id.clone()));
+            found.extend(unsaved);
+            let persisted = database.lock().await.query_signals(filter);
+            found.extend(persisted.into_iter().filter(|s| !known.contains(&s.id)));
+        }
+        found.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.id.cmp(&b.id)));
+        found
+    }
+
     /// Purchase a signal using X402 protocol
     pub async fn purchase_signal(&self, user_id: &str, signal_id: &str, payment: f64) -> Result<X402Message, String> {
         let mut signals = self.signals.lock().await;
@@ -471,7 +1084,7 @@ impl SignalMarketplace {
         let signal = signals.get_mut(signal_id)
             .ok_or("Signal not found")?;
         
-        if !matches!(signal.status, SignalStatus::Active) {
+        if !matches!(signal.status, SignalStatus::Active) || signal.sandboxed {
             return Err("Signal is not active".to_string());
         }
         
@@ -489,13 +1102,27 @@ impl SignalMarketplace {
             .or_insert_with(Vec::new)
             .push(signal_id.to_string());
         
+        let purchase = PurchaseRecord {
+            signal_id: signal_id.to_string(),
+            provider_id: signal.provider.clone(),
+            buyer_id: user_id.to_string(),
+            symbol: signal.symbol.clone(),
+            list_price: signal.price,
+            payment,
+            timestamp: self.now(),
+        };
+        drop(subscriptions);
+        drop(providers);
+        drop(signals);
+        self.record_purchase(purchase).await;
+        
         // Create X402 confirmation message
         let message = X402Message {
             protocol_version: "1.0".to_string(),
             message_type: X402MessageType::SignalConfirmation,
             sender_id: "marketplace".to_string(),
             receiver_id: Some(user_id.to_string()),
-            timestamp: Utc::now().timestamp(),
+            timestamp: self.now(),
             payload: X402Payload::Confirmation {
                 signal_id: signal_id.to_string(),
                 status: "purchased".to_string(),
@@ -508,6 +1135,50 @@ impl SignalMarketplace {
         Ok(message)
     }
 
+    async fn record_purchase(&self, purchase: PurchaseRecord) {
+        if self.database.is_some() {
+            self.unsaved_purchases.lock().await.push(purchase.clone());
+        }
+        let mut purchases = self.purchases.lock().await;
+        while purchases.len() >= MAX_PURCHASE_RECORDS {
+            purchases.pop_front();
+        }
+        purchases.push_back(purchase);
+    }
+
+    /// Write purchases made since the last flush to the database in one save. Returns how many were written
+    pub async fn flush_purchases(&self) -> usize {
+        let Some(ref database) = self.database else {
+            return 0;
+        };
+        let purchases: Vec<PurchaseRecord> = std::mem::take(&mut *self.unsaved_purchases.lock().await);
+        if purchases.is_empty() {
+            return 0;
+        }
+        if let Err(e) = database.lock().await.insert_purchases(&purchases) {
+            log::warn!("⚠️ Failed to persist {} purchase(s) - retrying next flush: {}", purchases.len(), e);
+            let mut unsaved = self.unsaved_purchases.lock().await;
+            let newer = std::mem::replace(&mut *unsaved, purchases);
+            unsaved.extend(newer);
+            return 0;
+        }
+        purchases.len()
+    }
+
+    /// Purchase payments per provider within [from, to] - persisted purchases (and any not yet flushed)
+    /// when a database is configured
+    pub async fn get_revenue_report(&self, from: Option<i64>, to: Option<i64>) -> RevenueReport {
+        let purchases: Vec<PurchaseRecord> = match self.database {
+            Some(ref database) => {
+                let mut purchases = database.lock().await.get_purchases().to_vec();
+                purchases.extend(self.unsaved_purchases.lock().await.iter().cloned());
+                purchases
+            }
+            None => self.purchases.lock().await.iter().cloned().collect(),
+        };
+        RevenueReport::from_purchases(&purchases, from, to)
+    }
+
     /// Create X402 message for signal offer
     pub fn create_signal_offer(&self, signal: TradingSignalData, provider_id: &str) -> X402Message {
         X402Message {
@@ -515,7 +1186,7 @@ impl SignalMarketplace {
             message_type: X402MessageType::SignalOffer,
             sender_id: provider_id.to_string(),
             receiver_id: None, // Broadcast to all
-            timestamp: Utc::now().timestamp(),
+            timestamp: self.now(),
             payload: X402Payload::Signal(signal),
             signature: None,
         }
@@ -538,7 +1209,7 @@ impl SignalMarketplace {
                     let signals = self.signals.lock().await;
                     
                     for signal in signals.values() {
-                        if symbols.contains(&signal.symbol) && signal.price <= max_price {
+                        if !signal.sandboxed && symbols.contains(&signal.symbol) && signal.price <= max_price {
                             matching_signals.push(signal.clone());
                         }
                     }
@@ -576,6 +1247,50 @@ impl SignalMarketplace {
         let providers = self.providers.lock().await;
         providers.values().cloned().collect()
     }
+    
+    /// Route a registered provider's future signals to the sandbox (true) or the live marketplace (false)
+    /// Changeable at runtime through POST /providers/{id}/sandbox and /promote; SANDBOX_PROVIDERS seeds it at startup
+    pub async fn set_provider_sandboxed(&self, provider_id: &str, sandboxed: bool) -> Result<(), String> {
+        let mut providers = self.providers.lock().await;
+        let provider = providers.get_mut(provider_id)
+            .ok_or_else(|| format!("Provider {} is not registered", provider_id))?;
+        provider.sandboxed = sandboxed;
+        Ok(())
+    }
+    
+    /// Price a registered provider's future signals with `strategy`
+    pub async fn set_signal_pricing_strategy(&self, provider_id: &str, strategy: SignalPricingStrategy) -> Result<SignalProvider, String> {
+        strategy.validate()?;
+        let mut providers = self.providers.lock().await;
+        let provider = providers.get_mut(provider_id)
+            .ok_or_else(|| format!("Provider {} is not registered", provider_id))?;
+        provider.pricing = strategy;
+        log::info!("🏷️ Provider {} now prices signals with {:?}", provider_id, strategy);
+        Ok(provider.clone())
+    }
+    
+    pub async fn is_provider_sandboxed(&self, provider_id: &str) -> bool {
+        self.providers.lock().await.get(provider_id).is_some_and(|p| p.sandboxed)
+    }
+    
+    /// Take a provider out of the sandbox - signals it has already sandboxed stay there
+    pub async fn promote_provider(&self, provider_id: &str) -> Result<SignalProvider, String> {
+        self.set_provider_sandboxed(provider_id, false).await?;
+        log::info!("🎓 Provider {} promoted out of the sandbox - its signals now reach the auto-executor", provider_id);
+        self.get_provider_stats(provider_id).await
+            .ok_or_else(|| format!("Provider {} is not registered", provider_id))
+    }
+    
+    /// Sandboxed signals, optionally for one provider, newest first
+    pub async fn get_sandbox_signals(&self, provider_id: Option<&str>) -> Vec<TradingSignalData> {
+        let stored = self.signals.lock().await;
+        let mut signals: Vec<TradingSignalData> = stored.values()
+            .filter(|s| s.sandboxed && provider_id.is_none_or(|p| s.provider == p))
+            .cloned()
+            .collect();
+        signals.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        signals
+    }
 
     /// Get marketplace statistics
     pub async fn get_marketplace_stats(&self) -> HashMap<String, String> {
@@ -583,14 +1298,23 @@ impl SignalMarketplace {
         let providers = self.providers.lock().await;
         let subscriptions = self.subscriptions.lock().await;
         
-        let now = Utc::now().timestamp();
+        let now = self.now();
         let active_signals = signals.values()
-            .filter(|s| matches!(s.status, SignalStatus::Active) && s.expiry > now)
+            .filter(|s| !s.sandboxed && matches!(s.status, SignalStatus::Active) && s.expiry > now)
             .count();
+        let executing_signals = signals.values()
+            .filter(|s| matches!(s.status, SignalStatus::Executing))
+            .count();
+        let recovered_signals = *self.recovered_signals.lock().await;
+        let rejected_risk_reward = *self.rejected_risk_reward.lock().await;
         
         let mut stats = HashMap::new();
         stats.insert("total_signals".to_string(), signals.len().to_string());
         stats.insert("active_signals".to_string(), active_signals.to_string());
+        stats.insert("executing_signals".to_string(), executing_signals.to_string());
+        stats.insert("recovered_signals".to_string(), recovered_signals.to_string());
+        stats.insert("rejected_risk_reward".to_string(), rejected_risk_reward.to_string());
+        stats.insert("min_risk_reward".to_string(), format!("{:.2}", self.min_risk_reward));
         stats.insert("total_providers".to_string(), providers.len().to_string());
         stats.insert("total_subscriptions".to_string(), subscriptions.len().to_string());
         stats.insert("protocol_version".to_string(), "X402-1.0".to_string());
@@ -601,20 +1325,21 @@ impl SignalMarketplace {
     /// Clean up expired signals and remove old ones to prevent memory leaks
     pub async fn cleanup_expired_signals(&self) {
         let mut signals = self.signals.lock().await;
-        let now = Utc::now().timestamp();
+        let now = self.now();
         const CLEANUP_DELAY_SECS: i64 = 86400; // Keep signals for 24 hours after expiry
         
         // First pass: Mark expired signals and collect IDs of old signals to remove
         let mut to_remove = Vec::new();
-        let mut expired_count = 0u32;
+        let mut expired = Vec::new();
         
         for (_id, signal) in signals.iter_mut() {
             // Mark active signals that have expired
             if signal.expiry < now && matches!(signal.status, SignalStatus::Active) {
                 signal.status = SignalStatus::Expired;
-                expired_count += 1;
+                expired.push(signal.clone());
             }
         }
+        let expired_count = expired.len();
         
         // Second pass: Collect IDs of signals to remove (after marking is done)
         for (id, signal) in signals.iter() {
@@ -632,6 +1357,8 @@ impl SignalMarketplace {
         for id in to_remove {
             signals.remove(&id);
         }
+        drop(signals);
+        self.persist_signals(&expired).await;
         
         if removed_count > 0 {
             log::info!("🧹 Cleaned up {} expired signals (older than 24h)", removed_count);
@@ -642,21 +1369,239 @@ impl SignalMarketplace {
         }
     }
     
+    /// Signals that have sat in Executing for longer than `timeout_secs`
+    pub async fn stuck_executing_signals(&self, timeout_secs: i64) -> Vec<String> {
+        let signals = self.signals.lock().await;
+        let executing_since = self.executing_since.lock().await;
+        let cutoff = self.now() - timeout_secs;
+        executing_since.iter()
+            .filter(|(id, since)| **since <= cutoff
+                && signals.get(*id).is_some_and(|s| matches!(s.status, SignalStatus::Executing)))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+    
+    /// Force stuck Executing signals back to Active (or Expired once past their expiry)
+    /// Callers filter out signals whose swap is still live on-chain - see `stuck_executing_signals`
+    pub async fn recover_stuck_signals(&self, signal_ids: &[String]) -> usize {
+        let mut signals = self.signals.lock().await;
+        let mut executing_since = self.executing_since.lock().await;
+        let now = self.now();
+        let mut recovered_signals = Vec::new();
+        
+        for id in signal_ids {
+            let Some(signal) = signals.get_mut(id) else {
+                executing_since.remove(id);
+                continue;
+            };
+            if !matches!(signal.status, SignalStatus::Executing) {
+                continue;
+            }
+            let stuck_for = executing_since.remove(id).map(|since| now - since).unwrap_or_default();
+            signal.status = if signal.expiry > now { SignalStatus::Active } else { SignalStatus::Expired };
+            log::warn!("🩹 Recovered signal {} stuck in Executing for {}s -> {:?}", id, stuck_for, signal.status);
+            recovered_signals.push(signal.clone());
+        }
+        let recovered = recovered_signals.len();
+        drop(signals);
+        drop(executing_since);
+        self.persist_signals(&recovered_signals).await;
+        
+        if recovered > 0 {
+            *self.recovered_signals.lock().await += recovered as u64;
+        }
+        recovered
+    }
+    
+    /// Every signal currently in Executing and how long it has held the lock, longest first
+    pub async fn execution_locks(&self) -> Vec<ExecutionLock> {
+        let signals = self.signals.lock().await;
+        let executing_since = self.executing_since.lock().await;
+        let now = self.now();
+        let mut locks: Vec<ExecutionLock> = signals.values()
+            .filter(|s| matches!(s.status, SignalStatus::Executing))
+            .map(|s| {
+                let locked_at = executing_since.get(&s.id).copied();
+                ExecutionLock {
+                    signal_id: s.id.clone(),
+                    provider: s.provider.clone(),
+                    symbol: s.symbol.clone(),
+                    locked_at,
+                    locked_for_secs: locked_at.map(|since| now - since),
+                    expiry: s.expiry,
+                }
+            })
+            .collect();
+        locks.sort_by_key(|lock| (lock.locked_at.unwrap_or(i64::MIN), lock.signal_id.clone()));
+        locks
+    }
+    
+    /// Operator release of one Executing lock, as the stuck-signal sweep would do it without waiting for the timeout
+    /// Returns the status the signal went back to (Active, or Expired once past its expiry)
+    pub async fn release_execution_lock(&self, signal_id: &str) -> Result<SignalStatus, String> {
+        match self.get_signal(signal_id).await {
+            None => return Err(format!("Signal {} not found", signal_id)),
+            Some(signal) if !matches!(signal.status, SignalStatus::Executing) => {
+                return Err(format!("Signal {} is not executing ({:?})", signal_id, signal.status));
+            }
+            Some(_) => {}
+        }
+        self.recover_stuck_signals(&[signal_id.to_string()]).await;
+        self.get_signal(signal_id).await
+            .map(|signal| signal.status)
+            .ok_or_else(|| format!("Signal {} not found", signal_id))
+    }
+    
     /// Get high-confidence signals ready for auto-execution
     /// Returns signals with confidence >= threshold that are still active
     /// FIX #1: Only returns Active signals (not Executing, Filled, etc.)
-    pub async fn get_executable_signals(&self, min_confidence: f64) -> Vec<TradingSignalData> {
-        let signals = self.signals.lock().await;
-        let now = Utc::now().timestamp();
+    /// Near-identical signals (same symbol + action within the dedupe window) collapse to the
+    /// highest-confidence one; the rest are marked Superseded and counted against their providers
+    /// ORDERING: Most time-sensitive, highest-confidence first (see `TradingSignalData::execution_priority`),
+    /// then soonest expiry, then oldest - so short scalps aren't left to expire behind long-horizon signals
+    pub async fn get_executable_signals(&self) -> Vec<TradingSignalData> {
+        let (overrides, default_threshold) = self.get_confidence_thresholds().await;
+        let dedupe = self.signal_dedupe.lock().await.clone();
+        let mut signals = self.signals.lock().await;
+        let now = self.now();
         
-        signals.values()
+        let mut executable: Vec<TradingSignalData> = signals.values()
             .filter(|s| {
+                let min_confidence = overrides.get(&s.symbol).copied().unwrap_or(default_threshold);
                 matches!(s.status, SignalStatus::Active)
                     && s.expiry > now
                     && s.confidence >= min_confidence
             })
             .cloned()
-            .collect()
+            .collect();
+        
+        if !dedupe.enabled {
+            Self::sort_by_execution_priority(&mut executable, now);
+            return executable;
+        }
+        
+        // Signals already being executed or filled anchor their window too, so a duplicate
+        // arriving one cycle later doesn't open a second position
+        let mut kept: Vec<TradingSignalData> = signals.values()
+            .filter(|s| matches!(s.status, SignalStatus::Executing | SignalStatus::Filled))
+            .cloned()
+            .collect();
+        
+        executable.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.timestamp.cmp(&b.timestamp)));
+        
+        let mut superseded = Vec::new();
+        let mut result = Vec::new();
+        for signal in executable {
+            // Sandboxed signals never displace live ones (or the reverse)
+            let winner = kept.iter().find(|k| {
+                k.sandboxed == signal.sandboxed
+                    && k.symbol == signal.symbol
+                    && k.action == signal.action
+                    && (k.timestamp - signal.timestamp).abs() <= dedupe.window_secs
+            });
+            match winner {
+                Some(winner) => superseded.push((signal, winner.id.clone(), winner.provider.clone())),
+                None => {
+                    kept.push(signal.clone());
+                    result.push(signal);
+                }
+            }
+        }
+        
+        let mut superseded_signals = Vec::new();
+        for (signal, _, _) in &superseded {
+            if let Some(stored) = signals.get_mut(&signal.id) {
+                stored.status = SignalStatus::Superseded;
+                superseded_signals.push(stored.clone());
+            }
+        }
+        drop(signals);
+        self.persist_signals(&superseded_signals).await;
+        
+        for (signal, winner_id, winner_provider) in superseded {
+            log::info!("🔁 Signal {} ({} / {:?}, {:.1}%) superseded by {} from {}",
+                      signal.id, signal.symbol, signal.action, signal.confidence * 100.0, winner_id, winner_provider);
+            if let Some(provider) = self.providers.lock().await.get_mut(&signal.provider) {
+                provider.superseded_signals += 1;
+            }
+            self.record_skipped_signal(&signal, SkipReason::Superseded, format!(
+                "Duplicate of {} from {} within {}s", winner_id, winner_provider, dedupe.window_secs
+            )).await;
+        }
+        
+        Self::sort_by_execution_priority(&mut result, now);
+        result
+    }
+    
+    fn sort_by_execution_priority(signals: &mut [TradingSignalData], now: i64) {
+        signals.sort_by(|a, b| b.execution_priority(now).partial_cmp(&a.execution_priority(now))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.expiry.cmp(&b.expiry))
+            .then(a.timestamp.cmp(&b.timestamp)));
+    }
+    
+    pub async fn set_signal_dedupe(&self, config: SignalDedupeConfig) {
+        *self.signal_dedupe.lock().await = config;
+    }
+    
+    /// Auto-execution confidence bar for a symbol (per-symbol override or the global default)
+    pub async fn get_confidence_threshold(&self, symbol: &str) -> f64 {
+        if let Some(threshold) = self.confidence_thresholds.lock().await.get(symbol) {
+            return *threshold;
+        }
+        *self.default_confidence_threshold.lock().await
+    }
+    
+    /// Per-symbol overrides and the global default
+    pub async fn get_confidence_thresholds(&self) -> (HashMap<String, f64>, f64) {
+        let overrides = self.confidence_thresholds.lock().await.clone();
+        let default_threshold = *self.default_confidence_threshold.lock().await;
+        (overrides, default_threshold)
+    }
+    
+    /// Set the confidence threshold for a symbol, or the global default when `symbol` is None
+    pub async fn set_confidence_threshold(&self, symbol: Option<&str>, threshold: f64) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(format!("Confidence threshold must be between 0.0 and 1.0, got {}", threshold));
+        }
+        match symbol {
+            Some(symbol) => {
+                self.confidence_thresholds.lock().await.insert(symbol.to_string(), threshold);
+                log::info!("🎚️ Confidence threshold for {} set to {:.1}%", symbol, threshold * 100.0);
+            }
+            None => {
+                *self.default_confidence_threshold.lock().await = threshold;
+                log::info!("🎚️ Default confidence threshold set to {:.1}%", threshold * 100.0);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Drop a symbol's override so it falls back to the global default
+    pub async fn remove_confidence_threshold(&self, symbol: &str) -> bool {
+        self.confidence_thresholds.lock().await.remove(symbol).is_some()
+    }
+    
+    /// Paper-trade every future signal from `provider_id` (or stop doing so)
+    pub async fn set_provider_dry_run(&self, provider_id: &str, enabled: bool) {
+        let mut providers = self.dry_run_providers.lock().await;
+        if enabled {
+            providers.insert(provider_id.to_string());
+        } else {
+            providers.remove(provider_id);
+        }
+    }
+    
+    pub async fn is_provider_dry_run(&self, provider_id: &str) -> bool {
+        self.dry_run_providers.lock().await.contains(provider_id)
+    }
+    
+    pub async fn get_dry_run_providers(&self) -> Vec<String> {
+        let mut providers: Vec<String> = self.dry_run_providers.lock().await.iter().cloned().collect();
+        providers.sort();
+        providers
     }
     
     /// FIX #1: Atomically mark signal as Executing (returns false if already Executing/Filled)
@@ -667,6 +1612,7 @@ impl SignalMarketplace {
             // Only allow transition from Active to Executing
             if matches!(signal.status, SignalStatus::Active) {
                 signal.status = SignalStatus::Executing;
+                self.executing_since.lock().await.insert(signal_id.to_string(), self.now());
                 log::info!("🔒 Signal {} marked as Executing (atomic lock)", signal_id);
                 Ok(true)
             } else {
@@ -679,7 +1625,20 @@ impl SignalMarketplace {
     }
     
     /// Update signal status with validation for valid transitions
+    /// Executions should fill through `mark_signal_executed`, which reports the fill to callbacks
     pub async fn update_signal_status(&self, signal_id: &str, new_status: SignalStatus) -> Result<(), String> {
+        self.transition_signal(signal_id, new_status).await.map(|_| ())
+    }
+    
+    /// Mark an executing signal Filled and queue the executed callback with the price and size it filled at
+    pub async fn mark_signal_executed(&self, signal_id: &str, fill_price: f64, fill_size: Option<f64>) -> Result<(), String> {
+        let updated = self.transition_signal(signal_id, SignalStatus::Filled).await?;
+        let executed = crate::signal_callbacks::ExecutedSignal { signal: &updated, fill_price, fill_size };
+        self.emit_callback(crate::signal_callbacks::SignalLifecycleEvent::Executed, &updated, &executed).await;
+        Ok(())
+    }
+    
+    async fn transition_signal(&self, signal_id: &str, new_status: SignalStatus) -> Result<TradingSignalData, String> {
         let mut signals = self.signals.lock().await;
         if let Some(signal) = signals.get_mut(signal_id) {
             let old_status = signal.status.clone();
@@ -690,12 +1649,14 @@ impl SignalMarketplace {
                 (SignalStatus::Active, SignalStatus::Executing) => true,
                 (SignalStatus::Active, SignalStatus::Expired) => true,
                 (SignalStatus::Active, SignalStatus::Cancelled) => true,
+                (SignalStatus::Active, SignalStatus::Superseded) => true,
                 (SignalStatus::Executing, SignalStatus::Filled) => true,
                 (SignalStatus::Executing, SignalStatus::Active) => true, // On failure, revert
                 // Invalid transitions
                 (SignalStatus::Filled, _) => false, // Can't change filled signals
                 (SignalStatus::Expired, _) => false, // Can't change expired signals
                 (SignalStatus::Cancelled, _) => false, // Can't change cancelled signals
+                (SignalStatus::Superseded, _) => false, // Can't change superseded signals
                 (_, SignalStatus::Executing) => false, // Can only go to Executing from Active
                 _ => false,
             };
@@ -708,8 +1669,18 @@ impl SignalMarketplace {
             }
             
             signal.status = new_status.clone();
+            let mut executing_since = self.executing_since.lock().await;
+            if matches!(new_status, SignalStatus::Executing) {
+                executing_since.insert(signal_id.to_string(), self.now());
+            } else {
+                executing_since.remove(signal_id);
+            }
             log::info!("📊 Signal {} status updated: {:?} -> {:?}", signal_id, old_status, new_status);
-            Ok(())
+            let updated = signal.clone();
+            drop(executing_since);
+            drop(signals);
+            self.persist_signals(std::slice::from_ref(&updated)).await;
+            Ok(updated)
         } else {
             Err(format!("Signal {} not found", signal_id))
         }
@@ -720,11 +1691,79 @@ impl SignalMarketplace {
         let signals = self.signals.lock().await;
         signals.get(signal_id).cloned()
     }
+    
+    /// Record why a signal was not executed
+    /// Repeated skips of the same signal for the same reason refresh the existing entry
+    /// instead of flooding the log every execution cycle
+    pub async fn record_skipped_signal(&self, signal: &TradingSignalData, reason: SkipReason, detail: String) {
+        let mut skipped = self.skipped_signals.lock().await;
+        
+        skipped.retain(|s| !(s.signal_id == signal.id && s.reason == reason));
+        
+        // RESOURCE LIMIT: Drop oldest entries once the log is full
+        while skipped.len() >= MAX_SKIPPED_SIGNALS {
+            skipped.pop_front();
+        }
+        
+        log::debug!(event = "signal_skipped", signal_id = signal.id.as_str(), symbol = signal.symbol.as_str(),
+                    provider = signal.provider.as_str(), confidence = signal.confidence, reason:? = reason;
+                    "⏭️ Skipped signal {} ({}): {:?} - {}", signal.id, signal.symbol, reason, detail);
+        skipped.push_back(SkippedSignal {
+            signal_id: signal.id.clone(),
+            provider: signal.provider.clone(),
+            symbol: signal.symbol.clone(),
+            confidence: signal.confidence,
+            reason,
+            detail,
+            timestamp: self.now(),
+        });
+    }
+    
+    /// Log Active signals that the auto-executor will pass over this cycle
+    /// (expired or below the confidence threshold)
+    pub async fn record_unexecutable_signals(&self) {
+        let (overrides, default_threshold) = self.get_confidence_thresholds().await;
+        let now = self.now();
+        let candidates: Vec<(TradingSignalData, SkipReason, String)> = {
+            let signals = self.signals.lock().await;
+            signals.values()
+                .filter(|s| matches!(s.status, SignalStatus::Active))
+                .filter_map(|s| {
+                    let min_confidence = overrides.get(&s.symbol).copied().unwrap_or(default_threshold);
+                    if s.expiry <= now {
+                        Some((s.clone(), SkipReason::Expired, format!("Expired {}s ago", now - s.expiry)))
+                    } else if s.confidence < min_confidence {
+                        Some((s.clone(), SkipReason::ConfidenceBelowThreshold, format!(
+                            "Confidence {:.1}% below threshold {:.1}%", s.confidence * 100.0, min_confidence * 100.0
+                        )))
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+        
+        for (signal, reason, detail) in candidates {
+            self.record_skipped_signal(&signal, reason, detail).await;
+        }
+    }
+    
+    /// Get the most recent skipped signals (newest first), optionally filtered by reason
+    pub async fn get_skipped_signals(&self, limit: usize, reason: Option<SkipReason>) -> Vec<SkippedSignal> {
+        let skipped = self.skipped_signals.lock().await;
+        skipped.iter()
+            .rev()
+            .filter(|s| reason.is_none() || reason == Some(s.reason))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
 
     #[tokio::test]
     async fn test_marketplace_creation() {
@@ -769,6 +1808,7 @@ mod tests {
             expiry: Utc::now().timestamp() + 3600,
             price: 10.0,
             status: SignalStatus::Active,
+            ..Default::default()
         };
         
         let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
@@ -777,4 +1817,493 @@ mod tests {
         assert_eq!(message.protocol_version, "1.0");
         assert!(matches!(message.message_type, X402MessageType::SignalOffer));
     }
+
+    #[tokio::test]
+    async fn test_skipped_signal_log() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let signal = TradingSignalData {
+            id: "skip1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.6,
+            timeframe: "1h".to_string(),
+            data_sources: vec!["Oracle".to_string()],
+            analysis: "Test signal".to_string(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        
+        marketplace.record_skipped_signal(&signal, SkipReason::ConfidenceBelowThreshold, "60% < 75%".to_string()).await;
+        // Same signal and reason should refresh, not duplicate
+        marketplace.record_skipped_signal(&signal, SkipReason::ConfidenceBelowThreshold, "60% < 75%".to_string()).await;
+        marketplace.record_skipped_signal(&signal, SkipReason::Drawdown, "Drawdown 25%".to_string()).await;
+        
+        assert_eq!(marketplace.get_skipped_signals(10, None).await.len(), 2);
+        assert_eq!(marketplace.get_skipped_signals(1, None).await[0].reason, SkipReason::Drawdown);
+        
+        let filtered = marketplace.get_skipped_signals(10, Some(SkipReason::ConfidenceBelowThreshold)).await;
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].signal_id, "skip1");
+        
+        assert_eq!("position_cap".parse::<SkipReason>(), Ok(SkipReason::PositionCap));
+        assert!("nonsense".parse::<SkipReason>().is_err());
+        assert_eq!(SkipReason::from_execution_error("Insufficient balance for signal: x"), SkipReason::InsufficientBalance);
+        assert_eq!(SkipReason::from_execution_error("Price impact 7.00% at size 10 exceeds the 5.00% cap for X"), SkipReason::PriceImpact);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_provider_flags_published_signals() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let make_signal = |id: &str, provider: &str| TradingSignalData {
+            id: id.to_string(),
+            provider: provider.to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        
+        marketplace.set_provider_dry_run("master_analyzer", true).await;
+        marketplace.publish_signal(make_signal("new", "master_analyzer")).await.unwrap();
+        marketplace.publish_signal(make_signal("proven", "memecoin_monitor")).await.unwrap();
+        
+        let signals = marketplace.signals.lock().await;
+        assert!(signals["new"].force_dry_run);
+        assert!(!signals["proven"].force_dry_run);
+        drop(signals);
+        
+        marketplace.set_provider_dry_run("master_analyzer", false).await;
+        assert!(marketplace.get_dry_run_providers().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sandboxed_provider_only_paper_trades() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        marketplace.register_provider("experimental".to_string(), "Experimental".to_string()).await.unwrap();
+        marketplace.set_provider_sandboxed("experimental", true).await.unwrap();
+        assert!(marketplace.set_provider_sandboxed("unknown", true).await.is_err());
+        let make_signal = |id: &str| TradingSignalData::fixture(id, "experimental", "BONK");
+        
+        // Paper-traded through the executor, but never listed or sold
+        marketplace.publish_signal(make_signal("sandboxed")).await.unwrap();
+        let executable = marketplace.get_executable_signals().await;
+        assert_eq!(executable.len(), 1);
+        assert!(executable[0].sandboxed && executable[0].force_dry_run);
+        assert!(marketplace.get_active_signals().await.is_empty());
+        assert!(marketplace.purchase_signal("buyer", "sandboxed", 100.0).await.is_err());
+        assert_eq!(marketplace.get_sandbox_signals(Some("experimental")).await.len(), 1);
+        assert!(marketplace.get_sandbox_signals(Some("memecoin_monitor")).await.is_empty());
+        
+        assert!(!marketplace.promote_provider("experimental").await.unwrap().sandboxed);
+        marketplace.publish_signal(make_signal("live")).await.unwrap();
+        // The live signal isn't superseded by the sandboxed one for the same symbol
+        let executable = marketplace.get_executable_signals().await;
+        assert_eq!(executable.len(), 2);
+        let live = executable.iter().find(|s| s.id == "live").unwrap();
+        assert!(!live.sandboxed && !live.force_dry_run);
+        assert_eq!(marketplace.get_active_signals().await.len(), 1);
+        // Already-sandboxed signals don't follow the provider out
+        assert_eq!(marketplace.get_sandbox_signals(None).await[0].id, "sandboxed");
+    }
+
+    #[tokio::test]
+    async fn test_executable_signals_ordered_by_urgency() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let now = Utc::now().timestamp();
+        let make_signal = |id: &str, symbol: &str, confidence: f64, ttl: i64| TradingSignalData {
+            id: id.to_string(),
+            provider: "memecoin_monitor".to_string(),
+            symbol: symbol.to_string(),
+            action: SignalAction::Buy,
+            entry_price: 1.0,
+            target_price: 1.1,
+            stop_loss: 0.95,
+            confidence,
+            timeframe: "15m".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry: now + ttl,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        {
+            let mut signals = marketplace.signals.lock().await;
+            signals.insert("swing".to_string(), make_signal("swing", "SOL/USD", 0.95, 7_200));
+            signals.insert("scalp".to_string(), make_signal("scalp", "BONK", 0.80, 600));
+            signals.insert("swing_low".to_string(), make_signal("swing_low", "JUP", 0.85, 10_800));
+        }
+        
+        // The 10-minute scalp jumps the 2-hour swing; long-horizon signals keep confidence order
+        let order: Vec<String> = marketplace.get_executable_signals().await.into_iter().map(|s| s.id).collect();
+        assert_eq!(order, vec!["scalp", "swing", "swing_low"]);
+        
+        let scalp = make_signal("s", "BONK", 0.80, 600);
+        assert!((scalp.execution_priority(now + 600) - (0.80 + TTL_PRIORITY_BOOST)).abs() < 1e-9);
+        assert_eq!(make_signal("l", "SOL/USD", 0.9, 9_000).execution_priority(now), 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_signal_pricing_and_revenue_report() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        marketplace.register_provider("alpha".to_string(), "Alpha".to_string()).await.unwrap();
+        marketplace.register_provider("beta".to_string(), "Beta".to_string()).await.unwrap();
+        let performance = SignalPricingStrategy::Performance { min_multiplier: 0.5, max_multiplier: 2.0 };
+        marketplace.set_signal_pricing_strategy("alpha", performance).await.unwrap();
+        marketplace.set_signal_pricing_strategy("beta", SignalPricingStrategy::Flat { price: 12.0 }).await.unwrap();
+        assert!(marketplace.set_signal_pricing_strategy("alpha", SignalPricingStrategy::Performance {
+            min_multiplier: 2.0, max_multiplier: 1.0,
+        }).await.is_err());
+        
+        // Reputation alone prices a new provider; hit rate joins once enough signals resolved
+        let mut provider = SignalProvider::new("p".to_string(), "P".to_string());
+        provider.pricing = performance;
+        provider.reputation_score = 80.0;
+        assert_eq!(provider.signal_price(10.0), 17.0);
+        provider.total_signals = MIN_SIGNALS_FOR_ACCURACY_PRICING;
+        provider.successful_signals = 4;
+        assert_eq!(provider.signal_price(10.0), 14.0);
+        
+        let make_signal = |id: &str, provider: &str| TradingSignalData { price: 20.0, ..TradingSignalData::fixture(id, provider, "BONK") };
+        marketplace.publish_signal(make_signal("a1", "alpha")).await.unwrap();
+        marketplace.publish_signal(make_signal("b1", "beta")).await.unwrap();
+        // Default reputation 50 -> 0.5 + 1.5 * 0.5 = 1.25x
+        assert_eq!(marketplace.signals.lock().await["a1"].price, 25.0);
+        assert_eq!(marketplace.signals.lock().await["b1"].price, 12.0);
+        assert!(marketplace.purchase_signal("buyer", "a1", 20.0).await.is_err());
+        
+        marketplace.purchase_signal("buyer", "a1", 25.0).await.unwrap();
+        marketplace.purchase_signal("other", "a1", 30.0).await.unwrap();
+        marketplace.purchase_signal("buyer", "b1", 12.0).await.unwrap();
+        let report = marketplace.get_revenue_report(None, None).await;
+        assert_eq!(report.total_purchases, 3);
+        assert_eq!(report.total_revenue, 67.0);
+        assert_eq!(report.providers[0].provider_id, "alpha");
+        assert_eq!(report.providers[0].revenue, 55.0);
+        assert_eq!(report.providers[0].avg_payment, 27.5);
+        let future = Utc::now().timestamp() + 60;
+        assert_eq!(marketplace.get_revenue_report(Some(future), None).await.total_purchases, 0);
+        
+        // With a database, purchases are written together on the next flush and counted before it
+        let database = Arc::new(Mutex::new(crate::database::Database::new_in_memory()));
+        let persisted = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string())
+            .with_database(database.clone());
+        persisted.register_provider("beta".to_string(), "Beta".to_string()).await.unwrap();
+        persisted.publish_signal(make_signal("b2", "beta")).await.unwrap();
+        persisted.purchase_signal("buyer", "b2", 20.0).await.unwrap();
+        persisted.purchase_signal("other", "b2", 20.0).await.unwrap();
+        assert!(database.lock().await.get_purchases().is_empty());
+        assert_eq!(persisted.get_revenue_report(None, None).await.total_purchases, 2);
+        assert_eq!(persisted.flush_purchases().await, 2);
+        assert_eq!(database.lock().await.get_purchases().len(), 2);
+        assert_eq!(persisted.get_revenue_report(None, None).await.total_purchases, 2);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_signals_keep_highest_confidence() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        marketplace.register_provider("memecoin_monitor".to_string(), "Memecoin Monitor".to_string()).await.unwrap();
+        let now = Utc::now().timestamp();
+        let make_signal = |id: &str, provider: &str, symbol: &str, confidence: f64, timestamp: i64| TradingSignalData {
+            id: id.to_string(),
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp,
+            expiry: now + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        {
+            let mut signals = marketplace.signals.lock().await;
+            signals.insert("monitor".to_string(), make_signal("monitor", "memecoin_monitor", "PUMP", 0.80, now));
+            signals.insert("jupiter".to_string(), make_signal("jupiter", "jupiter_memecoin_trader", "PUMP", 0.90, now + 5));
+            signals.insert("other".to_string(), make_signal("other", "memecoin_monitor", "BONK", 0.80, now));
+            signals.insert("later".to_string(), make_signal("later", "memecoin_monitor", "PUMP", 0.85, now + 600));
+        }
+        
+        let mut executable: Vec<String> = marketplace.get_executable_signals().await.into_iter().map(|s| s.id).collect();
+        executable.sort();
+        assert_eq!(executable, vec!["jupiter", "later", "other"]);
+        
+        let superseded = marketplace.get_signal("monitor").await.unwrap();
+        assert_eq!(superseded.status, SignalStatus::Superseded);
+        assert_eq!(marketplace.providers.lock().await["memecoin_monitor"].superseded_signals, 1);
+        let skipped = marketplace.get_skipped_signals(10, Some(SkipReason::Superseded)).await;
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].signal_id, "monitor");
+        
+        // An executing signal still blocks a late duplicate
+        marketplace.update_signal_status("jupiter", SignalStatus::Executing).await.unwrap();
+        marketplace.signals.lock().await.insert("late_dup".to_string(), make_signal("late_dup", "memecoin_monitor", "PUMP", 0.95, now + 30));
+        assert!(!marketplace.get_executable_signals().await.iter().any(|s| s.id == "late_dup"));
+        
+        // Disabled: duplicates pass through untouched
+        marketplace.set_signal_dedupe(SignalDedupeConfig { enabled: false, window_secs: 60 }).await;
+        marketplace.signals.lock().await.insert("dup2".to_string(), make_signal("dup2", "memecoin_monitor", "BONK", 0.79, now));
+        assert_eq!(marketplace.get_executable_signals().await.iter().filter(|s| s.symbol == "BONK").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_per_symbol_confidence_threshold() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let make_signal = |id: &str, symbol: &str, confidence: f64| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: symbol.to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        {
+            let mut signals = marketplace.signals.lock().await;
+            signals.insert("sol".to_string(), make_signal("sol", "SOL/USD", 0.72));
+            signals.insert("meme".to_string(), make_signal("meme", "PUMP", 0.80));
+        }
+        
+        // Global default (75%) only lets the 80% signal through
+        assert_eq!(marketplace.get_confidence_threshold("SOL/USD").await, DEFAULT_CONFIDENCE_THRESHOLD);
+        let executable = marketplace.get_executable_signals().await;
+        assert_eq!(executable.len(), 1);
+        assert_eq!(executable[0].id, "meme");
+        
+        marketplace.set_confidence_threshold(Some("SOL/USD"), 0.70).await.unwrap();
+        marketplace.set_confidence_threshold(Some("PUMP"), 0.85).await.unwrap();
+        let executable = marketplace.get_executable_signals().await;
+        assert_eq!(executable.len(), 1);
+        assert_eq!(executable[0].id, "sol");
+        assert_eq!(marketplace.get_confidence_threshold("PUMP").await, 0.85);
+        
+        // Removing the override falls back to a lowered global default
+        assert!(marketplace.remove_confidence_threshold("PUMP").await);
+        marketplace.set_confidence_threshold(None, 0.78).await.unwrap();
+        assert_eq!(marketplace.get_executable_signals().await.len(), 2);
+        
+        assert!(marketplace.set_confidence_threshold(Some("SOL/USD"), 1.5).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stuck_executing_signals_are_recovered() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let now = Utc::now().timestamp();
+        let make_signal = |id: &str, expiry: i64| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.9,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        {
+            let mut signals = marketplace.signals.lock().await;
+            signals.insert("live".to_string(), make_signal("live", now + 3600));
+            signals.insert("lapsed".to_string(), make_signal("lapsed", now - 1));
+            signals.insert("filled".to_string(), make_signal("filled", now + 3600));
+        }
+        for id in ["live", "lapsed", "filled"] {
+            assert!(marketplace.try_mark_executing(id).await.unwrap());
+        }
+        marketplace.update_signal_status("filled", SignalStatus::Filled).await.unwrap();
+        
+        // Nothing is stuck until the timeout has passed
+        assert!(marketplace.stuck_executing_signals(DEFAULT_EXECUTING_TIMEOUT_SECS).await.is_empty());
+        let mut stuck = marketplace.stuck_executing_signals(0).await;
+        stuck.sort();
+        assert_eq!(stuck, vec!["lapsed", "live"]);
+        
+        assert_eq!(marketplace.recover_stuck_signals(&stuck).await, 2);
+        assert_eq!(marketplace.get_signal("live").await.unwrap().status, SignalStatus::Active);
+        assert_eq!(marketplace.get_signal("lapsed").await.unwrap().status, SignalStatus::Expired);
+        assert_eq!(marketplace.get_signal("filled").await.unwrap().status, SignalStatus::Filled);
+        
+        // A second sweep finds nothing, and the stats keep the running total
+        assert!(marketplace.stuck_executing_signals(0).await.is_empty());
+        assert_eq!(marketplace.recover_stuck_signals(&stuck).await, 0);
+        assert_eq!(marketplace.get_marketplace_stats().await["recovered_signals"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_execution_locks_listed_and_released() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string());
+        let now = Utc::now().timestamp();
+        let make_signal = |id: &str| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.9,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry: now + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        {
+            let mut signals = marketplace.signals.lock().await;
+            signals.insert("stuck".to_string(), make_signal("stuck"));
+            signals.insert("idle".to_string(), make_signal("idle"));
+        }
+        assert!(marketplace.try_mark_executing("stuck").await.unwrap());
+        marketplace.executing_since.lock().await.insert("stuck".to_string(), now - 600);
+        
+        let locks = marketplace.execution_locks().await;
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].signal_id, "stuck");
+        assert!(locks[0].locked_for_secs.unwrap() >= 600);
+        
+        // Only a held lock can be released, and releasing it makes the signal executable again
+        assert!(marketplace.release_execution_lock("idle").await.is_err());
+        assert!(marketplace.release_execution_lock("missing").await.is_err());
+        assert_eq!(marketplace.release_execution_lock("stuck").await, Ok(SignalStatus::Active));
+        assert!(marketplace.execution_locks().await.is_empty());
+        assert!(marketplace.try_mark_executing("stuck").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_publish_rejects_poor_risk_reward() {
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string())
+            .with_min_risk_reward(1.5);
+        let make_signal = |id: &str, action: SignalAction, target_price: f64, stop_loss: f64| TradingSignalData {
+            id: id.to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action,
+            entry_price: 100.0,
+            target_price,
+            stop_loss,
+            confidence: 0.9,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: Utc::now().timestamp(),
+            expiry: Utc::now().timestamp() + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        
+        // 10 up for 5 down (2.0) and a mirrored sell both clear 1.5
+        assert_eq!(make_signal("buy", SignalAction::Buy, 110.0, 95.0).risk_reward(), 2.0);
+        marketplace.publish_signal(make_signal("buy", SignalAction::Buy, 110.0, 95.0)).await.unwrap();
+        marketplace.publish_signal(make_signal("sell", SignalAction::Sell, 90.0, 105.0)).await.unwrap();
+        
+        // 10 up for 10 down, and a buy whose "target" is below entry
+        let err = marketplace.publish_signal(make_signal("even", SignalAction::Buy, 110.0, 90.0)).await.unwrap_err();
+        assert!(err.contains("1.00"));
+        assert!(marketplace.publish_signal(make_signal("inverted", SignalAction::Buy, 90.0, 95.0)).await.is_err());
+        assert!(marketplace.get_signal("even").await.is_none());
+        
+        let stats = marketplace.get_marketplace_stats().await;
+        assert_eq!(stats["total_signals"], "2");
+        assert_eq!(stats["rejected_risk_reward"], "2");
+        
+        // 0 turns the floor off
+        assert!(validate_risk_reward(&make_signal("even", SignalAction::Buy, 110.0, 90.0), 0.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_signal_history_search() {
+        let database = Arc::new(Mutex::new(crate::database::Database::new_in_memory()));
+        let marketplace = SignalMarketplace::new("https://api.mainnet-beta.solana.com".to_string())
+            .with_database(database.clone());
+        let now = Utc::now().timestamp();
+        let make_signal = |id: &str, provider: &str, timestamp: i64| TradingSignalData {
+            id: id.to_string(),
+            provider: provider.to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.9,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp,
+            expiry: now + 3600,
+            price: 10.0,
+            status: SignalStatus::Active,
+            ..Default::default()
+        };
+        marketplace.publish_signal(make_signal("old", "memecoin_monitor", now - 8 * 86400)).await.unwrap();
+        marketplace.publish_signal(make_signal("last_week", "memecoin_monitor", now - 3 * 86400)).await.unwrap();
+        marketplace.publish_signal(make_signal("other", "oracle_monitor", now - 3 * 86400)).await.unwrap();
+        assert!(marketplace.try_mark_executing("last_week").await.unwrap());
+        marketplace.update_signal_status("last_week", SignalStatus::Filled).await.unwrap();
+        
+        // Gone from the marketplace, still in the history with its final status
+        marketplace.signals.lock().await.remove("last_week");
+        let filter = SignalFilter {
+            from: Some(now - 7 * 86400),
+            provider: Some("memecoin_monitor".to_string()),
+            ..SignalFilter::default()
+        };
+        let found = marketplace.get_signals(&filter).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "last_week");
+        assert_eq!(found[0].status, SignalStatus::Filled);
+        
+        // Changes reach the database together on the next flush
+        assert!(database.lock().await.query_signals(&filter).is_empty());
+        assert_eq!(marketplace.flush_signals().await, 3);
+        assert_eq!(database.lock().await.query_signals(&filter)[0].status, SignalStatus::Filled);
+        assert_eq!(marketplace.flush_signals().await, 0);
+        
+        let active = SignalFilter { statuses: vec![SignalStatus::parse("ACTIVE").unwrap()], ..SignalFilter::default() };
+        let ids: Vec<String> = marketplace.get_signals(&active).await.into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["other", "old"]); // Newest first
+        assert!(SignalStatus::parse("pending").is_none());
+    }
 }
@@ -0,0 +1,159 @@
+//! AI Signal Review
+//! Optional DeepSeek second opinion on buy signals before the auto-executor commits funds
+//! Verdicts are cached per signal for a short while so a signal retried on the next tick isn't re-billed
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::deepseek_ai::{DeepSeekClient, SignalReview};
+use crate::signal_platform::TradingSignalData;
+
+pub const DEFAULT_REVIEW_CACHE_SECS: i64 = 300;
+pub const DEFAULT_REVIEW_TIMEOUT_SECS: u64 = 15;
+
+/// AI_SIGNAL_REVIEW / AI_REVIEW_FAIL_OPEN / AI_REVIEW_CACHE_SECS / AI_REVIEW_TIMEOUT_SECS
+#[derive(Debug, Clone, Copy)]
+pub struct SignalReviewConfig {
+    pub enabled: bool,
+    /// Execute anyway when the AI call errors or times out (false = skip the signal)
+    pub fail_open: bool,
+    pub cache_secs: i64,
+    pub timeout_secs: u64,
+}
+
+impl SignalReviewConfig {
+    pub fn from_env() -> Self {
+        let flag = |name: &str, default: bool| std::env::var(name)
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(default);
+        Self {
+            enabled: flag("AI_SIGNAL_REVIEW", false),
+            fail_open: flag("AI_REVIEW_FAIL_OPEN", true),
+            cache_secs: std::env::var("AI_REVIEW_CACHE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_REVIEW_CACHE_SECS),
+            timeout_secs: std::env::var("AI_REVIEW_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_REVIEW_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// Shared by the auto-executor's per-symbol tasks
+pub struct SignalReviewer {
+    client: Arc<Mutex<DeepSeekClient>>,
+    config: SignalReviewConfig,
+    /// signal id -> (reviewed at, verdict)
+    cache: Mutex<HashMap<String, (i64, SignalReview)>>,
+}
+
+impl SignalReviewer {
+    pub fn new(client: Arc<Mutex<DeepSeekClient>>, config: SignalReviewConfig) -> Self {
+        Self {
+            client,
+            config,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Review `signal` and decide whether it may execute
+    /// Ok(Some(review)) when approved, Ok(None) when the AI was unavailable and the gate fails open,
+    /// Err(reason) when the signal should be skipped
+    pub async fn gate(&self, signal: &TradingSignalData, market_context: &str, threshold: f64) -> Result<Option<SignalReview>, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cached = {
+            let mut cache = self.cache.lock().await;
+            cache.retain(|_, (reviewed_at, _)| now - *reviewed_at < self.config.cache_secs);
+            cache.get(&signal.id).map(|(_, review)| review.clone())
+        };
+
+        let review = match cached {
+            Some(review) => review,
+            None => {
+                let timeout = tokio::time::Duration::from_secs(self.config.timeout_secs);
+                let result = tokio::time::timeout(timeout, async {
+                    self.client.lock().await.review_signal(signal, market_context).await
+                        .map_err(|e| e.to_string())
+                }).await;
+                let error = match result {
+                    Ok(Ok(review)) => {
+                        self.cache.lock().await.insert(signal.id.clone(), (now, review.clone()));
+                        return Self::verdict(review, threshold).map(Some);
+                    }
+                    Ok(Err(e)) => e,
+                    Err(_) => format!("no response within {}s", self.config.timeout_secs),
+                };
+                return if self.config.fail_open {
+                    log::warn!("🧠 AI review of {} unavailable ({}) - executing on provider confidence", signal.id, error);
+                    Ok(None)
+                } else {
+                    Err(format!("AI review unavailable: {}", error))
+                };
+            }
+        };
+        Self::verdict(review, threshold).map(Some)
+    }
+
+    /// A rejection, or an approval whose adjusted confidence no longer clears the threshold, skips the signal
+    fn verdict(review: SignalReview, threshold: f64) -> Result<SignalReview, String> {
+        if !review.approve {
+            return Err(format!("AI review rejected: {}", review.reasoning));
+        }
+        if review.adjusted_confidence < threshold {
+            return Err(format!("AI review lowered confidence to {:.1}% (threshold {:.1}%): {}",
+                              review.adjusted_confidence * 100.0, threshold * 100.0, review.reasoning));
+        }
+        Ok(review)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(id: &str) -> TradingSignalData {
+        TradingSignalData {
+            id: id.to_string(),
+            provider: "memecoin_monitor".to_string(),
+            symbol: "BONK".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 0.00002,
+            target_price: 0.000022,
+            stop_loss: 0.000019,
+            confidence: 0.85,
+            timeframe: "15m".to_string(),
+            data_sources: vec!["DexScreener".to_string()],
+            analysis: "Volume spike".to_string(),
+            timestamp: 0,
+            expiry: i64::MAX,
+            price: 1.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_gate_and_failure_mode() {
+        // Unreachable endpoint - every uncached review errors
+        let client = Arc::new(Mutex::new(DeepSeekClient::new(String::new()).with_api_url("http://127.0.0.1:9")));
+        let config = SignalReviewConfig { enabled: true, fail_open: true, cache_secs: 300, timeout_secs: 2 };
+        let reviewer = SignalReviewer::new(client.clone(), config);
+        assert!(reviewer.gate(&signal("uncached"), "", 0.75).await.unwrap().is_none());
+        let closed = SignalReviewer::new(client, SignalReviewConfig { fail_open: false, ..config });
+        assert!(closed.gate(&signal("uncached"), "", 0.75).await.unwrap_err().contains("unavailable"));
+
+        // Cached verdicts are reused without calling the API
+        let now = chrono::Utc::now().timestamp();
+        let approve = |adjusted_confidence| SignalReview { approve: true, adjusted_confidence, reasoning: "ok".to_string() };
+        reviewer.cache.lock().await.insert("approved".to_string(), (now, approve(0.8)));
+        reviewer.cache.lock().await.insert("downgraded".to_string(), (now, approve(0.6)));
+        reviewer.cache.lock().await.insert("rejected".to_string(), (now, SignalReview { approve: false, ..approve(0.9) }));
+        assert_eq!(reviewer.gate(&signal("approved"), "", 0.75).await.unwrap().unwrap().adjusted_confidence, 0.8);
+        assert!(reviewer.gate(&signal("downgraded"), "", 0.75).await.unwrap_err().contains("lowered"));
+        assert!(reviewer.gate(&signal("rejected"), "", 0.75).await.unwrap_err().contains("rejected"));
+    }
+}
@@ -8,14 +8,55 @@ use crate::wallet::Wallet;
 use crate::pda::TreasuryPDA;
 use crate::rpc_client::SolanaRpcClient;
 use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
     pubkey::Pubkey,
     system_instruction,
-    system_program,
     transaction::Transaction,
     native_token::LAMPORTS_PER_SOL,
-    instruction::{Instruction, AccountMeta},
 };
 
+/// Base fee for one signature - used when the cluster can't quote a message's fee
+const DEFAULT_SIGNATURE_FEE_LAMPORTS: u64 = 5000;
+
+fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+/// A live swap that didn't come back with a confirmed submission
+/// Once the swap is signed and handed to the network, a transport error or timeout says nothing about
+/// whether it lands - `signature` is set from then on so callers check the chain instead of resending
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapError {
+    pub signature: Option<String>,
+    pub message: String,
+}
+
+impl SwapError {
+    /// Sent (or possibly sent) - the outcome is up to the chain
+    pub fn unknown(signature: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { signature: Some(signature.into()), message: message.into() }
+    }
+}
+
+impl From<String> for SwapError {
+    fn from(message: String) -> Self {
+        Self { signature: None, message }
+    }
+}
+
+impl From<&str> for SwapError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl std::fmt::Display for SwapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SolanaClient {
     pub connected: bool,
@@ -26,6 +67,76 @@ pub struct SolanaClient {
     pub treasury_bump: Option<u8>, // Store PDA bump seed for withdrawals
     pub rpc_url: Option<String>,
     pub trading_budget: f64,
+    pub wallet_name: String, // "default" for WALLET_PRIVATE_KEY, else a strategy wallet
+    wallet_key_env: String,  // Env var the signing key is loaded from
+    pub base_currency: crate::dex_aggregator::BaseCurrency, // What trading_budget is held in - trades spend and settle into it
+}
+
+/// One wallet's balances, for /wallet/status
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WalletBalance {
+    pub name: String,
+    pub wallet_address: Option<String>,
+    pub treasury_address: Option<String>,
+    pub wallet_balance: f64,
+    pub trading_budget: f64,
+    pub transaction_count: u64,
+    pub providers: Vec<String>, // Providers assigned to this wallet (the default wallet lists only explicit ones)
+    pub positions: HashMap<String, f64>, // Open positions held in this wallet, by symbol (filled in by the engine)
+    pub realized_pnl: f64, // Realized P&L of this wallet's sells (filled in by the engine)
+}
+
+/// Wallet and PDA balances a deposit or withdrawal is checked against
+struct PdaTransferContext {
+    rpc_client: SolanaRpcClient,
+    wallet_pubkey: Pubkey,
+    treasury_pubkey: Pubkey,
+    wallet_lamports: u64,
+    pda_lamports: u64,
+    pda_exists: bool,
+    min_rent: u64,
+}
+
+/// What a PDA deposit or withdrawal would do, from ?dry_run=true - nothing is signed or sent
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PdaTransferPreview {
+    pub action: String, // "deposit" or "withdraw"
+    pub amount_sol: f64,
+    /// SOL that would move - a deposit that creates the PDA is topped up to the rent-exempt minimum
+    pub transfer_sol: f64,
+    pub estimated_fee_sol: f64,
+    pub wallet_balance_before: f64,
+    pub wallet_balance_after: f64,
+    pub pda_balance_before: f64,
+    pub pda_balance_after: f64,
+    pub rent_exempt_minimum_sol: f64,
+    pub creates_pda_account: bool,
+    /// The PDA would stay at or above the rent-exempt minimum
+    pub rent_exempt_after: bool,
+    /// Why the transfer would be refused (None = it would be submitted)
+    pub blocked_reason: Option<String>,
+}
+
+impl PdaTransferPreview {
+    #[allow(clippy::too_many_arguments)]
+    fn new(action: &str, amount_sol: f64, lamports: u64, fee: u64, context: &PdaTransferContext,
+           wallet_after: i128, pda_after: i128, blocked_reason: Option<String>) -> Self {
+        let to_sol = |lamports: i128| lamports as f64 / LAMPORTS_PER_SOL as f64;
+        Self {
+            action: action.to_string(),
+            amount_sol,
+            transfer_sol: lamports_to_sol(lamports),
+            estimated_fee_sol: lamports_to_sol(fee),
+            wallet_balance_before: lamports_to_sol(context.wallet_lamports),
+            wallet_balance_after: to_sol(wallet_after),
+            pda_balance_before: lamports_to_sol(context.pda_lamports),
+            pda_balance_after: to_sol(pda_after),
+            rent_exempt_minimum_sol: lamports_to_sol(context.min_rent),
+            creates_pda_account: !context.pda_exists,
+            rent_exempt_after: pda_after >= context.min_rent as i128,
+            blocked_reason,
+        }
+    }
 }
 
 impl SolanaClient {
@@ -40,15 +151,29 @@ impl SolanaClient {
             treasury_bump: None,
             rpc_url: None,
             trading_budget: 10000.0,
+            wallet_name: crate::wallet::DEFAULT_WALLET_NAME.to_string(),
+            wallet_key_env: crate::wallet::key_env_for(crate::wallet::DEFAULT_WALLET_NAME),
+            base_currency: crate::dex_aggregator::BaseCurrency::from_env(),
         }
     }
 
     /// Create a new SolanaClient with wallet and RPC integration
     pub async fn new_with_integration(rpc_url: String) -> Self {
-        log::info!("🔐 Initializing Solana integration with wallet and PDA...");
-        
         // Load or create wallet
-        let wallet = Wallet::from_env_or_new("WALLET_PRIVATE_KEY");
+        let key_env = crate::wallet::key_env_for(crate::wallet::DEFAULT_WALLET_NAME);
+        let wallet = Wallet::from_env_or_new(&key_env);
+        Self::connect(rpc_url, crate::wallet::DEFAULT_WALLET_NAME, key_env, wallet).await
+    }
+
+    /// Client for a named strategy wallet with its own PDA treasury (key from WALLET_PRIVATE_KEY_<NAME>)
+    pub async fn new_for_wallet(rpc_url: String, wallet_name: &str) -> Result<Self, String> {
+        let key_env = crate::wallet::key_env_for(wallet_name);
+        let wallet = Wallet::from_env(&key_env)?;
+        Ok(Self::connect(rpc_url, wallet_name, key_env, wallet).await)
+    }
+
+    async fn connect(rpc_url: String, wallet_name: &str, wallet_key_env: String, wallet: Wallet) -> Self {
+        log::info!("🔐 Initializing Solana integration with wallet and PDA ({} wallet)...", wallet_name);
         let wallet_pubkey = wallet.pubkey();
         
         // Derive treasury PDA for agent trading
@@ -77,6 +202,23 @@ impl SolanaClient {
         // Create RPC client
         let rpc_client = SolanaRpcClient::new(rpc_url.clone());
         
+        // WITHDRAW_PROGRAM_ID moves the treasury off its System Program address - flag funds left behind there
+        if crate::pda::withdraw_program_id().is_some() {
+            let legacy_address = TreasuryPDA::legacy_address(&wallet_pubkey);
+            if treasury_address.as_deref() != Some(legacy_address.to_string().as_str()) {
+                match rpc_client.get_balance(&legacy_address).await {
+                    Ok(balance) if balance > 0.0 => {
+                        log::warn!("⚠️ WITHDRAW_PROGRAM_ID moved the treasury PDA: {} SOL is still at the previous address {}",
+                                  balance, legacy_address);
+                        log::warn!("   That balance is not tracked or traded - deposits now go to {}",
+                                  treasury_address.as_deref().unwrap_or("unknown"));
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("⚠️ Could not check the previous treasury PDA {}: {}", legacy_address, e),
+                }
+            }
+        }
+
         // Initialize PDA account on-chain (will be created automatically on first deposit if needed)
         // This creates a real on-chain account that can hold SOL
         if let (Some(ref treasury_addr), Some(bump)) = (treasury_address.as_ref(), treasury_bump) {
@@ -130,12 +272,14 @@ impl SolanaClient {
         };
 
         // REAL TRADING: Get trading budget ONLY from PDA balance (no simulation)
-        // Agents will use real SOL from the PDA treasury
+        // Agents will use real SOL (or USDC with BASE_CURRENCY=usdc) from the PDA treasury
+        let base_currency = crate::dex_aggregator::BaseCurrency::from_env();
         let trading_budget = if let Some(ref treasury_addr) = treasury_address {
-            match Self::get_pda_balance_internal(&rpc_client, treasury_addr).await {
+            match Self::get_trading_balance_internal(&rpc_client, treasury_addr, base_currency).await {
                 Ok(pda_balance) => {
                     if pda_balance > 0.0 {
-                        log::info!("💰 REAL PDA treasury balance: {:.6} SOL - Agents can trade with real funds", pda_balance);
+                        log::info!("💰 REAL PDA treasury balance: {:.6} {} - Agents can trade with real funds",
+                                  pda_balance, base_currency.symbol());
                     } else {
                         // PDA might not exist yet (will be created on first deposit)
                         log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -180,10 +324,10 @@ impl SolanaClient {
         
         // Ignore TRADING_BUDGET env var - always use real PDA balance
         if trading_budget == 0.0 {
-            log::info!("💵 Trading budget: 0.0 SOL - Agents will wait for funds");
-            log::info!("   💰 Deposit real SOL to PDA to enable trading: POST /pda/deposit");
+            log::info!("💵 Trading budget: 0.0 {} - Agents will wait for funds", base_currency.symbol());
+            log::info!("   💰 Deposit real {} to PDA to enable trading: POST /pda/deposit", base_currency.symbol());
         } else {
-            log::info!("✅ Trading enabled with REAL SOL: {:.6} SOL available in PDA", trading_budget);
+            log::info!("✅ Trading enabled with REAL {}: {:.6} available in PDA", base_currency.symbol(), trading_budget);
         }
 
         Self {
@@ -195,17 +339,46 @@ impl SolanaClient {
             treasury_bump,
             rpc_url: Some(rpc_url),
             trading_budget,
+            wallet_name: wallet_name.to_string(),
+            wallet_key_env,
+            base_currency,
         }
     }
     
-    pub async fn execute_trade(&mut self, symbol: &str, size: f64, is_buy: bool, price: f64, fee_lamports: Option<u64>) -> Result<String, String> {
-        // SAFETY: Check for dry-run mode
+    /// Keypair that signs for this client's wallet (and is the authority of its PDA)
+    /// Never generated on the fly - a throwaway key would sign for a wallet that holds nothing
+    fn signing_wallet(&self) -> Result<Wallet, String> {
+        Wallet::from_env(&self.wallet_key_env)
+            .map_err(|e| format!("No signing key for wallet {}: {} - refusing to send", self.wallet_name, e))
+    }
+    
+    pub fn wallet_summary(&self, providers: Vec<String>) -> WalletBalance {
+        WalletBalance {
+            name: self.wallet_name.clone(),
+            wallet_address: self.wallet_address.clone(),
+            treasury_address: self.treasury_address.clone(),
+            wallet_balance: self.wallet_balance,
+            trading_budget: self.trading_budget,
+            transaction_count: self.transaction_count,
+            providers,
+            positions: HashMap::new(),
+            realized_pnl: 0.0,
+        }
+    }
+    
+    /// DRY_RUN_MODE (defaults to true) - simulate instead of sending transactions
+    /// Read when a trade executes; a session replay always paper-trades whatever DRY_RUN_MODE says
+    pub fn dry_run_mode() -> bool {
         let dry_run = std::env::var("DRY_RUN_MODE")
             .unwrap_or_else(|_| "true".to_string())
             .parse::<bool>()
             .unwrap_or(true);
-        
-        if dry_run {
+        dry_run || crate::historical_data::SessionMode::from_env().is_replay()
+    }
+    
+    pub async fn execute_trade(&mut self, symbol: &str, size: f64, is_buy: bool, price: f64, fee_lamports: Option<u64>) -> Result<String, String> {
+        // SAFETY: Check for dry-run mode
+        if Self::dry_run_mode() {
             // DRY-RUN MODE: Simulate trade without executing
             let action = if is_buy { "BUY" } else { "SELL" };
             self.transaction_count += 1;
@@ -224,58 +397,165 @@ impl SolanaClient {
             return Ok(trade_id);
         }
         
-        // REAL TRADING: Always sync from PDA balance before executing
-        self.sync_trading_budget_from_pda().await;
+        // LIVE: There is no route to swap on here - live swaps go through execute_swap with a Jupiter client
+        // Failing closed: a fake id would be booked as a fill that never happened on-chain
+        Err(format!(
+            "Live {} of {} {} needs a Jupiter swap route - refusing to report a trade that was never sent",
+            if is_buy { "buy" } else { "sell" }, size, symbol
+        ))
+    }
+    
+    /// LIVE SWAP: Build the trade's swap on `route` (the aggregator that won the quote), sign it with the
+    /// wallet (the PDA authority) and send it
+    /// The swap reverts on-chain if it would receive less than the quote minus `slippage_bps`.
+    /// Returns the transaction signature - the trade id the dedupe, audit trail and confirmation poller track
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_swap(
+        &mut self,
+        route: &dyn crate::dex_aggregator::DexAggregator,
+        symbol: &str,
+        size: f64,
+        is_buy: bool,
+        price: f64,
+        slippage_bps: u16,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<String, SwapError> {
+        let rpc_url = self.rpc_url.clone().ok_or("No RPC URL configured. Cannot execute real trades.")?;
+        let (swap_tx, _) = self.signed_swap_transaction(
+            route, symbol, size, is_buy, price, slippage_bps, 0, compute_unit_price, compute_unit_limit,
+        ).await?;
+        let signature = swap_tx.signatures[0];
+        self.transaction_count += 1;
+        // The RPC node may have forwarded the swap before the error - it can still land
+        SolanaRpcClient::new(rpc_url).send_transaction(&swap_tx).await
+            .map_err(|e| SwapError::unknown(signature.to_string(), e))?;
         
-        // Verify PDA is initialized
+        log::info!("🔧 REAL SWAP SENT via {}: {} {} {} at ${:.8} | Signature: {}",
+                   route.name(), if is_buy { "BUY" } else { "SELL" }, size, symbol, price, signature);
+        Ok(signature.to_string())
+    }
+    
+    /// Swap for the trade built by `route` and signed by the wallet, after checking the PDA budget covers it plus
+    /// `tip_lamports` (paid in SOL, so only counted against a SOL budget). Returns the swap and its signer
+    #[allow(clippy::too_many_arguments)]
+    async fn signed_swap_transaction(
+        &mut self,
+        route: &dyn crate::dex_aggregator::DexAggregator,
+        symbol: &str,
+        size: f64,
+        is_buy: bool,
+        price: f64,
+        slippage_bps: u16,
+        tip_lamports: u64,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<(Transaction, Wallet), String> {
+        self.sync_trading_budget_from_pda().await;
         if self.treasury_address.is_none() {
             return Err("PDA treasury not initialized. Cannot execute real trades.".to_string());
         }
-        
-        // Check if we have sufficient REAL SOL in PDA
         let trade_cost = size * price;
-        if trade_cost > self.trading_budget {
+        let tip = match self.base_currency {
+            crate::dex_aggregator::BaseCurrency::Sol => tip_lamports as f64 / LAMPORTS_PER_SOL as f64,
+            crate::dex_aggregator::BaseCurrency::Usdc => 0.0,
+        };
+        if trade_cost + tip > self.trading_budget {
+            let base = self.base_currency.symbol();
             return Err(format!(
-                "Insufficient REAL SOL in PDA. Required: {:.6} SOL, Available: {:.6} SOL",
-                trade_cost, self.trading_budget
+                "Insufficient REAL {} in PDA. Required: {:.6} {} (incl. {:.6} SOL tip), Available: {:.6} {}",
+                base, trade_cost + tip, base, tip, self.trading_budget, base
             ));
         }
         
-        if self.trading_budget == 0.0 {
-            return Err("PDA treasury has no funds. Deposit real SOL first: POST /pda/deposit".to_string());
-        }
-        
-        self.transaction_count += 1;
-        
-        // In production, this would execute a real Solana transaction
-        // For now, we validate and prepare for real execution
-        let action = if is_buy { "BUY" } else { "SELL" };
-        let trade_id = format!("{}_{}_{}", action, symbol, self.transaction_count);
-        
-        // FEE OPTIMIZATION: Use optimal fee estimate from TradingEngine's fee_optimizer
-        let estimated_fee_lamports = fee_lamports.unwrap_or(5000u64); // Default: 5000 lamports if not provided
-        let estimated_fee_sol = estimated_fee_lamports as f64 / LAMPORTS_PER_SOL as f64;
-        
-        // Update trading budget (will be synced from PDA after real transaction)
-        // For now, estimate the new balance
-        if is_buy {
-            self.trading_budget -= trade_cost + estimated_fee_sol;
-        } else {
-            self.trading_budget += trade_cost - estimated_fee_sol;
-        }
+        let (input_mint, output_mint, amount) = crate::dex_aggregator::trade_amounts(symbol, size, price, is_buy, self.base_currency)?;
+        let wallet = self.signing_wallet()?;
+        let mut swap_tx = route.swap_transaction(
+            &wallet.pubkey().to_string(), &input_mint, &output_mint, amount, slippage_bps, compute_unit_price, compute_unit_limit,
+        ).await?;
+        let recent_blockhash = swap_tx.message.recent_blockhash;
+        swap_tx.try_sign(&[wallet.keypair()], recent_blockhash)
+            .map_err(|e| format!("Failed to sign swap transaction: {}", e))?;
+        Ok((swap_tx, wallet))
+    }
+    
+    /// MEV PROTECTION: Signed swap (built by `route`) followed by a Jito tip transfer, ready to submit as one atomic bundle
+    /// Both are signed by the wallet (the PDA authority) against the swap's blockhash, and the swap
+    /// reverts on-chain if it would receive less than the quote minus `slippage_bps`.
+    /// Returns the swap signature - the trade id once the bundle lands - and the bundle
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build_jito_swap_bundle(
+        &mut self,
+        route: &dyn crate::dex_aggregator::DexAggregator,
+        jito: &crate::jito_bam::JitoBamClient,
+        symbol: &str,
+        size: f64,
+        is_buy: bool,
+        price: f64,
+        slippage_bps: u16,
+        tip_lamports: u64,
+        compute_unit_price: Option<u64>,
+        compute_unit_limit: Option<u32>,
+    ) -> Result<(String, Vec<Transaction>), String> {
+        let tip_account = *jito.get_random_tip_account()
+            .ok_or("No Jito tip accounts configured")?;
         
-        log::info!("🔧 REAL TRADE EXECUTED: {} {} {} at ${:.8} | Fee: {:.6} SOL | PDA Balance: {:.6} SOL", 
-                   action, size, symbol, price, estimated_fee_sol, self.trading_budget);
-        log::info!("   Trade ID: {} | Using REAL SOL from PDA treasury", trade_id);
+        let (swap_tx, wallet) = self.signed_swap_transaction(
+            route, symbol, size, is_buy, price, slippage_bps, tip_lamports, compute_unit_price, compute_unit_limit,
+        ).await?;
+        let recent_blockhash = swap_tx.message.recent_blockhash;
+        let signature = swap_tx.signatures[0].to_string();
         
-        // TODO: In production, execute real Solana transaction here
-        // This would:
-        // 1. Create transaction using PDA as signer (via invoke_signed in a program)
-        // 2. Execute swap via Jupiter or other DEX
-        // 3. Update PDA balance from on-chain state
-        // 4. Sync trading_budget from real PDA balance
+        let bundle = crate::jito_bam::TradingBundleBuilder::new(&format!("{} {} {}", if is_buy { "BUY" } else { "SELL" }, size, symbol))
+            .add_transaction(swap_tx)
+            .add_tip(wallet.keypair(), &tip_account, tip_lamports, recent_blockhash)
+            .build();
+        self.transaction_count += 1;
         
-        Ok(trade_id)
+        Ok((signature, bundle))
+    }
+    
+    /// Look up a previously submitted swap on-chain (see RpcClient::get_signature_result)
+    pub async fn signature_status(&self, signature: &str) -> Result<Option<bool>, String> {
+        let signature = solana_sdk::signature::Signature::from_str(signature)
+            .map_err(|_| format!("'{}' is not a transaction signature", signature))?;
+        let rpc_url = self.rpc_url.as_ref().ok_or("No RPC URL configured")?;
+        SolanaRpcClient::new(rpc_url.clone()).get_signature_result(&signature).await
+    }
+    
+    /// Slot and commitment of a submitted swap (see RpcClient::get_signature_confirmation)
+    pub async fn signature_confirmation(&self, signature: &str) -> Result<Option<crate::rpc_client::SignatureConfirmation>, String> {
+        let signature = solana_sdk::signature::Signature::from_str(signature)
+            .map_err(|_| format!("'{}' is not a transaction signature", signature))?;
+        let rpc_url = self.rpc_url.as_ref().ok_or("No RPC URL configured")?;
+        SolanaRpcClient::new(rpc_url.clone()).get_signature_confirmation(&signature).await
+    }
+    
+    /// What a confirmed swap of `symbol` actually moved for this wallet (see RpcClient::get_swap_fill)
+    pub async fn swap_fill(&self, signature: &str, symbol: &str) -> Result<Option<crate::rpc_client::SwapFill>, String> {
+        let signature = solana_sdk::signature::Signature::from_str(signature)
+            .map_err(|_| format!("'{}' is not a transaction signature", signature))?;
+        let rpc_url = self.rpc_url.as_ref().ok_or("No RPC URL configured")?;
+        let owner = self.wallet_address.as_ref().ok_or("No wallet address configured")?;
+        let (token_mint, _) = crate::dex_aggregator::resolve_token(symbol)
+            .ok_or_else(|| format!("No mint known for {}", symbol))?;
+        let (quote_mint, _) = crate::dex_aggregator::quote_token(&token_mint, self.base_currency);
+        SolanaRpcClient::new(rpc_url.clone()).get_swap_fill(&signature, owner, &token_mint, quote_mint).await
+    }
+    
+    /// RPC URL and wallet to read the token balances of - swaps settle there. Copy them out and read with
+    /// `token_balances` so the client isn't locked across the RPC call
+    pub fn token_balance_source(&self) -> Result<(String, Pubkey), String> {
+        let rpc_url = self.rpc_url.as_ref().ok_or("No RPC URL configured")?;
+        let owner = self.wallet_address.as_ref().ok_or("No wallet address configured")?;
+        let owner = Pubkey::from_str(owner)
+            .map_err(|e| format!("Invalid wallet address: {}", e))?;
+        Ok((rpc_url.clone(), owner))
+    }
+    
+    /// Token balances by mint held by `owner` (see RpcClient::get_token_balances)
+    pub async fn token_balances(rpc_url: String, owner: &Pubkey) -> Result<std::collections::HashMap<String, f64>, String> {
+        SolanaRpcClient::new(rpc_url).get_token_balances(owner).await
     }
     
     /// Sync trading budget from REAL PDA balance on-chain
@@ -285,12 +565,12 @@ impl SolanaClient {
         if let Some(ref treasury_addr) = self.treasury_address {
             if let Some(ref rpc_url) = self.rpc_url {
                 let rpc_client = SolanaRpcClient::new(rpc_url.clone());
-                match Self::get_pda_balance_internal(&rpc_client, treasury_addr).await {
+                match Self::get_trading_balance_internal(&rpc_client, treasury_addr, self.base_currency).await {
                     Ok(pda_balance) => {
                         // Always update to real balance (no threshold check for real trading)
                         if (pda_balance - self.trading_budget).abs() > 0.000001 {
-                            log::debug!("🔄 Syncing REAL PDA balance: {:.6} SOL (was {:.6} SOL)", 
-                                       pda_balance, self.trading_budget);
+                            log::debug!("🔄 Syncing REAL PDA balance: {:.6} {} (was {:.6})", 
+                                       pda_balance, self.base_currency.symbol(), self.trading_budget);
                             self.trading_budget = pda_balance;
                         }
                     }
@@ -358,114 +638,40 @@ impl SolanaClient {
             return Err("Deposit amount must be positive".to_string());
         }
 
-        let (wallet_pubkey, treasury_pubkey, rpc_url) = {
-            let wallet_addr = self.wallet_address.as_ref()
-                .ok_or("Wallet address not configured")?;
-            let treasury_addr = self.treasury_address.as_ref()
-                .ok_or("Treasury PDA not initialized")?;
-            let rpc = self.rpc_url.as_ref()
-                .ok_or("RPC URL not configured")?;
-            
-            (wallet_addr.clone(), treasury_addr.clone(), rpc.clone())
-        };
-
-        // Parse pubkeys
-        let from_pubkey = Pubkey::from_str(&wallet_pubkey)
-            .map_err(|e| format!("Invalid wallet address: {}", e))?;
-        let to_pubkey = Pubkey::from_str(&treasury_pubkey)
-            .map_err(|e| format!("Invalid treasury address: {}", e))?;
+        let context = self.pda_transfer_context().await?;
+        let (instruction, lamports) = Self::deposit_instruction(&context, amount_sol)?;
+        let rpc_client = &context.rpc_client;
 
         // Load wallet
-        let wallet = Wallet::from_env_or_new("WALLET_PRIVATE_KEY");
-        
-        // Check wallet balance
-        let rpc_client = SolanaRpcClient::new(rpc_url.clone());
-        let wallet_balance = rpc_client.get_balance(&from_pubkey).await
-            .map_err(|e| format!("Failed to check wallet balance: {}", e))?;
-        
-        if wallet_balance < amount_sol {
-            return Err(format!("Insufficient wallet balance. Balance: {:.6} SOL, Requested: {:.6} SOL", 
-                             wallet_balance, amount_sol));
-        }
+        let wallet = self.signing_wallet()?;
 
-        // Ensure PDA account exists before deposit (creates it if needed)
-        if !rpc_client.account_exists(&to_pubkey).await {
-            log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        // Ensure PDA account exists before deposit (the first transfer creates it)
+        if !context.pda_exists {
+            log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
             log::info!("🔧 AUTO-INITIALIZING PDA ACCOUNT...");
-            log::info!("📍 PDA Address: {}", treasury_pubkey);
-            // The first transfer to a PDA creates the account automatically
-            // But we need to ensure minimum rent-exempt balance
-            let min_rent = rpc_client.client()
-                .get_minimum_balance_for_rent_exemption(0)
-                .unwrap_or(890880); // Default rent-exempt minimum
-            
-            // If deposit amount is less than rent-exempt, add it
-            let deposit_lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
-            let total_lamports = deposit_lamports.max(min_rent);
-            
-            if total_lamports > deposit_lamports {
+            log::info!("📍 PDA Address: {}", context.treasury_pubkey);
+            if lamports > (amount_sol * LAMPORTS_PER_SOL as f64) as u64 {
                 log::info!("   Adding rent-exempt minimum: {} lamports (total: {} lamports)", 
-                          min_rent, total_lamports);
+                          context.min_rent, lamports);
             }
-            
-            let instruction = system_instruction::transfer(&from_pubkey, &to_pubkey, total_lamports);
-            
-            // Build and sign transaction
-            let rpc_client_sync = rpc_client.client();
-            let latest_blockhash = rpc_client_sync.get_latest_blockhash()
-                .map_err(|e| format!("Failed to get latest blockhash: {}", e))?;
-            
-            let mut transaction = Transaction::new_with_payer(
-                &[instruction],
-                Some(&from_pubkey),
-            );
-            
-            transaction.sign(&[wallet.keypair()], latest_blockhash);
-            
-            // Send and confirm
-            let signature = rpc_client.send_transaction(&transaction).await
-                .map_err(|e| format!("Failed to create PDA account: {}", e))?;
-            
-            log::info!("📤 PDA account creation transaction: {}", signature);
-            rpc_client.confirm_transaction(&signature).await
-                .map_err(|e| format!("PDA creation confirmation failed: {}", e))?;
-            
-            let final_balance = total_lamports as f64 / LAMPORTS_PER_SOL as f64;
-            log::info!("✅ PDA ACCOUNT AUTO-CREATED SUCCESSFULLY!");
-            log::info!("   Balance: {:.6} SOL", final_balance);
-            log::info!("   Transaction: {}", signature);
-            log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-            
-            // Update trading budget
-            self.trading_budget = final_balance;
-            self.wallet_balance = wallet_balance - (total_lamports as f64 / LAMPORTS_PER_SOL as f64);
-            
-            return Ok((signature.to_string(), self.trading_budget));
         }
 
-        // Get PDA balance before deposit
-        let balance_before = rpc_client.get_balance(&to_pubkey).await
-            .map_err(|e| format!("Failed to get PDA balance: {}", e))?;
-
-        // Create transfer instruction for deposit
-        let lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
-        let instruction = system_instruction::transfer(&from_pubkey, &to_pubkey, lamports);
-
         // Build and sign transaction
-        let rpc_client_sync = rpc_client.client();
-        let latest_blockhash = rpc_client_sync.get_latest_blockhash()
-            .map_err(|e| format!("Failed to get latest blockhash: {}", e))?;
-        
+        let latest_blockhash = rpc_client.get_latest_blockhash().await?;
         let mut transaction = Transaction::new_with_payer(
             &[instruction],
-            Some(&from_pubkey),
+            Some(&context.wallet_pubkey),
         );
         
         transaction.sign(&[wallet.keypair()], latest_blockhash);
         
         // Send and confirm transaction
         let signature = rpc_client.send_transaction(&transaction).await
-            .map_err(|e| format!("Failed to send transaction: {}", e))?;
+            .map_err(|e| if context.pda_exists {
+                format!("Failed to send transaction: {}", e)
+            } else {
+                format!("Failed to create PDA account: {}", e)
+            })?;
         
         log::info!("📤 Deposit transaction sent: {}", signature);
         
@@ -474,19 +680,125 @@ impl SolanaClient {
             .map_err(|e| format!("Transaction confirmation failed: {}", e))?;
         
         // Get updated PDA balance
-        let balance_after = rpc_client.get_balance(&to_pubkey).await
-            .unwrap_or(balance_before);
+        let expected_balance = lamports_to_sol(context.pda_lamports + lamports);
+        let balance_after = rpc_client.get_balance(&context.treasury_pubkey).await
+            .unwrap_or(expected_balance);
+        if !context.pda_exists {
+            log::info!("✅ PDA ACCOUNT AUTO-CREATED SUCCESSFULLY!");
+            log::info!("   Balance: {:.6} SOL", balance_after);
+            log::info!("   Transaction: {}", signature);
+            log::info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        }
         
-        // Update trading budget to match PDA balance
-        self.trading_budget = balance_after;
-        self.wallet_balance = wallet_balance - amount_sol;
+        // Update trading budget to match PDA balance (SOL base only - a USDC budget isn't moved by SOL transfers)
+        if self.base_currency == crate::dex_aggregator::BaseCurrency::Sol {
+            self.trading_budget = balance_after;
+        }
+        self.wallet_balance = lamports_to_sol(context.wallet_lamports) - lamports_to_sol(lamports);
         
         log::info!("✅ Deposited {:.6} SOL to PDA treasury. New balance: {:.6} SOL | TX: {}", 
-                 amount_sol, balance_after, signature);
+                 lamports_to_sol(lamports), balance_after, signature);
         
         Ok((signature.to_string(), balance_after))
     }
 
+    /// Wallet and PDA balances a deposit or withdrawal is checked against
+    async fn pda_transfer_context(&self) -> Result<PdaTransferContext, String> {
+        let wallet_addr = self.wallet_address.as_ref()
+            .ok_or("Wallet address not configured")?;
+        let treasury_addr = self.treasury_address.as_ref()
+            .ok_or("Treasury PDA not initialized")?;
+        let rpc_url = self.rpc_url.as_ref()
+            .ok_or("RPC URL not configured")?;
+
+        let wallet_pubkey = Pubkey::from_str(wallet_addr)
+            .map_err(|e| format!("Invalid wallet address: {}", e))?;
+        let treasury_pubkey = Pubkey::from_str(treasury_addr)
+            .map_err(|e| format!("Invalid treasury address: {}", e))?;
+
+        let rpc_client = SolanaRpcClient::new(rpc_url.clone());
+        let wallet_lamports = rpc_client.get_balance_lamports(&wallet_pubkey).await
+            .map_err(|e| format!("Failed to check wallet balance: {}", e))?;
+        let pda_exists = rpc_client.account_exists(&treasury_pubkey).await;
+        let pda_lamports = if pda_exists {
+            rpc_client.get_balance_lamports(&treasury_pubkey).await
+                .map_err(|e| format!("Failed to check PDA balance: {}", e))?
+        } else {
+            0
+        };
+        let min_rent = rpc_client.client()
+            .get_minimum_balance_for_rent_exemption(0)
+            .unwrap_or(890880); // Default rent-exempt minimum
+
+        Ok(PdaTransferContext {
+            rpc_client,
+            wallet_pubkey,
+            treasury_pubkey,
+            wallet_lamports,
+            pda_lamports,
+            pda_exists,
+            min_rent,
+        })
+    }
+
+    /// Lamports a deposit of `amount_sol` sends - the transfer that creates the PDA is topped up to the rent-exempt minimum
+    fn deposit_lamports(context: &PdaTransferContext, amount_sol: f64) -> u64 {
+        let lamports = (amount_sol.max(0.0) * LAMPORTS_PER_SOL as f64) as u64;
+        if context.pda_exists {
+            lamports
+        } else {
+            lamports.max(context.min_rent)
+        }
+    }
+
+    /// Transfer instruction for a deposit, with the lamports it sends
+    fn deposit_instruction(context: &PdaTransferContext, amount_sol: f64) -> Result<(Instruction, u64), String> {
+        if amount_sol <= 0.0 {
+            return Err("Deposit amount must be positive".to_string());
+        }
+        let requested = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
+        if context.wallet_lamports < requested {
+            return Err(format!("Insufficient wallet balance. Balance: {:.6} SOL, Requested: {:.6} SOL", 
+                             lamports_to_sol(context.wallet_lamports), amount_sol));
+        }
+        let lamports = Self::deposit_lamports(context, amount_sol);
+        let instruction = system_instruction::transfer(&context.wallet_pubkey, &context.treasury_pubkey, lamports);
+        Ok((instruction, lamports))
+    }
+
+    /// Network fee the cluster would charge for `instruction` right now (the signature fee if it can't say)
+    async fn estimate_fee_lamports(context: &PdaTransferContext, instruction: Instruction) -> u64 {
+        let Ok(blockhash) = context.rpc_client.get_latest_blockhash().await else {
+            return DEFAULT_SIGNATURE_FEE_LAMPORTS;
+        };
+        let message = Message::new_with_blockhash(&[instruction], Some(&context.wallet_pubkey), &blockhash);
+        context.rpc_client.client()
+            .get_fee_for_message(&message)
+            .unwrap_or(DEFAULT_SIGNATURE_FEE_LAMPORTS)
+    }
+
+    /// What `deposit_to_pda(amount_sol)` would do - fee, resulting balances and rent - without sending anything
+    pub async fn preview_deposit(&self, amount_sol: f64) -> Result<PdaTransferPreview, String> {
+        let context = self.pda_transfer_context().await?;
+        let planned = Self::deposit_instruction(&context, amount_sol);
+        let lamports = Self::deposit_lamports(&context, amount_sol);
+        let fee = match &planned {
+            Ok((instruction, _)) => Self::estimate_fee_lamports(&context, instruction.clone()).await,
+            Err(_) => DEFAULT_SIGNATURE_FEE_LAMPORTS,
+        };
+        let wallet_after = context.wallet_lamports as i128 - lamports as i128 - fee as i128;
+        let pda_after = context.pda_lamports as i128 + lamports as i128;
+        let blocked_reason = match planned {
+            Err(e) => Some(e),
+            Ok(_) if wallet_after < 0 => Some(format!(
+                "Wallet can't cover the transfer plus the network fee ({:.6} SOL needed)",
+                lamports_to_sol(lamports + fee)
+            )),
+            Ok(_) => None,
+        };
+        Ok(PdaTransferPreview::new("deposit", amount_sol, lamports, fee, &context, wallet_after, pda_after, blocked_reason))
+    }
+
     /// Get PDA treasury balance
     pub async fn get_pda_balance(&self) -> Result<f64, String> {
         let treasury_addr = self.treasury_address.as_ref()
@@ -498,6 +810,34 @@ impl SolanaClient {
         Self::get_pda_balance_internal(&rpc_client, treasury_addr).await
     }
     
+    /// Treasury balance in the base currency - what trading_budget tracks
+    /// Same as `get_pda_balance` for a SOL base; the treasury's USDC token balance for a USDC base
+    pub async fn get_trading_balance(&self) -> Result<f64, String> {
+        let treasury_addr = self.treasury_address.as_ref()
+            .ok_or("Treasury PDA not initialized")?;
+        let rpc_url = self.rpc_url.as_ref()
+            .ok_or("RPC URL not configured")?;
+        
+        let rpc_client = SolanaRpcClient::new(rpc_url.clone());
+        Self::get_trading_balance_internal(&rpc_client, treasury_addr, self.base_currency).await
+    }
+    
+    async fn get_trading_balance_internal(
+        rpc_client: &SolanaRpcClient,
+        treasury_addr: &str,
+        base_currency: crate::dex_aggregator::BaseCurrency,
+    ) -> Result<f64, String> {
+        match base_currency {
+            crate::dex_aggregator::BaseCurrency::Sol => Self::get_pda_balance_internal(rpc_client, treasury_addr).await,
+            crate::dex_aggregator::BaseCurrency::Usdc => {
+                let treasury_pubkey = Pubkey::from_str(treasury_addr)
+                    .map_err(|e| format!("Invalid treasury address: {}", e))?;
+                rpc_client.get_token_balance(&treasury_pubkey, base_currency.mint()).await
+                    .map_err(|e| format!("Failed to get PDA USDC balance: {}", e))
+            }
+        }
+    }
+    
     /// Internal helper to get PDA balance
     async fn get_pda_balance_internal(rpc_client: &SolanaRpcClient, treasury_addr: &str) -> Result<f64, String> {
         let treasury_pubkey = Pubkey::from_str(treasury_addr)
@@ -597,181 +937,189 @@ impl SolanaClient {
 
     /// Withdraw funds from PDA treasury back to wallet (real Solana transaction)
     /// 
-    /// IMPORTANT: This requires a Solana program that uses invoke_signed.
-    /// According to Solana docs: invoke_signed is program-side only and must be called
-    /// from within a Solana program context (https://docs.rs/solana-cpi/latest/solana_cpi/fn.invoke_signed.html)
+    /// invoke_signed is program-side only (https://docs.rs/solana-cpi/latest/solana_cpi/fn.invoke_signed.html),
+    /// so this calls the withdrawal program configured via WITHDRAW_PROGRAM_ID, which signs
+    /// for the PDA with seeds ['agent-treasury', authority, bump] (see pda_withdraw_helper::program).
+    /// The treasury PDA must have been derived under that program.
     /// 
-    /// For client-side code, we need to:
-    /// 1. Create a transaction that calls a Solana program
-    /// 2. That program uses invoke_signed to transfer from PDA
-    /// 
-    /// If WITHDRAW_PROGRAM_ID env var is set, we'll call that program.
-    /// Otherwise, returns instructions on how to set it up.
+    /// Refuses withdrawals that would leave the PDA below the rent-exempt minimum.
+    /// Returns the transaction signature and the updated PDA balance
     pub async fn withdraw_from_pda(&mut self, amount_sol: f64) -> Result<(String, f64), String> {
         if amount_sol <= 0.0 {
             return Err("Withdrawal amount must be positive".to_string());
         }
 
-        let (wallet_pubkey, treasury_pubkey, treasury_bump, rpc_url) = {
-            let wallet_addr = self.wallet_address.as_ref()
-                .ok_or("Wallet address not configured")?;
-            let treasury_addr = self.treasury_address.as_ref()
-                .ok_or("Treasury PDA not initialized")?;
-            let bump = self.treasury_bump
-                .ok_or("Treasury PDA bump seed not available")?;
-            let rpc = self.rpc_url.as_ref()
-                .ok_or("RPC URL not configured")?;
-            
-            (wallet_addr.clone(), treasury_addr.clone(), bump, rpc.clone())
-        };
-
-        // Parse pubkeys
-        let from_pubkey = Pubkey::from_str(&treasury_pubkey)
-            .map_err(|e| format!("Invalid treasury address: {}", e))?;
-        let to_pubkey = Pubkey::from_str(&wallet_pubkey)
-            .map_err(|e| format!("Invalid wallet address: {}", e))?;
+        let context = self.pda_transfer_context().await?;
+        let withdraw_instruction = self.withdraw_instruction(&context, amount_sol)?;
+        let rpc_client = &context.rpc_client;
+        let pda_balance = lamports_to_sol(context.pda_lamports);
 
         // Load wallet for authority
-        let wallet = Wallet::from_env_or_new("WALLET_PRIVATE_KEY");
+        let wallet = self.signing_wallet()?;
         let authority_pubkey = wallet.pubkey();
         
+        // Build transaction
+        let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_with_payer(
+            &[withdraw_instruction],
+            Some(&authority_pubkey), // Authority pays for transaction
+        );
+        
+        // Sign with wallet (the program signs for the PDA)
+        transaction.sign(&[wallet.keypair()], latest_blockhash);
+        
+        // Send and confirm transaction
+        let signature = rpc_client.send_transaction(&transaction).await
+            .map_err(|e| format!("Failed to send withdrawal transaction: {}", e))?;
+        
+        log::info!("📤 Withdrawal transaction sent: {}", signature);
+        
+        // Wait for confirmation
+        rpc_client.confirm_transaction(&signature).await
+            .map_err(|e| format!("Transaction confirmation failed: {}", e))?;
+        
+        // Get updated balances
+        let balance_after = rpc_client.get_balance(&context.treasury_pubkey).await
+            .unwrap_or(pda_balance - amount_sol);
+        if let Ok(wallet_balance) = rpc_client.get_balance(&context.wallet_pubkey).await {
+            self.wallet_balance = wallet_balance;
+        }
+        
+        // Update trading budget (SOL base only - a USDC budget isn't moved by SOL transfers)
+        if self.base_currency == crate::dex_aggregator::BaseCurrency::Sol {
+            self.trading_budget = balance_after;
+        }
+        self.transaction_count += 1;
+        
+        log::info!("✅ Withdrew {:.6} SOL from PDA treasury. New balance: {:.6} SOL | TX: {}", 
+                 amount_sol, balance_after, signature);
+        
+        Ok((signature.to_string(), balance_after))
+    }
+
+    /// Withdrawal-program instruction moving `amount_sol` from the PDA to the wallet, after every check
+    /// that would refuse it: balance, rent exemption, withdrawal program and PDA derivation
+    fn withdraw_instruction(&self, context: &PdaTransferContext, amount_sol: f64) -> Result<Instruction, String> {
+        if amount_sol <= 0.0 {
+            return Err("Withdrawal amount must be positive".to_string());
+        }
+        let treasury_bump = self.treasury_bump
+            .ok_or("Treasury PDA bump seed not available")?;
+        let authority_pubkey = self.signing_wallet()?.pubkey();
+        Self::program_withdraw_instruction(context, amount_sol, authority_pubkey, treasury_bump, crate::pda::withdraw_program_id())
+    }
+
+    /// The checks and instruction of `withdraw_instruction` for a resolved signer, bump and withdrawal program
+    fn program_withdraw_instruction(
+        context: &PdaTransferContext,
+        amount_sol: f64,
+        authority_pubkey: Pubkey,
+        treasury_bump: u8,
+        program_id: Option<Pubkey>,
+    ) -> Result<Instruction, String> {
         // Verify the authority matches
-        if authority_pubkey != to_pubkey {
+        if authority_pubkey != context.wallet_pubkey {
             return Err("Wallet authority mismatch".to_string());
         }
         
         // Check PDA balance
-        let rpc_client = SolanaRpcClient::new(rpc_url.clone());
-        let pda_balance = rpc_client.get_balance(&from_pubkey).await
-            .map_err(|e| format!("Failed to check PDA balance: {}", e))?;
-        
-        if pda_balance < amount_sol {
+        let pda_balance = lamports_to_sol(context.pda_lamports);
+        let amount_lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
+        if context.pda_lamports < amount_lamports {
             return Err(format!("Insufficient PDA balance. Balance: {:.6} SOL, Requested: {:.6} SOL", 
                              pda_balance, amount_sol));
         }
+        
+        // RENT EXEMPTION: Keep the PDA rent-exempt so the account isn't closed unexpectedly
+        let max_withdrawable = context.pda_lamports.saturating_sub(context.min_rent);
+        if amount_lamports > max_withdrawable {
+            return Err(format!(
+                "Withdrawal would leave the PDA below the rent-exempt minimum ({:.6} SOL). Max withdrawable: {:.6} SOL",
+                lamports_to_sol(context.min_rent), lamports_to_sol(max_withdrawable)
+            ));
+        }
 
-        // Get wallet balance before withdrawal
-        let wallet_balance_before = rpc_client.get_balance(&to_pubkey).await
-            .unwrap_or(0.0);
-
-        // Get RPC client and latest blockhash
-        let rpc_client_sync = rpc_client.client();
-        let latest_blockhash = rpc_client_sync.get_latest_blockhash()
-            .map_err(|e| format!("Failed to get latest blockhash: {}", e))?;
+        // A withdrawal program is required to sign for the PDA
+        let treasury_pubkey = context.treasury_pubkey;
+        let program_id = match program_id {
+            Some(program_id) => program_id,
+            None => {
+                log::warn!("PDA withdrawal requires a Solana program using invoke_signed");
+                log::info!("PDA Address: {}", treasury_pubkey);
+                log::info!("Requested withdrawal: {:.6} SOL from {:.6} SOL balance", amount_sol, pda_balance);
+                
+                // Return informative error with setup instructions
+                return Err(format!(
+                    "PDA withdrawals require a Solana program using invoke_signed.\n\n\
+                     PDA Details:\n\
+                     - Address: {}\n\
+                     - Current Balance: {:.6} SOL\n\
+                     - Requested Withdrawal: {:.6} SOL\n\
+                     - Bump Seed: {}\n\
+                     - Seeds: ['agent-treasury', authority_pubkey, bump]\n\n\
+                     Setup Instructions:\n\n\
+                     1. Deploy the withdrawal program in backend/src/pda_withdraw_helper.rs (program::process_instruction)\n\n\
+                     2. Set WITHDRAW_PROGRAM_ID environment variable and restart:\n\
+                        WITHDRAW_PROGRAM_ID=YourProgramIdHere\n\n\
+                     3. The treasury PDA is then derived under the program so it can sign withdrawals.\n\
+                        Funds at the current System Program PDA cannot be withdrawn by any program.\n\n\
+                     Available Now:\n\
+                     ✅ Deposit to PDA: POST /pda/deposit\n\
+                     ✅ Check PDA balance: GET /pda/balance\n\
+                     ✅ Get PDA info: GET /pda/info",
+                    treasury_pubkey, pda_balance, amount_sol, treasury_bump
+                ));
+            }
+        };
         
-        // Verify PDA derivation
-        let system_program = solana_sdk::system_program::id();
+        // Verify PDA derivation - only the program the PDA was derived under can sign for it
         let seeds: &[&[u8]] = &[
-            b"agent-treasury",
+            crate::pda::TREASURY_SEED.as_bytes(),
             authority_pubkey.as_ref(),
             &[treasury_bump],
         ];
-        
-        match Pubkey::create_program_address(seeds, &system_program) {
+        match Pubkey::create_program_address(seeds, &program_id) {
+            Ok(derived_pda) if derived_pda == treasury_pubkey => {}
             Ok(derived_pda) => {
-                if derived_pda != from_pubkey {
-                    return Err(format!("PDA derivation mismatch. Expected: {}, Got: {}", from_pubkey, derived_pda));
-                }
+                return Err(format!(
+                    "Treasury PDA {} was not derived under withdrawal program {} (expected {}). Restart after setting WITHDRAW_PROGRAM_ID",
+                    treasury_pubkey, program_id, derived_pda
+                ));
             }
             Err(_) => {
                 return Err("Invalid PDA seeds".to_string());
             }
         }
         
-        // Check if a withdrawal program ID is configured
-        // If set, we'll create a transaction that calls that program
-        // The program must use invoke_signed to transfer from PDA
-        let withdraw_program_id = std::env::var("WITHDRAW_PROGRAM_ID").ok();
-        
-        if let Some(program_id_str) = withdraw_program_id {
-            // We have a program ID - create instruction to call it
-            let program_id = Pubkey::from_str(&program_id_str)
-                .map_err(|e| format!("Invalid WITHDRAW_PROGRAM_ID: {}", e))?;
-            
-            // Create instruction to call the withdrawal program
-            // The program should expect: [instruction_discriminator: u8, amount: u64, bump: u8]
-            let lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
-            let mut instruction_data = Vec::with_capacity(10);
-            instruction_data.push(0); // Instruction discriminator (0 = withdraw)
-            instruction_data.extend_from_slice(&lamports.to_le_bytes());
-            instruction_data.push(treasury_bump);
-            
-            let withdraw_instruction = Instruction {
-                program_id,
-                accounts: vec![
-                    AccountMeta::new(from_pubkey, false),              // PDA account (signer via seeds in program)
-                    AccountMeta::new(to_pubkey, false),                // Destination wallet (writable)
-                    AccountMeta::new_readonly(authority_pubkey, true), // Authority wallet (signer)
-                    AccountMeta::new_readonly(system_program::id(), false), // System Program
-                ],
-                data: instruction_data,
-            };
-            
-            // Build transaction
-            let mut transaction = Transaction::new_with_payer(
-                &[withdraw_instruction],
-                Some(&authority_pubkey), // Authority pays for transaction
-            );
-            
-            // Sign with wallet
-            transaction.sign(&[wallet.keypair()], latest_blockhash);
-            
-            // Send and confirm transaction
-            let signature = rpc_client.send_transaction(&transaction).await
-                .map_err(|e| format!("Failed to send withdrawal transaction: {}", e))?;
-            
-            log::info!("📤 Withdrawal transaction sent: {}", signature);
-            
-            // Wait for confirmation
-            rpc_client.confirm_transaction(&signature).await
-                .map_err(|e| format!("Transaction confirmation failed: {}", e))?;
-            
-            // Get updated PDA balance
-            let balance_after = rpc_client.get_balance(&from_pubkey).await
-                .unwrap_or(pda_balance);
-            
-            // Update trading budget
-            self.trading_budget = balance_after;
-            self.wallet_balance = wallet_balance_before + amount_sol;
-            
-            log::info!("✅ Withdrew {:.6} SOL from PDA treasury. New balance: {:.6} SOL | TX: {}", 
-                     amount_sol, balance_after, signature);
-            
-            Ok((signature.to_string(), balance_after))
-        } else {
-            // No program configured - return instructions
-            log::warn!("PDA withdrawal requires a Solana program using invoke_signed");
-            log::info!("PDA Address: {}", treasury_pubkey);
-            log::info!("Requested withdrawal: {:.6} SOL from {:.6} SOL balance", amount_sol, pda_balance);
-            
-            // Return informative error with setup instructions
-            return Err(format!(
-                "PDA withdrawals require a Solana program using invoke_signed.\n\n\
-                 According to Solana documentation:\n\
-                 https://docs.rs/solana-cpi/latest/solana_cpi/fn.invoke_signed.html\n\n\
-                 invoke_signed is program-side only and must be called from within a Solana program context.\n\n\
-                 PDA Details:\n\
-                 - Address: {}\n\
-                 - Current Balance: {:.6} SOL\n\
-                 - Requested Withdrawal: {:.6} SOL\n\
-                 - Bump Seed: {}\n\
-                 - Seeds: ['agent-treasury', authority_pubkey, bump]\n\n\
-                 Setup Instructions:\n\n\
-                 1. Deploy a Solana program with a withdraw function that uses invoke_signed\n\
-                    Example code available in: backend/src/pda_withdraw_helper.rs\n\n\
-                 2. Set WITHDRAW_PROGRAM_ID environment variable:\n\
-                    WITHDRAW_PROGRAM_ID=YourProgramIdHere\n\n\
-                 3. The program should:\n\
-                    - Accept authority as signer\n\
-                    - Use invoke_signed with seeds: ['agent-treasury', authority, bump]\n\
-                    - Call system_instruction::transfer from PDA to wallet\n\n\
-                 Available Now:\n\
-                 ✅ Deposit to PDA: POST /pda/deposit\n\
-                 ✅ Check PDA balance: GET /pda/balance\n\
-                 ✅ Get PDA info: GET /pda/info",
-                treasury_pubkey, pda_balance, amount_sol, treasury_bump
-            ));
-        }
+        Ok(crate::pda_withdraw_helper::PDAWithdrawHelper::create_withdraw_instruction(
+            &program_id,
+            &treasury_pubkey,
+            &authority_pubkey,
+            &context.wallet_pubkey,
+            amount_lamports,
+            treasury_bump,
+        ))
+    }
+
+    /// What `withdraw_from_pda(amount_sol)` would do - fee, resulting balances and rent - without sending anything
+    pub async fn preview_withdraw(&self, amount_sol: f64) -> Result<PdaTransferPreview, String> {
+        let context = self.pda_transfer_context().await?;
+        let planned = self.withdraw_instruction(&context, amount_sol);
+        let lamports = (amount_sol.max(0.0) * LAMPORTS_PER_SOL as f64) as u64;
+        let fee = match &planned {
+            Ok(instruction) => Self::estimate_fee_lamports(&context, instruction.clone()).await,
+            Err(_) => DEFAULT_SIGNATURE_FEE_LAMPORTS,
+        };
+        let wallet_after = context.wallet_lamports as i128 + lamports as i128 - fee as i128;
+        let pda_after = context.pda_lamports as i128 - lamports as i128;
+        let blocked_reason = match planned {
+            Err(e) => Some(e),
+            Ok(_) if context.wallet_lamports < fee => Some(format!(
+                "Wallet can't pay the {:.6} SOL network fee", lamports_to_sol(fee)
+            )),
+            Ok(_) => None,
+        };
+        Ok(PdaTransferPreview::new("withdraw", amount_sol, lamports, fee, &context, wallet_after, pda_after, blocked_reason))
     }
 
     /// Deposit funds to trading budget (legacy simulated method - use deposit_to_pda for real deposits)
@@ -857,3 +1205,86 @@ pub async fn simulate_market_data(engine: Arc<Mutex<super::trading_engine::Tradi
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::system_program;
+
+    const MIN_RENT: u64 = 890_880;
+
+    fn transfer_context(wallet_pubkey: Pubkey, treasury_pubkey: Pubkey, wallet_lamports: u64, pda_lamports: u64) -> PdaTransferContext {
+        PdaTransferContext {
+            rpc_client: SolanaRpcClient::new("http://127.0.0.1:8899".to_string()),
+            wallet_pubkey,
+            treasury_pubkey,
+            wallet_lamports,
+            pda_lamports,
+            pda_exists: pda_lamports > 0,
+            min_rent: MIN_RENT,
+        }
+    }
+
+    #[test]
+    fn test_deposit_lamports_and_instruction() {
+        let (wallet, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // An existing PDA receives exactly the amount
+        let funded = transfer_context(wallet, treasury, 2 * LAMPORTS_PER_SOL, MIN_RENT);
+        assert_eq!(SolanaClient::deposit_lamports(&funded, 0.5), LAMPORTS_PER_SOL / 2);
+        assert_eq!(SolanaClient::deposit_lamports(&funded, -1.0), 0);
+
+        // The deposit that creates the PDA is topped up to the rent-exempt minimum
+        let empty = transfer_context(wallet, treasury, 2 * LAMPORTS_PER_SOL, 0);
+        assert_eq!(SolanaClient::deposit_lamports(&empty, 0.0001), MIN_RENT);
+        assert_eq!(SolanaClient::deposit_lamports(&empty, 1.0), LAMPORTS_PER_SOL);
+
+        // Wallet signs and pays, the PDA receives
+        let (instruction, lamports) = SolanaClient::deposit_instruction(&empty, 0.0001).unwrap();
+        assert_eq!(lamports, MIN_RENT);
+        assert_eq!(instruction, system_instruction::transfer(&wallet, &treasury, MIN_RENT));
+        assert_eq!(instruction.program_id, system_program::id());
+        assert_eq!(instruction.accounts, vec![AccountMeta::new(wallet, true), AccountMeta::new(treasury, false)]);
+
+        assert!(SolanaClient::deposit_instruction(&funded, 0.0).unwrap_err().contains("must be positive"));
+        assert!(SolanaClient::deposit_instruction(&funded, 3.0).unwrap_err().contains("Insufficient wallet balance"));
+    }
+
+    #[test]
+    fn test_withdraw_instruction() {
+        let (authority, program_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (treasury, bump) = Pubkey::find_program_address(
+            &[crate::pda::TREASURY_SEED.as_bytes(), authority.as_ref()],
+            &program_id,
+        );
+        let context = transfer_context(authority, treasury, 0, LAMPORTS_PER_SOL);
+        let withdraw = |amount_sol: f64, signer: Pubkey, program: Option<Pubkey>| {
+            SolanaClient::program_withdraw_instruction(&context, amount_sol, signer, bump, program)
+        };
+
+        // The program moves the amount from the PDA to the wallet, signed by the authority
+        let instruction = withdraw(0.5, authority, Some(program_id)).unwrap();
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, vec![
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(authority, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+        assert_eq!(instruction.data, crate::pda_withdraw_helper::WithdrawInstruction {
+            amount_lamports: LAMPORTS_PER_SOL / 2,
+            bump,
+        }.pack());
+
+        // Refusals: wrong signer, more than the PDA holds, below rent, no program, PDA not derived under the program
+        assert!(withdraw(0.5, Pubkey::new_unique(), Some(program_id)).unwrap_err().contains("authority mismatch"));
+        assert!(withdraw(2.0, authority, Some(program_id)).unwrap_err().contains("Insufficient PDA balance"));
+        assert!(withdraw(1.0, authority, Some(program_id)).unwrap_err().contains("rent-exempt minimum"));
+        assert!(withdraw(0.5, authority, None).unwrap_err().contains("require a Solana program"));
+        assert!(withdraw(0.5, authority, Some(Pubkey::new_unique())).is_err());
+
+        // Amount is checked before the signing key is looked up
+        assert!(SolanaClient::new().withdraw_instruction(&context, 0.0).unwrap_err().contains("must be positive"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use serde::{Deserialize, Serialize};
@@ -11,17 +12,327 @@ use crate::pumpfun::PumpFunClient;
 use crate::jupiter_integration::JupiterClient;
 use crate::signal_platform::{SignalMarketplace, TradingSignalData, SignalAction, SignalStatus};
 use crate::reinforcement_learning::{RLAgent, LearningCoordinator};
+use crate::quant_analysis::{QuantAnalyzer, DEFAULT_ATR_STOP_MULTIPLIER};
+use crate::error_handling::AppError;
+use crate::symbol_registry;
+use crate::market_regime::{average_change_pct, MarketRegime, MarketRegimeState, DEFAULT_REGIME_BENCHMARKS, DEFAULT_REGIME_THRESHOLD_PCT};
+
+/// Scans averaged into the ATR behind volatility-scaled stops
+const STOP_ATR_PERIOD: usize = 14;
+
+/// Per-symbol scan prices kept for the ATR (a little more than one period)
+const STOP_PRICE_HISTORY_LEN: usize = 30;
 
 /// Quick profit opportunity analysis result (5-10% profit targets)
-struct QuickProfitOpportunity {
-    entry_price: f64,
-    target_price: f64,
-    stop_loss: f64,
-    confidence: f64,
-    timeframe: String,
-    timeframe_seconds: i64,
-    analysis: String,
-    data_sources: Vec<String>,
+pub struct QuickProfitOpportunity {
+    pub entry_price: f64,
+    pub target_price: f64,
+    pub stop_loss: f64,
+    pub confidence: f64,
+    pub timeframe: String,
+    pub timeframe_seconds: i64,
+    pub analysis: String,
+    pub data_sources: Vec<String>,
+}
+
+/// Market inputs for the quick profit analysis
+/// Built from DEX Screener pairs when live, or from historical candles when backtesting
+#[derive(Debug, Clone, Copy)]
+pub struct QuickProfitPriceData {
+    pub price: f64,
+    pub m5_change: f64,
+    pub h1_change: f64,
+    pub liquidity: f64,
+    pub volume_24h: f64,
+    pub buys: i32,
+    pub sells: i32,
+}
+
+impl QuickProfitPriceData {
+    /// 5m trade count extrapolated to 24h vs actual 24h volume - 2x spike or $10k+ volume
+    pub fn volume_spike_ratio(&self) -> f64 {
+        let volume_5m_estimate = (self.buys + self.sells) as f64 * 288.0; // 288 * 5min = 24h
+        if self.volume_24h > 0.0 {
+            volume_5m_estimate / self.volume_24h
+        } else {
+            1.0
+        }
+    }
+
+    pub fn has_volume_spike(&self) -> bool {
+        self.volume_spike_ratio() > 2.0 || self.volume_24h > 10000.0
+    }
+}
+
+/// Default minimum pool liquidity for a memecoin signal (USD)
+pub const DEFAULT_MIN_MEMECOIN_LIQUIDITY_USD: f64 = 5000.0;
+/// Lower liquidity floor while volume is spiking - early detection (USD)
+pub const DEFAULT_MIN_MEMECOIN_LIQUIDITY_SPIKE_USD: f64 = 3000.0;
+
+/// Age and holder stats for a token - `None` where no source reported them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenProfile {
+    pub age_hours: Option<f64>,
+    pub holder_count: Option<u32>,
+    pub top_holder_pct: Option<f64>,
+}
+
+impl TokenProfile {
+    /// Age from the DEX pair's creation time (falls back to the launch timestamp), holders from the launch
+    pub fn new(launch: &crate::pumpfun::TokenLaunch, pair_created_at_ms: Option<i64>, now: i64) -> Self {
+        Self {
+            age_hours: pair_created_at_ms
+                .map(|ms| (now - ms / 1000).max(0) as f64 / 3600.0)
+                .or_else(|| launch.age_hours(now)),
+            holder_count: launch.holder_count,
+            top_holder_pct: launch.top_holder_pct,
+        }
+    }
+}
+
+/// Gates every memecoin signal must pass - liquidity for a clean exit, age and holder spread against rugs
+/// Age and holder gates are off at their defaults (0h, 0 holders, 100%)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemecoinFilterConfig {
+    pub min_liquidity_usd: f64,
+    pub min_liquidity_spike_usd: f64,
+    pub min_token_age_hours: f64,
+    pub min_holder_count: u32,
+    pub max_top_holder_pct: f64,
+    /// Reject when an enabled gate has no data for the token instead of letting it through
+    pub reject_unknown: bool,
+}
+
+impl Default for MemecoinFilterConfig {
+    fn default() -> Self {
+        Self {
+            min_liquidity_usd: DEFAULT_MIN_MEMECOIN_LIQUIDITY_USD,
+            min_liquidity_spike_usd: DEFAULT_MIN_MEMECOIN_LIQUIDITY_SPIKE_USD,
+            min_token_age_hours: 0.0,
+            min_holder_count: 0,
+            max_top_holder_pct: 100.0,
+            reject_unknown: false,
+        }
+    }
+}
+
+impl MemecoinFilterConfig {
+    /// MEMECOIN_MIN_LIQUIDITY_USD, MEMECOIN_MIN_LIQUIDITY_SPIKE_USD, MEMECOIN_MIN_TOKEN_AGE_HOURS,
+    /// MEMECOIN_MIN_HOLDERS, MEMECOIN_MAX_TOP_HOLDER_PCT, MEMECOIN_REJECT_UNKNOWN (invalid values keep the defaults)
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.trim().parse().ok())
+        }
+        let defaults = Self::default();
+        let config = Self {
+            min_liquidity_usd: var("MEMECOIN_MIN_LIQUIDITY_USD").unwrap_or(defaults.min_liquidity_usd),
+            min_liquidity_spike_usd: var("MEMECOIN_MIN_LIQUIDITY_SPIKE_USD").unwrap_or(defaults.min_liquidity_spike_usd),
+            min_token_age_hours: var("MEMECOIN_MIN_TOKEN_AGE_HOURS").unwrap_or(defaults.min_token_age_hours),
+            min_holder_count: var("MEMECOIN_MIN_HOLDERS").unwrap_or(defaults.min_holder_count),
+            max_top_holder_pct: var("MEMECOIN_MAX_TOP_HOLDER_PCT").unwrap_or(defaults.max_top_holder_pct),
+            reject_unknown: var("MEMECOIN_REJECT_UNKNOWN").unwrap_or(defaults.reject_unknown),
+        };
+        match config.validate() {
+            Ok(()) => config,
+            Err(e) => {
+                log::warn!("⚠️ Invalid memecoin filter settings ({}) - using defaults", e);
+                defaults
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("min_liquidity_usd", self.min_liquidity_usd),
+            ("min_liquidity_spike_usd", self.min_liquidity_spike_usd),
+            ("min_token_age_hours", self.min_token_age_hours),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be a non-negative number", name));
+            }
+        }
+        if !(0.0..=100.0).contains(&self.max_top_holder_pct) {
+            return Err("max_top_holder_pct must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+
+    /// Why a token fails the gates, `None` if it passes
+    /// Without price data there is no liquidity reading - only `reject_unknown` refuses it
+    pub fn rejection_reason(&self, price_data: Option<&QuickProfitPriceData>, profile: &TokenProfile) -> Option<String> {
+        let unknown = |what: &str| self.reject_unknown.then(|| format!("{} unknown", what));
+
+        match price_data {
+            Some(data) => {
+                let min = if data.has_volume_spike() { self.min_liquidity_spike_usd } else { self.min_liquidity_usd };
+                if data.liquidity <= min {
+                    return Some(format!("liquidity ${:.0} not above ${:.0}", data.liquidity, min));
+                }
+            }
+            None => {
+                if let Some(reason) = unknown("liquidity") {
+                    return Some(reason);
+                }
+            }
+        }
+
+        if self.min_token_age_hours > 0.0 {
+            match profile.age_hours {
+                Some(age) if age < self.min_token_age_hours => {
+                    return Some(format!("token age {:.1}h below {:.1}h", age, self.min_token_age_hours));
+                }
+                None => if let Some(reason) = unknown("token age") { return Some(reason) },
+                _ => {}
+            }
+        }
+
+        if self.min_holder_count > 0 {
+            match profile.holder_count {
+                Some(holders) if holders < self.min_holder_count => {
+                    return Some(format!("{} holders below {}", holders, self.min_holder_count));
+                }
+                None => if let Some(reason) = unknown("holder count") { return Some(reason) },
+                _ => {}
+            }
+        }
+
+        if self.max_top_holder_pct < 100.0 {
+            match profile.top_holder_pct {
+                Some(pct) if pct > self.max_top_holder_pct => {
+                    return Some(format!("top holder owns {:.1}% (max {:.1}%)", pct, self.max_top_holder_pct));
+                }
+                None => if let Some(reason) = unknown("top holder share") { return Some(reason) },
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Live-editable memecoin gates
+/// Cheap, cloneable handle shared between the memecoin providers, the backtester route and the API
+#[derive(Debug, Clone)]
+pub struct MemecoinFilter {
+    config: Arc<Mutex<MemecoinFilterConfig>>,
+}
+
+impl MemecoinFilter {
+    pub fn new(config: MemecoinFilterConfig) -> Self {
+        Self { config: Arc::new(Mutex::new(config)) }
+    }
+
+    pub async fn get(&self) -> MemecoinFilterConfig {
+        *self.config.lock().await
+    }
+
+    /// Replace the gates - rejects invalid settings and keeps the current ones
+    pub async fn set(&self, config: MemecoinFilterConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.config.lock().await = config;
+        Ok(())
+    }
+}
+
+/// Weighted agreement a consensus needs - three providers at baseline reputation
+pub const DEFAULT_CONSENSUS_MIN_WEIGHTED_SCORE: f64 = 3.0;
+/// Distinct providers a consensus needs however reputable one of them is
+pub const DEFAULT_CONSENSUS_MIN_PROVIDERS: usize = 2;
+/// Reputation worth one full vote - new providers start at 50
+pub const DEFAULT_CONSENSUS_BASELINE_REPUTATION: f64 = 50.0;
+
+/// Consensus rules for the Signal Trader meta-signals and the Master Analyzer
+/// Each agreeing provider votes `reputation / baseline_reputation`, so one proven provider
+/// outweighs several noisy ones instead of every provider counting as one
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusConfig {
+    pub min_weighted_score: f64,
+    pub min_providers: usize,
+    pub baseline_reputation: f64,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            min_weighted_score: DEFAULT_CONSENSUS_MIN_WEIGHTED_SCORE,
+            min_providers: DEFAULT_CONSENSUS_MIN_PROVIDERS,
+            baseline_reputation: DEFAULT_CONSENSUS_BASELINE_REPUTATION,
+        }
+    }
+}
+
+impl ConsensusConfig {
+    /// CONSENSUS_MIN_WEIGHTED_SCORE, CONSENSUS_MIN_PROVIDERS, CONSENSUS_BASELINE_REPUTATION (invalid values keep the defaults)
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.trim().parse().ok())
+        }
+        let defaults = Self::default();
+        let config = Self {
+            min_weighted_score: var("CONSENSUS_MIN_WEIGHTED_SCORE").unwrap_or(defaults.min_weighted_score),
+            min_providers: var("CONSENSUS_MIN_PROVIDERS").unwrap_or(defaults.min_providers),
+            baseline_reputation: var("CONSENSUS_BASELINE_REPUTATION").unwrap_or(defaults.baseline_reputation),
+        };
+        match config.validate() {
+            Ok(()) => config,
+            Err(e) => {
+                log::warn!("⚠️ Invalid consensus settings ({}) - using defaults", e);
+                defaults
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.min_weighted_score.is_finite() || self.min_weighted_score < 0.0 {
+            return Err("min_weighted_score must be a non-negative number".to_string());
+        }
+        if !self.baseline_reputation.is_finite() || self.baseline_reputation <= 0.0 {
+            return Err("baseline_reputation must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// Tally one vote per distinct provider from their reputation scores (0-100)
+    pub fn evaluate(&self, reputations: &[f64]) -> Consensus {
+        let weighted_score = reputations.iter()
+            .map(|reputation| reputation.clamp(0.0, 100.0) / self.baseline_reputation)
+            .sum::<f64>();
+        Consensus {
+            providers: reputations.len(),
+            weighted_score,
+            reached: reputations.len() >= self.min_providers && weighted_score >= self.min_weighted_score,
+            min_weighted_score: self.min_weighted_score,
+        }
+    }
+}
+
+/// Outcome of a reputation-weighted vote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Consensus {
+    pub providers: usize,
+    pub weighted_score: f64,
+    pub reached: bool,
+    min_weighted_score: f64,
+}
+
+impl Consensus {
+    /// Weighted score relative to the threshold - 1.0 is just enough
+    pub fn strength(&self) -> f64 {
+        if self.min_weighted_score > 0.0 {
+            self.weighted_score / self.min_weighted_score
+        } else {
+            1.0
+        }
+    }
+
+    /// Text for a signal's analysis, e.g. "weighted 3.6/3.0 (1.20x) from 3 providers"
+    pub fn summary(&self) -> String {
+        format!(
+            "weighted {:.1}/{:.1} ({:.2}x) from {} providers",
+            self.weighted_score, self.min_weighted_score, self.strength(), self.providers
+        )
+    }
 }
 
 /// Profitability validation result
@@ -46,6 +357,56 @@ pub enum ProviderType {
     MasterAnalyzer,
 }
 
+impl ProviderType {
+    /// Cadence that suits what the provider watches: launches move in seconds, oracle prices in minutes
+    pub fn default_check_interval_secs(&self) -> u64 {
+        match self {
+            ProviderType::MemecoinMonitor => 15,
+            ProviderType::JupiterMemecoinTrader => 20,
+            ProviderType::JupiterBlueChipTrader | ProviderType::OpportunityAnalyzer | ProviderType::SignalTrader => 60,
+            ProviderType::OracleMonitor | ProviderType::MasterAnalyzer => 120,
+        }
+    }
+
+    /// Name the provider type goes by in REGIME_GATING and /market/regime
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProviderType::MemecoinMonitor => "memecoin_monitor",
+            ProviderType::OracleMonitor => "oracle_monitor",
+            ProviderType::JupiterMemecoinTrader => "jupiter_memecoin_trader",
+            ProviderType::JupiterBlueChipTrader => "jupiter_bluechip_trader",
+            ProviderType::OpportunityAnalyzer => "opportunity_analyzer",
+            ProviderType::SignalTrader => "signal_trader",
+            ProviderType::MasterAnalyzer => "master_analyzer",
+        }
+    }
+
+    pub const ALL: [ProviderType; 7] = [
+        ProviderType::MemecoinMonitor,
+        ProviderType::OracleMonitor,
+        ProviderType::JupiterMemecoinTrader,
+        ProviderType::JupiterBlueChipTrader,
+        ProviderType::OpportunityAnalyzer,
+        ProviderType::SignalTrader,
+        ProviderType::MasterAnalyzer,
+    ];
+}
+
+/// Parse PROVIDER_CHECK_INTERVALS="memecoin_monitor:10,oracle_monitor:180" (seconds)
+pub fn parse_check_intervals(value: &str) -> Result<HashMap<String, u64>, String> {
+    let mut intervals = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (provider_id, secs) = entry.split_once(':')
+            .ok_or_else(|| format!("Expected provider:seconds, got {:?}", entry))?;
+        let secs: u64 = secs.trim().parse()
+            .map_err(|_| format!("Invalid check interval for {}: {:?}", provider_id.trim(), secs.trim()))?;
+        crate::provider_registry::ProviderRegistry::validate_check_interval(secs)
+            .map_err(|e| format!("{}: {}", provider_id.trim(), e))?;
+        intervals.insert(provider_id.trim().to_string(), secs);
+    }
+    Ok(intervals)
+}
+
 /// Specialized provider agent with RL integration
 pub struct SpecializedProvider {
     pub provider_id: String,
@@ -56,10 +417,26 @@ pub struct SpecializedProvider {
     dex_client: Arc<DexScreenerClient>,
     pumpfun_client: Arc<PumpFunClient>,
     jupiter_client: Arc<JupiterClient>,
+    /// Pause between cycles at startup - the registry's value (editable via the API) wins once registered
     check_interval_secs: u64,
     capital: Arc<Mutex<f64>>,
     rl_agent: Arc<RLAgent>,
     rl_coordinator: Option<Arc<Mutex<LearningCoordinator>>>,
+    heartbeat: Option<crate::watchdog::Heartbeat>,
+    risk_manager: Option<Arc<Mutex<crate::risk_management::RiskManager>>>,
+    /// Price seen for each symbol on recent scans - the volatility behind ATR stops
+    scan_prices: Mutex<HashMap<String, VecDeque<f64>>>,
+    atr_stop_multiplier: f64,
+    /// Runtime on/off switch - a disabled provider skips its cycles but keeps running
+    registry: Option<crate::provider_registry::ProviderRegistry>,
+    /// Manual mint blacklist / whitelist - filtered signals are never published
+    token_filter: Option<crate::security::TokenFilter>,
+    /// Liquidity / age / holder gates for memecoin signals
+    memecoin_filter: MemecoinFilter,
+    /// Reputation-weighted agreement needed for meta / master signals
+    consensus_config: ConsensusConfig,
+    /// Shared market regime - the Master Analyzer writes it, gated provider types stop buying in it
+    market_regime: Option<MarketRegimeState>,
 }
 
 impl SpecializedProvider {
@@ -77,19 +454,34 @@ impl SpecializedProvider {
             None, // DeepSeek client optional
         ));
         
+        let dex_client = Arc::new(DexScreenerClient::new());
+        
         Self {
             provider_id,
             provider_name,
             provider_type,
             marketplace,
-            oracle_client: Arc::new(SwitchboardClient::new(rpc_url.clone(), std::env::var("SOLANA_RPC_URL").is_ok())),
-            dex_client: Arc::new(DexScreenerClient::new()),
+            oracle_client: Arc::new(SwitchboardClient::new(rpc_url.clone(), std::env::var("SOLANA_RPC_URL").is_ok())
+                .with_pool_fallback(dex_client.clone())),
+            dex_client,
             pumpfun_client: Arc::new(PumpFunClient::new()),
             jupiter_client: Arc::new(JupiterClient::new()),
-            check_interval_secs: 60,
+            check_interval_secs: provider_type.default_check_interval_secs(),
             capital: Arc::new(Mutex::new(10000.0)),
             rl_agent,
             rl_coordinator: None,
+            heartbeat: None,
+            risk_manager: None,
+            scan_prices: Mutex::new(HashMap::new()),
+            atr_stop_multiplier: std::env::var("ATR_STOP_MULTIPLIER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_ATR_STOP_MULTIPLIER),
+            registry: None,
+            token_filter: None,
+            memecoin_filter: MemecoinFilter::new(MemecoinFilterConfig::default()),
+            consensus_config: ConsensusConfig::default(),
+            market_regime: None,
         }
     }
     
@@ -108,6 +500,147 @@ impl SpecializedProvider {
         self
     }
 
+    /// Override the type's default cadence (clamped to the registry's bounds)
+    pub fn with_check_interval(mut self, secs: u64) -> Self {
+        self.check_interval_secs = secs.clamp(
+            crate::provider_registry::MIN_CHECK_INTERVAL_SECS,
+            crate::provider_registry::MAX_CHECK_INTERVAL_SECS,
+        );
+        self
+    }
+
+    pub fn check_interval_secs(&self) -> u64 {
+        self.check_interval_secs
+    }
+
+    /// Interval for the next cycle - re-read each time so API changes apply without a restart
+    async fn current_check_interval(&self) -> tokio::time::Duration {
+        let secs = match self.registry {
+            Some(ref registry) => registry.check_interval_secs(&self.provider_id).await,
+            None => None,
+        };
+        tokio::time::Duration::from_secs(secs.unwrap_or(self.check_interval_secs))
+    }
+
+    /// Report loop progress to the watchdog
+    pub fn with_heartbeat(mut self, heartbeat: crate::watchdog::Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Check the registry's enabled flag before every cycle
+    pub fn with_registry(mut self, registry: crate::provider_registry::ProviderRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Drop signals for filtered mints before they reach the marketplace
+    pub fn with_token_filter(mut self, token_filter: crate::security::TokenFilter) -> Self {
+        self.token_filter = Some(token_filter);
+        self
+    }
+
+    /// Share the live-editable memecoin gates with the API
+    pub fn with_memecoin_filter(mut self, memecoin_filter: MemecoinFilter) -> Self {
+        self.memecoin_filter = memecoin_filter;
+        self
+    }
+
+    /// Consensus threshold for the Signal Trader and Master Analyzer
+    pub fn with_consensus_config(mut self, consensus_config: ConsensusConfig) -> Self {
+        self.consensus_config = consensus_config;
+        self
+    }
+
+    /// Publish (Master Analyzer) or obey (everyone else) the shared market regime
+    pub fn with_market_regime(mut self, market_regime: MarketRegimeState) -> Self {
+        self.market_regime = Some(market_regime);
+        self
+    }
+
+    /// Reputation of each provider from the marketplace - unknown providers get the baseline
+    async fn provider_reputations(&self, provider_ids: &[String]) -> Vec<f64> {
+        let mut reputations = Vec::with_capacity(provider_ids.len());
+        for provider_id in provider_ids {
+            let reputation = self.marketplace.get_provider_stats(provider_id).await
+                .map(|provider| provider.reputation_score)
+                .unwrap_or(self.consensus_config.baseline_reputation);
+            reputations.push(reputation);
+        }
+        reputations
+    }
+
+    /// Size Jupiter orders against the risk manager's slippage cap
+    pub fn with_risk_manager(mut self, risk_manager: Arc<Mutex<crate::risk_management::RiskManager>>) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Shrink a quoted order until its price impact fits under `target_impact` (and the risk manager's cap)
+    /// Records the resulting max position value for `symbol` so execution is sized to match
+    /// Returns None when even the minimum viable size is too expensive
+    async fn slippage_sized_amount(&self, symbol: &str, quote: &crate::jupiter_integration::JupiterQuote, target_impact: f64) -> Option<u64> {
+        let Some(ref risk_manager) = self.risk_manager else {
+            // No risk manager - plain threshold check on the quoted size
+            return if quote.price_impact_pct <= target_impact {
+                quote.in_amount.parse().ok()
+            } else {
+                None
+            };
+        };
+
+        let mut risk_manager = risk_manager.lock().await;
+        match risk_manager.size_for_slippage(quote, target_impact) {
+            Ok(amount) => {
+                risk_manager.set_slippage_cap(symbol, amount as f64 / 1_000_000_000.0); // lamports -> SOL
+                Some(amount)
+            }
+            Err(e) => {
+                log::debug!("Skipping {}: {}", symbol, e);
+                None
+            }
+        }
+    }
+
+    /// Remember this scan's price for `symbol` so later stops can follow its volatility
+    async fn record_scan_price(&self, symbol: &str, price: f64) {
+        if !price.is_finite() || price <= 0.0 {
+            return;
+        }
+        let mut scan_prices = self.scan_prices.lock().await;
+        let history = scan_prices.entry(symbol_registry::canonical(symbol)).or_default();
+        history.push_back(price);
+        while history.len() > STOP_PRICE_HISTORY_LEN {
+            history.pop_front();
+        }
+    }
+
+    /// Stop `atr_stop_multiplier` ATRs from `entry_price`, with ATR measured as a fraction of price
+    /// over this provider's recent scans of `symbol` (so it applies to SOL- or USD-denominated entries)
+    /// Uses the fixed `fallback_pct` until enough scans have been seen to measure volatility
+    async fn volatility_stop(&self, symbol: &str, entry_price: f64, is_buy: bool, fallback_pct: f64) -> f64 {
+        let atr_fraction = {
+            let scan_prices = self.scan_prices.lock().await;
+            scan_prices.get(&symbol_registry::canonical(symbol)).and_then(|history| {
+                let prices: Vec<f64> = history.iter().copied().collect();
+                let last = *prices.last()?;
+                QuantAnalyzer::new()
+                    .calculate_atr(&prices, &prices, &prices, STOP_ATR_PERIOD)
+                    .map(|atr| atr / last)
+            })
+        };
+        
+        let long_stop = match atr_fraction {
+            Some(atr_fraction) => QuantAnalyzer::suggested_stop(entry_price, atr_fraction * entry_price, self.atr_stop_multiplier),
+            None => entry_price * (1.0 - fallback_pct / 100.0),
+        };
+        if is_buy {
+            long_stop
+        } else {
+            2.0 * entry_price - long_stop
+        }
+    }
+
     /// Main provider loop with crash protection and error recovery
     pub async fn run(&self) {
         log::info!(
@@ -118,16 +651,20 @@ impl SpecializedProvider {
 
         let mut consecutive_errors = 0u32;
         let max_consecutive_errors = 10;
-        let mut error_backoff = tokio::time::Duration::from_secs(self.check_interval_secs);
+        let mut error_backoff = self.current_check_interval().await;
 
         loop {
+            if let Some(ref heartbeat) = self.heartbeat {
+                heartbeat.beat();
+            }
+            
             // CRASH PROTECTION: Catch panics and recover
             let result = std::panic::AssertUnwindSafe(self.generate_and_publish_signals()).catch_unwind().await;
             
             match result {
                 Ok(Ok(count)) => {
                     consecutive_errors = 0; // Reset error counter on success
-                    error_backoff = tokio::time::Duration::from_secs(self.check_interval_secs); // Reset backoff
+                    error_backoff = self.current_check_interval().await; // Reset backoff
                     
                     if count > 0 {
                         log::info!(
@@ -148,15 +685,22 @@ impl SpecializedProvider {
                     log::error!("❌ {} error: {}", self.provider_name, e);
                     log::error!("   Error details: {} | Provider will retry after backoff", e);
                     
-                    // Log specific error types for debugging
-                    if e.contains("rate limit") || e.contains("429") {
-                        log::warn!("   ⚠️ Rate limit hit - provider will wait longer");
-                    } else if e.contains("network") || e.contains("connection") || e.contains("timeout") {
-                        log::warn!("   ⚠️ Network error - check internet connection and API availability");
-                    } else if e.contains("not found") || e.contains("404") {
-                        log::warn!("   ⚠️ Resource not found - API endpoint may have changed");
-                    } else if e.contains("empty") || e.contains("no data") {
-                        log::debug!("   ℹ️ No data available - this is normal if market is quiet");
+                    // Branch on the error kind for debugging (and a longer wait when rate limited)
+                    match &e {
+                        AppError::RateLimited(_) => {
+                            log::warn!("   ⚠️ Rate limit hit - provider will wait longer");
+                            error_backoff = (error_backoff * 2).min(tokio::time::Duration::from_secs(300));
+                        }
+                        AppError::Network(_) | AppError::Timeout(_) => {
+                            log::warn!("   ⚠️ Network error - check internet connection and API availability");
+                        }
+                        AppError::NotFound(_) => {
+                            log::warn!("   ⚠️ Resource not found - API endpoint may have changed");
+                        }
+                        AppError::CircuitOpen(_) => {
+                            log::warn!("   ⚠️ Circuit breaker open - waiting for the upstream API to recover");
+                        }
+                        _ => {}
                     }
                     
                     // Exponential backoff on consecutive errors
@@ -192,7 +736,14 @@ impl SpecializedProvider {
     }
 
     /// Generate and publish signals based on provider type
-    async fn generate_and_publish_signals(&self) -> Result<usize, String> {
+    async fn generate_and_publish_signals(&self) -> Result<usize, AppError> {
+        if let Some(ref registry) = self.registry {
+            if !registry.should_run(&self.provider_id).await {
+                log::debug!("⏸️ [{}] Disabled - skipping cycle", self.provider_name);
+                return Ok(0);
+            }
+        }
+        
         log::debug!("🔄 [{}] Starting signal generation cycle...", self.provider_name);
         
         let signals = match &self.provider_type {
@@ -231,8 +782,25 @@ impl SpecializedProvider {
 
         log::debug!("   Generated {} signals (before filtering)", signals.len());
 
+        // REGIME GATING: Provider types configured to stand down in the current regime publish no buys
+        let standing_down = match self.market_regime {
+            Some(ref market_regime) => market_regime.stand_down(self.provider_type.key(), Utc::now().timestamp()).await,
+            None => None,
+        };
+
         let mut published_count = 0;
         for signal in signals {
+            if let Some(regime) = standing_down.filter(|_| signal.action == SignalAction::Buy) {
+                log::info!("🌡️ [{}] Not publishing {} buy signal - standing down in a {} market", self.provider_name, signal.symbol, regime);
+                continue;
+            }
+            // TOKEN FILTER: Buys of filtered mints are dropped here; sells still publish so held positions can exit
+            if let Some(token_filter) = self.token_filter.as_ref().filter(|_| signal.action == SignalAction::Buy) {
+                if !token_filter.is_allowed(signal.filter_token()).await {
+                    log::info!("🚧 [{}] Not publishing {} buy signal - refused by the token filter", self.provider_name, signal.symbol);
+                    continue;
+                }
+            }
             match self.marketplace.publish_signal(signal.clone()).await {
                 Ok(signal_id) => {
                     published_count += 1;
@@ -240,7 +808,7 @@ impl SpecializedProvider {
                               self.provider_name, signal_id, signal.symbol, signal.confidence * 100.0, signal.price);
                     log::debug!("   Signal will be auto-executed if confidence ≥75% and trading is enabled");
                     
-                    // Register this agent with RL coordinator if connected
+                    // Register this agent with RL coordinator if connected (a no-op once registered)
                     if let Some(coordinator) = &self.rl_coordinator {
                         let coordinator_lock = coordinator.lock().await;
                         coordinator_lock.register_agent(self.rl_agent.clone()).await;
@@ -264,16 +832,14 @@ impl SpecializedProvider {
     /// Provider 1: Memecoin Monitor - Analyzes ALL pairs for 5-10% quick profit opportunities
     /// ENHANCED: Volume spike detection, trend confirmation, dynamic thresholds
     /// Scans all Solana pairs from Mobula API, not just hardcoded memecoins
-    async fn generate_memecoin_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_memecoin_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
 
         // SCAN ALL PAIRS from Mobula API and pump.fun scraping
         log::info!("🔍 [ENHANCED] Scanning ALL Solana pairs + pump.fun for 5-10% quick profit opportunities...");
         
         // ENHANCED: Also scrape pump.fun page for real-time opportunities
         let mut pumpfun_tokens = Vec::new();
-        match self.pumpfun_client.scrape_trading_opportunities().await
-            .map_err(|e| format!("{}", e))
-        {
+        match self.pumpfun_client.scrape_trading_opportunities().await {
             Ok(tokens) => {
                 log::info!("✅ Scraped {} tokens from pump.fun page", tokens.len());
                 pumpfun_tokens = tokens;
@@ -284,31 +850,19 @@ impl SpecializedProvider {
         }
         
         // Fetch trending pairs with minimum liquidity (scans all available pairs)
-        // Convert Box<dyn Error> to String using map_err to ensure Send trait compatibility
-        let pairs = match self.dex_client.find_trending_solana_tokens(1000.0).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        let pairs = match self.dex_client.find_trending_solana_tokens(1000.0).await {
             Ok(pairs) => {
                 log::info!("📊 Found {} pairs from Mobula API to analyze", pairs.len());
                 pairs
             }
             Err(error_msg) => {
-                // error_msg is now String (Send-safe), not Box<dyn Error>
                 log::warn!("⚠️ Could not fetch pairs from Mobula API: {}. Using pump.fun data.", error_msg);
                 // If we have pump.fun tokens, use those
                 if !pumpfun_tokens.is_empty() {
                     return self.analyze_launches_for_signals(pumpfun_tokens).await;
                 }
                 // Fallback to old method
-                let launches = match self.pumpfun_client.get_recent_launches(30).await
-                    .map_err(|e| format!("{}", e)) // Convert to String immediately
-                {
-                    Ok(launches) => launches,
-                    Err(pump_error_msg) => {
-                        // pump_error_msg is now String (Send-safe)
-                        return Err(format!("PumpFun error: {}", pump_error_msg));
-                    }
-                };
+                let launches = self.pumpfun_client.get_recent_launches(30).await?;
                 // Convert launches to pairs format for analysis
                 return self.analyze_launches_for_signals(launches).await;
             }
@@ -323,6 +877,7 @@ impl SpecializedProvider {
         }
         
         // ENHANCED: Pre-filter pairs by volume spike and momentum before detailed analysis
+        let memecoin_filter = self.memecoin_filter.get().await;
         let filtered_pairs: Vec<_> = pairs.into_iter()
             .filter(|pair| {
                 let volume_24h = pair.volume.h24;
@@ -333,7 +888,7 @@ impl SpecializedProvider {
                 // Quick pre-filter: volume spike OR strong momentum OR high liquidity
                 let has_volume_spike = volume_24h > 5000.0; // $5k+ volume indicates activity
                 let has_strong_momentum = m5_change.abs() > 2.0 || h1_change.abs() > 5.0;
-                let has_good_liquidity = liquidity > 3000.0; // Minimum liquidity for exit
+                let has_good_liquidity = liquidity > memecoin_filter.min_liquidity_spike_usd; // Lowest liquidity floor for exit
                 
                 has_volume_spike || has_strong_momentum || has_good_liquidity
             })
@@ -359,12 +914,14 @@ impl SpecializedProvider {
                 symbol: pair.base_token.symbol.clone(),
                 uri: pair.url.clone(),
                 creator: pair.pair_address.clone(),
-                created_timestamp: chrono::Utc::now().timestamp(),
+                created_timestamp: pair.pair_created_at.map(|ms| ms / 1000).unwrap_or(0),
                 market_cap: estimated_market_cap.max(1000.0),
                 reply_count: (pair.txns.h24.buys + pair.txns.h24.sells) as u32,
                 is_currently_live: liquidity_usd > 1000.0 && price_usd > 0.0,
                 king_of_the_hill_timestamp: None,
                 bonding_curve: pair.pair_address.clone(),
+                holder_count: None,
+                top_holder_pct: None,
             }
         }).collect();
         
@@ -372,17 +929,13 @@ impl SpecializedProvider {
     }
     
     /// Analyze launches and generate signals (extracted for reuse)
-    async fn analyze_launches_for_signals(&self, launches: Vec<crate::pumpfun::TokenLaunch>) -> Result<Vec<TradingSignalData>, String> {
+    async fn analyze_launches_for_signals(&self, launches: Vec<crate::pumpfun::TokenLaunch>) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         // Get oracle data for price validation
         let oracle_feeds = self.oracle_client.fetch_multiple_feeds(&[
             "SOL/USD".to_string(),
-        ]).await.map_err(|e| {
-            // Convert error to String immediately to ensure Send trait
-            let error_msg = e.to_string();
-            format!("Oracle error: {}", error_msg)
-        })?;
+        ]).await?;
 
         let sol_price = oracle_feeds.first().map(|f| f.price).unwrap_or(100.0);
 
@@ -400,7 +953,9 @@ impl SpecializedProvider {
         
         // OPTIMIZED: Batch API calls in parallel (process up to 10 at a time to avoid rate limits)
         let batch_size = 10;
-        let mut launch_price_map: std::collections::HashMap<String, Option<(f64, f64, f64, f64, f64, i32, i32)>> = std::collections::HashMap::new();
+        let mut launch_price_map: std::collections::HashMap<String, Option<QuickProfitPriceData>> = std::collections::HashMap::new();
+        // Pair creation time (ms) of the best pair - the token age behind the memecoin filter
+        let mut pair_created_map: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
         
         for batch in valid_launches.chunks(batch_size) {
             // Create parallel futures for all API calls in this batch
@@ -411,8 +966,7 @@ impl SpecializedProvider {
                     let mint = launch.mint.clone();
                     let symbol = launch.symbol.clone();
                     async move {
-                        let result = dex_client.get_token_pairs(&mint).await
-                            .map_err(|e| format!("{}", e));
+                        let result = dex_client.get_token_pairs(&mint).await;
                         (symbol, result.ok())
                     }
                 })
@@ -431,20 +985,23 @@ impl SpecializedProvider {
                                 let liq_b = b.liquidity.usd.unwrap_or(0.0);
                                 liq_a.partial_cmp(&liq_b).unwrap_or(std::cmp::Ordering::Equal)
                             }) {
+                            if let Some(created_at) = best_pair.pair_created_at {
+                                pair_created_map.insert(symbol.clone(), created_at);
+                            }
                             let price_usd = best_pair.price_usd.as_ref()
                                 .and_then(|p| p.parse::<f64>().ok())
                                 .unwrap_or(0.0);
                             
                             if price_usd > 0.0 {
-                                Some((
-                                    price_usd,
-                                    best_pair.price_change.m5,
-                                    best_pair.price_change.h1,
-                                    best_pair.liquidity.usd.unwrap_or(0.0),
-                                    best_pair.volume.h24,
-                                    best_pair.txns.m5.buys,
-                                    best_pair.txns.m5.sells,
-                                ))
+                                Some(QuickProfitPriceData {
+                                    price: price_usd,
+                                    m5_change: best_pair.price_change.m5,
+                                    h1_change: best_pair.price_change.h1,
+                                    liquidity: best_pair.liquidity.usd.unwrap_or(0.0),
+                                    volume_24h: best_pair.volume.h24,
+                                    buys: best_pair.txns.m5.buys,
+                                    sells: best_pair.txns.m5.sells,
+                                })
                             } else {
                                 None
                             }
@@ -467,6 +1024,8 @@ impl SpecializedProvider {
         }
         
         // Now process launches with their price data
+        let memecoin_filter = self.memecoin_filter.get().await;
+        let now = Utc::now().timestamp();
         for launch in valid_launches {
             // ENHANCED: Run comprehensive safety check before analyzing
             let sentiment = self.pumpfun_client.analyze_sentiment(&launch);
@@ -502,13 +1061,22 @@ impl SpecializedProvider {
                 continue; // Skip if no real price data available
             }
 
+            // MEMECOIN FILTER: Liquidity, age and holder gates (live-editable via /config/memecoin-filter)
+            let profile = TokenProfile::new(&launch, pair_created_map.get(&launch.symbol).copied(), now);
+            if let Some(reason) = memecoin_filter.rejection_reason(price_data.as_ref(), &profile) {
+                log::info!("🚫 Skipping {} - memecoin filter: {}", launch.symbol, reason);
+                continue;
+            }
+
             // Analyze for 5-10% quick profit opportunities
-            let opportunity = self.analyze_quick_profit_opportunity(
+            let opportunity = Self::analyze_quick_profit_opportunity(
                 &launch,
                 &sentiment,
                 price_data,
                 sol_price,
-            ).await;
+                &profile,
+                &memecoin_filter,
+            );
 
             if let Some(opp) = opportunity {
                 // Clone timeframe before it's moved into the signal
@@ -532,6 +1100,9 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + timeframe_seconds,
                     price: 30.0, // Premium price for quick profit signals
                     status: SignalStatus::Active,
+                    max_hold_secs: Some(timeframe_seconds), // Scalps get one timeframe, not the default two
+                    mint: Some(launch.mint.clone()),
+                    ..Default::default()
                 };
                 
                 signals.push(signal);
@@ -546,21 +1117,28 @@ impl SpecializedProvider {
     }
 
     /// Analyze a memecoin for 5-10% quick profit opportunities
-    async fn analyze_quick_profit_opportunity(
-        &self,
+    /// Pure function of its inputs (no API calls) so the backtester can replay it on historical candles
+    pub fn analyze_quick_profit_opportunity(
         launch: &crate::pumpfun::TokenLaunch,
         sentiment: &crate::pumpfun::MemeSentiment,
-        price_data: Option<(f64, f64, f64, f64, f64, i32, i32)>, // (price, m5_change, h1_change, liquidity, volume_24h, buys, sells)
+        price_data: Option<QuickProfitPriceData>,
         sol_price: f64,
+        profile: &TokenProfile,
+        filter: &MemecoinFilterConfig,
     ) -> Option<QuickProfitOpportunity> {
+        if let Some(reason) = filter.rejection_reason(price_data.as_ref(), profile) {
+            log::debug!("🚫 {} rejected by memecoin filter: {}", launch.symbol, reason);
+            return None;
+        }
+
         let (entry_price, target_price, stop_loss, confidence, timeframe, timeframe_seconds, analysis, data_sources) = 
-            if let Some((current_price, m5_change, h1_change, liquidity, volume_24h, buys, sells)) = price_data {
+            if let Some(data @ QuickProfitPriceData { price: current_price, m5_change, h1_change, liquidity, volume_24h, buys, sells }) = price_data {
                 // REAL PRICE DATA AVAILABLE - Analyze for quick profit
                 
                 // ENHANCED Criteria for 5-10% quick profit opportunities:
                 // 1. Positive momentum (even small moves can lead to 5-10%)
                 // 2. More buys than sells (bullish sentiment)
-                // 3. Minimum liquidity (for quick exit)
+                // 3. Minimum liquidity (for quick exit) - enforced by the memecoin filter above
                 // 4. Volume activity (recent trading)
                 // 5. Early price movement detection (catch moves early)
                 
@@ -568,13 +1146,8 @@ impl SpecializedProvider {
                 
                 // ENHANCED: Volume spike detection (recent volume vs average)
                 // Calculate volume spike ratio (current 5m volume extrapolated to 24h vs actual 24h)
-                let volume_5m_estimate = (buys + sells) as f64 * 288.0; // Extrapolate 5m to 24h (288 * 5min = 24h)
-                let volume_spike_ratio = if volume_24h > 0.0 {
-                    volume_5m_estimate / volume_24h
-                } else {
-                    1.0
-                };
-                let has_volume_spike = volume_spike_ratio > 2.0 || volume_24h > 10000.0; // 2x spike or $10k+ volume
+                let volume_spike_ratio = data.volume_spike_ratio();
+                let has_volume_spike = data.has_volume_spike(); // 2x spike or $10k+ volume
                 
                 // ENHANCED: Trend confirmation (multiple timeframe alignment)
                 let trend_aligned = (m5_change > 0.0 && h1_change > 0.0) || (m5_change < 0.0 && h1_change < 0.0);
@@ -583,10 +1156,8 @@ impl SpecializedProvider {
                 // ENHANCED: Dynamic thresholds based on market conditions
                 // Lower thresholds for high volume spikes (early detection)
                 let momentum_threshold = if has_volume_spike { 0.5 } else { 1.0 };
-                let liquidity_threshold = if has_volume_spike { 3000.0 } else { 5000.0 };
                 
                 let has_momentum = m5_change.abs() > momentum_threshold || h1_change.abs() > 3.0;
-                let has_liquidity = liquidity > liquidity_threshold;
                 let is_bullish = buy_sell_ratio > 1.1 || buys > sells;
                 let has_volume = volume_24h > 1000.0 || (buys + sells) > 5;
                 
@@ -615,14 +1186,14 @@ impl SpecializedProvider {
                 };
                 
                 // ENHANCED conditions - prioritize volume spikes and trend alignment
-                // Signal if we have: (momentum OR bullish) AND liquidity AND (volume OR volume spike)
+                // Signal if we have: (momentum OR bullish) AND (volume OR volume spike) - liquidity already gated
                 // OR: volume spike with any positive momentum
                 let qualifies = if has_volume_spike {
                     // Volume spike = early detection opportunity
-                    (has_momentum || is_bullish) && has_liquidity
+                    has_momentum || is_bullish
                 } else {
-                    // Normal conditions: momentum + liquidity + volume
-                    (has_momentum || is_bullish) && has_liquidity && has_volume
+                    // Normal conditions: momentum + volume
+                    (has_momentum || is_bullish) && has_volume
                 };
                 
                 if qualifies {
@@ -730,7 +1301,7 @@ impl SpecializedProvider {
     }
 
     /// Provider 2: Oracle Monitor - ENHANCED with multi-timeframe analysis and price action patterns
-    async fn generate_oracle_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_oracle_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         let symbols = vec![
@@ -739,18 +1310,11 @@ impl SpecializedProvider {
             "ETH/USD".to_string(),
         ];
 
-        // Convert Box<dyn Error> to String to ensure Send trait compatibility
-        let feeds = match self.oracle_client.fetch_multiple_feeds(&symbols).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
-            Ok(feeds) => feeds,
-            Err(error_msg) => {
-                // error_msg is now String (Send-safe)
-                return Err(format!("Oracle error: {}", error_msg));
-            }
-        };
+        let feeds = self.oracle_client.fetch_multiple_feeds(&symbols).await?;
 
         for feed in feeds {
+            self.record_scan_price(&feed.symbol, feed.price).await;
+            
             // ENHANCED: Multi-timeframe analysis
             // Use real price change from oracle (24h change if available)
             let change_24h = if let Some(price_change_24h) = feed.price_change_24h {
@@ -823,11 +1387,9 @@ impl SpecializedProvider {
                     1.03 // Standard target
                 };
                 
-                let stop_multiplier = if is_high_volatility {
-                    0.97 // Wider stop in volatile markets
-                } else {
-                    0.98 // Tighter stop in stable markets
-                };
+                // Fixed stop until enough scans to measure ATR: wider in volatile markets, tighter in stable ones
+                let fallback_stop_pct = if is_high_volatility { 3.0 } else { 2.0 };
+                let stop_loss = self.volatility_stop(&feed.symbol, feed.price, matches!(action, SignalAction::Buy), fallback_stop_pct).await;
 
                 let action_clone = action.clone();
                 let signal = TradingSignalData {
@@ -841,11 +1403,7 @@ impl SpecializedProvider {
                     } else { 
                         feed.price / target_multiplier
                     },
-                    stop_loss: if matches!(action_clone, SignalAction::Buy) { 
-                        feed.price * stop_multiplier
-                    } else { 
-                        feed.price / stop_multiplier
-                    },
+                    stop_loss,
                     confidence,
                     timeframe: "1h".to_string(),
                     data_sources: vec!["Switchboard Oracle".to_string(), "Multi-Timeframe Analysis".to_string()],
@@ -864,6 +1422,7 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + 3600, // 1 hour
                     price: 10.0,
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
 
                 signals.push(signal);
@@ -876,7 +1435,7 @@ impl SpecializedProvider {
     /// Provider 3: Jupiter Memecoin Trader - ENHANCED coordination with Blue Chip Trader
     /// Analyzes memecoins and generates Jupiter swap signals with real-time price validation
     /// Works in tandem with Blue Chip Trader for portfolio diversification
-    async fn generate_jupiter_memecoin_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_jupiter_memecoin_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         log::info!("🪙 [ENHANCED] Jupiter Memecoin Trader: Scanning memecoins for Jupiter-tradeable opportunities...");
@@ -899,20 +1458,16 @@ impl SpecializedProvider {
         log::info!("📊 Market sentiment from blue chips: {}", market_sentiment);
         
         // Get trending memecoins from Mobula
-        // Convert Box<dyn Error> to String to ensure Send trait compatibility
-        let pairs = match self.dex_client.find_trending_solana_tokens(5000.0).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        let memecoin_filter = self.memecoin_filter.get().await;
+        let pairs = match self.dex_client.find_trending_solana_tokens(memecoin_filter.min_liquidity_usd).await {
             Ok(pairs) => {
                 log::info!("📊 Found {} memecoin pairs to analyze via Jupiter", pairs.len());
                 pairs
             }
             Err(error_msg) => {
-                // error_msg is now String (Send-safe)
                 log::warn!("⚠️ Could not fetch pairs: {}. Using PumpFun fallback.", error_msg);
                 // Fallback to PumpFun
-                let launches = self.pumpfun_client.get_recent_launches(20).await
-                    .map_err(|e| format!("PumpFun error: {}", e))?;
+                let launches = self.pumpfun_client.get_recent_launches(20).await?;
                 return self.analyze_jupiter_memecoin_opportunities_from_launches(launches).await;
             }
         };
@@ -929,26 +1484,36 @@ impl SpecializedProvider {
             let h1_change = pair.price_change.h1;
             
             // Memecoin criteria: decent liquidity, volume, and momentum
-            if liquidity < 5000.0 || volume_24h < 1000.0 {
-                continue; // Skip low liquidity/volume tokens
+            if volume_24h < 1000.0 {
+                continue; // Skip low volume tokens
+            }
+            let profile = TokenProfile {
+                age_hours: pair.pair_created_at.map(|ms| (Utc::now().timestamp() - ms / 1000).max(0) as f64 / 3600.0),
+                ..TokenProfile::default()
+            };
+            let market = QuickProfitPriceData {
+                price: pair.price_usd.as_ref().and_then(|p| p.parse().ok()).unwrap_or(0.0),
+                m5_change,
+                h1_change,
+                liquidity,
+                volume_24h,
+                buys: pair.txns.m5.buys,
+                sells: pair.txns.m5.sells,
+            };
+            if let Some(reason) = memecoin_filter.rejection_reason(Some(&market), &profile) {
+                log::info!("🚫 Skipping {} - memecoin filter: {}", pair.base_token.symbol, reason);
+                continue;
             }
             
             // Check if token is tradeable via Jupiter
             let token_mint = &pair.base_token.address;
-            // Convert Box<dyn Error> to String to ensure Send trait compatibility
-            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, token_mint).await
-                .map_err(|e| format!("{}", e)) // Convert to String immediately
-            {
+            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, token_mint).await {
                 Ok(true) => true,
                 Ok(false) => {
                     log::debug!("Token {} not supported by Jupiter, skipping", pair.base_token.symbol);
                     false
                 }
-                Err(_error_msg) => {
-                    // error_msg is now String (Send-safe), but we don't need it here
-                    // Assume tradeable if check fails
-                    true
-                }
+                Err(_) => true, // Assume tradeable if check fails
             };
             
             if !is_tradeable {
@@ -963,23 +1528,21 @@ impl SpecializedProvider {
             if price_usd <= 0.0 {
                 continue;
             }
+            self.record_scan_price(&pair.base_token.symbol, price_usd).await;
             
             // Calculate swap amount (0.1 SOL worth)
             let sol_amount = 100_000_000; // 0.1 SOL in lamports
-            // Convert Box<dyn Error> to String to ensure Send trait compatibility
-            let quote = match self.jupiter_client.get_quote(sol_mint, token_mint, sol_amount, 100).await
-                .map_err(|e| format!("{}", e)) // Convert to String immediately
-            {
+            let quote = match self.jupiter_client.get_quote(sol_mint, token_mint, sol_amount, 100).await {
                 Ok(q) => q,
                 Err(error_msg) => {
-                    // error_msg is now String (Send-safe)
                     log::debug!("Could not get Jupiter quote for {}: {}", pair.base_token.symbol, error_msg);
                     continue;
                 }
             };
             
-            // Check price impact (skip if too high)
-            if quote.price_impact_pct > 5.0 {
+            // Size the order to the slippage cap (skip if even the minimum size is too expensive)
+            let signal_symbol = symbol_registry::venue_symbol(&pair.base_token.symbol, symbol_registry::JUPITER_VENUE);
+            if self.slippage_sized_amount(&signal_symbol, &quote, 5.0).await.is_none() {
                 log::debug!("Price impact too high ({:.1}%) for {}, skipping", quote.price_impact_pct, pair.base_token.symbol);
                 continue;
             }
@@ -1020,7 +1583,7 @@ impl SpecializedProvider {
                 // Profit target: 5-8% for memecoins
                 let profit_target_pct = if m5_change > 3.0 { 8.0 } else { 5.0 };
                 let target_price = expected_price * (1.0 + profit_target_pct / 100.0);
-                let stop_loss = expected_price * (1.0 - 3.0 / 100.0); // 3% stop loss
+                let stop_loss = self.volatility_stop(&pair.base_token.symbol, expected_price, true, 3.0).await; // ATR stop, 3% until measured
                 
                 // ENHANCED: Confidence with market sentiment adjustment
                 let base_confidence = 0.60 +
@@ -1039,7 +1602,7 @@ impl SpecializedProvider {
                 let signal = TradingSignalData {
                     id: uuid::Uuid::new_v4().to_string(),
                     provider: self.provider_id.clone(),
-                    symbol: format!("{} (Jupiter)", pair.base_token.symbol),
+                    symbol: signal_symbol,
                     action: SignalAction::Buy,
                     entry_price: expected_price,
                     target_price,
@@ -1062,6 +1625,8 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + 900, // 15 minutes
                     price: 25.0, // Premium for Jupiter-executable signals
                     status: SignalStatus::Active,
+                    mint: Some(token_mint.clone()),
+                    ..Default::default()
                 };
                 
                 signals.push(signal);
@@ -1078,7 +1643,7 @@ impl SpecializedProvider {
     async fn analyze_jupiter_memecoin_opportunities_from_launches(
         &self,
         launches: Vec<crate::pumpfun::TokenLaunch>,
-    ) -> Result<Vec<TradingSignalData>, String> {
+    ) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
         let sol_mint = "So11111111111111111111111111111111111111112";
         
@@ -1086,10 +1651,7 @@ impl SpecializedProvider {
         
         for launch in launches {
             // Check if token is tradeable via Jupiter
-            // Convert Box<dyn Error> to String to ensure Send trait compatibility
-            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, &launch.mint).await
-                .map_err(|e| format!("{}", e)) // Convert to String immediately
-            {
+            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, &launch.mint).await {
                 Ok(true) => true,
                 Ok(false) => false,
                 Err(_) => true, // Assume tradeable if check fails
@@ -1105,11 +1667,9 @@ impl SpecializedProvider {
             // Only high sentiment memecoins
             if sentiment.sentiment_score > 70.0 && !matches!(sentiment.risk_level, crate::pumpfun::RiskLevel::Extreme) {
                 let sol_amount = 100_000_000; // 0.1 SOL
-                // Convert Box<dyn Error> to String to ensure Send trait compatibility
-                if let Ok(quote) = self.jupiter_client.get_quote(sol_mint, &launch.mint, sol_amount, 100).await
-                    .map_err(|e| format!("{}", e)) // Convert to String immediately
-                {
-                    if quote.price_impact_pct < 5.0 {
+                    if let Ok(quote) = self.jupiter_client.get_quote(sol_mint, &launch.mint, sol_amount, 100).await {
+                    let signal_symbol = symbol_registry::venue_symbol(&launch.symbol, symbol_registry::JUPITER_VENUE);
+                    if self.slippage_sized_amount(&signal_symbol, &quote, 5.0).await.is_some() {
                         let out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0);
                         let entry_price = if out_amount > 0.0 {
                             (sol_amount as f64) / out_amount
@@ -1120,7 +1680,7 @@ impl SpecializedProvider {
                         let signal = TradingSignalData {
                             id: uuid::Uuid::new_v4().to_string(),
                             provider: self.provider_id.clone(),
-                            symbol: format!("{} (Jupiter)", launch.symbol),
+                            symbol: signal_symbol,
                             action: SignalAction::Buy,
                             entry_price,
                             target_price: entry_price * 1.08,
@@ -1136,6 +1696,8 @@ impl SpecializedProvider {
                             expiry: Utc::now().timestamp() + 1200,
                             price: 25.0,
                             status: SignalStatus::Active,
+                            mint: Some(launch.mint.clone()),
+                            ..Default::default()
                         };
                         
                         signals.push(signal);
@@ -1152,7 +1714,7 @@ impl SpecializedProvider {
     /// Discovers tokens dynamically from DEX Screener and validates via Switchboard Oracle
     /// Focuses on established tokens with high liquidity and lower risk
     /// Provides market context for Memecoin Trader
-    async fn generate_jupiter_bluechip_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_jupiter_bluechip_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         log::info!("💎 [ENHANCED] Jupiter Blue Chip Trader: Discovering tokens >10M market cap with oracle feed validation...");
@@ -1175,10 +1737,7 @@ impl SpecializedProvider {
         let mut discovered_tokens: Vec<(String, String, f64)> = Vec::new(); // (symbol, mint, market_cap)
         
         log::info!("🔍 Discovering tokens with market cap > 10M from DEX Screener...");
-        // Convert Box<dyn Error> to String to ensure Send trait compatibility
-        match self.dex_client.find_trending_solana_tokens(10000.0).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        match self.dex_client.find_trending_solana_tokens(10000.0).await {
             Ok(pairs) => {
                 for pair in pairs {
                     // Use FDV (Fully Diluted Valuation) as market cap proxy
@@ -1203,7 +1762,6 @@ impl SpecializedProvider {
                 log::info!("📊 Discovered {} tokens with market cap > 10M", discovered_tokens.len());
             }
             Err(error_msg) => {
-                // error_msg is now String (Send-safe)
                 log::warn!("Could not discover tokens from DEX Screener: {}", error_msg);
             }
         }
@@ -1220,16 +1778,12 @@ impl SpecializedProvider {
         
         // Get oracle data for all tokens (try to get feeds for as many as possible)
         let oracle_symbols: Vec<String> = all_tokens.iter()
-            .map(|(sym, _, _)| format!("{}/USD", sym))
+            .map(|(sym, _, _)| symbol_registry::canonical(sym))
             .collect();
         
-        // Convert Box<dyn Error> to String to ensure Send trait compatibility
-        let feeds = match self.oracle_client.fetch_multiple_feeds(&oracle_symbols).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        let feeds = match self.oracle_client.fetch_multiple_feeds(&oracle_symbols).await {
             Ok(f) => f,
             Err(error_msg) => {
-                // error_msg is now String (Send-safe)
                 log::warn!("Could not fetch oracle data: {}", error_msg);
                 Vec::new() // Continue with empty feeds - will skip tokens without oracle data
             }
@@ -1247,7 +1801,7 @@ impl SpecializedProvider {
             
             // ENHANCED: Require oracle feed for all tokens (increased trading opportunities with validation)
             let feed_opt = feeds.iter()
-                .find(|f| f.symbol.starts_with(&symbol) || f.symbol.contains(&symbol));
+                .find(|f| symbol_registry::same_market(&f.symbol, &symbol));
             
             let (current_price, price_change_24h, feed) = if let Some(f) = feed_opt {
                 (f.price, f.price_change_24h.unwrap_or(0.0), f)
@@ -1257,6 +1811,7 @@ impl SpecializedProvider {
                 log::debug!("⚠️ No oracle feed found for {}, skipping (oracle feed required for blue chip trading)", symbol);
                 continue;
             };
+            self.record_scan_price(&symbol, current_price).await;
             
             // ENHANCED: Market cap validation for discovered tokens
             if let Some(market_cap) = market_cap_opt {
@@ -1268,18 +1823,14 @@ impl SpecializedProvider {
             }
             
             // Check if tradeable via Jupiter
-            // Convert Box<dyn Error> to String to ensure Send trait compatibility
             let symbol_for_log = symbol.clone(); // Clone for logging
-            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, mint_address.as_str()).await
-                .map_err(|e| format!("{}", e)) // Convert to String immediately
-            {
+            let is_tradeable = match self.jupiter_client.is_pair_supported(sol_mint, mint_address.as_str()).await {
                 Ok(true) => true,
                 Ok(false) => {
                     log::debug!("{} not supported by Jupiter, skipping", symbol_for_log);
                     false
                 }
                 Err(error_msg) => {
-                    // error_msg is now String (Send-safe)
                     log::warn!("Could not verify Jupiter support for {}: {}", symbol_for_log, error_msg);
                     true
                 }
@@ -1291,20 +1842,17 @@ impl SpecializedProvider {
             
             // Get Jupiter quote for validation
             let swap_amount = 1_000_000_000; // 1 SOL in lamports
-            // Convert Box<dyn Error> to String to ensure Send trait compatibility
-            let quote = match self.jupiter_client.get_quote(sol_mint, mint_address.as_str(), swap_amount, 50).await
-                .map_err(|e| format!("{}", e)) // Convert to String immediately
-            {
+            let quote = match self.jupiter_client.get_quote(sol_mint, mint_address.as_str(), swap_amount, 50).await {
                 Ok(q) => q,
                 Err(error_msg) => {
-                    // error_msg is now String (Send-safe)
                     log::debug!("Could not get Jupiter quote for {}: {}", symbol, error_msg);
                     continue;
                 }
             };
             
             // Blue chips should have very low price impact
-            if quote.price_impact_pct > 1.0 {
+            let signal_symbol = symbol_registry::venue_symbol(&symbol, symbol_registry::JUPITER_VENUE);
+            if self.slippage_sized_amount(&signal_symbol, &quote, 1.0).await.is_none() {
                 log::debug!("Price impact too high ({:.2}%) for {}, skipping", quote.price_impact_pct, symbol);
                 continue;
             }
@@ -1349,11 +1897,8 @@ impl SpecializedProvider {
                     expected_price * (1.0 - profit_target_pct / 100.0)
                 };
                 
-                let stop_loss = if matches!(action, SignalAction::Buy) {
-                    expected_price * 0.98 // 2% stop loss
-                } else {
-                    expected_price * 1.02
-                };
+                // ATR stop, 2% until enough scans to measure volatility
+                let stop_loss = self.volatility_stop(&symbol, expected_price, matches!(action, SignalAction::Buy), 2.0).await;
                 
                 // ENHANCED: Higher confidence with trend confirmation
                 let base_confidence = 0.75 +
@@ -1369,7 +1914,7 @@ impl SpecializedProvider {
                 let signal = TradingSignalData {
                     id: uuid::Uuid::new_v4().to_string(),
                     provider: self.provider_id.clone(),
-                    symbol: format!("{} (Jupiter)", symbol),
+                    symbol: signal_symbol,
                     action,
                     entry_price: expected_price,
                     target_price,
@@ -1394,6 +1939,8 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + 7200, // 2 hours
                     price: 20.0, // Premium for blue chip signals
                     status: SignalStatus::Active,
+                    mint: Some(mint_address.clone()),
+                    ..Default::default()
                 };
 
                 signals.push(signal);
@@ -1407,14 +1954,11 @@ impl SpecializedProvider {
     }
 
     /// Provider 5: Opportunity Analyzer - Analyzes all trading opportunities
-    async fn generate_opportunity_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_opportunity_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         // Get DEX opportunities - gracefully handle API failures
-        // Convert Box<dyn Error> to String to ensure Send trait compatibility
-        let opportunities = match self.dex_client.get_top_opportunities(10).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        let opportunities = match self.dex_client.get_top_opportunities(10).await {
             Ok(opps) => {
                 if opps.is_empty() {
                     log::debug!("No DEX opportunities found (Mobula API may be unavailable). Continuing with other signals.");
@@ -1453,6 +1997,8 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + 14400, // 4 hours
                     price: 20.0,
                     status: SignalStatus::Active,
+                    mint: Some(opp.token_address.clone()),
+                    ..Default::default()
                 };
 
                 signals.push(signal);
@@ -1463,7 +2009,7 @@ impl SpecializedProvider {
     }
 
     /// Provider 6: Signal Trader - Buys/sells signals from other providers
-    async fn trade_signals(&self) -> Result<(), String> {
+    async fn trade_signals(&self) -> Result<(), AppError> {
         // Get all active signals from marketplace
         let active_signals = self.marketplace.get_active_signals().await;
 
@@ -1513,13 +2059,7 @@ impl SpecializedProvider {
         // 6. Signal diversification (avoid too many signals on same symbol)
         
         let time_remaining = signal.expiry - Utc::now().timestamp();
-        let potential_profit = (signal.target_price - signal.entry_price).abs();
-        let potential_loss = (signal.entry_price - signal.stop_loss).abs();
-        let risk_reward = if potential_loss > 0.0 {
-            potential_profit / potential_loss
-        } else {
-            0.0
-        };
+        let risk_reward = signal.risk_reward();
         
         // ENHANCED: Value score (confidence * risk_reward / price)
         let value_score = (signal.confidence * risk_reward) / signal.price.max(1.0);
@@ -1537,27 +2077,31 @@ impl SpecializedProvider {
     }
 
     /// Generate meta-signals based on purchased signals
-    async fn generate_meta_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_meta_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         // Analyze patterns from marketplace
         let active_signals = self.marketplace.get_active_signals().await;
         
         // Find consensus signals (multiple providers agreeing)
-        let mut symbol_votes: std::collections::HashMap<String, (usize, Vec<&TradingSignalData>)> = 
+        let mut symbol_votes: std::collections::HashMap<String, (Vec<String>, Vec<&TradingSignalData>)> = 
             std::collections::HashMap::new();
 
         for signal in &active_signals {
             if signal.provider != self.provider_id {
-                let entry = symbol_votes.entry(signal.symbol.clone()).or_insert((0, Vec::new()));
-                entry.0 += 1;
+                let entry = symbol_votes.entry(signal.symbol.clone()).or_insert((Vec::new(), Vec::new()));
+                if !entry.0.contains(&signal.provider) {
+                    entry.0.push(signal.provider.clone());
+                }
                 entry.1.push(signal);
             }
         }
 
-        // Generate meta-signals for symbols with consensus (3+ providers)
-        for (symbol, (count, provider_signals)) in symbol_votes {
-            if count >= 3 {
+        // Generate meta-signals for symbols with reputation-weighted consensus
+        for (symbol, (voters, provider_signals)) in symbol_votes {
+            let consensus = self.consensus_config.evaluate(&self.provider_reputations(&voters).await);
+            if consensus.reached {
+                let count = voters.len();
                 let avg_confidence: f64 = provider_signals.iter()
                     .map(|s| s.confidence)
                     .sum::<f64>() / provider_signals.len() as f64;
@@ -1581,13 +2125,14 @@ impl SpecializedProvider {
                         "Signal Consensus".to_string(),
                     ],
                     analysis: format!(
-                        "Consensus signal: {} - {} providers agree, Avg confidence: {:.1}%",
-                        symbol, count, avg_confidence * 100.0
+                        "Consensus signal: {} - {} providers agree ({}), Avg confidence: {:.1}%",
+                        symbol, count, consensus.summary(), avg_confidence * 100.0
                     ),
                     timestamp: Utc::now().timestamp(),
                     expiry: Utc::now().timestamp() + 21600, // 6 hours
                     price: 30.0, // Premium for consensus signals
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
 
                 signals.push(signal);
@@ -1597,36 +2142,45 @@ impl SpecializedProvider {
         Ok(signals)
     }
 
+    /// Price the regime benchmarks and classify the market from their move across this provider's recent
+    /// scans (about an hour at the Master Analyzer's interval) - NEUTRAL until a benchmark has two scans
+    async fn classify_market_regime(&self) -> MarketRegime {
+        let benchmarks: Vec<String> = match self.market_regime {
+            Some(ref state) => state.benchmarks().to_vec(),
+            None => DEFAULT_REGIME_BENCHMARKS.iter().map(|symbol| symbol.to_string()).collect(),
+        };
+        for feed in self.oracle_client.fetch_multiple_feeds(&benchmarks).await.unwrap_or_default() {
+            if !feed.stale {
+                self.record_scan_price(&feed.symbol, feed.price).await;
+            }
+        }
+        
+        let benchmark_changes: Vec<(String, f64)> = {
+            let scan_prices = self.scan_prices.lock().await;
+            benchmarks.iter().filter_map(|symbol| {
+                let history = scan_prices.get(&symbol_registry::canonical(symbol))?;
+                let (first, last) = (*history.front()?, *history.back()?);
+                (history.len() >= 2).then(|| (symbol.clone(), (last - first) / first * 100.0))
+            }).collect()
+        };
+        let regime = match self.market_regime {
+            // Published for the other providers' regime gating and /market/regime
+            Some(ref state) => state.update(benchmark_changes).await,
+            None => average_change_pct(&benchmark_changes)
+                .map(|change| MarketRegime::from_market_change(change, DEFAULT_REGIME_THRESHOLD_PCT)),
+        };
+        regime.unwrap_or(MarketRegime::Neutral)
+    }
+
     /// Provider 7: Master Analyzer - ENHANCED with pattern recognition, market regime detection, and predictive analytics
-    async fn generate_master_analysis_signals(&self) -> Result<Vec<TradingSignalData>, String> {
+    async fn generate_master_analysis_signals(&self) -> Result<Vec<TradingSignalData>, AppError> {
         let mut signals = Vec::new();
 
         // Get all active signals from marketplace
         let active_signals = self.marketplace.get_active_signals().await;
         
-        // ENHANCED: Market regime detection
-        let market_regime = {
-            let buy_count = active_signals.iter()
-                .filter(|s| matches!(s.action, SignalAction::Buy))
-                .count();
-            let _sell_count = active_signals.iter()
-                .filter(|s| matches!(s.action, SignalAction::Sell))
-                .count();
-            let total = active_signals.len();
-            
-            if total == 0 {
-                "NEUTRAL".to_string()
-            } else {
-                let buy_ratio = buy_count as f64 / total as f64;
-                if buy_ratio > 0.6 {
-                    "BULLISH".to_string()
-                } else if buy_ratio < 0.4 {
-                    "BEARISH".to_string()
-                } else {
-                    "NEUTRAL".to_string()
-                }
-            }
-        };
+        // ENHANCED: Market regime detection from benchmark prices
+        let market_regime = self.classify_market_regime().await;
 
         // Get all provider statistics
         let provider_ids = vec![
@@ -1666,6 +2220,8 @@ impl SpecializedProvider {
                 avg_stop_loss: 0.0,
                 data_sources: Vec::new(),
                 providers: Vec::new(),
+                buy_providers: Vec::new(),
+                sell_providers: Vec::new(),
             });
 
             entry.provider_count += 1;
@@ -1682,8 +2238,18 @@ impl SpecializedProvider {
             }
 
             match signal.action {
-                SignalAction::Buy => entry.buy_signals += 1,
-                SignalAction::Sell => entry.sell_signals += 1,
+                SignalAction::Buy => {
+                    entry.buy_signals += 1;
+                    if !entry.buy_providers.contains(&signal.provider) {
+                        entry.buy_providers.push(signal.provider.clone());
+                    }
+                }
+                SignalAction::Sell => {
+                    entry.sell_signals += 1;
+                    if !entry.sell_providers.contains(&signal.provider) {
+                        entry.sell_providers.push(signal.provider.clone());
+                    }
+                }
                 _ => {}
             }
         }
@@ -1698,7 +2264,7 @@ impl SpecializedProvider {
             let avg_stop = analysis.avg_stop_loss / count;
 
             // Master signal criteria:
-            // 1. Multiple providers (2+) agree
+            // 1. Reputation-weighted consensus among the providers on the majority side
             // 2. Strong directional bias (75%+ agreement)
             // 3. High average confidence (>65%)
             let total_directional = analysis.buy_signals + analysis.sell_signals;
@@ -1708,7 +2274,14 @@ impl SpecializedProvider {
                 0.0
             };
 
-            if analysis.provider_count >= 2 && directional_strength >= 0.75 && avg_confidence > 0.65 {
+            let majority_providers = if analysis.buy_signals >= analysis.sell_signals {
+                &analysis.buy_providers
+            } else {
+                &analysis.sell_providers
+            };
+            let consensus = self.consensus_config.evaluate(&self.provider_reputations(majority_providers).await);
+
+            if consensus.reached && directional_strength >= 0.75 && avg_confidence > 0.65 {
                 let action = if analysis.buy_signals > analysis.sell_signals {
                     SignalAction::Buy
                 } else {
@@ -1737,10 +2310,10 @@ impl SpecializedProvider {
                 
                 // ENHANCED: Market regime bonus/penalty
                 let action_for_regime = action.clone();
-                let regime_bonus = match market_regime.as_str() {
-                    "BULLISH" => if matches!(action_for_regime, SignalAction::Buy) { 0.05 } else { -0.05 },
-                    "BEARISH" => if matches!(action_for_regime, SignalAction::Sell) { 0.05 } else { -0.05 },
-                    _ => 0.0,
+                let regime_bonus = match market_regime {
+                    MarketRegime::Bullish => if matches!(action_for_regime, SignalAction::Buy) { 0.05 } else { -0.05 },
+                    MarketRegime::Bearish => if matches!(action_for_regime, SignalAction::Sell) { 0.05 } else { -0.05 },
+                    MarketRegime::Neutral => 0.0,
                 };
                 
                 // ENHANCED: Pattern recognition bonus
@@ -1803,10 +2376,11 @@ impl SpecializedProvider {
                     timeframe: "8h".to_string(),
                     data_sources: analysis.data_sources.clone(),
                     analysis: format!(
-                        "[ENHANCED] MASTER ANALYSIS: {} - {} providers ({}) | Confidence: {:.1}% | Directional: {:.0}% | Market: {} | Pattern: {} | Sources: {}. {} | ✅ Profitability: {:.1}% profit, {:.2}x risk/reward, {:.1}% margin",
+                        "[ENHANCED] MASTER ANALYSIS: {} - {} providers ({}) | Consensus: {} | Confidence: {:.1}% | Directional: {:.0}% | Market: {} | Pattern: {} | Sources: {}. {} | ✅ Profitability: {:.1}% profit, {:.2}x risk/reward, {:.1}% margin",
                         symbol,
                         analysis.provider_count,
                         analysis.providers.join(", "),
+                        consensus.summary(),
                         master_confidence * 100.0,
                         directional_strength * 100.0,
                         market_regime,
@@ -1821,6 +2395,7 @@ impl SpecializedProvider {
                     expiry: Utc::now().timestamp() + 28800, // 8 hours
                     price: 40.0, // Premium for master analysis
                     status: SignalStatus::Active,
+                    ..Default::default()
                 };
 
                 signals.push(signal);
@@ -1971,26 +2546,18 @@ impl SpecializedProvider {
         }
     }
 
-    /// Validate signal with oracle data
+    /// Validate signal with oracle data - memecoins without a feed are checked against their DEX pool
     async fn validate_with_oracle_data(&self, symbol: &str) -> String {
-        // Try to get oracle data for the symbol
-        let oracle_symbol = if symbol.contains("/") {
-            symbol.to_string()
-        } else {
-            format!("{}/USD", symbol)
-        };
-
-        // Convert Box<dyn Error + Send + Sync> to String to ensure Send trait compatibility
-        match self.oracle_client.fetch_price(&oracle_symbol).await
-            .map_err(|e| format!("{}", e)) // Convert to String immediately
-        {
+        match self.oracle_client.fetch_price_or_pool(symbol).await {
+            Ok(feed) if feed.feed_address.starts_with("dexscreener:") => {
+                format!("Oracle validation: ${:.8} from the DEX Screener pool (no oracle feed)", feed.price)
+            }
             Ok(feed) => {
                 format!("Oracle validation: ${:.2} (confidence: {:.1}%)", 
                     feed.price, feed.confidence * 100.0)
             }
-            Err(_error_msg) => {
-                // error_msg is now String (Send-safe)
-                "Oracle validation: N/A".to_string()
+            Err(_) => {
+                "Oracle validation: unverified - no oracle feed or DEX pool prices this token".to_string()
             }
         }
     }
@@ -2057,6 +2624,7 @@ impl SpecializedProvider {
                 expiry: Utc::now().timestamp() + 43200, // 12 hours
                 price: 50.0, // Premium for market-wide insights
                 status: SignalStatus::Active,
+                ..Default::default()
             };
 
             Ok(Some(signal))
@@ -2076,39 +2644,21 @@ impl SpecializedProvider {
         
         let reward = RLAgent::calculate_reward(entry_price, exit_price, action, confidence);
         
-        let experience = Experience {
-            state: MarketState {
-                symbol: symbol.clone(),
-                price: entry_price,
-                volume: 0.0,
-                price_change_1h: 0.0,
-                price_change_24h: 0.0,
-                sentiment_score: confidence * 100.0,
-                liquidity: 0.0,
-                volatility: 0.0,
-                market_cap: None,
-            },
-            action: Action {
+        let experience = Experience::new(
+            &self.provider_id,
+            MarketState::at_price(&symbol, entry_price, confidence),
+            Action {
                 action_type: action.to_string(),
                 confidence,
                 size: 0.05,
                 price: entry_price,
             },
             reward,
-            next_state: Some(MarketState {
-                symbol: symbol.clone(),
-                price: exit_price,
-                volume: 0.0,
+            Some(MarketState {
                 price_change_1h: ((exit_price - entry_price) / entry_price) * 100.0,
-                price_change_24h: 0.0,
-                sentiment_score: confidence * 100.0,
-                liquidity: 0.0,
-                volatility: 0.0,
-                market_cap: None,
+                ..MarketState::at_price(&symbol, exit_price, confidence)
             }),
-            timestamp: Utc::now().timestamp(),
-            provider_id: self.provider_id.clone(),
-        };
+        );
         
         self.rl_agent.record_experience(experience).await;
         log::info!("🧠 {} learned from {} trade: reward={:.3}", self.provider_name, symbol, reward);
@@ -2128,6 +2678,8 @@ struct SymbolAnalysis {
     avg_stop_loss: f64,
     data_sources: Vec<String>,
     providers: Vec<String>,
+    buy_providers: Vec<String>,
+    sell_providers: Vec<String>,
 }
 
 /// Initialize all 6 specialized providers
@@ -2173,6 +2725,15 @@ pub async fn initialize_all_providers(
         ),
     ];
 
+    // Per-provider cadence overrides (PROVIDER_CHECK_INTERVALS=memecoin_monitor:10,...)
+    let check_intervals = match std::env::var("PROVIDER_CHECK_INTERVALS") {
+        Ok(value) => parse_check_intervals(&value).unwrap_or_else(|e| {
+            log::error!("❌ Invalid PROVIDER_CHECK_INTERVALS ({}) - using each provider's default", e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(),
+    };
+
     let mut provider_agents = Vec::new();
 
     for (id, name, provider_type) in providers {
@@ -2183,13 +2744,16 @@ pub async fn initialize_all_providers(
             log::info!("✅ Registered provider: {}", name);
         }
 
+        let check_interval = check_intervals.get(&id).copied()
+            .unwrap_or_else(|| provider_type.default_check_interval_secs());
+        log::info!("⏱️ {} checks every {}s", name, check_interval);
         let provider = SpecializedProvider::new(
             id,
             name,
             provider_type,
             marketplace.clone(),
             rpc_url.clone(),
-        );
+        ).with_check_interval(check_interval);
 
         provider_agents.push(provider);
     }
@@ -2214,4 +2778,137 @@ mod tests {
         ];
         assert_eq!(types.len(), 7);
     }
+
+    #[tokio::test]
+    async fn test_check_interval_overrides() {
+        let intervals = parse_check_intervals("memecoin_monitor:10, oracle_monitor:180").unwrap();
+        assert_eq!(intervals.get("memecoin_monitor"), Some(&10));
+        assert_eq!(intervals.get("oracle_monitor"), Some(&180));
+        assert!(parse_check_intervals("memecoin_monitor:1").is_err());
+        assert!(parse_check_intervals("memecoin_monitor").is_err());
+
+        assert!(ProviderType::MemecoinMonitor.default_check_interval_secs()
+            < ProviderType::OracleMonitor.default_check_interval_secs());
+        let rpc_url = "https://api.devnet.solana.com".to_string();
+        let provider = SpecializedProvider::new(
+            "memecoin_monitor".to_string(),
+            "Memecoin Monitor".to_string(),
+            ProviderType::MemecoinMonitor,
+            Arc::new(SignalMarketplace::new(rpc_url.clone())),
+            rpc_url,
+        ).with_check_interval(1);
+        assert_eq!(provider.check_interval_secs(), crate::provider_registry::MIN_CHECK_INTERVAL_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_stops_follow_scan_volatility() {
+        let rpc_url = "https://api.devnet.solana.com".to_string();
+        let provider = SpecializedProvider::new(
+            "test_provider".to_string(),
+            "Test Provider".to_string(),
+            ProviderType::OracleMonitor,
+            Arc::new(SignalMarketplace::new(rpc_url.clone())),
+            rpc_url,
+        );
+
+        // Not enough scans yet - fixed fallback stop
+        provider.record_scan_price("BONK", 100.0).await;
+        assert!((provider.volatility_stop("BONK", 100.0, true, 3.0).await - 97.0).abs() < 1e-9);
+
+        // Swings of ~2% per scan: 2 ATRs below a SOL-denominated entry of 0.5
+        for i in 0..STOP_PRICE_HISTORY_LEN {
+            provider.record_scan_price("BONK", if i % 2 == 0 { 102.0 } else { 100.0 }).await;
+        }
+        let long_stop = provider.volatility_stop("BONK", 0.5, true, 3.0).await;
+        let expected_distance = 0.5 * 2.0 / 100.0 * provider.atr_stop_multiplier;
+        assert!((long_stop - (0.5 - expected_distance)).abs() < 1e-9);
+        let short_stop = provider.volatility_stop("BONK", 0.5, false, 3.0).await;
+        assert!((short_stop - (0.5 + expected_distance)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_memecoin_filter_gates() {
+        let market = |liquidity: f64, volume_24h: f64| QuickProfitPriceData {
+            price: 0.001,
+            m5_change: 2.5,
+            h1_change: 4.0,
+            liquidity,
+            volume_24h,
+            buys: 12,
+            sells: 4,
+        };
+        let filter = MemecoinFilterConfig::default();
+        let unknown = TokenProfile::default();
+
+        // Liquidity floor drops while volume spikes (defaults match the old 5000 / 3000)
+        assert!(filter.rejection_reason(Some(&market(4000.0, 8000.0)), &unknown).is_some());
+        assert!(filter.rejection_reason(Some(&market(4000.0, 20000.0)), &unknown).is_none());
+        assert!(filter.rejection_reason(None, &unknown).is_none());
+
+        let strict = MemecoinFilterConfig {
+            min_token_age_hours: 2.0,
+            min_holder_count: 100,
+            max_top_holder_pct: 20.0,
+            ..filter
+        };
+        let launch = crate::pumpfun::TokenLaunch {
+            mint: "mint".to_string(),
+            name: "Young".to_string(),
+            symbol: "YNG".to_string(),
+            uri: String::new(),
+            creator: String::new(),
+            created_timestamp: 0,
+            market_cap: 50000.0,
+            reply_count: 10,
+            is_currently_live: true,
+            king_of_the_hill_timestamp: None,
+            bonding_curve: "curve".to_string(),
+            holder_count: Some(250),
+            top_holder_pct: Some(35.0),
+        };
+        let now = 1_700_000_000;
+        let young = TokenProfile::new(&launch, Some((now - 1800) * 1000), now);
+        assert_eq!(young.age_hours, Some(0.5));
+        assert!(strict.rejection_reason(Some(&market(50000.0, 8000.0)), &young).unwrap().contains("age"));
+
+        let aged = TokenProfile::new(&launch, Some((now - 3 * 3600) * 1000), now);
+        assert!(strict.rejection_reason(Some(&market(50000.0, 8000.0)), &aged).unwrap().contains("top holder"));
+        let spread = TokenProfile { top_holder_pct: Some(10.0), ..aged };
+        assert!(strict.rejection_reason(Some(&market(50000.0, 8000.0)), &spread).is_none());
+
+        // Missing holder data passes unless unknowns are refused
+        let no_holders = TokenProfile { holder_count: None, top_holder_pct: None, ..aged };
+        assert!(strict.rejection_reason(Some(&market(50000.0, 8000.0)), &no_holders).is_none());
+        let refuse_unknown = MemecoinFilterConfig { reject_unknown: true, ..strict };
+        assert!(refuse_unknown.rejection_reason(Some(&market(50000.0, 8000.0)), &no_holders).unwrap().contains("unknown"));
+
+        // Analysis honours the gates too
+        let sentiment = crate::pumpfun::PumpFunClient::new().analyze_sentiment(&launch);
+        assert!(SpecializedProvider::analyze_quick_profit_opportunity(
+            &launch, &sentiment, Some(market(50000.0, 8000.0)), 100.0, &young, &strict,
+        ).is_none());
+        assert!(MemecoinFilterConfig { max_top_holder_pct: 120.0, ..filter }.validate().is_err());
+    }
+
+    #[test]
+    fn test_consensus_weights_reputation() {
+        let config = ConsensusConfig::default();
+
+        // Three noisy providers don't outvote one proven provider
+        let noisy = config.evaluate(&[20.0, 20.0, 20.0]);
+        assert!(!noisy.reached);
+        assert!((noisy.weighted_score - 1.2).abs() < 1e-9);
+
+        let proven = config.evaluate(&[95.0, 60.0]);
+        assert!(proven.reached);
+        assert!(proven.weighted_score > noisy.weighted_score);
+        assert!((proven.strength() - 1.0333).abs() < 1e-3);
+        assert_eq!(proven.summary(), "weighted 3.1/3.0 (1.03x) from 2 providers");
+
+        // Three baseline providers are the old "3+ agree" rule
+        assert!(config.evaluate(&[50.0, 50.0, 50.0]).reached);
+        // One provider is never a consensus, however reputable
+        assert!(!ConsensusConfig { min_weighted_score: 1.0, ..config }.evaluate(&[100.0]).reached);
+        assert!(ConsensusConfig { baseline_reputation: 0.0, ..config }.validate().is_err());
+    }
 }
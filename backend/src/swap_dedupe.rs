@@ -0,0 +1,169 @@
+//! Swap Idempotency
+//! Guarantees one on-chain swap per signal, even when the executor retries after a network hiccup
+//! Complements `try_mark_executing`: that lock covers the marketplace status, this covers the
+//! window between submission and on-chain confirmation
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::trading_engine::TradingSignal;
+
+/// How long submitted keys are remembered
+pub const DEFAULT_SWAP_KEY_TTL_SECS: i64 = 600;
+
+/// After this long a transaction that never showed up on-chain has an expired blockhash and can't land
+pub const BLOCKHASH_EXPIRY_SECS: i64 = 90;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SwapState {
+    InFlight,  // Submission started, no result yet
+    Submitted, // Executor returned a signature - outcome on-chain unknown until verified
+    Confirmed, // Verified on-chain
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapRecord {
+    pub key: String,
+    pub state: SwapState,
+    pub signature: Option<String>,
+    pub created_at: i64,
+}
+
+/// What to do before submitting a swap for a key
+#[derive(Debug, Clone, PartialEq)]
+pub enum SwapCheck {
+    Fresh,    // Never seen - submit
+    InFlight, // Another attempt is mid-submission - block
+    Submitted { signature: String, submitted_at: i64 }, // Ask the chain before deciding
+    Confirmed { signature: String }, // Already filled - block
+}
+
+/// Recently submitted swap keys with a TTL
+#[derive(Debug)]
+pub struct SwapDedupe {
+    records: HashMap<String, SwapRecord>,
+    ttl_secs: i64,
+}
+
+impl SwapDedupe {
+    pub fn new(ttl_secs: i64) -> Self {
+        Self {
+            records: HashMap::new(),
+            ttl_secs,
+        }
+    }
+
+    /// Load TTL from SWAP_DEDUPE_TTL_SECS
+    pub fn from_env() -> Self {
+        let ttl_secs = std::env::var("SWAP_DEDUPE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SWAP_KEY_TTL_SECS);
+        Self::new(ttl_secs)
+    }
+
+    /// Idempotency key: signal id plus a nonce (action + signal timestamp) that is stable across retries
+    /// Distinct executions of one signal (DCA chunks, closes) already carry their own ids
+    pub fn key_for(signal: &TradingSignal) -> String {
+        format!("{}:{:?}:{}", signal.id, signal.action, signal.timestamp)
+    }
+
+    fn purge_expired(&mut self, now: i64) {
+        let ttl = self.ttl_secs;
+        self.records.retain(|_, record| now - record.created_at < ttl);
+    }
+
+    pub fn check(&mut self, key: &str, now: i64) -> SwapCheck {
+        self.purge_expired(now);
+        match self.records.get(key) {
+            None => SwapCheck::Fresh,
+            Some(record) => match (&record.state, &record.signature) {
+                (SwapState::Confirmed, Some(signature)) => SwapCheck::Confirmed { signature: signature.clone() },
+                (SwapState::Submitted, Some(signature)) => SwapCheck::Submitted {
+                    signature: signature.clone(),
+                    submitted_at: record.created_at,
+                },
+                _ => SwapCheck::InFlight,
+            },
+        }
+    }
+
+    /// Claim the key right before submission
+    pub fn begin(&mut self, key: &str, now: i64) {
+        self.records.insert(key.to_string(), SwapRecord {
+            key: key.to_string(),
+            state: SwapState::InFlight,
+            signature: None,
+            created_at: now,
+        });
+    }
+
+    pub fn record_submitted(&mut self, key: &str, signature: &str, now: i64) {
+        self.records.insert(key.to_string(), SwapRecord {
+            key: key.to_string(),
+            state: SwapState::Submitted,
+            signature: Some(signature.to_string()),
+            created_at: now,
+        });
+    }
+
+    pub fn mark_confirmed(&mut self, key: &str) {
+        if let Some(record) = self.records.get_mut(key) {
+            record.state = SwapState::Confirmed;
+        }
+    }
+
+    /// Release the key so the signal may be retried (rejected before submission, or failed on-chain)
+    pub fn forget(&mut self, key: &str) {
+        self.records.remove(key);
+    }
+}
+
+impl Default for SwapDedupe {
+    fn default() -> Self {
+        Self::new(DEFAULT_SWAP_KEY_TTL_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trading_engine::TradeAction;
+
+    #[test]
+    fn test_swap_key_lifecycle() {
+        let signal = TradingSignal {
+            id: "sig1".to_string(),
+            action: TradeAction::Buy,
+            symbol: "SOL/USD".to_string(),
+            price: 100.0,
+            confidence: 0.9,
+            size: 1.0,
+            stop_loss: 95.0,
+            take_profit: 110.0,
+            timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+        let key = SwapDedupe::key_for(&signal);
+        assert_eq!(key, SwapDedupe::key_for(&signal.clone()));
+
+        let mut dedupe = SwapDedupe::new(600);
+        assert_eq!(dedupe.check(&key, 0), SwapCheck::Fresh);
+
+        dedupe.begin(&key, 0);
+        assert_eq!(dedupe.check(&key, 1), SwapCheck::InFlight);
+
+        dedupe.record_submitted(&key, "5sig", 2);
+        assert_eq!(dedupe.check(&key, 3), SwapCheck::Submitted { signature: "5sig".to_string(), submitted_at: 2 });
+
+        dedupe.mark_confirmed(&key);
+        assert_eq!(dedupe.check(&key, 4), SwapCheck::Confirmed { signature: "5sig".to_string() });
+
+        // TTL expiry releases the key
+        assert_eq!(dedupe.check(&key, 700), SwapCheck::Fresh);
+
+        dedupe.begin(&key, 800);
+        dedupe.forget(&key);
+        assert_eq!(dedupe.check(&key, 801), SwapCheck::Fresh);
+    }
+}
@@ -1,12 +1,92 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
+use crate::error_handling::AppError;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::Mutex;
 use solana_client::rpc_client::RpcClient;
 use reqwest;
 
+/// Default maximum quote age before a price is considered stale
+pub const DEFAULT_MAX_STALENESS_SECS: i64 = 60;
+/// Sources further than this from the median (percent) are dropped from the consensus price
+pub const DEFAULT_ORACLE_OUTLIER_PCT: f64 = 2.0;
+/// Consensus dispersion (percent) above which the auto-executor refuses to buy
+pub const DEFAULT_MAX_ORACLE_DISPERSION_PCT: f64 = 1.0;
+/// Agreeing sources a buy needs - a lone source can't be cross-checked
+pub const MIN_CONSENSUS_SOURCES: usize = 2;
+/// Default lifetime of a cached price (ORACLE_PRICE_CACHE_TTL_SECS, 0 disables caching)
+pub const DEFAULT_PRICE_CACHE_TTL_SECS: u64 = 10;
+
+/// Feed addresses added on top of the built-in map (ORACLE_FEED_ADDRESSES and POST /oracle/feeds/register)
+/// Process-wide, so every SwitchboardClient - providers each hold their own - sees a registration at once
+#[derive(Debug, Default)]
+pub struct FeedRegistry {
+    feeds: std::sync::RwLock<HashMap<String, String>>,
+}
+
+impl FeedRegistry {
+    pub fn global() -> &'static FeedRegistry {
+        static FEEDS: std::sync::OnceLock<FeedRegistry> = std::sync::OnceLock::new();
+        FEEDS.get_or_init(FeedRegistry::default)
+    }
+
+    /// Add or override the feed for `symbol` (stored under its canonical symbol)
+    /// Returns the canonical symbol the feed was registered as
+    pub fn register(&self, symbol: &str, feed_address: &str) -> Result<String, String> {
+        let symbol = crate::symbol_registry::canonical(symbol);
+        if symbol.starts_with('/') {
+            return Err("Symbol is empty".to_string());
+        }
+        let feed_address = feed_address.trim();
+        feed_address.parse::<solana_sdk::pubkey::Pubkey>()
+            .map_err(|_| format!("Invalid feed address for {}: {:?}", symbol, feed_address))?;
+        if let Ok(mut feeds) = self.feeds.write() {
+            feeds.insert(symbol.clone(), feed_address.to_string());
+        }
+        log::info!("🔮 Oracle feed for {} set to {}", symbol, feed_address);
+        Ok(symbol)
+    }
+
+    /// Register every "SYMBOL:address" pair of ORACLE_FEED_ADDRESSES="WIF/USD:<address>,BONK:<address>"
+    pub fn load(&self, value: &str) -> Result<usize, String> {
+        let mut entries = Vec::new();
+        for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (symbol, feed_address) = entry.split_once(':')
+                .ok_or_else(|| format!("Expected symbol:address, got {:?}", entry))?;
+            entries.push((symbol.trim(), feed_address.trim()));
+        }
+        // Validate everything first so a typo doesn't leave half the map loaded
+        for (symbol, feed_address) in &entries {
+            feed_address.parse::<solana_sdk::pubkey::Pubkey>()
+                .map_err(|_| format!("Invalid feed address for {}: {:?}", symbol, feed_address))?;
+        }
+        for (symbol, feed_address) in &entries {
+            self.register(symbol, feed_address)?;
+        }
+        Ok(entries.len())
+    }
+
+    /// Load ORACLE_FEED_ADDRESSES if set
+    pub fn load_from_env(&self) {
+        if let Ok(value) = std::env::var("ORACLE_FEED_ADDRESSES") {
+            match self.load(&value) {
+                Ok(count) => log::info!("🔮 Loaded {} oracle feed addresses from ORACLE_FEED_ADDRESSES", count),
+                Err(e) => log::error!("❌ Invalid ORACLE_FEED_ADDRESSES ({}) - using the built-in feeds only", e),
+            }
+        }
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<String> {
+        self.feeds.read().ok()?.get(symbol).cloned()
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        self.feeds.read().map(|feeds| feeds.keys().cloned().collect()).unwrap_or_default()
+    }
+}
+
 /// Represents a Switchboard Oracle price feed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OracleFeed {
@@ -19,6 +99,16 @@ pub struct OracleFeed {
     pub min_price: f64,  // price - confidence
     pub max_price: f64,  // price + confidence
     pub price_change_24h: Option<f64>,
+    /// Set by `fetch_price` when the quote is older than the client's max staleness
+    #[serde(default)]
+    pub stale: bool,
+}
+
+impl OracleFeed {
+    /// Seconds since the quote was published
+    pub fn age_secs(&self) -> i64 {
+        (chrono::Utc::now().timestamp() - self.timestamp).max(0)
+    }
 }
 
 /// Response from Switchboard API
@@ -40,12 +130,98 @@ struct SwitchboardData {
 
 /// Cached price entry with TTL
 #[derive(Debug, Clone)]
-struct CachedPrice {
-    feed: OracleFeed,
+struct CachedPrice<T> {
+    value: T,
     cached_at: Instant,
+}
+
+/// Short-TTL price cache with single-flight fetches - concurrent misses for the same symbol
+/// wait on one fetch instead of each hitting the RPC
+#[derive(Debug)]
+pub struct PriceCache<T> {
+    entries: Mutex<HashMap<String, CachedPrice<T>>>,
+    // One lock per symbol, held for the duration of its fetch - removed once no caller holds it
+    in_flight: Mutex<HashMap<String, Arc<Mutex<()>>>>,
     ttl: Duration,
 }
 
+impl<T: Clone> PriceCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+    
+    /// ORACLE_PRICE_CACHE_TTL_SECS (default 10)
+    pub fn ttl_from_env() -> Duration {
+        let secs = std::env::var("ORACLE_PRICE_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_PRICE_CACHE_TTL_SECS);
+        Duration::from_secs(secs)
+    }
+    
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+    
+    async fn get_fresh(&self, key: &str) -> Option<T> {
+        let entries = self.entries.lock().await;
+        entries.get(key)
+            .filter(|cached| cached.cached_at.elapsed() < self.ttl)
+            .map(|cached| cached.value.clone())
+    }
+    
+    /// Cached value for `key`, or the result of `fetch` - only one fetch per key runs at a time,
+    /// and callers that waited on it reuse its result. Errors are not cached
+    pub async fn get_or_fetch<F, Fut, E>(&self, key: &str, fetch: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get_fresh(key).await {
+            log::debug!("✅ Using cached price for {}", key);
+            return Ok(value);
+        }
+        
+        let flight = self.in_flight.lock().await
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+        let guard = flight.lock().await;
+        
+        // Another caller may have filled the cache while we waited for the flight
+        let result = match self.get_fresh(key).await {
+            Some(value) => Ok(value),
+            None => {
+                let fetched = fetch().await;
+                if let (Ok(value), false) = (&fetched, self.ttl.is_zero()) {
+                    self.entries.lock().await.insert(key.to_string(), CachedPrice {
+                        value: value.clone(),
+                        cached_at: Instant::now(),
+                    });
+                }
+                fetched
+            }
+        };
+        drop(guard);
+        
+        // The map and this call hold the only references - nobody is waiting on this flight
+        let mut in_flight = self.in_flight.lock().await;
+        if Arc::strong_count(&flight) <= 2 {
+            in_flight.remove(key);
+        }
+        result
+    }
+    
+    #[cfg(test)]
+    async fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().await.len()
+    }
+}
+
 /// Rate limiter state for API calls
 #[derive(Debug)]
 struct RateLimiterState {
@@ -132,15 +308,20 @@ pub struct SwitchboardClient {
     pub feed_addresses: HashMap<String, String>, // Legacy feed addresses (for fallback)
     feed_hashes: HashMap<String, String>, // Oracle Quotes feed hashes (new standard)
     use_real_oracle: bool,
-    // Price cache with TTL
-    price_cache: Arc<Mutex<HashMap<String, CachedPrice>>>,
-    cache_ttl: Duration,
+    // Price cache with TTL and single-flight fetches
+    price_cache: PriceCache<OracleFeed>,
+    // Quotes older than this are flagged stale
+    max_staleness_secs: i64,
     // Rate limiters per API
     jupiter_rate_limiter: ApiRateLimiter,
     mobula_rate_limiter: ApiRateLimiter,
     switchboard_rate_limiter: ApiRateLimiter,
     // Circuit breaker for API protection
     circuit_breaker: Option<Arc<tokio::sync::Mutex<crate::error_handling::CircuitBreaker>>>,
+    // Set while replaying a recorded session - prices come from the recording, never the network
+    replay_prices: Option<crate::historical_data::ReplayPrices>,
+    // Prices tokens no feed lists (most memecoins) from their DEX pool in fetch_price_or_pool
+    pool_fallback: Option<Arc<crate::dex_screener::DexScreenerClient>>,
 }
 
 impl SwitchboardClient {
@@ -197,8 +378,11 @@ impl SwitchboardClient {
             feed_addresses,
             feed_hashes,
             use_real_oracle,
-            price_cache: Arc::new(Mutex::new(HashMap::new())),
-            cache_ttl: Duration::from_secs(10), // Cache prices for 10 seconds
+            price_cache: PriceCache::new(PriceCache::<OracleFeed>::ttl_from_env()),
+            max_staleness_secs: std::env::var("ORACLE_MAX_STALENESS_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_STALENESS_SECS),
             // Jupiter: ~100 requests/min (conservative)
             jupiter_rate_limiter: ApiRateLimiter::new(80, 60),
             // Mobula: ~1000 requests/min with API key, ~500 without
@@ -207,6 +391,8 @@ impl SwitchboardClient {
             // Switchboard Oracle Quotes: Higher limits (no account setup needed)
             switchboard_rate_limiter: ApiRateLimiter::new(1000, 60),
             circuit_breaker: None,
+            replay_prices: None,
+            pool_fallback: None,
         }
     }
     
@@ -221,6 +407,34 @@ impl SwitchboardClient {
         client
     }
     
+    /// Override the maximum quote age before prices are flagged stale
+    pub fn with_max_staleness(mut self, max_staleness_secs: i64) -> Self {
+        self.max_staleness_secs = max_staleness_secs;
+        self
+    }
+    
+    pub fn max_staleness_secs(&self) -> i64 {
+        self.max_staleness_secs
+    }
+    
+    /// REPLAY: Serve the recorded session's prices instead of fetching
+    pub fn with_replay_prices(mut self, replay_prices: crate::historical_data::ReplayPrices) -> Self {
+        self.replay_prices = Some(replay_prices);
+        self
+    }
+    
+    /// Price symbols without an oracle feed from DEX Screener's most liquid pool in `fetch_price_or_pool`
+    pub fn with_pool_fallback(mut self, dex_screener: Arc<crate::dex_screener::DexScreenerClient>) -> Self {
+        self.pool_fallback = Some(dex_screener);
+        self
+    }
+    
+    /// Override how long fetched prices are reused
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.price_cache = PriceCache::new(ttl);
+        self
+    }
+    
     /// Create client with default settings (simulated oracle for development)
     pub fn new_simulated() -> Self {
         Self::new(
@@ -236,44 +450,50 @@ impl SwitchboardClient {
     
     /// Fetch the latest price from a Switchboard feed
     /// Uses caching to reduce API calls and prevent rate limiting
-    pub async fn fetch_price(&self, symbol: &str) -> Result<OracleFeed, Box<dyn Error + Send + Sync>> {
-        // Check cache first
-        {
-            let cache = self.price_cache.lock().await;
-            if let Some(cached) = cache.get(symbol) {
-                if cached.cached_at.elapsed() < cached.ttl {
-                    log::debug!("✅ Using cached price for {} (age: {:?})", symbol, cached.cached_at.elapsed());
-                    return Ok(cached.feed.clone());
-                }
-            }
+    /// Quotes older than `max_staleness_secs` are returned with `stale: true` - callers must not
+    /// make trading decisions on them
+    /// Any provider label works - "BONK (Jupiter)" and "SOL/USDC" resolve to the "BONK/USD" / "SOL/USD" feeds
+    pub async fn fetch_price(&self, symbol: &str) -> Result<OracleFeed, AppError> {
+        let symbol = crate::symbol_registry::canonical(symbol);
+        let symbol = symbol.as_str();
+        if let Some(ref replay_prices) = self.replay_prices {
+            return replay_prices.get(symbol)
+                .map(|feed| self.mark_staleness(feed))
+                .ok_or_else(|| AppError::Oracle(format!("No replayed price for {} yet", symbol)));
         }
         
-        let feed_address = self.feed_addresses.get(symbol)
-            .ok_or_else(|| format!("No feed address found for symbol: {}", symbol))?;
-        
-        let result = if self.use_real_oracle {
-            self.fetch_real_oracle_price(symbol, feed_address).await
-        } else {
-            self.fetch_simulated_price(symbol, feed_address).await
-        };
+        let feed = self.price_cache.get_or_fetch(symbol, || async {
+            let feed_address = self.feed_address(symbol).ok_or_else(|| AppError::NotFound(format!(
+                "No oracle feed for {} (known feeds: {}) - register one via POST /oracle/feeds/register",
+                symbol, self.get_available_symbols().join(", "))))?;
+            
+            let started = Instant::now();
+            let result = if self.use_real_oracle {
+                self.fetch_real_oracle_price(symbol, &feed_address).await
+            } else {
+                self.fetch_simulated_price(symbol, &feed_address).await
+            };
+            crate::metrics::Metrics::global().observe_oracle_latency(started.elapsed());
+            result
+        }).await?;
         
-        // Cache the result if successful
-        if let Ok(feed) = &result {
-            let mut cache = self.price_cache.lock().await;
-            cache.insert(symbol.to_string(), CachedPrice {
-                feed: feed.clone(),
-                cached_at: Instant::now(),
-                ttl: self.cache_ttl,
-            });
+        Ok(self.mark_staleness(feed))
+    }
+    
+    /// Flag feeds whose publish timestamp is older than the staleness limit
+    fn mark_staleness(&self, mut feed: OracleFeed) -> OracleFeed {
+        feed.stale = !Self::is_data_fresh(&feed, self.max_staleness_secs);
+        if feed.stale {
+            log::warn!("⏰ Stale oracle price for {}: ${:.4} is {}s old (max {}s)",
+                      feed.symbol, feed.price, feed.age_secs(), self.max_staleness_secs);
         }
-        
-        result
+        feed
     }
     
     /// Fetch real oracle price using free data sources
     /// Priority: 1. Switchboard Oracle Quotes (new standard - fastest, cheapest), 
     ///          2. Jupiter Quote API (free), 3. Mobula API, 4. Switchboard on-chain (legacy)
-    async fn fetch_real_oracle_price(&self, symbol: &str, feed_address: &str) -> Result<OracleFeed, Box<dyn Error + Send + Sync>> {
+    async fn fetch_real_oracle_price(&self, symbol: &str, feed_address: &str) -> Result<OracleFeed, AppError> {
         log::debug!("🌐 Fetching real price data for {} from {}", symbol, feed_address);
         
         // Try Switchboard Oracle Quotes first (new standard - 90% cheaper, <1s latency, no account setup)
@@ -293,6 +513,7 @@ impl SwitchboardClient {
                         timestamp: chrono::Utc::now().timestamp(),
                         slot: 0,
                         price_change_24h: None,
+                        stale: false,
                     });
                 }
                 Err(quotes_err) => {
@@ -318,12 +539,12 @@ impl SwitchboardClient {
                     timestamp: chrono::Utc::now().timestamp(),
                     slot: 0,
                     price_change_24h: None, // Jupiter doesn't provide 24h change
+                    stale: false,
                 })
             }
             Err(jupiter_err) => {
                 // Check if it's a DNS/network error - these are often temporary
-                let error_str = format!("{}", jupiter_err);
-                if error_str.contains("dns") || error_str.contains("No such host") || error_str.contains("connection") {
+                if matches!(jupiter_err, AppError::Network(_) | AppError::Timeout(_)) {
                     log::warn!("⚠️ Jupiter API network error for {}: {}. This may be temporary. Trying Mobula API...", symbol, jupiter_err);
                 } else {
                     log::debug!("Jupiter API failed for {}: {}. Trying Mobula API...", symbol, jupiter_err);
@@ -344,12 +565,12 @@ impl SwitchboardClient {
                             timestamp: chrono::Utc::now().timestamp(),
                             slot: 0,
                             price_change_24h: None,
+                            stale: false,
                         });
                     }
                     Err(mobula_err) => {
                         // Check if it's a rate limit error
-                        let error_str = format!("{}", mobula_err);
-                        if error_str.contains("429") || error_str.contains("rate limit") {
+                        if matches!(mobula_err, AppError::RateLimited(_)) {
                             log::warn!("⚠️ Mobula API rate limit for {}: {}. Waiting before trying Switchboard on-chain...", symbol, mobula_err);
                         } else {
                             log::debug!("Mobula API failed for {}: {}. Trying Switchboard on-chain...", symbol, mobula_err);
@@ -370,21 +591,17 @@ impl SwitchboardClient {
                                     timestamp: chrono::Utc::now().timestamp(),
                                     slot: 0,
                                     price_change_24h: None,
+                                    stale: false,
                                 })
                             }
                             Err(switchboard_err) => {
-                                // Convert all errors to String to avoid Send issues
-                                let jupiter_msg = format!("{}", jupiter_err);
-                                let mobula_msg = format!("{}", mobula_err);
-                                let switchboard_msg = format!("{}", switchboard_err);
-                                
                                 // FALLBACK: Use simulated price when all real sources fail
                                 // This prevents system from crashing when APIs are unavailable
                                 let simulated_price = self.get_simulated_price(symbol);
                                 
                                 // Log error (reduced frequency handled by cache TTL and retry logic)
                                 log::warn!("⚠️ All real data sources failed for {}: Switchboard Oracle Quotes: (not configured), Jupiter: {}, Mobula: {}, Switchboard Legacy: {}", 
-                                    symbol, jupiter_msg, mobula_msg, switchboard_msg);
+                                    symbol, jupiter_err, mobula_err, switchboard_err);
                                 log::warn!("   Using simulated fallback price: ${:.2}", simulated_price);
                                 log::warn!("   This may be due to network issues, rate limits, or API unavailability.");
                                 log::warn!("   💡 Tip: Configure Switchboard Oracle Quotes feed hashes for best performance");
@@ -401,6 +618,7 @@ impl SwitchboardClient {
                                     timestamp: chrono::Utc::now().timestamp(),
                                     slot: 0,
                                     price_change_24h: None,
+                                    stale: false,
                                 })
                             }
                         }
@@ -413,10 +631,13 @@ impl SwitchboardClient {
     /// Fetch price from Jupiter Quote API (FREE - no API key needed for quotes)
     /// Gets price by requesting a quote for 1 unit of token to USDC
     /// Includes rate limiting to prevent hitting API limits
-    async fn fetch_price_from_jupiter(&self, symbol: &str) -> Result<f64, String> {
+    async fn fetch_price_from_jupiter(&self, symbol: &str) -> Result<f64, AppError> {
+        if self.replay_prices.is_some() {
+            return Err(AppError::Oracle("Network price sources are disabled while replaying".to_string()));
+        }
         // Check rate limit before making request
         self.jupiter_rate_limiter.check_and_wait().await
-            .map_err(|e| format!("Jupiter rate limit check failed: {}", e))?;
+            .map_err(|e| AppError::RateLimited(format!("Jupiter rate limit check failed: {}", e)))?;
         // Solana token mint addresses
         let (token_mint, usdc_mint) = match symbol {
             "SOL/USD" => (
@@ -425,17 +646,17 @@ impl SwitchboardClient {
             ),
             "BTC/USD" => {
                 // For BTC, we'll use wrapped BTC (WBTC) if available, or fall back
-                return Err("BTC/USD not directly available via Jupiter. Use Switchboard on-chain.".to_string());
+                return Err(AppError::Oracle("BTC/USD not directly available via Jupiter. Use Switchboard on-chain.".to_string()));
             }
             "ETH/USD" => {
                 // For ETH, we'll use wrapped ETH (WETH) if available, or fall back
-                return Err("ETH/USD not directly available via Jupiter. Use Switchboard on-chain.".to_string());
+                return Err(AppError::Oracle("ETH/USD not directly available via Jupiter. Use Switchboard on-chain.".to_string()));
             }
             "USDC/USD" => {
                 // USDC/USD is always 1.0
                 return Ok(1.0);
             }
-            _ => return Err(format!("Unsupported symbol for Jupiter: {}", symbol)),
+            _ => return Err(AppError::NotFound(format!("Unsupported symbol for Jupiter: {}", symbol))),
         };
         
         // Request quote for 1 SOL (1_000_000_000 lamports) to USDC
@@ -448,12 +669,12 @@ impl SwitchboardClient {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10)) // Increased timeout for network issues
             .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
         
         // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of retry_with_backoff
         // CRITICAL IMPROVEMENT #4: Detect and map reqwest timeout errors
         // CRITICAL IMPROVEMENT #5: Use conservative retry config for price fetching (not critical)
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
+        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, map_http_status_to_error};
         
         let url_clone = url.clone();
         let client_clone = client.clone();
@@ -471,17 +692,7 @@ impl SwitchboardClient {
                         .get(&url)
                         .send()
                         .await
-                        .map_err(|e| {
-                            let error_str = format!("{}", e);
-                            // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                TradingError::TimeoutError(format!("Request timeout: {}", e))
-                            } else if error_str.contains("dns") || error_str.contains("No such host") || error_str.contains("connection") {
-                                TradingError::NetworkError(format!("Network/DNS error: {}", e))
-                            } else {
-                                TradingError::ApiError(format!("Jupiter API request failed: {}", e))
-                            }
-                        })?;
+                        .map_err(AppError::from)?;
                     
                     if !response.status().is_success() {
                         // CRITICAL IMPROVEMENT #2: Use map_http_status_to_error() for proper error type mapping
@@ -491,18 +702,18 @@ impl SwitchboardClient {
                     }
                     
                     let json: serde_json::Value = response.json().await
-                        .map_err(|e| TradingError::ApiError(
+                        .map_err(|e| AppError::Api(
                             format!("Failed to parse Jupiter response: {}", e)
                         ))?;
                     
                     // Parse Jupiter quote response
                     let out_amount_str = json["outAmount"].as_str()
-                        .ok_or_else(|| TradingError::ApiError(
+                        .ok_or_else(|| AppError::Api(
                             "outAmount not found in Jupiter response".to_string()
                         ))?;
                     
                     let out_amount: u64 = out_amount_str.parse()
-                        .map_err(|e| TradingError::ApiError(
+                        .map_err(|e| AppError::Api(
                             format!("Failed to parse outAmount: {}", e)
                         ))?;
                     
@@ -517,23 +728,26 @@ impl SwitchboardClient {
             &operation_name,
         ).await;
         
-        result.map_err(|e| format!("{}", e))
+        result
     }
     
     /// Fetch price from Mobula API (free tier available, already integrated)
     /// Includes retry logic for rate limits (429 errors) with exponential backoff
     /// Includes rate limiting to prevent 429 errors
-    pub async fn fetch_price_from_mobula(&self, symbol: &str) -> Result<f64, String> {
+    pub async fn fetch_price_from_mobula(&self, symbol: &str) -> Result<f64, AppError> {
+        if self.replay_prices.is_some() {
+            return Err(AppError::Oracle("Network price sources are disabled while replaying".to_string()));
+        }
         // Check rate limit before making request
         self.mobula_rate_limiter.check_and_wait().await
-            .map_err(|e| format!("Mobula rate limit check failed: {}", e))?;
+            .map_err(|e| AppError::RateLimited(format!("Mobula rate limit check failed: {}", e)))?;
         // Map symbol to token address
         let token_address = match symbol {
             "SOL/USD" => "So11111111111111111111111111111111111111112",
             "BTC/USD" => "9n4nbM75f5Ui33ZbPYXn59EwSgE8CGsHtAeTH5YFeJ9E", // Wrapped BTC
             "ETH/USD" => "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs", // Wrapped ETH
             "USDC/USD" => "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-            _ => return Err(format!("Unsupported symbol for Mobula: {}", symbol)),
+            _ => return Err(AppError::NotFound(format!("Unsupported symbol for Mobula: {}", symbol))),
         };
         
         let url = format!(
@@ -544,7 +758,7 @@ impl SwitchboardClient {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10)) // Increased timeout
             .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
         
         // Get API key if available
         let api_key = std::env::var("MOBULA_API_KEY").ok();
@@ -552,7 +766,7 @@ impl SwitchboardClient {
         // CRITICAL IMPROVEMENT #1: Use retry_with_backoff_retryable instead of retry_with_backoff
         // CRITICAL IMPROVEMENT #4: Detect and map reqwest timeout errors
         // CRITICAL IMPROVEMENT #5: Use conservative retry config for price fetching (not critical)
-        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, TradingError, map_http_status_to_error};
+        use crate::error_handling::{retry_with_backoff_retryable, RetryConfig, map_http_status_to_error};
         
         let url_clone = url.clone();
         let client_clone = client.clone();
@@ -582,17 +796,7 @@ impl SwitchboardClient {
                     let response = request
                         .send()
                         .await
-                        .map_err(|e| {
-                            let error_str = e.to_string();
-                            // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                TradingError::TimeoutError(format!("Request timeout: {}", e))
-                            } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                TradingError::NetworkError(format!("Network error: {}", e))
-                            } else {
-                                TradingError::NetworkError(format!("Mobula API request failed: {}", e))
-                            }
-                        })?;
+                        .map_err(AppError::from)?;
                     
                     let status = response.status();
                     if status == 429 {
@@ -610,7 +814,7 @@ impl SwitchboardClient {
                         // Wait before retrying (cap at 30 seconds)
                         tokio::time::sleep(Duration::from_secs(retry_after.min(30))).await;
                         
-                        return Err(TradingError::RateLimitExceeded(
+                        return Err(AppError::RateLimited(
                             format!("Mobula API rate limit (429). Retry-After: {}s. Retrying with backoff...", retry_after)
                         ));
                     }
@@ -622,12 +826,12 @@ impl SwitchboardClient {
                     }
                     
                     let json: serde_json::Value = response.json().await
-                        .map_err(|e| TradingError::ApiError(
+                        .map_err(|e| AppError::Api(
                             format!("Failed to parse Mobula response: {}", e)
                         ))?;
                     
                     let price = json["data"]["price"].as_f64()
-                        .ok_or_else(|| TradingError::ApiError(
+                        .ok_or_else(|| AppError::Api(
                             format!("Price not found in Mobula response for {}", symbol)
                         ))?;
                     
@@ -638,16 +842,16 @@ impl SwitchboardClient {
             &operation_name,
         ).await;
         
-        result.map_err(|e| format!("{}", e))
+        result
     }
     
     /// Fetch price from Switchboard Oracle Quotes (new standard)
     /// Uses Oracle Quotes API - 90% cheaper, <1s latency, no account setup required
     /// Reference: https://docs.switchboard.xyz/oracle-quotes-the-new-standard/oracle-quotes#getting-started
-    async fn fetch_price_from_oracle_quotes(&self, symbol: &str, feed_hash: &str) -> Result<f64, String> {
+    async fn fetch_price_from_oracle_quotes(&self, symbol: &str, feed_hash: &str) -> Result<f64, AppError> {
         // Check rate limit before making request
         self.switchboard_rate_limiter.check_and_wait().await
-            .map_err(|e| format!("Switchboard rate limit check failed: {}", e))?;
+            .map_err(|e| AppError::RateLimited(format!("Switchboard rate limit check failed: {}", e)))?;
         
         // Switchboard Oracle Quotes Gateway API
         // Using the on-demand gateway endpoint
@@ -656,7 +860,7 @@ impl SwitchboardClient {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(5))
             .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
         
         // Request Oracle Quote for the feed hash
         // Format: GET /api/v1/quote?feeds=0xFEED_HASH
@@ -664,9 +868,9 @@ impl SwitchboardClient {
         
         // CRITICAL IMPROVEMENT #3: Use circuit breaker .call() method instead of manual state checks
         // This properly tracks successes/failures and manages circuit breaker state transitions
-        use crate::error_handling::{TradingError, map_http_status_to_error};
+        use crate::error_handling::map_http_status_to_error;
         
-        let result: Result<reqwest::Response, TradingError> = if let Some(ref cb) = self.circuit_breaker {
+        let result: Result<reqwest::Response, AppError> = if let Some(ref cb) = self.circuit_breaker {
             let cb_clone = cb.clone();
             // Scope the lock so guard is dropped after await completes
             {
@@ -677,17 +881,7 @@ impl SwitchboardClient {
                         .header("Content-Type", "application/json")
                         .send()
                         .await
-                        .map_err(|e| {
-                            let error_str = e.to_string();
-                            // CRITICAL IMPROVEMENT #4: Detect timeout errors
-                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                TradingError::TimeoutError(format!("Request timeout: {}", e))
-                            } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                TradingError::NetworkError(format!("Network error: {}", e))
-                            } else {
-                                TradingError::NetworkError(format!("Request failed: {}", e))
-                            }
-                        })
+                        .map_err(AppError::from)
                 }).await
             } // Guard dropped here after await completes
         } else {
@@ -697,57 +891,51 @@ impl SwitchboardClient {
                 .header("Content-Type", "application/json")
                 .send()
                 .await
-                .map_err(|e| {
-                    let error_str = e.to_string();
-                    if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                        TradingError::TimeoutError(format!("Request timeout: {}", e))
-                    } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                        TradingError::NetworkError(format!("Network error: {}", e))
-                    } else {
-                        TradingError::NetworkError(format!("Request failed: {}", e))
-                    }
-                })
+                .map_err(AppError::from)
         };
         
-        let response = result.map_err(|e| format!("Switchboard Oracle Quotes API request failed: {}", e))?;
+        let response = result?;
         
         if !response.status().is_success() {
             // CRITICAL IMPROVEMENT #2: Use map_http_status_to_error() for proper error type mapping
             let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            let mapped_error = map_http_status_to_error(status, error_text);
-            return Err(format!("Switchboard Oracle Quotes API error: {}", mapped_error));
+            return Err(map_http_status_to_error(status, format!("Switchboard Oracle Quotes API: {}", error_text)));
         }
         
         let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse Switchboard Oracle Quotes response: {}", e))?;
+            .map_err(|e| AppError::Api(format!("Failed to parse Switchboard Oracle Quotes response: {}", e)))?;
         
         // Parse Oracle Quote response
         // Response format: { "feeds": [{ "id": "0x...", "value": 123.45, ... }] }
         let feeds = json["feeds"].as_array()
-            .ok_or_else(|| "feeds array not found in Oracle Quotes response".to_string())?;
+            .ok_or_else(|| AppError::Api("feeds array not found in Oracle Quotes response".to_string()))?;
         
         if feeds.is_empty() {
-            return Err(format!("No feeds found in Oracle Quotes response for {}", symbol));
+            return Err(AppError::Oracle(format!("No feeds found in Oracle Quotes response for {}", symbol)));
         }
         
         // Get the first feed's value (assuming single feed per request)
         let price = feeds[0]["value"].as_f64()
-            .ok_or_else(|| format!("Price value not found in Oracle Quotes response for {}", symbol))?;
+            .ok_or_else(|| AppError::Api(format!("Price value not found in Oracle Quotes response for {}", symbol)))?;
         
         Ok(price)
     }
     
     /// Fetch price from Switchboard on-chain data using SDK
-    pub async fn fetch_price_from_switchboard_onchain(&self, _symbol: &str, feed_address: &str) -> Result<f64, String> {
+    pub async fn fetch_price_from_switchboard_onchain(&self, _symbol: &str, feed_address: &str) -> Result<f64, AppError> {
         use solana_sdk::pubkey::Pubkey;
         use std::str::FromStr;
         
+        if self.replay_prices.is_some() {
+            return Err(AppError::Oracle("Network price sources are disabled while replaying".to_string()));
+        }
+        
         let pubkey = Pubkey::from_str(feed_address)
-            .map_err(|e| format!("Invalid feed address: {}", e))?;
+            .map_err(|e| AppError::Validation(format!("Invalid feed address: {}", e)))?;
         
         let account = self.rpc_client.get_account(&pubkey)
-            .map_err(|e| format!("Failed to fetch Switchboard account: {}", e))?;
+            .map_err(|e| AppError::Network(format!("Failed to fetch Switchboard account: {}", e)))?;
         
         // Use Switchboard SDK to parse the aggregator account
         // TODO: Implement proper Switchboard account deserialization
@@ -755,12 +943,12 @@ impl SwitchboardClient {
         // For now, return error and use API-based price fetching instead
         
         log::debug!("Switchboard on-chain parsing: Not yet implemented - use API endpoint instead");
-        Err("Switchboard on-chain parsing not yet fully implemented. Use /oracle/price/{symbol} API endpoint instead.".to_string())
+        Err(AppError::Oracle("Switchboard on-chain parsing not yet fully implemented. Use /oracle/price/{symbol} API endpoint instead.".to_string()))
     }
     
     
     /// Fetch simulated price for development/testing
-    async fn fetch_simulated_price(&self, symbol: &str, feed_address: &str) -> Result<OracleFeed, Box<dyn Error + Send + Sync>> {
+    async fn fetch_simulated_price(&self, symbol: &str, feed_address: &str) -> Result<OracleFeed, AppError> {
         log::debug!("Using simulated Switchboard feed for {} at {}", symbol, feed_address);
         
         let price = self.simulate_oracle_price(symbol).await?;
@@ -776,11 +964,39 @@ impl SwitchboardClient {
             timestamp: chrono::Utc::now().timestamp(),
             slot: 0,
             price_change_24h: Some(self.simulate_price_change()),
+            stale: false,
+        })
+    }
+    
+    /// `fetch_price`, falling back to the token's DEX Screener pool when no oracle feed lists it - memecoins
+    /// are the riskiest tokens and the ones no feed covers. Pool prices carry a `dexscreener:` feed address
+    pub async fn fetch_price_or_pool(&self, symbol: &str) -> Result<OracleFeed, AppError> {
+        let oracle_error = match self.fetch_price(symbol).await {
+            Ok(feed) => return Ok(feed),
+            Err(e) => e,
+        };
+        let (Some(dex_screener), Some((mint, _))) = (&self.pool_fallback, crate::dex_aggregator::resolve_token(symbol)) else {
+            return Err(oracle_error);
+        };
+        let price = dex_screener.token_price_usd(&mint).await
+            .map_err(|e| AppError::NotFound(format!("{} has no oracle feed ({}) or DEX pool price ({})", symbol, oracle_error, e)))?;
+        let confidence = price * 0.01;
+        Ok(OracleFeed {
+            feed_address: format!("dexscreener:{}", mint),
+            symbol: crate::symbol_registry::canonical(symbol),
+            price,
+            confidence,
+            timestamp: chrono::Utc::now().timestamp(),
+            slot: 0,
+            min_price: price - confidence,
+            max_price: price + confidence,
+            price_change_24h: None,
+            stale: false,
         })
     }
     
     /// Fetch multiple feeds at once
-    pub async fn fetch_multiple_feeds(&self, symbols: &[String]) -> Result<Vec<OracleFeed>, Box<dyn Error + Send + Sync>> {
+    pub async fn fetch_multiple_feeds(&self, symbols: &[String]) -> Result<Vec<OracleFeed>, AppError> {
         let mut feeds = Vec::new();
         
         for symbol in symbols {
@@ -796,9 +1012,28 @@ impl SwitchboardClient {
         Ok(feeds)
     }
     
-    /// Get all available feed symbols
+    /// Get all available feed symbols (built-in and registered)
     pub fn get_available_symbols(&self) -> Vec<String> {
-        self.feed_addresses.keys().cloned().collect()
+        let mut symbols: Vec<String> = self.feed_addresses.keys().cloned()
+            .chain(FeedRegistry::global().symbols())
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+    
+    /// Feed address for a canonical symbol - registered feeds override the built-in ones
+    pub fn feed_address(&self, symbol: &str) -> Option<String> {
+        FeedRegistry::global().get(symbol)
+            .or_else(|| self.feed_addresses.get(symbol).cloned())
+    }
+    
+    /// Symbols in `symbols` that have no feed, for a clear error instead of a silent skip
+    pub fn unknown_symbols(&self, symbols: &[String]) -> Vec<String> {
+        symbols.iter()
+            .filter(|symbol| self.feed_address(&crate::symbol_registry::canonical(symbol)).is_none())
+            .cloned()
+            .collect()
     }
     
     /// Add a custom feed address
@@ -822,7 +1057,7 @@ impl SwitchboardClient {
     
     /// Simulate oracle price for development/testing
     /// Uses realistic price ranges based on current market conditions
-    async fn simulate_oracle_price(&self, symbol: &str) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    async fn simulate_oracle_price(&self, symbol: &str) -> Result<f64, AppError> {
         Ok(self.get_simulated_price(symbol))
     }
     
@@ -880,6 +1115,92 @@ pub struct PriceComparison {
     pub timestamp: i64,
 }
 
+/// Trusted price from the sources that agree with the median
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusPrice {
+    pub symbol: String,
+    /// Weighted mean of the accepted sources (confidence and recency weighted)
+    pub price: f64,
+    /// Weighted standard deviation of every available source, outliers included, as a percent of their mean
+    pub dispersion_pct: f64,
+    pub sources: Vec<PriceSource>,
+    /// Sources discarded as outliers
+    pub rejected: Vec<PriceSource>,
+    pub timestamp: i64,
+}
+
+impl ConsensusPrice {
+    /// Combine the available sources: drop those more than `outlier_pct` from the median, then take
+    /// a mean weighted by source weight, tightness of the confidence interval and quote age.
+    /// Dispersion is measured before the outliers are dropped - a source far off is the disagreement to catch
+    pub fn from_sources(symbol: &str, sources: &[PriceSource], outlier_pct: f64, now: i64) -> Option<Self> {
+        let available: Vec<&PriceSource> = sources.iter()
+            .filter(|source| source.available && source.price > 0.0)
+            .collect();
+        if available.is_empty() {
+            return None;
+        }
+        
+        let mut sorted: Vec<f64> = available.iter().map(|source| source.price).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] };
+        
+        let weight_of = |source: &PriceSource| {
+            let confidence_pct = source.confidence / source.price * 100.0;
+            let age_secs = (now - source.timestamp).max(0) as f64;
+            let base_weight = if source.weight > 0.0 { source.weight } else { 0.1 };
+            base_weight / (1.0 + confidence_pct) / (1.0 + age_secs / DEFAULT_MAX_STALENESS_SECS as f64)
+        };
+        let weighted_mean = |sources: &[&PriceSource]| {
+            let total_weight: f64 = sources.iter().map(|source| weight_of(source)).sum();
+            let mean = sources.iter().map(|source| source.price * weight_of(source)).sum::<f64>() / total_weight;
+            (mean, total_weight)
+        };
+        let (mean, total_weight) = weighted_mean(&available);
+        let variance = available.iter()
+            .map(|source| weight_of(source) * (source.price - mean).powi(2))
+            .sum::<f64>() / total_weight;
+        
+        let (accepted, rejected): (Vec<&PriceSource>, Vec<&PriceSource>) = available.into_iter()
+            .partition(|source| ((source.price - median) / median).abs() * 100.0 <= outlier_pct);
+        if accepted.is_empty() {
+            return None;
+        }
+        let (price, _) = weighted_mean(&accepted);
+        
+        Some(Self {
+            symbol: symbol.to_string(),
+            price,
+            dispersion_pct: variance.sqrt() / mean * 100.0,
+            sources: accepted.into_iter().cloned().collect(),
+            rejected: rejected.into_iter().cloned().collect(),
+            timestamp: now,
+        })
+    }
+    
+    /// Why a buy priced by this consensus should be refused - None when enough sources agree closely enough
+    pub fn refusal(&self, max_dispersion_pct: f64) -> Option<String> {
+        if self.sources.len() < MIN_CONSENSUS_SOURCES {
+            return Some(format!("only {} price source(s) agree on {} - need {} to cross-check",
+                                self.sources.len(), self.symbol, MIN_CONSENSUS_SOURCES));
+        }
+        if self.dispersion_pct > max_dispersion_pct {
+            return Some(format!("oracle sources disagree on {}: {:.2}% dispersion (max {:.2}%)",
+                                self.symbol, self.dispersion_pct, max_dispersion_pct));
+        }
+        None
+    }
+    
+    /// MAX_ORACLE_DISPERSION_PCT (default 1.0)
+    pub fn max_dispersion_from_env() -> f64 {
+        std::env::var("MAX_ORACLE_DISPERSION_PCT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_ORACLE_DISPERSION_PCT)
+    }
+}
+
 /// Oracle health status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OracleHealth {
@@ -903,18 +1224,35 @@ pub struct SourceHealth {
 /// Aggregates prices from Switchboard, Jupiter, Mobula, and other sources
 pub struct OracleAggregator {
     switchboard: Arc<SwitchboardClient>,
+    // Aggregates are cached separately - the Jupiter / Mobula / on-chain sources bypass the client cache
+    aggregate_cache: PriceCache<AggregatedPrice>,
+    dex_screener: Option<Arc<crate::dex_screener::DexScreenerClient>>, // Pool prices for tokens no oracle lists
 }
 
 impl OracleAggregator {
     pub fn new(switchboard_client: Arc<SwitchboardClient>) -> Self {
+        let cache_ttl = switchboard_client.price_cache.ttl();
         Self {
             switchboard: switchboard_client,
+            aggregate_cache: PriceCache::new(cache_ttl),
+            dex_screener: None,
         }
     }
     
+    /// Add DEX Screener's most liquid pool as a price source
+    pub fn with_dex_screener(mut self, dex_screener: Arc<crate::dex_screener::DexScreenerClient>) -> Self {
+        self.dex_screener = Some(dex_screener);
+        self
+    }
+    
     /// Get aggregated price from multiple oracle sources
     /// Uses weighted average based on source confidence and reliability
     pub async fn get_aggregated_price(&self, symbol: &str) -> Result<AggregatedPrice, Box<dyn Error + Send + Sync>> {
+        self.aggregate_cache.get_or_fetch(symbol, || self.fetch_aggregated_price(symbol)).await
+    }
+    
+    /// Query every source and combine them (uncached)
+    async fn fetch_aggregated_price(&self, symbol: &str) -> Result<AggregatedPrice, Box<dyn Error + Send + Sync>> {
         let mut sources = Vec::new();
         let mut prices = Vec::new();
         let mut weights = Vec::new();
@@ -979,8 +1317,8 @@ impl OracleAggregator {
         }
         
         // Source 4: Switchboard on-chain (10% weight - legacy, less reliable)
-        if let Some(feed_address) = self.switchboard.feed_addresses.get(symbol) {
-            if let Ok(onchain_price) = self.switchboard.fetch_price_from_switchboard_onchain(symbol, feed_address).await {
+        if let Some(feed_address) = self.switchboard.feed_address(symbol) {
+            if let Ok(onchain_price) = self.switchboard.fetch_price_from_switchboard_onchain(symbol, &feed_address).await {
                 let weight = 0.1;
                 sources.push(PriceSource {
                     source_name: "Switchboard On-Chain".to_string(),
@@ -995,6 +1333,26 @@ impl OracleAggregator {
             }
         }
         
+        // Source 5: DEX Screener (20% weight) - the pool price, and often the only one for a new memecoin
+        if let (Some(dex_screener), Some((mint, _))) = (&self.dex_screener, crate::dex_aggregator::resolve_token(symbol)) {
+            match dex_screener.token_price_usd(&mint).await {
+                Ok(dex_price) => {
+                    let weight = 0.2;
+                    sources.push(PriceSource {
+                        source_name: "DEX Screener".to_string(),
+                        price: dex_price,
+                        confidence: dex_price * 0.01,
+                        weight,
+                        timestamp: chrono::Utc::now().timestamp(),
+                        available: true,
+                    });
+                    prices.push(dex_price);
+                    weights.push(weight);
+                }
+                Err(e) => log::debug!("DEX Screener unavailable for {}: {}", symbol, e),
+            }
+        }
+        
         // Calculate weighted average
         if prices.is_empty() {
             return Err(format!("No price sources available for {}", symbol).into());
@@ -1053,6 +1411,36 @@ impl OracleAggregator {
         Ok((aggregated.aggregated_price, aggregated.confidence))
     }
     
+    /// Single trusted price: outliers beyond ORACLE_OUTLIER_PCT (default 2%) of the median are dropped,
+    /// the rest are combined by confidence and recency. Reuses the cached aggregate's sources
+    pub async fn get_consensus_price(&self, symbol: &str) -> Result<ConsensusPrice, Box<dyn Error + Send + Sync>> {
+        let aggregated = self.get_aggregated_price(symbol).await?;
+        let outlier_pct = std::env::var("ORACLE_OUTLIER_PCT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ORACLE_OUTLIER_PCT);
+        let consensus = ConsensusPrice::from_sources(symbol, &aggregated.sources, outlier_pct, chrono::Utc::now().timestamp())
+            .ok_or_else(|| format!("No price sources available for consensus: {}", symbol))?;
+        if !consensus.rejected.is_empty() {
+            log::warn!("⚖️ {} consensus ${:.6} dropped {} outlier source(s): {}", symbol, consensus.price,
+                      consensus.rejected.len(),
+                      consensus.rejected.iter().map(|source| format!("{} ${:.6}", source.source_name, source.price))
+                          .collect::<Vec<_>>().join(", "));
+        }
+        Ok(consensus)
+    }
+    
+    /// Consensus price for a buy, failing closed: an error when no consensus can be formed, too few
+    /// sources agree, or they disagree by more than `max_dispersion_pct`
+    pub async fn verify_price(&self, symbol: &str, max_dispersion_pct: f64) -> Result<ConsensusPrice, String> {
+        let consensus = self.get_consensus_price(symbol).await
+            .map_err(|e| format!("price of {} can't be verified: {}", symbol, e))?;
+        match consensus.refusal(max_dispersion_pct) {
+            Some(reason) => Err(reason),
+            None => Ok(consensus),
+        }
+    }
+    
     /// Compare prices across all available sources
     pub async fn compare_prices(&self, symbol: &str) -> Result<PriceComparison, Box<dyn Error + Send + Sync>> {
         let aggregated = self.get_aggregated_price(symbol).await?;
@@ -1200,6 +1588,36 @@ mod tests {
         assert!(feed.max_price > feed.price);
     }
 
+    #[tokio::test]
+    async fn test_fetch_price_resolves_provider_labels() {
+        // The performance tracker passes signal symbols straight through
+        let client = SwitchboardClient::new_simulated();
+        for label in ["BTC (Jupiter)", "BTC/USDC", "btc"] {
+            let feed = client.fetch_price(label).await.unwrap();
+            assert_eq!(feed.symbol, "BTC/USD");
+        }
+        assert!(matches!(client.fetch_price("BONK (Jupiter)").await, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_registered_feeds_extend_the_defaults() {
+        let client = SwitchboardClient::new_simulated();
+        let registry = FeedRegistry::global();
+        assert!(client.feed_address("WIFTEST/USD").is_none());
+        assert_eq!(client.unknown_symbols(&["SOL/USD".to_string(), "WIFTEST".to_string()]), vec!["WIFTEST".to_string()]);
+        assert!(client.fetch_price("WIFTEST").await.unwrap_err().to_string().contains("known feeds"));
+
+        assert!(registry.register("WIFTEST", "not-a-pubkey").is_err());
+        assert!(registry.load("WIFTEST/USD:GvDMxPzN1sCj7L26YDK2HnMRXEQmQ2aemov8YBtPS7vR,BAD:oops").is_err());
+        assert!(client.feed_address("WIFTEST/USD").is_none());
+
+        let symbol = registry.register("wiftest (Jupiter)", "8SXvChNYFhRq4EZuZvnhjrB3jJRQCv4k3P4W6hesH3Ee").unwrap();
+        assert_eq!(symbol, "WIFTEST/USD");
+        assert_eq!(client.feed_address("WIFTEST/USD").as_deref(), Some("8SXvChNYFhRq4EZuZvnhjrB3jJRQCv4k3P4W6hesH3Ee"));
+        assert!(client.get_available_symbols().contains(&"WIFTEST/USD".to_string()));
+        assert!(client.unknown_symbols(&["WIFTEST".to_string()]).is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_available_symbols() {
         let client = SwitchboardClient::new_simulated();
@@ -1227,10 +1645,105 @@ mod tests {
             confidence: 0.01,
             timestamp: chrono::Utc::now().timestamp(),
             slot: 0,
+            min_price: 99.99,
+            max_price: 100.01,
+            price_change_24h: None,
+            stale: false,
         };
         assert!(SwitchboardClient::is_data_fresh(&feed, 60));
     }
 
+    #[tokio::test]
+    async fn test_stale_prices_are_flagged() {
+        let client = SwitchboardClient::new_simulated().with_max_staleness(30);
+        let feed = client.fetch_price("SOL/USD").await.unwrap();
+        assert!(!feed.stale);
+
+        // Backdate the cached quote past the staleness limit
+        {
+            let mut cache = client.price_cache.entries.lock().await;
+            let cached = cache.get_mut("SOL/USD").unwrap();
+            cached.value.timestamp -= 120;
+        }
+        let feed = client.fetch_price("SOL/USD").await.unwrap();
+        assert!(feed.stale);
+        assert!(feed.age_secs() >= 120);
+    }
+
+    #[tokio::test]
+    async fn test_price_cache_single_flight() {
+        let cache: PriceCache<f64> = PriceCache::new(Duration::from_millis(200));
+        let fetches = std::sync::atomic::AtomicU32::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok::<_, String>(150.0)
+        };
+
+        // Concurrent misses share one fetch
+        let (a, b, c) = tokio::join!(
+            cache.get_or_fetch("SOL/USD", fetch),
+            cache.get_or_fetch("SOL/USD", fetch),
+            cache.get_or_fetch("SOL/USD", fetch),
+        );
+        assert_eq!((a.unwrap(), b.unwrap(), c.unwrap()), (150.0, 150.0, 150.0));
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+        // Finished flights don't linger
+        assert_eq!(cache.in_flight_count().await, 0);
+
+        // Errors are not cached, other symbols fetch on their own
+        assert!(cache.get_or_fetch("BTC/USD", || async { Err::<f64, _>("rpc down".to_string()) }).await.is_err());
+        cache.get_or_fetch("BTC/USD", fetch).await.unwrap();
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(cache.in_flight_count().await, 0);
+
+        // Expired entries are refetched
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        cache.get_or_fetch("SOL/USD", fetch).await.unwrap();
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_consensus_price_rejects_outliers() {
+        let now = chrono::Utc::now().timestamp();
+        let source = |name: &str, price: f64, weight: f64| PriceSource {
+            source_name: name.to_string(),
+            price,
+            confidence: price * 0.01,
+            weight,
+            timestamp: now,
+            available: true,
+        };
+        let sources = vec![
+            source("Switchboard Oracle Quotes", 100.0, 0.4),
+            source("Jupiter Quote API", 100.4, 0.3),
+            source("Mobula API", 112.0, 0.2), // Manipulated pool
+            PriceSource { available: false, ..source("Switchboard On-Chain", 0.0, 0.0) },
+        ];
+
+        let consensus = ConsensusPrice::from_sources("SOL/USD", &sources, 2.0, now).unwrap();
+        assert_eq!(consensus.sources.len(), 2);
+        assert_eq!(consensus.rejected[0].source_name, "Mobula API");
+        // Weighted towards Switchboard's heavier weight
+        assert!(consensus.price > 100.0 && consensus.price < 100.2);
+        // The outlier is out of the price, but not out of the dispersion - the buy is refused
+        assert!(consensus.dispersion_pct > DEFAULT_MAX_ORACLE_DISPERSION_PCT);
+        assert!(consensus.refusal(DEFAULT_MAX_ORACLE_DISPERSION_PCT).is_some());
+        let agreeing = ConsensusPrice::from_sources("SOL/USD", &sources[..2], 2.0, now).unwrap();
+        assert!(agreeing.dispersion_pct < 0.3);
+        assert!(agreeing.refusal(DEFAULT_MAX_ORACLE_DISPERSION_PCT).is_none());
+        // A lone source can't be cross-checked
+        let lone = ConsensusPrice::from_sources("SOL/USD", &sources[..1], 2.0, now).unwrap();
+        assert!(lone.refusal(DEFAULT_MAX_ORACLE_DISPERSION_PCT).is_some());
+
+        // Two sources far apart both survive a wide outlier band - and the dispersion shows it
+        let split = vec![source("Switchboard Oracle Quotes", 100.0, 0.4), source("Jupiter Quote API", 104.0, 0.3)];
+        let consensus = ConsensusPrice::from_sources("SOL/USD", &split, 5.0, now).unwrap();
+        assert!(consensus.dispersion_pct > DEFAULT_MAX_ORACLE_DISPERSION_PCT);
+
+        assert!(ConsensusPrice::from_sources("SOL/USD", &sources[3..], 2.0, now).is_none());
+    }
+
     #[test]
     fn test_calculate_price_change() {
         let change = SwitchboardClient::calculate_price_change(100.0, 110.0);
@@ -0,0 +1,91 @@
+//! Symbol Registry
+//! Canonical "BASE/QUOTE" symbols shared by providers, the oracle and the performance tracker.
+//! Providers label signals with venues and quote tokens ("BONK (Jupiter)", "SOL/USDC", "bonk") while
+//! oracle feeds are keyed "BONK/USD" - compare or look up through `canonical`, never the raw label
+
+/// Quote every stablecoin pair is priced in
+pub const QUOTE_CURRENCY: &str = "USD";
+
+/// Venue label Jupiter-routed signals carry, e.g. "BONK (Jupiter)"
+pub const JUPITER_VENUE: &str = "Jupiter";
+
+/// Quotes that price as USD
+const USD_QUOTES: &[&str] = &["USD", "USDC", "USDT"];
+
+/// Wrapped / alternate tickers and the asset they track
+const ALIASES: &[(&str, &str)] = &[("WSOL", "SOL"), ("WBTC", "BTC"), ("WETH", "ETH")];
+
+/// Venue in a trailing parenthesis - "Jupiter" for "BONK (Jupiter)"
+pub fn venue(raw: &str) -> Option<&str> {
+    raw.trim()
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+        .map(|(_, venue)| venue.trim())
+        .filter(|venue| !venue.is_empty())
+}
+
+/// The label without its venue - "BONK (Jupiter)" -> "BONK", case and quote untouched (mints stay valid)
+pub fn strip_venue(raw: &str) -> &str {
+    let trimmed = raw.trim();
+    match venue(trimmed) {
+        Some(_) => trimmed.rsplit_once('(').map(|(pair, _)| pair.trim()).unwrap_or(trimmed),
+        None => trimmed,
+    }
+}
+
+/// Label a base symbol with the venue that routes it
+pub fn venue_symbol(base: &str, venue: &str) -> String {
+    format!("{} ({})", base, venue)
+}
+
+/// "BASE/QUOTE" with the venue stripped, tickers upper-cased and stablecoin quotes as USD
+/// Bare symbols quote in USD: "BONK (Jupiter)" -> "BONK/USD", "SOL/USDC" -> "SOL/USD", "BONK/SOL" stays
+pub fn canonical(raw: &str) -> String {
+    let (base, quote) = split(raw);
+    format!("{}/{}", base, quote)
+}
+
+/// Both labels name the same market once normalized
+pub fn same_market(a: &str, b: &str) -> bool {
+    canonical(a) == canonical(b)
+}
+
+fn split(raw: &str) -> (String, String) {
+    let pair = strip_venue(raw);
+    let (base, quote) = pair.split_once(['/', '-']).unwrap_or((pair, QUOTE_CURRENCY));
+    let quote = asset(quote);
+    let quote = if USD_QUOTES.contains(&quote.as_str()) { QUOTE_CURRENCY.to_string() } else { quote };
+    (asset(base), quote)
+}
+
+fn asset(ticker: &str) -> String {
+    let ticker = ticker.trim().trim_start_matches('$').to_uppercase();
+    ALIASES.iter()
+        .find(|(alias, _)| *alias == ticker)
+        .map(|(_, asset)| asset.to_string())
+        .unwrap_or(ticker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jupiter_suffix_maps_to_oracle_symbol() {
+        let label = venue_symbol("BONK", JUPITER_VENUE);
+        assert_eq!(label, "BONK (Jupiter)");
+        assert_eq!(venue(&label), Some("Jupiter"));
+        assert_eq!(canonical(&label), "BONK/USD");
+        assert!(same_market("BONK (Jupiter)", "BONK/USD"));
+        assert!(same_market("bonk (Jupiter)", "$BONK"));
+        assert!(same_market("SOL/USDC (Jupiter)", "SOL/USD"));
+
+        assert_eq!(canonical("SOL/USD"), "SOL/USD");
+        assert_eq!(canonical("SOL-USDT"), "SOL/USD");
+        assert_eq!(canonical("WSOL"), "SOL/USD");
+        assert_eq!(canonical("USDC/USD"), "USDC/USD");
+        assert_eq!(canonical("BONK/SOL"), "BONK/SOL");
+        assert_eq!(venue("SOL/USD"), None);
+        assert!(!same_market("BONK (Jupiter)", "BONKBOT/USD"));
+    }
+}
@@ -16,7 +16,7 @@ pub struct MarketData {
     pub spread: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TradingSignal {
     pub id: String,
     pub action: TradeAction,
@@ -27,12 +27,28 @@ pub struct TradingSignal {
     pub stop_loss: f64,
     pub take_profit: f64,
     pub timestamp: i64,
+    /// Aggregator that routed the fill (None for paper/simulated trades)
+    #[serde(default)]
+    pub aggregator: Option<String>,
+    /// Submit as an atomic Jito bundle (front-running protection for high-value trades)
+    #[serde(default)]
+    pub mev_protected: bool,
+    /// Per-signal swap slippage override (bps) - None uses the learned per-symbol tolerance
+    #[serde(default)]
+    pub slippage_bps: Option<u16>,
+    /// Per-signal compute-unit limit for the swap - None estimates it from the route
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,
+    /// Strategy wallet that executes this trade (None = the default wallet)
+    #[serde(default)]
+    pub wallet: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum TradeAction {
     Buy,
     Sell,
+    #[default]
     Hold,
 }
 
@@ -62,8 +78,457 @@ pub struct TradingEngine {
     pending_portfolio_updates: HashMap<String, (TradeAction, f64)>, // trade_id -> (action, size)
     /// Jupiter client for executing swaps (optional)
     pub jupiter_client: Option<Arc<crate::jupiter_integration::JupiterClient>>,
+    /// Aggregator fallback chain used to route real swaps (optional)
+    pub aggregator_router: Option<Arc<crate::dex_aggregator::AggregatorRouter>>,
     /// Fee optimizer for transaction fee tracking and optimization
     pub fee_optimizer: Option<Arc<Mutex<crate::fee_optimization::FeeOptimizer>>>,
+    /// Learned per-symbol slippage tolerances and auto-blocklist
+    pub slippage: crate::adaptive_slippage::AdaptiveSlippage,
+    /// Resting limit orders waiting for price to cross their limit
+    pending_orders: HashMap<String, PendingOrder>,
+    /// Pushes trade executions to WebSocket subscribers
+    pub ws_broadcaster: Option<crate::websocket::WSBroadcaster>,
+    /// Kill switch engaged - blocks every new trade (including paper trades) until resumed
+    pub halted: bool,
+    /// Set while the drawdown guard holds trading off (cooldown / operator re-enable)
+    pub drawdown_lockout: Option<crate::production_safeguards::DrawdownLockout>,
+    /// Set while the funding guard holds real trading off (PDA treasury below the minimum)
+    pub funding_lockout: Option<crate::production_safeguards::FundingLockout>,
+    /// Last PDA treasury reconciliation by the balance anomaly detector
+    pub balance_reconciliation: Option<crate::production_safeguards::BalanceReconciliation>,
+    /// Last check of tracked positions against the wallets' on-chain token balances
+    pub portfolio_reconciliation: Option<crate::production_safeguards::PortfolioReconciliation>,
+    /// Realized SOL movements of the default treasury not yet reconciled
+    pub pending_treasury_flows: Vec<crate::production_safeguards::TreasuryFlow>,
+    /// Submitted swaps from the default treasury awaiting confirmation, by signature
+    pub unsettled_treasury_flows: HashMap<String, crate::production_safeguards::UnsettledSwapFlow>,
+    /// OVERTRADING: When each symbol last traded (entries, exits and closes)
+    pub last_trade_at: HashMap<String, i64>,
+    /// Stamps `last_trade_at` - the shared replay clock while replaying, so cooldowns age in replayed time
+    pub clock: Arc<dyn crate::historical_data::ReplayClock>,
+    /// Recently submitted swap keys - one on-chain swap per signal across retries
+    pub swap_dedupe: crate::swap_dedupe::SwapDedupe,
+    /// How dry-run swaps are filled (price impact, random slippage, network fee)
+    pub paper_fills: PaperFillConfig,
+    /// Source of paper-fill randomness - seed with SIM_SEED to make dry runs reproducible
+    pub sim_rng: crate::simulation::SimRng,
+    /// Largest entry-price-to-quote move a marketplace signal may still trade at
+    pub quote_drift: QuoteDriftConfig,
+    /// Numeraire for balances, sizing and P&L - buys spend it, sells settle into it (BASE_CURRENCY)
+    pub base_currency: crate::dex_aggregator::BaseCurrency,
+    /// Execution audit trail for submitted swaps (optional)
+    pub database: Option<Arc<Mutex<crate::database::Database>>>,
+    /// Transaction fees paid per signal (SOL) - the fee drag reported by /performance
+    fee_ledger: HashMap<String, f64>,
+    /// Estimated network fee (lamports) booked for live swaps until their confirmed fill reports the real one
+    fee_estimates: HashMap<String, u64>,
+    /// Average-cost basis per symbol, maintained through partial fills and partial closes
+    pub cost_basis: HashMap<String, CostBasis>,
+    /// Cumulative P&L of closed quantity (net of sell fees)
+    pub realized_pnl: f64,
+    /// Jito block engine for MEV-protected swaps (optional)
+    pub jito_client: Option<Arc<crate::jito_bam::JitoBamClient>>,
+    /// Trades worth at least this much are flagged `mev_protected` and sent as Jito bundles
+    pub jito_min_trade_value: f64,
+    /// Once triggered no new trades start; the in-flight one finishes (optional)
+    pub shutdown: Option<crate::shutdown::Shutdown>,
+    /// Manual mint blacklist / whitelist checked before any marketplace signal trades (optional)
+    pub token_filter: Option<crate::security::TokenFilter>,
+    /// Oracle sources a marketplace buy's price must be verified against - fails closed (optional)
+    pub oracle_aggregator: Option<Arc<crate::switchboard_oracle::OracleAggregator>>,
+    /// Consensus dispersion (percent) above which a marketplace buy is refused
+    pub max_oracle_dispersion_pct: f64,
+    /// Provider -> strategy wallet assignments (unassigned providers trade from solana_client)
+    pub wallet_manager: crate::key_manager::WalletManager,
+    /// Connected strategy wallets by name, each with its own PDA treasury
+    pub strategy_wallets: HashMap<String, Arc<Mutex<crate::solana_integration::SolanaClient>>>,
+    /// (provider, symbol) -> the provider's position and the wallet holding it - providers sharing a token
+    /// keep separate entries, and a sell has to come from the wallet holding the tokens
+    position_wallets: HashMap<(String, String), WalletPosition>,
+    /// Realized P&L per wallet, net of sell fees - `realized_pnl` is their total
+    pub wallet_realized_pnl: HashMap<String, f64>,
+}
+
+/// A provider's open position in one symbol and the wallet holding its tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletPosition {
+    pub wallet: String,
+    pub size: f64,
+}
+
+/// Position sizes below this are float residue from partial sells, not tokens
+const POSITION_DUST: f64 = 1e-9;
+
+/// Limit order lifecycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    Pending,
+    Filled,
+    Cancelled,
+    Expired,
+}
+
+/// Dollar-cost-averaging parameters for splitting a large entry into smaller swaps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcaConfig {
+    pub chunks: u32,
+    pub interval_secs: u64,
+}
+
+impl Default for DcaConfig {
+    fn default() -> Self {
+        Self {
+            chunks: 4,
+            interval_secs: 30,
+        }
+    }
+}
+
+impl DcaConfig {
+    /// Load defaults from DCA_CHUNKS / DCA_INTERVAL_SECS
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            chunks: std::env::var("DCA_CHUNKS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(defaults.chunks)
+                .max(1),
+            interval_secs: std::env::var("DCA_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(defaults.interval_secs),
+        }
+    }
+}
+
+/// What a closed position is converted into (POSITION_CLOSE_TO)
+/// Swapping back to a base asset locks the gain - holding the token leaves "realized" profit exposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseTo {
+    #[default]
+    Sol,
+    Usdc,
+    Hold,
+}
+
+impl CloseTo {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "sol" => Some(Self::Sol),
+            "usdc" => Some(Self::Usdc),
+            "hold" => Some(Self::Hold),
+            _ => None,
+        }
+    }
+
+    /// Load from POSITION_CLOSE_TO (sol | usdc | hold, default the BASE_CURRENCY)
+    pub fn from_env() -> Self {
+        match std::env::var("POSITION_CLOSE_TO") {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|| {
+                log::warn!("⚠️ Invalid POSITION_CLOSE_TO '{}' - closing to SOL", value);
+                Self::Sol
+            }),
+            // Unset: settle into the base currency
+            Err(_) => match crate::dex_aggregator::BaseCurrency::from_env() {
+                crate::dex_aggregator::BaseCurrency::Sol => Self::Sol,
+                crate::dex_aggregator::BaseCurrency::Usdc => Self::Usdc,
+            },
+        }
+    }
+}
+
+/// Realized proceeds of an exit swap, in the asset the position actually ended up in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExitProceeds {
+    pub asset: CloseTo,
+    pub amount: f64,
+    #[serde(default)]
+    pub fill_price: Option<f64>, // Price the position's tokens sold at (None when nothing had to be sold)
+}
+
+/// Realistic execution for paper trades
+/// Dry-run fills pay the quoted price impact (when a Jupiter client is configured), a random
+/// slippage up to `random_slippage_bps`, and the fee optimizer's base network fee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperFillConfig {
+    pub enabled: bool,
+    pub random_slippage_bps: f64,
+}
+
+impl Default for PaperFillConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            random_slippage_bps: 0.0,
+        }
+    }
+}
+
+impl PaperFillConfig {
+    /// Load from PAPER_FILL_SIMULATION / PAPER_RANDOM_SLIPPAGE_BPS
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("PAPER_FILL_SIMULATION")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(defaults.enabled),
+            random_slippage_bps: std::env::var("PAPER_RANDOM_SLIPPAGE_BPS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(defaults.random_slippage_bps)
+                .max(0.0),
+        }
+    }
+}
+
+/// Default largest move between a signal's entry price and a fresh quote (5%)
+pub const DEFAULT_MAX_QUOTE_DRIFT_PCT: f64 = 5.0;
+/// Default largest price impact a buy may take at its real size (5%, the memecoin provider's own limit)
+pub const DEFAULT_MAX_PRICE_IMPACT_PCT: f64 = 5.0;
+
+/// Re-quote guard for marketplace signals
+/// A signal can sit in the queue while its token pumps - the swap is refused when a fresh
+/// quote has moved more than `max_drift_pct` from the signal's entry price (0 disables it)
+/// Providers estimate impact with a small probe; the same quote at the sized amount is held to
+/// `max_price_impact_pct` so a thin pool can't eat a full-size buy (0 disables it)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteDriftConfig {
+    pub max_drift_pct: f64,
+    pub max_price_impact_pct: f64,
+}
+
+impl Default for QuoteDriftConfig {
+    fn default() -> Self {
+        Self {
+            max_drift_pct: DEFAULT_MAX_QUOTE_DRIFT_PCT,
+            max_price_impact_pct: DEFAULT_MAX_PRICE_IMPACT_PCT,
+        }
+    }
+}
+
+impl QuoteDriftConfig {
+    /// Load from MAX_QUOTE_DRIFT_PCT / MAX_PRICE_IMPACT_PCT
+    pub fn from_env() -> Self {
+        let max_drift_pct = match std::env::var("MAX_QUOTE_DRIFT_PCT") {
+            Ok(value) => match value.parse::<f64>() {
+                Ok(pct) if pct >= 0.0 => pct,
+                _ => {
+                    log::warn!("⚠️ Invalid MAX_QUOTE_DRIFT_PCT '{}' - using {}", value, DEFAULT_MAX_QUOTE_DRIFT_PCT);
+                    DEFAULT_MAX_QUOTE_DRIFT_PCT
+                }
+            },
+            Err(_) => DEFAULT_MAX_QUOTE_DRIFT_PCT,
+        };
+        let max_price_impact_pct = match std::env::var("MAX_PRICE_IMPACT_PCT") {
+            Ok(value) => match value.parse::<f64>() {
+                Ok(pct) if pct >= 0.0 => pct,
+                _ => {
+                    log::warn!("⚠️ Invalid MAX_PRICE_IMPACT_PCT '{}' - using {}", value, DEFAULT_MAX_PRICE_IMPACT_PCT);
+                    DEFAULT_MAX_PRICE_IMPACT_PCT
+                }
+            },
+            Err(_) => DEFAULT_MAX_PRICE_IMPACT_PCT,
+        };
+        Self { max_drift_pct, max_price_impact_pct }
+    }
+    
+    /// Percent the quoted price has moved from the entry price, when that is past the limit
+    pub fn excess_drift(&self, entry_price: f64, quoted_price: f64) -> Option<f64> {
+        if self.max_drift_pct <= 0.0 || entry_price <= 0.0 {
+            return None;
+        }
+        let drift_pct = (quoted_price - entry_price) / entry_price * 100.0;
+        (drift_pct.abs() > self.max_drift_pct).then_some(drift_pct)
+    }
+    
+    /// The quoted price impact (percent), when it is past the cap
+    pub fn excess_impact(&self, price_impact_pct: f64) -> Option<f64> {
+        let impact = price_impact_pct.abs();
+        (self.max_price_impact_pct > 0.0 && impact > self.max_price_impact_pct).then_some(impact)
+    }
+}
+
+/// How a paper trade was filled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperFill {
+    pub signal_price: f64,
+    pub fill_price: f64,
+    pub price_impact_pct: f64,
+    pub slippage_bps: f64,
+    pub fee: f64, // Network fee in SOL
+}
+
+impl PaperFill {
+    /// Adverse fill - buys pay up, sells receive less
+    pub fn new(signal_price: f64, is_buy: bool, price_impact_pct: f64, slippage_bps: f64, fee: f64) -> Self {
+        let adverse = price_impact_pct.abs() / 100.0 + slippage_bps / 10_000.0;
+        let fill_price = if is_buy {
+            signal_price * (1.0 + adverse)
+        } else {
+            signal_price * (1.0 - adverse).max(0.0)
+        };
+        Self { signal_price, fill_price, price_impact_pct, slippage_bps, fee }
+    }
+}
+
+/// Average-cost basis of one position
+/// Buys fold their price (and fee) into the average entry; sells realize P&L against it
+/// and leave the average unchanged, so partial closes keep the remaining lot's basis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBasis {
+    pub size: f64,
+    pub average_entry: f64,
+}
+
+impl CostBasis {
+    pub fn buy(&mut self, size: f64, price: f64, fee: f64) {
+        let new_size = self.size + size;
+        if new_size <= 0.0 {
+            return;
+        }
+        self.average_entry = (self.average_entry * self.size + price * size + fee) / new_size;
+        self.size = new_size;
+    }
+
+    /// Close up to `size` at `price` - returns the realized P&L net of the fee
+    pub fn sell(&mut self, size: f64, price: f64, fee: f64) -> f64 {
+        let matched = size.min(self.size).max(0.0);
+        self.size -= matched;
+        matched * (price - self.average_entry) - fee
+    }
+
+    /// Swap a booked fill for the one that actually landed - returns the change in realized P&L
+    pub fn amend_fill(&mut self, is_buy: bool, booked: (f64, f64), filled: (f64, f64)) -> f64 {
+        let (booked_size, booked_price) = booked;
+        let (filled_size, filled_price) = filled;
+        if is_buy {
+            let remaining = self.size - booked_size;
+            if remaining > f64::EPSILON {
+                self.average_entry = (self.average_entry * self.size - booked_price * booked_size) / remaining;
+                self.size = remaining;
+            } else {
+                self.size = 0.0;
+            }
+            self.buy(filled_size, filled_price, 0.0);
+            0.0
+        } else {
+            // Sells leave the average alone - only the size sold and its proceeds change
+            self.size = (self.size + booked_size - filled_size).max(0.0);
+            filled_size * (filled_price - self.average_entry) - booked_size * (booked_price - self.average_entry)
+        }
+    }
+}
+
+/// One open position with its basis and mark, for /portfolio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionBreakdown {
+    pub symbol: String,
+    pub size: f64,
+    pub average_entry: f64,
+    pub cost_basis: f64,
+    /// Last known price (None until the symbol has market data)
+    pub mark_price: Option<f64>,
+    pub market_value: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+    pub unrealized_pnl_pct: Option<f64>,
+}
+
+/// What executing a signal would do right now, from POST /simulate/execute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeSimulation {
+    pub signal_id: String,
+    pub symbol: String,
+    pub action: String,
+    /// False when any check below would have refused the trade
+    pub would_execute: bool,
+    pub rejections: Vec<String>,
+    pub balance: f64,
+    /// Size after the risk checks (allocation, price impact, correlation)
+    pub size: f64,
+    pub trade_value: f64,
+    pub sizing_adjustments: Vec<String>,
+    pub entry_price: f64,
+    /// Price per token the Jupiter quote implies (None without a quote)
+    pub quoted_price: Option<f64>,
+    pub price_impact_pct: Option<f64>,
+    /// Quoted price vs entry price
+    pub slippage_bps: f64,
+    pub quote_error: Option<String>,
+    /// Network fee plus any Jito tip (SOL)
+    pub fee_sol: f64,
+    pub mev_protected: bool,
+    /// Projected P&L net of fees if the position exits at the target / stop (sells: at the quote)
+    pub pnl_at_target: f64,
+    pub pnl_at_stop: f64,
+}
+
+/// What a simulation decided before quoting: the pending rejections and the risk-sized trade
+struct SimulationPlan {
+    action: TradeAction,
+    rejections: Vec<String>,
+    size: f64,
+    adjustments: Vec<String>,
+    /// Entry price in the token the trade pays with
+    quote_entry_price: f64,
+}
+
+impl SimulationPlan {
+    /// Quote the risk-sized trade - None when sizing left nothing to trade
+    async fn quote(
+        &self,
+        jupiter: Option<&crate::jupiter_integration::JupiterClient>,
+        symbol: &str,
+        base_currency: crate::dex_aggregator::BaseCurrency,
+    ) -> Option<Result<crate::dex_aggregator::AggregatorQuote, String>> {
+        if self.size <= 0.0 {
+            return None;
+        }
+        let is_buy = matches!(self.action, TradeAction::Buy);
+        Some(match jupiter {
+            Some(jupiter) => crate::dex_aggregator::quote_trade(jupiter, symbol, self.size, self.quote_entry_price, is_buy, base_currency).await,
+            None => Err("No Jupiter client configured".to_string()),
+        })
+    }
+}
+
+/// Outcome of a DCA execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcaResult {
+    pub signal_id: String,
+    pub symbol: String,
+    pub chunks_planned: u32,
+    pub chunks_filled: u32,
+    pub target_size: f64,
+    pub filled_size: f64,
+    pub average_fill_price: f64,
+    /// Why the remaining chunks were not executed, if they weren't
+    pub aborted: Option<String>,
+}
+
+/// Limit order resting until price crosses `limit_price`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingOrder {
+    pub order_id: String,
+    pub signal: crate::signal_platform::TradingSignalData,
+    pub limit_price: f64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub status: OrderStatus,
+    pub fill_price: Option<f64>,
+}
+
+impl PendingOrder {
+    /// Buys fill at or below the limit, sells at or above it
+    pub fn is_triggered(&self, price: f64) -> bool {
+        match self.signal.action {
+            crate::signal_platform::SignalAction::Buy => price <= self.limit_price,
+            crate::signal_platform::SignalAction::Sell => price >= self.limit_price,
+            crate::signal_platform::SignalAction::Hold => false,
+        }
+    }
 }
 
 impl TradingEngine {
@@ -86,8 +551,41 @@ impl TradingEngine {
             risk_manager,
             solana_client: Some(solana_client),
             jupiter_client,
+            aggregator_router: None,
             fee_optimizer,
             pending_portfolio_updates: HashMap::new(), // FIX #3: Initialize pending updates tracker
+            slippage: crate::adaptive_slippage::AdaptiveSlippage::from_env(),
+            pending_orders: HashMap::new(),
+            ws_broadcaster: None,
+            halted: false,
+            drawdown_lockout: None,
+            funding_lockout: None,
+            balance_reconciliation: None,
+            portfolio_reconciliation: None,
+            pending_treasury_flows: Vec::new(),
+            unsettled_treasury_flows: HashMap::new(),
+            last_trade_at: HashMap::new(),
+            clock: Arc::new(crate::historical_data::WallClock),
+            swap_dedupe: crate::swap_dedupe::SwapDedupe::from_env(),
+            paper_fills: PaperFillConfig::from_env(),
+            sim_rng: crate::simulation::SimRng::from_env(),
+            quote_drift: QuoteDriftConfig::from_env(),
+            base_currency: crate::dex_aggregator::BaseCurrency::from_env(),
+            database: None,
+            token_filter: None,
+            oracle_aggregator: None,
+            max_oracle_dispersion_pct: crate::switchboard_oracle::DEFAULT_MAX_ORACLE_DISPERSION_PCT,
+            wallet_manager: crate::key_manager::WalletManager::new(),
+            strategy_wallets: HashMap::new(),
+            position_wallets: HashMap::new(),
+            wallet_realized_pnl: HashMap::new(),
+            fee_ledger: HashMap::new(),
+            fee_estimates: HashMap::new(),
+            cost_basis: HashMap::new(),
+            realized_pnl: 0.0,
+            jito_client: None,
+            jito_min_trade_value: f64::INFINITY,
+            shutdown: None,
         };
         
         // Log initialization status
@@ -99,6 +597,303 @@ impl TradingEngine {
         engine
     }
     
+    /// Publish trade executions to WebSocket subscribers
+    pub fn with_ws_broadcaster(mut self, broadcaster: crate::websocket::WSBroadcaster) -> Self {
+        self.ws_broadcaster = Some(broadcaster);
+        self
+    }
+    
+    /// Stamp trades on `clock` instead of the wall clock (session replay)
+    pub fn with_clock(mut self, clock: Arc<dyn crate::historical_data::ReplayClock>) -> Self {
+        self.clock = clock;
+        self
+    }
+    
+    /// Route real swaps through an aggregator fallback chain
+    pub fn with_aggregator_router(mut self, router: Arc<crate::dex_aggregator::AggregatorRouter>) -> Self {
+        self.aggregator_router = Some(router);
+        self
+    }
+    
+    /// Send trades worth at least `min_trade_value` as atomic Jito bundles
+    pub fn with_jito_bundles(mut self, jito_client: Arc<crate::jito_bam::JitoBamClient>, min_trade_value: f64) -> Self {
+        self.jito_client = Some(jito_client);
+        self.jito_min_trade_value = min_trade_value;
+        self
+    }
+    
+    /// Higher-confidence signals pay for faster landing (network fee and Jito tip alike)
+    fn fee_priority(confidence: f64) -> crate::fee_optimization::FeePriority {
+        use crate::fee_optimization::FeePriority;
+        if confidence >= 0.8 {
+            FeePriority::High
+        } else if confidence >= 0.6 {
+            FeePriority::Normal
+        } else {
+            FeePriority::Low
+        }
+    }
+    
+    /// Network fee for a swap - higher-confidence signals pay for higher priority
+    async fn estimate_fee_lamports(&self, confidence: f64) -> u64 {
+        let Some(ref fee_optimizer) = self.fee_optimizer else {
+            // Fallback to default fee if no optimizer available
+            return crate::fee_optimization::DEFAULT_BASE_FEE_LAMPORTS;
+        };
+        let priority = Self::fee_priority(confidence);
+        let recommended_fee = fee_optimizer.lock().await.estimate_fee(priority).recommended_fee;
+        log::debug!("💰 Using optimal fee estimate: {} lamports (priority: {:?}, confidence: {:.1}%)", 
+                   recommended_fee, priority, confidence * 100.0);
+        recommended_fee
+    }
+    
+    /// Compute-unit price (micro-lamports) for a swap at this confidence's priority, from the network's
+    /// recent prioritization fees - None until they've been sampled
+    async fn compute_unit_price(&self, confidence: f64) -> Option<u64> {
+        let fee_optimizer = self.fee_optimizer.as_ref()?;
+        fee_optimizer.lock().await.compute_unit_price(Self::fee_priority(confidence), Utc::now().timestamp())
+    }
+    
+    /// Whether a trade of this value should be flagged `mev_protected`
+    pub fn wants_jito_bundle(&self, trade_value: f64) -> bool {
+        self.jito_client.is_some() && trade_value >= self.jito_min_trade_value
+    }
+    
+    /// Slippage a swap for this signal may take: its own override if it set one, else the learned
+    /// per-symbol tolerance (SLIPPAGE_BASE_BPS until the symbol struggles). Never above the ceiling
+    pub fn swap_slippage_bps(&self, signal: &TradingSignal) -> u16 {
+        let ceiling_bps = self.slippage.config().ceiling_bps;
+        match signal.slippage_bps {
+            Some(bps) if bps > ceiling_bps => {
+                log::warn!("⚠️ {} asked for {} bps slippage - capped at the {} bps ceiling", signal.id, bps, ceiling_bps);
+                ceiling_bps
+            }
+            Some(bps) => bps,
+            None => self.slippage.tolerance_bps(&signal.symbol).min(ceiling_bps),
+        }
+    }
+    
+    /// Stop starting new trades once shutdown is triggered
+    pub fn with_shutdown(mut self, shutdown: crate::shutdown::Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+    
+    /// Refuse marketplace signals for blacklisted (or, in whitelist-only mode, unlisted) mints
+    pub fn with_token_filter(mut self, token_filter: crate::security::TokenFilter) -> Self {
+        self.token_filter = Some(token_filter);
+        self
+    }
+    
+    /// Route providers' trades to their assigned strategy wallets
+    pub fn with_strategy_wallets(
+        mut self,
+        wallet_manager: crate::key_manager::WalletManager,
+        strategy_wallets: HashMap<String, Arc<Mutex<crate::solana_integration::SolanaClient>>>,
+    ) -> Self {
+        self.wallet_manager = wallet_manager;
+        self.strategy_wallets = strategy_wallets;
+        self
+    }
+    
+    /// Client for a wallet by name - "default" is `solana_client`
+    pub fn wallet_client(&self, wallet_name: &str) -> Option<Arc<Mutex<crate::solana_integration::SolanaClient>>> {
+        if wallet_name == crate::wallet::DEFAULT_WALLET_NAME {
+            self.solana_client.clone()
+        } else {
+            self.strategy_wallets.get(wallet_name).cloned()
+        }
+    }
+    
+    /// WALLETS: Buys trade from the provider's assigned wallet; sells from the wallet holding the position
+    pub fn route_wallet(&self, provider_id: &str, symbol: &str, action: &TradeAction) -> String {
+        if matches!(action, TradeAction::Sell) {
+            if let Some((wallet_name, _)) = self.sell_source(Some(provider_id), symbol) {
+                return wallet_name;
+            }
+        }
+        self.wallet_manager.wallet_for_provider(provider_id).to_string()
+    }
+    
+    /// How much of `symbol` each wallet holds, by wallet name - positions bought before wallets were
+    /// tracked sit in the default wallet
+    pub fn symbol_wallets(&self, symbol: &str) -> Vec<(String, f64)> {
+        let mut held: std::collections::BTreeMap<String, f64> = std::collections::BTreeMap::new();
+        for ((_, held_symbol), position) in &self.position_wallets {
+            if held_symbol == symbol {
+                *held.entry(position.wallet.clone()).or_default() += position.size;
+            }
+        }
+        let position = self.portfolio.get(symbol).copied().unwrap_or(0.0).max(0.0);
+        let tracked: f64 = held.values().sum();
+        if position - tracked > POSITION_DUST {
+            *held.entry(crate::wallet::DEFAULT_WALLET_NAME.to_string()).or_default() += position - tracked;
+        } else if tracked > position {
+            // A fill came in short of its quote - the wallets share the shortfall
+            held.values_mut().for_each(|size| *size *= position / tracked);
+        }
+        held.into_iter().filter(|(_, size)| *size > POSITION_DUST).collect()
+    }
+    
+    /// How much of `symbol` `wallet_name` holds
+    fn wallet_holding(&self, wallet_name: &str, symbol: &str) -> f64 {
+        self.symbol_wallets(symbol).into_iter()
+            .find(|(held_in, _)| held_in == wallet_name)
+            .map_or(0.0, |(_, size)| size)
+    }
+    
+    /// Wallet and size a sell of `symbol` comes out of: the provider's own position, else the largest holding
+    pub fn sell_source(&self, provider_id: Option<&str>, symbol: &str) -> Option<(String, f64)> {
+        let own = provider_id.and_then(|provider_id| self.position_wallets.get(&(provider_id.to_string(), symbol.to_string())));
+        match own {
+            Some(position) => Some((position.wallet.clone(), position.size.min(self.wallet_holding(&position.wallet, symbol)))),
+            None => self.symbol_wallets(symbol).into_iter().max_by(|a, b| a.1.total_cmp(&b.1)),
+        }
+    }
+    
+    /// RECONCILIATION: Set what `wallet_name` holds of `symbol` to the size actually held on-chain
+    /// Its providers' positions and deployed capital scale with it, and the basis keeps its average entry.
+    /// Tokens the wallet holds untracked are filed under its first assigned provider
+    pub async fn correct_position(&mut self, symbol: &str, wallet_name: &str, size: f64) {
+        let size = size.max(0.0);
+        let tracked = self.wallet_holding(wallet_name, symbol);
+        let before = self.portfolio.get(symbol).copied().unwrap_or(0.0);
+        // Tokens added per provider - what their deployed capital grows by
+        let mut added: Vec<(String, f64)> = Vec::new();
+        if tracked > 0.0 {
+            let scale = size / tracked;
+            for ((provider_id, held_symbol), position) in self.position_wallets.iter_mut() {
+                if held_symbol == symbol && position.wallet == wallet_name {
+                    added.push((provider_id.clone(), position.size * (scale - 1.0)));
+                    position.size *= scale;
+                }
+            }
+        } else if size > 0.0 && wallet_name != crate::wallet::DEFAULT_WALLET_NAME {
+            let provider_id = self.wallet_manager.providers_for_wallet(wallet_name).into_iter().next().unwrap_or_default();
+            self.position_wallets.entry((provider_id.clone(), symbol.to_string()))
+                .or_insert_with(|| WalletPosition { wallet: wallet_name.to_string(), size: 0.0 })
+                .size += size;
+            added.push((provider_id, size));
+        }
+        self.position_wallets.retain(|(_, held_symbol), position| held_symbol != symbol || position.size > POSITION_DUST);
+        let total = (before - tracked + size).max(0.0);
+        self.portfolio.insert(symbol.to_string(), total);
+        if let Some(basis) = self.cost_basis.get_mut(symbol) {
+            basis.size = total;
+        }
+        
+        let price = self.cost_basis.get(symbol)
+            .map(|basis| basis.average_entry)
+            .filter(|price| *price > 0.0)
+            .or_else(|| self.last_price(symbol))
+            .unwrap_or(0.0);
+        let mut risk_manager = self.risk_manager.lock().await;
+        if size < tracked && before > 0.0 {
+            risk_manager.release_provider_exposure(symbol, (tracked - size) / before);
+        } else if size > tracked {
+            for (provider_id, tokens) in added.into_iter().filter(|(provider_id, _)| !provider_id.is_empty()) {
+                risk_manager.record_provider_buy(&provider_id, symbol, tokens * price);
+            }
+        }
+    }
+    
+    /// Track the provider's position in `signal.symbol` and its wallet after a fill. A sell draws down the
+    /// seller's own position first, then the wallet's other holders'; sold-out positions are forgotten
+    fn record_position_wallet(&mut self, provider_id: Option<&str>, signal: &TradingSignal) {
+        let wallet_name = signal.wallet.clone().unwrap_or_else(|| crate::wallet::DEFAULT_WALLET_NAME.to_string());
+        match signal.action {
+            TradeAction::Buy => {
+                let position = self.position_wallets
+                    .entry((provider_id.unwrap_or_default().to_string(), signal.symbol.clone()))
+                    .or_insert_with(|| WalletPosition { wallet: wallet_name.clone(), size: 0.0 });
+                position.wallet = wallet_name;
+                position.size += signal.size;
+            }
+            TradeAction::Sell => {
+                let mut holders: Vec<(String, String)> = self.position_wallets.iter()
+                    .filter(|((_, symbol), position)| *symbol == signal.symbol && position.wallet == wallet_name)
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                holders.sort_by_key(|(provider, _)| (Some(provider.as_str()) != provider_id, provider.clone()));
+                let mut remaining = signal.size;
+                for key in holders {
+                    if let Some(position) = self.position_wallets.get_mut(&key) {
+                        let sold = remaining.min(position.size);
+                        position.size -= sold;
+                        remaining -= sold;
+                    }
+                }
+                let sold_out = self.portfolio.get(&signal.symbol).copied().unwrap_or(0.0) <= 0.0;
+                self.position_wallets.retain(|(_, symbol), position| {
+                    *symbol != signal.symbol || (!sold_out && position.size > POSITION_DUST)
+                });
+            }
+            TradeAction::Hold => {}
+        }
+    }
+    
+    /// Book a fill: which wallet holds the position, and the capital its provider has deployed
+    async fn record_fill(&mut self, provider_id: Option<&str>, signal: &TradingSignal) {
+        self.record_position_wallet(provider_id, signal);
+        self.last_trade_at.insert(signal.symbol.clone(), self.clock.now_secs());
+        let mut risk_manager = self.risk_manager.lock().await;
+        match signal.action {
+            TradeAction::Buy => {
+                if let Some(provider_id) = provider_id {
+                    risk_manager.record_provider_buy(provider_id, &signal.symbol, signal.size * signal.price);
+                }
+            }
+            TradeAction::Sell => {
+                let remaining = self.portfolio.get(&signal.symbol).copied().unwrap_or(0.0).max(0.0);
+                risk_manager.release_provider_exposure(&signal.symbol, signal.size / (remaining + signal.size).max(f64::EPSILON));
+            }
+            TradeAction::Hold => {}
+        }
+    }
+    
+    /// CAPITAL ALLOCATION: What `provider_id` may still spend - its slice of the treasury (cash plus
+    /// every provider's deployed capital) less what its open positions already hold
+    pub async fn provider_capacity(&self, provider_id: &str, balance: f64) -> f64 {
+        let risk_manager = self.risk_manager.lock().await;
+        risk_manager.provider_capacity(provider_id, balance + risk_manager.total_provider_deployed())
+    }
+    
+    /// Allocation and utilization per provider, for /providers/capital
+    pub async fn provider_capital(&self, providers: &[String]) -> Vec<crate::risk_management::ProviderAllocation> {
+        let risk_manager = self.risk_manager.lock().await;
+        risk_manager.provider_allocation_report(providers, self.current_balance + risk_manager.total_provider_deployed())
+    }
+    
+    /// Balances, open positions and realized P&L of the default wallet and every strategy wallet, for /wallet/status
+    pub async fn wallet_balances(&self) -> Vec<crate::solana_integration::WalletBalance> {
+        let mut clients = Vec::new();
+        if let Some(ref solana_client) = self.solana_client {
+            clients.push((crate::wallet::DEFAULT_WALLET_NAME, solana_client));
+        }
+        let mut names: Vec<&String> = self.strategy_wallets.keys().collect();
+        names.sort();
+        clients.extend(names.into_iter().map(|name| (name.as_str(), &self.strategy_wallets[name])));
+        
+        let mut balances = Vec::new();
+        for (name, client) in clients {
+            let providers = self.wallet_manager.providers_for_wallet(name);
+            let mut balance = client.lock().await.wallet_summary(providers);
+            balance.realized_pnl = self.wallet_realized_pnl.get(name).copied().unwrap_or(0.0);
+            balance.positions = self.portfolio.keys()
+                .map(|symbol| (symbol.clone(), self.wallet_holding(name, symbol)))
+                .filter(|(_, size)| *size > 0.0)
+                .collect();
+            balances.push(balance);
+        }
+        balances
+    }
+    
+    /// Persist an audit trail of submitted swaps and their on-chain status
+    pub fn with_database(mut self, database: Arc<Mutex<crate::database::Database>>) -> Self {
+        self.database = Some(database);
+        self
+    }
+    
     /// Create new trading engine (legacy - uses simulated balance)
     pub fn new(risk_manager: Arc<Mutex<RiskManager>>) -> Self {
         Self {
@@ -110,8 +905,41 @@ impl TradingEngine {
             risk_manager,
             solana_client: None,
             jupiter_client: None,
+            aggregator_router: None,
             fee_optimizer: None,
             pending_portfolio_updates: HashMap::new(), // FIX #3: Initialize pending updates tracker
+            slippage: crate::adaptive_slippage::AdaptiveSlippage::from_env(),
+            pending_orders: HashMap::new(),
+            ws_broadcaster: None,
+            halted: false,
+            drawdown_lockout: None,
+            funding_lockout: None,
+            balance_reconciliation: None,
+            portfolio_reconciliation: None,
+            pending_treasury_flows: Vec::new(),
+            unsettled_treasury_flows: HashMap::new(),
+            last_trade_at: HashMap::new(),
+            clock: Arc::new(crate::historical_data::WallClock),
+            swap_dedupe: crate::swap_dedupe::SwapDedupe::from_env(),
+            paper_fills: PaperFillConfig::from_env(),
+            sim_rng: crate::simulation::SimRng::from_env(),
+            quote_drift: QuoteDriftConfig::from_env(),
+            base_currency: crate::dex_aggregator::BaseCurrency::from_env(),
+            database: None,
+            token_filter: None,
+            oracle_aggregator: None,
+            max_oracle_dispersion_pct: crate::switchboard_oracle::DEFAULT_MAX_ORACLE_DISPERSION_PCT,
+            wallet_manager: crate::key_manager::WalletManager::new(),
+            strategy_wallets: HashMap::new(),
+            position_wallets: HashMap::new(),
+            wallet_realized_pnl: HashMap::new(),
+            fee_ledger: HashMap::new(),
+            fee_estimates: HashMap::new(),
+            cost_basis: HashMap::new(),
+            realized_pnl: 0.0,
+            jito_client: None,
+            jito_min_trade_value: f64::INFINITY,
+            shutdown: None,
         }
     }
     
@@ -127,6 +955,12 @@ impl TradingEngine {
             client.sync_trading_budget_from_pda().await;
             let pda_balance = client.get_trading_budget();
             
+            // WALLETS: The balance is the default treasury's - each strategy wallet's budget stays on its
+            // own client, and buys routed to it size against that
+            for strategy_client in self.strategy_wallets.values() {
+                strategy_client.lock().await.sync_trading_budget_from_pda().await;
+            }
+            
             if self.initial_balance == 0.0 {
                 self.initial_balance = pda_balance;
             }
@@ -134,7 +968,7 @@ impl TradingEngine {
             
             // FIX #2: Sync RiskManager current_capital with actual balance
             let mut risk_manager = self.risk_manager.lock().await;
-            risk_manager.current_capital = pda_balance;
+            risk_manager.sync_treasury(pda_balance);
             drop(risk_manager);
             
             log::debug!("🔄 Trading engine balance synced from PDA: {:.6} SOL | Risk manager capital updated", pda_balance);
@@ -199,6 +1033,7 @@ impl TradingEngine {
                     stop_loss: data.price * 0.95,
                     take_profit: data.price * 1.05,
                     timestamp: Utc::now().timestamp(),
+                    ..Default::default()
                 };
                 self.trade_history.push(signal.clone());
                 return Some(signal);
@@ -216,6 +1051,7 @@ impl TradingEngine {
                             stop_loss: data.price * 1.05,
                             take_profit: data.price * 0.95,
                             timestamp: Utc::now().timestamp(),
+                            ..Default::default()
                         };
                         self.trade_history.push(signal.clone());
                         return Some(signal);
@@ -235,12 +1071,25 @@ impl TradingEngine {
     /// Execute trade using REAL Solana transactions (if Solana client available)
     /// Falls back to simulated execution if no Solana client or if dry_run is enabled
     /// In dry-run mode, performs paper trading that updates state for ML/RL learning
+    /// Holds the engine for the whole swap - callers sharing the engine use begin_trade and TradeStep::settle
     pub async fn execute_trade(
         &mut self, 
         signal: &TradingSignal, 
         trading_enabled: Option<&Arc<Mutex<bool>>>,
         dry_run: Option<&Arc<Mutex<bool>>>,
     ) -> bool {
+        self.begin_trade(signal, trading_enabled, dry_run).await.finish_locked(self).await
+    }
+    
+    /// Every engine-side check and book entry that comes before a live swap goes out
+    /// Simulated and rejected trades finish here; a live swap comes back as TradeStep::Swap to be sent,
+    /// and a paper trade that needs its price impact quoted as TradeStep::Paper
+    pub async fn begin_trade(
+        &mut self, 
+        signal: &TradingSignal, 
+        trading_enabled: Option<&Arc<Mutex<bool>>>,
+        dry_run: Option<&Arc<Mutex<bool>>>,
+    ) -> TradeStep {
         // Check if dry-run mode is enabled
         let is_dry_run = if let Some(dry_run_flag) = dry_run {
             *dry_run_flag.lock().await
@@ -248,6 +1097,18 @@ impl TradingEngine {
             false
         };
         
+        // Kill switch blocks everything, paper trades included - exits go through close_position
+        if self.halted {
+            log::warn!("🛑 Kill switch engaged - trade for {} blocked", signal.symbol);
+            return TradeStep::Done(false);
+        }
+        
+        // SHUTDOWN: The in-flight trade finishes, but no new one starts
+        if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_triggered()) {
+            log::warn!("🛑 Shutdown in progress - trade for {} not started", signal.symbol);
+            return TradeStep::Done(false);
+        }
+        
         // In dry-run mode, always use paper trading
         if is_dry_run {
             let action_str = match signal.action {
@@ -257,7 +1118,13 @@ impl TradingEngine {
             };
             log::info!("🧪 DRY-RUN MODE: Executing paper trade for {} {} {} at ${:.8}", 
                       action_str, signal.size, signal.symbol, signal.price);
-            return self.execute_paper_trade(signal).await;
+            return self.begin_paper_trade(signal).await;
+        }
+        
+        // FUNDING GUARD: never send a real swap from an unfunded treasury
+        if let Some(lockout) = &self.funding_lockout {
+            log::warn!("💸 Real trade for {} blocked: {}", signal.symbol, lockout.reason);
+            return TradeStep::Done(false);
         }
         
         // Check if trading is enabled
@@ -265,7 +1132,7 @@ impl TradingEngine {
             let is_enabled = *enabled.lock().await;
             if !is_enabled {
                 log::warn!("⚠️ Trading is disabled - trade execution blocked");
-                return false;
+                return TradeStep::Done(false);
             }
         }
         
@@ -284,23 +1151,29 @@ impl TradingEngine {
         
         if !is_valid {
             log::warn!("❌ Trade rejected by risk manager");
-            return false;
+            return TradeStep::Done(false);
         }
         
         // Execute REAL trade if Solana client is available
-        match &self.solana_client {
+        let solana_client = match signal.wallet {
+            Some(ref wallet_name) => match self.strategy_wallets.get(wallet_name) {
+                Some(strategy_client) => Some(strategy_client),
+                None => {
+                    log::error!("❌ Wallet {} for {} is not connected - trade blocked", wallet_name, signal.id);
+                    return TradeStep::Done(false);
+                }
+            },
+            None => self.solana_client.as_ref(),
+        };
+        match solana_client {
             Some(solana_client) => {
-                log::info!("🔗 Executing REAL Solana transaction via Jupiter API");
+                log::info!("🔗 Executing REAL Solana transaction{}",
+                          signal.wallet.as_ref().map(|w| format!(" from wallet {}", w)).unwrap_or_default());
                 let solana_client_clone = solana_client.clone();
-                let success = self.execute_real_trade(signal, solana_client_clone).await;
-                
-                if success {
-                    log::info!("✅ Real Solana transaction executed successfully");
-                } else {
-                    log::warn!("⚠️ Real Solana transaction execution returned false");
+                match self.prepare_real_trade(signal, solana_client_clone).await {
+                    Some(swap) => TradeStep::Swap(Box::new(swap)),
+                    None => TradeStep::Done(false),
                 }
-                
-                return success;
             }
             None => {
                 log::error!("❌ CRITICAL: No Solana client available - cannot execute real trades!");
@@ -308,81 +1181,171 @@ impl TradingEngine {
                 log::error!("   Ensure TradingEngine::new_with_solana() is used instead of TradingEngine::new()");
                 log::warn!("⚠️ Falling back to simulated execution (for testing only)");
                 log::warn!("   ⚠️  WARNING: This is NOT a real trade - only for testing!");
-                self.execute_simulated_trade(signal).await
+                TradeStep::Done(self.execute_simulated_trade(signal).await)
             }
         }
     }
     
-    /// Execute REAL Solana trade via Solana client
+    /// Execute REAL Solana trade via Solana client, holding the engine until it is booked
     async fn execute_real_trade(
         &mut self,
         signal: &TradingSignal,
         solana_client: Arc<Mutex<crate::solana_integration::SolanaClient>>,
     ) -> bool {
+        let Some(swap) = self.prepare_real_trade(signal, solana_client).await else { return false };
+        let outcome = swap.send().await;
+        self.finish_swap(swap, outcome).await
+    }
+    
+    /// Fee budget, routing, idempotency and the optimistic portfolio update for a REAL Solana trade
+    /// Returns the swap ready to send, or None if the trade must not go out
+    async fn prepare_real_trade(
+        &mut self,
+        signal: &TradingSignal,
+        solana_client: Arc<Mutex<crate::solana_integration::SolanaClient>>,
+    ) -> Option<PendingSwap> {
         let is_buy = matches!(signal.action, TradeAction::Buy);
         
         // FEE OPTIMIZATION: Get optimal fee estimate BEFORE executing trade
-        use crate::fee_optimization::FeePriority;
-        let (estimated_fee_lamports, _confirmation_time) = if let Some(ref fee_optimizer) = self.fee_optimizer {
-                    let optimizer = fee_optimizer.lock().await;
-                    // Get optimal fee estimate based on signal confidence (higher confidence = higher priority)
-                    let priority = if signal.confidence >= 0.8 {
-                        FeePriority::High
-                    } else if signal.confidence >= 0.6 {
-                        FeePriority::Normal
-                    } else {
-                        FeePriority::Low
-                    };
-                    let fee_estimate = optimizer.estimate_fee(priority);
-                    let recommended_fee = fee_estimate.recommended_fee;
-                    let confirmation_time = std::time::Duration::from_secs(1); // Default confirmation time
-                    
-                    log::debug!("💰 Using optimal fee estimate: {} lamports (priority: {:?}, confidence: {:.1}%)", 
-                               recommended_fee, priority, signal.confidence * 100.0);
-                    (recommended_fee, confirmation_time)
-                } else {
-                    // Fallback to default fee if no optimizer available
-                    let default_fee = 5000u64;
-                    let confirmation_time = std::time::Duration::from_secs(1);
-                    (default_fee, confirmation_time)
-                };
-        
-        // Execute real trade via Solana client with optimal fee estimate
-        let trade_start_time = std::time::Instant::now();
+        let estimated_fee_lamports = self.estimate_fee_lamports(signal.confidence).await;
         
-        // FIX #3: Store pending portfolio update BEFORE execution (for rollback)
-        let pending_update_key = format!("{}_{}", signal.id, signal.symbol); // Use signal.id instead of trade_id
-        self.pending_portfolio_updates.insert(pending_update_key.clone(), (signal.action.clone(), signal.size));
+        // MEV PROTECTION: Opted-in signals go out as a Jito bundle - the tip counts as a fee
+        let jito = self.jito_client.clone().filter(|_| signal.mev_protected);
+        let jito_tip_lamports = jito.as_ref().map_or(0, |jito| jito.effective_tip(Self::fee_priority(signal.confidence)));
         
-        // FIX #3: Update portfolio optimistically BEFORE execution
-        match signal.action {
-            TradeAction::Buy => {
-                *self.portfolio.entry(signal.symbol.clone()).or_insert(0.0) += signal.size;
-                log::debug!("📊 Portfolio updated optimistically (pre-execution): +{} {}", signal.size, signal.symbol);
-            }
-            TradeAction::Sell => {
-                if let Some(position) = self.portfolio.get_mut(&signal.symbol) {
-                    *position = (*position - signal.size).max(0.0);
-                    log::debug!("📊 Portfolio updated optimistically (pre-execution): -{} {}", signal.size, signal.symbol);
-                }
-            }
-            TradeAction::Hold => {}
+        // FEE BUDGET: Pause trading once fees for the rolling window are spent
+        // A bundle that doesn't land is retried with a bumped tip - budget for the last attempt's
+        let worst_case_tip_lamports = jito.as_ref().map_or(0, |jito| jito.worst_case_tip(jito_tip_lamports));
+        if !self.fee_budget_allows(estimated_fee_lamports + worst_case_tip_lamports).await {
+            return None;
         }
         
-        let mut client = solana_client.lock().await;
-        let trade_result = client.execute_trade(
-            &signal.symbol,
-            signal.size,
-            is_buy,
-            signal.price,
-            Some(estimated_fee_lamports), // PASS: Optimal fee estimate from fee optimizer
-        ).await;
-        drop(client); // Release lock early
+        // ROUTING: quote every configured aggregator and build the swap on the best one - no route means no trade
+        // Aggregators with an open circuit breaker are skipped, so a Jupiter outage falls back to Raydium
+        let route: Option<Arc<dyn crate::dex_aggregator::DexAggregator>> = match self.aggregator_router {
+            Some(ref router) => match router.route_trade(&signal.symbol, signal.size, signal.price, is_buy, self.swap_slippage_bps(signal), self.base_currency).await {
+                Ok(quote) => match router.aggregator(&quote.aggregator) {
+                    Some(route) => {
+                        log::info!("💱 Best quote for {} from {} (net out {}, impact {:.2}%) - executing there",
+                                  signal.symbol, quote.aggregator, quote.net_out_amount(), quote.price_impact_pct);
+                        Some(route)
+                    }
+                    None => {
+                        log::error!("❌ Quote for {} came from unknown aggregator {}", signal.symbol, quote.aggregator);
+                        return None;
+                    }
+                },
+                Err(e) => {
+                    log::error!("❌ No aggregator route for {}: {}", signal.symbol, e);
+                    return None;
+                }
+            },
+            None => self.jupiter_client.clone().map(|jupiter| jupiter as Arc<dyn crate::dex_aggregator::DexAggregator>),
+        };
+        
+        // IDEMPOTENCY: A signal may only produce one on-chain swap, even if the executor retries
+        use crate::swap_dedupe::{SwapCheck, SwapDedupe, BLOCKHASH_EXPIRY_SECS};
+        let swap_key = SwapDedupe::key_for(signal);
+        let now = Utc::now().timestamp();
+        match self.swap_dedupe.check(&swap_key, now) {
+            SwapCheck::Fresh => {}
+            SwapCheck::InFlight => {
+                log::warn!("🔁 Duplicate submission blocked for {} - a swap is already in flight", swap_key);
+                return None;
+            }
+            SwapCheck::Confirmed { signature } => {
+                log::warn!("🔁 Duplicate submission blocked for {} - already filled by {}", swap_key, signature);
+                return None;
+            }
+            SwapCheck::Submitted { signature, submitted_at } => {
+                // Ask the chain whether the earlier submission landed before deciding
+                let status = solana_client.lock().await.signature_status(&signature).await;
+                match status {
+                    Ok(Some(true)) => {
+                        self.swap_dedupe.mark_confirmed(&swap_key);
+                        log::warn!("🔁 Duplicate submission blocked for {} - {} is confirmed on-chain", swap_key, signature);
+                        return None;
+                    }
+                    Ok(Some(false)) => {
+                        log::info!("🔄 Earlier swap {} failed on-chain - resubmitting {}", signature, swap_key);
+                        self.swap_dedupe.forget(&swap_key);
+                    }
+                    Ok(None) if now - submitted_at > BLOCKHASH_EXPIRY_SECS => {
+                        log::info!("🔄 Earlier swap {} never landed and its blockhash expired - resubmitting {}", signature, swap_key);
+                        self.swap_dedupe.forget(&swap_key);
+                    }
+                    Ok(None) => {
+                        log::warn!("🔁 Duplicate submission blocked for {} - {} may still land", swap_key, signature);
+                        return None;
+                    }
+                    Err(e) => {
+                        log::warn!("🔁 Duplicate submission blocked for {} - cannot verify {}: {}", swap_key, signature, e);
+                        return None;
+                    }
+                }
+            }
+        }
+        self.swap_dedupe.begin(&swap_key, now);
+        
+        // FIX #3: Store pending portfolio update BEFORE execution (for rollback)
+        let pending_update_key = format!("{}_{}", signal.id, signal.symbol); // Use signal.id instead of trade_id
+        self.pending_portfolio_updates.insert(pending_update_key.clone(), (signal.action.clone(), signal.size));
+        
+        // FIX #3: Update portfolio optimistically BEFORE execution
+        match signal.action {
+            TradeAction::Buy => {
+                *self.portfolio.entry(signal.symbol.clone()).or_insert(0.0) += signal.size;
+                log::debug!("📊 Portfolio updated optimistically (pre-execution): +{} {}", signal.size, signal.symbol);
+            }
+            TradeAction::Sell => {
+                if let Some(position) = self.portfolio.get_mut(&signal.symbol) {
+                    *position = (*position - signal.size).max(0.0);
+                    log::debug!("📊 Portfolio updated optimistically (pre-execution): -{} {}", signal.size, signal.symbol);
+                }
+            }
+            TradeAction::Hold => {}
+        }
+        
+        Some(PendingSwap {
+            signal: signal.clone(),
+            solana_client,
+            route,
+            jito,
+            estimated_fee_lamports,
+            jito_tip_lamports,
+            compute_unit_price: self.compute_unit_price(signal.confidence).await,
+            slippage_bps: self.swap_slippage_bps(signal),
+            swap_key,
+            pending_update_key,
+            started: std::time::Instant::now(),
+        })
+    }
+    
+    /// Book a sent swap - the fill if it went out, the rollback if it didn't, tracking if nobody knows yet
+    pub async fn finish_swap(&mut self, swap: PendingSwap, outcome: Result<(String, u64), crate::solana_integration::SwapError>) -> bool {
+        let PendingSwap { signal, route, jito, estimated_fee_lamports, jito_tip_lamports: quoted_tip_lamports, swap_key, pending_update_key, started, .. } = swap;
+        let signal = &signal;
+        let is_buy = matches!(signal.action, TradeAction::Buy);
+        crate::metrics::Metrics::global().observe_swap_latency(started.elapsed());
+        // The aggregator that built the swap; simulated fills never reached a venue
+        let venue = match jito {
+            _ if crate::solana_integration::SolanaClient::dry_run_mode() => None,
+            Some(_) => route.as_ref().map(|route| format!("{}_jito_bundle", route.name())),
+            None => route.as_ref().map(|route| route.name().to_string()),
+        };
         
-        match trade_result {
-            Ok(trade_id) => {
+        match outcome {
+            Ok((trade_id, jito_tip_lamports)) => {
+                // Live swaps return their transaction signature - the dedupe and audit trail track it on-chain
+                self.swap_dedupe.record_submitted(&swap_key, &trade_id, Utc::now().timestamp());
+                if trade_id.parse::<solana_sdk::signature::Signature>().is_err() {
+                    // A simulated fill has nothing on-chain to verify - it is settled already
+                    self.swap_dedupe.mark_confirmed(&swap_key);
+                }
+                self.record_execution_submitted(signal, &trade_id, venue.as_deref()).await;
+                
                 // Measure actual execution time (approximation of confirmation time)
-                let actual_execution_time = trade_start_time.elapsed();
+                let actual_execution_time = started.elapsed();
                 
                 log::info!("✅ REAL trade executed: {} {} {} at ${:.8} | Trade ID: {} | Fee: {} lamports | Execution time: {:?}", 
                     if is_buy { "BUY" } else { "SELL" },
@@ -404,26 +1367,79 @@ impl TradingEngine {
                     // FIX #5: Use execution time as approximation, but note it's not true confirmation time
                     // For more accurate fee estimation, this should be replaced with actual blockchain confirmation time
                     optimizer.record_transaction(estimated_fee_lamports, actual_execution_time);
+                    optimizer.record_fee_spent(estimated_fee_lamports + jito_tip_lamports, Utc::now().timestamp());
                     log::debug!("💰 Recorded transaction for fee optimization: fee={} lamports, execution_time={:?} (approximation - not true blockchain confirmation)", 
                                estimated_fee_lamports, actual_execution_time);
                 }
                 
-                // Sync balance from PDA (actual balance from blockchain)
-                self.sync_balance_from_pda().await;
-                
-                // FIX #2: Sync RiskManager capital after trade execution (ensure accuracy)
-                // This is already handled in sync_balance_from_pda(), but we ensure it's done here too
-                
                 // FIX #3: Mark pending update as confirmed (after successful execution)
                 // In production, this would only happen after blockchain confirmation
                 // For now, we assume execution success = confirmation
                 self.pending_portfolio_updates.remove(&pending_update_key);
                 log::debug!("✅ Portfolio update confirmed for trade {}", trade_id);
                 
+                let fee_sol = (estimated_fee_lamports + jito_tip_lamports) as f64 / 1_000_000_000.0;
+                self.record_fee_paid(&signal.id, fee_sol);
+                if trade_id.parse::<solana_sdk::signature::Signature>().is_ok() {
+                    self.fee_estimates.insert(signal.id.clone(), estimated_fee_lamports);
+                }
+                let realized = self.book_fill(signal.wallet.as_deref(), &signal.symbol, &signal.action, signal.size, signal.price, self.fee_in_base(fee_sol));
+                // Network fees only come out of a SOL treasury
+                let treasury_fee = if self.base_currency == crate::dex_aggregator::BaseCurrency::Sol { fee_sol } else { 0.0 };
+                
+                // FIX #2: The swap may not have landed yet, so a PDA reading now may or may not include it -
+                // book the quote; the confirmation poller corrects it to the realized fill and the periodic
+                // PDA sync re-anchors the balance to the chain
+                let trade_value = signal.size * signal.price;
+                self.current_balance += if is_buy { -trade_value } else { trade_value } - treasury_fee;
+                {
+                    let mut risk_manager = self.risk_manager.lock().await;
+                    risk_manager.sync_treasury(self.current_balance);
+                    if !is_buy {
+                        risk_manager.record_close(realized);
+                    }
+                }
+                
+                if signal.wallet.is_none() {
+                    self.record_swap_flow(&trade_id, crate::production_safeguards::UnsettledSwapFlow {
+                        is_buy,
+                        estimated_sol: trade_value,
+                        fee_sol: treasury_fee,
+                    });
+                }
+                
+                // Record the fill with the route the swap went out on
+                let mut filled = signal.clone();
+                filled.aggregator = venue;
+                self.trade_history.push(filled);
+                
                 true  // FIX #3: Removed duplicate `true` statement
             }
             Err(e) => {
-                log::error!("❌ REAL trade execution failed: {}", e);
+                match e.signature {
+                    // Sent but unconfirmed: keep the key so a retry asks the chain instead of swapping twice
+                    // Nothing is booked - the confirmation poller books the fill if the swap lands after all
+                    Some(ref signature) => {
+                        log::error!("⏳ REAL trade outcome unknown: {} - tracking swap {} until it settles", e, signature);
+                        self.swap_dedupe.record_submitted(&swap_key, signature, Utc::now().timestamp());
+                        self.record_execution_submitted(signal, signature, venue.as_deref()).await;
+                        if signal.wallet.is_none() {
+                            // It may already have moved treasury SOL - excused at reconciliation until it settles
+                            let fee_sol = (estimated_fee_lamports + quoted_tip_lamports) as f64 / 1_000_000_000.0;
+                            let treasury_fee = if self.base_currency == crate::dex_aggregator::BaseCurrency::Sol { fee_sol } else { 0.0 };
+                            self.record_swap_flow(signature, crate::production_safeguards::UnsettledSwapFlow {
+                                is_buy,
+                                estimated_sol: signal.size * signal.price,
+                                fee_sol: treasury_fee,
+                            });
+                        }
+                    }
+                    // Rejected before anything reached the chain - safe to retry
+                    None => {
+                        log::error!("❌ REAL trade execution failed: {}", e);
+                        self.swap_dedupe.forget(&swap_key);
+                    }
+                }
                 
                 // FIX #3: Rollback portfolio update if trade execution failed
                 // Use the pending_update_key we stored before execution
@@ -450,9 +1466,48 @@ impl TradingEngine {
         }
     }
     
+    /// AUDIT TRAIL: Start tracking a submitted swap until the chain settles it
+    /// Only real transaction signatures are tracked - there is nothing on-chain to poll for anything else
+    async fn record_execution_submitted(&self, signal: &TradingSignal, signature: &str, venue: Option<&str>) {
+        let Some(ref database) = self.database else { return };
+        if signature.parse::<solana_sdk::signature::Signature>().is_err() {
+            log::debug!("Trade ID {} is not a transaction signature - not added to the execution audit trail", signature);
+            return;
+        }
+        
+        let now = Utc::now().timestamp();
+        let execution = crate::database::ExecutionRecord {
+            signature: signature.to_string(),
+            signal_id: signal.id.clone(),
+            symbol: signal.symbol.clone(),
+            action: format!("{:?}", signal.action).to_uppercase(),
+            size: signal.size,
+            price: signal.price,
+            submitted_at: now,
+            confirmation_slot: None,
+            status: crate::database::ExecutionStatus::Submitted,
+            error: None,
+            updated_at: now,
+            wallet: signal.wallet.clone(),
+            filled_size: None,
+            fill_price: None,
+            venue: venue.map(str::to_string),
+        };
+        if let Err(e) = database.lock().await.insert_execution(execution) {
+            log::warn!("⚠️ Failed to record execution {}: {}", signature, e);
+        }
+    }
+    
+    /// Latest swap submitted for a signal that hasn't failed on-chain
+    pub async fn live_execution(&self, signal_id: &str) -> Option<crate::database::ExecutionRecord> {
+        let database = self.database.as_ref()?;
+        let db = database.lock().await;
+        db.get_live_execution_for_signal(signal_id).cloned()
+    }
+    
     /// Execute paper trade for dry-run mode
     /// Properly tracks PnL, updates state, and records trades for ML/RL learning
-    async fn execute_paper_trade(&mut self, signal: &TradingSignal) -> bool {
+    async fn begin_paper_trade(&mut self, signal: &TradingSignal) -> TradeStep {
         // Initialize paper trading balance if not already set (10 SOL starting balance)
         const PAPER_STARTING_BALANCE: f64 = 10.0; // 10 SOL for paper trading
         if self.current_balance == 0.0 && self.initial_balance == 0.0 {
@@ -466,14 +1521,14 @@ impl TradingEngine {
                 risk_manager.initial_capital = PAPER_STARTING_BALANCE;
                 risk_manager.current_capital = PAPER_STARTING_BALANCE;
                 // CRITICAL FIX: Reset peak_capital to prevent false drawdown (10000 -> 10 = 99.9% drawdown)
-                risk_manager.peak_capital = PAPER_STARTING_BALANCE;
+                risk_manager.reset_peak();
                 log::info!("   RiskManager synced with paper trading balance: {:.8} SOL", PAPER_STARTING_BALANCE);
                 log::info!("   Peak capital reset to {:.8} SOL (prevents false drawdown)", PAPER_STARTING_BALANCE);
             } else if risk_manager.peak_capital > risk_manager.current_capital * 10.0 && risk_manager.current_capital < 1000.0 {
                 // FIX: If peak_capital is way out of sync (likely from initialization issue), reset it
                 log::warn!("   ⚠️ Peak capital ({:.8}) out of sync with current ({:.8}) - resetting for paper trading", 
                           risk_manager.peak_capital, risk_manager.current_capital);
-                risk_manager.peak_capital = risk_manager.current_capital;
+                risk_manager.reset_peak();
             }
             drop(risk_manager);
             
@@ -496,12 +1551,282 @@ impl TradingEngine {
         
         if !is_valid {
             log::warn!("❌ Paper trade rejected by risk manager");
+            return TradeStep::Done(false);
+        }
+        
+        let paper = self.paper_trade(signal);
+        if paper.jupiter.is_none() {
+            return TradeStep::Done(self.settle_paper_trade(signal, 0.0).await);
+        }
+        TradeStep::Paper(Box::new(paper))
+    }
+    
+    /// A paper trade, with the Jupiter client its fill's price impact is quoted from (None when fills aren't simulated)
+    fn paper_trade(&self, signal: &TradingSignal) -> PaperTrade {
+        PaperTrade {
+            signal: signal.clone(),
+            jupiter: self.jupiter_client.clone().filter(|_| self.paper_fills.enabled),
+            base_currency: self.base_currency,
+        }
+    }
+    
+    /// Simulate how a dry-run swap would have filled, given its quoted price impact
+    async fn simulate_paper_fill(&mut self, signal: &TradingSignal, price_impact_pct: f64) -> PaperFill {
+        let is_buy = matches!(signal.action, TradeAction::Buy);
+        if !self.paper_fills.enabled {
+            return PaperFill::new(signal.price, is_buy, 0.0, 0.0, 0.0);
+        }
+        
+        let slippage_bps = if self.paper_fills.random_slippage_bps > 0.0 {
+            self.sim_rng.unit() * self.paper_fills.random_slippage_bps
+        } else {
+            0.0
+        };
+        let fee = match &self.fee_optimizer {
+            Some(fee_optimizer) => fee_optimizer.lock().await.base_fee() as f64 / 1_000_000_000.0,
+            None => 0.0,
+        };
+        
+        PaperFill::new(signal.price, is_buy, price_impact_pct, slippage_bps, fee)
+    }
+    
+    /// False (with a clear log) when `fee_lamports` would overrun the fee budget
+    async fn fee_budget_allows(&self, fee_lamports: u64) -> bool {
+        let Some(ref fee_optimizer) = self.fee_optimizer else { return true };
+        let optimizer = fee_optimizer.lock().await;
+        if optimizer.can_afford_fee(fee_lamports) {
+            return true;
+        }
+        let status = optimizer.budget_status(Utc::now().timestamp());
+        log::error!("💸 FEE BUDGET EXHAUSTED - trading paused: {} of {} lamports spent in the last {}s, next fee {} lamports",
+                   status.spent_lamports, status.limit_lamports.unwrap_or(0), status.window_secs, fee_lamports);
+        log::error!("   Trading resumes as fees roll out of the window (raise FEE_BUDGET_LAMPORTS to resume sooner)");
+        false
+    }
+    
+    fn record_fee_paid(&mut self, signal_id: &str, fee_sol: f64) {
+        *self.fee_ledger.entry(signal_id.to_string()).or_insert(0.0) += fee_sol;
+    }
+    
+    /// Book the network fee a confirmed swap actually paid in place of the estimate booked when it went out
+    /// (a swap that landed after its submission timed out had nothing booked). Corrects the fee drag, the
+    /// fee budget and a SOL treasury
+    pub async fn record_realized_fee(&mut self, execution: &crate::database::ExecutionRecord, realized_lamports: u64) {
+        let estimated_lamports = self.fee_estimates.remove(&execution.signal_id).unwrap_or(0);
+        if realized_lamports == estimated_lamports {
+            return;
+        }
+        log::debug!("💰 Swap {} paid {} lamports in fees (estimated {})", execution.signature, realized_lamports, estimated_lamports);
+        let correction_sol = (realized_lamports as f64 - estimated_lamports as f64) / 1_000_000_000.0;
+        self.record_fee_paid(&execution.signal_id, correction_sol);
+        if execution.wallet.is_none() && self.base_currency == crate::dex_aggregator::BaseCurrency::Sol {
+            self.current_balance -= correction_sol;
+        }
+        if let Some(ref fee_optimizer) = self.fee_optimizer {
+            fee_optimizer.lock().await.correct_fee_spent(estimated_lamports, realized_lamports, Utc::now().timestamp());
+        }
+    }
+    
+    /// A SOL network fee in the base currency - priced at the last SOL mark for a USDC base (0 without one)
+    fn fee_in_base(&self, fee_sol: f64) -> f64 {
+        match self.base_currency {
+            crate::dex_aggregator::BaseCurrency::Sol => fee_sol,
+            crate::dex_aggregator::BaseCurrency::Usdc => self.sol_usd_price().map_or(0.0, |sol_price| fee_sol * sol_price),
+        }
+    }
+    
+    /// Last SOL mark in USD - the live feed's SOL/USD tick, else a traded SOL/USDC price
+    fn sol_usd_price(&self) -> Option<f64> {
+        ["SOL/USD", "SOL/USDC", "SOL"].iter().find_map(|symbol| self.last_price(symbol))
+    }
+    
+    /// USD value of one unit of the token `symbol` is paid for in (dex_aggregator::quote_token):
+    /// 1 for USDC, the SOL mark for SOL. Symbols that don't resolve to a mint never reach a venue,
+    /// so their prices are taken as they come
+    fn quote_token_usd(&self, symbol: &str) -> Option<f64> {
+        let Some((mint, _)) = crate::dex_aggregator::resolve_token(symbol) else { return Some(1.0) };
+        match crate::dex_aggregator::quote_token(&mint, self.base_currency).0 {
+            crate::dex_aggregator::SOL_MINT => self.sol_usd_price(),
+            _ => Some(1.0),
+        }
+    }
+    
+    /// CURRENCY: Providers and the oracle price in USD; trades are priced in the token they pay with
+    /// A USD price as the quote-token price a swap of `symbol` uses - None without a SOL mark to convert at
+    pub fn usd_to_quote_price(&self, symbol: &str, usd_price: f64) -> Option<f64> {
+        self.quote_token_usd(symbol).map(|usd| usd_price / usd)
+    }
+    
+    /// A quote-token price (a swap's fill or quote) back in USD
+    pub fn quote_to_usd_price(&self, symbol: &str, price: f64) -> Option<f64> {
+        self.quote_token_usd(symbol).map(|usd| price * usd)
+    }
+    
+    /// SOL as an amount of the token `symbol` trades against
+    fn sol_in_quote(&self, symbol: &str, sol: f64) -> Option<f64> {
+        let quote_is_sol = crate::dex_aggregator::resolve_token(symbol)
+            .map_or(self.base_currency == crate::dex_aggregator::BaseCurrency::Sol,
+                    |(mint, _)| crate::dex_aggregator::quote_token(&mint, self.base_currency).0 == crate::dex_aggregator::SOL_MINT);
+        if quote_is_sol {
+            return Some(sol);
+        }
+        self.sol_usd_price().map(|sol_price| sol * sol_price)
+    }
+    
+    /// A marketplace signal with its entry, target and stop in the token a swap of it pays with
+    fn quote_priced_signal(&self, signal_data: &crate::signal_platform::TradingSignalData) -> Result<crate::signal_platform::TradingSignalData, String> {
+        let convert = |usd: f64| self.usd_to_quote_price(&signal_data.symbol, usd);
+        match (convert(signal_data.entry_price), convert(signal_data.target_price), convert(signal_data.stop_loss)) {
+            (Some(entry_price), Some(target_price), Some(stop_loss)) => Ok(crate::signal_platform::TradingSignalData {
+                entry_price,
+                target_price,
+                stop_loss,
+                ..signal_data.clone()
+            }),
+            _ => Err(format!("No SOL price to convert {}'s USD prices into SOL - signal {} skipped",
+                             signal_data.symbol, signal_data.id)),
+        }
+    }
+    
+    /// Fill price of a marketplace signal in USD, the currency its provider priced it in
+    pub fn fill_price_usd(&self, signal_id: &str, symbol: &str) -> Option<f64> {
+        self.fill_price(signal_id).and_then(|price| self.quote_to_usd_price(symbol, price))
+    }
+    
+    /// Fees (SOL) paid for one signal's trades - None if it never filled
+    pub fn realized_fee(&self, signal_id: &str) -> Option<f64> {
+        self.fee_ledger.get(signal_id).copied()
+    }
+    
+    /// Fees (SOL) paid across every trade
+    pub fn total_fees_paid(&self) -> f64 {
+        self.fee_ledger.values().sum()
+    }
+    
+    /// Price a trade actually filled at (paper fills include simulated slippage)
+    pub fn fill_price(&self, signal_id: &str) -> Option<f64> {
+        self.trade_history.iter().rev()
+            .find(|t| t.id == signal_id)
+            .map(|t| t.price)
+    }
+    
+    /// Size a trade actually filled at - the reconciled on-chain amount once a live swap is confirmed
+    pub fn fill_size(&self, signal_id: &str) -> Option<f64> {
+        self.trade_history.iter().rev()
+            .find(|t| t.id == signal_id)
+            .map(|t| t.size)
+    }
+    
+    /// PARTIAL FILLS: Replace the assumed full fill of a confirmed swap with what the chain moved
+    /// Corrects the position (applied optimistically at the requested size), the balance (booked at
+    /// the quote) and the recorded trade's size and price. Returns the average fill price, None if
+    /// the fill can't be priced
+    pub fn apply_swap_fill(&mut self, execution: &crate::database::ExecutionRecord, fill: &crate::rpc_client::SwapFill) -> Option<f64> {
+        let fill_price = fill.average_price()?;
+        if !self.trade_history.iter().any(|t| t.id == execution.signal_id) {
+            return Some(self.book_late_swap(execution, fill, fill_price));
+        }
+        let shortfall = execution.size - fill.token_amount;
+        if shortfall.abs() > execution.size * 0.001 {
+            log::warn!("🧩 Swap {} for {} filled {:.6} of {:.6} {} at ${:.8} (quoted ${:.8}) - correcting the position",
+                      execution.signature, execution.signal_id, fill.token_amount, execution.size,
+                      execution.symbol, fill_price, execution.price);
+        } else {
+            log::info!("🧾 Swap {} for {} fully filled at ${:.8} (quoted ${:.8})",
+                      execution.signature, execution.signal_id, fill_price, execution.price);
+        }
+        
+        // A short buy leaves less in the position; a short sell leaves more behind
+        let position = self.portfolio.entry(execution.symbol.clone()).or_insert(0.0);
+        if execution.action == "BUY" {
+            *position = (*position - shortfall).max(0.0);
+        } else {
+            *position = (*position + shortfall).max(0.0);
+        }
+        
+        // The treasury paid (or received) the realized quote amount, not the quoted trade value
+        let booked_value = execution.size * execution.price;
+        self.current_balance += if execution.action == "BUY" {
+            booked_value - fill.quote_amount
+        } else {
+            fill.quote_amount - booked_value
+        };
+        
+        if let Some(trade) = self.trade_history.iter_mut().rev().find(|t| t.id == execution.signal_id) {
+            trade.size = fill.token_amount;
+            trade.price = fill_price;
+        }
+        if let Some(basis) = self.cost_basis.get_mut(&execution.symbol) {
+            let amended = basis.amend_fill(execution.action == "BUY",
+                                           (execution.size, execution.price),
+                                           (fill.token_amount, fill_price));
+            self.realized_pnl += amended;
+            *self.wallet_realized_pnl.entry(execution.wallet.clone().unwrap_or_else(|| crate::wallet::DEFAULT_WALLET_NAME.to_string()))
+                .or_default() += amended;
+        }
+        self.settle_swap_flow(&execution.signature, Some(fill.quote_amount));
+        Some(fill_price)
+    }
+    
+    /// A swap whose submission timed out was never booked - it landed after all, so book what it moved
+    fn book_late_swap(&mut self, execution: &crate::database::ExecutionRecord, fill: &crate::rpc_client::SwapFill, fill_price: f64) -> f64 {
+        let action = if execution.action == "BUY" { TradeAction::Buy } else { TradeAction::Sell };
+        log::warn!("🧾 Swap {} for {} landed after its submission timed out - booking {:.6} {} at ${:.8}",
+                  execution.signature, execution.signal_id, fill.token_amount, execution.symbol, fill_price);
+        
+        let position = self.portfolio.entry(execution.symbol.clone()).or_insert(0.0);
+        match action {
+            TradeAction::Buy => {
+                *position += fill.token_amount;
+                self.current_balance -= fill.quote_amount;
+            }
+            _ => {
+                *position = (*position - fill.token_amount).max(0.0);
+                self.current_balance += fill.quote_amount;
+            }
+        }
+        self.book_fill(execution.wallet.as_deref(), &execution.symbol, &action, fill.token_amount, fill_price, 0.0);
+        if execution.wallet.is_none() && !self.settle_swap_flow(&execution.signature, Some(fill.quote_amount)) {
+            self.record_treasury_flow(if action == TradeAction::Buy { -fill.quote_amount } else { fill.quote_amount });
+        }
+        self.trade_history.push(TradingSignal {
+            id: execution.signal_id.clone(),
+            action,
+            symbol: execution.symbol.clone(),
+            price: fill_price,
+            confidence: 0.0,
+            size: fill.token_amount,
+            stop_loss: 0.0,
+            take_profit: 0.0,
+            timestamp: execution.submitted_at,
+            // Records from before venues were tracked could only have gone out through Jupiter
+            aggregator: Some(execution.venue.clone().unwrap_or_else(|| "jupiter".to_string())),
+            wallet: execution.wallet.clone(),
+            ..Default::default()
+        });
+        fill_price
+    }
+    
+    /// Apply a paper trade to balance and portfolio, then record it for ML/RL learning
+    /// `price_impact_pct` is quoted beforehand (PaperTrade::price_impact_pct) so Jupiter isn't called under the engine lock
+    async fn settle_paper_trade(&mut self, signal: &TradingSignal, price_impact_pct: f64) -> bool {
+        // FILL MODEL: Paper trades pay the impact, slippage and fees a real swap would
+        let fill = self.simulate_paper_fill(signal, price_impact_pct).await;
+        let fee_lamports = (fill.fee * 1_000_000_000.0).round() as u64;
+        // FEE BUDGET: Paper trades spend the budget too, so dry runs show when live trading would pause
+        if fee_lamports > 0 && !self.fee_budget_allows(fee_lamports).await {
             return false;
         }
+        if fill.fill_price != fill.signal_price || fill.fee > 0.0 {
+            log::info!("🧪 Simulated fill for {}: ${:.8} -> ${:.8} (impact {:.3}%, slippage {:.1} bps, fee {:.9} SOL)",
+                      signal.symbol, fill.signal_price, fill.fill_price, fill.price_impact_pct, fill.slippage_bps, fill.fee);
+        }
+        let signal = &TradingSignal { price: fill.fill_price, ..signal.clone() };
+        let fee = self.fee_in_base(fill.fee);
         
         let success = match signal.action {
             TradeAction::Buy => {
-                let cost = signal.size * signal.price;
+                let cost = signal.size * signal.price + fee;
                 if cost <= self.current_balance {
                     self.current_balance -= cost;
                     *self.portfolio.entry(signal.symbol.clone()).or_insert(0.0) += signal.size;
@@ -515,16 +1840,13 @@ impl TradingEngine {
                 }
             }
             TradeAction::Sell => {
-                // Calculate PnL before getting mutable borrow
-                let pnl = Self::calculate_pnl_for_sell_static(&self.trade_history, &signal.symbol, signal.size, signal.price);
-                
                 if let Some(position) = self.portfolio.get_mut(&signal.symbol) {
                     if *position >= signal.size {
                         *position -= signal.size;
-                        self.current_balance += signal.size * signal.price;
+                        self.current_balance += signal.size * signal.price - fee;
                         
-                        log::info!("🧪 [PAPER TRADE] Sold {} {} at ${:.8} (PnL: ${:.8})", 
-                                  signal.size, signal.symbol, signal.price, pnl);
+                        log::info!("🧪 [PAPER TRADE] Sold {} {} at ${:.8}", 
+                                  signal.size, signal.symbol, signal.price);
                         true
                     } else {
                         log::warn!("❌ Insufficient position for sell order (position: {}, required: {})", 
@@ -543,11 +1865,22 @@ impl TradingEngine {
         };
         
         if success {
+            if fee_lamports > 0 {
+                if let Some(ref fee_optimizer) = self.fee_optimizer {
+                    fee_optimizer.lock().await.record_fee_spent(fee_lamports, Utc::now().timestamp());
+                }
+                self.record_fee_paid(&signal.id, fill.fee);
+            }
+            let realized = self.book_fill(signal.wallet.as_deref(), &signal.symbol, &signal.action, signal.size, signal.price, fee);
+            if signal.action == TradeAction::Sell {
+                log::info!("🧪 [PAPER TRADE] Realized ${:.8} on {} {}", realized, signal.size, signal.symbol);
+            }
+            
             // Record trade in trade_history for ML/RL learning
             self.trade_history.push(signal.clone());
             
-            // Record trade in risk manager with proper PnL
-            self.record_paper_trade_in_risk_manager(signal).await;
+            // Record trade in risk manager with the cost-basis P&L
+            self.record_paper_trade_in_risk_manager(signal, realized).await;
             
             // CRITICAL: Sync RiskManager's current_capital with trading engine's balance
             // This ensures the dashboard shows correct paper trading balance
@@ -568,58 +1901,14 @@ impl TradingEngine {
         success
     }
     
-    /// Calculate PnL for a sell trade by matching with previous buy trades (FIFO)
-    fn calculate_pnl_for_sell(&self, symbol: &str, sell_size: f64, sell_price: f64) -> f64 {
-        Self::calculate_pnl_for_sell_static(&self.trade_history, symbol, sell_size, sell_price)
-    }
-    
-    /// Static helper to calculate PnL without borrowing self
-    fn calculate_pnl_for_sell_static(trade_history: &Vec<TradingSignal>, symbol: &str, sell_size: f64, sell_price: f64) -> f64 {
-        // Find all buy trades for this symbol
-        let buy_trades: Vec<&TradingSignal> = trade_history.iter()
-            .filter(|t| t.symbol == symbol && matches!(t.action, TradeAction::Buy))
-            .collect();
-        
-        if buy_trades.is_empty() {
-            // No buy trades found, assume average cost basis
-            return 0.0;
-        }
-        
-        // Calculate average cost basis from buy trades
-        let mut total_cost = 0.0;
-        let mut total_size = 0.0;
-        
-        for trade in buy_trades {
-            total_cost += trade.size * trade.price;
-            total_size += trade.size;
-        }
-        
-        if total_size == 0.0 {
-            return 0.0;
-        }
-        
-        let avg_cost_basis = total_cost / total_size;
-        let pnl = (sell_price - avg_cost_basis) * sell_size;
-        
-        pnl
-    }
-    
-    /// Record paper trade in risk manager with proper PnL calculation
-    async fn record_paper_trade_in_risk_manager(&self, signal: &TradingSignal) {
+    /// Record paper trade in risk manager with the P&L `book_fill` realized
+    async fn record_paper_trade_in_risk_manager(&self, signal: &TradingSignal, pnl: f64) {
         let action_str = match signal.action {
             TradeAction::Buy => "BUY",
             TradeAction::Sell => "SELL",
             TradeAction::Hold => "HOLD",
         };
         
-        // Calculate PnL for sell trades
-        let pnl = match signal.action {
-            TradeAction::Sell => {
-                self.calculate_pnl_for_sell(&signal.symbol, signal.size, signal.price)
-            }
-            _ => 0.0,
-        };
-        
         let trade = crate::risk_management::Trade {
             id: format!("paper_{}", signal.id),
             symbol: signal.symbol.clone(),
@@ -671,25 +1960,21 @@ impl TradingEngine {
         };
         
         if success {
-            self.record_trade_in_risk_manager(signal, "simulated").await;
+            let realized = self.book_fill(signal.wallet.as_deref(), &signal.symbol, &signal.action, signal.size, signal.price, 0.0);
+            self.record_trade_in_risk_manager(signal, "simulated", realized).await;
         }
         
         success
     }
     
     /// Record trade in risk manager
-    async fn record_trade_in_risk_manager(&self, signal: &TradingSignal, trade_id: &str) {
+    async fn record_trade_in_risk_manager(&self, signal: &TradingSignal, trade_id: &str, pnl: f64) {
             let action_str = match signal.action {
                 TradeAction::Buy => "BUY",
                 TradeAction::Sell => "SELL",
                 TradeAction::Hold => "HOLD",
             };
             
-            let pnl = match signal.action {
-                TradeAction::Sell => signal.size * signal.price, // Simplified P&L calculation
-                _ => 0.0,
-            };
-            
             let trade = crate::risk_management::Trade {
             id: trade_id.to_string(),
                 symbol: signal.symbol.clone(),
@@ -712,84 +1997,1043 @@ impl TradingEngine {
         trading_enabled: Option<&Arc<Mutex<bool>>>,
         dry_run: Option<&Arc<Mutex<bool>>>,
     ) -> Result<String, String> {
+        Self::verify_oracle_price(self.oracle_check(), signal_data).await?;
+        let mut trade = self.begin_marketplace_signal(signal_data, trading_enabled, dry_run).await?;
+        let step = std::mem::replace(&mut trade.step, TradeStep::Done(false));
+        trade.step = TradeStep::Done(step.finish_locked(self).await);
+        self.finish_marketplace_signal(signal_data, trade).await
+    }
+    
+    /// execute_marketplace_signal for callers sharing the engine: it is unlocked while the swap is in flight
+    pub async fn execute_marketplace_signal_shared(
+        engine: &Arc<Mutex<Self>>,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        trading_enabled: Option<&Arc<Mutex<bool>>>,
+        dry_run: Option<&Arc<Mutex<bool>>>,
+    ) -> Result<String, String> {
+        let oracle_check = engine.lock().await.oracle_check();
+        Self::verify_oracle_price(oracle_check, signal_data).await?;
+        let trade = engine.lock().await.begin_marketplace_signal(signal_data, trading_enabled, dry_run).await?;
+        let trade = trade.settle(engine).await;
+        engine.lock().await.finish_marketplace_signal(signal_data, trade).await
+    }
+    
+    /// The oracle aggregator and dispersion limit buys are checked against, cloned out of the lock
+    fn oracle_check(&self) -> Option<(Arc<crate::switchboard_oracle::OracleAggregator>, f64)> {
+        self.oracle_aggregator.clone().map(|aggregator| (aggregator, self.max_oracle_dispersion_pct))
+    }
+    
+    /// ORACLE CONSENSUS: Refuse a buy unless enough oracle sources agree on its price (exits still go out).
+    /// The sources are network calls - callers sharing the engine run this with it unlocked
+    async fn verify_oracle_price(
+        oracle_check: Option<(Arc<crate::switchboard_oracle::OracleAggregator>, f64)>,
+        signal_data: &crate::signal_platform::TradingSignalData,
+    ) -> Result<(), String> {
+        match oracle_check {
+            Some((aggregator, max_dispersion_pct)) if signal_data.action == crate::signal_platform::SignalAction::Buy => {
+                aggregator.verify_price(&signal_data.symbol, max_dispersion_pct).await
+                    .map(|_| ())
+                    .map_err(|reason| format!("Oracle check refused signal {}: {}", signal_data.id, reason))
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    /// The dry-run flag a marketplace signal trades under - `forced` (set) for a force_dry_run signal,
+    /// else the global one. The most restrictive wins, so a signal forced to paper never trades live
+    fn signal_dry_run<'a>(
+        signal_data: &crate::signal_platform::TradingSignalData,
+        dry_run: Option<&'a Arc<Mutex<bool>>>,
+        forced: &'a Arc<Mutex<bool>>,
+    ) -> Option<&'a Arc<Mutex<bool>>> {
+        if signal_data.force_dry_run { Some(forced) } else { dry_run }
+    }
+    
+    /// Gates, sizing and begin_trade for a marketplace signal - the swap itself (if live) is left to send
+    pub async fn begin_marketplace_signal(
+        &mut self,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        trading_enabled: Option<&Arc<Mutex<bool>>>,
+        dry_run: Option<&Arc<Mutex<bool>>>,
+    ) -> Result<MarketplaceTrade, String> {
+        // TOKEN FILTER: The manual override beats every provider's safety checks
+        // Only buys are refused - a token blacklisted after we bought it can still be sold
+        if let Some(token_filter) = self.token_filter.as_ref().filter(|_| signal_data.action == crate::signal_platform::SignalAction::Buy) {
+            if let Err(reason) = token_filter.check(signal_data.filter_token()).await {
+                return Err(format!("Token filter refused signal {}: {}", signal_data.id, reason));
+            }
+        }
+        
         // Convert marketplace signal to trading signal
         let action = match signal_data.action {
             crate::signal_platform::SignalAction::Buy => TradeAction::Buy,
             crate::signal_platform::SignalAction::Sell => TradeAction::Sell,
             crate::signal_platform::SignalAction::Hold => TradeAction::Hold,
         };
+        // CURRENCY: Size and swap in the token the trade pays with - the provider priced it in USD
+        let priced = self.quote_priced_signal(signal_data)?;
         
         // FIX #6: Calculate position size with locked balance access to prevent race conditions
         // Check if we're in dry-run mode - don't sync from PDA in paper trading
-        let is_dry_run = if let Some(dry_run_flag) = dry_run {
+        let global_dry_run = if let Some(dry_run_flag) = dry_run {
             *dry_run_flag.lock().await
         } else {
             false
         };
         
-        let position_size = if matches!(action, TradeAction::Buy) {
+        // Most restrictive wins: a signal forced to paper never trades live, whatever the global mode
+        let is_dry_run = global_dry_run || signal_data.force_dry_run;
+        let forced_dry_run = Arc::new(Mutex::new(true));
+        let dry_run = Self::signal_dry_run(signal_data, dry_run, &forced_dry_run);
+        log::info!("▶️ Executing signal {} from {} in {} mode{}",
+                  signal_data.id, signal_data.provider,
+                  if is_dry_run { "PAPER" } else { "LIVE" },
+                  if is_dry_run && !global_dry_run { " (forced per signal/provider)" } else { "" });
+        
+        // WALLETS: A provider assigned to a strategy wallet only trades that wallet's treasury
+        let wallet_name = self.route_wallet(&signal_data.provider, &signal_data.symbol, &action);
+        let strategy_client = if wallet_name == crate::wallet::DEFAULT_WALLET_NAME {
+            None
+        } else {
+            Some(self.strategy_wallets.get(&wallet_name).cloned().ok_or_else(|| {
+                format!("Wallet {} assigned to {} is not connected", wallet_name, signal_data.provider)
+            })?)
+        };
+        
+        let current_balance = if matches!(action, TradeAction::Buy) {
             // Only sync balance from PDA if NOT in dry-run mode
             // In dry-run mode, use paper trading balance (already initialized)
             if !is_dry_run {
                 self.sync_balance_from_pda().await;
             }
             // Immediately capture balance to ensure consistency
-            let current_balance = self.current_balance; // Use paper balance in dry-run mode
-            let max_cost = current_balance * 0.1; // Use 10% of balance per signal
-            let calculated_size = max_cost / signal_data.entry_price;
-            
-            // Validate we have sufficient balance for the calculated size
-            if calculated_size <= 0.0 || (calculated_size * signal_data.entry_price) > current_balance {
-                return Err(format!("Insufficient balance for signal: {} (balance: {:.6}, required: {:.6})", 
-                    signal_data.id, current_balance, calculated_size * signal_data.entry_price));
+            match strategy_client.filter(|_| !is_dry_run) {
+                Some(strategy_client) => strategy_client.lock().await.get_trading_budget(), // Synced just above
+                None => self.current_balance, // Use paper balance in dry-run mode
             }
-            
-            calculated_size
         } else {
-            // For sell, use existing position
-            self.portfolio.get(&signal_data.symbol).copied().unwrap_or(0.0)
+            self.current_balance
         };
+        let (position_size, _) = self.risk_sized_position(&priced, &action, current_balance).await?;
         
-        if position_size <= 0.0 {
-            return Err(format!("Insufficient balance/position for signal: {}", signal_data.id));
+        // SLIPPAGE GATE: Refuse blocklisted symbols
+        if self.slippage.is_blocklisted(&signal_data.symbol) {
+            return Err(format!("Symbol {} is blocked: no route within the {} bps ceiling",
+                signal_data.symbol, self.slippage.config().ceiling_bps));
+        }
+        
+        // QUOTE DRIFT: Re-quote right before the swap - the provider's price may be stale by now
+        let is_buy = matches!(action, TradeAction::Buy);
+        let quote = match self.jupiter_client {
+            Some(ref jupiter) => match crate::dex_aggregator::quote_trade(jupiter.as_ref(), &signal_data.symbol, position_size, priced.entry_price, is_buy, self.base_currency).await {
+                Ok(quote) => Some(quote),
+                // A buy whose impact can't be measured could be walking into a thin pool - skip it
+                Err(e) if is_buy && self.quote_drift.max_price_impact_pct > 0.0 => {
+                    log::warn!("🌊 No quote for {} at size {:.6} - price impact unverified, skipping signal {}: {}",
+                              signal_data.symbol, position_size, signal_data.id, e);
+                    return Err(format!("Price impact unverified for {}: no quote at size {:.6} ({})",
+                        signal_data.symbol, position_size, e));
+                }
+                Err(e) => {
+                    log::debug!("📈 No re-quote for {} - trading at the signal price: {}", signal_data.symbol, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        
+        // SLIPPAGE GATE: The quote's price impact at this size is the slippage the swap will take
+        // Without a quote, fall back to how far the signal is priced from the market
+        let slippage_bps = match quote {
+            Some(ref quote) => Some(quote.price_impact_pct.abs() * 100.0),
+            None => self.market_state.get(&signal_data.symbol)
+                .and_then(|history| history.back())
+                .map(|data| data.price)
+                .filter(|price| *price > 0.0)
+                .map(|market_price| ((signal_data.entry_price - market_price).abs() / market_price) * 10_000.0),
+        };
+        if let Some(slippage_bps) = slippage_bps {
+            let tolerance_bps = self.slippage.tolerance_bps(&signal_data.symbol);
+            if slippage_bps > tolerance_bps as f64 {
+                self.slippage.record_failure(&signal_data.symbol);
+                return Err(format!("No route within slippage tolerance for {}: {:.0} bps > {} bps",
+                    signal_data.symbol, slippage_bps, tolerance_bps));
+            }
+        }
+        
+        if let Some(ref quote) = quote {
+            // PRICE IMPACT: The quote is at the real size, not the provider's probe
+            // Exits are never held back - being stuck in a thin pool is worse than the impact
+            if let Some(impact) = self.quote_drift.excess_impact(quote.price_impact_pct).filter(|_| is_buy) {
+                log::warn!("🌊 {} price impact {:.2}% at size {:.6} exceeds the {:.2}% cap - skipping signal {}",
+                          signal_data.symbol, impact, position_size, self.quote_drift.max_price_impact_pct, signal_data.id);
+                return Err(format!("Price impact {:.2}% at size {:.6} exceeds the {:.2}% cap for {}",
+                    impact, position_size, self.quote_drift.max_price_impact_pct, signal_data.symbol));
+            }
+            // The quote is in the token the swap pays with, the entry in USD - compare both in USD
+            let quoted_price = crate::dex_aggregator::quoted_price(&signal_data.symbol, quote, is_buy, self.base_currency)
+                .and_then(|quoted| self.quote_to_usd_price(&signal_data.symbol, quoted));
+            if let Some(quoted_price) = quoted_price {
+                if let Some(drift_pct) = self.quote_drift.excess_drift(signal_data.entry_price, quoted_price) {
+                    log::warn!("📈 {} price drifted {:+.2}% since signal {} (entry {:.8}, quote {:.8}, max {:.2}%)",
+                              signal_data.symbol, drift_pct, signal_data.id, signal_data.entry_price,
+                              quoted_price, self.quote_drift.max_drift_pct);
+                    return Err(format!("Price drifted {:+.2}% from entry for {} (max {:.2}%)",
+                        drift_pct, signal_data.symbol, self.quote_drift.max_drift_pct));
+                }
+                log::debug!("📈 Re-quote for {} within drift limit: entry {:.8}, quote {:.8}",
+                           signal_data.symbol, signal_data.entry_price, quoted_price);
+            }
         }
         
         let signal = TradingSignal {
             id: signal_data.id.clone(),
             action,
             symbol: signal_data.symbol.clone(),
-            price: signal_data.entry_price,
+            price: priced.entry_price,
             confidence: signal_data.confidence,
             size: position_size,
-            stop_loss: signal_data.stop_loss,
-            take_profit: signal_data.target_price,
+            stop_loss: priced.stop_loss,
+            take_profit: priced.target_price,
             timestamp: signal_data.timestamp,
+            aggregator: None,
+            mev_protected: self.wants_jito_bundle(position_size * priced.entry_price),
+            slippage_bps: signal_data.slippage_bps,
+            compute_unit_limit: signal_data.compute_unit_limit,
+            wallet: (wallet_name != crate::wallet::DEFAULT_WALLET_NAME).then_some(wallet_name),
         };
         
-        let success = self.execute_trade(&signal, trading_enabled, dry_run).await;
+        let step = self.begin_trade(&signal, trading_enabled, dry_run).await;
+        Ok(MarketplaceTrade { signal, is_dry_run, step })
+    }
+    
+    /// Book a marketplace signal's trade once its swap (if any) has settled
+    pub async fn finish_marketplace_signal(
+        &mut self,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        trade: MarketplaceTrade,
+    ) -> Result<String, String> {
+        let MarketplaceTrade { signal, is_dry_run, step } = trade;
+        let success = matches!(step, TradeStep::Done(true));
         
         if success {
+            self.record_fill(Some(&signal_data.provider), &signal).await;
+            self.slippage.record_success(&signal_data.symbol);
+            if let Some(ref broadcaster) = self.ws_broadcaster {
+                crate::websocket::broadcast_trade_executed(broadcaster, crate::websocket::TradeExecuted {
+                    signal_id: signal_data.id.clone(),
+                    symbol: signal_data.symbol.clone(),
+                    action: signal.action.to_string(),
+                    fill_price: self.fill_price_usd(&signal_data.id, &signal_data.symbol).unwrap_or(signal_data.entry_price),
+                    size: signal.size,
+                    balance: self.current_balance,
+                    dry_run: is_dry_run,
+                    timestamp: Utc::now().timestamp(),
+                });
+            }
             Ok(format!("Signal {} executed successfully", signal_data.id))
         } else {
-            Err(format!("Failed to execute signal: {}", signal_data.id))
+            // Surface the risk manager's rationale so callers can tell why the trade was rejected
+            let risk_manager = self.risk_manager.lock().await;
+            match risk_manager.rejection_reason(signal.size, signal.price, signal.confidence) {
+                Some(reason) => Err(format!("Failed to execute signal: {} ({})", signal_data.id, reason)),
+                None => Err(format!("Failed to execute signal: {}", signal_data.id)),
+            }
         }
     }
     
-    pub fn get_portfolio_value(&self, current_prices: &HashMap<String, f64>) -> f64 {
-        let positions_value: f64 = self.portfolio.iter()
-            .map(|(symbol, size)| {
-                current_prices.get(symbol).unwrap_or(&0.0) * size
-            })
-            .sum();
-        
-        self.current_balance + positions_value
-    }
-    
-    pub fn get_portfolio_data(&self) -> HashMap<String, f64> {
-        let mut data = self.portfolio.clone();
-        data.insert("CASH".to_string(), self.current_balance);
+    /// Size a marketplace signal the way execution will: max_position_fraction of `current_balance` for buys, capped by the
+    /// provider's capital allocation, the price impact cap and correlated exposure; the whole position for sells
+    /// Returns the size and a note for every cap that shrank it
+    async fn risk_sized_position(
+        &self,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        action: &TradeAction,
+        current_balance: f64,
+    ) -> Result<(f64, Vec<String>), String> {
+        let mut adjustments = Vec::new();
+        let position_size = if matches!(action, TradeAction::Buy) {
+            // STREAK SIZING: Smaller after a losing run, larger after a winning one - up to the position limit
+            let (max_cost, streak_multiplier) = {
+                let risk_manager = self.risk_manager.lock().await;
+                (risk_manager.streak_position_value(current_balance), risk_manager.streak_multiplier())
+            };
+            if streak_multiplier < 1.0 {
+                log::info!("📉 Sizing {} down to {:.2}x after a losing streak", signal_data.id, streak_multiplier);
+                adjustments.push(format!("Scaled {:.2}x after a losing streak", streak_multiplier));
+            } else if streak_multiplier > 1.0 {
+                log::info!("📈 Sizing {} up to {:.2}x after a winning streak", signal_data.id, streak_multiplier);
+                adjustments.push(format!("Scaled {:.2}x after a winning streak", streak_multiplier));
+            }
+            
+            // CAPITAL ALLOCATION: One provider can't consume the whole treasury
+            let capacity = self.provider_capacity(&signal_data.provider, current_balance).await;
+            if capacity <= 0.0 {
+                return Err(format!("Provider {} has used its capital allocation - signal {} skipped",
+                    signal_data.provider, signal_data.id));
+            }
+            if capacity < max_cost {
+                log::info!("📉 Sizing {} down to {:.6} - the rest of {}'s capital allocation", signal_data.id, capacity, signal_data.provider);
+                adjustments.push(format!("Capped at {:.6} - the rest of {}'s capital allocation", capacity, signal_data.provider));
+            }
+            let calculated_size = max_cost.min(capacity) / signal_data.entry_price;
+            
+            // Validate we have sufficient balance for the calculated size
+            if calculated_size <= 0.0 || (calculated_size * signal_data.entry_price) > current_balance {
+                return Err(format!("Insufficient balance for signal: {} (balance: {:.6}, required: {:.6})", 
+                    signal_data.id, current_balance, calculated_size * signal_data.entry_price));
+            }
+            
+            calculated_size
+        } else {
+            // For sell, the provider's position in the wallet it sells from
+            self.sell_source(Some(&signal_data.provider), &signal_data.symbol).map_or(0.0, |(_, size)| size)
+        };
+        
+        if position_size <= 0.0 {
+            return Err(format!("Insufficient balance/position for signal: {}", signal_data.id));
+        }
+        
+        // SLIPPAGE SIZING: Shrink buys to the value at which the quoted price impact stays under the cap
+        // Providers size against SOL-input quotes, so the cap is in SOL
+        let slippage_cap = self.risk_manager.lock().await.slippage_cap(&signal_data.symbol)
+            .and_then(|cap_sol| self.sol_in_quote(&signal_data.symbol, cap_sol));
+        let position_size = match slippage_cap {
+            Some(max_value) if matches!(action, TradeAction::Buy) && position_size * signal_data.entry_price > max_value => {
+                log::info!("📉 Sizing {} down to {:.6} (value {:.6}) to stay under the price impact cap",
+                          signal_data.symbol, max_value / signal_data.entry_price, max_value);
+                adjustments.push(format!("Capped at value {:.6} to stay under the price impact cap", max_value));
+                max_value / signal_data.entry_price
+            }
+            _ => position_size,
+        };
+        
+        // CORRELATION: Keep groups of correlated positions (memecoins that dump together) under the exposure cap
+        let position_size = if matches!(action, TradeAction::Buy) {
+            use crate::risk_management::PortfolioRisk;
+            let exposures = self.position_exposures();
+            let correlations = self.correlation_matrix();
+            let value = position_size * signal_data.entry_price;
+            match self.risk_manager.lock().await.portfolio_risk(&signal_data.symbol, value, &exposures, &correlations) {
+                PortfolioRisk::Accept => position_size,
+                PortfolioRisk::Downsize { max_value, reason } => {
+                    log::info!("📉 Sizing {} down to value {:.6}: {}", signal_data.symbol, max_value, reason);
+                    adjustments.push(format!("Capped at value {:.6}: {}", max_value, reason));
+                    max_value / signal_data.entry_price
+                }
+                PortfolioRisk::Reject { reason } => {
+                    return Err(format!("Portfolio risk check failed for {}: {}", signal_data.symbol, reason));
+                }
+            }
+        } else {
+            position_size
+        };
+        
+        Ok((position_size, adjustments))
+    }
+    
+    /// WHAT-IF: Everything execution would decide for `signal_data`, without trading
+    /// Read-only - no balance sync, no slippage bookkeeping, no marketplace status change.
+    /// The engine is unlocked while Jupiter quotes the trade
+    pub async fn simulate_marketplace_signal(
+        engine: &Arc<Mutex<Self>>,
+        signal_data: &crate::signal_platform::TradingSignalData,
+    ) -> Result<TradeSimulation, String> {
+        let (plan, jupiter, base_currency) = {
+            let engine_lock = engine.lock().await;
+            (engine_lock.plan_simulation(signal_data).await?, engine_lock.jupiter_client.clone(), engine_lock.base_currency)
+        };
+        let quote = plan.quote(jupiter.as_deref(), &signal_data.symbol, base_currency).await;
+        engine.lock().await.finish_simulation(signal_data, plan, quote).await
+    }
+    
+    /// The checks and sizing a simulation runs before quoting
+    async fn plan_simulation(&self, signal_data: &crate::signal_platform::TradingSignalData) -> Result<SimulationPlan, String> {
+        if signal_data.entry_price <= 0.0 {
+            return Err(format!("Invalid entry price for signal {}: {}", signal_data.id, signal_data.entry_price));
+        }
+        let action = match signal_data.action {
+            crate::signal_platform::SignalAction::Buy => TradeAction::Buy,
+            crate::signal_platform::SignalAction::Sell => TradeAction::Sell,
+            crate::signal_platform::SignalAction::Hold => {
+                return Err(format!("Nothing to simulate for a HOLD signal: {}", signal_data.id));
+            }
+        };
+        let is_buy = matches!(action, TradeAction::Buy);
+        let mut rejections = Vec::new();
+        
+        if let Some(token_filter) = self.token_filter.as_ref().filter(|_| is_buy) {
+            if let Err(reason) = token_filter.check(signal_data.filter_token()).await {
+                rejections.push(format!("Token filter: {}", reason));
+            }
+        }
+        if self.slippage.is_blocklisted(&signal_data.symbol) {
+            rejections.push(format!("Symbol {} is blocked: no route within the {} bps ceiling",
+                signal_data.symbol, self.slippage.config().ceiling_bps));
+        }
+        
+        // Sized and quoted in the token the trade pays with; prices below are reported in USD
+        let sized = match self.quote_priced_signal(signal_data) {
+            Ok(priced) => self.risk_sized_position(&priced, &action, self.current_balance).await
+                .map(|sized| (sized, priced.entry_price)),
+            Err(reason) => Err(reason),
+        };
+        let ((size, adjustments), quote_entry_price) = match sized {
+            Ok(sized) => sized,
+            Err(reason) => {
+                rejections.push(reason);
+                ((0.0, Vec::new()), signal_data.entry_price)
+            }
+        };
+        Ok(SimulationPlan { action, rejections, size, adjustments, quote_entry_price })
+    }
+    
+    /// The rest of a simulation, from the plan's quote (None when there was nothing to quote)
+    async fn finish_simulation(
+        &self,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        plan: SimulationPlan,
+        quote: Option<Result<crate::dex_aggregator::AggregatorQuote, String>>,
+    ) -> Result<TradeSimulation, String> {
+        let SimulationPlan { action, mut rejections, size, adjustments, quote_entry_price } = plan;
+        let is_buy = matches!(action, TradeAction::Buy);
+        // Price impact is the slippage the swap would take
+        let (quoted_price, price_impact_pct, quote_error) = match quote {
+            Some(Ok(quote)) => (crate::dex_aggregator::quoted_price(&signal_data.symbol, &quote, is_buy, self.base_currency)
+                                    .and_then(|quoted| self.quote_to_usd_price(&signal_data.symbol, quoted)),
+                                Some(quote.price_impact_pct), None),
+            Some(Err(e)) => (None, None, Some(e)),
+            None => (None, None, None),
+        };
+        // Same gate as execution: the quote's impact, else the signal's distance from the market
+        let gate_bps = match price_impact_pct {
+            Some(impact) => Some(impact.abs() * 100.0),
+            None => self.market_state.get(&signal_data.symbol)
+                .and_then(|history| history.back())
+                .map(|data| data.price)
+                .filter(|price| *price > 0.0)
+                .map(|market_price| ((signal_data.entry_price - market_price).abs() / market_price) * 10_000.0),
+        };
+        if let Some(gate_bps) = gate_bps {
+            let tolerance_bps = self.slippage.tolerance_bps(&signal_data.symbol);
+            if gate_bps > tolerance_bps as f64 {
+                rejections.push(format!("No route within slippage tolerance for {}: {:.0} bps > {} bps",
+                    signal_data.symbol, gate_bps, tolerance_bps));
+            }
+        }
+        if let Some(drift_pct) = quoted_price.and_then(|quoted| self.quote_drift.excess_drift(signal_data.entry_price, quoted)) {
+            rejections.push(format!("Price drifted {:+.2}% from entry (max {:.2}%)", drift_pct, self.quote_drift.max_drift_pct));
+        }
+        if let Some(impact) = price_impact_pct.and_then(|impact| self.quote_drift.excess_impact(impact)).filter(|_| is_buy) {
+            rejections.push(format!("Price impact {:.2}% at this size exceeds the {:.2}% cap", impact, self.quote_drift.max_price_impact_pct));
+        }
+        if let (Some(e), Some(_)) = (&quote_error, &self.jupiter_client) {
+            if is_buy && self.quote_drift.max_price_impact_pct > 0.0 {
+                rejections.push(format!("Price impact unverified: no quote at this size ({})", e));
+            }
+        }
+        let expected_price = quoted_price.unwrap_or(signal_data.entry_price);
+        let slippage_bps = (expected_price - signal_data.entry_price).abs() / signal_data.entry_price * 10_000.0;
+        // Value, risk and P&L are in the quote token, like the balance they're compared with
+        let quote_price = |usd: f64| self.usd_to_quote_price(&signal_data.symbol, usd).unwrap_or(usd);
+        let expected_price = quoted_price.map_or(quote_entry_price, quote_price);
+        
+        let mev_protected = self.wants_jito_bundle(size * expected_price);
+        let tip_lamports = self.jito_client.as_ref().filter(|_| mev_protected)
+            .map_or(0, |jito| jito.effective_tip(Self::fee_priority(signal_data.confidence)));
+        let fee_sol = (self.estimate_fee_lamports(signal_data.confidence).await + tip_lamports) as f64 / 1_000_000_000.0;
+        
+        if size > 0.0 {
+            if let Some(reason) = self.risk_manager.lock().await.rejection_reason(size, expected_price, signal_data.confidence) {
+                rejections.push(reason);
+            }
+        }
+        
+        // Long P&L for buys; a sell closes the position, so it realizes against the average entry
+        let pnl_at = |exit: f64| {
+            if is_buy {
+                size * (exit - expected_price) - fee_sol
+            } else {
+                let entry = self.cost_basis.get(&signal_data.symbol).map_or(expected_price, |basis| basis.average_entry);
+                size * (exit - entry) - fee_sol
+            }
+        };
+        let (pnl_at_target, pnl_at_stop) = if is_buy {
+            (pnl_at(quote_price(signal_data.target_price)), pnl_at(quote_price(signal_data.stop_loss)))
+        } else {
+            (pnl_at(expected_price), pnl_at(expected_price))
+        };
+        
+        Ok(TradeSimulation {
+            signal_id: signal_data.id.clone(),
+            symbol: signal_data.symbol.clone(),
+            action: action.to_string(),
+            would_execute: rejections.is_empty(),
+            rejections,
+            balance: self.current_balance,
+            size,
+            trade_value: size * expected_price,
+            sizing_adjustments: adjustments,
+            entry_price: signal_data.entry_price,
+            quoted_price,
+            price_impact_pct,
+            slippage_bps,
+            quote_error,
+            fee_sol,
+            mev_protected,
+            pnl_at_target,
+            pnl_at_stop,
+        })
+    }
+    
+    /// Execute a signal as `config.chunks` separate swaps spaced `config.interval_secs` apart
+    /// The engine lock is only held per chunk, and released while its swap is in flight. Remaining chunks are aborted once price moves past the stop-loss
+    pub async fn dca_execute(
+        engine: &Arc<Mutex<TradingEngine>>,
+        signal_data: &crate::signal_platform::TradingSignalData,
+        config: &DcaConfig,
+        trading_enabled: Option<&Arc<Mutex<bool>>>,
+        dry_run: Option<&Arc<Mutex<bool>>>,
+    ) -> Result<DcaResult, String> {
+        let action = match signal_data.action {
+            crate::signal_platform::SignalAction::Buy => TradeAction::Buy,
+            crate::signal_platform::SignalAction::Sell => TradeAction::Sell,
+            crate::signal_platform::SignalAction::Hold => {
+                return Err(format!("Cannot DCA a HOLD signal: {}", signal_data.id));
+            }
+        };
+        if signal_data.entry_price <= 0.0 {
+            return Err(format!("Invalid entry price for signal {}: {}", signal_data.id, signal_data.entry_price));
+        }
+        let oracle_check = engine.lock().await.oracle_check();
+        Self::verify_oracle_price(oracle_check, signal_data).await?;
+        let chunks = config.chunks.max(1);
+        // Every chunk trades in the mode a single-shot execution of the signal would
+        let forced_dry_run = Arc::new(Mutex::new(true));
+        let dry_run = Self::signal_dry_run(signal_data, dry_run, &forced_dry_run);
+        
+        // Same sizing as a single-shot execution: 10% of balance for buys, the whole position for sells
+        let target_size = {
+            let engine_lock = engine.lock().await;
+            if engine_lock.slippage.is_blocklisted(&signal_data.symbol) {
+                return Err(format!("Symbol {} is blocked: no route within the {} bps ceiling",
+                    signal_data.symbol, engine_lock.slippage.config().ceiling_bps));
+            }
+            match action {
+                TradeAction::Buy => {
+                    let capacity = engine_lock.provider_capacity(&signal_data.provider, engine_lock.current_balance).await;
+                    let entry_price = engine_lock.usd_to_quote_price(&signal_data.symbol, signal_data.entry_price)
+                        .ok_or_else(|| format!("No SOL price to convert {}'s USD entry into its quote token", signal_data.symbol))?;
+                    (engine_lock.current_balance * 0.1).min(capacity) / entry_price
+                }
+                _ => engine_lock.sell_source(Some(&signal_data.provider), &signal_data.symbol).map_or(0.0, |(_, size)| size),
+            }
+        };
+        if target_size <= 0.0 {
+            return Err(format!("Insufficient balance/position for signal: {}", signal_data.id));
+        }
+        
+        let chunk_size = target_size / chunks as f64;
+        log::info!("🧩 DCA {} {:?} {}: {:.6} in {} chunks of {:.6} every {}s",
+                  signal_data.id, action, signal_data.symbol, target_size, chunks, chunk_size, config.interval_secs);
+        
+        let mut result = DcaResult {
+            signal_id: signal_data.id.clone(),
+            symbol: signal_data.symbol.clone(),
+            chunks_planned: chunks,
+            chunks_filled: 0,
+            target_size,
+            filled_size: 0.0,
+            average_fill_price: 0.0,
+            aborted: None,
+        };
+        let mut total_cost = 0.0;
+        
+        for chunk in 0..chunks {
+            if chunk > 0 && config.interval_secs > 0 {
+                tokio::time::sleep(tokio::time::Duration::from_secs(config.interval_secs)).await;
+            }
+            
+            let mut engine_lock = engine.lock().await;
+            let price = engine_lock.market_state.get(&signal_data.symbol)
+                .and_then(|history| history.back())
+                .map(|data| data.price)
+                .filter(|price| *price > 0.0)
+                .unwrap_or(signal_data.entry_price);
+            
+            // STOP-LOSS GUARD: Don't keep averaging into a move that has already invalidated the signal
+            let stopped_out = match action {
+                TradeAction::Buy => price <= signal_data.stop_loss,
+                _ => signal_data.stop_loss > 0.0 && price >= signal_data.stop_loss,
+            };
+            if stopped_out {
+                let reason = format!("Price ${:.8} moved past stop-loss ${:.8}", price, signal_data.stop_loss);
+                log::warn!("🛑 DCA {} aborted after {}/{} chunks: {}", signal_data.id, result.chunks_filled, chunks, reason);
+                result.aborted = Some(reason);
+                break;
+            }
+            
+            // The chunk swaps in the quote token; the stop-loss guard and the average stay in USD
+            let Some(quote_price) = engine_lock.usd_to_quote_price(&signal_data.symbol, price) else {
+                let reason = format!("No SOL price to convert chunk {}/{} into its quote token", chunk + 1, chunks);
+                log::warn!("⚠️ DCA {} aborted: {}", signal_data.id, reason);
+                result.aborted = Some(reason);
+                break;
+            };
+            let to_quote = |usd: f64| engine_lock.usd_to_quote_price(&signal_data.symbol, usd).unwrap_or(usd);
+            let chunk_signal = TradingSignal {
+                id: format!("{}_dca{}", signal_data.id, chunk + 1),
+                action: action.clone(),
+                symbol: signal_data.symbol.clone(),
+                price: quote_price,
+                confidence: signal_data.confidence,
+                size: chunk_size,
+                stop_loss: to_quote(signal_data.stop_loss),
+                take_profit: to_quote(signal_data.target_price),
+                timestamp: Utc::now().timestamp(),
+                aggregator: None,
+                mev_protected: engine_lock.wants_jito_bundle(chunk_size * quote_price),
+                slippage_bps: signal_data.slippage_bps,
+                compute_unit_limit: signal_data.compute_unit_limit,
+                wallet: Some(engine_lock.route_wallet(&signal_data.provider, &signal_data.symbol, &action))
+                    .filter(|wallet_name| wallet_name != crate::wallet::DEFAULT_WALLET_NAME),
+            };
+            
+            // The chunk's swap goes out with the engine unlocked
+            let step = engine_lock.begin_trade(&chunk_signal, trading_enabled, dry_run).await;
+            drop(engine_lock);
+            if !step.settle(engine).await {
+                let reason = format!("Chunk {}/{} failed to execute", chunk + 1, chunks);
+                log::warn!("⚠️ DCA {} aborted: {}", signal_data.id, reason);
+                result.aborted = Some(reason);
+                break;
+            }
+            
+            engine.lock().await.record_fill(Some(&signal_data.provider), &chunk_signal).await;
+            result.chunks_filled += 1;
+            result.filled_size += chunk_size;
+            total_cost += chunk_size * price;
+            result.average_fill_price = total_cost / result.filled_size;
+            log::info!("🧩 DCA {} partial fill {}/{}: {:.6} {} at ${:.8} (avg ${:.8}, filled {:.6}/{:.6})",
+                      signal_data.id, chunk + 1, chunks, chunk_size, signal_data.symbol, price,
+                      result.average_fill_price, result.filled_size, target_size);
+        }
+        
+        if result.chunks_filled == 0 {
+            return Err(result.aborted.unwrap_or_else(|| format!("DCA {} filled no chunks", signal_data.id)));
+        }
+        Ok(result)
+    }
+    
+    /// Place a limit order at the signal's entry price; it fills when price crosses the limit
+    /// and expires with the signal
+    pub fn place_limit_order(&mut self, signal_data: &crate::signal_platform::TradingSignalData) -> Result<PendingOrder, String> {
+        let now = Utc::now().timestamp();
+        if signal_data.expiry <= now {
+            return Err(format!("Signal {} has already expired", signal_data.id));
+        }
+        if signal_data.entry_price <= 0.0 {
+            return Err(format!("Invalid limit price for signal {}: {}", signal_data.id, signal_data.entry_price));
+        }
+        if matches!(signal_data.action, crate::signal_platform::SignalAction::Hold) {
+            return Err(format!("Cannot place a limit order for HOLD signal {}", signal_data.id));
+        }
+        if self.pending_orders.values().any(|o| o.signal.id == signal_data.id) {
+            return Err(format!("Signal {} already has a pending limit order", signal_data.id));
+        }
+        
+        let order = PendingOrder {
+            order_id: format!("order_{}", uuid::Uuid::new_v4()),
+            signal: signal_data.clone(),
+            limit_price: signal_data.entry_price,
+            created_at: now,
+            expires_at: signal_data.expiry,
+            status: OrderStatus::Pending,
+            fill_price: None,
+        };
+        log::info!("📝 Limit order {} placed: {:?} {} @ ${:.8} (expires {})",
+                  order.order_id, signal_data.action, signal_data.symbol, order.limit_price, order.expires_at);
+        self.pending_orders.insert(order.order_id.clone(), order.clone());
+        Ok(order)
+    }
+    
+    /// Cancel every pending limit order
+    pub fn cancel_all_orders(&mut self) -> Vec<PendingOrder> {
+        self.pending_orders.drain()
+            .map(|(order_id, mut order)| {
+                order.status = OrderStatus::Cancelled;
+                log::info!("❌ Limit order {} cancelled", order_id);
+                order
+            })
+            .collect()
+    }
+    
+    /// Sell the entire position in `symbol` at `price`
+    /// Skips the trading toggle, kill switch and risk validation - those guard new exposure,
+    /// and an exit only ever reduces it. Returns the size sold
+    /// A position spread over several wallets is sold from each of them
+    pub async fn close_position(&mut self, symbol: &str, price: f64, dry_run: bool) -> Result<f64, String> {
+        let holdings = self.symbol_wallets(symbol);
+        if holdings.is_empty() {
+            return Err(format!("No open position in {}", symbol));
+        }
+        let mut sold = 0.0;
+        for (wallet_name, size) in holdings {
+            sold += self.sell_position(symbol, &wallet_name, size, price, dry_run).await?.size;
+        }
+        Ok(sold)
+    }
+    
+    /// Sell `size` of `symbol` from `wallet_name` (capped at its holding) at `price` into its quote token -
+    /// see close_position. Returns the sale as booked: size and fill price
+    async fn sell_position(&mut self, symbol: &str, wallet_name: &str, size: f64, price: f64, dry_run: bool) -> Result<TradingSignal, String> {
+        let size = size.min(self.wallet_holding(wallet_name, symbol));
+        if size <= 0.0 {
+            return Err(format!("No open position in {}", symbol));
+        }
+        if price <= 0.0 {
+            return Err(format!("No price available to close {}", symbol));
+        }
+        
+        let signal = TradingSignal {
+            id: format!("close_{}", uuid::Uuid::new_v4()),
+            action: TradeAction::Sell,
+            symbol: symbol.to_string(),
+            price,
+            confidence: 1.0,
+            size,
+            stop_loss: price,
+            take_profit: price,
+            timestamp: Utc::now().timestamp(),
+            mev_protected: self.wants_jito_bundle(size * price),
+            wallet: Some(wallet_name.to_string()).filter(|wallet_name| wallet_name != crate::wallet::DEFAULT_WALLET_NAME),
+            ..Default::default()
+        };
+        
+        let success = if dry_run {
+            let price_impact_pct = self.paper_trade(&signal).price_impact_pct().await;
+            self.settle_paper_trade(&signal, price_impact_pct).await
+        } else {
+            let solana_client = match signal.wallet {
+                Some(ref wallet_name) => self.wallet_client(wallet_name),
+                None => self.solana_client.clone(),
+            };
+            match solana_client {
+                Some(solana_client) => self.execute_real_trade(&signal, solana_client).await,
+                // A simulated sale would report a live exit that never reached the chain
+                None => {
+                    log::error!("❌ No Solana client for {} - live sale of {} {} not sent", signal.wallet.as_deref().unwrap_or("the default wallet"), size, symbol);
+                    false
+                }
+            }
+        };
+        
+        if success {
+            self.record_fill(None, &signal).await;
+            let fill_price = self.fill_price(&signal.id).unwrap_or(price);
+            Ok(TradingSignal { price: fill_price, ..signal })
+        } else {
+            Err(format!("Sell of {} {} failed", size, symbol))
+        }
+    }
+    
+    /// PROFIT TAKING: Sell `size` of `symbol` and convert the proceeds into `close_to`
+    /// Tokens sell into the base currency (SOL itself sells into USDC); closing into the other asset
+    /// adds a SOL <-> USDC swap. `Hold` swaps nothing and returns None. Proceeds are the sale as filled,
+    /// in the quote token it sold into; if the conversion swap can't be sent they stay there
+    pub async fn exit_position(&mut self, symbol: &str, size: f64, price: f64, dry_run: bool, close_to: CloseTo) -> Result<Option<ExitProceeds>, String> {
+        let is_sol = crate::dex_aggregator::resolve_token(symbol)
+            .is_some_and(|(mint, _)| mint == crate::dex_aggregator::SOL_MINT);
+        match close_to {
+            CloseTo::Hold => return Ok(None),
+            // A SOL position already is SOL - nothing to swap
+            CloseTo::Sol if is_sol => return Ok(Some(ExitProceeds { asset: CloseTo::Sol, amount: size, fill_price: None })),
+            _ => {}
+        }
+        
+        let (wallet_name, _) = self.sell_source(None, symbol).ok_or_else(|| format!("No open position in {}", symbol))?;
+        let sale = self.sell_position(symbol, &wallet_name, size, price, dry_run).await?;
+        let proceeds = sale.size * sale.price;
+        let landed = match self.base_currency {
+            _ if is_sol => CloseTo::Usdc,
+            crate::dex_aggregator::BaseCurrency::Sol => CloseTo::Sol,
+            crate::dex_aggregator::BaseCurrency::Usdc => CloseTo::Usdc,
+        };
+        let fill_price = Some(sale.price);
+        if landed == close_to {
+            return Ok(Some(ExitProceeds { asset: landed, amount: proceeds, fill_price }));
+        }
+        
+        // Second leg: SOL -> USDC, or USDC -> SOL for a USDC base. The proceeds came out of the
+        // balance, not a tracked position. SOL is always priced in USDC here
+        let Some(sol_price) = self.last_price("SOL/USDC") else {
+            log::warn!("⚠️ No SOL/USDC price - {:.6} {:?} from closing {} left as is", proceeds, landed, symbol);
+            return Ok(Some(ExitProceeds { asset: landed, amount: proceeds, fill_price }));
+        };
+        let buy_sol = landed == CloseTo::Usdc;
+        let converted = if buy_sol { proceeds / sol_price } else { proceeds * sol_price };
+        if !dry_run {
+            let sol_size = if buy_sol { converted } else { proceeds };
+            if let Err(e) = self.send_conversion_swap(sol_size, buy_sol, sol_price).await {
+                log::warn!("⚠️ {:?} -> {:?} conversion after closing {} failed ({}) - {:.6} left as is",
+                          landed, close_to, symbol, e, proceeds);
+                return Ok(Some(ExitProceeds { asset: landed, amount: proceeds, fill_price }));
+            }
+            // Treasury flows are in the base currency: whichever side of the swap it is on moves
+            let base_is_sol = self.base_currency == crate::dex_aggregator::BaseCurrency::Sol;
+            self.record_treasury_flow(if base_is_sol == buy_sol { converted } else { -proceeds });
+            self.sync_balance_from_pda().await;
+        }
+        Ok(Some(ExitProceeds { asset: close_to, amount: converted, fill_price }))
+    }
+    
+    /// The SOL <-> USDC leg of an exit, as a real Jupiter swap from the default wallet
+    async fn send_conversion_swap(&self, sol_size: f64, buy_sol: bool, sol_price: f64) -> Result<String, String> {
+        let solana_client = self.solana_client.clone().ok_or("No Solana client")?;
+        if crate::solana_integration::SolanaClient::dry_run_mode() {
+            let fee_lamports = self.estimate_fee_lamports(1.0).await;
+            return solana_client.lock().await
+                .execute_trade("SOL", sol_size, buy_sol, sol_price, Some(fee_lamports)).await;
+        }
+        let jupiter = self.jupiter_client.clone().ok_or("Live swaps need a Jupiter client to build the transaction")?;
+        let compute_unit_price = self.compute_unit_price(1.0).await;
+        let slippage_bps = self.slippage.tolerance_bps("SOL").min(self.slippage.config().ceiling_bps);
+        let mut client = solana_client.lock().await;
+        client.execute_swap(jupiter.as_ref(), "SOL", sol_size, buy_sol, sol_price, slippage_bps, compute_unit_price, None).await
+            .map_err(|e| e.to_string())
+    }
+    
+    /// Seconds left before `symbol` may be traded again under a `cooldown_secs` cooldown, None once clear
+    pub fn symbol_cooldown_remaining(&self, symbol: &str, cooldown_secs: i64, now: i64) -> Option<i64> {
+        let last_trade_at = self.last_trade_at.get(symbol)?;
+        let remaining = last_trade_at + cooldown_secs - now;
+        (remaining > 0).then_some(remaining)
+    }
+    
+    /// BALANCE ANOMALY: Book SOL the default PDA treasury gained (or lost, negative) in a confirmed movement
+    /// Whatever isn't booked here shows up as unexplained at the next reconciliation
+    pub fn record_treasury_flow(&mut self, delta_sol: f64) {
+        self.pending_treasury_flows.push(crate::production_safeguards::TreasuryFlow {
+            booked_at_ms: Utc::now().timestamp_millis(),
+            delta_sol,
+        });
+    }
+    
+    /// Hold a sent swap's treasury movement until the chain settles it (see settle_swap_flow)
+    /// Dry-run ids never reach the chain, so their simulated movement is booked right away
+    pub fn record_swap_flow(&mut self, trade_id: &str, flow: crate::production_safeguards::UnsettledSwapFlow) {
+        if trade_id.parse::<solana_sdk::signature::Signature>().is_err() {
+            self.record_treasury_flow(flow.realized(Some(flow.estimated_sol.abs())));
+            return;
+        }
+        self.unsettled_treasury_flows.insert(trade_id.to_string(), flow);
+    }
+    
+    /// Book what a settled swap actually moved - `quote_amount` is its realized fill, None if it failed
+    /// Returns false when the swap's movement wasn't being held
+    pub fn settle_swap_flow(&mut self, signature: &str, quote_amount: Option<f64>) -> bool {
+        let Some(flow) = self.unsettled_treasury_flows.remove(signature) else { return false };
+        self.record_treasury_flow(flow.realized(quote_amount));
+        true
+    }
+    
+    /// Best available mark for `symbol`: latest live-feed tick, else the last traded price
+    pub fn last_price(&self, symbol: &str) -> Option<f64> {
+        self.market_state.get(symbol)
+            .and_then(|history| history.back())
+            .map(|data| data.price)
+            .or_else(|| self.trade_history.iter().rev()
+                .find(|t| t.symbol == symbol)
+                .map(|t| t.price))
+            .filter(|price| *price > 0.0)
+    }
+    
+    /// Cancel a pending limit order
+    pub fn cancel_order(&mut self, order_id: &str) -> Result<PendingOrder, String> {
+        let mut order = self.pending_orders.remove(order_id)
+            .ok_or_else(|| format!("Pending order not found: {}", order_id))?;
+        order.status = OrderStatus::Cancelled;
+        log::info!("❌ Limit order {} cancelled", order_id);
+        Ok(order)
+    }
+    
+    pub fn get_pending_orders(&self) -> Vec<PendingOrder> {
+        let mut orders: Vec<PendingOrder> = self.pending_orders.values().cloned().collect();
+        orders.sort_by_key(|o| o.created_at);
+        orders
+    }
+    
+    /// Drop orders whose signal has expired
+    pub fn expire_orders(&mut self, now: i64) -> Vec<PendingOrder> {
+        let expired_ids: Vec<String> = self.pending_orders.values()
+            .filter(|o| o.expires_at <= now)
+            .map(|o| o.order_id.clone())
+            .collect();
+        
+        expired_ids.iter()
+            .filter_map(|id| self.pending_orders.remove(id))
+            .map(|mut order| {
+                order.status = OrderStatus::Expired;
+                log::info!("⌛ Limit order {} expired unfilled", order.order_id);
+                order
+            })
+            .collect()
+    }
+    
+    /// Execute a pending order at `price` if its limit has been reached
+    /// Returns Ok(None) when the order is not triggered (or no longer pending)
+    pub async fn try_fill_order(
+        &mut self,
+        order_id: &str,
+        price: f64,
+        trading_enabled: Option<&Arc<Mutex<bool>>>,
+        dry_run: Option<&Arc<Mutex<bool>>>,
+    ) -> Result<Option<PendingOrder>, String> {
+        let triggered = match self.pending_orders.get(order_id) {
+            Some(order) => order.is_triggered(price),
+            None => return Ok(None),
+        };
+        if !triggered {
+            return Ok(None);
+        }
+        
+        // Fill at the crossing price, not the stale limit
+        let mut order = self.pending_orders.remove(order_id).expect("order checked above");
+        let mut fill_signal = order.signal.clone();
+        fill_signal.entry_price = price;
+        
+        match self.execute_marketplace_signal(&fill_signal, trading_enabled, dry_run).await {
+            Ok(_) => {
+                order.status = OrderStatus::Filled;
+                order.fill_price = Some(price);
+                log::info!("✅ Limit order {} filled at ${:.8} (limit ${:.8})", order.order_id, price, order.limit_price);
+                Ok(Some(order))
+            }
+            Err(e) => {
+                // Keep the order resting so it can fill on a later cross
+                self.pending_orders.insert(order.order_id.clone(), order);
+                Err(e)
+            }
+        }
+    }
+    
+    /// Price change (%) over roughly the last `window_secs` for a symbol
+    /// Uses the buffered data point nearest to `window_secs` before the latest one and returns
+    /// (change_pct, actual_window_secs) - the actual window is shorter when the buffer doesn't reach back far enough
+    pub fn price_change_over(&self, symbol: &str, window_secs: i64) -> Option<(f64, i64)> {
+        let data = self.market_state.get(symbol)?;
+        let latest = data.back()?;
+        let target_ts = latest.timestamp - window_secs;
+        
+        let reference = data.iter()
+            .filter(|d| d.timestamp < latest.timestamp)
+            .min_by_key(|d| (d.timestamp - target_ts).abs())?;
+        
+        if reference.price <= 0.0 {
+            return None;
+        }
+        
+        let change_pct = ((latest.price - reference.price) / reference.price) * 100.0;
+        Some((change_pct, latest.timestamp - reference.timestamp))
+    }
+    
+    pub fn get_portfolio_value(&self, current_prices: &HashMap<String, f64>) -> f64 {
+        let positions_value: f64 = self.portfolio.iter()
+            .map(|(symbol, size)| {
+                current_prices.get(symbol).unwrap_or(&0.0) * size
+            })
+            .sum();
+        
+        self.current_balance + positions_value
+    }
+    
+    pub fn get_portfolio_data(&self) -> HashMap<String, f64> {
+        let mut data = self.portfolio.clone();
+        data.insert("CASH".to_string(), self.current_balance);
         data
     }
+
+    /// COST BASIS: Fold a fill into the symbol's average entry, or realize P&L against it on a sell
+    /// Realized P&L is also booked to the selling wallet (None = the default wallet)
+    /// Returns the P&L the fill realized - 0 for buys
+    fn book_fill(&mut self, wallet: Option<&str>, symbol: &str, action: &TradeAction, size: f64, price: f64, fee: f64) -> f64 {
+        let basis = self.cost_basis.entry(symbol.to_string()).or_default();
+        match action {
+            TradeAction::Buy => {
+                basis.buy(size, price, fee);
+                0.0
+            }
+            TradeAction::Sell => {
+                let realized = basis.sell(size, price, fee);
+                self.realized_pnl += realized;
+                *self.wallet_realized_pnl.entry(wallet.unwrap_or(crate::wallet::DEFAULT_WALLET_NAME).to_string()).or_default() += realized;
+                log::debug!("📒 Realized ${:.8} on {} {} (avg entry ${:.8})", realized, size, symbol, basis.average_entry);
+                realized
+            }
+            TradeAction::Hold => 0.0,
+        }
+    }
+
+    /// Every open position with its average entry, mark and unrealized P&L
+    pub fn position_breakdown(&self) -> Vec<PositionBreakdown> {
+        let mut positions: Vec<PositionBreakdown> = self.portfolio.iter()
+            .filter(|(_, size)| **size > 0.0)
+            .map(|(symbol, size)| {
+                let average_entry = self.cost_basis.get(symbol).map(|b| b.average_entry).unwrap_or(0.0);
+                let cost_basis = average_entry * size;
+                let mark_price = self.last_price(symbol);
+                let unrealized_pnl = mark_price.map(|mark| (mark - average_entry) * size);
+                PositionBreakdown {
+                    symbol: symbol.clone(),
+                    size: *size,
+                    average_entry,
+                    cost_basis,
+                    mark_price,
+                    market_value: mark_price.map(|mark| mark * size),
+                    unrealized_pnl,
+                    unrealized_pnl_pct: unrealized_pnl.filter(|_| cost_basis > 0.0).map(|pnl| pnl / cost_basis * 100.0),
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        positions
+    }
+
+    /// Value of each open position at its last known price
+    pub fn position_exposures(&self) -> HashMap<String, f64> {
+        self.portfolio.iter()
+            .filter(|(_, size)| **size > 0.0)
+            .filter_map(|(symbol, size)| self.last_price(symbol).map(|price| (symbol.clone(), size * price)))
+            .collect()
+    }
+    
+    /// Return correlations estimated from the buffered market history
+    pub fn correlation_matrix(&self) -> crate::risk_management::CorrelationMatrix {
+        let history: HashMap<String, Vec<f64>> = self.market_state.iter()
+            .map(|(symbol, data)| (symbol.clone(), data.iter().map(|d| d.price).collect()))
+            .collect();
+        crate::risk_management::CorrelationMatrix::from_price_history(&history)
+    }
+    
+    /// Mark every open position at its last known price for the equity curve
+    /// daily_pnl is left at 0 - the database fills it from earlier snapshots
+    pub fn portfolio_snapshot(&self, dry_run: bool) -> crate::database::PortfolioSnapshot {
+        let marks: HashMap<String, f64> = self.portfolio.keys()
+            .filter_map(|symbol| self.last_price(symbol).map(|price| (symbol.clone(), price)))
+            .collect();
+        let total_value = self.get_portfolio_value(&marks);
+
+        crate::database::PortfolioSnapshot {
+            timestamp: Utc::now().timestamp(),
+            total_value,
+            cash_balance: self.current_balance,
+            positions: self.portfolio.clone(),
+            marks,
+            daily_pnl: 0.0,
+            total_pnl: total_value - self.initial_balance,
+            dry_run,
+        }
+    }
     
     /// Get return on investment (ROI) percentage based on initial balance
     pub fn get_roi(&self) -> f64 {
@@ -875,3 +3119,1012 @@ pub async fn generate_trading_signals(
         }
     }
 }
+
+/// Background poller that fills resting limit orders once live prices cross their limit
+pub async fn run_limit_order_poller(
+    engine: Arc<Mutex<TradingEngine>>,
+    oracle_client: Arc<crate::switchboard_oracle::SwitchboardClient>,
+    trading_enabled: Arc<Mutex<bool>>,
+    dry_run: Arc<Mutex<bool>>,
+    heartbeat: crate::watchdog::Heartbeat,
+) {
+    log::info!("📝 Limit order poller started - checking pending orders every 5 seconds");
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+    
+    loop {
+        interval.tick().await;
+        heartbeat.beat();
+        
+        let orders = {
+            let mut engine_lock = engine.lock().await;
+            engine_lock.expire_orders(Utc::now().timestamp());
+            engine_lock.get_pending_orders()
+        };
+        
+        for order in orders {
+            // Fresh oracle price first, fall back to the latest live-feed price in market_state
+            let price = match oracle_client.fetch_price(&order.signal.symbol).await {
+                Ok(feed) if feed.price > 0.0 && !feed.stale => Some(feed.price),
+                _ => {
+                    let engine_lock = engine.lock().await;
+                    engine_lock.market_state.get(&order.signal.symbol)
+                        .and_then(|history| history.back())
+                        .map(|data| data.price)
+                }
+            };
+            let Some(price) = price else {
+                log::debug!("No live price for {} - limit order {} waits", order.signal.symbol, order.order_id);
+                continue;
+            };
+            
+            let mut engine_lock = engine.lock().await;
+            if let Err(e) = engine_lock.try_fill_order(&order.order_id, price, Some(&trading_enabled), Some(&dry_run)).await {
+                log::warn!("⚠️ Limit order {} triggered but execution failed: {}", order.order_id, e);
+            }
+        }
+    }
+}
+
+/// How often submitted swaps are checked on-chain
+pub const EXECUTION_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Next audit-trail state for a pending execution given what the chain reports
+/// None means nothing changed - keep polling
+fn next_execution_state(
+    execution: &crate::database::ExecutionRecord,
+    observed: Option<&crate::rpc_client::SignatureConfirmation>,
+    now: i64,
+) -> Option<(crate::database::ExecutionStatus, Option<u64>, Option<String>)> {
+    use crate::database::ExecutionStatus;
+    let (status, slot, error) = match observed {
+        Some(confirmation) if confirmation.error.is_some() => {
+            (ExecutionStatus::Failed, Some(confirmation.slot), confirmation.error.clone())
+        }
+        Some(confirmation) if confirmation.finalized => (ExecutionStatus::Finalized, Some(confirmation.slot), None),
+        Some(confirmation) if confirmation.confirmed => (ExecutionStatus::Confirmed, Some(confirmation.slot), None),
+        Some(_) => return None, // Processed only
+        None if now - execution.submitted_at > crate::swap_dedupe::BLOCKHASH_EXPIRY_SECS => {
+            if execution.status == ExecutionStatus::Confirmed {
+                // Aged out of the RPC's recent status cache - don't downgrade a confirmed swap
+                return None;
+            }
+            (ExecutionStatus::Failed, None, Some("Never landed before its blockhash expired".to_string()))
+        }
+        None => return None,
+    };
+    
+    if status == execution.status && slot == execution.confirmation_slot {
+        return None;
+    }
+    Some((status, slot, error))
+}
+
+/// A live swap that passed every engine-side check (see TradingEngine::begin_trade)
+/// Sending it needs nothing from the engine, so callers sharing the engine release its lock while the
+/// swap - or a Jito bundle waiting for a slot - is in flight, then book it with TradingEngine::finish_swap
+pub struct PendingSwap {
+    signal: TradingSignal,
+    solana_client: Arc<Mutex<crate::solana_integration::SolanaClient>>,
+    route: Option<Arc<dyn crate::dex_aggregator::DexAggregator>>, // Aggregator that won the quote - it builds the swap
+    jito: Option<Arc<crate::jito_bam::JitoBamClient>>,
+    estimated_fee_lamports: u64,
+    jito_tip_lamports: u64,
+    compute_unit_price: Option<u64>,
+    slippage_bps: u16,
+    swap_key: String,
+    pending_update_key: String,
+    started: std::time::Instant,
+}
+
+impl PendingSwap {
+    /// Send the swap - as a Jito bundle for MEV-protected signals, else straight to the RPC node
+    /// Returns the trade id (the swap signature when live) and the Jito tip it paid (none in dry-run)
+    pub async fn send(&self) -> Result<(String, u64), crate::solana_integration::SwapError> {
+        use crate::solana_integration::SwapError;
+        let signal = &self.signal;
+        let is_buy = matches!(signal.action, TradeAction::Buy);
+        
+        if crate::solana_integration::SolanaClient::dry_run_mode() {
+            // No bundle goes out, so no tip is paid - it is only reported
+            if self.jito.is_some() {
+                log::info!("🛡️ {} is MEV-protected, but DRY_RUN_MODE is active - simulating without a bundle ({} lamport tip simulated, not paid)",
+                          signal.id, self.jito_tip_lamports);
+            }
+            return self.solana_client.lock().await
+                .execute_trade(&signal.symbol, signal.size, is_buy, signal.price, Some(self.estimated_fee_lamports)).await
+                .map(|trade_id| (trade_id, 0))
+                .map_err(SwapError::from);
+        }
+        
+        let route = self.route.as_deref().ok_or("Live swaps need a Jupiter client or an aggregator router to build the transaction")?;
+        match self.jito {
+            Some(ref jito) => self.send_jito_bundle(route, jito).await,
+            None => self.solana_client.lock().await
+                .execute_swap(route, &signal.symbol, signal.size, is_buy, signal.price, self.slippage_bps, self.compute_unit_price, signal.compute_unit_limit)
+                .await
+                .map(|signature| (signature, 0)),
+        }
+    }
+    
+    /// MEV PROTECTION: Submit the swap plus a tip transfer as one atomic Jito bundle and poll until it lands
+    /// A bundle that fails or is dropped executed nothing, so it is rebuilt with a bumped tip (up to the
+    /// cap, `max_retries` times). A bundle that times out may still land before its blockhash expires, so
+    /// its outcome is unknown - the dedupe and confirmation poller settle it like any other swap
+    /// Returns the swap signature and the tip it was sent with
+    async fn send_jito_bundle(
+        &self,
+        route: &dyn crate::dex_aggregator::DexAggregator,
+        jito: &crate::jito_bam::JitoBamClient,
+    ) -> Result<(String, u64), crate::solana_integration::SwapError> {
+        use crate::jito_bam::BundleStatus;
+        use crate::solana_integration::SwapError;
+        let signal = &self.signal;
+        let is_buy = matches!(signal.action, TradeAction::Buy);
+        let mut tip_lamports = self.jito_tip_lamports;
+        let mut attempt = 1;
+        loop {
+            let (signature, bundle) = self.solana_client.lock().await
+                .build_jito_swap_bundle(route, jito, &signal.symbol, signal.size, is_buy, signal.price, self.slippage_bps, tip_lamports, self.compute_unit_price, signal.compute_unit_limit)
+                .await?;
+            
+            log::info!("🛡️ Submitting {} {} as a Jito bundle ({} txs, {} lamport tip, attempt {})",
+                      signal.symbol, signal.id, bundle.len(), tip_lamports, attempt);
+            // The block engine may have accepted the bundle before the error - its swap can still land
+            let bundle_id = jito.submit_bundle_with_retry(bundle).await
+                .map_err(|e| SwapError::unknown(signature.clone(), format!("Jito bundle submission failed: {}", e)))?;
+            
+            match jito.wait_for_bundle(&bundle_id).await {
+                Ok(BundleStatus::Landed) => {
+                    jito.record_bundle_outcome(true);
+                    log::info!("✅ Jito bundle {} landed - swap {}", bundle_id, signature);
+                    return Ok((signature, tip_lamports));
+                }
+                Ok(status) => {
+                    jito.record_bundle_outcome(false);
+                    let bumped = jito.bumped_tip(tip_lamports);
+                    if attempt >= jito.config().max_retries || bumped <= tip_lamports {
+                        return Err(format!("Jito bundle {} {:?} - nothing executed", bundle_id, status).into());
+                    }
+                    log::warn!("⚠️ Jito bundle {} {:?} - rebuilding with a {} lamport tip", bundle_id, status, bumped);
+                    tip_lamports = bumped;
+                    attempt += 1;
+                }
+                // Not a fill: the swap may land or expire - booked only once the chain says it landed
+                Err(e) => {
+                    return Err(SwapError::unknown(signature, format!("Jito bundle {} not settled: {}", bundle_id, e)));
+                }
+            }
+        }
+    }
+}
+
+/// Where a trade stands after TradingEngine::begin_trade
+pub enum TradeStep {
+    Done(bool),             // Filled, paper-traded or refused - nothing left to send
+    Swap(Box<PendingSwap>), // A live swap ready to go out
+    Paper(Box<PaperTrade>), // A paper trade waiting on its price-impact quote
+}
+
+/// A validated paper trade whose fill still needs Jupiter's price impact at its size
+pub struct PaperTrade {
+    signal: TradingSignal,
+    jupiter: Option<Arc<crate::jupiter_integration::JupiterClient>>,
+    base_currency: crate::dex_aggregator::BaseCurrency,
+}
+
+impl PaperTrade {
+    /// Quoted price impact for the trade - 0 without a client or a quote
+    async fn price_impact_pct(&self) -> f64 {
+        let Some(ref jupiter) = self.jupiter else { return 0.0 };
+        let signal = &self.signal;
+        let is_buy = matches!(signal.action, TradeAction::Buy);
+        match crate::dex_aggregator::quote_trade(jupiter.as_ref(), &signal.symbol, signal.size, signal.price, is_buy, self.base_currency).await {
+            Ok(quote) => quote.price_impact_pct,
+            Err(e) => {
+                log::debug!("🧪 No quote for paper fill of {} - filling without price impact: {}", signal.symbol, e);
+                0.0
+            }
+        }
+    }
+}
+
+/// A marketplace signal past TradingEngine::begin_marketplace_signal, waiting on its trade
+pub struct MarketplaceTrade {
+    signal: TradingSignal,
+    is_dry_run: bool,
+    step: TradeStep,
+}
+
+impl MarketplaceTrade {
+    /// Send the signal's swap (if any) with the engine unlocked - see TradeStep::settle
+    pub async fn settle(mut self, engine: &Arc<Mutex<TradingEngine>>) -> Self {
+        let step = std::mem::replace(&mut self.step, TradeStep::Done(false));
+        self.step = TradeStep::Done(step.settle(engine).await);
+        self
+    }
+}
+
+impl TradeStep {
+    /// Send a pending swap (or quote a paper fill) with the engine unlocked, then lock it again to book the outcome
+    pub async fn settle(self, engine: &Arc<Mutex<TradingEngine>>) -> bool {
+        match self {
+            TradeStep::Done(success) => success,
+            TradeStep::Swap(swap) => {
+                let outcome = swap.send().await;
+                engine.lock().await.finish_swap(*swap, outcome).await
+            }
+            TradeStep::Paper(paper) => {
+                let price_impact_pct = paper.price_impact_pct().await;
+                engine.lock().await.settle_paper_trade(&paper.signal, price_impact_pct).await
+            }
+        }
+    }
+    
+    /// Finish the step on an engine the caller already holds - the swap or quote goes out under its lock
+    async fn finish_locked(self, engine: &mut TradingEngine) -> bool {
+        match self {
+            TradeStep::Done(success) => success,
+            TradeStep::Swap(swap) => {
+                let outcome = swap.send().await;
+                engine.finish_swap(*swap, outcome).await
+            }
+            TradeStep::Paper(paper) => {
+                let price_impact_pct = paper.price_impact_pct().await;
+                engine.settle_paper_trade(&paper.signal, price_impact_pct).await
+            }
+        }
+    }
+}
+
+/// Poll the chain for every submitted swap until it is finalized or failed
+/// Confirmed swaps are reconciled against the balances the transaction actually moved; buys also
+/// re-anchor the position's P/L and the provider's RL entry experience to the real fill
+pub async fn run_execution_confirmation_poller(
+    engine: Arc<Mutex<TradingEngine>>,
+    enhanced_marketplace: Option<Arc<crate::enhanced_marketplace::EnhancedMarketplace>>,
+    rl_coordinator: Option<Arc<Mutex<crate::reinforcement_learning::LearningCoordinator>>>,
+    heartbeat: crate::watchdog::Heartbeat,
+) {
+    log::info!("🧾 Execution confirmation poller started - checking submitted swaps every {} seconds", EXECUTION_POLL_INTERVAL_SECS);
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(EXECUTION_POLL_INTERVAL_SECS));
+    
+    let (database, solana_client) = {
+        let engine_lock = engine.lock().await;
+        (engine_lock.database.clone(), engine_lock.solana_client.clone())
+    };
+    let (Some(database), Some(solana_client)) = (database, solana_client) else {
+        log::warn!("⚠️ Execution confirmation poller idle - no database or Solana client attached");
+        loop {
+            interval.tick().await;
+            heartbeat.beat();
+        }
+    };
+    
+    loop {
+        interval.tick().await;
+        heartbeat.beat();
+        
+        let pending = database.lock().await.get_pending_executions();
+        for execution in pending {
+            let observed = match solana_client.lock().await.signature_confirmation(&execution.signature).await {
+                Ok(observed) => observed,
+                Err(e) => {
+                    log::debug!("Could not check execution {}: {}", execution.signature, e);
+                    continue;
+                }
+            };
+            
+            let now = Utc::now().timestamp();
+            let mut status = execution.status.clone();
+            if let Some((next_status, slot, error)) = next_execution_state(&execution, observed.as_ref(), now) {
+                match next_status {
+                    crate::database::ExecutionStatus::Failed => {
+                        log::error!("❌ Swap {} for signal {} failed on-chain: {}",
+                            execution.signature, execution.signal_id, error.as_deref().unwrap_or("unknown error"));
+                        engine.lock().await.settle_swap_flow(&execution.signature, None);
+                    }
+                    _ => log::info!("🧾 Swap {} for signal {} is {:?} (slot {:?})",
+                        execution.signature, execution.signal_id, next_status, slot),
+                }
+                if let Err(e) = database.lock().await.update_execution(&execution.signature, next_status.clone(), slot, error, now) {
+                    log::warn!("⚠️ Failed to update execution {}: {}", execution.signature, e);
+                }
+                status = next_status;
+            }
+            
+            let landed = matches!(status, crate::database::ExecutionStatus::Confirmed | crate::database::ExecutionStatus::Finalized);
+            if landed && execution.filled_size.is_none() {
+                reconcile_swap_fill(&engine, &database, &solana_client, &execution,
+                                    enhanced_marketplace.as_ref(), rl_coordinator.as_ref()).await;
+            }
+        }
+    }
+}
+
+/// PARTIAL FILLS: Read what a landed swap actually moved and correct the books to match
+/// Leaves the execution unreconciled (retried next poll) while the transaction isn't retrievable
+async fn reconcile_swap_fill(
+    engine: &Arc<Mutex<TradingEngine>>,
+    database: &Arc<Mutex<crate::database::Database>>,
+    default_client: &Arc<Mutex<crate::solana_integration::SolanaClient>>,
+    execution: &crate::database::ExecutionRecord,
+    enhanced_marketplace: Option<&Arc<crate::enhanced_marketplace::EnhancedMarketplace>>,
+    rl_coordinator: Option<&Arc<Mutex<crate::reinforcement_learning::LearningCoordinator>>>,
+) {
+    // The swap was signed by whichever wallet sent it
+    let client = match execution.wallet {
+        Some(ref wallet) => match engine.lock().await.strategy_wallets.get(wallet).cloned() {
+            Some(client) => client,
+            None => {
+                log::debug!("Wallet {} for swap {} is not connected - fill not reconciled", wallet, execution.signature);
+                return;
+            }
+        },
+        None => default_client.clone(),
+    };
+    let fill = match client.lock().await.swap_fill(&execution.signature, &execution.symbol).await {
+        Ok(Some(fill)) => fill,
+        Ok(None) => {
+            log::debug!("Swap {} has no readable fill yet - retrying next poll", execution.signature);
+            return;
+        }
+        Err(e) => {
+            log::debug!("Could not read the fill of swap {}: {}", execution.signature, e);
+            return;
+        }
+    };
+    
+    let (fill_price, fill_price_usd) = {
+        let mut engine_lock = engine.lock().await;
+        let Some(fill_price) = engine_lock.apply_swap_fill(execution, &fill) else { return };
+        engine_lock.record_realized_fee(execution, fill.fee_lamports).await;
+        let balance = engine_lock.current_balance;
+        engine_lock.risk_manager.lock().await.sync_treasury(balance);
+        // Positions and providers track entries in USD, like the signals they came from
+        (fill_price, engine_lock.quote_to_usd_price(&execution.symbol, fill_price).unwrap_or(fill_price))
+    };
+    if let Err(e) = database.lock().await.record_fill(&execution.signature, fill.token_amount, fill_price, Utc::now().timestamp()) {
+        log::warn!("⚠️ Failed to record the fill of swap {}: {}", execution.signature, e);
+    }
+    if execution.action != "BUY" {
+        return;
+    }
+    
+    // Position P/L and the provider's learning both start from the real entry
+    let Some(enhanced) = enhanced_marketplace else { return };
+    if let Err(e) = enhanced.set_fill_price(&execution.signal_id, fill_price_usd).await {
+        log::debug!("No tracked position to re-anchor for {}: {}", execution.signal_id, e);
+    }
+    if let (Some(performance), Some(coordinator)) = (enhanced.get_signal_performance(&execution.signal_id).await, rl_coordinator) {
+        coordinator.lock().await
+            .amend_entry_fill(&performance.provider_id, &execution.symbol, fill.token_amount, fill_price_usd).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(symbol: &str, price: f64, timestamp: i64) -> MarketData {
+        MarketData {
+            symbol: symbol.to_string(),
+            price,
+            volume: 1000.0,
+            timestamp,
+            bid: price,
+            ask: price,
+            spread: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_price_change_over_window() {
+        let mut engine = TradingEngine::new_default();
+        let now = 100_000;
+        let history: VecDeque<MarketData> = vec![
+            tick("SOL", 80.0, now - 90_000),
+            tick("SOL", 100.0, now - 86_000),
+            tick("SOL", 105.0, now - 3_600),
+            tick("SOL", 110.0, now),
+        ].into_iter().collect();
+        engine.market_state.insert("SOL".to_string(), history);
+
+        // Nearest point to 24h ago is the one 86,000s back
+        let (change, window) = engine.price_change_over("SOL", 86_400).unwrap();
+        assert!((change - 10.0).abs() < 1e-9);
+        assert_eq!(window, 86_000);
+
+        // 1h window picks the 1h-old point
+        let (change, window) = engine.price_change_over("SOL", 3_600).unwrap();
+        assert!((change - (5.0 / 105.0 * 100.0)).abs() < 1e-9);
+        assert_eq!(window, 3_600);
+
+        assert!(engine.price_change_over("BONK", 86_400).is_none());
+    }
+
+    #[test]
+    fn test_quote_drift_limit() {
+        let drift = QuoteDriftConfig { max_drift_pct: 5.0, max_price_impact_pct: 2.0 };
+        assert!(drift.excess_drift(1.0, 1.04).is_none());
+        assert!((drift.excess_drift(1.0, 1.2).unwrap() - 20.0).abs() < 1e-9);
+        assert!((drift.excess_drift(1.0, 0.9).unwrap() + 10.0).abs() < 1e-9);
+        assert!(QuoteDriftConfig { max_drift_pct: 0.0, ..drift }.excess_drift(1.0, 2.0).is_none());
+
+        // Impact at the sized amount is held to its own cap
+        assert!(drift.excess_impact(1.5).is_none());
+        assert_eq!(drift.excess_impact(-3.0), Some(3.0));
+        assert!(QuoteDriftConfig { max_price_impact_pct: 0.0, ..drift }.excess_impact(50.0).is_none());
+    }
+
+    #[test]
+    fn test_usd_prices_convert_to_the_quote_token() {
+        let mut engine = TradingEngine::new_default();
+        engine.base_currency = crate::dex_aggregator::BaseCurrency::Sol;
+        // RAY trades against the SOL base - no SOL mark, no conversion
+        assert_eq!(engine.usd_to_quote_price("RAY (Jupiter)", 3.0), None);
+
+        engine.market_state.insert("SOL/USD".to_string(), VecDeque::from(vec![tick("SOL/USD", 150.0, 0)]));
+        assert!((engine.usd_to_quote_price("RAY (Jupiter)", 3.0).unwrap() - 0.02).abs() < 1e-12);
+        assert!((engine.quote_to_usd_price("RAY", 0.02).unwrap() - 3.0).abs() < 1e-12);
+        // SOL itself trades against USDC; unresolvable symbols never reach a venue
+        assert_eq!(engine.usd_to_quote_price("SOL/USD", 150.0), Some(150.0));
+        assert_eq!(engine.usd_to_quote_price("BONK", 0.00002), Some(0.00002));
+    }
+
+    #[test]
+    fn test_limit_order_lifecycle() {
+        let mut engine = TradingEngine::new_default();
+        let now = Utc::now().timestamp();
+        let signal = crate::signal_platform::TradingSignalData {
+            id: "limit1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry: now + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+
+        let order = engine.place_limit_order(&signal).unwrap();
+        assert!(!order.is_triggered(101.0));
+        assert!(order.is_triggered(99.5));
+        // One resting order per signal
+        assert!(engine.place_limit_order(&signal).is_err());
+
+        assert!(engine.expire_orders(now).is_empty());
+        assert_eq!(engine.expire_orders(now + 3600).len(), 1);
+        assert!(engine.get_pending_orders().is_empty());
+
+        let order = engine.place_limit_order(&signal).unwrap();
+        assert_eq!(engine.cancel_order(&order.order_id).unwrap().status, OrderStatus::Cancelled);
+        assert!(engine.cancel_order(&order.order_id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dca_execute_averages_and_stops() {
+        let mut paper_engine = TradingEngine::new_default();
+        paper_engine.initial_balance = 10.0;
+        paper_engine.current_balance = 10.0;
+        let engine = Arc::new(Mutex::new(paper_engine));
+        let dry_run = Arc::new(Mutex::new(true));
+        let now = Utc::now().timestamp();
+        let signal = crate::signal_platform::TradingSignalData {
+            id: "dca1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "BONK".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 1.0,
+            target_price: 1.1,
+            stop_loss: 0.9,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry: now + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+        let config = DcaConfig { chunks: 4, interval_secs: 0 };
+
+        let result = TradingEngine::dca_execute(&engine, &signal, &config, None, Some(&dry_run)).await.unwrap();
+        assert_eq!(result.chunks_filled, 4);
+        assert!(result.aborted.is_none());
+        assert!((result.filled_size - result.target_size).abs() < 1e-9);
+        assert!((result.average_fill_price - 1.0).abs() < 1e-9);
+
+        // Market already below the stop - nothing is filled
+        engine.lock().await.market_state.insert("BONK".to_string(), VecDeque::from(vec![tick("BONK", 0.85, now)]));
+        assert!(TradingEngine::dca_execute(&engine, &signal, &config, None, Some(&dry_run)).await.is_err());
+
+        // Live globally with trading off, but the signal forces paper - the chunks still fill
+        let mut paper_engine = TradingEngine::new_default();
+        paper_engine.initial_balance = 10.0;
+        paper_engine.current_balance = 10.0;
+        let engine = Arc::new(Mutex::new(paper_engine));
+        let live = Arc::new(Mutex::new(false));
+        let disabled = Arc::new(Mutex::new(false));
+        let forced = crate::signal_platform::TradingSignalData { id: "dca2".to_string(), force_dry_run: true, ..signal };
+        let result = TradingEngine::dca_execute(&engine, &forced, &config, Some(&disabled), Some(&live)).await.unwrap();
+        assert_eq!(result.chunks_filled, 4);
+    }
+
+    #[test]
+    fn test_execution_state_follows_chain_confirmation() {
+        use crate::database::{ExecutionRecord, ExecutionStatus};
+        use crate::rpc_client::SignatureConfirmation;
+        let mut execution = ExecutionRecord {
+            signature: "5sig".to_string(),
+            signal_id: "sig_1".to_string(),
+            symbol: "SOL/USDC".to_string(),
+            action: "BUY".to_string(),
+            size: 1.0,
+            price: 100.0,
+            submitted_at: 1_000,
+            confirmation_slot: None,
+            status: ExecutionStatus::Submitted,
+            error: None,
+            updated_at: 1_000,
+            wallet: None,
+            filled_size: None,
+            fill_price: None,
+            venue: None,
+        };
+        let observed = |confirmed, finalized, error: Option<&str>| SignatureConfirmation {
+            slot: 42,
+            confirmed,
+            finalized,
+            error: error.map(str::to_string),
+        };
+        
+        // Not seen yet, blockhash still valid - keep polling
+        assert_eq!(next_execution_state(&execution, None, 1_010), None);
+        assert_eq!(next_execution_state(&execution, Some(&observed(false, false, None)), 1_010), None);
+        
+        let (status, slot, _) = next_execution_state(&execution, Some(&observed(true, false, None)), 1_010).unwrap();
+        assert_eq!((status.clone(), slot), (ExecutionStatus::Confirmed, Some(42)));
+        execution.status = status;
+        execution.confirmation_slot = slot;
+        assert_eq!(next_execution_state(&execution, Some(&observed(true, false, None)), 1_015), None);
+        // A confirmed swap that ages out of the status cache is not failed
+        assert_eq!(next_execution_state(&execution, None, 2_000), None);
+        assert_eq!(next_execution_state(&execution, Some(&observed(true, true, None)), 1_030).unwrap().0, ExecutionStatus::Finalized);
+        
+        execution.status = ExecutionStatus::Submitted;
+        let (status, _, error) = next_execution_state(&execution, Some(&observed(true, false, Some("custom program error"))), 1_010).unwrap();
+        assert_eq!(status, ExecutionStatus::Failed);
+        assert_eq!(error.as_deref(), Some("custom program error"));
+        assert_eq!(next_execution_state(&execution, None, 2_000).unwrap().0, ExecutionStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_submitted_swap_is_recorded_by_signature() {
+        let database = Arc::new(Mutex::new(crate::database::Database::new_in_memory()));
+        let engine = TradingEngine::new_default().with_database(database.clone());
+        let signal = TradingSignal {
+            id: "sig_live".to_string(),
+            action: TradeAction::Buy,
+            symbol: "BONK".to_string(),
+            price: 0.001,
+            confidence: 0.8,
+            size: 1_000.0,
+            stop_loss: 0.0009,
+            take_profit: 0.0012,
+            timestamp: 1_000,
+            wallet: Some("momentum".to_string()),
+            ..Default::default()
+        };
+        
+        // Simulated trade ids have nothing on-chain to poll
+        engine.record_execution_submitted(&signal, "DRY_RUN_BUY_BONK_1", None).await;
+        assert!(engine.live_execution("sig_live").await.is_none());
+        
+        let signature = solana_sdk::signature::Signature::new_unique().to_string();
+        engine.record_execution_submitted(&signal, &signature, Some("raydium")).await;
+        let execution = engine.live_execution("sig_live").await.unwrap();
+        assert_eq!(execution.signature, signature);
+        assert_eq!(execution.status, crate::database::ExecutionStatus::Submitted);
+        assert_eq!((execution.symbol.as_str(), execution.action.as_str()), ("BONK", "BUY"));
+        assert_eq!((execution.size, execution.price), (1_000.0, 0.001));
+        assert_eq!(execution.wallet.as_deref(), Some("momentum"));
+        assert_eq!(execution.venue.as_deref(), Some("raydium"));
+        assert_eq!(database.lock().await.get_execution(&signature).map(|e| e.signal_id.clone()).as_deref(), Some("sig_live"));
+    }
+
+    #[test]
+    fn test_partial_fill_corrects_position_and_trade() {
+        use crate::database::{ExecutionRecord, ExecutionStatus};
+        let mut engine = TradingEngine::new_default();
+        engine.portfolio.insert("BONK".to_string(), 1_000.0);
+        engine.trade_history.push(TradingSignal {
+            id: "sig_fill".to_string(),
+            action: TradeAction::Buy,
+            symbol: "BONK".to_string(),
+            price: 0.001,
+            confidence: 0.8,
+            size: 1_000.0,
+            stop_loss: 0.0009,
+            take_profit: 0.0012,
+            timestamp: 1_000,
+            aggregator: Some("jupiter".to_string()),
+            ..Default::default()
+        });
+        let execution = ExecutionRecord {
+            signature: "5fill".to_string(),
+            signal_id: "sig_fill".to_string(),
+            symbol: "BONK".to_string(),
+            action: "BUY".to_string(),
+            size: 1_000.0,
+            price: 0.001,
+            submitted_at: 1_000,
+            confirmation_slot: Some(42),
+            status: ExecutionStatus::Confirmed,
+            error: None,
+            updated_at: 1_010,
+            wallet: None,
+            filled_size: None,
+            fill_price: None,
+            venue: None,
+        };
+        
+        // The 1 SOL quote was booked when the swap went out
+        engine.current_balance = 9.0;
+        
+        // Only 600 of 1000 tokens arrived, for 0.66 SOL
+        let fill = crate::rpc_client::SwapFill { token_amount: 600.0, quote_amount: 0.66, fee_lamports: 5_000 };
+        let price = engine.apply_swap_fill(&execution, &fill).unwrap();
+        assert!((price - 0.0011).abs() < 1e-12);
+        assert!((engine.portfolio["BONK"] - 600.0).abs() < 1e-9);
+        assert!((engine.current_balance - 9.34).abs() < 1e-9);
+        assert_eq!(engine.fill_size("sig_fill"), Some(600.0));
+        assert!((engine.fill_price("sig_fill").unwrap() - 0.0011).abs() < 1e-12);
+        
+        // A swap whose submission timed out was never booked - landing books the whole fill
+        let late = ExecutionRecord { signature: "5late".to_string(), signal_id: "sig_late".to_string(), ..execution };
+        engine.apply_swap_fill(&late, &fill).unwrap();
+        assert!((engine.portfolio["BONK"] - 1_200.0).abs() < 1e-9);
+        assert_eq!(engine.fill_size("sig_late"), Some(600.0));
+        assert!((engine.current_balance - 8.68).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_realized_fee_replaces_estimate() {
+        use crate::database::{ExecutionRecord, ExecutionStatus};
+        let mut engine = TradingEngine::new_default();
+        engine.base_currency = crate::dex_aggregator::BaseCurrency::Sol;
+        let optimizer = Arc::new(Mutex::new(crate::fee_optimization::FeeOptimizer::new(5000)));
+        engine.fee_optimizer = Some(optimizer.clone());
+        engine.current_balance = 10.0;
+        
+        // Booked at submission: a 20k lamport estimate
+        engine.record_fee_paid("sig_fee", 0.00002);
+        engine.fee_estimates.insert("sig_fee".to_string(), 20_000);
+        optimizer.lock().await.record_fee_spent(20_000, Utc::now().timestamp());
+        
+        let execution = ExecutionRecord {
+            signature: "5fee".to_string(),
+            signal_id: "sig_fee".to_string(),
+            symbol: "BONK".to_string(),
+            action: "BUY".to_string(),
+            size: 1_000.0,
+            price: 0.001,
+            submitted_at: 1_000,
+            confirmation_slot: Some(42),
+            status: ExecutionStatus::Confirmed,
+            error: None,
+            updated_at: 1_010,
+            wallet: None,
+            filled_size: None,
+            fill_price: None,
+            venue: None,
+        };
+        engine.record_realized_fee(&execution, 5_000).await;
+        assert!((engine.realized_fee("sig_fee").unwrap() - 0.000005).abs() < 1e-12);
+        assert!((engine.current_balance - 10.000015).abs() < 1e-12);
+        assert_eq!(optimizer.lock().await.budget_status(Utc::now().timestamp()).spent_lamports, 5_000);
+        assert!(engine.fee_estimates.is_empty());
+    }
+
+    #[test]
+    fn test_cost_basis_through_partial_fills_and_closes() {
+        let mut basis = CostBasis::default();
+        basis.buy(10.0, 1.0, 0.0);
+        basis.buy(10.0, 2.0, 0.0);
+        assert!((basis.average_entry - 1.5).abs() < 1e-12);
+
+        // Partial close realizes against the average and keeps it for the rest
+        let realized = basis.sell(5.0, 2.5, 0.01);
+        assert!((realized - (5.0 * 1.0 - 0.01)).abs() < 1e-12);
+        assert_eq!(basis.size, 15.0);
+        assert!((basis.average_entry - 1.5).abs() < 1e-12);
+
+        // The 10 @ 2.0 buy only filled 6 @ 2.1
+        basis.amend_fill(true, (10.0, 2.0), (6.0, 2.1));
+        assert!((basis.size - 11.0).abs() < 1e-12);
+        assert!((basis.average_entry - (5.0 * 1.0 + 6.0 * 2.1) / 11.0).abs() < 1e-9);
+
+        // A sell booked as 4 @ 3.0 only sold 3 @ 2.9
+        let average = basis.average_entry;
+        let delta = basis.amend_fill(false, (4.0, 3.0), (3.0, 2.9));
+        assert!((delta - (3.0 * (2.9 - average) - 4.0 * (3.0 - average))).abs() < 1e-9);
+        assert!((basis.size - 12.0).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_breakdown_splits_realized_and_unrealized() {
+        let mut engine = TradingEngine::new_default();
+        engine.initial_balance = 10.0;
+        engine.current_balance = 10.0;
+        let dry_run = Arc::new(Mutex::new(true));
+        let trade = |id: &str, action: TradeAction, size: f64, price: f64| TradingSignal {
+            id: id.to_string(),
+            action,
+            symbol: "SOL/USD".to_string(),
+            price,
+            confidence: 0.9,
+            size,
+            stop_loss: 0.0,
+            take_profit: 0.0,
+            timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        };
+        assert!(engine.execute_trade(&trade("b1", TradeAction::Buy, 0.002, 100.0), None, Some(&dry_run)).await);
+        assert!(engine.execute_trade(&trade("b2", TradeAction::Buy, 0.002, 110.0), None, Some(&dry_run)).await);
+        assert!(engine.execute_trade(&trade("s1", TradeAction::Sell, 0.001, 120.0), None, Some(&dry_run)).await);
+        assert!((engine.realized_pnl - 0.015).abs() < 1e-9);
+
+        let positions = engine.position_breakdown();
+        assert_eq!(positions.len(), 1);
+        let position = &positions[0];
+        assert!((position.size - 0.003).abs() < 1e-12);
+        assert!((position.average_entry - 105.0).abs() < 1e-9);
+        // Marked at the last trade (120) until market data arrives
+        assert!((position.unrealized_pnl.unwrap() - 0.045).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_simulation_sizes_without_trading() {
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 10.0;
+        let engine = Arc::new(Mutex::new(engine));
+        let mut signal = crate::signal_platform::TradingSignalData {
+            id: "what-if".to_string(),
+            provider: "memecoin_monitor".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.9,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: 0,
+            expiry: i64::MAX,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+
+        let simulation = TradingEngine::simulate_marketplace_signal(&engine, &signal).await.unwrap();
+        assert!(simulation.would_execute, "{:?}", simulation.rejections);
+        // 10% of the balance at the entry price, default 5000 lamport fee, no quote without Jupiter
+        assert!((simulation.size - 0.01).abs() < 1e-12);
+        assert!((simulation.fee_sol - 0.000005).abs() < 1e-12);
+        assert!(simulation.quote_error.is_some());
+        assert!((simulation.pnl_at_target - (0.1 - 0.000005)).abs() < 1e-9);
+        assert!((simulation.pnl_at_stop - (-0.05 - 0.000005)).abs() < 1e-9);
+
+        signal.confidence = 0.4;
+        let simulation = TradingEngine::simulate_marketplace_signal(&engine, &signal).await.unwrap();
+        assert!(!simulation.would_execute);
+        assert!(simulation.rejections.iter().any(|r| r.contains("Confidence")));
+
+        // Nothing was traded or booked
+        let engine = engine.lock().await;
+        assert_eq!(engine.current_balance, 10.0);
+        assert!(engine.trade_history.is_empty());
+        assert!(engine.portfolio.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_cooldown_after_trade() {
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 1.0;
+        engine.portfolio.insert("RAY".to_string(), 100.0);
+        let now = Utc::now().timestamp();
+        assert_eq!(engine.symbol_cooldown_remaining("RAY", 300, now), None);
+
+        // Closing counts as a trade on the symbol
+        engine.close_position("RAY", 0.01, true).await.unwrap();
+        let remaining = engine.symbol_cooldown_remaining("RAY", 300, now).unwrap();
+        assert!((300..=301).contains(&remaining));
+        assert_eq!(engine.symbol_cooldown_remaining("RAY", 300, now + 301), None);
+        assert_eq!(engine.symbol_cooldown_remaining("RAY", 0, now), None);
+        assert_eq!(engine.symbol_cooldown_remaining("SOL", 300, now), None);
+    }
+
+    #[tokio::test]
+    async fn test_exit_swaps_into_close_to_asset() {
+        let mut engine = TradingEngine::new_default();
+        engine.current_balance = 1.0;
+        engine.portfolio.insert("RAY".to_string(), 1_000.0);
+        engine.portfolio.insert("SOL/USD".to_string(), 2.0);
+
+        // Holding swaps nothing
+        assert_eq!(engine.exit_position("RAY", 400.0, 0.001, true, CloseTo::Hold).await.unwrap(), None);
+        assert_eq!(engine.portfolio["RAY"], 1_000.0);
+
+        let proceeds = engine.exit_position("RAY", 400.0, 0.001, true, CloseTo::Sol).await.unwrap().unwrap();
+        assert_eq!(proceeds.asset, CloseTo::Sol);
+        assert!((proceeds.amount - 0.4).abs() < 1e-12);
+        assert!((engine.portfolio["RAY"] - 600.0).abs() < 1e-9);
+
+        // No SOL price for the USDC leg - the proceeds stay in SOL
+        let stuck = engine.exit_position("RAY", 100.0, 0.001, true, CloseTo::Usdc).await.unwrap().unwrap();
+        assert_eq!(stuck.asset, CloseTo::Sol);
+        engine.market_state.insert("SOL/USDC".to_string(), VecDeque::from(vec![tick("SOL/USDC", 150.0, 0)]));
+        let usdc = engine.exit_position("RAY", 100.0, 0.001, true, CloseTo::Usdc).await.unwrap().unwrap();
+        assert_eq!(usdc.asset, CloseTo::Usdc);
+        assert!((usdc.amount - 15.0).abs() < 1e-9);
+
+        // A SOL position closed to SOL is already there
+        let sol = engine.exit_position("SOL/USD", 2.0, 150.0, true, CloseTo::Sol).await.unwrap().unwrap();
+        assert_eq!(sol, ExitProceeds { asset: CloseTo::Sol, amount: 2.0, fill_price: None });
+        assert_eq!(engine.portfolio["SOL/USD"], 2.0);
+        assert!(engine.exit_position("RAY", 1.0, 0.001, true, CloseTo::Sol).await.is_ok());
+        assert!(engine.exit_position("JUP", 1.0, 0.001, true, CloseTo::Sol).await.is_err());
+
+        // USDC base: tokens settle straight into USDC, and closing to SOL buys SOL with it
+        engine.base_currency = crate::dex_aggregator::BaseCurrency::Usdc;
+        let usdc = engine.exit_position("RAY", 100.0, 0.3, true, CloseTo::Usdc).await.unwrap().unwrap();
+        assert_eq!(usdc, ExitProceeds { asset: CloseTo::Usdc, amount: 30.0, fill_price: Some(0.3) });
+        let sol = engine.exit_position("RAY", 100.0, 0.3, true, CloseTo::Sol).await.unwrap().unwrap();
+        assert_eq!(sol.asset, CloseTo::Sol);
+        assert!((sol.amount - 0.2).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_providers_sharing_a_token_keep_their_own_wallet_positions() {
+        let mut engine = TradingEngine::new_default();
+        let buy = |id: &str, size: f64, wallet: Option<&str>| TradingSignal {
+            id: id.to_string(),
+            action: TradeAction::Buy,
+            symbol: "RAY".to_string(),
+            price: 1.0,
+            size,
+            wallet: wallet.map(str::to_string),
+            ..Default::default()
+        };
+        for (provider_id, signal) in [("momentum_trader", buy("b1", 2.0, Some("momentum"))), ("memecoin_monitor", buy("b2", 1.0, None))] {
+            *engine.portfolio.entry("RAY".to_string()).or_default() += signal.size;
+            engine.cost_basis.entry("RAY".to_string()).or_default().buy(signal.size, signal.price, 0.0);
+            engine.record_fill(Some(provider_id), &signal).await;
+        }
+        
+        // The second provider's buy didn't take over the first one's position
+        assert_eq!(engine.symbol_wallets("RAY"), vec![("default".to_string(), 1.0), ("momentum".to_string(), 2.0)]);
+        assert_eq!(engine.route_wallet("momentum_trader", "RAY", &TradeAction::Sell), "momentum");
+        assert_eq!(engine.sell_source(Some("memecoin_monitor"), "RAY"), Some(("default".to_string(), 1.0)));
+        
+        // A provider's sell only draws down its own position
+        let sell = TradingSignal { action: TradeAction::Sell, price: 1.5, ..buy("s1", 1.0, None) };
+        engine.portfolio.insert("RAY".to_string(), 2.0);
+        engine.book_fill(None, "RAY", &TradeAction::Sell, 1.0, 1.5, 0.0);
+        engine.record_fill(Some("memecoin_monitor"), &sell).await;
+        assert_eq!(engine.symbol_wallets("RAY"), vec![("momentum".to_string(), 2.0)]);
+        assert!((engine.wallet_realized_pnl["default"] - 0.5).abs() < 1e-9);
+        assert!(!engine.wallet_realized_pnl.contains_key("momentum"));
+        
+        // Closing sells from the wallet that holds the tokens
+        assert_eq!(engine.close_position("RAY", 1.5, true).await.unwrap(), 2.0);
+        assert!(engine.symbol_wallets("RAY").is_empty());
+        assert!(engine.wallet_realized_pnl["momentum"] > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_paper_fill_pays_slippage_and_fee() {
+        let mut engine = TradingEngine::new_default();
+        engine.fee_optimizer = Some(Arc::new(Mutex::new(crate::fee_optimization::FeeOptimizer::new(5000))));
+        engine.paper_fills = PaperFillConfig { enabled: true, random_slippage_bps: 50.0 };
+        engine.initial_balance = 10.0;
+        engine.current_balance = 10.0;
+        let dry_run = Arc::new(Mutex::new(true));
+        let signal = TradingSignal {
+            id: "paper1".to_string(),
+            action: TradeAction::Buy,
+            symbol: "SOL/USD".to_string(),
+            price: 100.0,
+            confidence: 0.9,
+            size: 0.01,
+            stop_loss: 95.0,
+            take_profit: 110.0,
+            timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        };
+
+        assert!(engine.execute_trade(&signal, None, Some(&dry_run)).await);
+        let fill_price = engine.fill_price("paper1").unwrap();
+        assert!((100.0..=100.5).contains(&fill_price));
+        let expected_balance = 10.0 - 0.01 * fill_price - 0.000005;
+        assert!((engine.current_balance - expected_balance).abs() < 1e-12);
+
+        // Impact and slippage always work against the trade
+        let sell = PaperFill::new(100.0, false, 0.5, 20.0, 0.0);
+        assert!((sell.fill_price - 99.3).abs() < 1e-9);
+        assert_eq!(PaperFill::new(100.0, true, 0.0, 0.0, 0.0).fill_price, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_execution_is_pushed_to_websocket() {
+        let broadcaster = crate::websocket::create_ws_broadcaster();
+        let mut rx = broadcaster.subscribe();
+        let mut engine = TradingEngine::new_default().with_ws_broadcaster(broadcaster);
+        engine.initial_balance = 10.0;
+        engine.current_balance = 10.0;
+        let dry_run = Arc::new(Mutex::new(true));
+        let now = Utc::now().timestamp();
+        let signal = crate::signal_platform::TradingSignalData {
+            id: "ws1".to_string(),
+            provider: "provider1".to_string(),
+            symbol: "SOL/USD".to_string(),
+            action: crate::signal_platform::SignalAction::Buy,
+            entry_price: 100.0,
+            target_price: 110.0,
+            stop_loss: 95.0,
+            confidence: 0.8,
+            timeframe: "1h".to_string(),
+            data_sources: vec![],
+            analysis: String::new(),
+            timestamp: now,
+            expiry: now + 3600,
+            price: 0.0,
+            status: crate::signal_platform::SignalStatus::Active,
+            ..Default::default()
+        };
+
+        engine.execute_marketplace_signal(&signal, None, Some(&dry_run)).await.unwrap();
+        match rx.try_recv().unwrap() {
+            crate::websocket::WSMessage::TradeExecuted(trade) => {
+                assert_eq!(trade.signal_id, "ws1");
+                assert_eq!(trade.action, "BUY");
+                assert!(trade.dry_run);
+                assert!((trade.balance - engine.current_balance).abs() < 1e-9);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        
+        // Global mode is live, but the signal forces a paper fill
+        let broadcaster = crate::websocket::create_ws_broadcaster();
+        let mut rx = broadcaster.subscribe();
+        let mut engine = TradingEngine::new_default().with_ws_broadcaster(broadcaster);
+        engine.initial_balance = 10.0;
+        engine.current_balance = 10.0;
+        let live = Arc::new(Mutex::new(false));
+        let forced = crate::signal_platform::TradingSignalData {
+            id: "ws2".to_string(),
+            force_dry_run: true,
+            ..signal
+        };
+        engine.execute_marketplace_signal(&forced, None, Some(&live)).await.unwrap();
+        match rx.try_recv().unwrap() {
+            crate::websocket::WSMessage::TradeExecuted(trade) => {
+                assert_eq!(trade.signal_id, "ws2");
+                assert!(trade.dry_run);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert!(!*live.lock().await);
+    }
+}
+
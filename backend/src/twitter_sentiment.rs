@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use crate::error_handling::{TradingError, retry_with_backoff_retryable, RetryConfig, map_http_status_to_error};
+use crate::error_handling::{AppError, retry_with_backoff_retryable, RetryConfig, map_http_status_to_error};
 use crate::http_client::SharedHttpClient;
 use reqwest::Client;
 
@@ -90,13 +90,13 @@ impl TwitterSentimentClient {
     /// 
     /// # Returns
     /// * `Ok(TwitterSentimentData)` - Sentiment analysis results
-    /// * `Err(TradingError)` - Error if analysis fails
+    /// * `Err(AppError)` - Error if analysis fails
     pub async fn get_sentiment(
         &self,
         symbol: &str,
         search_term: Option<String>,
         community_accounts: Option<Vec<String>>,
-    ) -> Result<TwitterSentimentData, TradingError> {
+    ) -> Result<TwitterSentimentData, AppError> {
         let url = format!("{}/api/sentiment", self.service_url);
         
         let request = SentimentRequest {
@@ -119,16 +119,7 @@ impl TwitterSentimentClient {
                         .json(&request)
                         .send()
                         .await
-                        .map_err(|e| {
-                            let error_str = e.to_string();
-                            if e.is_timeout() || error_str.contains("timeout") || error_str.contains("timed out") {
-                                TradingError::TimeoutError(format!("Twitter sentiment service timeout: {}", e))
-                            } else if error_str.contains("dns") || error_str.contains("connection") || error_str.contains("No such host") {
-                                TradingError::NetworkError(format!("Twitter sentiment service connection error: {}", e))
-                            } else {
-                                TradingError::NetworkError(format!("Twitter sentiment API error: {}", e))
-                            }
-                        })?;
+                        .map_err(AppError::from)?;
 
                     if !response.status().is_success() {
                         let status = response.status().as_u16();
@@ -140,7 +131,7 @@ impl TwitterSentimentClient {
                     let data: TwitterSentimentData = response
                         .json()
                         .await
-                        .map_err(|e| TradingError::ApiError(format!("Failed to parse sentiment response: {}", e)))?;
+                        .map_err(|e| AppError::Api(format!("Failed to parse sentiment response: {}", e)))?;
 
                     Ok(data)
                 })
@@ -157,8 +148,8 @@ impl TwitterSentimentClient {
     /// # Returns
     /// * `Ok(true)` - Service is healthy
     /// * `Ok(false)` - Service is not responding
-    /// * `Err(TradingError)` - Network error
-    pub async fn health_check(&self) -> Result<bool, TradingError> {
+    /// * `Err(AppError)` - Network error
+    pub async fn health_check(&self) -> Result<bool, AppError> {
         let url = format!("{}/health", self.service_url);
         
         match self.client.get(&url).send().await {
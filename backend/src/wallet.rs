@@ -13,6 +13,18 @@ use std::path::Path;
 use std::fs;
 use std::sync::Arc;
 
+/// Name of the main wallet (WALLET_PRIVATE_KEY) - every provider trades from it unless assigned elsewhere
+pub const DEFAULT_WALLET_NAME: &str = "default";
+
+/// Env var holding a strategy wallet's key: "memecoin" -> WALLET_PRIVATE_KEY_MEMECOIN
+pub fn key_env_for(wallet_name: &str) -> String {
+    if wallet_name == DEFAULT_WALLET_NAME {
+        "WALLET_PRIVATE_KEY".to_string()
+    } else {
+        format!("WALLET_PRIVATE_KEY_{}", wallet_name.to_uppercase())
+    }
+}
+
 /// Real Solana Wallet SDK implementation
 /// Provides full wallet functionality using official Solana SDK
 pub struct Wallet {
@@ -94,6 +106,18 @@ impl Wallet {
         })
     }
 
+    /// Load wallet from an environment variable - unlike from_env_or_new, a missing key is an error
+    /// (a strategy wallet must never silently become a fresh, empty keypair)
+    pub fn from_env(env_var: &str) -> Result<Self, String> {
+        let key = std::env::var(env_var)
+            .map_err(|_| format!("Environment variable {} not found", env_var))?;
+        let mut wallet = Self::from_base58(&key)?;
+        if let Ok(rpc_url) = std::env::var("SOLANA_RPC_URL") {
+            wallet.set_rpc_client(rpc_url);
+        }
+        Ok(wallet)
+    }
+
     /// Load wallet from environment variable or generate new one
     pub fn from_env_or_new(env_var: &str) -> Self {
         match std::env::var(env_var) {
@@ -0,0 +1,342 @@
+//! Background Task Watchdog
+//! Long-running loops report heartbeats; the watchdog flags any task that stops ticking
+//! beyond its timeout and re-spawns it (catch_unwind handles panics, this handles hangs)
+
+use chrono::Utc;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::notifications::{Notification, NotificationEvent, Notifier};
+
+/// Watchdog configuration
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    /// When false, heartbeats are still tracked but stalled tasks are not restarted
+    pub enabled: bool,
+    pub check_interval_secs: u64,
+    /// Default time without a heartbeat before a task counts as stalled
+    pub timeout_secs: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval_secs: 30,
+            timeout_secs: 600, // Longest loop backoff is 5 minutes
+        }
+    }
+}
+
+impl WatchdogConfig {
+    /// Load from WATCHDOG_ENABLED / WATCHDOG_CHECK_INTERVAL_SECS / WATCHDOG_TIMEOUT_SECS
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("WATCHDOG_ENABLED")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(defaults.enabled),
+            check_interval_secs: std::env::var("WATCHDOG_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(defaults.check_interval_secs)
+                .max(1),
+            timeout_secs: std::env::var("WATCHDOG_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(defaults.timeout_secs)
+                .max(1),
+        }
+    }
+}
+
+/// Cheap, cloneable heartbeat handle shared between a task and the watchdog
+#[derive(Debug, Clone)]
+pub struct Heartbeat(Arc<AtomicI64>);
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicI64::new(Utc::now().timestamp())))
+    }
+
+    /// Record that the task is still making progress
+    pub fn beat(&self) {
+        self.0.store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    pub fn last_beat(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Restart hook invoked when a task is found stalled
+pub type RestartFn = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// The running instance of a supervised task - replaced on every restart
+pub type TaskHandle = Arc<Mutex<Option<JoinHandle<()>>>>;
+
+/// How often a supervised server reports it is still up, and how long without that before it is re-spawned
+pub const SERVER_HEARTBEAT_SECS: u64 = 5;
+pub const SERVER_TIMEOUT_SECS: u64 = 30;
+
+/// Run `server`, beating `heartbeat` for as long as it is up. A server has no loop of its own to
+/// tick from - once it exits or panics the beats stop and the watchdog re-spawns it
+pub async fn beat_while_running<Fut: Future<Output = ()>>(heartbeat: Heartbeat, server: Fut) {
+    tokio::pin!(server);
+    let mut ticker = tokio::time::interval(tokio::time::Duration::from_secs(SERVER_HEARTBEAT_SECS));
+    loop {
+        tokio::select! {
+            _ = &mut server => return,
+            _ = ticker.tick() => heartbeat.beat(),
+        }
+    }
+}
+
+struct SupervisedTask {
+    heartbeat: Heartbeat,
+    timeout_secs: u64,
+    restarts: u32,
+    last_restart_at: Option<i64>,
+    restart: RestartFn,
+}
+
+/// Per-task heartbeat status for /health/detailed
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    pub name: String,
+    pub last_heartbeat: i64,
+    pub seconds_since_heartbeat: i64,
+    pub timeout_secs: u64,
+    pub stalled: bool,
+    pub restarts: u32,
+    pub last_restart_at: Option<i64>,
+}
+
+pub struct Watchdog {
+    config: WatchdogConfig,
+    tasks: Mutex<HashMap<String, SupervisedTask>>,
+    notifier: Option<Arc<dyn Notifier>>,
+}
+
+impl Watchdog {
+    pub fn new(config: WatchdogConfig, notifier: Option<Arc<dyn Notifier>>) -> Self {
+        Self {
+            config,
+            tasks: Mutex::new(HashMap::new()),
+            notifier,
+        }
+    }
+
+    pub fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+
+    /// Register a task that manages its own lifecycle; `restart` is called when it stalls
+    pub async fn register(&self, name: &str, heartbeat: Heartbeat, timeout_secs: Option<u64>, restart: RestartFn) {
+        let mut tasks = self.tasks.lock().await;
+        tasks.insert(name.to_string(), SupervisedTask {
+            heartbeat,
+            timeout_secs: timeout_secs.unwrap_or(self.config.timeout_secs),
+            restarts: 0,
+            last_restart_at: None,
+            restart,
+        });
+    }
+
+    /// Stop supervising a task that is meant to exit (e.g. a server draining on shutdown)
+    pub async fn unregister(&self, name: &str) {
+        self.tasks.lock().await.remove(name);
+    }
+
+    /// Spawn a task from `factory` and re-spawn it (aborting the stalled instance) when it stops ticking
+    pub async fn spawn_supervised<F, Fut>(&self, name: &str, heartbeat: Heartbeat, timeout_secs: Option<u64>, factory: F) -> TaskHandle
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let factory = Arc::new(factory);
+        let handle: TaskHandle = Arc::new(Mutex::new(Some(tokio::spawn(factory()))));
+        let task_handle = handle.clone();
+
+        let restart: RestartFn = Arc::new(move || {
+            let factory = factory.clone();
+            let handle = handle.clone();
+            Box::pin(async move {
+                let mut handle = handle.lock().await;
+                if let Some(stalled) = handle.take() {
+                    stalled.abort();
+                }
+                *handle = Some(tokio::spawn(factory()));
+            })
+        });
+
+        self.register(name, heartbeat, timeout_secs, restart).await;
+        task_handle
+    }
+
+    /// Heartbeat status of every supervised task
+    pub async fn snapshot(&self) -> Vec<TaskStatus> {
+        let now = Utc::now().timestamp();
+        let tasks = self.tasks.lock().await;
+        let mut statuses: Vec<TaskStatus> = tasks.iter()
+            .map(|(name, task)| {
+                let last_heartbeat = task.heartbeat.last_beat();
+                let seconds_since_heartbeat = (now - last_heartbeat).max(0);
+                TaskStatus {
+                    name: name.clone(),
+                    last_heartbeat,
+                    seconds_since_heartbeat,
+                    timeout_secs: task.timeout_secs,
+                    stalled: seconds_since_heartbeat as u64 > task.timeout_secs,
+                    restarts: task.restarts,
+                    last_restart_at: task.last_restart_at,
+                }
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// Run a single supervision pass; returns the names of restarted tasks
+    pub async fn check_once(&self) -> Vec<String> {
+        let now = Utc::now().timestamp();
+        let mut stalled: Vec<(String, i64, RestartFn)> = Vec::new();
+
+        {
+            let mut tasks = self.tasks.lock().await;
+            for (name, task) in tasks.iter_mut() {
+                let silent_for = now - task.heartbeat.last_beat();
+                if silent_for <= task.timeout_secs as i64 {
+                    continue;
+                }
+
+                log::error!("🐕 Task '{}' has not reported a heartbeat for {}s (timeout {}s)",
+                           name, silent_for, task.timeout_secs);
+                if !self.config.enabled {
+                    continue;
+                }
+
+                task.restarts += 1;
+                task.last_restart_at = Some(now);
+                // Give the new instance a full timeout before judging it
+                task.heartbeat.beat();
+                stalled.push((name.clone(), silent_for, task.restart.clone()));
+            }
+        }
+
+        let mut restarted = Vec::new();
+        for (name, silent_for, restart) in stalled {
+            log::warn!("🔄 Watchdog restarting stalled task '{}'", name);
+            restart().await;
+
+            if let Some(ref notifier) = self.notifier {
+                let notification = Notification::new(
+                    NotificationEvent::TaskRestarted,
+                    "🐕 Watchdog restarted a stalled task",
+                    &format!("Task '{}' stopped ticking for {}s and was re-spawned", name, silent_for),
+                );
+                let result = notifier.send(&notification).await;
+                if !result.delivered {
+                    log::warn!("⚠️ Failed to deliver watchdog notification: {:?}", result.error);
+                }
+            }
+            restarted.push(name);
+        }
+        restarted
+    }
+
+    /// Start the supervision loop in the background
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        log::info!("🐕 Watchdog started (check every {}s, default timeout {}s, restarts {})",
+                  self.config.check_interval_secs, self.config.timeout_secs,
+                  if self.config.enabled { "ENABLED" } else { "DISABLED" });
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(self.config.check_interval_secs));
+            loop {
+                interval.tick().await;
+                self.check_once().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[tokio::test]
+    async fn test_stalled_task_is_restarted() {
+        let watchdog = Watchdog::new(WatchdogConfig { enabled: true, check_interval_secs: 1, timeout_secs: 60 }, None);
+        let heartbeat = Heartbeat::new();
+        let spawns = Arc::new(AtomicU32::new(0));
+
+        let spawn_counter = spawns.clone();
+        watchdog.spawn_supervised("hung_loop", heartbeat.clone(), None, move || {
+            let spawn_counter = spawn_counter.clone();
+            async move {
+                spawn_counter.fetch_add(1, Ordering::SeqCst);
+                // Simulate a task stuck forever
+                futures::future::pending::<()>().await;
+            }
+        }).await;
+        tokio::task::yield_now().await;
+
+        // Fresh heartbeat - nothing to do
+        assert!(watchdog.check_once().await.is_empty());
+
+        // Pretend the last heartbeat was long ago
+        heartbeat.0.store(Utc::now().timestamp() - 120, Ordering::Relaxed);
+        assert!(watchdog.snapshot().await[0].stalled);
+        assert_eq!(watchdog.check_once().await, vec!["hung_loop".to_string()]);
+        tokio::task::yield_now().await;
+
+        assert_eq!(spawns.load(Ordering::SeqCst), 2);
+        let status = &watchdog.snapshot().await[0];
+        assert_eq!(status.restarts, 1);
+        assert!(!status.stalled);
+    }
+
+    #[tokio::test]
+    async fn test_exited_server_stops_beating() {
+        let heartbeat = Heartbeat::new();
+        heartbeat.0.store(0, Ordering::Relaxed);
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(beat_while_running(heartbeat.clone(), async move {
+            let _ = stop_rx.await;
+        }));
+        tokio::task::yield_now().await;
+        assert!(heartbeat.last_beat() > 0);
+
+        // The server exits - so does its heartbeat
+        stop_tx.send(()).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), server).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_disabled_watchdog_only_reports() {
+        let watchdog = Watchdog::new(WatchdogConfig { enabled: false, check_interval_secs: 1, timeout_secs: 10 }, None);
+        let heartbeat = Heartbeat::new();
+        let restart: RestartFn = Arc::new(|| Box::pin(async {}));
+        watchdog.register("feed", heartbeat.clone(), Some(5), restart).await;
+
+        heartbeat.0.store(Utc::now().timestamp() - 30, Ordering::Relaxed);
+        assert!(watchdog.check_once().await.is_empty());
+        let status = &watchdog.snapshot().await[0];
+        assert!(status.stalled);
+        assert_eq!(status.timeout_secs, 5);
+        assert_eq!(status.restarts, 0);
+    }
+}
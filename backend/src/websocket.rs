@@ -22,11 +22,27 @@ pub struct TradeUpdate {
     pub timestamp: i64,
 }
 
+/// Pushed when the trading engine executes a marketplace signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeExecuted {
+    pub signal_id: String,
+    pub symbol: String,
+    pub action: String,
+    pub fill_price: f64,
+    pub size: f64,
+    /// Engine balance after the trade
+    pub balance: f64,
+    /// Paper trade (no on-chain transaction)
+    pub dry_run: bool,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WSMessage {
     MarketUpdate(MarketUpdate),
     TradeUpdate(TradeUpdate),
+    TradeExecuted(TradeExecuted),
     PortfolioUpdate {
         total_value: f64,
         cash: f64,
@@ -158,3 +174,11 @@ pub fn broadcast_trade_update(
         log::warn!("Failed to broadcast trade update: {}", e);
     }
 }
+
+/// Broadcast an executed trade to all connected clients
+pub fn broadcast_trade_executed(broadcaster: &WSBroadcaster, trade: TradeExecuted) {
+    if let Err(e) = broadcaster.send(WSMessage::TradeExecuted(trade)) {
+        // Only fails when nobody is subscribed
+        log::debug!("No WebSocket subscribers for trade execution: {}", e);
+    }
+}
@@ -8,25 +8,43 @@ import RLAgents from './components/RLAgents'
 import X402Marketplace from './components/X402Marketplace'
 import MemeAnalyzer from './components/MemeAnalyzer'
 import axios from 'axios'
+import { getApiKey, setApiKey } from './apiKey'
 
 function App() {
   const [activeTab, setActiveTab] = useState('dashboard')
-  const [isConnected, setIsConnected] = useState(false)
+  // 'degraded' = backend up but /health answered 503 because a dependency is down
+  const [apiStatus, setApiStatus] = useState<'connected' | 'degraded' | 'offline'>('offline')
+  const [apiStatusDetail, setApiStatusDetail] = useState('')
   const [apiV2Connected, setApiV2Connected] = useState(false)
   const [systemStats, setSystemStats] = useState<any>(null)
+  const [apiKeyInput, setApiKeyInput] = useState(getApiKey())
+  const [hasApiKey, setHasApiKey] = useState(getApiKey() !== '')
+
+  const saveApiKey = () => {
+    setApiKey(apiKeyInput)
+    setHasApiKey(getApiKey() !== '')
+  }
 
   useEffect(() => {
     const checkConnection = async () => {
       try {
-        // Check legacy API
-        const response = await axios.get('http://localhost:8080/health', { timeout: 2000 })
-        setIsConnected(response.data.success)
-        
+        // Check legacy API - a 503 still carries the readiness report, so accept it
+        const response = await axios.get('http://localhost:8080/health', {
+          timeout: 5000,
+          validateStatus: status => status === 200 || status === 503,
+        })
+        setApiStatus(response.data.success ? 'connected' : 'degraded')
+        setApiStatusDetail(response.data.message || '')
+      } catch {
+        setApiStatus('offline')
+        setApiStatusDetail('')
+      }
+
+      try {
         // Check new AI Orchestrator API
         const v2Response = await axios.get('http://localhost:8081/health', { timeout: 2000 })
         setApiV2Connected(v2Response.status === 200)
       } catch {
-        setIsConnected(false)
         setApiV2Connected(false)
       }
     }
@@ -87,14 +105,28 @@ function App() {
           </div>
           
           <div className="connection-panel">
-            <div className={`connection-status ${isConnected ? 'connected' : 'offline'}`}>
-              <div className={`status-dot ${isConnected ? 'connected' : 'offline'}`}></div>
-              <span>{isConnected ? 'API v1' : 'Offline'}</span>
+            <div className={`connection-status ${apiStatus}`} title={apiStatusDetail}>
+              <div className={`status-dot ${apiStatus}`}></div>
+              <span>{apiStatus === 'offline' ? 'Offline' : apiStatus === 'degraded' ? 'API v1 (degraded)' : 'API v1'}</span>
             </div>
             <div className={`connection-status ${apiV2Connected ? 'connected' : 'offline'}`}>
               <div className={`status-dot ${apiV2Connected ? 'connected' : 'offline'}`}></div>
               <span>{apiV2Connected ? 'AI API v2' : 'Offline'}</span>
             </div>
+            <div className={`connection-status ${hasApiKey ? 'connected' : 'offline'}`}>
+              <input
+                type="password"
+                value={apiKeyInput}
+                onChange={(e) => setApiKeyInput(e.target.value)}
+                onKeyPress={(e) => e.key === 'Enter' && saveApiKey()}
+                placeholder="API key"
+                className="input-field"
+                autoComplete="off"
+              />
+              <button onClick={saveApiKey} className="btn primary">
+                {hasApiKey ? '🔓' : '🔐'}
+              </button>
+            </div>
           </div>
         </div>
       </header>
@@ -0,0 +1,25 @@
+import axios from 'axios'
+
+// The operator pastes their API key at runtime; it lives only in this tab's sessionStorage
+// and is never baked into the bundle
+const STORAGE_KEY = 'solanatradebot.apiKey'
+
+export const getApiKey = (): string => sessionStorage.getItem(STORAGE_KEY) || ''
+
+export const setApiKey = (key: string) => {
+  const trimmed = key.trim()
+  if (trimmed) {
+    sessionStorage.setItem(STORAGE_KEY, trimmed)
+  } else {
+    sessionStorage.removeItem(STORAGE_KEY)
+  }
+}
+
+// Attach the session's key as a bearer token to every request to the backends
+axios.interceptors.request.use(config => {
+  const key = getApiKey()
+  if (key) {
+    config.headers.Authorization = `Bearer ${key}`
+  }
+  return config
+})
@@ -1,5 +1,6 @@
 import React from 'react'
 import ReactDOM from 'react-dom/client'
+import './apiKey'
 import App from './App'
 import './styles/futuristic.css'
 import './styles/enhanced.css'